@@ -8,9 +8,12 @@ actions!(
     [
         // Request actions
         SendRequest,
+        SendAndSave,
         NewRequest,
         CancelRequest,
         DuplicateRequest,
+        EditAndResend,
+        FetchOAuthToken,
         // Tab navigation
         NextTab,
         PreviousTab,
@@ -41,6 +44,7 @@ actions!(
         SwitchToParamsTab,
         SwitchToHeadersTab,
         SwitchToAuthTab,
+        SwitchToTestsTab,
         // Response panel tabs
         SwitchToResponseBody,
         SwitchToResponseHeaders,
@@ -54,6 +58,7 @@ actions!(
         ToggleFullscreen,
         // History actions
         ClearHistory,
+        ToggleHistoryStar,
         // Editing
         Copy,
         Paste,
@@ -62,9 +67,11 @@ actions!(
         Undo,
         Redo,
         FormatDocument,
+        FormatSelectionAsJson,
         // Application actions
         Quit,
         ShowSettings,
+        ManageHeaderPresets,
         ShowHelp,
         SaveRequest,
         OpenRequest,
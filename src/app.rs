@@ -4,6 +4,7 @@ use gpui_component::Root;
 
 use crate::actions::*;
 use crate::assets::Assets;
+use crate::cli::Launch;
 use crate::completion::init_completion_navigation;
 use crate::theme::init_theme;
 use crate::utils::set_app_focus_handle;
@@ -16,6 +17,7 @@ impl SetuApp {
     /// Initialize and run the application
     pub fn run() {
         env_logger::init();
+        let launch = Launch::from_env();
 
         gpui_platform::application()
             .with_assets(Assets)
@@ -32,7 +34,7 @@ impl SetuApp {
                 Self::register_keybindings(cx);
 
                 // Create main window
-                Self::create_main_window(cx);
+                Self::create_main_window(launch, cx);
 
                 // Activate the application
                 cx.activate(true);
@@ -54,6 +56,8 @@ impl SetuApp {
             // ============ Request Actions ============
             KeyBinding::new("cmd-enter", SendRequest, None),
             KeyBinding::new("ctrl-enter", SendRequest, None),
+            KeyBinding::new("cmd-shift-enter", SendAndSave, None),
+            KeyBinding::new("cmd-s", SaveRequest, None),
             KeyBinding::new("cmd-.", CancelRequest, None),
             KeyBinding::new("cmd-n", NewRequest, None),
             KeyBinding::new("cmd-d", DuplicateRequest, None),
@@ -67,6 +71,7 @@ impl SetuApp {
             KeyBinding::new("cmd-w", CloseTab, None),
             KeyBinding::new("cmd-shift-w", CloseAllTabs, None),
             KeyBinding::new("cmd-alt-w", CloseOtherTabs, None),
+            KeyBinding::new("cmd-shift-t", ReopenClosedTab, None),
             // Go to specific tab (like browsers/VSCode)
             KeyBinding::new("cmd-1", GoToTab1, None),
             KeyBinding::new("cmd-2", GoToTab2, None),
@@ -93,6 +98,9 @@ impl SetuApp {
             KeyBinding::new("cmd-\\", ToggleSidebar, None),
             // ============ History ============
             KeyBinding::new("cmd-shift-delete", ClearHistory, None),
+            KeyBinding::new("cmd-alt-s", ToggleHistoryStar, None),
+            // ============ Editing ============
+            KeyBinding::new("cmd-shift-j", FormatSelectionAsJson, None),
             // ============ HTTP Method Shortcuts ============
             // Alt + first letter (like Postman)
             KeyBinding::new("alt-g", SetMethodGet, None),
@@ -105,8 +113,8 @@ impl SetuApp {
         ]);
     }
 
-    /// Create the main application window
-    fn create_main_window(cx: &mut App) {
+    /// Create the main application window in the context the command line asked for
+    fn create_main_window(launch: Launch, cx: &mut App) {
         let initial_size = Size {
             width: px(1280.0),
             height: px(840.0),
@@ -129,10 +137,17 @@ impl SetuApp {
 
         // Wrap MainView in Root for gpui-component to work properly
         cx.open_window(options, |window, cx| {
-            let main_view = cx.new(|cx| MainView::new(cx));
+            let Launch { request, context } = launch;
+            let main_view = cx.new(|cx| MainView::new(context, cx));
             let main_focus_handle = main_view.focus_handle(cx);
             set_app_focus_handle(main_focus_handle.clone(), cx);
+            let launch_view = main_view.clone();
             window.defer(cx, move |window, cx| {
+                if let Some(request) = request {
+                    launch_view.update(cx, |view, cx| {
+                        view.open_launch_request(&request, window, cx);
+                    });
+                }
                 main_focus_handle.focus(window, cx);
             });
             cx.new(|cx| Root::new(main_view, window, cx))
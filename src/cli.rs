@@ -0,0 +1,241 @@
+//! Command-line launch: `setu [curl options] URL` opens that request in the first tab, and
+//! `--collection`/`--env` pick the working context.
+
+use std::io::{IsTerminal, Read};
+
+use crate::utils::{ParsedCurl, parse_curl_args};
+
+const USAGE: &str = "\
+Usage: setu [--collection NAME] [--env NAME] [-X METHOD] [-H 'Name: value']... [URL]
+
+Opens the request in a new window without sending it. A body piped on stdin is
+used as the request body, for example:
+
+    echo '{\"name\":\"setu\"}' | setu https://api.example.com/items
+
+A lone `-`, or `@-` as a data value (`-d @-`), reads the body from stdin even
+when it is a terminal.
+
+  --collection NAME   expand the collection NAME in the sidebar
+  --env NAME          make the environment NAME active
+
+Other options are read the way curl reads them.";
+
+/// Names of the collection and environment to open, resolved once they have loaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchContext {
+    pub collection: Option<String>,
+    pub environment: Option<String>,
+}
+
+/// What the command line asked to open.
+pub struct Launch {
+    pub request: Option<ParsedCurl>,
+    pub context: LaunchContext,
+}
+
+impl Launch {
+    /// Read the command line, and stdin when a request was given.
+    ///
+    /// Exits after printing the usage for `--help` or arguments that do not parse. Stdin
+    /// is only read when the request asks for it with `-` or `@-`, or when it is not a
+    /// terminal, so launching the app plainly never waits on it.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = std::env::args()
+            .skip(1)
+            // Older macOS versions pass a process serial number to apps started from Finder.
+            .filter(|arg| !arg.starts_with("-psn_"))
+            .collect();
+        if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+            println!("{USAGE}");
+            std::process::exit(0);
+        }
+        let (context, request_args) =
+            split_context(args).unwrap_or_else(|error| exit_with_usage(&error));
+        if request_args.is_empty() {
+            return Self {
+                request: None,
+                context,
+            };
+        }
+
+        let stdin = std::io::stdin();
+        let piped_body = if !reads_stdin(&request_args) && stdin.is_terminal() {
+            None
+        } else {
+            let mut body = String::new();
+            match stdin.lock().read_to_string(&mut body) {
+                Ok(_) => Some(body),
+                Err(error) => exit_with_usage(&format!("could not read stdin: {error}")),
+            }
+        };
+        match parse_request_args(request_args, piped_body) {
+            Ok(request) => Self {
+                request: Some(request),
+                context,
+            },
+            Err(error) => exit_with_usage(&error),
+        }
+    }
+}
+
+fn exit_with_usage(error: &str) -> ! {
+    eprintln!("setu: {error}\n\n{USAGE}");
+    std::process::exit(2);
+}
+
+/// Take `--collection` and `--env` out of the arguments, leaving the request's.
+fn split_context(args: Vec<String>) -> Result<(LaunchContext, Vec<String>), String> {
+    let mut context = LaunchContext::default();
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let slot = match flag.as_str() {
+            "--collection" => &mut context.collection,
+            "--env" | "--environment" => &mut context.environment,
+            _ => {
+                rest.push(arg);
+                continue;
+            }
+        };
+        let value = inline_value
+            .or_else(|| args.next())
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| format!("{flag} needs a name"))?;
+        *slot = Some(value);
+    }
+    Ok((context, rest))
+}
+
+/// Whether the arguments name stdin as the body: a lone `-`, or `@-` as a data value.
+fn reads_stdin(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "-" || arg == "@-")
+}
+
+/// Treat the arguments as curl's, with a piped body standing in for `--data-binary`, or
+/// for the `-` and `@-` that asked for it.
+fn parse_request_args(
+    mut args: Vec<String>,
+    piped_body: Option<String>,
+) -> Result<ParsedCurl, String> {
+    // `echo` ends its output with a newline that is not part of the body.
+    let piped_body = piped_body.map(|body| {
+        let trimmed = body.strip_suffix('\n').unwrap_or(&body);
+        trimmed.strip_suffix('\r').unwrap_or(trimmed).to_string()
+    });
+    if reads_stdin(&args) {
+        let body = piped_body.unwrap_or_default();
+        let mut substituted = Vec::with_capacity(args.len() + 1);
+        for arg in args {
+            match arg.as_str() {
+                "-" if body.is_empty() => {}
+                "-" => substituted.extend(["--data-binary".to_string(), body.clone()]),
+                "@-" => substituted.push(body.clone()),
+                _ => substituted.push(arg),
+            }
+        }
+        args = substituted;
+    } else if let Some(body) = piped_body.filter(|body| !body.is_empty()) {
+        args.push("--data-binary".to_string());
+        args.push(body);
+    }
+    parse_curl_args(args).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LaunchContext, parse_request_args, split_context};
+    use crate::entities::{HttpMethod, RequestBody};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn a_piped_body_makes_the_request_a_post() {
+        let request = parse_request_args(
+            args(&["https://api.example.com/items"]),
+            Some("{\"name\":\"setu\"}\n".to_string()),
+        )
+        .expect("URL is given");
+        assert_eq!(request.method, HttpMethod::Post);
+        assert_eq!(request.url, "https://api.example.com/items");
+        assert!(matches!(request.body, RequestBody::Json(body) if body == "{\"name\":\"setu\"}"));
+    }
+
+    #[test]
+    fn keeps_the_method_and_headers_and_ignores_an_empty_pipe() {
+        let request = parse_request_args(
+            args(&[
+                "-X",
+                "PUT",
+                "-H",
+                "Accept: text/plain",
+                "https://example.com",
+            ]),
+            Some(String::new()),
+        )
+        .expect("URL is given");
+        assert_eq!(request.method, HttpMethod::Put);
+        assert_eq!(request.headers.len(), 1);
+        assert!(matches!(request.body, RequestBody::None));
+
+        assert!(parse_request_args(args(&["-X", "GET"]), None).is_err());
+    }
+
+    #[test]
+    fn dash_and_at_dash_take_the_body_from_stdin() {
+        let request = parse_request_args(
+            args(&["-X", "PUT", "https://example.com", "-"]),
+            Some("plain body\n".to_string()),
+        )
+        .expect("URL is given");
+        assert_eq!(request.method, HttpMethod::Put);
+        assert!(matches!(request.body, RequestBody::Text(body) if body == "plain body"));
+
+        let request = parse_request_args(
+            args(&["-d", "@-", "https://example.com"]),
+            Some("a=1".to_string()),
+        )
+        .expect("URL is given");
+        assert_eq!(request.url, "https://example.com");
+        assert!(!matches!(request.body, RequestBody::None));
+
+        let request =
+            parse_request_args(args(&["https://example.com", "-"]), None).expect("URL is given");
+        assert!(matches!(request.body, RequestBody::None));
+    }
+
+    #[test]
+    fn separates_the_collection_and_environment_from_the_request() {
+        let (context, rest) = split_context(args(&[
+            "--env",
+            "prod",
+            "--collection=Billing",
+            "-X",
+            "POST",
+            "https://example.com",
+        ]))
+        .expect("both flags have names");
+        assert_eq!(
+            context,
+            LaunchContext {
+                collection: Some("Billing".to_string()),
+                environment: Some("prod".to_string()),
+            }
+        );
+        assert_eq!(rest, args(&["-X", "POST", "https://example.com"]));
+
+        assert!(split_context(args(&["--env"])).is_err());
+        assert_eq!(
+            split_context(Vec::new()).map(|(context, _)| context),
+            Ok(LaunchContext::default())
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::entities::ResponseData;
+use base64::Engine as _;
+use serde_json::{Map, Value, json};
+
+/// Languages a captured response can be written out in for tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureFormat {
+    /// A plain JSON document, for test data files.
+    Json,
+    JavaScript,
+    Python,
+    Rust,
+}
+
+impl FixtureFormat {
+    pub fn all() -> &'static [FixtureFormat] {
+        &[
+            FixtureFormat::Json,
+            FixtureFormat::JavaScript,
+            FixtureFormat::Python,
+            FixtureFormat::Rust,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FixtureFormat::Json => "JSON File",
+            FixtureFormat::JavaScript => "JavaScript",
+            FixtureFormat::Python => "Python",
+            FixtureFormat::Rust => "Rust",
+        }
+    }
+
+    fn indent(&self) -> &'static str {
+        match self {
+            FixtureFormat::Json | FixtureFormat::JavaScript => "  ",
+            FixtureFormat::Python | FixtureFormat::Rust => "    ",
+        }
+    }
+}
+
+/// The response as a mock object: status, headers ordered by name and the body. JSON
+/// bodies are embedded as values, other text as a string and anything else as base64,
+/// flagged by `bodyEncoding`.
+pub fn response_fixture(data: &ResponseData) -> Value {
+    let mut headers: Vec<_> = data.headers.iter().collect();
+    headers.sort();
+    let headers: Map<String, Value> = headers
+        .into_iter()
+        .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+        .collect();
+
+    let text = if data.body().is_empty() {
+        std::str::from_utf8(data.body_bytes()).ok()
+    } else {
+        Some(data.body())
+    };
+    let mut fixture = json!({
+        "status": data.status_code,
+        "statusText": data.status_text,
+        "headers": headers,
+    });
+    match text {
+        Some(text) => {
+            let body = data
+                .is_json()
+                .then(|| serde_json::from_str(text).ok())
+                .flatten()
+                .unwrap_or_else(|| Value::String(text.to_string()));
+            fixture["body"] = body;
+        }
+        None => {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(data.body_bytes());
+            fixture["body"] = Value::String(encoded);
+            fixture["bodyEncoding"] = Value::String("base64".to_string());
+        }
+    }
+    fixture
+}
+
+/// [`response_fixture`] as a snippet ready to paste into a test.
+pub fn render_fixture(data: &ResponseData, format: FixtureFormat) -> String {
+    let mut literal = String::new();
+    write_literal(&mut literal, &response_fixture(data), format, 0);
+    match format {
+        FixtureFormat::Json => format!("{literal}\n"),
+        FixtureFormat::JavaScript => format!("export const responseFixture = {literal};\n"),
+        FixtureFormat::Python => format!("RESPONSE_FIXTURE = {literal}\n"),
+        FixtureFormat::Rust => format!("let response_fixture = serde_json::json!({literal});\n"),
+    }
+}
+
+/// Write `value` as a literal of `format`. JavaScript and `serde_json::json!` read JSON
+/// syntax as is; Python only needs its own keywords. Strings take each language's escapes.
+pub(super) fn write_literal(out: &mut String, value: &Value, format: FixtureFormat, depth: usize) {
+    let python = format == FixtureFormat::Python;
+    match value {
+        Value::Null => out.push_str(if python { "None" } else { "null" }),
+        Value::Bool(true) => out.push_str(if python { "True" } else { "true" }),
+        Value::Bool(false) => out.push_str(if python { "False" } else { "false" }),
+        Value::Number(number) => out.push_str(&number.to_string()),
+        Value::String(text) => out.push_str(&quote(text, format)),
+        Value::Array(items) if items.is_empty() => out.push_str("[]"),
+        Value::Object(fields) if fields.is_empty() => out.push_str("{}"),
+        Value::Array(items) => {
+            out.push_str("[\n");
+            for (index, item) in items.iter().enumerate() {
+                push_indent(out, format, depth + 1);
+                write_literal(out, item, format, depth + 1);
+                out.push_str(if index + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            push_indent(out, format, depth);
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            out.push_str("{\n");
+            for (index, (key, field)) in fields.iter().enumerate() {
+                push_indent(out, format, depth + 1);
+                out.push_str(&quote(key, format));
+                out.push_str(": ");
+                write_literal(out, field, format, depth + 1);
+                out.push_str(if index + 1 < fields.len() {
+                    ",\n"
+                } else {
+                    "\n"
+                });
+            }
+            push_indent(out, format, depth);
+            out.push('}');
+        }
+    }
+}
+
+fn push_indent(out: &mut String, format: FixtureFormat, depth: usize) {
+    for _ in 0..depth {
+        out.push_str(format.indent());
+    }
+}
+
+/// JSON escapes are valid in JavaScript and Python strings but not in Rust, where `\u`
+/// needs braces, so Rust strings use its own debug escaping.
+fn quote(text: &str, format: FixtureFormat) -> String {
+    match format {
+        FixtureFormat::Rust => format!("{text:?}"),
+        _ => Value::String(text.to_string()).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FixtureFormat, render_fixture, response_fixture};
+    use crate::entities::ResponseData;
+    use std::collections::HashMap;
+
+    fn json_response(body: &str) -> ResponseData {
+        ResponseData::new(
+            200,
+            "OK".to_string(),
+            HashMap::from([("content-type".to_string(), "application/json".to_string())]),
+            body.to_string(),
+            Vec::new(),
+            body.len(),
+            12,
+            Some("application/json".to_string()),
+        )
+    }
+
+    #[test]
+    fn writes_json_bodies_as_values_in_each_language() {
+        let response = json_response(r#"{"ok": true, "note": null, "tags": []}"#);
+        assert_eq!(
+            render_fixture(&response, FixtureFormat::Python),
+            "RESPONSE_FIXTURE = {\n    \"status\": 200,\n    \"statusText\": \"OK\",\n    \
+             \"headers\": {\n        \"content-type\": \"application/json\"\n    },\n    \
+             \"body\": {\n        \"ok\": True,\n        \"note\": None,\n        \
+             \"tags\": []\n    }\n}\n"
+        );
+        let json = render_fixture(&response, FixtureFormat::Json);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, response_fixture(&response));
+        assert!(
+            render_fixture(&response, FixtureFormat::JavaScript)
+                .starts_with("export const responseFixture = {\n  \"status\": 200,")
+        );
+    }
+
+    #[test]
+    fn escapes_strings_for_rust_and_encodes_binary_bodies() {
+        let response = ResponseData {
+            headers: HashMap::new(),
+            ..json_response("not json \u{1}")
+        };
+        let rust = render_fixture(&response, FixtureFormat::Rust);
+        assert!(rust.contains(r#""body": "not json \u{1}""#), "{rust}");
+
+        let binary = ResponseData::new(
+            200,
+            "OK".to_string(),
+            HashMap::new(),
+            String::new(),
+            vec![0xff, 0xfe],
+            2,
+            0,
+            Some("application/octet-stream".to_string()),
+        );
+        let fixture = response_fixture(&binary);
+        assert_eq!(fixture["body"], "//4=");
+        assert_eq!(fixture["bodyEncoding"], "base64");
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeSet;
+
+use crate::entities::RequestData;
+
+use super::snippet::{CodeGenerator, SnippetBody, SnippetRequest, quote};
+
+/// A `main` package on `net/http`. Go refuses unused imports, so each one is added only
+/// when the body needs it.
+pub struct GoGenerator;
+
+impl CodeGenerator for GoGenerator {
+    fn generate(&self, request: &RequestData) -> String {
+        let request = SnippetRequest::new(request);
+        let mut imports = BTreeSet::from(["fmt", "io", "net/http"]);
+        let mut body = String::new();
+        let mut content_type = None;
+
+        let reader = match &request.body {
+            SnippetBody::None => "nil",
+            SnippetBody::Text(text) => {
+                imports.insert("strings");
+                body.push_str(&format!("\tbody := strings.NewReader({})\n", quote(text)));
+                "body"
+            }
+            SnippetBody::Json(text, _) => {
+                imports.insert("strings");
+                body.push_str(&format!("\tbody := strings.NewReader({})\n", quote(text)));
+                "body"
+            }
+            SnippetBody::Form(pairs) => {
+                imports.extend(["net/url", "strings"]);
+                body.push_str("\tform := url.Values{}\n");
+                for (key, value) in pairs {
+                    body.push_str(&format!("\tform.Add({}, {})\n", quote(key), quote(value)));
+                }
+                body.push_str("\tbody := strings.NewReader(form.Encode())\n");
+                content_type = Some("\"application/x-www-form-urlencoded\"".to_string());
+                "body"
+            }
+            SnippetBody::Multipart(fields) => {
+                imports.extend(["bytes", "mime/multipart"]);
+                body.push_str("\tbody := &bytes.Buffer{}\n\twriter := multipart.NewWriter(body)\n");
+                for field in *fields {
+                    match &field.file_path {
+                        Some(path) => {
+                            imports.extend(["os", "path/filepath"]);
+                            body.push_str(&format!(
+                                "\t{{\n\
+                                 \t\tfile, err := os.Open({})\n\
+                                 \t\tif err != nil {{\n\t\t\tpanic(err)\n\t\t}}\n\
+                                 \t\tdefer file.Close()\n\
+                                 \t\tpart, err := writer.CreateFormFile({}, filepath.Base(file.Name()))\n\
+                                 \t\tif err != nil {{\n\t\t\tpanic(err)\n\t\t}}\n\
+                                 \t\tif _, err := io.Copy(part, file); err != nil {{\n\t\t\tpanic(err)\n\t\t}}\n\
+                                 \t}}\n",
+                                quote(path),
+                                quote(&field.key)
+                            ));
+                        }
+                        None => body.push_str(&format!(
+                            "\tif err := writer.WriteField({}, {}); err != nil {{\n\t\tpanic(err)\n\t}}\n",
+                            quote(&field.key),
+                            quote(&field.value)
+                        )),
+                    }
+                }
+                body.push_str("\tif err := writer.Close(); err != nil {\n\t\tpanic(err)\n\t}\n");
+                content_type = Some("writer.FormDataContentType()".to_string());
+                "body"
+            }
+            SnippetBody::File(path) => {
+                imports.insert("os");
+                body.push_str(&format!(
+                    "\tbody, err := os.Open({})\n\tif err != nil {{\n\t\tpanic(err)\n\t}}\n\
+                     \tdefer body.Close()\n",
+                    quote(path)
+                ));
+                "body"
+            }
+        };
+
+        let mut out = "package main\n\nimport (\n".to_string();
+        for import in &imports {
+            out.push_str(&format!("\t{}\n", quote(import)));
+        }
+        out.push_str(")\n\nfunc main() {\n");
+        out.push_str(&body);
+        out.push_str(&format!(
+            "\treq, err := http.NewRequest({}, {}, {reader})\n\
+             \tif err != nil {{\n\t\tpanic(err)\n\t}}\n",
+            quote(request.method),
+            quote(request.url)
+        ));
+        for (key, value) in &request.headers {
+            out.push_str(&format!(
+                "\treq.Header.Add({}, {})\n",
+                quote(key),
+                quote(value)
+            ));
+        }
+        if let Some(content_type) = content_type.filter(|_| !request.has_header("content-type")) {
+            out.push_str(&format!(
+                "\treq.Header.Set(\"Content-Type\", {content_type})\n"
+            ));
+        }
+        out.push_str(
+            "\n\tresp, err := http.DefaultClient.Do(req)\n\
+             \tif err != nil {\n\t\tpanic(err)\n\t}\n\
+             \tdefer resp.Body.Close()\n\n\
+             \trespBody, err := io.ReadAll(resp.Body)\n\
+             \tif err != nil {\n\t\tpanic(err)\n\t}\n\
+             \tfmt.Println(resp.Status)\n\
+             \tfmt.Println(string(respBody))\n\
+             }\n",
+        );
+        out
+    }
+}
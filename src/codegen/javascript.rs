@@ -0,0 +1,89 @@
+use crate::entities::RequestData;
+
+use super::fixture::{FixtureFormat, write_literal};
+use super::snippet::{CodeGenerator, SnippetBody, SnippetRequest, file_name, quote};
+
+/// `fetch` as in browsers and Node 18+. Files are read with `fs.openAsBlob`, so multipart
+/// uploads need Node 19.8 or later.
+pub struct FetchGenerator;
+
+impl CodeGenerator for FetchGenerator {
+    fn generate(&self, request: &RequestData) -> String {
+        let request = SnippetRequest::new(request);
+        let mut out = String::new();
+
+        let body = match &request.body {
+            SnippetBody::None => None,
+            SnippetBody::Text(text) => Some(quote(text)),
+            SnippetBody::Json(_, Some(value)) => {
+                let mut literal = String::new();
+                write_literal(&mut literal, value, FixtureFormat::JavaScript, 1);
+                Some(format!("JSON.stringify({literal})"))
+            }
+            SnippetBody::Json(text, None) => Some(quote(text)),
+            SnippetBody::Form(pairs) => {
+                let mut literal = "new URLSearchParams({\n".to_string();
+                for (key, value) in pairs {
+                    literal.push_str(&format!("    {}: {},\n", quote(key), quote(value)));
+                }
+                literal.push_str("  })");
+                Some(literal)
+            }
+            SnippetBody::Multipart(fields) => {
+                if fields.iter().any(|field| field.file_path.is_some()) {
+                    out.push_str("import fs from \"node:fs\";\n\n");
+                }
+                out.push_str("const form = new FormData();\n");
+                for field in *fields {
+                    match &field.file_path {
+                        Some(path) => out.push_str(&format!(
+                            "form.append({}, await fs.openAsBlob({}), {});\n",
+                            quote(&field.key),
+                            quote(path),
+                            quote(file_name(path))
+                        )),
+                        None => out.push_str(&format!(
+                            "form.append({}, {});\n",
+                            quote(&field.key),
+                            quote(&field.value)
+                        )),
+                    }
+                }
+                out.push('\n');
+                Some("form".to_string())
+            }
+            SnippetBody::File(path) => {
+                out.push_str("import fs from \"node:fs\";\n\n");
+                Some(format!("await fs.openAsBlob({})", quote(path)))
+            }
+        };
+
+        let mut options = String::new();
+        if request.method != "GET" {
+            options.push_str(&format!("  method: {},\n", quote(request.method)));
+        }
+        if !request.headers.is_empty() {
+            options.push_str("  headers: {\n");
+            for (key, value) in &request.headers {
+                options.push_str(&format!("    {}: {},\n", quote(key), quote(value)));
+            }
+            options.push_str("  },\n");
+        }
+        if let Some(body) = body {
+            options.push_str(&format!("  body: {body},\n"));
+        }
+        if options.is_empty() {
+            out.push_str(&format!(
+                "const response = await fetch({});\n",
+                quote(request.url)
+            ));
+        } else {
+            out.push_str(&format!(
+                "const response = await fetch({}, {{\n{options}}});\n",
+                quote(request.url)
+            ));
+        }
+        out.push_str("\nconsole.log(response.status, await response.text());\n");
+        out
+    }
+}
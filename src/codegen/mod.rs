@@ -0,0 +1,9 @@
+pub mod fixture;
+mod go;
+mod javascript;
+mod python;
+mod rust;
+pub mod snippet;
+
+pub use fixture::*;
+pub use snippet::*;
@@ -0,0 +1,111 @@
+use crate::entities::RequestData;
+
+use super::fixture::{FixtureFormat, write_literal};
+use super::snippet::{CodeGenerator, SnippetBody, SnippetRequest, file_name, quote};
+
+/// The `requests` library. Query pairs go in `params`, which encodes them again, so the
+/// URL is written without its query whenever it could be split off.
+pub struct PythonGenerator;
+
+impl CodeGenerator for PythonGenerator {
+    fn generate(&self, request: &RequestData) -> String {
+        let request = SnippetRequest::new(request);
+        let mut out = "import requests\n\n".to_string();
+        let mut arguments = vec!["url".to_string()];
+
+        let url = request.base_url.as_deref().unwrap_or(request.url);
+        out.push_str(&format!("url = {}\n", quote(url)));
+        if request.base_url.is_some() {
+            out.push_str("params = [\n");
+            for (key, value) in &request.query {
+                out.push_str(&format!("    ({}, {}),\n", quote(key), quote(value)));
+            }
+            out.push_str("]\n");
+            arguments.push("params=params".to_string());
+        }
+        if !request.headers.is_empty() {
+            out.push_str("headers = {\n");
+            for (key, value) in &request.headers {
+                out.push_str(&format!("    {}: {},\n", quote(key), quote(value)));
+            }
+            out.push_str("}\n");
+            arguments.push("headers=headers".to_string());
+        }
+
+        match &request.body {
+            SnippetBody::None => {}
+            SnippetBody::Text(text) => {
+                out.push_str(&format!("data = {}\n", quote(text)));
+                arguments.push("data=data".to_string());
+            }
+            SnippetBody::Json(_, Some(value)) => {
+                let mut literal = String::new();
+                write_literal(&mut literal, value, FixtureFormat::Python, 0);
+                out.push_str(&format!("payload = {literal}\n"));
+                arguments.push("json=payload".to_string());
+            }
+            SnippetBody::Json(text, None) => {
+                out.push_str(&format!("data = {}\n", quote(text)));
+                arguments.push("data=data".to_string());
+            }
+            SnippetBody::Form(pairs) => {
+                out.push_str("data = {\n");
+                for (key, value) in pairs {
+                    out.push_str(&format!("    {}: {},\n", quote(key), quote(value)));
+                }
+                out.push_str("}\n");
+                arguments.push("data=data".to_string());
+            }
+            SnippetBody::Multipart(fields) => {
+                let (files, texts): (Vec<_>, Vec<_>) =
+                    fields.iter().partition(|field| field.file_path.is_some());
+                if !texts.is_empty() {
+                    out.push_str("data = {\n");
+                    for field in texts {
+                        out.push_str(&format!(
+                            "    {}: {},\n",
+                            quote(&field.key),
+                            quote(&field.value)
+                        ));
+                    }
+                    out.push_str("}\n");
+                    arguments.push("data=data".to_string());
+                }
+                if !files.is_empty() {
+                    out.push_str("files = [\n");
+                    for field in files {
+                        let path = field.file_path.as_deref().unwrap_or_default();
+                        out.push_str(&format!(
+                            "    ({}, ({}, open({}, \"rb\"))),\n",
+                            quote(&field.key),
+                            quote(file_name(path)),
+                            quote(path)
+                        ));
+                    }
+                    out.push_str("]\n");
+                    arguments.push("files=files".to_string());
+                }
+            }
+            SnippetBody::File(path) => {
+                out.push_str(&format!("data = open({}, \"rb\")\n", quote(path)));
+                arguments.push("data=data".to_string());
+            }
+        }
+
+        // `requests` only has helpers for the common verbs.
+        let call = match request.method {
+            "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" | "OPTIONS" => {
+                request.method.to_lowercase()
+            }
+            method => {
+                arguments.insert(0, quote(method));
+                "request".to_string()
+            }
+        };
+        out.push_str(&format!(
+            "\nresponse = requests.{call}({})\nprint(response.status_code, response.text)\n",
+            arguments.join(", ")
+        ));
+        out
+    }
+}
@@ -0,0 +1,93 @@
+use crate::entities::RequestData;
+
+use super::snippet::{CodeGenerator, SnippetBody, SnippetRequest};
+
+/// An async `main` on reqwest and tokio. Multipart files need reqwest's `stream` feature.
+pub struct ReqwestGenerator;
+
+impl CodeGenerator for ReqwestGenerator {
+    fn generate(&self, request: &RequestData) -> String {
+        let request = SnippetRequest::new(request);
+        let mut out =
+            "#[tokio::main]\nasync fn main() -> Result<(), Box<dyn std::error::Error>> {\n"
+                .to_string();
+
+        if let SnippetBody::Multipart(fields) = &request.body {
+            out.push_str("    let form = reqwest::multipart::Form::new()");
+            for field in *fields {
+                match &field.file_path {
+                    Some(path) => out.push_str(&format!(
+                        "\n        .file({}, {})\n        .await?",
+                        quote(&field.key),
+                        quote(path)
+                    )),
+                    None => out.push_str(&format!(
+                        "\n        .text({}, {})",
+                        quote(&field.key),
+                        quote(&field.value)
+                    )),
+                }
+            }
+            out.push_str(";\n\n");
+        }
+
+        let url = quote(request.base_url.as_deref().unwrap_or(request.url));
+        let call = match request.method {
+            "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "HEAD" => {
+                format!(".{}({url})", request.method.to_lowercase())
+            }
+            method => format!(".request(reqwest::Method::{method}, {url})"),
+        };
+        out.push_str(&format!(
+            "    let response = reqwest::Client::new()\n        {call}\n"
+        ));
+        if request.base_url.is_some() {
+            let pairs: Vec<String> = request
+                .query
+                .iter()
+                .map(|(key, value)| format!("({}, {})", quote(key), quote(value)))
+                .collect();
+            out.push_str(&format!("        .query(&[{}])\n", pairs.join(", ")));
+        }
+        for (key, value) in &request.headers {
+            out.push_str(&format!(
+                "        .header({}, {})\n",
+                quote(key),
+                quote(value)
+            ));
+        }
+        match &request.body {
+            SnippetBody::None => {}
+            SnippetBody::Text(text) => {
+                out.push_str(&format!("        .body({})\n", quote(text)));
+            }
+            SnippetBody::Json(text, _) => {
+                out.push_str(&format!("        .body({})\n", quote(text)));
+            }
+            SnippetBody::Form(pairs) => {
+                let pairs: Vec<String> = pairs
+                    .iter()
+                    .map(|(key, value)| format!("({}, {})", quote(key), quote(value)))
+                    .collect();
+                out.push_str(&format!("        .form(&[{}])\n", pairs.join(", ")));
+            }
+            SnippetBody::Multipart(_) => out.push_str("        .multipart(form)\n"),
+            SnippetBody::File(path) => out.push_str(&format!(
+                "        .body(tokio::fs::read({}).await?)\n",
+                quote(path)
+            )),
+        }
+        out.push_str(
+            "        .send()\n        .await?;\n\n    \
+             println!(\"{}\", response.status());\n    \
+             println!(\"{}\", response.text().await?);\n    \
+             Ok(())\n}\n",
+        );
+        out
+    }
+}
+
+/// Rust's own escaping, which writes `\u{..}` where JSON would write `\u..`.
+fn quote(text: &str) -> String {
+    format!("{text:?}")
+}
@@ -0,0 +1,408 @@
+use crate::entities::{
+    MultipartField, RequestBody, RequestData, file_content_type, graphql_payload,
+};
+use serde_json::Value;
+
+use super::go::GoGenerator;
+use super::javascript::FetchGenerator;
+use super::python::PythonGenerator;
+use super::rust::ReqwestGenerator;
+
+/// Writes a request out as code that sends it.
+pub trait CodeGenerator {
+    fn generate(&self, request: &RequestData) -> String;
+}
+
+/// Languages and clients a request can be copied as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeTarget {
+    JavaScriptFetch,
+    PythonRequests,
+    GoNetHttp,
+    RustReqwest,
+}
+
+impl CodeTarget {
+    pub fn all() -> &'static [CodeTarget] {
+        &[
+            CodeTarget::JavaScriptFetch,
+            CodeTarget::PythonRequests,
+            CodeTarget::GoNetHttp,
+            CodeTarget::RustReqwest,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CodeTarget::JavaScriptFetch => "JavaScript (fetch)",
+            CodeTarget::PythonRequests => "Python (requests)",
+            CodeTarget::GoNetHttp => "Go (net/http)",
+            CodeTarget::RustReqwest => "Rust (reqwest)",
+        }
+    }
+
+    pub fn generator(&self) -> &'static dyn CodeGenerator {
+        match self {
+            CodeTarget::JavaScriptFetch => &FetchGenerator,
+            CodeTarget::PythonRequests => &PythonGenerator,
+            CodeTarget::GoNetHttp => &GoGenerator,
+            CodeTarget::RustReqwest => &ReqwestGenerator,
+        }
+    }
+}
+
+pub fn generate_code(request: &RequestData, target: CodeTarget) -> String {
+    target.generator().generate(request)
+}
+
+/// A request body reduced to the shapes the generators write out.
+pub(super) enum SnippetBody<'a> {
+    None,
+    Text(&'a str),
+    /// JSON text, parsed when it is valid so it can be written as a literal.
+    Json(String, Option<Value>),
+    /// URL-encoded pairs, ordered by key.
+    Form(Vec<(&'a str, &'a str)>),
+    Multipart(&'a [MultipartField]),
+    /// Path of a file sent as the whole body.
+    File(String),
+}
+
+/// What every generator needs from a [`RequestData`], worked out once.
+pub(super) struct SnippetRequest<'a> {
+    pub method: &'static str,
+    /// The URL as typed, query included.
+    pub url: &'a str,
+    /// The URL without its query, when it parses and the query could be split off.
+    pub base_url: Option<String>,
+    /// Decoded query pairs, for clients that encode them themselves.
+    pub query: Vec<(String, String)>,
+    /// Enabled headers, with the `Content-Type` the client would add for JSON bodies.
+    pub headers: Vec<(String, String)>,
+    pub body: SnippetBody<'a>,
+}
+
+impl<'a> SnippetRequest<'a> {
+    pub fn new(request: &'a RequestData) -> Self {
+        let body = match &request.body {
+            RequestBody::None => SnippetBody::None,
+            RequestBody::Text(text) if text.is_empty() => SnippetBody::None,
+            RequestBody::Json(json) if json.is_empty() => SnippetBody::None,
+            RequestBody::Text(text) => SnippetBody::Text(text),
+            RequestBody::Json(json) => {
+                SnippetBody::Json(json.clone(), serde_json::from_str(json).ok())
+            }
+            RequestBody::GraphQl { query, variables } => {
+                // Variables that are not JSON would fail the send, so only the query goes.
+                let payload = graphql_payload(query, variables)
+                    .or_else(|_| graphql_payload(query, ""))
+                    .unwrap_or_default();
+                let value = serde_json::from_str(&payload).ok();
+                SnippetBody::Json(payload, value)
+            }
+            RequestBody::FormData(map) => {
+                let mut pairs: Vec<_> = map
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect();
+                pairs.sort();
+                SnippetBody::Form(pairs)
+            }
+            RequestBody::MultipartFormData(fields) => SnippetBody::Multipart(fields),
+            RequestBody::Binary(path) if path.as_os_str().is_empty() => SnippetBody::None,
+            RequestBody::Binary(path) => SnippetBody::File(path.to_string_lossy().into_owned()),
+        };
+
+        let multipart = matches!(body, SnippetBody::Multipart(_));
+        let mut headers: Vec<(String, String)> = request
+            .headers
+            .iter()
+            .filter(|header| header.enabled && !header.key.trim().is_empty())
+            // The multipart boundary is only known to the client that builds the body.
+            .filter(|header| !(multipart && header.key.eq_ignore_ascii_case("content-type")))
+            .map(|header| (header.key.trim().to_string(), header.value.clone()))
+            .collect();
+        let implied_content_type = match &body {
+            SnippetBody::Json(..) => Some("application/json".to_string()),
+            SnippetBody::File(path) => Some(file_content_type(std::path::Path::new(path))),
+            _ => None,
+        };
+        if let Some(content_type) = implied_content_type
+            && !headers
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+        {
+            headers.push(("Content-Type".to_string(), content_type));
+        }
+
+        let (base_url, query) = split_query(&request.url);
+        Self {
+            method: request.method.as_str(),
+            url: &request.url,
+            base_url,
+            query,
+            headers,
+            body,
+        }
+    }
+
+    /// Whether the request sets `name` itself, ignoring case.
+    pub fn has_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|(key, _)| key.eq_ignore_ascii_case(name))
+    }
+}
+
+/// The URL without its query plus the decoded pairs, or `None` and no pairs when there is
+/// no query or the URL does not parse, as with `{{baseUrl}}/users`.
+fn split_query(url: &str) -> (Option<String>, Vec<(String, String)>) {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        return (None, Vec::new());
+    };
+    if parsed.query().is_none_or(str::is_empty) {
+        return (None, Vec::new());
+    }
+    let query = parsed
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    parsed.set_query(None);
+    (Some(parsed.to_string()), query)
+}
+
+/// A double-quoted string literal. JSON escapes read the same in JavaScript, Python and Go.
+pub(super) fn quote(text: &str) -> String {
+    Value::String(text.to_string()).to_string()
+}
+
+/// The file name a multipart file is uploaded under.
+pub(super) fn file_name(path: &str) -> &str {
+    std::path::Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CodeTarget, generate_code};
+    use crate::entities::{Header, HttpMethod, MultipartField, RequestBody, RequestData};
+    use std::collections::HashMap;
+
+    fn request(method: HttpMethod, url: &str, body: RequestBody) -> RequestData {
+        RequestData {
+            method,
+            url: url.to_string(),
+            headers: vec![
+                Header::new("Authorization", "Bearer \"abc\""),
+                Header {
+                    enabled: false,
+                    ..Header::new("X-Debug", "1")
+                },
+            ],
+            body,
+            ..RequestData::default()
+        }
+    }
+
+    #[test]
+    fn writes_json_requests_in_every_language() {
+        let request = request(
+            HttpMethod::Post,
+            "https://api.example.com/users?team=a%20b&active",
+            RequestBody::Json(r#"{"name": "Ada\nLovelace", "admin": true}"#.to_string()),
+        );
+
+        assert_eq!(
+            generate_code(&request, CodeTarget::JavaScriptFetch),
+            "const response = await fetch(\"https://api.example.com/users?team=a%20b&active\", {\n  \
+             method: \"POST\",\n  headers: {\n    \"Authorization\": \"Bearer \\\"abc\\\"\",\n    \
+             \"Content-Type\": \"application/json\",\n  },\n  body: JSON.stringify({\n    \
+             \"name\": \"Ada\\nLovelace\",\n    \"admin\": true\n  }),\n});\n\n\
+             console.log(response.status, await response.text());\n"
+        );
+        assert_eq!(
+            generate_code(&request, CodeTarget::PythonRequests),
+            "import requests\n\nurl = \"https://api.example.com/users\"\n\
+             params = [\n    (\"team\", \"a b\"),\n    (\"active\", \"\"),\n]\n\
+             headers = {\n    \"Authorization\": \"Bearer \\\"abc\\\"\",\n    \
+             \"Content-Type\": \"application/json\",\n}\npayload = {\n    \
+             \"name\": \"Ada\\nLovelace\",\n    \"admin\": True\n}\n\n\
+             response = requests.post(url, params=params, headers=headers, json=payload)\n\
+             print(response.status_code, response.text)\n"
+        );
+
+        let go = generate_code(&request, CodeTarget::GoNetHttp);
+        assert!(go.contains("\t\"strings\"\n"), "{go}");
+        assert!(!go.contains("\"net/url\""), "{go}");
+        assert!(
+            go.contains(
+                "body := strings.NewReader(\"{\\\"name\\\": \\\"Ada\\\\nLovelace\\\", \\\"admin\\\": true}\")"
+            ),
+            "{go}"
+        );
+        assert!(
+            go.contains("req.Header.Add(\"Authorization\", \"Bearer \\\"abc\\\"\")"),
+            "{go}"
+        );
+        assert!(!go.contains("X-Debug"), "{go}");
+
+        let rust = generate_code(&request, CodeTarget::RustReqwest);
+        assert!(
+            rust.contains("        .post(\"https://api.example.com/users\")\n"),
+            "{rust}"
+        );
+        assert!(
+            rust.contains("        .query(&[(\"team\", \"a b\"), (\"active\", \"\")])\n"),
+            "{rust}"
+        );
+        assert!(
+            rust.contains(
+                "        .body(\"{\\\"name\\\": \\\"Ada\\\\nLovelace\\\", \\\"admin\\\": true}\")\n"
+            ),
+            "{rust}"
+        );
+    }
+
+    #[test]
+    fn writes_form_multipart_and_templated_requests() {
+        let form = request(
+            HttpMethod::Put,
+            "{{baseUrl}}/login?next=/",
+            RequestBody::FormData(HashMap::from([
+                ("user".to_string(), "ada".to_string()),
+                ("pass".to_string(), "p&ss".to_string()),
+            ])),
+        );
+        let python = generate_code(&form, CodeTarget::PythonRequests);
+        assert!(
+            python.contains("url = \"{{baseUrl}}/login?next=/\"\n"),
+            "{python}"
+        );
+        assert!(!python.contains("params"), "{python}");
+        assert!(
+            python.contains("data = {\n    \"pass\": \"p&ss\",\n    \"user\": \"ada\",\n}\n"),
+            "{python}"
+        );
+        let go = generate_code(&form, CodeTarget::GoNetHttp);
+        assert!(go.contains("\t\"net/url\"\n"), "{go}");
+        assert!(go.contains("\tform.Add(\"pass\", \"p&ss\")\n"), "{go}");
+        assert!(
+            go.contains("req.Header.Set(\"Content-Type\", \"application/x-www-form-urlencoded\")"),
+            "{go}"
+        );
+
+        let mut upload = request(
+            HttpMethod::Post,
+            "https://example.com/upload",
+            RequestBody::MultipartFormData(vec![
+                MultipartField::text("title", "Cat"),
+                MultipartField {
+                    key: "photo".to_string(),
+                    value: String::new(),
+                    file_path: Some("/tmp/cat.png".to_string()),
+                },
+            ]),
+        );
+        upload
+            .headers
+            .push(Header::new("Content-Type", "multipart/form-data"));
+        let fetch = generate_code(&upload, CodeTarget::JavaScriptFetch);
+        assert!(
+            fetch.starts_with("import fs from \"node:fs\";\n"),
+            "{fetch}"
+        );
+        assert!(
+            fetch.contains(
+                "form.append(\"photo\", await fs.openAsBlob(\"/tmp/cat.png\"), \"cat.png\");"
+            ),
+            "{fetch}"
+        );
+        assert!(!fetch.contains("multipart/form-data"), "{fetch}");
+        let rust = generate_code(&upload, CodeTarget::RustReqwest);
+        assert!(
+            rust.contains(
+                "    let form = reqwest::multipart::Form::new()\n        .text(\"title\", \"Cat\")\n        \
+                 .file(\"photo\", \"/tmp/cat.png\")\n        .await?;\n"
+            ),
+            "{rust}"
+        );
+        let go = generate_code(&upload, CodeTarget::GoNetHttp);
+        for import in [
+            "\"bytes\"",
+            "\"mime/multipart\"",
+            "\"os\"",
+            "\"path/filepath\"",
+        ] {
+            assert!(go.contains(import), "{go}");
+        }
+        assert!(!go.contains("\t\"strings\"\n"), "{go}");
+    }
+
+    #[test]
+    fn sends_graphql_as_json_and_skips_empty_bodies() {
+        let graphql = request(
+            HttpMethod::Post,
+            "https://example.com/graphql",
+            RequestBody::GraphQl {
+                query: "{ me { id } }".to_string(),
+                variables: "not json".to_string(),
+            },
+        );
+        let fetch = generate_code(&graphql, CodeTarget::JavaScriptFetch);
+        assert!(
+            fetch.contains("body: JSON.stringify({\n    \"query\": \"{ me { id } }\""),
+            "{fetch}"
+        );
+
+        let get = request(
+            HttpMethod::Get,
+            "https://example.com",
+            RequestBody::Text(String::new()),
+        );
+        let fetch = generate_code(&get, CodeTarget::JavaScriptFetch);
+        assert!(!fetch.contains("method:"), "{fetch}");
+        assert!(!fetch.contains("body:"), "{fetch}");
+        let go = generate_code(&get, CodeTarget::GoNetHttp);
+        assert!(
+            go.contains("http.NewRequest(\"GET\", \"https://example.com\", nil)"),
+            "{go}"
+        );
+        let rust = generate_code(&get, CodeTarget::RustReqwest);
+        assert!(
+            rust.contains("        .get(\"https://example.com\")\n"),
+            "{rust}"
+        );
+    }
+
+    #[test]
+    fn reads_binary_bodies_from_their_file() {
+        let upload = request(
+            HttpMethod::Put,
+            "https://example.com/avatar",
+            RequestBody::Binary("/tmp/cat.png".into()),
+        );
+        let fetch = generate_code(&upload, CodeTarget::JavaScriptFetch);
+        assert!(
+            fetch.contains("  body: await fs.openAsBlob(\"/tmp/cat.png\"),\n"),
+            "{fetch}"
+        );
+        assert!(fetch.contains("\"Content-Type\": \"image/png\""), "{fetch}");
+        let python = generate_code(&upload, CodeTarget::PythonRequests);
+        assert!(
+            python.contains("data = open(\"/tmp/cat.png\", \"rb\")\n"),
+            "{python}"
+        );
+        let go = generate_code(&upload, CodeTarget::GoNetHttp);
+        assert!(
+            go.contains("body, err := os.Open(\"/tmp/cat.png\")"),
+            "{go}"
+        );
+        let rust = generate_code(&upload, CodeTarget::RustReqwest);
+        assert!(
+            rust.contains("        .body(tokio::fs::read(\"/tmp/cat.png\").await?)\n"),
+            "{rust}"
+        );
+    }
+}
@@ -1,15 +1,17 @@
 use gpui::prelude::*;
-use gpui::{AnyElement, App, Entity, IntoElement, Styled, Window, div, px};
+use gpui::{AnyElement, App, Entity, IntoElement, SharedString, Styled, Window, div, px};
 use gpui_component::input::InputState;
 use gpui_component::{ActiveTheme, Icon};
 use std::rc::Rc;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::entities::{CollectionsEntity, HistoryEntity, HistoryRow};
+use crate::entities::{CollectionsEntity, HistoryEntity, HistoryRow, RunOnOpen};
+use crate::http::StoredCookie;
 use crate::icons::IconName;
 
 use super::collections_panel::CollectionsPanel;
+use super::cookies_panel::CookiesPanel;
 use super::environment_panel::EnvironmentPanel;
 use super::history_panel::{HistoryFilter, HistoryGroupBy, HistoryPanel};
 
@@ -19,6 +21,7 @@ pub enum SidebarTab {
     History,
     Collections,
     Environments,
+    Cookies,
 }
 
 #[derive(IntoElement)]
@@ -33,8 +36,12 @@ pub struct AppSidebar {
     history_rows_initialized: bool,
     history_filter: HistoryFilter,
     history_group_by: HistoryGroupBy,
+    cookies: Vec<StoredCookie>,
+    cookie_scope_label: Option<SharedString>,
     on_tab_change: Option<Rc<dyn Fn(SidebarTab, &mut Window, &mut App) + 'static>>,
     on_load_history_request: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
+    on_load_history_request_in_place: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
+    on_copy_history_headers: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_delete_history_entry: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_toggle_star: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_clear_history: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
@@ -46,13 +53,21 @@ pub struct AppSidebar {
         Option<Rc<dyn Fn(Uuid, Uuid, String, &mut Window, &mut App) + 'static>>,
     on_new_collection: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
     on_import_collection: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
+    on_export_collection: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_new_folder: Option<Rc<dyn Fn(Uuid, Option<Uuid>, &mut Window, &mut App) + 'static>>,
     on_move_collection_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_revert_collection_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_redo_revert_collection_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_show_request_examples: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_set_collection_node_run_on_open:
+        Option<Rc<dyn Fn(Uuid, Uuid, RunOnOpen, &mut Window, &mut App) + 'static>>,
     on_toggle_collection_expand: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_toggle_collection_node_expand:
         Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
     on_filter_change: Option<Rc<dyn Fn(HistoryFilter, &mut Window, &mut App) + 'static>>,
     on_group_by_change: Option<Rc<dyn Fn(HistoryGroupBy, &mut Window, &mut App) + 'static>>,
+    on_delete_cookie: Option<Rc<dyn Fn(&StoredCookie, &mut Window, &mut App) + 'static>>,
+    on_clear_cookies: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
 }
 
 impl AppSidebar {
@@ -76,8 +91,12 @@ impl AppSidebar {
             history_rows_initialized,
             history_filter: HistoryFilter::All,
             history_group_by: HistoryGroupBy::Time,
+            cookies: Vec::new(),
+            cookie_scope_label: None,
             on_tab_change: None,
             on_load_history_request: None,
+            on_load_history_request_in_place: None,
+            on_copy_history_headers: None,
             on_delete_history_entry: None,
             on_toggle_star: None,
             on_clear_history: None,
@@ -88,12 +107,19 @@ impl AppSidebar {
             on_rename_collection_node: None,
             on_new_collection: None,
             on_import_collection: None,
+            on_export_collection: None,
             on_new_folder: None,
             on_move_collection_node: None,
+            on_revert_collection_node: None,
+            on_redo_revert_collection_node: None,
+            on_show_request_examples: None,
+            on_set_collection_node_run_on_open: None,
             on_toggle_collection_expand: None,
             on_toggle_collection_node_expand: None,
             on_filter_change: None,
             on_group_by_change: None,
+            on_delete_cookie: None,
+            on_clear_cookies: None,
         }
     }
 
@@ -118,6 +144,14 @@ impl AppSidebar {
         self
     }
 
+    pub fn on_load_history_request_in_place(
+        mut self,
+        f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_load_history_request_in_place = Some(Rc::new(f));
+        self
+    }
+
     pub fn on_delete_history_entry(
         mut self,
         f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
@@ -126,6 +160,14 @@ impl AppSidebar {
         self
     }
 
+    pub fn on_copy_history_headers(
+        mut self,
+        f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_copy_history_headers = Some(Rc::new(f));
+        self
+    }
+
     pub fn on_toggle_star(mut self, f: impl Fn(Uuid, &mut Window, &mut App) + 'static) -> Self {
         self.on_toggle_star = Some(Rc::new(f));
         self
@@ -186,6 +228,14 @@ impl AppSidebar {
         self
     }
 
+    pub fn on_export_collection(
+        mut self,
+        f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_export_collection = Some(Rc::new(f));
+        self
+    }
+
     pub fn on_new_folder(
         mut self,
         f: impl Fn(Uuid, Option<Uuid>, &mut Window, &mut App) + 'static,
@@ -202,6 +252,38 @@ impl AppSidebar {
         self
     }
 
+    pub fn on_revert_collection_node(
+        mut self,
+        f: impl Fn(Uuid, Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_revert_collection_node = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_redo_revert_collection_node(
+        mut self,
+        f: impl Fn(Uuid, Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_redo_revert_collection_node = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_show_request_examples(
+        mut self,
+        f: impl Fn(Uuid, Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_show_request_examples = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_set_collection_node_run_on_open(
+        mut self,
+        f: impl Fn(Uuid, Uuid, RunOnOpen, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_set_collection_node_run_on_open = Some(Rc::new(f));
+        self
+    }
+
     pub fn on_toggle_collection_expand(
         mut self,
         f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
@@ -244,6 +326,30 @@ impl AppSidebar {
         self
     }
 
+    /// Cookies of the active environment, labelled with that environment's name.
+    pub fn cookies(
+        mut self,
+        scope_label: Option<SharedString>,
+        cookies: Vec<StoredCookie>,
+    ) -> Self {
+        self.cookie_scope_label = scope_label;
+        self.cookies = cookies;
+        self
+    }
+
+    pub fn on_delete_cookie(
+        mut self,
+        f: impl Fn(&StoredCookie, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_delete_cookie = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_clear_cookies(mut self, f: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_clear_cookies = Some(Rc::new(f));
+        self
+    }
+
     fn build_history_panel(&self) -> HistoryPanel {
         let mut panel = HistoryPanel::new(
             self.history.clone(),
@@ -259,6 +365,16 @@ impl AppSidebar {
             panel = panel.on_load_request(move |id, window, cx| f(id, window, cx));
         }
 
+        if let Some(ref f) = self.on_load_history_request_in_place {
+            let f = Rc::clone(f);
+            panel = panel.on_load_request_in_place(move |id, window, cx| f(id, window, cx));
+        }
+
+        if let Some(ref f) = self.on_copy_history_headers {
+            let f = Rc::clone(f);
+            panel = panel.on_copy_headers(move |id, window, cx| f(id, window, cx));
+        }
+
         if let Some(ref f) = self.on_delete_history_entry {
             let f = Rc::clone(f);
             panel = panel.on_delete_entry(move |id, window, cx| f(id, window, cx));
@@ -335,6 +451,11 @@ impl AppSidebar {
             panel = panel.on_import_collection(move |window, cx| f(window, cx));
         }
 
+        if let Some(ref f) = self.on_export_collection {
+            let f = Rc::clone(f);
+            panel = panel.on_export_collection(move |id, window, cx| f(id, window, cx));
+        }
+
         if let Some(ref f) = self.on_new_folder {
             let f = Rc::clone(f);
             panel = panel.on_new_folder(move |collection_id, folder_id, window, cx| {
@@ -349,6 +470,34 @@ impl AppSidebar {
             });
         }
 
+        if let Some(ref f) = self.on_revert_collection_node {
+            let f = Rc::clone(f);
+            panel = panel.on_revert_node(move |collection_id, node_id, window, cx| {
+                f(collection_id, node_id, window, cx)
+            });
+        }
+
+        if let Some(ref f) = self.on_redo_revert_collection_node {
+            let f = Rc::clone(f);
+            panel = panel.on_redo_revert_node(move |collection_id, node_id, window, cx| {
+                f(collection_id, node_id, window, cx)
+            });
+        }
+
+        if let Some(ref f) = self.on_show_request_examples {
+            let f = Rc::clone(f);
+            panel = panel.on_show_examples(move |collection_id, node_id, window, cx| {
+                f(collection_id, node_id, window, cx)
+            });
+        }
+
+        if let Some(ref f) = self.on_set_collection_node_run_on_open {
+            let f = Rc::clone(f);
+            panel = panel.on_set_run_on_open(move |collection_id, node_id, run, window, cx| {
+                f(collection_id, node_id, run, window, cx)
+            });
+        }
+
         if let Some(ref f) = self.on_toggle_collection_expand {
             let f = Rc::clone(f);
             panel = panel.on_toggle_collection_expand(move |id, window, cx| f(id, window, cx));
@@ -364,6 +513,25 @@ impl AppSidebar {
         panel
     }
 
+    fn build_cookies_panel(&mut self) -> CookiesPanel {
+        let mut panel = CookiesPanel::new(
+            std::mem::take(&mut self.cookies),
+            self.cookie_scope_label.take(),
+        );
+
+        if let Some(ref f) = self.on_delete_cookie {
+            let f = Rc::clone(f);
+            panel = panel.on_delete(move |cookie, window, cx| f(cookie, window, cx));
+        }
+
+        if let Some(ref f) = self.on_clear_cookies {
+            let f = Rc::clone(f);
+            panel = panel.on_clear(move |window, cx| f(window, cx));
+        }
+
+        panel
+    }
+
     fn render_icon_button(
         &self,
         tab: SidebarTab,
@@ -378,6 +546,7 @@ impl AppSidebar {
                 SidebarTab::History => "history-tab-btn",
                 SidebarTab::Collections => "collections-tab-btn",
                 SidebarTab::Environments => "environments-tab-btn",
+                SidebarTab::Cookies => "cookies-tab-btn",
             })
             .flex()
             .items_center()
@@ -402,13 +571,14 @@ impl AppSidebar {
 }
 
 impl RenderOnce for AppSidebar {
-    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let theme = cx.theme();
 
         let panel = match self.active_tab {
             SidebarTab::History => self.build_history_panel().into_any_element(),
             SidebarTab::Collections => self.build_collections_panel().into_any_element(),
             SidebarTab::Environments => self.environment_panel.clone().into_any_element(),
+            SidebarTab::Cookies => self.build_cookies_panel().into_any_element(),
         };
 
         div()
@@ -437,7 +607,8 @@ impl RenderOnce for AppSidebar {
                         SidebarTab::Environments,
                         IconName::Package,
                         &theme,
-                    )),
+                    ))
+                    .child(self.render_icon_button(SidebarTab::Cookies, IconName::Cookie, &theme)),
             )
             .child(
                 div()
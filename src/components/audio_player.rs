@@ -5,19 +5,45 @@ use std::time::Duration;
 use bytes::Bytes;
 use gpui::prelude::*;
 use gpui::{
-    App, Bounds, Context, FocusHandle, Focusable, IntoElement, KeyDownEvent, MouseButton,
+    App, Bounds, Context, FocusHandle, Focusable, Global, IntoElement, KeyDownEvent, MouseButton,
     MouseDownEvent, Pixels, Point, Render, Styled, Window, canvas, div, px,
 };
 use gpui_component::ActiveTheme;
 use gpui_component::Disableable;
 use gpui_component::Icon;
+use gpui_component::Selectable;
 use gpui_component::Sizable;
 use gpui_component::button::{Button, ButtonVariants};
 
 use crate::icons::IconName;
+use crate::utils::format_size;
 
 const VOLUME_STEP: f32 = 0.1;
+const DEFAULT_VOLUME: f32 = 0.8;
 const SEEK_STEP_SECS: f32 = 5.0;
+/// Playback speeds the speed button steps through.
+const SPEEDS: [f32; 6] = [0.5, 0.75, 1.0, 1.25, 1.5, 2.0];
+
+/// Volume, mute and speed shared by every audio response, mirrored to `UiPreferences` by the
+/// main view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioSettings {
+    pub volume: f32,
+    pub muted: bool,
+    pub speed: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            volume: DEFAULT_VOLUME,
+            muted: false,
+            speed: 1.0,
+        }
+    }
+}
+
+impl Global for AudioSettings {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlaybackState {
@@ -38,7 +64,13 @@ pub struct AudioPlayer {
     playback_state: PlaybackState,
     volume: f32,
     muted: bool,
+    speed: f32,
+    /// Start over from the beginning whenever playback reaches the end.
+    looping: bool,
     current_position: Duration,
+    /// Where the playing source starts in the file; non-zero after a seek that had to
+    /// re-decode, since the player counts from the start of its source.
+    source_offset: Duration,
     total_duration: Option<Duration>,
     focus_handle: FocusHandle,
     backend: Option<Arc<Mutex<AudioBackend>>>,
@@ -49,14 +81,21 @@ pub struct AudioPlayer {
 impl AudioPlayer {
     pub fn new(audio_bytes: Bytes, content_type: String, cx: &mut Context<Self>) -> Self {
         let duration = Self::probe_duration(&audio_bytes, &content_type);
+        let settings = cx
+            .try_global::<AudioSettings>()
+            .copied()
+            .unwrap_or_default();
 
         Self {
             audio_bytes,
             content_type,
             playback_state: PlaybackState::Stopped,
-            volume: 0.8,
-            muted: false,
+            volume: settings.volume.clamp(0.0, 1.0),
+            muted: settings.muted,
+            speed: settings.speed.clamp(SPEEDS[0], SPEEDS[SPEEDS.len() - 1]),
+            looping: false,
             current_position: Duration::ZERO,
+            source_offset: Duration::ZERO,
             total_duration: duration,
             focus_handle: cx.focus_handle(),
             backend: None,
@@ -86,6 +125,7 @@ impl AudioPlayer {
         };
         let player = rodio::Player::connect_new(device_sink.mixer());
         player.set_volume(self.effective_volume());
+        player.set_speed(self.speed);
 
         self.backend = Some(Arc::new(Mutex::new(AudioBackend {
             player,
@@ -103,14 +143,20 @@ impl AudioPlayer {
             return;
         }
 
-        let Some(ref backend) = self.backend else {
+        let Some(shared) = self.backend.clone() else {
             self.playback_state = PlaybackState::Stopped;
             return;
         };
 
-        if let Ok(backend) = backend.lock() {
+        if let Ok(backend) = shared.lock() {
             let sink_empty = backend.player.empty();
-            let sink_position = backend.player.get_pos();
+            if sink_empty && self.looping {
+                self.restart_from(&backend.player, Duration::ZERO);
+                return;
+            }
+            // The player reports how far into its source it is, whatever the speed, so
+            // the position needs no scaling.
+            let sink_position = backend.player.get_pos() + self.source_offset;
 
             self.current_position = if sink_empty {
                 self.total_duration.unwrap_or(sink_position)
@@ -154,20 +200,19 @@ impl AudioPlayer {
         };
 
         let start_position = self.current_position;
+        self.source_offset = Duration::ZERO;
 
-        if let Some(ref backend) = self.backend
-            && let Ok(backend) = backend.lock()
+        if let Some(shared) = self.backend.clone()
+            && let Ok(backend) = shared.lock()
         {
             backend.player.append(source);
 
             if start_position > Duration::ZERO {
                 if let Err(error) = backend.player.try_seek(start_position) {
-                    log::warn!("Failed to seek audio on play: {error:?}");
-                    self.error_message =
-                        Some("Seeking is not supported for this audio".to_string());
-                    self.current_position = Duration::ZERO;
+                    log::debug!("Seeking on play failed ({error:?}), decoding from the offset");
+                    self.restart_from(&backend.player, start_position);
                 } else {
-                    self.current_position = self.clamp_to_duration(backend.player.get_pos());
+                    self.current_position = self.playback_position(&backend.player);
                 }
             }
         }
@@ -181,7 +226,7 @@ impl AudioPlayer {
             && let Ok(backend) = backend.lock()
         {
             backend.player.pause();
-            self.current_position = self.clamp_to_duration(backend.player.get_pos());
+            self.current_position = self.playback_position(&backend.player);
         }
 
         self.playback_state = PlaybackState::Paused;
@@ -193,7 +238,7 @@ impl AudioPlayer {
             && let Ok(backend) = backend.lock()
         {
             backend.player.play();
-            self.current_position = self.clamp_to_duration(backend.player.get_pos());
+            self.current_position = self.playback_position(&backend.player);
         }
 
         self.playback_state = PlaybackState::Playing;
@@ -210,12 +255,14 @@ impl AudioPlayer {
         self.backend = None;
         self.playback_state = PlaybackState::Stopped;
         self.current_position = Duration::ZERO;
+        self.source_offset = Duration::ZERO;
         cx.notify();
     }
 
     pub fn toggle_mute(&mut self, cx: &mut Context<Self>) {
         self.muted = !self.muted;
         self.apply_volume();
+        self.store_settings(cx);
         cx.notify();
     }
 
@@ -225,15 +272,43 @@ impl AudioPlayer {
             self.muted = false;
         }
         self.apply_volume();
+        self.store_settings(cx);
         cx.notify();
     }
 
     pub fn volume_down(&mut self, cx: &mut Context<Self>) {
         self.volume = (self.volume - VOLUME_STEP).max(0.0);
         self.apply_volume();
+        self.store_settings(cx);
+        cx.notify();
+    }
+
+    pub fn toggle_loop(&mut self, cx: &mut Context<Self>) {
+        self.looping = !self.looping;
+        cx.notify();
+    }
+
+    /// Step to the next playback speed, wrapping from the fastest back to the slowest.
+    pub fn cycle_speed(&mut self, cx: &mut Context<Self>) {
+        self.speed = next_speed(self.speed);
+        if let Some(ref backend) = self.backend
+            && let Ok(backend) = backend.lock()
+        {
+            backend.player.set_speed(self.speed);
+        }
+        self.store_settings(cx);
         cx.notify();
     }
 
+    /// Make this volume and speed the ones the next audio response starts with.
+    fn store_settings(&self, cx: &mut Context<Self>) {
+        cx.set_global(AudioSettings {
+            volume: self.volume,
+            muted: self.muted,
+            speed: self.speed,
+        });
+    }
+
     fn apply_volume(&self) {
         if let Some(ref backend) = self.backend
             && let Ok(backend) = backend.lock()
@@ -260,18 +335,21 @@ impl AudioPlayer {
 
         let clamped = target.min(total_duration);
 
-        if let Some(ref backend) = self.backend
-            && let Ok(backend) = backend.lock()
+        // Lock a handle of our own, so the re-decode fallback may update `self`.
+        if let Some(shared) = self.backend.clone()
+            && let Ok(backend) = shared.lock()
         {
-            if let Err(error) = backend.player.try_seek(clamped) {
-                log::warn!("Failed to seek audio: {error:?}");
-                self.error_message = Some("Seeking is not supported for this audio".to_string());
-                cx.notify();
-                return;
+            match backend.player.try_seek(clamped) {
+                Ok(()) => {
+                    self.source_offset = Duration::ZERO;
+                    self.current_position = self.playback_position(&backend.player);
+                    self.error_message = None;
+                }
+                Err(error) => {
+                    log::debug!("Seeking failed ({error:?}), decoding from the offset");
+                    self.restart_from(&backend.player, clamped);
+                }
             }
-
-            self.current_position = self.clamp_to_duration(backend.player.get_pos());
-            self.error_message = None;
             cx.notify();
             return;
         }
@@ -281,6 +359,32 @@ impl AudioPlayer {
         cx.notify();
     }
 
+    /// Replace the player's source with one decoded from the start and skipped to `position`,
+    /// for formats whose decoder cannot seek. Keeps the player paused or playing as it was.
+    fn restart_from(&mut self, player: &rodio::Player, position: Duration) {
+        use rodio::Source;
+
+        let Ok(source) = rodio::Decoder::try_from(Cursor::new(self.audio_bytes.clone())) else {
+            self.error_message = Some("Failed to decode audio format".to_string());
+            return;
+        };
+        let paused = player.is_paused();
+        // Clearing also pauses the player.
+        player.clear();
+        player.append(source.skip_duration(position));
+        if !paused {
+            player.play();
+        }
+        self.source_offset = position;
+        self.current_position = position;
+        self.error_message = None;
+    }
+
+    /// Position in the file, from what the player has played of its current source.
+    fn playback_position(&self, player: &rodio::Player) -> Duration {
+        self.clamp_to_duration(player.get_pos() + self.source_offset)
+    }
+
     fn seek_to_pointer(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
         let Some(target) = self.pointer_seek_target(position) else {
             return;
@@ -352,16 +456,6 @@ impl AudioPlayer {
         }
     }
 
-    fn format_size(bytes: usize) -> String {
-        if bytes < 1024 {
-            format!("{} B", bytes)
-        } else if bytes < 1024 * 1024 {
-            format!("{:.1} KB", bytes as f64 / 1024.0)
-        } else {
-            format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
-        }
-    }
-
     fn volume_percent(&self) -> u32 {
         (self.volume * 100.0).round() as u32
     }
@@ -402,6 +496,18 @@ impl AudioPlayer {
     }
 }
 
+fn next_speed(speed: f32) -> f32 {
+    SPEEDS
+        .iter()
+        .copied()
+        .find(|&candidate| candidate > speed + f32::EPSILON)
+        .unwrap_or(SPEEDS[0])
+}
+
+fn format_speed(speed: f32) -> String {
+    format!("{}×", (speed * 100.0).round() / 100.0)
+}
+
 impl Focusable for AudioPlayer {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -428,13 +534,15 @@ impl Render for AudioPlayer {
             .map(Self::format_duration)
             .unwrap_or_else(|| "--:--".to_string());
         let format_label = self.audio_format_label().to_string();
-        let size_text = Self::format_size(self.audio_bytes.len());
+        let size_text = format_size(self.audio_bytes.len() as u64);
         let volume_text = if self.muted {
             "Muted".to_string()
         } else {
             format!("{}%", self.volume_percent())
         };
         let error_msg = self.error_message.clone();
+        let looping = self.looping;
+        let speed_text = format_speed(self.speed);
         div()
             .id("audio-player")
             .track_focus(&self.focus_handle)
@@ -480,10 +588,61 @@ impl Render for AudioPlayer {
                                             .gap(px(4.0))
                                             .child(
                                                 div()
-                                                    .text_size(px(14.0))
-                                                    .font_weight(gpui::FontWeight::SEMIBOLD)
-                                                    .text_color(theme.foreground)
-                                                    .child(format_label.clone()),
+                                                    .flex()
+                                                    .flex_row()
+                                                    .items_center()
+                                                    .gap(px(8.0))
+                                                    .child(
+                                                        div()
+                                                            .text_size(px(14.0))
+                                                            .font_weight(gpui::FontWeight::SEMIBOLD)
+                                                            .text_color(theme.foreground)
+                                                            .child(format_label.clone()),
+                                                    )
+                                                    .child(
+                                                        Button::new("audio-loop-toggle")
+                                                            .icon(
+                                                                Icon::new(IconName::Repeat)
+                                                                    .size(px(13.0)),
+                                                            )
+                                                            .ghost()
+                                                            .xsmall()
+                                                            .selected(looping)
+                                                            .tooltip(if looping {
+                                                                "Stop looping"
+                                                            } else {
+                                                                "Loop"
+                                                            })
+                                                            .on_click({
+                                                                let entity = entity.clone();
+                                                                move |_, _window, cx| {
+                                                                    entity.update(
+                                                                        cx,
+                                                                        |player, cx| {
+                                                                            player.toggle_loop(cx);
+                                                                        },
+                                                                    );
+                                                                }
+                                                            }),
+                                                    )
+                                                    .child(
+                                                        Button::new("audio-speed")
+                                                            .label(speed_text)
+                                                            .ghost()
+                                                            .xsmall()
+                                                            .tooltip("Playback speed")
+                                                            .on_click({
+                                                                let entity = entity.clone();
+                                                                move |_, _window, cx| {
+                                                                    entity.update(
+                                                                        cx,
+                                                                        |player, cx| {
+                                                                            player.cycle_speed(cx);
+                                                                        },
+                                                                    );
+                                                                }
+                                                            }),
+                                                    ),
                                             )
                                             .child(
                                                 div()
@@ -729,3 +888,17 @@ impl Render for AudioPlayer {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{format_speed, next_speed};
+
+    #[test]
+    fn steps_through_speeds_and_wraps_around() {
+        assert_eq!(next_speed(1.0), 1.25);
+        assert_eq!(next_speed(2.0), 0.5);
+        assert_eq!(next_speed(0.9), 1.0);
+        assert_eq!(format_speed(0.75), "0.75×");
+        assert_eq!(format_speed(2.0), "2×");
+    }
+}
@@ -1,49 +1,100 @@
-use base64::Engine as _;
+use std::time::Duration;
+
+use chrono::Utc;
 use gpui::prelude::*;
 use gpui::{
-    App, Context, Entity, FocusHandle, Focusable, IntoElement, Render, SharedString, Styled,
+    App, Context, Entity, FocusHandle, Focusable, IntoElement, Render, SharedString, Styled, Task,
     Window, div, px,
 };
 use gpui_component::Sizable;
+use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::input::{Input, InputState};
 use gpui_component::select::{Select, SelectEvent, SelectItem, SelectState};
 
 use gpui_component::ActiveTheme;
 
+use uuid::Uuid;
+
+use crate::actions::FetchOAuthToken;
 use crate::completion::{
     CompletionContext, CompletionEngine, CompletionInput, configure_completion,
 };
-
-/// Authentication type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum AuthType {
-    #[default]
-    None,
-    Basic,
-    Bearer,
-    ApiKey,
-}
-
-impl AuthType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            AuthType::None => "No Auth",
-            AuthType::Basic => "Basic Auth",
-            AuthType::Bearer => "Bearer Token",
-            AuthType::ApiKey => "API Key",
+use crate::entities::{CollectionsEntity, OAuthToken};
+
+pub use crate::entities::{AuthConfig, AuthType};
+
+/// One line describing an inherited config without revealing its secret.
+fn inherited_summary(auth: &AuthConfig) -> String {
+    match auth.auth_type {
+        AuthType::Basic => format!("{} as {}", auth.auth_type.as_str(), auth.username),
+        AuthType::ApiKey => format!(
+            "{} {} in {}",
+            auth.auth_type.as_str(),
+            auth.api_key_name,
+            if auth.api_key_in_header {
+                "header"
+            } else {
+                "query param"
+            }
+        ),
+        AuthType::OAuth2ClientCredentials => {
+            format!("{} from {}", auth.auth_type.as_str(), auth.oauth_token_url)
         }
+        AuthType::AwsSigV4 => format!(
+            "{} for {} in {}",
+            auth.auth_type.as_str(),
+            auth.aws_service,
+            auth.aws_region
+        ),
+        _ => auth.auth_type.as_str().to_string(),
     }
+}
 
-    pub fn all() -> &'static [AuthType] {
-        &[
-            AuthType::None,
-            AuthType::Basic,
-            AuthType::Bearer,
-            AuthType::ApiKey,
-        ]
+/// Remaining token lifetime as "59m 12s", or whole hours and minutes past an hour.
+fn format_lifetime(lifetime: chrono::Duration) -> String {
+    let seconds = lifetime.num_seconds().max(0);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
     }
 }
 
+/// A field label above its input, as the auth forms lay them out.
+fn labeled_field(
+    theme: &gpui_component::theme::ThemeColor,
+    label: &'static str,
+    input: Option<&Entity<InputState>>,
+) -> impl IntoElement {
+    div()
+        .flex()
+        .flex_col()
+        .gap(px(6.0))
+        .child(
+            div()
+                .text_color(theme.muted_foreground)
+                .text_size(px(11.0))
+                .font_weight(gpui::FontWeight::SEMIBOLD)
+                .child(label),
+        )
+        .when_some(input, |el, input| {
+            el.child(
+                div()
+                    .bg(theme.secondary)
+                    .rounded(px(6.0))
+                    .border_1()
+                    .border_color(theme.border)
+                    .child(CompletionInput::new(
+                        input,
+                        Input::new(input).appearance(false).small(),
+                    )),
+            )
+        })
+}
+
 /// Implement SelectItem for AuthType
 impl SelectItem for AuthType {
     type Value = AuthType;
@@ -91,116 +142,6 @@ impl SelectItem for ApiKeyLocation {
     }
 }
 
-/// Authentication configuration
-#[derive(Debug, Clone, Default)]
-pub struct AuthConfig {
-    pub auth_type: AuthType,
-    pub username: String,
-    pub password: String,
-    pub token: String,
-    pub api_key_name: String,
-    pub api_key_value: String,
-    pub api_key_in_header: bool, // true = header, false = query param
-}
-
-impl AuthConfig {
-    /// Generate Authorization header if applicable
-    pub fn to_header(&self) -> Option<(String, String)> {
-        match self.auth_type {
-            AuthType::None => None,
-            AuthType::Basic => {
-                let credentials = format!("{}:{}", self.username, self.password);
-                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
-                Some(("Authorization".to_string(), format!("Basic {}", encoded)))
-            }
-            AuthType::Bearer => {
-                if self.token.is_empty() {
-                    None
-                } else {
-                    Some((
-                        "Authorization".to_string(),
-                        format!("Bearer {}", self.token),
-                    ))
-                }
-            }
-            AuthType::ApiKey => {
-                if self.api_key_in_header && !self.api_key_name.is_empty() {
-                    Some((self.api_key_name.clone(), self.api_key_value.clone()))
-                } else {
-                    None
-                }
-            }
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{AuthConfig, AuthType};
-
-    #[test]
-    fn basic_auth_generates_expected_header() {
-        let config = AuthConfig {
-            auth_type: AuthType::Basic,
-            username: "raunak".to_string(),
-            password: "secret".to_string(),
-            ..Default::default()
-        };
-
-        assert_eq!(
-            config.to_header(),
-            Some((
-                "Authorization".to_string(),
-                "Basic cmF1bmFrOnNlY3JldA==".to_string()
-            ))
-        );
-    }
-
-    #[test]
-    fn bearer_auth_omits_empty_token() {
-        let empty = AuthConfig {
-            auth_type: AuthType::Bearer,
-            token: String::new(),
-            ..Default::default()
-        };
-        let filled = AuthConfig {
-            auth_type: AuthType::Bearer,
-            token: "abc123".to_string(),
-            ..Default::default()
-        };
-
-        assert_eq!(empty.to_header(), None);
-        assert_eq!(
-            filled.to_header(),
-            Some(("Authorization".to_string(), "Bearer abc123".to_string()))
-        );
-    }
-
-    #[test]
-    fn api_key_only_generates_header_for_header_mode() {
-        let query_param = AuthConfig {
-            auth_type: AuthType::ApiKey,
-            api_key_name: "X-Api-Key".to_string(),
-            api_key_value: "secret".to_string(),
-            api_key_in_header: false,
-            ..Default::default()
-        };
-        let header = AuthConfig {
-            auth_type: AuthType::ApiKey,
-            api_key_name: "X-Api-Key".to_string(),
-            api_key_value: "secret".to_string(),
-            api_key_in_header: true,
-            ..Default::default()
-        };
-
-        assert_eq!(query_param.to_header(), None);
-        assert_eq!(
-            header.to_header(),
-            Some(("X-Api-Key".to_string(), "secret".to_string()))
-        );
-    }
-}
-
 /// Authentication editor component
 pub struct AuthEditor {
     auth_type: AuthType,
@@ -215,6 +156,24 @@ pub struct AuthEditor {
     api_key_value_input: Option<gpui::Entity<InputState>>,
     api_key_location: ApiKeyLocation,
     api_key_location_select: Entity<SelectState<Vec<ApiKeyLocation>>>,
+    // OAuth 2.0 client credentials
+    oauth_token_url_input: Option<gpui::Entity<InputState>>,
+    oauth_client_id_input: Option<gpui::Entity<InputState>>,
+    oauth_client_secret_input: Option<gpui::Entity<InputState>>,
+    oauth_scope_input: Option<gpui::Entity<InputState>>,
+    oauth_token: Option<OAuthToken>,
+    oauth_fetching: bool,
+    oauth_error: Option<SharedString>,
+    /// Re-renders every second so the token lifetime counts down.
+    _oauth_countdown: Option<Task<()>>,
+    // AWS Signature Version 4
+    aws_access_key_input: Option<gpui::Entity<InputState>>,
+    aws_secret_key_input: Option<gpui::Entity<InputState>>,
+    aws_region_input: Option<gpui::Entity<InputState>>,
+    aws_service_input: Option<gpui::Entity<InputState>>,
+    aws_session_token_input: Option<gpui::Entity<InputState>>,
+    /// Collection the request belongs to, whose default auth `Inherit` uses.
+    owning_collection: Option<(Entity<CollectionsEntity>, Uuid)>,
 
     focus_handle: FocusHandle,
     completion_engine: Option<CompletionEngine>,
@@ -282,6 +241,20 @@ impl AuthEditor {
             api_key_value_input: None,
             api_key_location: ApiKeyLocation::Header,
             api_key_location_select,
+            oauth_token_url_input: None,
+            oauth_client_id_input: None,
+            oauth_client_secret_input: None,
+            oauth_scope_input: None,
+            oauth_token: None,
+            oauth_fetching: false,
+            oauth_error: None,
+            _oauth_countdown: None,
+            aws_access_key_input: None,
+            aws_secret_key_input: None,
+            aws_region_input: None,
+            aws_service_input: None,
+            aws_session_token_input: None,
+            owning_collection: None,
             focus_handle: cx.focus_handle(),
             completion_engine,
         }
@@ -330,10 +303,104 @@ impl AuthEditor {
                     CompletionContext::Auth,
                 )
             }));
+            let completion_engine = self.completion_engine.clone();
+            self.oauth_token_url_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("https://auth.example.com/oauth/token"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.oauth_client_id_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("Client ID"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.oauth_client_secret_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("Client secret"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.oauth_scope_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("Space-separated scopes (optional)"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.aws_access_key_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("Access key ID"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.aws_secret_key_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("Secret access key"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.aws_region_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("us-east-1"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.aws_service_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx).placeholder("execute-api, s3, dynamodb…"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
+            let completion_engine = self.completion_engine.clone();
+            self.aws_session_token_input = Some(cx.new(|cx| {
+                configure_completion(
+                    InputState::new(window, cx)
+                        .placeholder("Session token (temporary credentials only)"),
+                    completion_engine.as_ref(),
+                    CompletionContext::Auth,
+                )
+            }));
         }
     }
 
-    #[allow(dead_code)]
+    /// Switch to Basic auth with the given credentials
+    pub fn set_basic_credentials(
+        &mut self,
+        username: &str,
+        password: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.ensure_inputs(window, cx);
+        if let Some(input) = self.username_input.clone() {
+            input.update(cx, |state, cx| {
+                state.set_value(username.to_string(), window, cx)
+            });
+        }
+        if let Some(input) = self.password_input.clone() {
+            input.update(cx, |state, cx| {
+                state.set_value(password.to_string(), window, cx)
+            });
+        }
+        self.set_auth_type(AuthType::Basic, window, cx);
+    }
+
     /// Set auth type
     pub fn set_auth_type(
         &mut self,
@@ -348,6 +415,70 @@ impl AuthEditor {
         cx.notify();
     }
 
+    pub fn auth_type(&self) -> AuthType {
+        self.auth_type
+    }
+
+    pub fn set_owning_collection(
+        &mut self,
+        owning_collection: Option<(Entity<CollectionsEntity>, Uuid)>,
+        cx: &mut Context<Self>,
+    ) {
+        self.owning_collection = owning_collection;
+        cx.notify();
+    }
+
+    /// Show that a token request for this config is in flight.
+    pub fn set_oauth_fetching(&mut self, cx: &mut Context<Self>) {
+        self.oauth_fetching = true;
+        self.oauth_error = None;
+        cx.notify();
+    }
+
+    /// Cache the outcome of a token request and count down the new token's lifetime.
+    pub fn set_oauth_token(&mut self, result: Result<OAuthToken, String>, cx: &mut Context<Self>) {
+        self.oauth_fetching = false;
+        match result {
+            Ok(token) => {
+                let expires = token.expires_at.is_some();
+                self.oauth_token = Some(token);
+                self.oauth_error = None;
+                self._oauth_countdown = expires.then(|| {
+                    cx.spawn(async move |this, cx| {
+                        loop {
+                            cx.background_executor().timer(Duration::from_secs(1)).await;
+                            let ticking = this
+                                .update(cx, |editor, cx| {
+                                    cx.notify();
+                                    editor
+                                        .oauth_token
+                                        .as_ref()
+                                        .is_some_and(|token| !token.is_expired(Utc::now()))
+                                })
+                                .unwrap_or(false);
+                            if !ticking {
+                                break;
+                            }
+                        }
+                    })
+                });
+            }
+            Err(error) => self.oauth_error = Some(error.into()),
+        }
+        cx.notify();
+    }
+
+    /// Store the current config as the owning collection's default auth.
+    fn use_as_collection_default(&self, cx: &mut Context<Self>) {
+        let Some((collections, collection_id)) = self.owning_collection.clone() else {
+            return;
+        };
+        let config = self.get_config(cx);
+        collections.update(cx, |collections, cx| {
+            collections.set_collection_auth(collection_id, Some(config), cx);
+        });
+    }
+
     /// Get current auth config
     pub fn get_config(&self, cx: &App) -> AuthConfig {
         AuthConfig {
@@ -378,6 +509,52 @@ impl AuthEditor {
                 .map(|i| i.read(cx).text().to_string())
                 .unwrap_or_default(),
             api_key_in_header: self.api_key_location == ApiKeyLocation::Header,
+            oauth_token_url: self
+                .oauth_token_url_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            oauth_client_id: self
+                .oauth_client_id_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            oauth_client_secret: self
+                .oauth_client_secret_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            oauth_scope: self
+                .oauth_scope_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            oauth_token: self.oauth_token.clone(),
+            aws_access_key: self
+                .aws_access_key_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            aws_secret_key: self
+                .aws_secret_key_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            aws_region: self
+                .aws_region_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            aws_service: self
+                .aws_service_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
+            aws_session_token: self
+                .aws_session_token_input
+                .as_ref()
+                .map(|i| i.read(cx).text().to_string())
+                .unwrap_or_default(),
         }
     }
 }
@@ -448,6 +625,45 @@ impl Render for AuthEditor {
                     .when(self.auth_type == AuthType::ApiKey, |el| {
                         el.child(self.render_api_key_auth(&theme))
                     })
+                    .when(self.auth_type == AuthType::OAuth2ClientCredentials, |el| {
+                        el.child(self.render_oauth2_auth(&theme))
+                    })
+                    .when(self.auth_type == AuthType::AwsSigV4, |el| {
+                        el.child(self.render_aws_sigv4_auth(&theme))
+                    })
+                    .when(self.auth_type == AuthType::Inherit, |el| {
+                        el.child(self.render_inherited_auth(&theme, cx))
+                    })
+                    .when(
+                        self.owning_collection.is_some()
+                            && matches!(
+                                self.auth_type,
+                                AuthType::Basic
+                                    | AuthType::Bearer
+                                    | AuthType::ApiKey
+                                    | AuthType::OAuth2ClientCredentials
+                                    | AuthType::AwsSigV4
+                            ),
+                        |el| {
+                            let this = cx.entity().clone();
+                            el.child(
+                                div().pt(px(16.0)).child(
+                                    Button::new("use-as-collection-auth")
+                                        .label("Use as Collection Default")
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip(
+                                            "Requests in this collection set to inherit will use this auth",
+                                        )
+                                        .on_click(move |_, _, cx| {
+                                            this.update(cx, |editor, cx| {
+                                                editor.use_as_collection_default(cx);
+                                            });
+                                        }),
+                                ),
+                            )
+                        },
+                    )
                     .when(self.auth_type == AuthType::None, |el| {
                         el.child(
                             div()
@@ -466,6 +682,146 @@ impl Render for AuthEditor {
 }
 
 impl AuthEditor {
+    /// Explain which collection default an inheriting request sends.
+    fn render_inherited_auth(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        cx: &App,
+    ) -> impl IntoElement {
+        let collection = self
+            .owning_collection
+            .as_ref()
+            .and_then(|(collections, id)| collections.read(cx).get_collection(*id));
+        let (title, detail) = match collection {
+            None => (
+                "Not in a collection".to_string(),
+                "Save this request to a collection to inherit its auth. No auth is sent."
+                    .to_string(),
+            ),
+            Some(collection) => match collection.auth.as_ref() {
+                Some(auth) if auth.auth_type != AuthType::None => (
+                    format!("Inherited from \"{}\"", collection.name),
+                    inherited_summary(auth),
+                ),
+                _ => (
+                    format!("Inherited from \"{}\"", collection.name),
+                    "The collection has no default auth, so none is sent.".to_string(),
+                ),
+            },
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .p(px(12.0))
+            .rounded(px(6.0))
+            .border_1()
+            .border_color(theme.border)
+            .bg(theme.secondary)
+            .child(
+                div()
+                    .text_color(theme.foreground)
+                    .text_size(px(12.0))
+                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                    .child(title),
+            )
+            .child(
+                div()
+                    .text_color(theme.muted_foreground)
+                    .text_size(px(11.0))
+                    .child(detail),
+            )
+    }
+
+    fn render_oauth2_auth(&self, theme: &gpui_component::theme::ThemeColor) -> impl IntoElement {
+        let field = |label, input| labeled_field(theme, label, input);
+
+        let now = Utc::now();
+        let (status, status_color) = if self.oauth_fetching {
+            ("Requesting token…".to_string(), theme.muted_foreground)
+        } else if let Some(error) = &self.oauth_error {
+            (format!("Token request failed: {error}"), theme.danger)
+        } else {
+            match &self.oauth_token {
+                None => (
+                    "No token yet. One is fetched automatically on send.".to_string(),
+                    theme.muted_foreground,
+                ),
+                Some(token) if token.is_expired(now) => (
+                    "Token expired. A new one is fetched on the next send.".to_string(),
+                    theme.warning,
+                ),
+                Some(token) => match token.remaining(now) {
+                    Some(lifetime) => (
+                        format!("Token expires in {}", format_lifetime(lifetime)),
+                        theme.success,
+                    ),
+                    None => ("Token has no expiry".to_string(), theme.success),
+                },
+            }
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(16.0))
+            .child(field("Token URL", self.oauth_token_url_input.as_ref()))
+            .child(field("Client ID", self.oauth_client_id_input.as_ref()))
+            .child(field(
+                "Client Secret",
+                self.oauth_client_secret_input.as_ref(),
+            ))
+            .child(field("Scope", self.oauth_scope_input.as_ref()))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(10.0))
+                    .child(
+                        Button::new("oauth-get-token")
+                            .label("Get Token")
+                            .primary()
+                            .xsmall()
+                            .loading(self.oauth_fetching)
+                            .disabled(self.oauth_fetching)
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(Box::new(FetchOAuthToken), cx);
+                            }),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_color(status_color)
+                            .text_size(px(11.0))
+                            .child(status),
+                    ),
+            )
+    }
+
+    fn render_aws_sigv4_auth(&self, theme: &gpui_component::theme::ThemeColor) -> impl IntoElement {
+        let field = |label, input| labeled_field(theme, label, input);
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(16.0))
+            .child(field("Access Key", self.aws_access_key_input.as_ref()))
+            .child(field("Secret Key", self.aws_secret_key_input.as_ref()))
+            .child(field("Region", self.aws_region_input.as_ref()))
+            .child(field("Service", self.aws_service_input.as_ref()))
+            .child(field(
+                "Session Token",
+                self.aws_session_token_input.as_ref(),
+            ))
+            .child(
+                div()
+                    .text_color(theme.muted_foreground)
+                    .text_size(px(11.0))
+                    .child("The request is signed when it is sent, after variables are resolved."),
+            )
+    }
+
     fn render_basic_auth(&self, theme: &gpui_component::theme::ThemeColor) -> impl IntoElement {
         div()
             .flex()
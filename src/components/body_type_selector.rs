@@ -21,6 +21,8 @@ pub enum BodyType {
     FormData,
     Xml,
     Html,
+    GraphQl,
+    Binary,
 }
 
 impl BodyType {
@@ -33,6 +35,8 @@ impl BodyType {
             BodyType::FormData => "form-data",
             BodyType::Xml => "XML",
             BodyType::Html => "HTML",
+            BodyType::GraphQl => "GraphQL",
+            BodyType::Binary => "Binary",
         }
     }
 
@@ -45,6 +49,9 @@ impl BodyType {
             BodyType::FormData => Some("multipart/form-data"),
             BodyType::Xml => Some("application/xml"),
             BodyType::Html => Some("text/html"),
+            BodyType::GraphQl => Some("application/json"),
+            // Follows the chosen file's extension.
+            BodyType::Binary => None,
         }
     }
 
@@ -69,6 +76,25 @@ impl BodyType {
         )
     }
 
+    /// Whether the body is edited as raw text in the body editor.
+    pub fn is_raw_text(&self) -> bool {
+        matches!(
+            self,
+            BodyType::Json | BodyType::Text | BodyType::Xml | BodyType::Html
+        )
+    }
+
+    /// The raw body type a file with this extension usually holds.
+    pub fn from_file_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(BodyType::Json),
+            "xml" => Some(BodyType::Xml),
+            "html" | "htm" => Some(BodyType::Html),
+            "txt" | "text" => Some(BodyType::Text),
+            _ => None,
+        }
+    }
+
     pub fn syntax_language(&self) -> &'static str {
         match self {
             BodyType::Json => "json",
@@ -87,6 +113,8 @@ impl BodyType {
             BodyType::FormData,
             BodyType::Xml,
             BodyType::Html,
+            BodyType::GraphQl,
+            BodyType::Binary,
         ]
     }
 
@@ -97,6 +125,8 @@ impl BodyType {
             RequestBody::Text(_) => BodyType::Text,
             RequestBody::FormData(_) => BodyType::FormUrlEncoded,
             RequestBody::MultipartFormData(_) => BodyType::FormData,
+            RequestBody::GraphQl { .. } => BodyType::GraphQl,
+            RequestBody::Binary(_) => BodyType::Binary,
         }
     }
 }
@@ -217,25 +247,30 @@ impl Render for BodyTypeSelector {
             BodyType::Json | BodyType::Text | BodyType::Xml | BodyType::Html
         );
 
-        // Only show beautify button for JSON
-        let show_beautify = self.selected == BodyType::Json;
+        // Only show beautify button for JSON (GraphQL formats its variables)
+        let show_beautify = matches!(self.selected, BodyType::Json | BodyType::GraphQl);
+        let beautify_tooltip = if self.selected == BodyType::GraphQl {
+            "Beautify variables JSON"
+        } else {
+            "Beautify JSON"
+        };
 
-        // Show clear button for text-based body types (JSON, XML, HTML, Text)
+        // Show clear button for text-based body types (JSON, XML, HTML, Text, GraphQL)
         let show_clear = matches!(
             self.selected,
-            BodyType::Json | BodyType::Xml | BodyType::Html | BodyType::Text
+            BodyType::Json | BodyType::Xml | BodyType::Html | BodyType::Text | BodyType::GraphQl
         );
 
         // Show wrap toggle for text-based body types
         let show_wrap = matches!(
             self.selected,
-            BodyType::Json | BodyType::Xml | BodyType::Html | BodyType::Text
+            BodyType::Json | BodyType::Xml | BodyType::Html | BodyType::Text | BodyType::GraphQl
         );
 
         // Show find button for text-based body types
         let show_find = matches!(
             self.selected,
-            BodyType::Json | BodyType::Xml | BodyType::Html | BodyType::Text
+            BodyType::Json | BodyType::Xml | BodyType::Html | BodyType::Text | BodyType::GraphQl
         );
 
         let wrap_lines = self.wrap_lines;
@@ -315,7 +350,7 @@ impl Render for BodyTypeSelector {
                                 .icon(IconName::Sparkles)
                                 .ghost()
                                 .xsmall()
-                                .tooltip("Beautify JSON")
+                                .tooltip(beautify_tooltip)
                                 .on_click(move |_, _, cx| {
                                     this_for_beautify.update(cx, |_, cx| {
                                         cx.emit(BodyTypeSelectorEvent::BeautifyRequested);
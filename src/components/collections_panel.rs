@@ -1,7 +1,7 @@
 use gpui::prelude::*;
 use gpui::{
     AnyElement, App, Context, Corner, DismissEvent, Entity, Focusable, IntoElement, MouseButton,
-    Point, SharedString, Styled, Subscription, Window, anchored, deferred, div, px,
+    Point, Render, SharedString, Styled, Subscription, Window, anchored, deferred, div, px,
 };
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::input::{Input, InputState};
@@ -15,7 +15,10 @@ use std::rc::Rc;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::entities::{CollectionNode, CollectionsEntity, HttpMethod, SidebarLoadState};
+use crate::entities::{
+    CollectionDestination, CollectionNode, CollectionsEntity, HttpMethod, RunOnOpen,
+    SidebarLoadState,
+};
 use crate::icons::IconName;
 use crate::theme::method_color;
 
@@ -28,8 +31,13 @@ struct PanelCallbacks {
     on_rename_node: Option<Rc<dyn Fn(Uuid, Uuid, String, &mut Window, &mut App) + 'static>>,
     on_new_collection: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
     on_import_collection: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
+    on_export_collection: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_new_folder: Option<Rc<dyn Fn(Uuid, Option<Uuid>, &mut Window, &mut App) + 'static>>,
     on_move_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_revert_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_redo_revert_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_show_examples: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_set_run_on_open: Option<Rc<dyn Fn(Uuid, Uuid, RunOnOpen, &mut Window, &mut App) + 'static>>,
     on_toggle_collection_expand: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_toggle_node_expand: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
 }
@@ -42,6 +50,56 @@ struct RowContextMenuState {
     subscription: Option<Subscription>,
 }
 
+/// A request being dragged onto a collection, folder or sibling request.
+#[derive(Clone)]
+struct DraggedCollectionItem {
+    collection_id: Uuid,
+    item_id: Uuid,
+    name: String,
+    method: HttpMethod,
+}
+
+struct CollectionItemDragPreview {
+    name: String,
+    method: HttpMethod,
+}
+
+impl Render for CollectionItemDragPreview {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let m_color = method_color(&self.method, cx);
+        let theme = cx.theme();
+
+        div()
+            .w(px(240.0))
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap(px(8.0))
+            .h(px(32.0))
+            .px(px(12.0))
+            .bg(theme.background.opacity(0.95))
+            .border_1()
+            .border_color(theme.primary.opacity(0.5))
+            .rounded(px(6.0))
+            .shadow_lg()
+            .opacity(0.9)
+            .child(CollectionsPanel::render_method_badge(
+                self.method.as_str(),
+                m_color,
+            ))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w(px(0.0))
+                    .overflow_hidden()
+                    .text_color(theme.foreground)
+                    .text_size(px(11.5))
+                    .text_ellipsis()
+                    .child(self.name.clone()),
+            )
+    }
+}
+
 #[derive(Clone)]
 enum CollectionTreeRow {
     Collection {
@@ -59,9 +117,15 @@ enum CollectionTreeRow {
     },
     Request {
         collection_id: Uuid,
+        /// Folder holding the request, `None` at the collection root.
+        parent_id: Option<Uuid>,
         id: Uuid,
         name: String,
         method: HttpMethod,
+        version_count: usize,
+        redo_count: usize,
+        example_count: usize,
+        run_on_open: RunOnOpen,
     },
 }
 
@@ -84,8 +148,13 @@ pub struct CollectionsPanel {
     on_rename_node: Option<Rc<dyn Fn(Uuid, Uuid, String, &mut Window, &mut App) + 'static>>,
     on_new_collection: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
     on_import_collection: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
+    on_export_collection: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_new_folder: Option<Rc<dyn Fn(Uuid, Option<Uuid>, &mut Window, &mut App) + 'static>>,
     on_move_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_revert_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_redo_revert_node: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_show_examples: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
+    on_set_run_on_open: Option<Rc<dyn Fn(Uuid, Uuid, RunOnOpen, &mut Window, &mut App) + 'static>>,
     on_toggle_collection_expand: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_toggle_node_expand: Option<Rc<dyn Fn(Uuid, Uuid, &mut Window, &mut App) + 'static>>,
 }
@@ -102,8 +171,13 @@ impl CollectionsPanel {
             on_rename_node: None,
             on_new_collection: None,
             on_import_collection: None,
+            on_export_collection: None,
             on_new_folder: None,
             on_move_node: None,
+            on_revert_node: None,
+            on_redo_revert_node: None,
+            on_show_examples: None,
+            on_set_run_on_open: None,
             on_toggle_collection_expand: None,
             on_toggle_node_expand: None,
         }
@@ -159,6 +233,14 @@ impl CollectionsPanel {
         self
     }
 
+    pub fn on_export_collection(
+        mut self,
+        f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_export_collection = Some(Rc::new(f));
+        self
+    }
+
     pub fn on_new_folder(
         mut self,
         f: impl Fn(Uuid, Option<Uuid>, &mut Window, &mut App) + 'static,
@@ -172,6 +254,38 @@ impl CollectionsPanel {
         self
     }
 
+    pub fn on_revert_node(
+        mut self,
+        f: impl Fn(Uuid, Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_revert_node = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_redo_revert_node(
+        mut self,
+        f: impl Fn(Uuid, Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_redo_revert_node = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_show_examples(
+        mut self,
+        f: impl Fn(Uuid, Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_show_examples = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_set_run_on_open(
+        mut self,
+        f: impl Fn(Uuid, Uuid, RunOnOpen, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_set_run_on_open = Some(Rc::new(f));
+        self
+    }
+
     pub fn on_toggle_collection_expand(
         mut self,
         f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
@@ -202,6 +316,7 @@ impl CollectionsPanel {
 
     fn build_node_snapshot(
         collection_id: Uuid,
+        parent_id: Option<Uuid>,
         node: &CollectionNode,
         rows: &mut HashMap<SharedString, CollectionTreeRow>,
     ) -> TreeItem {
@@ -211,7 +326,9 @@ impl CollectionsPanel {
                 let children = folder
                     .children
                     .iter()
-                    .map(|child| Self::build_node_snapshot(collection_id, child, rows))
+                    .map(|child| {
+                        Self::build_node_snapshot(collection_id, Some(folder.id), child, rows)
+                    })
                     .collect::<Vec<_>>();
                 rows.insert(
                     id.clone(),
@@ -234,9 +351,14 @@ impl CollectionsPanel {
                     id.clone(),
                     CollectionTreeRow::Request {
                         collection_id,
+                        parent_id,
                         id: request.id,
                         name: name.clone(),
                         method: request.request.method,
+                        version_count: request.versions.len(),
+                        redo_count: request.reverted.len(),
+                        example_count: request.examples.len(),
+                        run_on_open: request.run_on_open,
                     },
                 );
                 TreeItem::new(id, name)
@@ -257,7 +379,7 @@ impl CollectionsPanel {
                 let children = collection
                     .nodes
                     .iter()
-                    .map(|node| Self::build_node_snapshot(collection.id, node, &mut rows))
+                    .map(|node| Self::build_node_snapshot(collection.id, None, node, &mut rows))
                     .collect::<Vec<_>>();
                 rows.insert(
                     id.clone(),
@@ -286,8 +408,13 @@ impl CollectionsPanel {
             on_rename_node: self.on_rename_node.clone(),
             on_new_collection: self.on_new_collection.clone(),
             on_import_collection: self.on_import_collection.clone(),
+            on_export_collection: self.on_export_collection.clone(),
             on_new_folder: self.on_new_folder.clone(),
             on_move_node: self.on_move_node.clone(),
+            on_revert_node: self.on_revert_node.clone(),
+            on_redo_revert_node: self.on_redo_revert_node.clone(),
+            on_show_examples: self.on_show_examples.clone(),
+            on_set_run_on_open: self.on_set_run_on_open.clone(),
             on_toggle_collection_expand: self.on_toggle_collection_expand.clone(),
             on_toggle_node_expand: self.on_toggle_node_expand.clone(),
         }
@@ -334,7 +461,7 @@ impl CollectionsPanel {
             container = container.child(
                 Button::new("import-first-collection")
                     .small()
-                    .label("Import Postman")
+                    .label("Import Postman or OpenAPI")
                     .icon(Icon::new(IconName::FileUp).size(px(14.0)))
                     .on_click(move |_, window, cx| handler(window, cx)),
             );
@@ -375,6 +502,7 @@ impl CollectionsPanel {
     ) -> PopupMenu {
         let on_rename_collection = callbacks.on_rename_collection.clone();
         let on_new_folder = callbacks.on_new_folder.clone();
+        let on_export_collection = callbacks.on_export_collection.clone();
         let on_delete_collection = callbacks.on_delete_collection.clone();
 
         menu.item(
@@ -395,6 +523,15 @@ impl CollectionsPanel {
                     }
                 }),
         )
+        .item(
+            PopupMenuItem::new("Export")
+                .icon(IconName::FileDown)
+                .on_click(move |_event, window, cx| {
+                    if let Some(ref handler) = on_export_collection {
+                        handler(collection_id, window, cx);
+                    }
+                }),
+        )
         .separator()
         .item(PopupMenuItem::new("Delete").icon(IconName::Trash).on_click(
             move |_event, window, cx| {
@@ -457,28 +594,98 @@ impl CollectionsPanel {
         collection_id: Uuid,
         node_id: Uuid,
         current_name: String,
+        method: HttpMethod,
+        version_count: usize,
+        redo_count: usize,
+        example_count: usize,
+        run_on_open: RunOnOpen,
     ) -> PopupMenu {
         let on_rename_node = callbacks.on_rename_node.clone();
         let on_move_node = callbacks.on_move_node.clone();
+        let on_revert_node = callbacks.on_revert_node.clone();
+        let on_redo_revert_node = callbacks.on_redo_revert_node.clone();
+        let on_show_examples = callbacks.on_show_examples.clone();
+        let on_set_run_on_open = callbacks.on_set_run_on_open.clone();
         let on_delete_node = callbacks.on_delete_node.clone();
 
-        menu.item(
-            PopupMenuItem::new("Rename")
-                .icon(IconName::FilePen)
-                .on_click(move |_event, window, cx| {
-                    if let Some(ref handler) = on_rename_node {
-                        handler(collection_id, node_id, current_name.clone(), window, cx);
+        // Methods that change data only auto-send when that is allowed for them explicitly.
+        let (run_label, next_run_on_open) = match run_on_open {
+            RunOnOpen::Off if method.is_safe() => {
+                (SharedString::from("Run on Open"), RunOnOpen::SafeMethods)
+            }
+            RunOnOpen::Off => (
+                SharedString::from(format!("Run on Open, Allowing {}", method.as_str())),
+                RunOnOpen::AnyMethod,
+            ),
+            RunOnOpen::SafeMethods | RunOnOpen::AnyMethod => {
+                (SharedString::from("Stop Running on Open"), RunOnOpen::Off)
+            }
+        };
+
+        let menu = menu
+            .item(
+                PopupMenuItem::new("Rename")
+                    .icon(IconName::FilePen)
+                    .on_click(move |_event, window, cx| {
+                        if let Some(ref handler) = on_rename_node {
+                            handler(collection_id, node_id, current_name.clone(), window, cx);
+                        }
+                    }),
+            )
+            .item(PopupMenuItem::new("Move").icon(IconName::Replace).on_click(
+                move |_event, window, cx| {
+                    if let Some(ref handler) = on_move_node {
+                        handler(collection_id, node_id, window, cx);
                     }
-                }),
-        )
-        .item(PopupMenuItem::new("Move").icon(IconName::Replace).on_click(
-            move |_event, window, cx| {
-                if let Some(ref handler) = on_move_node {
-                    handler(collection_id, node_id, window, cx);
-                }
-            },
-        ))
-        .item(PopupMenuItem::new("Delete").icon(IconName::Trash).on_click(
+                },
+            ))
+            .item(PopupMenuItem::new(run_label).icon(IconName::Play).on_click(
+                move |_event, window, cx| {
+                    if let Some(ref handler) = on_set_run_on_open {
+                        handler(collection_id, node_id, next_run_on_open, window, cx);
+                    }
+                },
+            ));
+        let menu = if version_count > 0 {
+            menu.item(
+                PopupMenuItem::new("Revert to Previous Version")
+                    .icon(IconName::History)
+                    .on_click(move |_event, window, cx| {
+                        if let Some(ref handler) = on_revert_node {
+                            handler(collection_id, node_id, window, cx);
+                        }
+                    }),
+            )
+        } else {
+            menu
+        };
+        let menu = if redo_count > 0 {
+            menu.item(
+                PopupMenuItem::new("Redo Revert")
+                    .icon(IconName::History)
+                    .on_click(move |_event, window, cx| {
+                        if let Some(ref handler) = on_redo_revert_node {
+                            handler(collection_id, node_id, window, cx);
+                        }
+                    }),
+            )
+        } else {
+            menu
+        };
+        let menu = if example_count > 0 {
+            menu.item(
+                PopupMenuItem::new(format!("Examples ({example_count})"))
+                    .icon(IconName::FileText)
+                    .on_click(move |_event, window, cx| {
+                        if let Some(ref handler) = on_show_examples {
+                            handler(collection_id, node_id, window, cx);
+                        }
+                    }),
+            )
+        } else {
+            menu
+        };
+        menu.item(PopupMenuItem::new("Delete").icon(IconName::Trash).on_click(
             move |_event, window, cx| {
                 if let Some(ref handler) = on_delete_node {
                     handler(collection_id, node_id, window, cx);
@@ -568,6 +775,28 @@ impl CollectionsPanel {
             .into_any_element()
     }
 
+    /// Move a dropped request into `destination`, before `before_node_id` when given.
+    fn drop_item(
+        collections: &Entity<CollectionsEntity>,
+        dragged: &DraggedCollectionItem,
+        destination: CollectionDestination,
+        before_node_id: Option<Uuid>,
+        cx: &mut App,
+    ) {
+        // Requests have no descendants, so this only fails when the target vanished mid-drag.
+        collections
+            .update(cx, |collections, cx| {
+                collections.move_item_between_collections(
+                    dragged.collection_id,
+                    dragged.item_id,
+                    destination,
+                    before_node_id,
+                    cx,
+                )
+            })
+            .ok();
+    }
+
     fn wrap_with_row_context_menu(
         window: &mut Window,
         cx: &mut App,
@@ -657,7 +886,9 @@ impl CollectionsPanel {
         request_count: usize,
         theme: &gpui_component::theme::ThemeColor,
         callbacks: &PanelCallbacks,
+        collections: &Entity<CollectionsEntity>,
     ) -> AnyElement {
+        let collections = collections.clone();
         let group_id = SharedString::from(format!("collection-row-{collection_id}"));
         let action_id = SharedString::from(format!("collection-actions-{collection_id}"));
         let row_name = name.to_string();
@@ -686,6 +917,16 @@ impl CollectionsPanel {
                     handler(collection_id, window, cx);
                 }
             })
+            .on_drop(move |dragged: &DraggedCollectionItem, _, cx| {
+                let destination = CollectionDestination {
+                    collection_id,
+                    folder_id: None,
+                };
+                Self::drop_item(&collections, dragged, destination, None, cx);
+            })
+            .drag_over::<DraggedCollectionItem>(|style, _, _, cx| {
+                style.bg(cx.theme().primary.opacity(0.15))
+            })
             .child(Self::render_chevron(is_expanded, theme))
             .child(
                 div()
@@ -755,7 +996,9 @@ impl CollectionsPanel {
         depth: usize,
         theme: &gpui_component::theme::ThemeColor,
         callbacks: &PanelCallbacks,
+        collections: &Entity<CollectionsEntity>,
     ) -> AnyElement {
+        let collections = collections.clone();
         let group_id = SharedString::from(format!("folder-row-{collection_id}-{node_id}"));
         let action_id = SharedString::from(format!("folder-actions-{collection_id}-{node_id}"));
         let row_name = name.to_string();
@@ -785,6 +1028,16 @@ impl CollectionsPanel {
                     handler(collection_id, node_id, window, cx);
                 }
             })
+            .on_drop(move |dragged: &DraggedCollectionItem, _, cx| {
+                let destination = CollectionDestination {
+                    collection_id,
+                    folder_id: Some(node_id),
+                };
+                Self::drop_item(&collections, dragged, destination, None, cx);
+            })
+            .drag_over::<DraggedCollectionItem>(|style, _, _, cx| {
+                style.bg(cx.theme().primary.opacity(0.15))
+            })
             .child(Self::render_chevron(has_children && is_expanded, theme))
             .child(
                 div()
@@ -847,13 +1100,20 @@ impl CollectionsPanel {
         window: &mut Window,
         cx: &mut App,
         collection_id: Uuid,
+        parent_id: Option<Uuid>,
         request_id: Uuid,
         name: &str,
         method: HttpMethod,
+        version_count: usize,
+        redo_count: usize,
+        example_count: usize,
+        run_on_open: RunOnOpen,
         depth: usize,
         theme: &gpui_component::theme::ThemeColor,
         callbacks: &PanelCallbacks,
+        collections: &Entity<CollectionsEntity>,
     ) -> AnyElement {
+        let collections = collections.clone();
         let group_id = SharedString::from(format!("request-row-{collection_id}-{request_id}"));
         let action_id = SharedString::from(format!("request-actions-{collection_id}-{request_id}"));
         let m_color = method_color(&method, cx);
@@ -884,6 +1144,31 @@ impl CollectionsPanel {
                     handler(collection_id, request_id, window, cx);
                 }
             })
+            .on_drag(
+                DraggedCollectionItem {
+                    collection_id,
+                    item_id: request_id,
+                    name: name.to_string(),
+                    method,
+                },
+                |dragged, _, _, cx| {
+                    cx.new(|_| CollectionItemDragPreview {
+                        name: dragged.name.clone(),
+                        method: dragged.method,
+                    })
+                },
+            )
+            // Dropping onto a request places the dragged one just above it.
+            .on_drop(move |dragged: &DraggedCollectionItem, _, cx| {
+                let destination = CollectionDestination {
+                    collection_id,
+                    folder_id: parent_id,
+                };
+                Self::drop_item(&collections, dragged, destination, Some(request_id), cx);
+            })
+            .drag_over::<DraggedCollectionItem>(|style, _, _, cx| {
+                style.border_t_2().border_color(cx.theme().primary)
+            })
             .child(Self::render_method_badge(method_str, m_color))
             .child(
                 div()
@@ -895,6 +1180,13 @@ impl CollectionsPanel {
                     .text_ellipsis()
                     .child(name.to_string()),
             )
+            .when(run_on_open.allows(method), |row| {
+                row.child(
+                    Icon::new(IconName::Play)
+                        .size(px(10.0))
+                        .text_color(theme.muted_foreground.opacity(0.7)),
+                )
+            })
             .child(Self::render_action_button(
                 theme,
                 group_id,
@@ -908,6 +1200,11 @@ impl CollectionsPanel {
                         collection_id,
                         request_id,
                         action_name.clone(),
+                        method,
+                        version_count,
+                        redo_count,
+                        example_count,
+                        run_on_open,
                     )
                 },
             ))
@@ -920,6 +1217,11 @@ impl CollectionsPanel {
                 collection_id,
                 request_id,
                 context_name.clone(),
+                method,
+                version_count,
+                redo_count,
+                example_count,
+                run_on_open,
             )
         })
     }
@@ -931,6 +1233,7 @@ impl CollectionsPanel {
         depth: usize,
         theme: &gpui_component::theme::ThemeColor,
         callbacks: &PanelCallbacks,
+        collections: &Entity<CollectionsEntity>,
     ) -> AnyElement {
         match row {
             CollectionTreeRow::Collection {
@@ -947,6 +1250,7 @@ impl CollectionsPanel {
                 *request_count,
                 theme,
                 callbacks,
+                collections,
             ),
             CollectionTreeRow::Folder {
                 collection_id,
@@ -965,22 +1269,34 @@ impl CollectionsPanel {
                 depth,
                 theme,
                 callbacks,
+                collections,
             ),
             CollectionTreeRow::Request {
                 collection_id,
+                parent_id,
                 id,
                 name,
                 method,
+                version_count,
+                redo_count,
+                example_count,
+                run_on_open,
             } => Self::render_request_row(
                 window,
                 cx,
                 *collection_id,
+                *parent_id,
                 *id,
                 name,
                 *method,
+                *version_count,
+                *redo_count,
+                *example_count,
+                *run_on_open,
                 depth,
                 theme,
                 callbacks,
+                collections,
             ),
         }
     }
@@ -1053,6 +1369,7 @@ impl RenderOnce for CollectionsPanel {
         } else {
             let list_theme = theme.clone();
             let tree_callbacks = callbacks.clone();
+            let tree_collections = self.collections.clone();
             tree(&tree_state, move |index, entry, selected, window, cx| {
                 let row = tree_rows
                     .get(&entry.item().id)
@@ -1068,6 +1385,7 @@ impl RenderOnce for CollectionsPanel {
                         entry.depth(),
                         &list_theme,
                         &tree_callbacks,
+                        &tree_collections,
                     ))
             })
             .size_full()
@@ -1081,7 +1399,7 @@ impl RenderOnce for CollectionsPanel {
                     .ghost()
                     .xsmall()
                     .icon(Icon::new(IconName::FileUp).size(px(14.0)))
-                    .tooltip("Import a Postman collection or environment")
+                    .tooltip("Import a Postman collection or environment, or an OpenAPI spec")
                     .on_click(move |_, window, cx| handler(window, cx)),
             );
         }
@@ -0,0 +1,303 @@
+use gpui::prelude::*;
+use gpui::{AnyElement, App, IntoElement, SharedString, Styled, Window, div, px};
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::tooltip::Tooltip;
+use gpui_component::{ActiveTheme, Icon, Sizable};
+use std::rc::Rc;
+
+use crate::http::StoredCookie;
+use crate::icons::IconName;
+
+/// Sidebar list of the cookies stored for the active environment, grouped by domain.
+#[derive(IntoElement)]
+pub struct CookiesPanel {
+    cookies: Vec<StoredCookie>,
+    scope_label: Option<SharedString>,
+    on_delete: Option<Rc<dyn Fn(&StoredCookie, &mut Window, &mut App) + 'static>>,
+    on_clear: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
+}
+
+impl CookiesPanel {
+    /// `cookies` must already be ordered by domain, as `CookieJar::cookies` returns them.
+    pub fn new(cookies: Vec<StoredCookie>, scope_label: Option<SharedString>) -> Self {
+        Self {
+            cookies,
+            scope_label,
+            on_delete: None,
+            on_clear: None,
+        }
+    }
+
+    pub fn on_delete(mut self, f: impl Fn(&StoredCookie, &mut Window, &mut App) + 'static) -> Self {
+        self.on_delete = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_clear(mut self, f: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_clear = Some(Rc::new(f));
+        self
+    }
+
+    fn render_empty_state(theme: &gpui_component::theme::ThemeColor) -> AnyElement {
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .justify_center()
+            .h_full()
+            .py(px(48.0))
+            .gap(px(12.0))
+            .child(
+                Icon::new(IconName::Cookie)
+                    .size(px(40.0))
+                    .text_color(theme.muted_foreground.opacity(0.5)),
+            )
+            .child(
+                div()
+                    .text_color(theme.muted_foreground)
+                    .text_size(px(13.0))
+                    .child("No cookies stored"),
+            )
+            .into_any_element()
+    }
+
+    fn render_domain_header(
+        domain: &str,
+        count: usize,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> AnyElement {
+        div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap(px(6.0))
+            .px(px(12.0))
+            .pt(px(10.0))
+            .pb(px(4.0))
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .text_color(theme.muted_foreground)
+                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                    .text_size(px(11.0))
+                    .child(domain.to_string()),
+            )
+            .child(
+                div()
+                    .text_color(theme.muted_foreground)
+                    .text_size(px(10.0))
+                    .child(count.to_string()),
+            )
+            .into_any_element()
+    }
+
+    fn render_cookie_row(
+        index: usize,
+        cookie: StoredCookie,
+        theme: &gpui_component::theme::ThemeColor,
+        on_delete: Option<Rc<dyn Fn(&StoredCookie, &mut Window, &mut App) + 'static>>,
+    ) -> AnyElement {
+        let list_hover = theme.list_hover;
+        let expiry: SharedString = match cookie.expires {
+            Some(expires) => format!(
+                "Expires {}",
+                expires
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+            )
+            .into(),
+            None => "Session".into(),
+        };
+        let details: SharedString = format!("Path {} · {}", cookie.path, expiry).into();
+
+        let badge = |label: &'static str| {
+            div()
+                .flex_shrink_0()
+                .px(px(4.0))
+                .rounded(px(3.0))
+                .bg(theme.muted)
+                .text_color(theme.muted_foreground)
+                .text_size(px(9.0))
+                .child(label)
+        };
+
+        div()
+            .id(("cookie-row", index))
+            .group("cookie-row")
+            .relative()
+            .flex()
+            .flex_col()
+            .w_full()
+            .gap(px(2.0))
+            .px(px(12.0))
+            .py(px(5.0))
+            .rounded(px(6.0))
+            .hover(|el| el.bg(list_hover))
+            .tooltip(move |window, cx| Tooltip::new(details.clone()).build(window, cx))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(4.0))
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_color(theme.foreground)
+                            .font_weight(gpui::FontWeight::MEDIUM)
+                            .text_size(px(11.5))
+                            .child(cookie.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .font_family(theme.mono_font_family.clone())
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(11.0))
+                            .child(cookie.value.clone()),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(4.0))
+                    .when(cookie.secure, |el| el.child(badge("Secure")))
+                    .when(cookie.http_only, |el| el.child(badge("HttpOnly")))
+                    .when(!cookie.host_only, |el| el.child(badge("Subdomains")))
+                    .child(
+                        div()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(10.0))
+                            .child(expiry),
+                    ),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .right(px(8.0))
+                    .top_0()
+                    .bottom_0()
+                    .flex()
+                    .items_center()
+                    .opacity(0.0)
+                    .group_hover("cookie-row", |s| s.opacity(1.0))
+                    .child({
+                        let mut btn = Button::new(("delete-cookie", index))
+                            .ghost()
+                            .xsmall()
+                            .icon(Icon::new(IconName::Trash).size(px(14.0)))
+                            .tooltip("Delete Cookie");
+                        if let Some(handler) = on_delete {
+                            btn = btn.on_click(move |_, window, cx| {
+                                cx.stop_propagation();
+                                handler(&cookie, window, cx);
+                            });
+                        }
+                        btn
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+impl RenderOnce for CookiesPanel {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let is_empty = self.cookies.is_empty();
+
+        let content = if is_empty {
+            Self::render_empty_state(&theme)
+        } else {
+            let mut list = div()
+                .id("cookie-list")
+                .flex()
+                .flex_col()
+                .h_full()
+                .px(px(4.0))
+                .pb(px(8.0))
+                .overflow_y_scroll();
+            for (index, cookie) in self.cookies.iter().enumerate() {
+                if index == 0 || self.cookies[index - 1].domain != cookie.domain {
+                    let count = self.cookies[index..]
+                        .iter()
+                        .take_while(|other| other.domain == cookie.domain)
+                        .count();
+                    list = list.child(Self::render_domain_header(&cookie.domain, count, &theme));
+                }
+                list = list.child(Self::render_cookie_row(
+                    index,
+                    cookie.clone(),
+                    &theme,
+                    self.on_delete.clone(),
+                ));
+            }
+            list.into_any_element()
+        };
+
+        let mut clear_btn = div();
+        if let Some(on_clear) = self.on_clear.filter(|_| !is_empty) {
+            clear_btn = clear_btn.child(
+                Button::new("clear-cookies")
+                    .ghost()
+                    .xsmall()
+                    .icon(Icon::new(IconName::Trash).size(px(14.0)))
+                    .tooltip("Clear Cookies")
+                    .on_click(move |_, window, cx| on_clear(window, cx)),
+            );
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .h_full()
+            .w_full()
+            .bg(theme.sidebar)
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .justify_between()
+                    .px(px(12.0))
+                    .py(px(8.0))
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(6.0))
+                            .child(
+                                Icon::new(IconName::Cookie)
+                                    .size(px(14.0))
+                                    .text_color(theme.muted_foreground),
+                            )
+                            .child(
+                                div()
+                                    .text_color(theme.foreground)
+                                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                                    .text_size(px(13.0))
+                                    .child("Cookies"),
+                            ),
+                    )
+                    .child(clear_btn),
+            )
+            .child(
+                div()
+                    .px(px(12.0))
+                    .pb(px(6.0))
+                    .text_color(theme.muted_foreground)
+                    .text_size(px(11.0))
+                    .child(
+                        self.scope_label
+                            .unwrap_or_else(|| "No active environment".into()),
+                    ),
+            )
+            .child(div().flex_1().overflow_hidden().child(content))
+    }
+}
@@ -9,6 +9,7 @@ use gpui_component::checkbox::Checkbox;
 use gpui_component::input::{Input, InputState};
 
 use crate::icons::IconName;
+use crate::utils::keyboard_row_move;
 use gpui_component::ActiveTheme;
 
 use crate::completion::{
@@ -244,6 +245,7 @@ impl Render for FormDataEditor {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
         let this = cx.entity().clone();
+        let row_count = self.rows.len();
 
         div()
             .id("form-data-editor-container")
@@ -349,6 +351,7 @@ impl Render for FormDataEditor {
                         let this_toggle = this.clone();
                         let this_remove = this.clone();
                         let this_drop = this.clone();
+                        let this_move = this.clone();
                         let enabled = row.enabled;
 
                         div()
@@ -365,6 +368,16 @@ impl Render for FormDataEditor {
                             .border_color(theme.border.opacity(0.3))
                             .hover(|s| s.bg(theme.secondary.opacity(0.3)))
                             .when(!enabled, |el| el.opacity(0.5))
+                            .on_key_down(move |event, _, cx| {
+                                if let Some(to) =
+                                    keyboard_row_move(&event.keystroke, idx, row_count)
+                                {
+                                    cx.stop_propagation();
+                                    this_move.update(cx, |editor, cx| {
+                                        editor.move_row(idx, to, cx);
+                                    });
+                                }
+                            })
                             .on_drop(move |dragged: &DraggedRow, _, cx| {
                                 this_drop.update(cx, |editor, cx| {
                                     editor.move_row(dragged.index, idx, cx);
@@ -1,15 +1,18 @@
 use gpui::prelude::*;
 use gpui::{
-    App, Context, ElementId, Entity, FocusHandle, Focusable, IntoElement, Render, SharedString,
-    Styled, Window, div, px,
+    App, Context, ElementId, Entity, FocusHandle, Focusable, Global, IntoElement, Render,
+    SharedString, Styled, Window, div, px,
 };
 use gpui_component::Sizable;
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::checkbox::Checkbox;
 use gpui_component::input::{Input, InputState};
+use gpui_component::menu::{DropdownMenu, PopupMenuItem};
 
-use crate::entities::{Header, RequestEntity};
+use crate::actions::ManageHeaderPresets;
+use crate::entities::{Header, HeaderPreset, RequestEntity};
 use crate::icons::IconName;
+use crate::utils::keyboard_row_move;
 use gpui_component::ActiveTheme;
 
 use crate::completion::{
@@ -23,6 +26,12 @@ pub struct HeaderRow {
     pub enabled: bool,
 }
 
+/// Header presets offered in the toolbar menu, mirrored from `UiPreferences` by the main view.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderPresets(pub Vec<HeaderPreset>);
+
+impl Global for HeaderPresets {}
+
 #[derive(Clone)]
 struct DraggedHeader {
     index: usize,
@@ -275,19 +284,107 @@ impl HeaderEditor {
         cx.notify();
     }
 
+    /// Add the headers of `preset`, skipping names the request already has.
+    pub fn apply_preset(
+        &mut self,
+        preset: &HeaderPreset,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for (key, value) in &preset.headers {
+            let present = self.header_rows.iter().any(|row| {
+                row.key_input
+                    .read(cx)
+                    .text()
+                    .trim()
+                    .eq_ignore_ascii_case(key)
+            });
+            if !present {
+                self.add_header_with_value(key, value, window, cx);
+            }
+        }
+    }
+
     pub fn get_headers(&self, cx: &App) -> Vec<Header> {
         self.header_rows
             .iter()
-            .map(|row| Header {
-                key: row.key_input.read(cx).text().to_string(),
-                value: row.value_input.read(cx).text().to_string(),
-                enabled: row.enabled,
+            .filter_map(|row| {
+                header_from_row(
+                    row.key_input.read(cx).text().as_ref(),
+                    row.value_input.read(cx).text().as_ref(),
+                    row.enabled,
+                )
             })
-            .filter(|h| !h.key.is_empty())
             .collect()
     }
 }
 
+/// How headers taken from another request combine with the ones already in the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderMergeMode {
+    /// Overwrite headers of the same name and keep the rest. A name sent several times,
+    /// such as `Cookie`, keeps all of its incoming values.
+    Merge,
+    /// Drop the current headers entirely.
+    Replace,
+}
+
+/// `incoming` applied over `current`, matching header names without regard to case.
+pub fn merge_headers(
+    current: Vec<Header>,
+    incoming: &[Header],
+    mode: HeaderMergeMode,
+) -> Vec<Header> {
+    if mode == HeaderMergeMode::Replace {
+        return incoming.to_vec();
+    }
+    let is_incoming = |key: &str| {
+        incoming
+            .iter()
+            .any(|header| header.key.eq_ignore_ascii_case(key))
+    };
+    // Incoming headers take the place of the first current one with their name.
+    let mut placed: Vec<String> = Vec::new();
+    let mut headers = Vec::new();
+    for header in current {
+        if !is_incoming(&header.key) {
+            headers.push(header);
+        } else if !placed
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&header.key))
+        {
+            headers.extend(
+                incoming
+                    .iter()
+                    .filter(|incoming| incoming.key.eq_ignore_ascii_case(&header.key))
+                    .cloned(),
+            );
+            placed.push(header.key);
+        }
+    }
+    headers.extend(
+        incoming
+            .iter()
+            .filter(|header| {
+                !placed
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(&header.key))
+            })
+            .cloned(),
+    );
+    headers
+}
+
+/// Rows without a key are placeholders; an empty value is a real header (e.g. `X-Debug:`).
+fn header_from_row(key: &str, value: &str, enabled: bool) -> Option<Header> {
+    let key = key.trim();
+    (!key.is_empty()).then(|| Header {
+        key: key.to_string(),
+        value: value.to_string(),
+        enabled,
+    })
+}
+
 impl Focusable for HeaderEditor {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -298,8 +395,10 @@ impl Render for HeaderEditor {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.populate_pending_headers(window, cx);
 
+        let preserve_header_order = self.request.read(cx).preserve_header_order();
         let theme = cx.theme();
         let this = cx.entity().clone();
+        let row_count = self.header_rows.len();
 
         div()
             .id("header-editor-container")
@@ -334,6 +433,63 @@ impl Render for HeaderEditor {
                             .flex_row()
                             .items_center()
                             .gap(px(4.0))
+                            .child(
+                                Checkbox::new("preserve-header-order")
+                                    .label("Keep order")
+                                    .xsmall()
+                                    .checked(preserve_header_order)
+                                    .on_click({
+                                        let request = self.request.clone();
+                                        move |checked, _, cx| {
+                                            request.update(cx, |request, cx| {
+                                                request.set_preserve_header_order(*checked, cx);
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                Button::new("header-presets-btn")
+                                    .icon(IconName::CopyPlus)
+                                    .ghost()
+                                    .xsmall()
+                                    .tooltip("Header Presets")
+                                    .dropdown_menu({
+                                        let this = this.clone();
+                                        move |menu, _window, cx| {
+                                            let presets = cx
+                                                .try_global::<HeaderPresets>()
+                                                .map(|presets| presets.0.clone())
+                                                .unwrap_or_default();
+                                            let menu = if presets.is_empty() {
+                                                menu.label("No presets yet")
+                                            } else {
+                                                presets.into_iter().fold(menu, |menu, preset| {
+                                                    let this = this.clone();
+                                                    menu.item(
+                                                        PopupMenuItem::new(preset.name.clone())
+                                                            .on_click(move |_, window, cx| {
+                                                                this.update(cx, |editor, cx| {
+                                                                    editor.apply_preset(
+                                                                        &preset, window, cx,
+                                                                    );
+                                                                });
+                                                            }),
+                                                    )
+                                                })
+                                            };
+                                            menu.separator().item(
+                                                PopupMenuItem::new("Manage Presets…")
+                                                    .icon(IconName::FilePen)
+                                                    .on_click(|_, window, cx| {
+                                                        window.dispatch_action(
+                                                            Box::new(ManageHeaderPresets),
+                                                            cx,
+                                                        );
+                                                    }),
+                                            )
+                                        }
+                                    }),
+                            )
                             .child(
                                 Button::new("clear-all-headers-btn")
                                     .icon(IconName::Trash)
@@ -414,6 +570,7 @@ impl Render for HeaderEditor {
                         let this_toggle = this.clone();
                         let this_remove = this.clone();
                         let this_drop = this.clone();
+                        let this_move = this.clone();
                         let enabled = row.enabled;
 
                         div()
@@ -430,6 +587,16 @@ impl Render for HeaderEditor {
                             .border_color(theme.border.opacity(0.3))
                             .hover(|s| s.bg(theme.secondary.opacity(0.3)))
                             .when(!enabled, |el| el.opacity(0.5))
+                            .on_key_down(move |event, _, cx| {
+                                if let Some(to) =
+                                    keyboard_row_move(&event.keystroke, idx, row_count)
+                                {
+                                    cx.stop_propagation();
+                                    this_move.update(cx, |editor, cx| {
+                                        editor.move_header(idx, to, cx);
+                                    });
+                                }
+                            })
                             .on_drop(move |dragged: &DraggedHeader, _, cx| {
                                 this_drop.update(cx, |editor, cx| {
                                     editor.move_header(dragged.index, idx, cx);
@@ -645,3 +812,92 @@ impl Render for HeaderDragPreview {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{HeaderMergeMode, header_from_row, merge_headers};
+    use crate::entities::Header;
+
+    #[test]
+    fn keeps_headers_with_empty_values() {
+        let header = header_from_row("X-Debug", "", true).expect("header kept");
+        assert_eq!(header.key, "X-Debug");
+        assert_eq!(header.value, "");
+        assert!(header.enabled);
+    }
+
+    #[test]
+    fn drops_rows_without_a_key() {
+        assert!(header_from_row("", "value", true).is_none());
+        assert!(header_from_row("   ", "", false).is_none());
+        assert_eq!(
+            header_from_row(" X-Trace ", " abc ", false).map(|h| (h.key, h.value)),
+            Some(("X-Trace".to_string(), " abc ".to_string()))
+        );
+    }
+
+    #[test]
+    fn merging_overwrites_same_named_headers_and_replacing_drops_the_rest() {
+        let current = vec![
+            Header::new("Accept", "text/html"),
+            Header::new("X-Trace", "local"),
+        ];
+        let incoming = vec![
+            Header::new("authorization", "Bearer abc"),
+            Header::new("accept", "application/json"),
+        ];
+        let pairs = |headers: Vec<Header>| {
+            headers
+                .into_iter()
+                .map(|h| format!("{}: {}", h.key, h.value))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            pairs(merge_headers(
+                current.clone(),
+                &incoming,
+                HeaderMergeMode::Merge
+            )),
+            [
+                "accept: application/json",
+                "X-Trace: local",
+                "authorization: Bearer abc"
+            ]
+        );
+        assert_eq!(
+            pairs(merge_headers(current, &incoming, HeaderMergeMode::Replace)),
+            ["authorization: Bearer abc", "accept: application/json"]
+        );
+    }
+
+    #[test]
+    fn merging_keeps_every_incoming_header_of_a_repeated_name() {
+        let current = vec![
+            Header::new("Cookie", "stale=1"),
+            Header::new("X-Trace", "local"),
+            Header::new("cookie", "stale=2"),
+        ];
+        let incoming = vec![
+            Header::new("Cookie", "a=1"),
+            Header::new("Accept", "text/html"),
+            Header::new("Cookie", "b=2"),
+            Header::new("Accept", "application/json"),
+        ];
+        let pairs: Vec<_> = merge_headers(current, &incoming, HeaderMergeMode::Merge)
+            .into_iter()
+            .map(|h| format!("{}: {}", h.key, h.value))
+            .collect();
+
+        assert_eq!(
+            pairs,
+            [
+                "Cookie: a=1",
+                "Cookie: b=2",
+                "X-Trace: local",
+                "Accept: text/html",
+                "Accept: application/json"
+            ]
+        );
+    }
+}
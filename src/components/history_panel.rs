@@ -11,7 +11,8 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::entities::{
-    HistoryEntity, HistoryGroupKey, HistoryRow, HistoryRowEntry, SidebarLoadState, TimeGroup,
+    HistoryEntity, HistoryGroupKey, HistoryRow, HistoryRowEntry, LatencySummary, SidebarLoadState,
+    TimeGroup, format_duration_ms,
 };
 use crate::icons::IconName;
 use crate::theme::method_color;
@@ -39,6 +40,8 @@ pub struct HistoryPanel {
     filter: HistoryFilter,
     group_by: HistoryGroupBy,
     on_load_request: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
+    on_load_request_in_place: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
+    on_copy_headers: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_delete_entry: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_toggle_star: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     on_clear: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
@@ -61,6 +64,8 @@ impl HistoryPanel {
             filter: HistoryFilter::All,
             group_by: HistoryGroupBy::Time,
             on_load_request: None,
+            on_load_request_in_place: None,
+            on_copy_headers: None,
             on_delete_entry: None,
             on_toggle_star: None,
             on_clear: None,
@@ -84,6 +89,21 @@ impl HistoryPanel {
         self
     }
 
+    /// Load an entry over the active tab instead of a new one; bound to alt-click.
+    pub fn on_load_request_in_place(
+        mut self,
+        f: impl Fn(Uuid, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_load_request_in_place = Some(Rc::new(f));
+        self
+    }
+
+    /// Bring an entry's headers into the active tab, asking whether to merge or replace.
+    pub fn on_copy_headers(mut self, f: impl Fn(Uuid, &mut Window, &mut App) + 'static) -> Self {
+        self.on_copy_headers = Some(Rc::new(f));
+        self
+    }
+
     pub fn on_delete_entry(mut self, f: impl Fn(Uuid, &mut Window, &mut App) + 'static) -> Self {
         self.on_delete_entry = Some(Rc::new(f));
         self
@@ -205,6 +225,7 @@ impl HistoryPanel {
         domain: &str,
         is_collapsed: bool,
         count: usize,
+        durations: &[u64],
         theme: &gpui_component::theme::ThemeColor,
         history: Entity<HistoryEntity>,
     ) -> AnyElement {
@@ -254,6 +275,18 @@ impl HistoryPanel {
                     .text_size(px(11.0))
                     .child(format!("({})", count)),
             )
+            .when_some(LatencySummary::from_durations(durations), |row, summary| {
+                row.child(Self::render_latency_sparkline(durations, summary, theme))
+                    .when(durations.len() > 1, |row| {
+                        let label = format!(
+                            "Min {} · Median {} · Max {}",
+                            format_duration_ms(summary.min),
+                            format_duration_ms(summary.median),
+                            format_duration_ms(summary.max)
+                        );
+                        row.tooltip(move |window, cx| Tooltip::new(label.clone()).build(window, cx))
+                    })
+            })
             .on_click(move |_, _window, cx| {
                 history.update(cx, |history, cx| {
                     history.toggle_url_group_collapsed(&domain_for_click, cx);
@@ -262,12 +295,57 @@ impl HistoryPanel {
             .into_any_element()
     }
 
+    /// Bars scaled to the slowest of the group's recent requests, then the median. A single
+    /// request shows only its time.
+    fn render_latency_sparkline(
+        durations: &[u64],
+        summary: LatencySummary,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> AnyElement {
+        const BAR_MAX_HEIGHT: f32 = 14.0;
+
+        div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .flex_shrink_0()
+            .ml_auto()
+            .gap(px(6.0))
+            .when(durations.len() > 1, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_row()
+                        .items_end()
+                        .h(px(BAR_MAX_HEIGHT))
+                        .gap(px(1.0))
+                        .children(durations.iter().map(|&duration| {
+                            let ratio = duration as f32 / summary.max.max(1) as f32;
+                            div()
+                                .w(px(3.0))
+                                .h(px((ratio * BAR_MAX_HEIGHT).max(2.0)))
+                                .rounded(px(1.0))
+                                .bg(theme.primary.opacity(0.6))
+                        })),
+                )
+            })
+            .child(
+                div()
+                    .text_color(theme.muted_foreground)
+                    .text_size(px(10.0))
+                    .child(format_duration_ms(summary.median)),
+            )
+            .into_any_element()
+    }
+
     fn render_history_item(
         entry_key: usize,
         entry: &HistoryRowEntry,
         theme: &gpui_component::theme::ThemeColor,
         m_color: Hsla,
         on_load: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
+        on_load_in_place: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
+        on_copy_headers: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
         on_delete: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
         on_star: Option<Rc<dyn Fn(Uuid, &mut Window, &mut App) + 'static>>,
     ) -> AnyElement {
@@ -350,6 +428,35 @@ impl HistoryPanel {
                     .px(px(4.0))
                     .opacity(0.0)
                     .group_hover("history-item", |s| s.opacity(1.0).bg(list_hover))
+                    .child({
+                        let handler = on_load_in_place.clone();
+                        let mut btn = Button::new(("load-in-place", entry_key))
+                            .ghost()
+                            .xsmall()
+                            .icon(Icon::new(IconName::Replace).size(px(14.0)))
+                            .tooltip("Load into current tab (Alt-click)");
+                        if let Some(h) = handler {
+                            btn = btn.on_click(move |_, window, cx| {
+                                cx.stop_propagation();
+                                h(entry_id, window, cx);
+                            });
+                        }
+                        btn
+                    })
+                    .child({
+                        let mut btn = Button::new(("copy-headers", entry_key))
+                            .ghost()
+                            .xsmall()
+                            .icon(Icon::new(IconName::CopyPlus).size(px(14.0)))
+                            .tooltip("Use headers in current tab");
+                        if let Some(h) = on_copy_headers {
+                            btn = btn.on_click(move |_, window, cx| {
+                                cx.stop_propagation();
+                                h(entry_id, window, cx);
+                            });
+                        }
+                        btn
+                    })
                     .child({
                         let handler = on_star_clone.clone();
                         let mut btn = Button::new(("star", entry_key))
@@ -392,8 +499,13 @@ impl HistoryPanel {
                         btn
                     }),
             )
-            .on_click(move |_, window, cx| {
-                if let Some(ref handler) = on_load_clone {
+            .on_click(move |event, window, cx| {
+                let handler = if event.modifiers().alt {
+                    on_load_in_place.as_ref()
+                } else {
+                    on_load_clone.as_ref()
+                };
+                if let Some(handler) = handler {
                     handler(entry_id, window, cx);
                 }
             })
@@ -413,6 +525,8 @@ impl RenderOnce for HistoryPanel {
         let theme = cx.theme();
 
         let on_load = self.on_load_request.clone();
+        let on_load_in_place = self.on_load_request_in_place.clone();
+        let on_copy_headers = self.on_copy_headers.clone();
         let on_delete = self.on_delete_entry.clone();
         let on_star = self.on_toggle_star.clone();
 
@@ -473,11 +587,12 @@ impl RenderOnce for HistoryPanel {
                                 key: HistoryGroupKey::Url(domain),
                                 count,
                                 collapsed,
-                                ..
+                                durations,
                             } => Self::render_url_group_header(
                                 domain,
                                 *collapsed,
                                 *count,
+                                durations,
                                 &list_theme,
                                 history.clone(),
                             ),
@@ -487,6 +602,8 @@ impl RenderOnce for HistoryPanel {
                                 &list_theme,
                                 method_color(&entry.method, cx),
                                 on_load.clone(),
+                                on_load_in_place.clone(),
+                                on_copy_headers.clone(),
                                 on_delete.clone(),
                                 on_star.clone(),
                             ),
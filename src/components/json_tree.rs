@@ -0,0 +1,666 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use gpui::prelude::*;
+use gpui::{
+    Context, ElementId, IntoElement, ListHorizontalSizingBehavior, Pixels, Render, SharedString,
+    Styled, UniformListScrollHandle, Window, div, px, uniform_list,
+};
+use gpui_component::ActiveTheme;
+use gpui_component::Icon;
+use gpui_component::Sizable;
+use gpui_component::scroll::Scrollbar;
+use gpui_component::spinner::Spinner;
+use serde_json::Value;
+
+use crate::entities::ResponseTextSnapshot;
+use crate::icons::IconName;
+
+const ROW_HEIGHT: Pixels = px(22.0);
+const ROW_WIDTH: Pixels = px(4096.0);
+const INDENT: Pixels = px(16.0);
+/// Children listed per container before a "show more" row, so opening a huge array stays cheap.
+const CHILD_PAGE: usize = 500;
+const STRING_PREVIEW_CHARS: usize = 200;
+/// Characters of one line of an unescaped string drawn before it is cut.
+const LINE_PREVIEW_CHARS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NodeLabel {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NodePreview {
+    Object {
+        len: usize,
+        expanded: bool,
+    },
+    Array {
+        len: usize,
+        expanded: bool,
+    },
+    /// Quoted and escaped, cut to [`STRING_PREVIEW_CHARS`].
+    String(String),
+    /// Unescaped first line of a string; strings of several lines expand into them.
+    Text {
+        first_line: String,
+        lines: usize,
+        expanded: bool,
+    },
+    Number(String),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JsonTreeRowKind {
+    Node {
+        label: Option<NodeLabel>,
+        preview: NodePreview,
+    },
+    /// One line of an expanded unescaped string.
+    Line(String),
+    More {
+        remaining: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct JsonTreeRow {
+    depth: usize,
+    /// JSON pointer of the node, or of the container a "show more" row belongs to.
+    path: String,
+    kind: JsonTreeRowKind,
+}
+
+enum ParseState {
+    Parsing,
+    Ready(Arc<Value>),
+    Failed(String),
+}
+
+/// Collapsible tree of a JSON response body.
+///
+/// Only the rows of expanded nodes are materialized, and they are drawn through a
+/// `uniform_list`, so large documents cost what is on screen rather than their size.
+pub struct JsonTreeView {
+    state: ParseState,
+    /// JSON pointers of expanded containers; the root starts expanded.
+    expanded: HashSet<String>,
+    /// Children shown per container once "show more" was clicked.
+    shown_children: HashMap<String, usize>,
+    rows: Arc<Vec<JsonTreeRow>>,
+    scroll_handle: UniformListScrollHandle,
+    /// Show string values with their escapes decoded instead of as JSON literals.
+    unescape_strings: bool,
+}
+
+impl JsonTreeView {
+    /// Parse the body on a background thread; the tree shows a spinner until it is ready.
+    pub fn new(snapshot: ResponseTextSnapshot, cx: &mut Context<Self>) -> Self {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+            let _ = tx.send(serde_json::from_str::<Value>(&snapshot.raw_body()));
+        });
+        cx.spawn(async move |this, cx| {
+            let parsed = rx.await;
+            let _ = this.update(cx, |this, cx| {
+                this.state = match parsed {
+                    Ok(Ok(value)) => ParseState::Ready(Arc::new(value)),
+                    Ok(Err(error)) => ParseState::Failed(error.to_string()),
+                    Err(_) => ParseState::Failed("Parsing stopped unexpectedly".to_string()),
+                };
+                this.rebuild_rows();
+                cx.notify();
+            });
+        })
+        .detach();
+
+        Self {
+            state: ParseState::Parsing,
+            expanded: HashSet::from([String::new()]),
+            shown_children: HashMap::new(),
+            rows: Arc::new(Vec::new()),
+            scroll_handle: UniformListScrollHandle::new(),
+            unescape_strings: false,
+        }
+    }
+
+    pub fn with_unescaped_strings(mut self, unescape_strings: bool) -> Self {
+        self.unescape_strings = unescape_strings;
+        self
+    }
+
+    pub fn set_unescape_strings(&mut self, unescape_strings: bool, cx: &mut Context<Self>) {
+        if self.unescape_strings == unescape_strings {
+            return;
+        }
+        self.unescape_strings = unescape_strings;
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    fn rebuild_rows(&mut self) {
+        self.rows = Arc::new(match &self.state {
+            ParseState::Ready(root) => flatten_rows(
+                root,
+                &self.expanded,
+                &self.shown_children,
+                self.unescape_strings,
+            ),
+            ParseState::Parsing | ParseState::Failed(_) => Vec::new(),
+        });
+    }
+
+    fn toggle(&mut self, path: &str, cx: &mut Context<Self>) {
+        if !self.expanded.remove(path) {
+            self.expanded.insert(path.to_string());
+        }
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    fn show_more(&mut self, path: &str, cx: &mut Context<Self>) {
+        *self
+            .shown_children
+            .entry(path.to_string())
+            .or_insert(CHILD_PAGE) += CHILD_PAGE;
+        self.rebuild_rows();
+        cx.notify();
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn string_preview(text: &str) -> String {
+    let mut chars = text.char_indices();
+    match chars.nth(STRING_PREVIEW_CHARS) {
+        Some((cut, _)) => format!("{}…", serde_json::Value::from(&text[..cut])),
+        None => serde_json::Value::from(text).to_string(),
+    }
+}
+
+/// A line of a decoded string as drawn in one row: tabs widened, control characters
+/// that the row cannot show replaced, and cut to [`LINE_PREVIEW_CHARS`].
+fn line_preview(line: &str) -> String {
+    let mut preview = String::new();
+    for (index, ch) in line.chars().enumerate() {
+        if index == LINE_PREVIEW_CHARS {
+            preview.push('…');
+            break;
+        }
+        match ch {
+            '\t' => preview.push_str("    "),
+            '\r' => {}
+            ch if ch.is_control() => preview.push('\u{fffd}'),
+            ch => preview.push(ch),
+        }
+    }
+    preview
+}
+
+/// Visible rows of the tree: every node whose ancestors are all expanded, depth first.
+fn flatten_rows(
+    root: &Value,
+    expanded: &HashSet<String>,
+    shown_children: &HashMap<String, usize>,
+    unescape_strings: bool,
+) -> Vec<JsonTreeRow> {
+    let mut rows = Vec::new();
+    let options = FlattenOptions {
+        expanded,
+        shown_children,
+        unescape_strings,
+    };
+    push_rows(root, None, String::new(), 0, &options, &mut rows);
+    rows
+}
+
+struct FlattenOptions<'a> {
+    expanded: &'a HashSet<String>,
+    shown_children: &'a HashMap<String, usize>,
+    unescape_strings: bool,
+}
+
+fn push_rows(
+    value: &Value,
+    label: Option<NodeLabel>,
+    path: String,
+    depth: usize,
+    options: &FlattenOptions,
+    rows: &mut Vec<JsonTreeRow>,
+) {
+    let is_expanded = options.expanded.contains(&path);
+    let preview = match value {
+        Value::Object(map) => NodePreview::Object {
+            len: map.len(),
+            expanded: is_expanded,
+        },
+        Value::Array(items) => NodePreview::Array {
+            len: items.len(),
+            expanded: is_expanded,
+        },
+        Value::String(text) if options.unescape_strings => {
+            let lines = text.lines().count().max(1);
+            NodePreview::Text {
+                first_line: line_preview(text.lines().next().unwrap_or_default()),
+                lines,
+                expanded: is_expanded && lines > 1,
+            }
+        }
+        Value::String(text) => NodePreview::String(string_preview(text)),
+        Value::Number(number) => NodePreview::Number(number.to_string()),
+        Value::Bool(value) => NodePreview::Bool(*value),
+        Value::Null => NodePreview::Null,
+    };
+    rows.push(JsonTreeRow {
+        depth,
+        path: path.clone(),
+        kind: JsonTreeRowKind::Node { label, preview },
+    });
+    if !is_expanded {
+        return;
+    }
+
+    let limit = options
+        .shown_children
+        .get(&path)
+        .copied()
+        .unwrap_or(CHILD_PAGE);
+    let total = match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter().take(limit) {
+                let child_path = format!("{path}/{}", escape_pointer(key));
+                let label = Some(NodeLabel::Key(key.clone()));
+                push_rows(child, label, child_path, depth + 1, options, rows);
+            }
+            map.len()
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate().take(limit) {
+                let child_path = format!("{path}/{index}");
+                let label = Some(NodeLabel::Index(index));
+                push_rows(child, label, child_path, depth + 1, options, rows);
+            }
+            items.len()
+        }
+        Value::String(text) if options.unescape_strings => {
+            let lines = text.lines().count();
+            if lines < 2 {
+                return;
+            }
+            for line in text.lines().take(limit) {
+                rows.push(JsonTreeRow {
+                    depth: depth + 1,
+                    path: path.clone(),
+                    kind: JsonTreeRowKind::Line(line_preview(line)),
+                });
+            }
+            lines
+        }
+        _ => return,
+    };
+    if total > limit {
+        rows.push(JsonTreeRow {
+            depth: depth + 1,
+            path,
+            kind: JsonTreeRowKind::More {
+                remaining: total - limit,
+            },
+        });
+    }
+}
+
+impl Render for JsonTreeView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let placeholder = div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .h_full()
+            .items_center()
+            .justify_center()
+            .gap(px(8.0))
+            .bg(theme.muted)
+            .text_color(theme.muted_foreground)
+            .text_size(px(11.0));
+        match &self.state {
+            ParseState::Parsing => {
+                return placeholder
+                    .child(Spinner::new().small())
+                    .child("Parsing JSON...")
+                    .into_any_element();
+            }
+            ParseState::Failed(error) => {
+                return placeholder
+                    .child(format!("Body is not valid JSON: {error}"))
+                    .into_any_element();
+            }
+            ParseState::Ready(_) => {}
+        }
+
+        let view = cx.entity().clone();
+        let rows = self.rows.clone();
+        let scroll_handle = self.scroll_handle.clone();
+        let mono_font = theme.mono_font_family.clone();
+        let muted_color = theme.muted_foreground;
+        let key_color = theme.primary;
+        let string_color = theme.success;
+        let number_color = theme.info;
+        let bool_color = theme.warning;
+        let badge_bg = theme.secondary;
+        let hover_bg = theme.secondary.opacity(0.6);
+
+        div()
+            .id("json-tree-container")
+            .relative()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .h_full()
+            .overflow_hidden()
+            .bg(theme.muted)
+            .child(
+                uniform_list("json-tree-list", rows.len(), move |visible_range, _, _| {
+                    visible_range
+                        .map(|index| {
+                            let row = &rows[index];
+                            let path = row.path.clone();
+                            let view = view.clone();
+                            let base = div()
+                                .id(ElementId::from(SharedString::from(format!(
+                                    "json-tree-row-{index}"
+                                ))))
+                                .flex()
+                                .flex_row()
+                                .items_center()
+                                .gap(px(4.0))
+                                .w(ROW_WIDTH)
+                                .min_w(ROW_WIDTH)
+                                .h(ROW_HEIGHT)
+                                .pl(px(8.0) + INDENT * row.depth as f32)
+                                .font_family(mono_font.clone())
+                                .text_size(px(12.0))
+                                .whitespace_nowrap();
+
+                            let (label, preview) = match &row.kind {
+                                JsonTreeRowKind::More { remaining } => {
+                                    return base
+                                        .pl(px(28.0) + INDENT * row.depth as f32)
+                                        .text_color(key_color)
+                                        .cursor_pointer()
+                                        .hover(|style| style.bg(hover_bg))
+                                        .child(format!(
+                                            "Show {} more of {remaining} remaining",
+                                            CHILD_PAGE.min(*remaining)
+                                        ))
+                                        .on_click(move |_, _, cx| {
+                                            view.update(cx, |tree, cx| tree.show_more(&path, cx));
+                                        });
+                                }
+                                JsonTreeRowKind::Line(line) => {
+                                    return base
+                                        .pl(px(28.0) + INDENT * row.depth as f32)
+                                        .text_color(string_color)
+                                        .child(line.clone());
+                                }
+                                JsonTreeRowKind::Node { label, preview } => (label, preview),
+                            };
+
+                            let container = match preview {
+                                NodePreview::Object { len, expanded } => {
+                                    Some((format!("{{{len}}}"), *expanded))
+                                }
+                                NodePreview::Array { len, expanded } => {
+                                    Some((format!("[{len}]"), *expanded))
+                                }
+                                NodePreview::Text {
+                                    lines, expanded, ..
+                                } if *lines > 1 => Some((format!("{lines} lines"), *expanded)),
+                                _ => None,
+                            };
+                            let chevron = div().w(px(16.0)).flex().justify_center().when_some(
+                                container.as_ref(),
+                                |el, (_, expanded)| {
+                                    el.child(
+                                        Icon::new(if *expanded {
+                                            IconName::ChevronDown
+                                        } else {
+                                            IconName::ChevronRight
+                                        })
+                                        .size(px(12.0))
+                                        .text_color(muted_color),
+                                    )
+                                },
+                            );
+
+                            base.child(chevron)
+                                .when_some(label.as_ref(), |el, label| {
+                                    el.child(match label {
+                                        NodeLabel::Key(key) => {
+                                            div().text_color(key_color).child(format!("{key}:"))
+                                        }
+                                        NodeLabel::Index(index) => {
+                                            div().text_color(muted_color).child(format!("{index}:"))
+                                        }
+                                    })
+                                })
+                                .child(match preview {
+                                    NodePreview::Object { .. } | NodePreview::Array { .. } => {
+                                        let badge = container
+                                            .as_ref()
+                                            .map(|(badge, _)| badge.clone())
+                                            .unwrap_or_default();
+                                        div()
+                                            .px(px(5.0))
+                                            .rounded(px(4.0))
+                                            .bg(badge_bg)
+                                            .text_size(px(10.0))
+                                            .text_color(muted_color)
+                                            .child(badge)
+                                    }
+                                    NodePreview::String(text) => {
+                                        div().text_color(string_color).child(text.clone())
+                                    }
+                                    NodePreview::Text { first_line, .. } => div()
+                                        .flex()
+                                        .items_center()
+                                        .gap(px(4.0))
+                                        .child(
+                                            div()
+                                                .text_color(string_color)
+                                                .child(first_line.clone()),
+                                        )
+                                        .when_some(container.as_ref(), |el, (badge, _)| {
+                                            el.child(
+                                                div()
+                                                    .px(px(5.0))
+                                                    .rounded(px(4.0))
+                                                    .bg(badge_bg)
+                                                    .text_size(px(10.0))
+                                                    .text_color(muted_color)
+                                                    .child(badge.clone()),
+                                            )
+                                        }),
+                                    NodePreview::Number(number) => {
+                                        div().text_color(number_color).child(number.clone())
+                                    }
+                                    NodePreview::Bool(value) => {
+                                        div().text_color(bool_color).child(value.to_string())
+                                    }
+                                    NodePreview::Null => {
+                                        div().text_color(muted_color).child("null")
+                                    }
+                                })
+                                .when(container.is_some(), |el| {
+                                    el.cursor_pointer()
+                                        .hover(|style| style.bg(hover_bg))
+                                        .on_click(move |_, _, cx| {
+                                            view.update(cx, |tree, cx| tree.toggle(&path, cx));
+                                        })
+                                })
+                        })
+                        .collect()
+                })
+                .flex_1()
+                .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+                .track_scroll(&scroll_handle),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .top_0()
+                    .right_0()
+                    .bottom(px(8.0))
+                    .w(px(8.0))
+                    .child(Scrollbar::vertical(&scroll_handle)),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .left_0()
+                    .right(px(8.0))
+                    .bottom_0()
+                    .h(px(8.0))
+                    .child(Scrollbar::horizontal(&scroll_handle)),
+            )
+            .into_any_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CHILD_PAGE, JsonTreeRowKind, NodeLabel, NodePreview, flatten_rows, line_preview,
+        string_preview,
+    };
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn lists_only_expanded_nodes_and_pages_large_arrays() {
+        let root = serde_json::json!({
+            "user": {"name": "Ada", "tags/ids": [1, 2]},
+            "items": (0..CHILD_PAGE + 3).collect::<Vec<_>>(),
+            "ok": true,
+        });
+        let mut expanded = HashSet::from([String::new()]);
+        let rows = flatten_rows(&root, &expanded, &HashMap::new(), false);
+        assert_eq!(rows.len(), 4);
+        assert!(matches!(
+            &rows[0].kind,
+            JsonTreeRowKind::Node {
+                label: None,
+                preview: NodePreview::Object {
+                    len: 3,
+                    expanded: true
+                }
+            }
+        ));
+
+        expanded.insert("/user".to_string());
+        expanded.insert("/user/tags~1ids".to_string());
+        expanded.insert("/items".to_string());
+        let rows = flatten_rows(&root, &expanded, &HashMap::new(), false);
+        let user_paths: Vec<_> = rows
+            .iter()
+            .filter(|row| row.path.starts_with("/user"))
+            .map(|row| (row.depth, row.path.as_str()))
+            .collect();
+        assert_eq!(
+            user_paths,
+            [
+                (1, "/user"),
+                (2, "/user/name"),
+                (2, "/user/tags~1ids"),
+                (3, "/user/tags~1ids/0"),
+                (3, "/user/tags~1ids/1"),
+            ]
+        );
+        let more = rows
+            .iter()
+            .find(|row| matches!(row.kind, JsonTreeRowKind::More { .. }))
+            .unwrap();
+        assert_eq!(more.path, "/items");
+        assert_eq!(more.kind, JsonTreeRowKind::More { remaining: 3 });
+
+        let shown = HashMap::from([("/items".to_string(), CHILD_PAGE * 2)]);
+        let rows = flatten_rows(&root, &expanded, &shown, false);
+        assert!(
+            !rows
+                .iter()
+                .any(|row| matches!(row.kind, JsonTreeRowKind::More { .. }))
+        );
+        let last = format!("/items/{}", CHILD_PAGE + 2);
+        let last = rows.iter().find(|row| row.path == last).unwrap();
+        assert!(matches!(
+            &last.kind,
+            JsonTreeRowKind::Node {
+                label: Some(NodeLabel::Index(index)),
+                ..
+            } if *index == CHILD_PAGE + 2
+        ));
+    }
+
+    #[test]
+    fn unescaped_strings_expand_into_their_lines() {
+        let root: serde_json::Value = serde_json::from_str(
+            r#"{"message": "Traceback:\n\tat main()\n\u00e9t\u00e9", "short": "tab\there"}"#,
+        )
+        .unwrap();
+        let mut expanded = HashSet::from([String::new()]);
+        let rows = flatten_rows(&root, &expanded, &HashMap::new(), true);
+        assert_eq!(
+            rows[1].kind,
+            JsonTreeRowKind::Node {
+                label: Some(NodeLabel::Key("message".to_string())),
+                preview: NodePreview::Text {
+                    first_line: "Traceback:".to_string(),
+                    lines: 3,
+                    expanded: false,
+                },
+            }
+        );
+        assert!(matches!(
+            &rows[2].kind,
+            JsonTreeRowKind::Node {
+                preview: NodePreview::Text { first_line, lines: 1, .. },
+                ..
+            } if first_line == "tab    here"
+        ));
+
+        expanded.insert("/message".to_string());
+        let rows = flatten_rows(&root, &expanded, &HashMap::new(), true);
+        let lines: Vec<_> = rows
+            .iter()
+            .filter_map(|row| match &row.kind {
+                JsonTreeRowKind::Line(line) => Some((row.depth, line.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lines, [(2, "Traceback:"), (2, "    at main()"), (2, "été")]);
+
+        let escaped = flatten_rows(&root, &expanded, &HashMap::new(), false);
+        assert!(matches!(
+            &escaped[1].kind,
+            JsonTreeRowKind::Node { preview: NodePreview::String(text), .. }
+                if text == r#""Traceback:\n\tat main()\nété""#
+        ));
+        assert_eq!(line_preview("a\u{7}b"), "a\u{fffd}b");
+    }
+
+    #[test]
+    fn previews_strings_escaped_and_truncated() {
+        assert_eq!(string_preview("a\"b\n"), r#""a\"b\n""#);
+        let long = "é".repeat(300);
+        let preview = string_preview(&long);
+        assert!(preview.ends_with("\"…"));
+        assert_eq!(preview.chars().count(), 200 + 3);
+    }
+}
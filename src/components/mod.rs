@@ -3,11 +3,13 @@ pub mod audio_player;
 pub mod auth_editor;
 pub mod body_type_selector;
 pub mod collections_panel;
+pub mod cookies_panel;
 pub mod custom_dropdown;
 pub mod environment_panel;
 pub mod form_data_editor;
 pub mod header_editor;
 pub mod history_panel;
+pub mod json_tree;
 pub mod multipart_form_data_editor;
 pub mod params_editor;
 
@@ -21,6 +23,7 @@ pub mod url_bar;
 pub use app_sidebar::*;
 pub use auth_editor::*;
 pub use body_type_selector::*;
+pub use cookies_panel::*;
 pub use custom_dropdown::*;
 pub use environment_panel::*;
 pub use form_data_editor::*;
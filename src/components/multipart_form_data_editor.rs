@@ -1,15 +1,17 @@
 use gpui::prelude::*;
 use gpui::{
-    App, Context, ElementId, Entity, FocusHandle, Focusable, IntoElement, PathPromptOptions,
-    Render, SharedString, Styled, Window, div, px,
+    App, Context, ElementId, Entity, ExternalPaths, FocusHandle, Focusable, IntoElement,
+    PathPromptOptions, Render, SharedString, Styled, Window, div, px,
 };
 use gpui_component::Sizable;
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::checkbox::Checkbox;
 use gpui_component::input::{Input, InputState};
-use std::path::PathBuf;
+use gpui_component::tooltip::Tooltip;
+use std::path::{Path, PathBuf};
 
 use crate::icons::IconName;
+use crate::utils::{format_size, keyboard_row_move};
 use gpui_component::ActiveTheme;
 
 use crate::completion::{
@@ -20,6 +22,8 @@ pub struct MultipartFormRow {
     pub key_input: Entity<InputState>,
     pub value_input: Entity<InputState>,
     pub file_path: Option<PathBuf>,
+    /// Size of `file_path` when it was last selected; `None` when the file is missing.
+    pub file_size: Option<u64>,
     pub enabled: bool,
 }
 
@@ -77,6 +81,7 @@ impl MultipartFormDataEditor {
             key_input,
             value_input,
             file_path: None,
+            file_size: None,
             enabled: true,
         });
 
@@ -119,14 +124,57 @@ impl MultipartFormDataEditor {
         cx: &mut Context<Self>,
     ) {
         if let Some(row) = self.rows.get_mut(index) {
+            row.file_size = file_size(&path);
             row.file_path = Some(path);
             cx.notify();
         }
     }
 
+    /// Append an enabled row sending `path` as the `key` field.
+    pub fn add_file_row(
+        &mut self,
+        key: &str,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.add_row(window, cx);
+        let index = self.rows.len() - 1;
+        self.rows[index].key_input.update(cx, |input, cx| {
+            input.set_value(key.to_string(), window, cx);
+        });
+        self.set_file_for_row(index, path, window, cx);
+    }
+
+    /// Attach files dragged in from the OS: the first to the row at `index`, the rest (or all
+    /// of them without a row) as new rows. Rows without a key get `file` so they are sent.
+    fn attach_dropped_files(
+        &mut self,
+        index: Option<usize>,
+        paths: &[PathBuf],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut paths = paths.iter().filter(|path| path.is_file()).cloned();
+        if let Some(index) = index.filter(|&index| index < self.rows.len())
+            && let Some(path) = paths.next()
+        {
+            self.rows[index].key_input.update(cx, |input, cx| {
+                if input.text().to_string().trim().is_empty() {
+                    input.set_value("file", window, cx);
+                }
+            });
+            self.set_file_for_row(index, path, window, cx);
+        }
+        for path in paths {
+            self.add_file_row("file", path, window, cx);
+        }
+    }
+
     pub fn clear_file_for_row(&mut self, index: usize, cx: &mut Context<Self>) {
         if let Some(row) = self.rows.get_mut(index) {
             row.file_path = None;
+            row.file_size = None;
             cx.notify();
         }
     }
@@ -144,38 +192,16 @@ impl MultipartFormDataEditor {
     }
 
     pub fn get_form_data(&self, cx: &App) -> std::collections::HashMap<String, String> {
-        let mut map = std::collections::HashMap::new();
-        for field in self.get_fields(cx) {
-            if field.enabled && !field.key.is_empty() {
-                if let Some(path) = field.file_path {
-                    map.insert(field.key, format!("@{}", path.display()));
-                } else {
-                    map.insert(field.key, field.value);
-                }
-            }
-        }
-        map
+        self.get_fields(cx)
+            .into_iter()
+            .filter_map(form_data_entry)
+            .collect()
     }
 
     pub fn get_multipart_fields(&self, cx: &App) -> Vec<crate::entities::MultipartField> {
         self.get_fields(cx)
             .into_iter()
-            .filter(|f| f.enabled && !f.key.is_empty())
-            .map(|f| {
-                if let Some(path) = f.file_path {
-                    crate::entities::MultipartField {
-                        key: f.key,
-                        value: String::new(),
-                        file_path: Some(path.to_string_lossy().to_string()),
-                    }
-                } else {
-                    crate::entities::MultipartField {
-                        key: f.key,
-                        value: f.value,
-                        file_path: None,
-                    }
-                }
-            })
+            .filter_map(multipart_field)
             .collect()
     }
 
@@ -210,11 +236,13 @@ impl MultipartFormDataEditor {
             });
 
             let file_path = field.file_path.as_ref().map(PathBuf::from);
+            let file_size = file_path.as_deref().and_then(file_size);
 
             self.rows.push(MultipartFormRow {
                 key_input,
                 value_input,
                 file_path,
+                file_size,
                 enabled: true,
             });
         }
@@ -222,6 +250,18 @@ impl MultipartFormDataEditor {
         cx.notify();
     }
 
+    /// Bytes the enabled fields will upload, counting text values by their length.
+    fn payload_size(&self, cx: &App) -> u64 {
+        self.rows
+            .iter()
+            .filter(|row| row.enabled && !row.key_input.read(cx).text().is_empty())
+            .map(|row| match row.file_path {
+                Some(_) => row.file_size.unwrap_or(0),
+                None => row.value_input.read(cx).text().len() as u64,
+            })
+            .sum()
+    }
+
     fn pick_file_for_row(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
         let this = cx.entity().clone();
 
@@ -265,6 +305,46 @@ impl MultipartFormDataEditor {
     }
 }
 
+/// Key and value of a field in the urlencoded map, files written as `@path`; `None` for
+/// disabled rows and rows without a key.
+fn form_data_entry(field: MultipartFormField) -> Option<(String, String)> {
+    if !field.enabled || field.key.is_empty() {
+        return None;
+    }
+    let value = match field.file_path {
+        Some(path) => format!("@{}", path.display()),
+        None => field.value,
+    };
+    Some((field.key, value))
+}
+
+/// The part a field sends; `None` for disabled rows and rows without a key.
+fn multipart_field(field: MultipartFormField) -> Option<crate::entities::MultipartField> {
+    if !field.enabled || field.key.is_empty() {
+        return None;
+    }
+    Some(match field.file_path {
+        Some(path) => crate::entities::MultipartField {
+            key: field.key,
+            value: String::new(),
+            file_path: Some(path.to_string_lossy().to_string()),
+        },
+        None => crate::entities::MultipartField {
+            key: field.key,
+            value: field.value,
+            file_path: None,
+        },
+    })
+}
+
+/// Size of a regular file at `path`, or `None` when it is missing or not a file.
+pub(crate) fn file_size(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .ok()
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+}
+
 impl Focusable for MultipartFormDataEditor {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -273,8 +353,14 @@ impl Focusable for MultipartFormDataEditor {
 
 impl Render for MultipartFormDataEditor {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let payload_size = format_size(self.payload_size(cx));
+        let has_missing_file = self
+            .rows
+            .iter()
+            .any(|row| row.enabled && row.file_path.is_some() && row.file_size.is_none());
         let theme = cx.theme();
         let this = cx.entity().clone();
+        let row_count = self.rows.len();
 
         div()
             .id("multipart-form-data-editor-container")
@@ -298,10 +384,48 @@ impl Render for MultipartFormDataEditor {
                     .border_color(theme.border)
                     .child(
                         div()
-                            .text_color(theme.muted_foreground)
-                            .text_size(px(11.0))
-                            .font_weight(gpui::FontWeight::SEMIBOLD)
-                            .child("Form Data (Multipart)"),
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(8.0))
+                            .child(
+                                div()
+                                    .text_color(theme.muted_foreground)
+                                    .text_size(px(11.0))
+                                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                                    .child("Form Data (Multipart)"),
+                            )
+                            .when(!self.rows.is_empty(), |el| {
+                                el.child(
+                                    div()
+                                        .id("multipart-payload-size")
+                                        .flex()
+                                        .flex_row()
+                                        .items_center()
+                                        .gap(px(4.0))
+                                        .text_color(if has_missing_file {
+                                            theme.warning
+                                        } else {
+                                            theme.muted_foreground
+                                        })
+                                        .text_size(px(10.0))
+                                        .when(has_missing_file, |el| {
+                                            el.child(
+                                                gpui_component::Icon::new(IconName::TriangleAlert)
+                                                    .size(px(11.0)),
+                                            )
+                                        })
+                                        .child(format!("Total {}", payload_size))
+                                        .tooltip(move |window, cx| {
+                                            Tooltip::new(if has_missing_file {
+                                                "Upload size, excluding missing files"
+                                            } else {
+                                                "Upload size of enabled fields"
+                                            })
+                                            .build(window, cx)
+                                        }),
+                                )
+                            }),
                     )
                     .child(
                         div()
@@ -379,14 +503,26 @@ impl Render for MultipartFormDataEditor {
                     .flex()
                     .flex_col()
                     .overflow_y_scroll()
+                    .on_drop({
+                        let this = this.clone();
+                        move |paths: &ExternalPaths, window, cx| {
+                            this.update(cx, |editor, cx| {
+                                editor.attach_dropped_files(None, paths.paths(), window, cx);
+                            });
+                        }
+                    })
                     .children(self.rows.iter().enumerate().map(|(idx, row)| {
                         let this_toggle = this.clone();
                         let this_remove = this.clone();
                         let this_drop = this.clone();
+                        let this_move = this.clone();
+                        let this_file_drop = this.clone();
                         let this_pick_file = this.clone();
                         let this_clear_file = this.clone();
                         let enabled = row.enabled;
                         let has_file = row.file_path.is_some();
+                        let file_missing = has_file && row.file_size.is_none();
+                        let file_size_label = row.file_size.map(format_size);
                         let file_name = row
                             .file_path
                             .as_ref()
@@ -408,6 +544,16 @@ impl Render for MultipartFormDataEditor {
                             .border_color(theme.border.opacity(0.3))
                             .hover(|s| s.bg(theme.secondary.opacity(0.3)))
                             .when(!enabled, |el| el.opacity(0.5))
+                            .on_key_down(move |event, _, cx| {
+                                if let Some(to) =
+                                    keyboard_row_move(&event.keystroke, idx, row_count)
+                                {
+                                    cx.stop_propagation();
+                                    this_move.update(cx, |editor, cx| {
+                                        editor.move_row(idx, to, cx);
+                                    });
+                                }
+                            })
                             .on_drop(move |dragged: &DraggedMultipartRow, _, cx| {
                                 this_drop.update(cx, |editor, cx| {
                                     editor.move_row(dragged.index, idx, cx);
@@ -417,6 +563,22 @@ impl Render for MultipartFormDataEditor {
                                 let theme = cx.theme();
                                 style.bg(theme.primary.opacity(0.15))
                             })
+                            // Files from the OS are a different drag type, so they never
+                            // reorder rows.
+                            .on_drop(move |paths: &ExternalPaths, window, cx| {
+                                this_file_drop.update(cx, |editor, cx| {
+                                    editor.attach_dropped_files(
+                                        Some(idx),
+                                        paths.paths(),
+                                        window,
+                                        cx,
+                                    );
+                                });
+                            })
+                            .drag_over::<ExternalPaths>(|style, _, _, cx| {
+                                let theme = cx.theme();
+                                style.bg(theme.primary.opacity(0.15))
+                            })
                             .child(
                                 div()
                                     .id(ElementId::from(SharedString::from(format!(
@@ -504,11 +666,37 @@ impl Render for MultipartFormDataEditor {
                                                 .rounded(px(4.0))
                                                 .bg(theme.secondary.opacity(0.5))
                                                 .overflow_hidden()
-                                                .child(
-                                                    gpui_component::Icon::new(IconName::FilePen)
-                                                        .size(px(12.0))
-                                                        .text_color(theme.muted_foreground),
-                                                )
+                                                .child(if file_missing {
+                                                    div()
+                                                        .id(ElementId::from(SharedString::from(
+                                                            format!("missing-file-{}", idx),
+                                                        )))
+                                                        .child(
+                                                            gpui_component::Icon::new(
+                                                                IconName::TriangleAlert,
+                                                            )
+                                                            .size(px(12.0))
+                                                            .text_color(theme.warning),
+                                                        )
+                                                        .tooltip(|window, cx| {
+                                                            Tooltip::new(
+                                                                "File not found; pick it again",
+                                                            )
+                                                            .build(window, cx)
+                                                        })
+                                                } else {
+                                                    div()
+                                                        .id(ElementId::from(SharedString::from(
+                                                            format!("file-icon-{}", idx),
+                                                        )))
+                                                        .child(
+                                                            gpui_component::Icon::new(
+                                                                IconName::FilePen,
+                                                            )
+                                                            .size(px(12.0))
+                                                            .text_color(theme.muted_foreground),
+                                                        )
+                                                })
                                                 .child(
                                                     div()
                                                         .flex_1()
@@ -520,6 +708,15 @@ impl Render for MultipartFormDataEditor {
                                                             file_name.clone().unwrap_or_default(),
                                                         ),
                                                 )
+                                                .when_some(file_size_label.clone(), |el, size| {
+                                                    el.child(
+                                                        div()
+                                                            .flex_shrink_0()
+                                                            .text_size(px(10.0))
+                                                            .text_color(theme.muted_foreground)
+                                                            .child(size),
+                                                    )
+                                                })
                                                 .child(
                                                     Button::new(SharedString::from(format!(
                                                         "clear-file-{}",
@@ -598,7 +795,7 @@ impl Render for MultipartFormDataEditor {
                                 .py(px(40.0))
                                 .text_color(theme.muted_foreground.opacity(0.5))
                                 .text_size(px(12.0))
-                                .child("No form fields. Click + to add one."),
+                                .child("No form fields. Click + to add one, or drop files here."),
                         )
                     }),
             )
@@ -708,3 +905,49 @@ impl Render for MultipartDragPreview {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MultipartFormField, form_data_entry, multipart_field};
+    use std::path::PathBuf;
+
+    fn field(key: &str, value: &str, file: Option<&str>, enabled: bool) -> MultipartFormField {
+        MultipartFormField {
+            key: key.to_string(),
+            value: value.to_string(),
+            file_path: file.map(PathBuf::from),
+            enabled,
+        }
+    }
+
+    fn rows() -> Vec<MultipartFormField> {
+        vec![
+            field("name", "Ada", None, true),
+            field("draft", "yes", None, false),
+            field("avatar", "", Some("/tmp/avatar.png"), true),
+            field("backup", "", Some("/tmp/backup.zip"), false),
+            field("", "orphan", None, true),
+        ]
+    }
+
+    #[test]
+    fn disabled_rows_are_left_out_of_the_multipart_body() {
+        let parts: Vec<_> = rows().into_iter().filter_map(multipart_field).collect();
+        let sent: Vec<_> = parts
+            .iter()
+            .map(|part| (part.key.as_str(), part.file_path.as_deref()))
+            .collect();
+        assert_eq!(sent, [("name", None), ("avatar", Some("/tmp/avatar.png"))]);
+        assert_eq!(parts[0].value, "Ada");
+    }
+
+    #[test]
+    fn disabled_rows_are_left_out_of_the_form_map() {
+        let map: std::collections::HashMap<_, _> =
+            rows().into_iter().filter_map(form_data_entry).collect();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["name"], "Ada");
+        assert_eq!(map["avatar"], "@/tmp/avatar.png");
+        assert!(!map.contains_key("draft") && !map.contains_key("backup"));
+    }
+}
@@ -1,17 +1,18 @@
 use gpui::prelude::*;
 use gpui::{
-    App, Context, ElementId, Entity, FocusHandle, Focusable, IntoElement, Render, SharedString,
-    Styled, Window, div, px,
+    App, Context, ElementId, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, Render,
+    SharedString, Styled, Window, div, px,
 };
 use gpui_component::Sizable;
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::checkbox::Checkbox;
-use gpui_component::input::{Input, InputState};
+use gpui_component::input::{Input, InputEvent, InputState};
 
 use crate::icons::IconName;
 use gpui_component::ActiveTheme;
 
 use crate::completion::{CompletionContext, CompletionEngine, CompletionInput};
+use crate::entities::DisabledParam;
 
 pub struct ParamRow {
     pub key_input: Entity<InputState>,
@@ -35,6 +36,15 @@ struct DraggedParam {
     value: String,
 }
 
+/// Events emitted by the params editor
+#[derive(Clone, Debug)]
+pub enum ParamsEditorEvent {
+    /// A row was edited, toggled, added, removed or moved.
+    Changed,
+}
+
+impl EventEmitter<ParamsEditorEvent> for ParamsEditor {}
+
 /// Params editor
 pub struct ParamsEditor {
     param_rows: Vec<ParamRow>,
@@ -72,6 +82,14 @@ impl ParamsEditor {
             }
         });
         let description_input = cx.new(|cx| InputState::new(window, cx).placeholder("Description"));
+        for input in [&key_input, &value_input] {
+            cx.subscribe(input, |_, _, event: &InputEvent, cx| {
+                if matches!(event, InputEvent::Change) {
+                    cx.emit(ParamsEditorEvent::Changed);
+                }
+            })
+            .detach();
+        }
 
         self.param_rows.push(ParamRow {
             key_input,
@@ -80,6 +98,7 @@ impl ParamsEditor {
             enabled: true,
         });
 
+        cx.emit(ParamsEditorEvent::Changed);
         cx.notify();
     }
 
@@ -87,6 +106,7 @@ impl ParamsEditor {
     pub fn remove_param(&mut self, index: usize, cx: &mut Context<Self>) {
         if index < self.param_rows.len() {
             self.param_rows.remove(index);
+            cx.emit(ParamsEditorEvent::Changed);
             cx.notify();
         }
     }
@@ -94,6 +114,7 @@ impl ParamsEditor {
     /// Clear all param rows
     pub fn clear_all_params(&mut self, cx: &mut Context<Self>) {
         self.param_rows.clear();
+        cx.emit(ParamsEditorEvent::Changed);
         cx.notify();
     }
 
@@ -101,6 +122,7 @@ impl ParamsEditor {
     pub fn toggle_param(&mut self, index: usize, cx: &mut Context<Self>) {
         if let Some(row) = self.param_rows.get_mut(index) {
             row.enabled = !row.enabled;
+            cx.emit(ParamsEditorEvent::Changed);
             cx.notify();
         }
     }
@@ -112,6 +134,7 @@ impl ParamsEditor {
         }
         let row = self.param_rows.remove(from);
         self.param_rows.insert(to, row);
+        cx.emit(ParamsEditorEvent::Changed);
         cx.notify();
     }
 
@@ -127,27 +150,200 @@ impl ParamsEditor {
             .collect()
     }
 
-    /// Build query string from params
-    pub fn build_query_string(&self, cx: &App) -> String {
-        let params: Vec<String> = self
-            .get_params(cx)
+    /// Params that will be sent: enabled and named.
+    pub fn active_param_count(&self, cx: &App) -> usize {
+        self.param_rows
+            .iter()
+            .filter(|row| row.enabled && !row.key_input.read(cx).text().is_empty())
+            .count()
+    }
+
+    /// Key and value of every param that will be sent, in order.
+    pub fn active_pairs(&self, cx: &App) -> Vec<(String, String)> {
+        self.get_params(cx)
             .into_iter()
             .filter(|p| p.enabled && !p.key.is_empty())
-            .map(|p| {
-                if p.value.is_empty() {
-                    p.key
-                } else {
-                    format!("{}={}", p.key, p.value)
-                }
-            })
+            .map(|p| (p.key, p.value))
+            .collect()
+    }
+
+    /// Named params that are switched off, kept with the request but left out of the URL.
+    pub fn disabled_params(&self, cx: &App) -> Vec<DisabledParam> {
+        disabled_params_of(self.get_params(cx))
+    }
+
+    /// Put `params` back as switched-off rows at the positions they were saved at, as
+    /// restored from a saved request. Params without a position are appended.
+    pub fn add_disabled_params(
+        &mut self,
+        params: &[DisabledParam],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut params: Vec<&DisabledParam> = params.iter().collect();
+        // Lower positions first, so each insert lands where it was saved.
+        params.sort_by_key(|param| param.index.unwrap_or(usize::MAX));
+        for param in params {
+            self.add_param(window, cx);
+            let last = self.param_rows.len() - 1;
+            let position = param.index.map_or(last, |index| index.min(last));
+            if position != last {
+                let row = self.param_rows.remove(last);
+                self.param_rows.insert(position, row);
+            }
+            if let Some(row) = self.param_rows.get_mut(position) {
+                row.enabled = false;
+                row.key_input.update(cx, |state, cx| {
+                    state.set_value(param.key.clone(), window, cx)
+                });
+                row.value_input.update(cx, |state, cx| {
+                    state.set_value(param.value.clone(), window, cx)
+                });
+            }
+        }
+    }
+
+    /// Build query string from params
+    pub fn build_query_string(&self, cx: &App) -> String {
+        serialize_query(&self.active_pairs(cx))
+    }
+
+    /// Make the active rows match `pairs`, as parsed from the URL.
+    ///
+    /// Rows are updated in place so their descriptions survive; disabled and unnamed rows
+    /// are not part of the URL and are left alone.
+    pub fn set_params_from_query(
+        &mut self,
+        pairs: &[(String, String)],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let active: Vec<usize> = self
+            .param_rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.enabled && !row.key_input.read(cx).text().is_empty())
+            .map(|(index, _)| index)
             .collect();
+        for (&index, (key, value)) in active.iter().zip(pairs) {
+            let row = &self.param_rows[index];
+            for (input, text) in [(&row.key_input, key), (&row.value_input, value)] {
+                if input.read(cx).text().to_string() != *text {
+                    input.update(cx, |state, cx| state.set_value(text.clone(), window, cx));
+                }
+            }
+        }
+        for &index in active.iter().skip(pairs.len()).rev() {
+            self.param_rows.remove(index);
+        }
+        for (key, value) in pairs.iter().skip(active.len()) {
+            self.add_param(window, cx);
+            if let Some(row) = self.param_rows.last() {
+                row.key_input
+                    .update(cx, |state, cx| state.set_value(key.clone(), window, cx));
+                row.value_input
+                    .update(cx, |state, cx| state.set_value(value.clone(), window, cx));
+            }
+        }
+        cx.notify();
+    }
+}
 
-        if params.is_empty() {
-            String::new()
+/// The switched-off params among `params`, each with its row among the named ones. Unnamed
+/// rows are not kept, so they do not count towards the positions.
+fn disabled_params_of(params: Vec<QueryParam>) -> Vec<DisabledParam> {
+    params
+        .into_iter()
+        .filter(|p| !p.key.is_empty())
+        .enumerate()
+        .filter(|(_, p)| !p.enabled)
+        .map(|(index, p)| DisabledParam {
+            key: p.key,
+            value: p.value,
+            index: Some(index),
+        })
+        .collect()
+}
+
+/// The query of `url` as key/value pairs, split the same way `serialize_query` joins them.
+pub fn parse_query(url: &str) -> Vec<(String, String)> {
+    let without_fragment = url.split_once('#').map_or(url, |(before, _)| before);
+    let Some((_, query)) = without_fragment.split_once('?') else {
+        return Vec::new();
+    };
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (!key.is_empty()).then(|| (decode_component(key), decode_component(value)))
+        })
+        .collect()
+}
+
+/// `?k=v&flag` for `pairs`, or an empty string when there are none.
+pub fn serialize_query(pairs: &[(String, String)]) -> String {
+    let params: Vec<String> = pairs
+        .iter()
+        .map(|(key, value)| {
+            if value.is_empty() {
+                encode_component(key)
+            } else {
+                format!("{}={}", encode_component(key), encode_component(value))
+            }
+        })
+        .collect();
+
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    }
+}
+
+/// Percent-encodes the characters that would change how a query splits, plus spaces,
+/// controls and non-ASCII. `{{variable}}` references are left as typed.
+fn encode_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        if rest.starts_with("{{")
+            && let Some(end) = rest.find("}}")
+        {
+            encoded.push_str(&rest[..end + 2]);
+            rest = &rest[end + 2..];
+            continue;
+        }
+        let ch = rest.chars().next().unwrap_or_default();
+        if ch.is_ascii_graphic() && !matches!(ch, '&' | '=' | '#' | '+' | '%') {
+            encoded.push(ch);
         } else {
-            format!("?{}", params.join("&"))
+            let mut buf = [0; 4];
+            for byte in ch.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{byte:02X}"));
+            }
         }
+        rest = &rest[ch.len_utf8()..];
     }
+    encoded
+}
+
+/// Decodes `%XX` escapes, keeping the text as-is when they don't form valid UTF-8.
+fn decode_component(text: &str) -> String {
+    urlencoding::decode(text)
+        .map(|decoded| decoded.into_owned())
+        .unwrap_or_else(|_| text.to_string())
+}
+
+/// `url` with its query replaced by `query`, keeping any fragment.
+pub fn replace_query(url: &str, query: &str) -> String {
+    let (before_fragment, fragment) = match url.find('#') {
+        Some(index) => url.split_at(index),
+        None => (url, ""),
+    };
+    let base = before_fragment
+        .split_once('?')
+        .map_or(before_fragment, |(base, _)| base);
+    format!("{base}{query}{fragment}")
 }
 
 impl Focusable for ParamsEditor {
@@ -158,6 +354,8 @@ impl Focusable for ParamsEditor {
 
 impl Render for ParamsEditor {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let active_count = self.active_param_count(cx);
+        let has_rows = !self.param_rows.is_empty();
         let theme = cx.theme();
         let this = cx.entity().clone();
 
@@ -183,10 +381,28 @@ impl Render for ParamsEditor {
                     .border_color(theme.border)
                     .child(
                         div()
-                            .text_color(theme.muted_foreground)
-                            .text_size(px(11.0))
-                            .font_weight(gpui::FontWeight::SEMIBOLD)
-                            .child("Query Parameters"),
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(6.0))
+                            .child(
+                                div()
+                                    .text_color(theme.muted_foreground)
+                                    .text_size(px(11.0))
+                                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                                    .child("Query Parameters"),
+                            )
+                            .when(active_count > 0, |el| {
+                                el.child(
+                                    div()
+                                        .px(px(6.0))
+                                        .rounded(px(8.0))
+                                        .bg(theme.primary.opacity(0.15))
+                                        .text_color(theme.primary)
+                                        .text_size(px(10.0))
+                                        .child(format!("{active_count} active")),
+                                )
+                            }),
                     )
                     .child(
                         div()
@@ -200,6 +416,7 @@ impl Render for ParamsEditor {
                                     .ghost()
                                     .xsmall()
                                     .tooltip("Clear All")
+                                    .disabled(!has_rows)
                                     .on_click({
                                         let this = this.clone();
                                         move |_, _, cx| {
@@ -505,3 +722,86 @@ impl Render for ParamDragPreview {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryParam, disabled_params_of, parse_query, replace_query, serialize_query};
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_and_serializes_a_query_round_trip() {
+        let url = "https://api.example.com/users?page=1&sort=name&verbose#top";
+        let parsed = parse_query(url);
+        assert_eq!(
+            parsed,
+            pairs(&[("page", "1"), ("sort", "name"), ("verbose", "")])
+        );
+        assert_eq!(serialize_query(&parsed), "?page=1&sort=name&verbose");
+        assert_eq!(replace_query(url, &serialize_query(&parsed)), url);
+        assert_eq!(
+            parse_query("https://api.example.com/users?&=x&a=1"),
+            pairs(&[("a", "1")])
+        );
+        assert!(parse_query("https://api.example.com/users#a?b").is_empty());
+    }
+
+    #[test]
+    fn disabled_params_remember_their_row_among_the_named_ones() {
+        let param = |key: &str, enabled| QueryParam {
+            key: key.to_string(),
+            value: String::new(),
+            enabled,
+        };
+        let disabled = disabled_params_of(vec![
+            param("page", true),
+            param("", false),
+            param("debug", false),
+            param("sort", true),
+            param("trace", false),
+        ]);
+        let positions: Vec<(&str, Option<usize>)> = disabled
+            .iter()
+            .map(|param| (param.key.as_str(), param.index))
+            .collect();
+        assert_eq!(positions, vec![("debug", Some(1)), ("trace", Some(3))]);
+    }
+
+    #[test]
+    fn escapes_reserved_characters_round_trip() {
+        let original = pairs(&[
+            ("q", "a&b=c"),
+            ("tag", "#1"),
+            ("sum", "1+1 = 2"),
+            ("50%", "{{term}}"),
+        ]);
+        let query = serialize_query(&original);
+        assert_eq!(
+            query,
+            "?q=a%26b%3Dc&tag=%231&sum=1%2B1%20%3D%202&50%25={{term}}"
+        );
+        let url = format!("https://api.example.com/search{query}");
+        assert_eq!(parse_query(&url), original);
+    }
+
+    #[test]
+    fn replaces_only_the_query_portion() {
+        assert_eq!(
+            replace_query("https://api.example.com/users?page=1#top", "?page=2"),
+            "https://api.example.com/users?page=2#top"
+        );
+        assert_eq!(
+            replace_query("https://api.example.com/users?page=1", ""),
+            "https://api.example.com/users"
+        );
+        assert_eq!(
+            replace_query("{{base}}/users", "?q={{term}}"),
+            "{{base}}/users?q={{term}}"
+        );
+    }
+}
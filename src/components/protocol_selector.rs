@@ -1,6 +1,9 @@
 use gpui::prelude::*;
-use gpui::{App, IntoElement, Styled, Window, div, px};
+use gpui::{App, ElementId, IntoElement, SharedString, Styled, Window, div, px};
 use gpui_component::ActiveTheme;
+use std::rc::Rc;
+
+pub type OnProtocolSelectCallback = Rc<dyn Fn(ProtocolType, &mut Window, &mut App) + 'static>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ProtocolType {
@@ -22,18 +25,30 @@ impl ProtocolType {
     }
 
     fn is_available(self) -> bool {
-        matches!(self, Self::Rest)
+        matches!(self, Self::Rest | Self::WebSocket)
     }
 }
 
 #[derive(IntoElement)]
 pub struct ProtocolSelector {
     selected: ProtocolType,
+    on_select: Option<OnProtocolSelectCallback>,
 }
 
 impl ProtocolSelector {
     pub fn new(selected: ProtocolType) -> Self {
-        Self { selected }
+        Self {
+            selected,
+            on_select: None,
+        }
+    }
+
+    pub fn on_select(
+        mut self,
+        callback: impl Fn(ProtocolType, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_select = Some(Rc::new(callback));
+        self
     }
 }
 
@@ -57,8 +72,13 @@ impl RenderOnce for ProtocolSelector {
             .children(protocols.into_iter().map(|protocol| {
                 let is_selected = protocol == self.selected;
                 let is_available = protocol.is_available();
+                let on_select = self.on_select.clone();
 
                 div()
+                    .id(ElementId::from(SharedString::from(format!(
+                        "protocol-{}",
+                        protocol.label()
+                    ))))
                     .relative()
                     .flex()
                     .items_center()
@@ -89,6 +109,13 @@ impl RenderOnce for ProtocolSelector {
                         gpui::FontWeight::NORMAL
                     })
                     .child(protocol.label())
+                    .when(is_available && !is_selected, |style| {
+                        style.on_click(move |_, window, cx| {
+                            if let Some(callback) = on_select.as_ref() {
+                                callback(protocol, window, cx);
+                            }
+                        })
+                    })
                     .when(!is_available, |style| {
                         style.child(
                             div()
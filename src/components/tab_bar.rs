@@ -1,7 +1,8 @@
 use gpui::prelude::*;
 use gpui::{App, Entity, Hsla, IntoElement, ScrollHandle, SharedString, Styled, Window, div, px};
-use gpui_component::ActiveTheme;
 use gpui_component::menu::{ContextMenuExt, PopupMenuItem};
+use gpui_component::spinner::Spinner;
+use gpui_component::{ActiveTheme, Sizable};
 
 use crate::entities::HttpMethod;
 use crate::icons::IconName;
@@ -20,6 +21,8 @@ pub struct TabInfo {
     pub name: SharedString,
     pub icon: TabIcon,
     pub is_active: bool,
+    /// A request is in flight in this tab.
+    pub is_loading: bool,
 }
 
 impl TabInfo {
@@ -30,6 +33,7 @@ impl TabInfo {
             name: name.into(),
             icon,
             is_active: false,
+            is_loading: false,
         }
     }
 
@@ -47,6 +51,11 @@ impl TabInfo {
         self.is_active = true;
         self
     }
+
+    pub fn loading(mut self, is_loading: bool) -> Self {
+        self.is_loading = is_loading;
+        self
+    }
 }
 
 #[derive(IntoElement)]
@@ -126,8 +135,8 @@ impl RenderOnce for TabBar {
                     .text_color(theme.muted_foreground)
                     .text_size(px(14.0))
                     .hover(|style| style.bg(theme.muted).text_color(theme.secondary_foreground))
-                    .on_click(move |_, _, cx| {
-                        main_view_for_new.update(cx, |view, cx| view.new_tab(cx));
+                    .on_click(move |_, window, cx| {
+                        main_view_for_new.update(cx, |view, cx| view.open_new_tab(window, cx));
                     })
                     .child(IconName::Plus),
             )
@@ -182,6 +191,7 @@ impl RenderOnce for WorkspaceTab {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let theme = cx.theme();
         let is_active = self.info.is_active;
+        let is_loading = self.info.is_loading;
         let tab_id = self.info.id;
         let tab_index = self.info.index;
         let tab_name = self.info.name.clone();
@@ -236,6 +246,9 @@ impl RenderOnce for WorkspaceTab {
                 element.on_click(move |event, window, cx| callback(event, window, cx))
             })
             .child(icon_badge)
+            .when(is_loading, |element| {
+                element.child(Spinner::new().xsmall().color(theme.primary))
+            })
             .child(
                 div()
                     .max_w(px(180.0))
@@ -1,20 +1,25 @@
 use gpui::prelude::*;
 use gpui::{App, ClickEvent, Entity, IntoElement, Styled, Window, div, hsla, px};
 use gpui_component::ActiveTheme;
+use gpui_component::Icon;
 use gpui_component::button::{Button, ButtonCustomVariant, ButtonVariants, DropdownButton};
 use gpui_component::input::{Input, InputState};
 use gpui_component::menu::{PopupMenu, PopupMenuItem};
+use gpui_component::tooltip::Tooltip;
 use std::rc::Rc;
 
 use crate::completion::CompletionInput;
 use crate::components::{MethodDropdown, MethodDropdownState};
 use crate::entities::RequestEntity;
+use crate::http::{UrlScheme, has_http_scheme};
 use crate::icons::IconName;
 
 /// Callback type for Send button
 pub type OnSendCallback = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
 pub type OnCancelCallback = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
+pub type OnPreviewCallback = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
 pub type OnSaveToCollectionCallback = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
+pub type OnUpdateSavedRequestCallback = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
 
 /// URL Bar component
 #[derive(IntoElement)]
@@ -22,10 +27,16 @@ pub struct UrlBar {
     input_state: Entity<InputState>,
     method_dropdown: Option<Entity<MethodDropdownState>>,
     request: Option<Entity<RequestEntity>>,
+    default_scheme: UrlScheme,
+    is_insecure: bool,
+    body_warning: Option<String>,
+    is_dry_run: bool,
     is_loading: bool,
     on_send: Option<OnSendCallback>,
     on_cancel: Option<OnCancelCallback>,
+    on_preview: Option<OnPreviewCallback>,
     on_save_to_collection: Option<OnSaveToCollectionCallback>,
+    on_update_saved_request: Option<OnUpdateSavedRequestCallback>,
 }
 
 impl UrlBar {
@@ -34,10 +45,16 @@ impl UrlBar {
             input_state,
             method_dropdown: None,
             request: None,
+            default_scheme: UrlScheme::default(),
+            is_insecure: false,
+            body_warning: None,
+            is_dry_run: false,
             is_loading: false,
             on_send: None,
             on_cancel: None,
+            on_preview: None,
             on_save_to_collection: None,
+            on_update_saved_request: None,
         }
     }
 
@@ -51,6 +68,30 @@ impl UrlBar {
         self
     }
 
+    /// Scheme the send adds to a URL typed without one, shown in front of the input.
+    pub fn default_scheme(mut self, scheme: UrlScheme) -> Self {
+        self.default_scheme = scheme;
+        self
+    }
+
+    /// Warn that requests from here skip certificate verification.
+    pub fn insecure(mut self, insecure: bool) -> Self {
+        self.is_insecure = insecure;
+        self
+    }
+
+    /// Pre-send warning about the body, listed next to any problem with the URL.
+    pub fn body_warning(mut self, warning: Option<String>) -> Self {
+        self.body_warning = warning;
+        self
+    }
+
+    /// Label the send button so it is clear nothing will reach the network.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.is_dry_run = dry_run;
+        self
+    }
+
     pub fn loading(mut self, loading: bool) -> Self {
         self.is_loading = loading;
         self
@@ -72,6 +113,14 @@ impl UrlBar {
         self
     }
 
+    pub fn on_preview(
+        mut self,
+        callback: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_preview = Some(Rc::new(callback));
+        self
+    }
+
     pub fn on_save_to_collection(
         mut self,
         callback: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
@@ -79,6 +128,14 @@ impl UrlBar {
         self.on_save_to_collection = Some(Rc::new(callback));
         self
     }
+
+    pub fn on_update_saved_request(
+        mut self,
+        callback: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_update_saved_request = Some(Rc::new(callback));
+        self
+    }
 }
 
 impl RenderOnce for UrlBar {
@@ -87,7 +144,19 @@ impl RenderOnce for UrlBar {
         let is_loading = self.is_loading;
         let on_send = self.on_send;
         let on_cancel = self.on_cancel;
+        let on_preview = self.on_preview;
         let on_save_to_collection = self.on_save_to_collection;
+        let on_update_saved_request = self.on_update_saved_request;
+        let upload_fraction = self
+            .request
+            .as_ref()
+            .and_then(|request| request.read(cx).upload_fraction())
+            .filter(|_| is_loading);
+        let hint = url_hint(
+            &self.input_state.read(cx).text().to_string(),
+            self.default_scheme,
+        );
+        let warnings: Vec<String> = hint.problem.into_iter().chain(self.body_warning).collect();
         let send_variant = ButtonCustomVariant::new(cx)
             .color(hsla(168.0 / 360.0, 0.67, 0.47, 1.0))
             .foreground(theme.background)
@@ -96,7 +165,11 @@ impl RenderOnce for UrlBar {
             .active(hsla(168.0 / 360.0, 0.67, 0.41, 1.0));
 
         let primary_button = Button::new("send-request-primary")
-            .label(if is_loading { "Cancel" } else { "Send" })
+            .label(match (is_loading, self.is_dry_run) {
+                (true, _) => "Cancel",
+                (false, true) => "Dry Run",
+                (false, false) => "Send",
+            })
             .compact()
             .on_click(move |event, window, cx| {
                 if is_loading {
@@ -117,6 +190,24 @@ impl RenderOnce for UrlBar {
         }
         .dropdown_menu(move |menu: PopupMenu, _window, _cx| {
             let mut menu = menu;
+            if let Some(callback) = on_preview.clone() {
+                menu = menu.item(
+                    PopupMenuItem::new("Preview Request")
+                        .icon(IconName::Eye)
+                        .on_click(move |event, window, cx| {
+                            callback(event, window, cx);
+                        }),
+                );
+            }
+            if let Some(callback) = on_update_saved_request.clone() {
+                menu = menu.item(
+                    PopupMenuItem::new("Update Saved Request")
+                        .icon(IconName::FilePen)
+                        .on_click(move |event, window, cx| {
+                            callback(event, window, cx);
+                        }),
+                );
+            }
             if let Some(callback) = on_save_to_collection.clone() {
                 menu = menu.item(
                     PopupMenuItem::new("Save to Collection")
@@ -130,6 +221,7 @@ impl RenderOnce for UrlBar {
         });
 
         div()
+            .relative()
             .flex()
             .flex_row()
             .items_center()
@@ -138,6 +230,7 @@ impl RenderOnce for UrlBar {
             .h(px(40.0))
             .bg(theme.muted)
             .rounded(px(6.0))
+            .overflow_hidden()
             // Method dropdown trigger
             .when_some(
                 self.method_dropdown.zip(self.request),
@@ -159,13 +252,143 @@ impl RenderOnce for UrlBar {
                     .items_center()
                     .h_full()
                     .px(px(8.0))
+                    .when_some(hint.added_scheme, |el, prefix| {
+                        el.child(
+                            div()
+                                .id("url-scheme-hint")
+                                .flex_shrink_0()
+                                .text_size(px(13.0))
+                                .text_color(theme.muted_foreground)
+                                .child(prefix)
+                                .tooltip(move |window, cx| {
+                                    Tooltip::new(format!(
+                                        "No scheme in the URL, so {prefix} is added when sending"
+                                    ))
+                                    .build(window, cx)
+                                }),
+                        )
+                    })
                     .child(CompletionInput::new(
                         &self.input_state,
                         Input::new(&self.input_state)
                             .appearance(false) // Remove default styling
                             .size_full(),
-                    )),
+                    ))
+                    .when(!warnings.is_empty(), |el| {
+                        let warnings = warnings.join("\n");
+                        el.child(
+                            div()
+                                .id("url-problem-hint")
+                                .flex_shrink_0()
+                                .child(
+                                    Icon::new(IconName::TriangleAlert)
+                                        .size(px(14.0))
+                                        .text_color(theme.warning),
+                                )
+                                .tooltip(move |window, cx| {
+                                    Tooltip::new(warnings.clone()).build(window, cx)
+                                }),
+                        )
+                    }),
             )
+            .when_some(upload_fraction, |el, fraction| {
+                el.child(
+                    div()
+                        .flex_shrink_0()
+                        .px(px(6.0))
+                        .text_size(px(11.0))
+                        .text_color(theme.muted_foreground)
+                        .child(format!("Uploading {:.0}%", fraction * 100.0)),
+                )
+            })
+            .when(self.is_insecure, |el| {
+                el.child(
+                    div()
+                        .id("url-insecure-tls")
+                        .flex_shrink_0()
+                        .flex()
+                        .items_center()
+                        .gap(px(4.0))
+                        .px(px(6.0))
+                        .py(px(2.0))
+                        .rounded(px(4.0))
+                        .bg(theme.danger.opacity(0.15))
+                        .text_size(px(11.0))
+                        .text_color(theme.danger)
+                        .child(Icon::new(IconName::Unlock).size(px(12.0)))
+                        .child("Insecure")
+                        .tooltip(|window, cx| {
+                            Tooltip::new(
+                                "Certificate verification is off for the active environment",
+                            )
+                            .build(window, cx)
+                        }),
+                )
+            })
             .child(div().mr(px(4.0)).child(split_button))
+            .when_some(upload_fraction, |el, fraction| {
+                el.child(
+                    div()
+                        .absolute()
+                        .left_0()
+                        .bottom_0()
+                        .h(px(2.0))
+                        .w(gpui::relative(fraction))
+                        .bg(theme.accent),
+                )
+            })
+    }
+}
+
+/// What the URL bar points out about the typed URL before it is sent.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct UrlHint {
+    /// Scheme the send will add because the URL has none.
+    added_scheme: Option<&'static str>,
+    /// Why the URL will not parse.
+    problem: Option<String>,
+}
+
+/// Check a typed URL. Anything with `{{variables}}` is left alone, since its resolved
+/// value may well carry the scheme.
+fn url_hint(text: &str, scheme: UrlScheme) -> UrlHint {
+    let text = text.trim();
+    if text.is_empty() || text.contains("{{") {
+        return UrlHint::default();
+    }
+    UrlHint {
+        added_scheme: (!has_http_scheme(text)).then(|| scheme.prefix()),
+        problem: reqwest::Url::parse(&scheme.apply(text))
+            .err()
+            .map(|error| format!("Invalid URL: {error}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UrlHint, url_hint};
+    use crate::http::UrlScheme;
+
+    #[test]
+    fn hints_the_added_scheme_and_invalid_urls() {
+        assert_eq!(
+            url_hint("localhost:3000/items", UrlScheme::Http),
+            UrlHint {
+                added_scheme: Some("http://"),
+                problem: None,
+            }
+        );
+        assert_eq!(
+            url_hint("https://api.test", UrlScheme::Http),
+            UrlHint::default()
+        );
+        assert_eq!(
+            url_hint("{{base}}/items", UrlScheme::Https),
+            UrlHint::default()
+        );
+        assert_eq!(
+            url_hint("https://exa mple.com", UrlScheme::Https).problem,
+            Some("Invalid URL: invalid domain character".to_string())
+        );
     }
 }
@@ -0,0 +1,325 @@
+use base64::Engine as _;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{Header, RequestBody};
+
+/// Tokens are refreshed this long before they expire, so one never lapses mid-request.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::seconds(10);
+
+/// Authentication type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthType {
+    #[default]
+    None,
+    /// Use the default auth of the collection the request belongs to.
+    Inherit,
+    Basic,
+    Bearer,
+    ApiKey,
+    /// OAuth 2.0 client-credentials grant: a token fetched from the token URL.
+    #[serde(rename = "oauth2_client_credentials")]
+    OAuth2ClientCredentials,
+    /// AWS Signature Version 4, computed over the final request when it is sent.
+    #[serde(rename = "aws_sigv4")]
+    AwsSigV4,
+}
+
+impl AuthType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthType::None => "No Auth",
+            AuthType::Inherit => "Inherit from Collection",
+            AuthType::Basic => "Basic Auth",
+            AuthType::Bearer => "Bearer Token",
+            AuthType::ApiKey => "API Key",
+            AuthType::OAuth2ClientCredentials => "OAuth 2.0 Client Credentials",
+            AuthType::AwsSigV4 => "AWS Signature",
+        }
+    }
+
+    pub fn all() -> &'static [AuthType] {
+        &[
+            AuthType::None,
+            AuthType::Inherit,
+            AuthType::Basic,
+            AuthType::Bearer,
+            AuthType::ApiKey,
+            AuthType::OAuth2ClientCredentials,
+            AuthType::AwsSigV4,
+        ]
+    }
+}
+
+/// Authentication configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    pub auth_type: AuthType,
+    pub username: String,
+    pub password: String,
+    pub token: String,
+    pub api_key_name: String,
+    pub api_key_value: String,
+    pub api_key_in_header: bool, // true = header, false = query param
+    pub oauth_token_url: String,
+    pub oauth_client_id: String,
+    pub oauth_client_secret: String,
+    pub oauth_scope: String,
+    /// Last token fetched for this config; kept in memory only.
+    #[serde(skip)]
+    pub oauth_token: Option<OAuthToken>,
+    pub aws_access_key: String,
+    pub aws_secret_key: String,
+    pub aws_region: String,
+    pub aws_service: String,
+    pub aws_session_token: String,
+}
+
+/// An access token obtained from an OAuth 2.0 token endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthToken {
+    pub access_token: String,
+    /// `None` when the server sent no `expires_in`; such tokens are used until rejected.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl OAuthToken {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at - TOKEN_EXPIRY_MARGIN <= now)
+    }
+
+    /// Time left before the token expires, zero once it has.
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.expires_at
+            .map(|expires_at| (expires_at - now).max(Duration::zero()))
+    }
+}
+
+impl AuthConfig {
+    /// Generate Authorization header if applicable
+    pub fn to_header(&self) -> Option<(String, String)> {
+        match self.auth_type {
+            // Resolved against the owning collection before headers are built.
+            AuthType::None | AuthType::Inherit => None,
+            // The signature depends on the whole request, so the HTTP client adds it last.
+            AuthType::AwsSigV4 => None,
+            // Added as the request goes out, see `oauth_header`.
+            AuthType::OAuth2ClientCredentials => None,
+            AuthType::Basic => {
+                let credentials = format!("{}:{}", self.username, self.password);
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                Some(("Authorization".to_string(), format!("Basic {}", encoded)))
+            }
+            AuthType::Bearer => {
+                if self.token.is_empty() {
+                    None
+                } else {
+                    Some((
+                        "Authorization".to_string(),
+                        format!("Bearer {}", self.token),
+                    ))
+                }
+            }
+            AuthType::ApiKey => {
+                if self.api_key_in_header && !self.api_key_name.is_empty() {
+                    Some((self.api_key_name.clone(), self.api_key_value.clone()))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// The `Authorization` header carrying the fetched OAuth token while it is live. It is
+    /// added to the outgoing request only, so the token never reaches saved headers.
+    pub fn oauth_header(&self, now: DateTime<Utc>) -> Option<(String, String)> {
+        if self.auth_type != AuthType::OAuth2ClientCredentials {
+            return None;
+        }
+        self.oauth_token
+            .as_ref()
+            .filter(|token| !token.is_expired(now))
+            .map(|token| {
+                (
+                    "Authorization".to_string(),
+                    format!("Bearer {}", token.access_token),
+                )
+            })
+    }
+
+    /// Whether sending with this config first needs a token from the token URL.
+    pub fn needs_oauth_token(&self, now: DateTime<Utc>) -> bool {
+        self.auth_type == AuthType::OAuth2ClientCredentials
+            && !self.oauth_token_url.trim().is_empty()
+            && self
+                .oauth_token
+                .as_ref()
+                .is_none_or(|token| token.is_expired(now))
+    }
+
+    /// URL, headers and form body of the client-credentials token request, still templated.
+    pub fn client_credentials_request(&self) -> (String, Vec<Header>, RequestBody) {
+        let mut form = HashMap::from([
+            ("grant_type".to_string(), "client_credentials".to_string()),
+            ("client_id".to_string(), self.oauth_client_id.clone()),
+            (
+                "client_secret".to_string(),
+                self.oauth_client_secret.clone(),
+            ),
+        ]);
+        if !self.oauth_scope.trim().is_empty() {
+            form.insert("scope".to_string(), self.oauth_scope.clone());
+        }
+        (
+            self.oauth_token_url.trim().to_string(),
+            vec![Header::new("Accept", "application/json")],
+            RequestBody::FormData(form),
+        )
+    }
+
+    /// The config a request actually sends: `Inherit` takes the collection's default, and a
+    /// collection without one (or a request outside any collection) sends no auth.
+    pub fn resolve(&self, collection_auth: Option<&AuthConfig>) -> AuthConfig {
+        match self.auth_type {
+            AuthType::Inherit => collection_auth
+                .filter(|auth| auth.auth_type != AuthType::Inherit)
+                .cloned()
+                .unwrap_or_default(),
+            _ => self.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthConfig, AuthType, OAuthToken};
+    use crate::entities::RequestBody;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn basic_auth_generates_expected_header() {
+        let config = AuthConfig {
+            auth_type: AuthType::Basic,
+            username: "raunak".to_string(),
+            password: "secret".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.to_header(),
+            Some((
+                "Authorization".to_string(),
+                "Basic cmF1bmFrOnNlY3JldA==".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn bearer_auth_omits_empty_token() {
+        let empty = AuthConfig {
+            auth_type: AuthType::Bearer,
+            token: String::new(),
+            ..Default::default()
+        };
+        let filled = AuthConfig {
+            auth_type: AuthType::Bearer,
+            token: "abc123".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(empty.to_header(), None);
+        assert_eq!(
+            filled.to_header(),
+            Some(("Authorization".to_string(), "Bearer abc123".to_string()))
+        );
+    }
+
+    #[test]
+    fn api_key_only_generates_header_for_header_mode() {
+        let query_param = AuthConfig {
+            auth_type: AuthType::ApiKey,
+            api_key_name: "X-Api-Key".to_string(),
+            api_key_value: "secret".to_string(),
+            api_key_in_header: false,
+            ..Default::default()
+        };
+        let header = AuthConfig {
+            auth_type: AuthType::ApiKey,
+            api_key_name: "X-Api-Key".to_string(),
+            api_key_value: "secret".to_string(),
+            api_key_in_header: true,
+            ..Default::default()
+        };
+
+        assert_eq!(query_param.to_header(), None);
+        assert_eq!(
+            header.to_header(),
+            Some(("X-Api-Key".to_string(), "secret".to_string()))
+        );
+    }
+
+    #[test]
+    fn inherit_resolves_to_the_collection_default() {
+        let inherit = AuthConfig {
+            auth_type: AuthType::Inherit,
+            ..Default::default()
+        };
+        let collection = AuthConfig {
+            auth_type: AuthType::Bearer,
+            token: "shared".to_string(),
+            ..Default::default()
+        };
+        let own = AuthConfig {
+            auth_type: AuthType::Bearer,
+            token: "own".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(inherit.to_header(), None);
+        assert_eq!(inherit.resolve(Some(&collection)), collection);
+        assert_eq!(inherit.resolve(None).auth_type, AuthType::None);
+        assert_eq!(own.resolve(Some(&collection)), own);
+    }
+
+    #[test]
+    fn oauth_sends_only_unexpired_tokens() {
+        let now = Utc::now();
+        let mut config = AuthConfig {
+            auth_type: AuthType::OAuth2ClientCredentials,
+            oauth_token_url: "https://auth.example.com/token".to_string(),
+            oauth_client_id: "app".to_string(),
+            oauth_client_secret: "shh".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(config.oauth_header(now), None);
+        assert!(config.needs_oauth_token(now));
+
+        config.oauth_token = Some(OAuthToken {
+            access_token: "tok".to_string(),
+            expires_at: Some(now + Duration::seconds(3600)),
+        });
+        assert!(!config.needs_oauth_token(now));
+        assert_eq!(
+            config.oauth_header(now),
+            Some(("Authorization".to_string(), "Bearer tok".to_string()))
+        );
+        assert_eq!(config.to_header(), None);
+        assert_eq!(config.oauth_header(now + Duration::seconds(3601)), None);
+        assert!(config.needs_oauth_token(now + Duration::seconds(3595)));
+
+        let (url, _, body) = config.client_credentials_request();
+        assert_eq!(url, "https://auth.example.com/token");
+        let RequestBody::FormData(form) = body else {
+            panic!("token request should be a form");
+        };
+        assert_eq!(form["grant_type"], "client_credentials");
+        assert!(!form.contains_key("scope"));
+
+        let stored = serde_json::to_string(&config).unwrap();
+        assert!(!stored.contains("access_token"));
+    }
+}
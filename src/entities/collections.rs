@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use gpui::{Context, EventEmitter};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -9,19 +10,75 @@ use uuid::Uuid;
 use crate::importers::{ImportedCollection, ImportedNode};
 use crate::utils::{DebouncedJsonWriter, shared_tokio_runtime};
 
-use super::{RequestData, SidebarLoadState, default_workspace_id};
+use super::{
+    AuthConfig, HttpMethod, RequestData, ResponseData, SidebarLoadState, default_workspace_id,
+};
 
 const COLLECTIONS_STORAGE_VERSION: u32 = 2;
+/// `format` field that marks a file as a collection exported from setu.
+const COLLECTION_EXPORT_FORMAT: &str = "setu-collection";
+const COLLECTION_EXPORT_VERSION: u32 = 1;
 const SAVE_DEBOUNCE: Duration = Duration::from_secs(1);
+/// Previous versions kept per saved request; older ones are dropped first.
+pub const MAX_REQUEST_VERSIONS: usize = 5;
+/// Example responses kept per saved request; older ones are dropped first.
+pub const MAX_REQUEST_EXAMPLES: usize = 5;
 
 fn default_expanded() -> bool {
     true
 }
 
+/// Whether opening a saved request from the sidebar also sends it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOnOpen {
+    #[default]
+    Off,
+    /// Send on open only while the method is GET, HEAD or OPTIONS.
+    SafeMethods,
+    /// Send on open whatever the method, as explicitly allowed for this request.
+    AnyMethod,
+}
+
+impl RunOnOpen {
+    pub fn is_off(&self) -> bool {
+        *self == RunOnOpen::Off
+    }
+
+    pub fn allows(&self, method: HttpMethod) -> bool {
+        match self {
+            RunOnOpen::Off => false,
+            RunOnOpen::SafeMethods => method.is_safe(),
+            RunOnOpen::AnyMethod => true,
+        }
+    }
+}
+
+/// A request as it was before it was overwritten.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionRequestVersion {
+    pub request: RequestData,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub replaced_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionRequestNode {
     pub id: Uuid,
     pub request: RequestData,
+    /// Earlier versions of `request`, newest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<CollectionRequestVersion>,
+    /// Versions a revert replaced, most recently reverted first, ready to be restored.
+    /// Saving over the request clears them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reverted: Vec<CollectionRequestVersion>,
+    #[serde(default, skip_serializing_if = "RunOnOpen::is_off")]
+    pub run_on_open: RunOnOpen,
+    /// Responses the request returned, saved as examples, newest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<ResponseData>,
 }
 
 impl CollectionRequestNode {
@@ -29,11 +86,83 @@ impl CollectionRequestNode {
         Self {
             id: Uuid::new_v4(),
             request,
+            versions: Vec::new(),
+            reverted: Vec::new(),
+            run_on_open: RunOnOpen::Off,
+            examples: Vec::new(),
         }
     }
 
     fn from_legacy(id: Uuid, request: RequestData) -> Self {
-        Self { id, request }
+        Self {
+            id,
+            request,
+            versions: Vec::new(),
+            reverted: Vec::new(),
+            run_on_open: RunOnOpen::Off,
+            examples: Vec::new(),
+        }
+    }
+
+    /// Replace the stored request, keeping the old one as a revertible version.
+    pub fn overwrite(&mut self, request: RequestData, note: Option<String>) {
+        let note = note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        let previous = std::mem::replace(&mut self.request, request);
+        self.versions.insert(
+            0,
+            CollectionRequestVersion {
+                request: previous,
+                note,
+                replaced_at: Utc::now(),
+            },
+        );
+        self.versions.truncate(MAX_REQUEST_VERSIONS);
+        self.reverted.clear();
+    }
+
+    /// Keep `response` as the newest example of what this request returns.
+    pub fn add_example(&mut self, response: ResponseData) {
+        self.examples.insert(0, response);
+        self.examples.truncate(MAX_REQUEST_EXAMPLES);
+    }
+
+    /// Restore the most recent previous version. The current request is kept for
+    /// `redo_revert`.
+    pub fn revert_to_previous(&mut self) -> bool {
+        if self.versions.is_empty() {
+            return false;
+        }
+        let previous = self.versions.remove(0);
+        let current = std::mem::replace(&mut self.request, previous.request);
+        self.reverted.insert(
+            0,
+            CollectionRequestVersion {
+                request: current,
+                note: previous.note,
+                replaced_at: Utc::now(),
+            },
+        );
+        true
+    }
+
+    /// Undo the most recent revert, putting the version it replaced back in place.
+    pub fn redo_revert(&mut self) -> bool {
+        if self.reverted.is_empty() {
+            return false;
+        }
+        let reverted = self.reverted.remove(0);
+        let current = std::mem::replace(&mut self.request, reverted.request);
+        self.versions.insert(
+            0,
+            CollectionRequestVersion {
+                request: current,
+                note: reverted.note,
+                replaced_at: reverted.replaced_at,
+            },
+        );
+        true
     }
 
     pub fn display_name(&self) -> String {
@@ -121,6 +250,19 @@ impl CollectionNode {
         }
     }
 
+    pub fn folder_count(&self) -> usize {
+        match self {
+            Self::Request(_) => 0,
+            Self::Folder(folder) => {
+                1 + folder
+                    .children
+                    .iter()
+                    .map(CollectionNode::folder_count)
+                    .sum::<usize>()
+            }
+        }
+    }
+
     fn matches_query(&self, query: &str) -> bool {
         match self {
             Self::Folder(folder) => folder.name.to_lowercase().contains(query),
@@ -151,6 +293,26 @@ impl CollectionNode {
         }
     }
 
+    /// Give this node, its requests and everything below it new ids.
+    fn regenerate_ids(&mut self) {
+        match self {
+            Self::Folder(folder) => {
+                folder.id = Uuid::new_v4();
+                folder
+                    .children
+                    .iter_mut()
+                    .for_each(CollectionNode::regenerate_ids);
+            }
+            Self::Request(request) => {
+                request.id = Uuid::new_v4();
+                request.request.id = Uuid::new_v4();
+                for version in &mut request.versions {
+                    version.request.id = Uuid::new_v4();
+                }
+            }
+        }
+    }
+
     fn filtered_clone(&self, query: &str) -> Option<Self> {
         match self {
             Self::Request(request) => {
@@ -190,6 +352,9 @@ impl CollectionNode {
 pub struct Collection {
     pub id: Uuid,
     pub name: String,
+    /// Auth used by requests in this collection whose own auth is set to inherit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
     #[serde(default)]
     pub nodes: Vec<CollectionNode>,
     #[serde(default = "default_expanded")]
@@ -201,6 +366,7 @@ impl Collection {
         Self {
             id: Uuid::new_v4(),
             name: name.to_string(),
+            auth: None,
             nodes: Vec::new(),
             expanded: true,
         }
@@ -210,6 +376,44 @@ impl Collection {
         self.nodes.iter().map(CollectionNode::request_count).sum()
     }
 
+    pub fn folder_count(&self) -> usize {
+        self.nodes.iter().map(CollectionNode::folder_count).sum()
+    }
+
+    /// The collection as a standalone file that can be shared and imported again.
+    pub fn to_export_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&CollectionExport {
+            format: COLLECTION_EXPORT_FORMAT.to_string(),
+            version: COLLECTION_EXPORT_VERSION,
+            collection: self.clone(),
+        })
+    }
+
+    /// Read a file written by [`Collection::to_export_json`], or `None` when `contents`
+    /// is not a setu export at all.
+    pub fn from_export_json(contents: &str) -> Option<Result<Self, String>> {
+        let value = serde_json::from_str::<serde_json::Value>(contents).ok()?;
+        if value.get("format").and_then(serde_json::Value::as_str) != Some(COLLECTION_EXPORT_FORMAT)
+        {
+            return None;
+        }
+        Some(
+            serde_json::from_value::<CollectionExport>(value)
+                .map_err(|error| format!("Invalid setu collection export: {error}"))
+                .and_then(|export| {
+                    if export.version > COLLECTION_EXPORT_VERSION {
+                        Err(format!(
+                            "This collection was exported by a newer version of setu \
+                             (format version {})",
+                            export.version
+                        ))
+                    } else {
+                        Ok(export.collection)
+                    }
+                }),
+        )
+    }
+
     pub fn filtered_clone(&self, query: &str) -> Option<Self> {
         if query.is_empty() {
             return Some(self.clone());
@@ -245,6 +449,13 @@ struct CollectionsStore {
     workspaces: HashMap<Uuid, Vec<Collection>>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CollectionExport {
+    format: String,
+    version: u32,
+    collection: Collection,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct LegacyVersionedCollectionsStore {
     version: u32,
@@ -272,6 +483,7 @@ impl LegacyCollection {
         Collection {
             id: self.id,
             name: self.name,
+            auth: None,
             nodes: self
                 .items
                 .into_iter()
@@ -488,6 +700,7 @@ impl CollectionsEntity {
         let collection = Collection {
             id: Uuid::new_v4(),
             name: imported.name,
+            auth: None,
             nodes: imported
                 .nodes
                 .into_iter()
@@ -504,6 +717,22 @@ impl CollectionsEntity {
         id
     }
 
+    /// Add a collection read from an export, keeping its ids unless it is already here.
+    pub fn import_exported_collection(
+        &mut self,
+        mut collection: Collection,
+        cx: &mut Context<Self>,
+    ) -> Uuid {
+        with_unique_ids(&self.collections, &mut collection);
+        let id = collection.id;
+        self.collections.push(collection);
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::CollectionAdded(id));
+        cx.notify();
+        id
+    }
+
     pub fn remove_collection(&mut self, id: Uuid, cx: &mut Context<Self>) {
         if let Some(pos) = self.collections.iter().position(|c| c.id == id) {
             self.collections.remove(pos);
@@ -524,6 +753,22 @@ impl CollectionsEntity {
         }
     }
 
+    /// Set or clear the default auth that inheriting requests in the collection use.
+    pub fn set_collection_auth(
+        &mut self,
+        id: Uuid,
+        auth: Option<AuthConfig>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(collection) = self.collections.iter_mut().find(|c| c.id == id) {
+            collection.auth = auth;
+            self.bump_revision();
+            self.save_to_file();
+            cx.emit(CollectionsEvent::CollectionUpdated(id));
+            cx.notify();
+        }
+    }
+
     pub fn rename_node(
         &mut self,
         collection_id: Uuid,
@@ -561,6 +806,15 @@ impl CollectionsEntity {
         }
     }
 
+    pub fn expand_collection(&mut self, id: Uuid, cx: &mut Context<Self>) {
+        if self
+            .get_collection(id)
+            .is_some_and(|collection| !collection.expanded)
+        {
+            self.toggle_collection_expanded(id, cx);
+        }
+    }
+
     pub fn toggle_node_expanded(
         &mut self,
         collection_id: Uuid,
@@ -602,7 +856,7 @@ impl CollectionsEntity {
             return None;
         };
 
-        if insert_node(&mut collection.nodes, parent_folder_id, node).is_err() {
+        if insert_node(&mut collection.nodes, parent_folder_id, None, node).is_err() {
             return None;
         }
 
@@ -627,7 +881,7 @@ impl CollectionsEntity {
             return None;
         };
 
-        if insert_node(&mut collection.nodes, parent_folder_id, node).is_err() {
+        if insert_node(&mut collection.nodes, parent_folder_id, None, node).is_err() {
             return None;
         }
 
@@ -638,6 +892,142 @@ impl CollectionsEntity {
         Some(node_id)
     }
 
+    /// Overwrite a saved request, recording the previous version with an optional note.
+    pub fn update_request_node(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        request: RequestData,
+        note: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(node) = self.request_node_mut(collection_id, node_id) else {
+            return false;
+        };
+        node.overwrite(request, note);
+
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::CollectionUpdated(collection_id));
+        cx.notify();
+        true
+    }
+
+    pub fn add_request_example(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        response: ResponseData,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(node) = self.request_node_mut(collection_id, node_id) else {
+            return false;
+        };
+        node.add_example(response);
+
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::CollectionUpdated(collection_id));
+        cx.notify();
+        true
+    }
+
+    pub fn delete_request_example(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        index: usize,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(node) = self.request_node_mut(collection_id, node_id) else {
+            return false;
+        };
+        if index >= node.examples.len() {
+            return false;
+        }
+        node.examples.remove(index);
+
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::CollectionUpdated(collection_id));
+        cx.notify();
+        true
+    }
+
+    pub fn revert_request_node(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(node) = self.request_node_mut(collection_id, node_id) else {
+            return false;
+        };
+        if !node.revert_to_previous() {
+            return false;
+        }
+
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::CollectionUpdated(collection_id));
+        cx.notify();
+        true
+    }
+
+    pub fn redo_revert_request_node(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(node) = self.request_node_mut(collection_id, node_id) else {
+            return false;
+        };
+        if !node.redo_revert() {
+            return false;
+        }
+
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::CollectionUpdated(collection_id));
+        cx.notify();
+        true
+    }
+
+    pub fn set_run_on_open(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        run_on_open: RunOnOpen,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(node) = self.request_node_mut(collection_id, node_id) else {
+            return false;
+        };
+        node.run_on_open = run_on_open;
+
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::CollectionUpdated(collection_id));
+        cx.notify();
+        true
+    }
+
+    fn request_node_mut(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+    ) -> Option<&mut CollectionRequestNode> {
+        let collection = self
+            .collections
+            .iter_mut()
+            .find(|c| c.id == collection_id)?;
+        match find_node_mut(&mut collection.nodes, node_id)? {
+            CollectionNode::Request(request) => Some(request),
+            CollectionNode::Folder(_) => None,
+        }
+    }
+
     pub fn remove_node(
         &mut self,
         collection_id: Uuid,
@@ -673,6 +1063,7 @@ impl CollectionsEntity {
             node_id,
             target_collection_id,
             target_parent_folder_id,
+            None,
         )?;
 
         self.bump_revision();
@@ -686,6 +1077,46 @@ impl CollectionsEntity {
         Ok(())
     }
 
+    /// Move a dragged item into `destination`, just before `before_node_id` when that is
+    /// one of its children. Within one collection this reorders the items.
+    pub fn move_item_between_collections(
+        &mut self,
+        source_collection_id: Uuid,
+        item_id: Uuid,
+        destination: CollectionDestination,
+        before_node_id: Option<Uuid>,
+        cx: &mut Context<Self>,
+    ) -> Result<(), MoveNodeError> {
+        if before_node_id == Some(item_id) {
+            return Ok(());
+        }
+        move_node_between_collections(
+            &mut self.collections,
+            source_collection_id,
+            item_id,
+            destination.collection_id,
+            destination.folder_id,
+            before_node_id,
+        )?;
+
+        self.bump_revision();
+        self.save_to_file();
+        cx.emit(CollectionsEvent::NodeMoved {
+            source_collection_id,
+            target_collection_id: destination.collection_id,
+            node_id: item_id,
+        });
+        cx.notify();
+        Ok(())
+    }
+
+    /// The collection called `name`, ignoring case.
+    pub fn find_by_name(&self, name: &str) -> Option<&Collection> {
+        self.collections
+            .iter()
+            .find(|collection| collection.name.eq_ignore_ascii_case(name.trim()))
+    }
+
     pub fn get_collection(&self, id: Uuid) -> Option<&Collection> {
         self.collections.iter().find(|c| c.id == id)
     }
@@ -768,6 +1199,19 @@ impl CollectionsEntity {
 
 impl EventEmitter<CollectionsEvent> for CollectionsEntity {}
 
+/// Renumber `collection` when its id is already taken in `existing`, as when one export
+/// is imported twice. Every folder, request and version gets a new id too, so lookups by
+/// node id never find two nodes.
+fn with_unique_ids(existing: &[Collection], collection: &mut Collection) {
+    if existing.iter().any(|c| c.id == collection.id) {
+        collection.id = Uuid::new_v4();
+        collection
+            .nodes
+            .iter_mut()
+            .for_each(CollectionNode::regenerate_ids);
+    }
+}
+
 impl CollectionNode {
     fn from_imported_node(node: ImportedNode) -> Self {
         match node {
@@ -887,20 +1331,27 @@ fn find_folder_mut(
     None
 }
 
+/// Add `node` to the folder (or the collection root), just before the sibling
+/// `before_node_id` when it is there and at the end otherwise.
 fn insert_node(
     nodes: &mut Vec<CollectionNode>,
     parent_folder_id: Option<Uuid>,
+    before_node_id: Option<Uuid>,
     node: CollectionNode,
 ) -> Result<(), MoveNodeError> {
-    match parent_folder_id {
+    let siblings = match parent_folder_id {
         Some(folder_id) => {
             let Some(folder) = find_folder_mut(nodes.as_mut_slice(), folder_id) else {
                 return Err(MoveNodeError::TargetFolderNotFound);
             };
-            folder.children.push(node);
+            &mut folder.children
         }
-        None => nodes.push(node),
-    }
+        None => nodes,
+    };
+    let index = before_node_id
+        .and_then(|before| siblings.iter().position(|sibling| sibling.id() == before))
+        .unwrap_or(siblings.len());
+    siblings.insert(index, node);
 
     Ok(())
 }
@@ -927,6 +1378,7 @@ fn move_node_between_collections(
     node_id: Uuid,
     target_collection_id: Uuid,
     target_parent_folder_id: Option<Uuid>,
+    before_node_id: Option<Uuid>,
 ) -> Result<(), MoveNodeError> {
     let source_index = collections
         .iter()
@@ -967,7 +1419,12 @@ fn move_node_between_collections(
         let collection = &mut collections[source_index];
         let node =
             remove_node(&mut collection.nodes, node_id).ok_or(MoveNodeError::NodeNotFound)?;
-        insert_node(&mut collection.nodes, target_parent_folder_id, node)?;
+        insert_node(
+            &mut collection.nodes,
+            target_parent_folder_id,
+            before_node_id,
+            node,
+        )?;
         return Ok(());
     }
 
@@ -977,14 +1434,19 @@ fn move_node_between_collections(
     };
 
     let target_collection = &mut collections[target_index];
-    insert_node(&mut target_collection.nodes, target_parent_folder_id, node)?;
+    insert_node(
+        &mut target_collection.nodes,
+        target_parent_folder_id,
+        before_node_id,
+        node,
+    )?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::{Header, HttpMethod, RequestBody};
+    use crate::entities::{AuthType, Header, HttpMethod, RequestBody};
 
     fn sample_request(name: &str, url: &str) -> RequestData {
         RequestData {
@@ -994,6 +1456,14 @@ mod tests {
             method: HttpMethod::Post,
             headers: vec![Header::new("Content-Type", "application/json")],
             body: RequestBody::Json(r#"{"ok":true}"#.to_string()),
+            preserve_header_order: false,
+            inherit_auth: false,
+            assertions: String::new(),
+            pre_request_script: String::new(),
+            extractions: String::new(),
+            disabled_params: Vec::new(),
+            subprotocols: String::new(),
+            auto_save: None,
             is_sending: false,
         }
     }
@@ -1041,6 +1511,7 @@ mod tests {
         let collection = Collection {
             id: Uuid::new_v4(),
             name: "Workspace".to_string(),
+            auth: None,
             expanded: true,
             nodes: vec![CollectionNode::Folder(CollectionFolderNode {
                 id: Uuid::new_v4(),
@@ -1072,6 +1543,166 @@ mod tests {
         );
     }
 
+    #[test]
+    fn round_trips_collection_auth_and_inheriting_requests() {
+        let mut request = sample_request("Me", "https://example.com/me");
+        request.inherit_auth = true;
+        let mut collection = Collection::new("Workspace");
+        collection.auth = Some(AuthConfig {
+            auth_type: AuthType::Bearer,
+            token: "{{token}}".to_string(),
+            ..AuthConfig::default()
+        });
+        collection
+            .nodes
+            .push(CollectionNode::Request(CollectionRequestNode::new(request)));
+
+        let encoded = serde_json::to_string(&collection).expect("encode");
+        let decoded: Collection = serde_json::from_str(&encoded).expect("decode");
+
+        assert_eq!(decoded.auth, collection.auth);
+        assert!(
+            decoded.nodes[0]
+                .request()
+                .expect("request")
+                .request
+                .inherit_auth
+        );
+
+        collection.auth = None;
+        let encoded = serde_json::to_string(&collection).expect("encode");
+        assert!(!encoded.contains("\"auth\""));
+    }
+
+    #[test]
+    fn importing_an_export_twice_gives_the_copy_new_ids() {
+        let mut folder = CollectionFolderNode::new("Users");
+        folder
+            .children
+            .push(CollectionNode::Request(CollectionRequestNode::new(
+                sample_request("Me", "https://example.com/me"),
+            )));
+        let mut collection = Collection::new("Shared");
+        collection.nodes = vec![
+            CollectionNode::Folder(folder),
+            CollectionNode::Request(CollectionRequestNode::new(sample_request(
+                "Create User",
+                "https://example.com/users",
+            ))),
+        ];
+        let exported = collection.to_export_json().expect("export");
+        let import = || {
+            Collection::from_export_json(&exported)
+                .expect("recognised as an export")
+                .expect("import")
+        };
+
+        let mut collections = Vec::new();
+        for _ in 0..2 {
+            let mut imported = import();
+            with_unique_ids(&collections, &mut imported);
+            collections.push(imported);
+        }
+        assert_eq!(collections[0].id, collection.id);
+        assert_ne!(collections[1].id, collection.id);
+
+        fn ids(nodes: &[CollectionNode], out: &mut Vec<Uuid>) {
+            for node in nodes {
+                out.push(node.id());
+                match node {
+                    CollectionNode::Folder(folder) => ids(&folder.children, out),
+                    CollectionNode::Request(request) => out.push(request.request.id),
+                }
+            }
+        }
+        let mut all = Vec::new();
+        for collection in &collections {
+            ids(&collection.nodes, &mut all);
+        }
+        assert_eq!(all.len(), 10);
+        let unique: std::collections::HashSet<_> = all.iter().collect();
+        assert_eq!(unique.len(), all.len());
+        assert_eq!(collections[1].request_count(), 2);
+        assert_eq!(collections[1].folder_count(), 1);
+    }
+
+    #[test]
+    fn exported_collections_import_back_unchanged() {
+        let mut inner = sample_request("Me", "https://example.com/me");
+        inner.inherit_auth = true;
+        let mut collection = Collection::new("Shared");
+        collection.auth = Some(AuthConfig {
+            auth_type: AuthType::Bearer,
+            token: "{{token}}".to_string(),
+            ..AuthConfig::default()
+        });
+        let mut nested = CollectionFolderNode::new("Accounts");
+        nested
+            .children
+            .push(CollectionNode::Request(CollectionRequestNode::new(inner)));
+        let mut folder = CollectionFolderNode::new("Users");
+        folder.children.push(CollectionNode::Folder(nested));
+        collection.nodes = vec![
+            CollectionNode::Folder(folder),
+            CollectionNode::Request(CollectionRequestNode::new(sample_request(
+                "Create User",
+                "https://example.com/users",
+            ))),
+        ];
+
+        let exported = collection.to_export_json().expect("export");
+        let value: serde_json::Value = serde_json::from_str(&exported).expect("json");
+        assert_eq!(value["format"], COLLECTION_EXPORT_FORMAT);
+        assert_eq!(value["version"], COLLECTION_EXPORT_VERSION);
+
+        let imported = Collection::from_export_json(&exported)
+            .expect("recognised as an export")
+            .expect("import");
+        assert_eq!(
+            serde_json::to_value(&imported).expect("encode"),
+            serde_json::to_value(&collection).expect("encode")
+        );
+        assert_eq!(imported.folder_count(), 2);
+        assert_eq!(imported.request_count(), 2);
+
+        assert!(Collection::from_export_json(r#"{"info":{"name":"Postman"}}"#).is_none());
+        let newer = exported.replacen(
+            &format!("\"version\": {COLLECTION_EXPORT_VERSION}"),
+            "\"version\": 99",
+            1,
+        );
+        assert!(Collection::from_export_json(&newer).is_some_and(|result| result.is_err()));
+    }
+
+    #[test]
+    fn keeps_empty_value_headers_in_storage() {
+        let mut request = sample_request("Debug", "https://example.com/debug");
+        request.headers.push(Header::new("X-Debug", ""));
+        let collection = Collection {
+            id: Uuid::new_v4(),
+            name: "Workspace".to_string(),
+            auth: None,
+            expanded: true,
+            nodes: vec![CollectionNode::Request(CollectionRequestNode::new(request))],
+        };
+        let store = CollectionsStore {
+            version: COLLECTIONS_STORAGE_VERSION,
+            workspaces: HashMap::from([(default_workspace_id(), vec![collection])]),
+        };
+
+        let encoded = serde_json::to_string(&store).expect("encode");
+        let (workspaces, _) = deserialize_store(&encoded).expect("decode");
+        let headers = &workspaces[&default_workspace_id()][0].nodes[0]
+            .request()
+            .expect("request")
+            .request
+            .headers;
+
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[1].key, "X-Debug");
+        assert_eq!(headers[1].value, "");
+    }
+
     #[test]
     fn prevents_moving_folder_into_descendant() {
         let folder_a_id = Uuid::new_v4();
@@ -1080,6 +1711,7 @@ mod tests {
         let mut collections = vec![Collection {
             id: collection_id,
             name: "Workspace".to_string(),
+            auth: None,
             expanded: true,
             nodes: vec![CollectionNode::Folder(CollectionFolderNode {
                 id: folder_a_id,
@@ -1100,6 +1732,7 @@ mod tests {
             folder_a_id,
             collection_id,
             Some(folder_b_id),
+            None,
         );
 
         assert_eq!(result, Err(MoveNodeError::CannotMoveIntoDescendant));
@@ -1119,12 +1752,14 @@ mod tests {
             Collection {
                 id: source_collection_id,
                 name: "Source".to_string(),
+                auth: None,
                 expanded: true,
                 nodes: vec![request_node],
             },
             Collection {
                 id: target_collection_id,
                 name: "Target".to_string(),
+                auth: None,
                 expanded: true,
                 nodes: vec![],
             },
@@ -1136,6 +1771,7 @@ mod tests {
             request_node_id,
             target_collection_id,
             None,
+            None,
         )
         .expect("move succeeds");
 
@@ -1144,6 +1780,184 @@ mod tests {
         assert_eq!(collections[1].nodes[0].id(), request_node_id);
     }
 
+    #[test]
+    fn dropping_before_a_sibling_reorders_or_moves_the_request() {
+        let request = |name: &str| {
+            CollectionNode::Request(CollectionRequestNode::new(sample_request(
+                name,
+                "https://example.com",
+            )))
+        };
+        let (a, b, c, x) = (request("A"), request("B"), request("C"), request("X"));
+        let (a_id, b_id, c_id, x_id) = (a.id(), b.id(), c.id(), x.id());
+        let (source_id, target_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let collection = |id, nodes| Collection {
+            id,
+            name: "Collection".to_string(),
+            auth: None,
+            expanded: true,
+            nodes,
+        };
+        let mut collections = vec![
+            collection(source_id, vec![a, b, c]),
+            collection(target_id, vec![x]),
+        ];
+        let ids = |collection: &Collection| -> Vec<Uuid> {
+            collection.nodes.iter().map(CollectionNode::id).collect()
+        };
+
+        move_node_between_collections(
+            &mut collections,
+            source_id,
+            c_id,
+            source_id,
+            None,
+            Some(a_id),
+        )
+        .expect("reorder succeeds");
+        assert_eq!(ids(&collections[0]), vec![c_id, a_id, b_id]);
+
+        move_node_between_collections(
+            &mut collections,
+            source_id,
+            a_id,
+            target_id,
+            None,
+            Some(x_id),
+        )
+        .expect("move succeeds");
+        assert_eq!(ids(&collections[0]), vec![c_id, b_id]);
+        assert_eq!(ids(&collections[1]), vec![a_id, x_id]);
+    }
+
+    #[test]
+    fn overwrite_keeps_a_bounded_revertible_history() {
+        let mut node = CollectionRequestNode::new(sample_request("v0", "https://example.com/0"));
+        for version in 1..=MAX_REQUEST_VERSIONS + 2 {
+            node.overwrite(
+                sample_request(&format!("v{version}"), "https://example.com"),
+                Some(format!("  change {version}  ")),
+            );
+        }
+
+        assert_eq!(node.request.name, format!("v{}", MAX_REQUEST_VERSIONS + 2));
+        assert_eq!(node.versions.len(), MAX_REQUEST_VERSIONS);
+        assert_eq!(
+            node.versions[0].note.as_deref(),
+            Some(format!("change {}", MAX_REQUEST_VERSIONS + 2).as_str())
+        );
+
+        assert!(node.revert_to_previous());
+        assert_eq!(node.request.name, format!("v{}", MAX_REQUEST_VERSIONS + 1));
+        assert_eq!(node.versions.len(), MAX_REQUEST_VERSIONS - 1);
+    }
+
+    #[test]
+    fn revert_without_history_is_a_no_op() {
+        let mut node = CollectionRequestNode::new(sample_request("Only", "https://example.com"));
+        node.overwrite(
+            sample_request("Next", "https://example.com"),
+            Some(" ".into()),
+        );
+        assert!(node.versions[0].note.is_none());
+
+        assert!(node.revert_to_previous());
+        assert!(!node.revert_to_previous());
+        assert_eq!(node.request.name, "Only");
+    }
+
+    #[test]
+    fn redo_restores_the_version_a_revert_replaced() {
+        let mut node = CollectionRequestNode::new(sample_request("v1", "https://example.com"));
+        node.overwrite(sample_request("v2", "https://example.com"), None);
+        node.overwrite(
+            sample_request("v3", "https://example.com"),
+            Some("third".into()),
+        );
+
+        assert!(node.revert_to_previous());
+        assert!(node.revert_to_previous());
+        assert_eq!(node.request.name, "v1");
+        assert!(node.versions.is_empty());
+        assert_eq!(node.reverted.len(), 2);
+
+        assert!(node.redo_revert());
+        assert!(node.redo_revert());
+        assert!(!node.redo_revert());
+        assert_eq!(node.request.name, "v3");
+        assert_eq!(node.versions[0].request.name, "v2");
+        assert_eq!(node.versions[0].note.as_deref(), Some("third"));
+
+        assert!(node.revert_to_previous());
+        node.overwrite(sample_request("v4", "https://example.com"), None);
+        assert!(node.reverted.is_empty());
+        assert!(!node.redo_revert());
+    }
+
+    #[test]
+    fn request_versions_persist_with_the_collection() {
+        let mut node = CollectionRequestNode::new(sample_request("v1", "https://example.com"));
+        node.overwrite(
+            sample_request("v2", "https://example.com"),
+            Some("rename".into()),
+        );
+        let encoded = serde_json::to_string(&node).expect("encode");
+        let decoded: CollectionRequestNode = serde_json::from_str(&encoded).expect("decode");
+
+        assert_eq!(decoded.versions.len(), 1);
+        assert_eq!(decoded.versions[0].request.name, "v1");
+        assert_eq!(decoded.versions[0].note.as_deref(), Some("rename"));
+
+        let without_history = serde_json::to_value(CollectionRequestNode::new(sample_request(
+            "plain",
+            "https://example.com",
+        )))
+        .expect("encode");
+        assert!(without_history.get("versions").is_none());
+    }
+
+    #[test]
+    fn examples_keep_the_newest_responses_and_persist() {
+        let mut node = CollectionRequestNode::new(sample_request("Users", "https://example.com"));
+        for status_code in 200..200 + MAX_REQUEST_EXAMPLES as u16 + 2 {
+            node.add_example(ResponseData {
+                status_code,
+                ..Default::default()
+            });
+        }
+        assert_eq!(node.examples.len(), MAX_REQUEST_EXAMPLES);
+        assert_eq!(
+            node.examples[0].status_code,
+            200 + MAX_REQUEST_EXAMPLES as u16 + 1
+        );
+
+        let encoded = serde_json::to_string(&node).expect("encode");
+        let decoded: CollectionRequestNode = serde_json::from_str(&encoded).expect("decode");
+        assert_eq!(decoded.examples.len(), MAX_REQUEST_EXAMPLES);
+        assert_eq!(
+            decoded.examples[0].status_code,
+            node.examples[0].status_code
+        );
+    }
+
+    #[test]
+    fn run_on_open_guards_unsafe_methods_and_persists() {
+        assert!(!RunOnOpen::Off.allows(HttpMethod::Get));
+        assert!(RunOnOpen::SafeMethods.allows(HttpMethod::Head));
+        assert!(!RunOnOpen::SafeMethods.allows(HttpMethod::Delete));
+        assert!(RunOnOpen::AnyMethod.allows(HttpMethod::Post));
+
+        let mut node = CollectionRequestNode::new(sample_request("health", "https://example.com"));
+        let plain = serde_json::to_value(&node).expect("encode");
+        assert!(plain.get("run_on_open").is_none());
+
+        node.run_on_open = RunOnOpen::AnyMethod;
+        let encoded = serde_json::to_string(&node).expect("encode");
+        assert!(encoded.contains(r#""run_on_open":"any_method""#));
+        let decoded: CollectionRequestNode = serde_json::from_str(&encoded).expect("decode");
+        assert_eq!(decoded.run_on_open, RunOnOpen::AnyMethod);
+    }
+
     #[test]
     fn search_preserves_ancestors_and_expands_only_the_filtered_path() {
         let matching_request = CollectionRequestNode::new(sample_request(
@@ -1153,6 +1967,7 @@ mod tests {
         let collection = Collection {
             id: Uuid::new_v4(),
             name: "Production".to_string(),
+            auth: None,
             expanded: false,
             nodes: vec![CollectionNode::Folder(CollectionFolderNode {
                 id: Uuid::new_v4(),
@@ -7,6 +7,7 @@ use std::time::Duration;
 use uuid::Uuid;
 
 use crate::entities::{Header, MultipartField, RequestBody, default_workspace_id};
+use crate::http::{ProxyConfig, TlsConfig};
 use crate::utils::{DebouncedJsonWriter, shared_tokio_runtime};
 
 const ENVIRONMENTS_STORAGE_VERSION: u32 = 3;
@@ -110,6 +111,12 @@ pub struct Environment {
     pub color: EnvironmentColor,
     #[serde(default)]
     pub variables: Vec<EnvironmentVariable>,
+    /// Overrides the global proxy while this environment is active.
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+    /// Client certificate and CAs for servers of this environment that require mutual TLS.
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
 }
 
 impl Environment {
@@ -120,6 +127,37 @@ impl Environment {
             scope,
             color: EnvironmentColor::default(),
             variables: Vec::new(),
+            proxy: None,
+            tls: None,
+        }
+    }
+
+    /// Store `value` under `key`, in the variable that currently supplies it, turning it on
+    /// if it was off, or in a new variable. `secret` marks the variable secret; one that
+    /// already is stays secret either way.
+    pub fn set_variable(&mut self, key: &str, value: String, secret: bool) {
+        let position = self
+            .variables
+            .iter()
+            .rposition(|variable| variable.enabled && variable.key.trim() == key)
+            .or_else(|| {
+                self.variables
+                    .iter()
+                    .rposition(|variable| variable.key.trim() == key)
+            });
+        match position {
+            Some(position) => {
+                let variable = &mut self.variables[position];
+                variable.value = value;
+                variable.enabled = true;
+                variable.secret |= secret;
+            }
+            None => self.variables.push(EnvironmentVariable {
+                key: key.to_string(),
+                value,
+                secret,
+                ..EnvironmentVariable::default()
+            }),
         }
     }
 }
@@ -497,6 +535,33 @@ impl EnvironmentsEntity {
         cx.notify();
     }
 
+    /// The environment called `name`, compared ignoring case. When several share it, the
+    /// current workspace's own environment wins, then one of its collections', then a
+    /// global one.
+    pub fn find_by_name(&self, name: &str) -> Option<&Environment> {
+        self.environments
+            .iter()
+            .filter(|environment| environment.name.eq_ignore_ascii_case(name.trim()))
+            .min_by_key(|environment| match environment.scope {
+                EnvironmentScope::Workspace => 0,
+                EnvironmentScope::Project(_) => 1,
+                EnvironmentScope::Global => 2,
+            })
+    }
+
+    /// Make the environment [`Self::find_by_name`] picks for `name` active in its own
+    /// scope, returning its id.
+    pub fn activate_by_name(&mut self, name: &str, cx: &mut Context<Self>) -> Option<Uuid> {
+        let environment = self.find_by_name(name)?;
+        let (id, scope) = (environment.id, environment.scope);
+        let collection_id = match scope {
+            EnvironmentScope::Project(project_id) => Some(project_id),
+            EnvironmentScope::Global | EnvironmentScope::Workspace => None,
+        };
+        self.set_active(collection_id, Some(id), cx);
+        Some(id)
+    }
+
     pub fn create_environment(
         &mut self,
         name: impl Into<String>,
@@ -649,6 +714,49 @@ impl EnvironmentsEntity {
         self.changed(EnvironmentEvent::Changed, cx);
     }
 
+    pub fn set_environment_proxy(
+        &mut self,
+        id: Uuid,
+        proxy: Option<ProxyConfig>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(environment) = self.get_mut(id) else {
+            return;
+        };
+        environment.proxy = proxy;
+        self.changed(EnvironmentEvent::Changed, cx);
+    }
+
+    pub fn set_environment_tls(
+        &mut self,
+        id: Uuid,
+        tls: Option<TlsConfig>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(environment) = self.get_mut(id) else {
+            return;
+        };
+        environment.tls = tls;
+        self.changed(EnvironmentEvent::Changed, cx);
+    }
+
+    /// Turn certificate verification off or back on for the environment, keeping its
+    /// client certificate and CA bundle.
+    pub fn set_environment_accepts_invalid_certs(
+        &mut self,
+        id: Uuid,
+        accept: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(environment) = self.get_mut(id) else {
+            return;
+        };
+        let mut tls = environment.tls.take().unwrap_or_default();
+        tls.accept_invalid_certs = accept;
+        environment.tls = (!tls.is_empty()).then_some(tls);
+        self.changed(EnvironmentEvent::Changed, cx);
+    }
+
     pub fn remove_project_environments(&mut self, project_id: Uuid, cx: &mut Context<Self>) {
         let previous_len = self.environments.len();
         self.environments
@@ -761,6 +869,22 @@ impl EnvironmentsEntity {
         self.changed(EnvironmentEvent::Changed, cx);
     }
 
+    /// Store each `(key, value)` in `environment_id`, as response extraction rules do.
+    pub fn set_variable_values(
+        &mut self,
+        environment_id: Uuid,
+        values: &[(String, String, bool)],
+        cx: &mut Context<Self>,
+    ) {
+        let Some(environment) = self.get_mut(environment_id) else {
+            return;
+        };
+        for (key, value, secret) in values {
+            environment.set_variable(key, value.clone(), *secret);
+        }
+        self.changed(EnvironmentEvent::Changed, cx);
+    }
+
     pub fn clear_variables(&mut self, environment_id: Uuid, cx: &mut Context<Self>) {
         let Some(environment) = self.get_mut(environment_id) else {
             return;
@@ -776,7 +900,24 @@ impl EnvironmentsEntity {
         headers: &[Header],
         body: &RequestBody,
     ) -> Result<ResolvedRequestParts, InterpolationError> {
-        let values = self.effective_values(collection_id);
+        self.resolve_request_with(collection_id, &HashMap::new(), url, headers, body)
+    }
+
+    /// Like `resolve_request`, with `variables` taking precedence over every environment.
+    pub fn resolve_request_with(
+        &self,
+        collection_id: Option<Uuid>,
+        variables: &HashMap<String, String>,
+        url: &str,
+        headers: &[Header],
+        body: &RequestBody,
+    ) -> Result<ResolvedRequestParts, InterpolationError> {
+        let mut values = self.effective_values(collection_id);
+        values.extend(
+            variables
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
         let mut resolver = Resolver::new(values);
         let url = resolver.resolve(url);
         let headers = headers
@@ -807,12 +948,31 @@ impl EnvironmentsEntity {
                     })
                     .collect(),
             ),
+            RequestBody::GraphQl { query, variables } => RequestBody::GraphQl {
+                query: resolver.resolve(query),
+                variables: resolver.resolve(variables),
+            },
+            RequestBody::Binary(path) => {
+                RequestBody::Binary(resolver.resolve(&path.to_string_lossy()).into())
+            }
         };
 
         resolver.finish()?;
         Ok(ResolvedRequestParts { url, headers, body })
     }
 
+    /// Resolve standalone template strings, such as auth settings applied at send time.
+    pub fn resolve_values<const N: usize>(
+        &self,
+        collection_id: Option<Uuid>,
+        templates: [&str; N],
+    ) -> Result<[String; N], InterpolationError> {
+        let mut resolver = Resolver::new(self.effective_values(collection_id));
+        let values = templates.map(|template| resolver.resolve(template));
+        resolver.finish()?;
+        Ok(values)
+    }
+
     fn effective_values(&self, collection_id: Option<Uuid>) -> HashMap<String, String> {
         let mut values = HashMap::new();
         if let Some(global) = self.active_global_environment.and_then(|id| self.get(id)) {
@@ -979,6 +1139,15 @@ fn duplicate_environment_data(source: Environment) -> Environment {
                 ..variable
             })
             .collect(),
+        // Like secret variables, the proxy and certificate passwords stay behind.
+        proxy: source.proxy.map(|proxy| ProxyConfig {
+            password: String::new(),
+            ..proxy
+        }),
+        tls: source.tls.map(|tls| TlsConfig {
+            pkcs12_password: String::new(),
+            ..tls
+        }),
     }
 }
 
@@ -1124,6 +1293,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn finding_by_name_prefers_the_current_workspace() {
+        let project_id = Uuid::new_v4();
+        let mut entity = entity_with_variables(&[], Some((project_id, &[])));
+        let global = Environment::new("development", EnvironmentScope::Global);
+        let global_id = global.id;
+        entity.environments.insert(0, global);
+        let workspace_id = entity.active_workspace_environment.unwrap();
+
+        assert_eq!(
+            entity
+                .find_by_name(" DEVELOPMENT ")
+                .map(|environment| environment.id),
+            Some(workspace_id)
+        );
+        entity.environments[0].name = "Project".to_string();
+        assert_eq!(
+            entity
+                .find_by_name("project")
+                .map(|environment| environment.scope),
+            Some(EnvironmentScope::Project(project_id))
+        );
+        entity
+            .environments
+            .retain(|environment| environment.id == global_id);
+        assert_eq!(
+            entity
+                .find_by_name("project")
+                .map(|environment| environment.id),
+            Some(global_id)
+        );
+        assert!(entity.find_by_name("staging").is_none());
+    }
+
+    #[test]
+    fn setting_a_variable_updates_the_one_in_effect_or_adds_it() {
+        let mut environment = Environment::new("Development", EnvironmentScope::Workspace);
+        environment.variables = vec![
+            EnvironmentVariable {
+                key: "token".to_string(),
+                value: "old".to_string(),
+                ..EnvironmentVariable::default()
+            },
+            EnvironmentVariable {
+                key: "csrf".to_string(),
+                enabled: false,
+                ..EnvironmentVariable::default()
+            },
+        ];
+
+        environment.set_variable("token", "new".to_string(), false);
+        environment.set_variable("csrf", "abc".to_string(), false);
+        environment.set_variable("userId", "7".to_string(), true);
+
+        let values: Vec<_> = environment
+            .variables
+            .iter()
+            .map(|variable| {
+                (
+                    variable.key.as_str(),
+                    variable.value.as_str(),
+                    variable.enabled,
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("token", "new", true),
+                ("csrf", "abc", true),
+                ("userId", "7", true)
+            ]
+        );
+        let secret: Vec<_> = environment
+            .variables
+            .iter()
+            .map(|variable| variable.secret)
+            .collect();
+        assert_eq!(secret, [false, false, true]);
+    }
+
+    #[test]
+    fn given_variables_override_environment_values() {
+        let entity = entity_with_variables(&[("token", "from-env"), ("host", "api.test")], None);
+        let variables = HashMap::from([("token".to_string(), "from-script".to_string())]);
+        let resolved = entity
+            .resolve_request_with(
+                None,
+                &variables,
+                "https://{{host}}/{{token}}",
+                &[],
+                &RequestBody::None,
+            )
+            .expect("request should resolve");
+
+        assert_eq!(resolved.url, "https://api.test/from-script");
+    }
+
     #[test]
     fn interpolates_url_headers_and_body_recursively() {
         let entity = entity_with_variables(
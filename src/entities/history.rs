@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use gpui::{Context, EventEmitter};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -9,10 +10,16 @@ use uuid::Uuid;
 
 use crate::utils::{DebouncedJsonWriter, shared_tokio_runtime};
 
-use super::{HttpMethod, RequestData, ResponseData, SidebarLoadState, default_workspace_id};
+use super::{
+    DEFAULT_HISTORY_BODY_MAX_KB, HttpMethod, RequestData, ResponseData, SidebarLoadState,
+    default_workspace_id,
+};
 
 const HISTORY_STORAGE_VERSION: u32 = 2;
 const SAVE_DEBOUNCE: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RESPONSE_BODY_BYTES: usize = DEFAULT_HISTORY_BODY_MAX_KB as usize * 1024;
+/// Most recent answered requests a URL group's latency sparkline covers.
+const SPARKLINE_POINTS: usize = 20;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct HistoryStore {
@@ -96,10 +103,42 @@ pub enum HistoryRow {
         key: HistoryGroupKey,
         count: usize,
         collapsed: bool,
+        /// Response times of the group's latest answered requests, oldest first. Only URL
+        /// groups fill this in.
+        durations: Vec<u64>,
     },
     Entry(HistoryRowEntry),
 }
 
+/// Fastest, median and slowest of a set of response times, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySummary {
+    pub min: u64,
+    pub median: u64,
+    pub max: u64,
+}
+
+impl LatencySummary {
+    pub fn from_durations(durations: &[u64]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        Some(Self {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
 /// Lightweight render data produced away from the GPUI thread.
 ///
 /// Keeping response bodies and request headers out of this type avoids cloning
@@ -144,6 +183,16 @@ pub struct HistoryRowsSnapshot {
 }
 
 impl HistoryRowsSnapshot {
+    /// Entries the sidebar currently shows for `query`, newest first, ignoring collapsed groups.
+    pub fn matching_entries(&self, query: &str, starred_only: bool) -> Vec<Arc<HistoryEntry>> {
+        let query = query.trim().to_ascii_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| entry_matches_filter(entry, &query, starred_only))
+            .cloned()
+            .collect()
+    }
+
     pub fn flattened_rows(
         &self,
         query: &str,
@@ -151,18 +200,7 @@ impl HistoryRowsSnapshot {
         grouping: HistoryGrouping,
     ) -> Vec<HistoryRow> {
         let query = query.trim().to_ascii_lowercase();
-        let matches = |entry: &HistoryEntry| {
-            (!starred_only || entry.starred)
-                && (query.is_empty()
-                    || entry.request.url.to_ascii_lowercase().contains(&query)
-                    || entry.request.name.to_ascii_lowercase().contains(&query)
-                    || entry
-                        .request
-                        .method
-                        .as_str()
-                        .to_ascii_lowercase()
-                        .contains(&query))
-        };
+        let matches = |entry: &HistoryEntry| entry_matches_filter(entry, &query, starred_only);
 
         match grouping {
             HistoryGrouping::Time => {
@@ -198,6 +236,7 @@ impl HistoryRowsSnapshot {
                         key: HistoryGroupKey::Time(group),
                         count: entries.len(),
                         collapsed,
+                        durations: Vec::new(),
                     });
                     if !collapsed {
                         rows.extend(
@@ -225,10 +264,19 @@ impl HistoryRowsSnapshot {
                 let mut rows = Vec::new();
                 for (domain, entries) in groups {
                     let collapsed = self.collapsed_url_groups.contains(&domain);
+                    // Entries are newest first; the sparkline reads left to right in time.
+                    let mut durations: Vec<u64> = entries
+                        .iter()
+                        .filter_map(|entry| entry.response.as_ref())
+                        .map(|response| response.duration_ms)
+                        .take(SPARKLINE_POINTS)
+                        .collect();
+                    durations.reverse();
                     rows.push(HistoryRow::Group {
                         key: HistoryGroupKey::Url(domain),
                         count: entries.len(),
                         collapsed,
+                        durations,
                     });
                     if !collapsed {
                         rows.extend(
@@ -245,6 +293,82 @@ impl HistoryRowsSnapshot {
     }
 }
 
+/// `query` must already be trimmed and lowercased.
+fn entry_matches_filter(entry: &HistoryEntry, query: &str, starred_only: bool) -> bool {
+    (!starred_only || entry.starred)
+        && (query.is_empty()
+            || entry.request.url.to_ascii_lowercase().contains(query)
+            || entry.request.name.to_ascii_lowercase().contains(query)
+            || entry
+                .request
+                .method
+                .as_str()
+                .to_ascii_lowercase()
+                .contains(query))
+}
+
+/// File formats history can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryExportFormat {
+    /// Full entries, requests and responses included, as stored on disk.
+    Json,
+    /// One summary row per entry for spreadsheets.
+    Csv,
+}
+
+impl HistoryExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            HistoryExportFormat::Json => "json",
+            HistoryExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Write `entries` to `writer` one at a time, so large histories never become one string.
+pub fn write_history_export(
+    entries: &[Arc<HistoryEntry>],
+    format: HistoryExportFormat,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    match format {
+        HistoryExportFormat::Json => {
+            writer.write_all(b"[")?;
+            for (index, entry) in entries.iter().enumerate() {
+                writer.write_all(if index == 0 { b"\n  " } else { b",\n  " })?;
+                serde_json::to_writer(&mut writer, entry.as_ref())?;
+            }
+            writer.write_all(if entries.is_empty() { b"]\n" } else { b"\n]\n" })?;
+        }
+        HistoryExportFormat::Csv => {
+            writer.write_all(b"method,url,status,duration_ms,size_bytes,timestamp\r\n")?;
+            for entry in entries {
+                let response = entry.response.as_ref();
+                let fields = [
+                    entry.request.method.as_str().to_string(),
+                    entry.request.url.clone(),
+                    response.map_or_else(String::new, |r| r.status_code.to_string()),
+                    response.map_or_else(String::new, |r| r.duration_ms.to_string()),
+                    response.map_or_else(String::new, |r| r.body_size_bytes.to_string()),
+                    entry.timestamp.to_rfc3339(),
+                ];
+                let row: Vec<_> = fields.iter().map(|field| csv_field(field)).collect();
+                writer.write_all(row.join(",").as_bytes())?;
+                writer.write_all(b"\r\n")?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
 impl TimeGroup {
     pub fn label(&self) -> &'static str {
         match self {
@@ -272,6 +396,11 @@ pub struct HistoryEntity {
     pub entries: Arc<Vec<Arc<HistoryEntry>>>,
     pub load_state: SidebarLoadState,
     pub max_entries: usize,
+    /// Response bodies larger than this are stored truncated, so big downloads do not bloat
+    /// the history file. Set from [`UiPreferences::history_body_max_kb`].
+    ///
+    /// [`UiPreferences::history_body_max_kb`]: super::UiPreferences::history_body_max_kb
+    pub max_response_body_bytes: usize,
     active_workspace_id: Uuid,
     workspace_entries: HashMap<Uuid, Arc<Vec<Arc<HistoryEntry>>>>,
     persistor: Option<DebouncedJsonWriter<HistoryStore>>,
@@ -290,6 +419,7 @@ impl HistoryEntity {
             entries: Arc::new(Vec::new()),
             load_state: SidebarLoadState::Loading,
             max_entries: 5_000,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
             active_workspace_id,
             workspace_entries: HashMap::new(),
             persistor: storage_path
@@ -420,13 +550,14 @@ impl HistoryEntity {
         }
     }
 
+    /// Record a sent request and return the new entry's id.
     pub fn add_entry(
         &mut self,
         request: RequestData,
         response: Option<ResponseData>,
         cx: &mut Context<Self>,
-    ) {
-        let entry = HistoryEntry::new(request, response);
+    ) -> Uuid {
+        let entry = self.entry_for(request, response);
         let id = entry.id;
 
         let entries = Arc::make_mut(&mut self.entries);
@@ -439,6 +570,16 @@ impl HistoryEntity {
         self.save_to_file();
         cx.emit(HistoryEvent::EntryAdded(id));
         cx.notify();
+        id
+    }
+
+    /// The entry a sent request is stored as, its response body cut to the history limit.
+    fn entry_for(&self, request: RequestData, response: Option<ResponseData>) -> HistoryEntry {
+        let response = response.map(|mut response| {
+            response.truncate_body(self.max_response_body_bytes);
+            response
+        });
+        HistoryEntry::new(request, response)
     }
 
     pub fn remove_entry(&mut self, id: Uuid, cx: &mut Context<Self>) {
@@ -615,6 +756,14 @@ mod tests {
             method,
             headers: vec![Header::new("Accept", "application/json")],
             body: RequestBody::None,
+            preserve_header_order: false,
+            inherit_auth: false,
+            assertions: String::new(),
+            pre_request_script: String::new(),
+            extractions: String::new(),
+            disabled_params: Vec::new(),
+            subprotocols: String::new(),
+            auto_save: None,
             is_sending: false,
         }
     }
@@ -629,6 +778,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keeps_empty_value_headers_in_storage() {
+        let mut entry = sample_entry("Debug", "https://api.example.com/debug", HttpMethod::Get);
+        entry.request.headers.push(Header::new("X-Debug", ""));
+        let store = HistoryStore {
+            version: HISTORY_STORAGE_VERSION,
+            workspaces: HashMap::from([(default_workspace_id(), vec![entry])]),
+        };
+
+        let encoded = serde_json::to_string(&store).expect("encode");
+        let (workspaces, _) = deserialize_history_store(&encoded).expect("decode");
+        let headers = &workspaces[&default_workspace_id()][0].request.headers;
+
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[1].key, "X-Debug");
+        assert_eq!(headers[1].value, "");
+    }
+
     #[test]
     fn display_name_prefers_request_name_and_falls_back_to_trimmed_url() {
         let named = sample_entry(
@@ -664,6 +831,7 @@ mod tests {
                 )),
             ]),
             max_entries: 500,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
             load_state: SidebarLoadState::Ready,
             active_workspace_id: default_workspace_id(),
             workspace_entries: HashMap::new(),
@@ -698,6 +866,7 @@ mod tests {
                 )),
             ]),
             max_entries: 500,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
             load_state: SidebarLoadState::Ready,
             active_workspace_id: default_workspace_id(),
             workspace_entries: HashMap::new(),
@@ -732,6 +901,7 @@ mod tests {
             ]),
             load_state: SidebarLoadState::Ready,
             max_entries: 5_000,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
             active_workspace_id: default_workspace_id(),
             workspace_entries: HashMap::new(),
             persistor: None,
@@ -754,6 +924,7 @@ mod tests {
             ))]),
             load_state: SidebarLoadState::Ready,
             max_entries: 5_000,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
             active_workspace_id: default_workspace_id(),
             workspace_entries: HashMap::new(),
             persistor: None,
@@ -771,6 +942,151 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn url_groups_carry_recent_durations_oldest_first() {
+        let answered = |duration_ms| {
+            let mut entry = sample_entry("Users", "https://api.example.com/users", HttpMethod::Get);
+            entry.response = Some(ResponseData {
+                duration_ms,
+                ..Default::default()
+            });
+            Arc::new(entry)
+        };
+        let pending = Arc::new(sample_entry(
+            "Users",
+            "https://api.example.com/users",
+            HttpMethod::Get,
+        ));
+        let history = HistoryEntity {
+            entries: Arc::new(vec![answered(30), pending, answered(10), answered(20)]),
+            load_state: SidebarLoadState::Ready,
+            max_entries: 5_000,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
+            active_workspace_id: default_workspace_id(),
+            workspace_entries: HashMap::new(),
+            persistor: None,
+            collapsed_groups: Arc::new(HashSet::new()),
+            collapsed_url_groups: Arc::new(HashSet::new()),
+        };
+        let rows = history.flattened_rows("", false, HistoryGrouping::Url);
+        let HistoryRow::Group { durations, .. } = &rows[0] else {
+            panic!("first row should be the group header");
+        };
+        assert_eq!(durations, &[20, 10, 30]);
+        assert_eq!(
+            LatencySummary::from_durations(durations),
+            Some(LatencySummary {
+                min: 10,
+                median: 20,
+                max: 30
+            })
+        );
+        assert_eq!(
+            LatencySummary::from_durations(&[10, 40]).unwrap().median,
+            25
+        );
+        assert_eq!(LatencySummary::from_durations(&[]), None);
+    }
+
+    #[test]
+    fn entries_keep_response_bodies_up_to_the_configured_limit() {
+        let response = |body: &str| {
+            Some(ResponseData::new(
+                200,
+                "OK".to_string(),
+                HashMap::new(),
+                body.to_string(),
+                Vec::new(),
+                body.len(),
+                5,
+                Some("text/plain".to_string()),
+            ))
+        };
+        let history = HistoryEntity {
+            entries: Arc::new(Vec::new()),
+            load_state: SidebarLoadState::Ready,
+            max_entries: 5_000,
+            max_response_body_bytes: 1024,
+            active_workspace_id: default_workspace_id(),
+            workspace_entries: HashMap::new(),
+            persistor: None,
+            collapsed_groups: Arc::new(HashSet::new()),
+            collapsed_url_groups: Arc::new(HashSet::new()),
+        };
+        let request =
+            sample_entry("Users", "https://api.example.com/users", HttpMethod::Get).request;
+
+        let small = history.entry_for(request.clone(), response(&"a".repeat(1024)));
+        assert_eq!(small.response.unwrap().body().len(), 1024);
+
+        let large = history.entry_for(request, response(&"a".repeat(4096)));
+        let large = large.response.unwrap();
+        assert!(large.body().starts_with(&"a".repeat(1024)));
+        assert!(
+            large
+                .body()
+                .ends_with("[Body truncated in history: first 1.0 KB of 4.0 KB kept]")
+        );
+        assert_eq!(large.body_size_bytes, 4096);
+    }
+
+    #[test]
+    fn exports_filtered_entries_as_json_and_csv() {
+        let mut answered = sample_entry("Users", "https://api.example.com/a,b", HttpMethod::Get);
+        answered.starred = true;
+        answered.response = Some(ResponseData::new(
+            200,
+            "OK".to_string(),
+            HashMap::new(),
+            "[]".to_string(),
+            Vec::new(),
+            2,
+            31,
+            None,
+        ));
+        let pending = sample_entry("Login", "https://auth.example.com", HttpMethod::Post);
+        let history = HistoryEntity {
+            entries: Arc::new(vec![Arc::new(answered), Arc::new(pending)]),
+            max_entries: 500,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
+            load_state: SidebarLoadState::Ready,
+            active_workspace_id: default_workspace_id(),
+            workspace_entries: HashMap::new(),
+            persistor: None,
+            collapsed_groups: Arc::new(HashSet::new()),
+            collapsed_url_groups: Arc::new(HashSet::new()),
+        };
+        let snapshot = history.rows_snapshot();
+
+        let starred = snapshot.matching_entries("", true);
+        assert_eq!(starred.len(), 1);
+        let mut csv = Vec::new();
+        write_history_export(&starred, HistoryExportFormat::Csv, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("method,url,status,duration_ms,size_bytes,timestamp")
+        );
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("GET,\"https://api.example.com/a,b\",200,31,2,")
+        );
+
+        let all = snapshot.matching_entries("  EXAMPLE ", false);
+        let mut json = Vec::new();
+        write_history_export(&all, HistoryExportFormat::Json, &mut json).unwrap();
+        let parsed: Vec<HistoryEntry> = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].request.name, "Login");
+
+        let mut empty = Vec::new();
+        write_history_export(&[], HistoryExportFormat::Json, &mut empty).unwrap();
+        assert_eq!(empty, b"[]\n");
+    }
+
     #[test]
     fn shared_entries_keep_the_existing_json_storage_shape() {
         let entry = sample_entry(
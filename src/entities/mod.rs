@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod collections;
 pub mod environment;
 pub mod history;
@@ -5,8 +6,10 @@ pub mod load_state;
 pub mod preferences;
 pub mod request;
 pub mod response;
+pub mod websocket;
 pub mod workspace;
 
+pub use auth::*;
 pub use collections::*;
 pub use environment::*;
 pub use history::*;
@@ -14,4 +17,5 @@ pub use load_state::*;
 pub use preferences::*;
 pub use request::*;
 pub use response::*;
+pub use websocket::*;
 pub use workspace::*;
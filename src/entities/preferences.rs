@@ -3,10 +3,15 @@ use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::http::{PoolConfig, ProxyConfig, RedirectPolicy, RetryPolicy, UrlScheme};
 use crate::utils::DebouncedJsonWriter;
 
 const UI_PREFERENCES_VERSION: u32 = 1;
 const SAVE_DEBOUNCE: Duration = Duration::from_millis(250);
+pub const DEFAULT_AUTO_FORMAT_MAX_KB: u64 = 5 * 1024;
+pub const MAX_AUTO_FORMAT_MAX_KB: u64 = 1024 * 1024;
+pub const DEFAULT_HISTORY_BODY_MAX_KB: u64 = 256;
+pub const MAX_HISTORY_BODY_MAX_KB: u64 = 64 * 1024;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -16,6 +21,85 @@ pub enum PreferredLayout {
     SideBySide,
 }
 
+/// A named set of headers added to a request in one go from the header editor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderPreset {
+    pub name: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl HeaderPreset {
+    fn json_client() -> Self {
+        Self {
+            name: "JSON client".to_string(),
+            headers: vec![("Accept".to_string(), "application/json".to_string())],
+        }
+    }
+}
+
+/// Read presets written as a `[Name]` line followed by one `Key: Value` line per header.
+/// Blank lines and `#` comments are skipped.
+pub fn parse_header_presets(text: &str) -> Result<Vec<HeaderPreset>, String> {
+    let mut presets: Vec<HeaderPreset> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_number = index + 1;
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!("Line {line_number}: Preset name is empty"));
+            }
+            if presets.iter().any(|preset| preset.name == name) {
+                return Err(format!(
+                    "Line {line_number}: There is already a preset named \"{name}\""
+                ));
+            }
+            presets.push(HeaderPreset {
+                name: name.to_string(),
+                headers: Vec::new(),
+            });
+            continue;
+        }
+        let Some(preset) = presets.last_mut() else {
+            return Err(format!(
+                "Line {line_number}: Start with a [Preset name] line before its headers"
+            ));
+        };
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(format!("Line {line_number}: Expected Header-Name: value"));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("Line {line_number}: Header name is empty"));
+        }
+        preset
+            .headers
+            .push((key.to_string(), value.trim().to_string()));
+    }
+    Ok(presets)
+}
+
+/// Write presets in the form [`parse_header_presets`] reads.
+pub fn format_header_presets(presets: &[HeaderPreset]) -> String {
+    presets
+        .iter()
+        .map(|preset| {
+            let mut section = format!("[{}]\n", preset.name);
+            for (key, value) in &preset.headers {
+                section.push_str(&format!("{key}: {value}\n"));
+            }
+            section
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UiPreferences {
@@ -25,6 +109,35 @@ pub struct UiPreferences {
     pub layout: PreferredLayout,
     pub stacked_split: [f32; 2],
     pub side_by_side_split: [f32; 2],
+    /// Beautify JSON/XML pasted into the body editor when it parses.
+    pub format_on_paste: bool,
+    /// Redirect policy the HTTP client is built with.
+    pub redirect_policy: RedirectPolicy,
+    /// Scheme added to URLs typed without one.
+    pub default_scheme: UrlScheme,
+    /// Proxy requests go through unless the active environment sets its own.
+    pub proxy: ProxyConfig,
+    /// Retries after connection errors and 5xx responses; off unless set up.
+    pub retry: RetryPolicy,
+    /// Connection reuse settings the HTTP client is built with.
+    pub pool: PoolConfig,
+    /// Show what a send would put on the wire instead of sending it.
+    pub dry_run: bool,
+    /// Sort object keys when pretty-printing JSON responses and beautified bodies.
+    pub sort_json_keys: bool,
+    /// Responses larger than this many KB open on the Raw tab instead of being
+    /// pretty-printed; 0 formats every response.
+    pub auto_format_max_kb: u64,
+    /// Response bodies larger than this many KB are kept truncated in history.
+    pub history_body_max_kb: u64,
+    /// Put the cursor in the URL bar of every new tab.
+    pub focus_url_on_new_tab: bool,
+    /// Header sets offered in the header editor's presets menu.
+    pub header_presets: Vec<HeaderPreset>,
+    /// Volume, mute and playback speed the audio player starts with.
+    pub audio_volume: f32,
+    pub audio_muted: bool,
+    pub audio_speed: f32,
 }
 
 impl Default for UiPreferences {
@@ -36,6 +149,21 @@ impl Default for UiPreferences {
             layout: PreferredLayout::Stacked,
             stacked_split: [360.0, 360.0],
             side_by_side_split: [620.0, 620.0],
+            format_on_paste: false,
+            redirect_policy: RedirectPolicy::default(),
+            default_scheme: UrlScheme::default(),
+            proxy: ProxyConfig::default(),
+            retry: RetryPolicy::default(),
+            pool: PoolConfig::default(),
+            dry_run: false,
+            sort_json_keys: false,
+            auto_format_max_kb: DEFAULT_AUTO_FORMAT_MAX_KB,
+            history_body_max_kb: DEFAULT_HISTORY_BODY_MAX_KB,
+            focus_url_on_new_tab: true,
+            header_presets: vec![HeaderPreset::json_client()],
+            audio_volume: 0.8,
+            audio_muted: false,
+            audio_speed: 1.0,
         }
     }
 }
@@ -48,6 +176,16 @@ impl UiPreferences {
         self.sidebar_width = finite_clamp(self.sidebar_width, 200.0, 500.0, 300.0);
         self.stacked_split = validate_split(self.stacked_split, [360.0, 360.0]);
         self.side_by_side_split = validate_split(self.side_by_side_split, [620.0, 620.0]);
+        self.audio_volume = finite_clamp(self.audio_volume, 0.0, 1.0, 0.8);
+        self.audio_speed = finite_clamp(self.audio_speed, 0.5, 2.0, 1.0);
+        self.retry.max_attempts = self.retry.max_attempts.clamp(1, 10);
+        self.retry.base_delay_ms = self.retry.base_delay_ms.clamp(50, 60_000);
+        self.pool.idle_timeout_secs = self.pool.idle_timeout_secs.min(3_600);
+        self.pool.tcp_keepalive_secs = self.pool.tcp_keepalive_secs.min(3_600);
+        self.auto_format_max_kb = self.auto_format_max_kb.min(MAX_AUTO_FORMAT_MAX_KB);
+        self.history_body_max_kb = self.history_body_max_kb.clamp(1, MAX_HISTORY_BODY_MAX_KB);
+        self.header_presets
+            .retain(|preset| !preset.name.trim().is_empty());
         self
     }
 }
@@ -106,7 +244,10 @@ fn storage_path() -> Option<PathBuf> {
 
 #[cfg(test)]
 mod tests {
-    use super::{PreferredLayout, UiPreferences};
+    use super::{
+        HeaderPreset, PreferredLayout, UiPreferences, format_header_presets, parse_header_presets,
+    };
+    use crate::http::{PoolConfig, RedirectPolicy, RetryPolicy, UrlScheme};
 
     #[test]
     fn validates_and_clamps_preferences() {
@@ -115,6 +256,19 @@ mod tests {
             stacked_split: [f32::NAN, 200.0],
             side_by_side_split: [100.0, 100.0],
             layout: PreferredLayout::SideBySide,
+            audio_volume: 3.0,
+            audio_speed: f32::INFINITY,
+            retry: RetryPolicy {
+                max_attempts: 0,
+                base_delay_ms: 600_000,
+                ..RetryPolicy::default()
+            },
+            pool: PoolConfig {
+                idle_timeout_secs: 86_400,
+                ..PoolConfig::default()
+            },
+            auto_format_max_kb: u64::MAX,
+            history_body_max_kb: 0,
             ..UiPreferences::default()
         }
         .validated();
@@ -123,6 +277,61 @@ mod tests {
         assert_eq!(preferences.stacked_split, [360.0, 360.0]);
         assert_eq!(preferences.side_by_side_split, [620.0, 620.0]);
         assert_eq!(preferences.layout, PreferredLayout::SideBySide);
+        assert_eq!(preferences.audio_volume, 1.0);
+        assert_eq!(preferences.audio_speed, 1.0);
+        assert_eq!(preferences.retry.max_attempts, 1);
+        assert_eq!(preferences.retry.base_delay_ms, 60_000);
+        assert_eq!(preferences.pool.idle_timeout_secs, 3_600);
+        assert_eq!(preferences.auto_format_max_kb, 1024 * 1024);
+        assert_eq!(preferences.history_body_max_kb, 1);
+        assert_eq!(
+            UiPreferences {
+                history_body_max_kb: u64::MAX,
+                ..UiPreferences::default()
+            }
+            .validated()
+            .history_body_max_kb,
+            64 * 1024
+        );
+    }
+
+    #[test]
+    fn header_presets_round_trip_through_their_text_form() {
+        let presets = parse_header_presets(
+            "# shared\n[JSON client]\nAccept: application/json\nUser-Agent: setu/1.0\n\n\
+             [Tracing]\nX-Trace: {{traceId}}\nX-Time: 12:00",
+        )
+        .unwrap();
+        assert_eq!(
+            presets,
+            [
+                HeaderPreset {
+                    name: "JSON client".to_string(),
+                    headers: vec![
+                        ("Accept".to_string(), "application/json".to_string()),
+                        ("User-Agent".to_string(), "setu/1.0".to_string()),
+                    ],
+                },
+                HeaderPreset {
+                    name: "Tracing".to_string(),
+                    headers: vec![
+                        ("X-Trace".to_string(), "{{traceId}}".to_string()),
+                        ("X-Time".to_string(), "12:00".to_string()),
+                    ],
+                },
+            ]
+        );
+        assert_eq!(
+            parse_header_presets(&format_header_presets(&presets)).unwrap(),
+            presets
+        );
+
+        assert_eq!(
+            parse_header_presets("Accept: */*").unwrap_err(),
+            "Line 1: Start with a [Preset name] line before its headers"
+        );
+        assert!(parse_header_presets("[A]\n[A]").is_err());
+        assert!(parse_header_presets("[A]\nno colon").is_err());
     }
 
     #[test]
@@ -135,4 +344,25 @@ mod tests {
         .validated();
         assert_eq!(preferences, UiPreferences::default());
     }
+
+    #[test]
+    fn redirect_policy_round_trips_and_defaults_to_following() {
+        let legacy: UiPreferences = serde_json::from_str(r#"{"version":1}"#).unwrap();
+        assert_eq!(legacy.redirect_policy, RedirectPolicy::Limited(10));
+        assert_eq!(legacy.default_scheme, UrlScheme::Https);
+        assert!(legacy.focus_url_on_new_tab);
+        assert!(!legacy.sort_json_keys);
+        assert!(legacy.proxy.is_empty());
+        assert!(!legacy.retry.retries());
+        assert_eq!(legacy.pool, PoolConfig::default());
+
+        let preferences = UiPreferences {
+            redirect_policy: RedirectPolicy::None,
+            ..UiPreferences::default()
+        };
+        let json = serde_json::to_string(&preferences).unwrap();
+        assert!(json.contains(r#""redirect_policy":"none""#));
+        let restored: UiPreferences = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.redirect_policy, RedirectPolicy::None);
+    }
 }
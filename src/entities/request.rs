@@ -1,6 +1,8 @@
+use chrono::{DateTime, Local};
 use gpui::{Context, EventEmitter};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// HTTP Methods supported by the application
@@ -41,6 +43,21 @@ impl HttpMethod {
             HttpMethod::Options,
         ]
     }
+
+    /// Methods that only read, per RFC 9110; sending them again is expected to be harmless.
+    pub fn is_safe(&self) -> bool {
+        matches!(
+            self,
+            HttpMethod::Get | HttpMethod::Head | HttpMethod::Options
+        )
+    }
+
+    /// Warn about a body this method is not expected to carry, since many servers ignore or
+    /// reject one on GET and HEAD. Sending still goes ahead: some APIs do read GET bodies.
+    pub fn body_warning(&self, has_body: bool) -> Option<String> {
+        (matches!(self, HttpMethod::Get | HttpMethod::Head) && has_body)
+            .then(|| format!("{self} request with a body: many servers ignore or reject it"))
+    }
 }
 
 impl std::fmt::Display for HttpMethod {
@@ -50,13 +67,94 @@ impl std::fmt::Display for HttpMethod {
 }
 
 /// A single header key-value pair
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Header {
     pub key: String,
     pub value: String,
     pub enabled: bool,
 }
 
+/// A query param switched off in the params editor, kept so it can be turned back on.
+///
+/// Enabled params are part of the URL itself, so only the disabled ones are stored apart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisabledParam {
+    pub key: String,
+    pub value: String,
+    /// Row among the named params it sat at, so turning it back on restores it in place.
+    /// `None` for params stored before positions were kept; those go after the rest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+/// Where the response body of a request is written after every send that gets one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseAutoSave {
+    /// File the body goes to, replaced on each send unless timestamped.
+    pub path: String,
+    /// Put the time of the response before the extension, so each send keeps its own file.
+    #[serde(default)]
+    pub timestamped: bool,
+}
+
+impl ResponseAutoSave {
+    /// The file a response received at `now` is written to.
+    pub fn target_path(&self, now: DateTime<Local>) -> PathBuf {
+        let path = Path::new(self.path.trim());
+        if !self.timestamped {
+            return path.to_path_buf();
+        }
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "response".to_string());
+        let mut name = format!("{stem}-{}", now.format("%Y%m%d-%H%M%S-%3f"));
+        if let Some(extension) = path.extension() {
+            name.push('.');
+            name.push_str(&extension.to_string_lossy());
+        }
+        path.with_file_name(name)
+    }
+
+    /// Why the configured file cannot be written, checked before any response arrives.
+    pub fn problem(&self) -> Option<String> {
+        let path = Path::new(self.path.trim());
+        if self.path.trim().is_empty() {
+            return Some("Choose a file to save responses to".to_string());
+        }
+        if !path.is_absolute() {
+            return Some(format!("{} is not an absolute path", path.display()));
+        }
+        if path.is_dir() {
+            return Some(format!("{} is a folder, not a file", path.display()));
+        }
+        let folder = path.parent().unwrap_or(path);
+        match std::fs::metadata(folder) {
+            Ok(metadata) if !metadata.is_dir() => {
+                return Some(format!("{} is not a folder", folder.display()));
+            }
+            Ok(_) => {}
+            Err(error) => return Some(format!("{}: {error}", folder.display())),
+        }
+        // Permission bits do not tell the whole story (ACLs, read-only mounts), so try the
+        // file itself, leaving nothing behind when it did not exist yet.
+        let existed = path.exists();
+        match std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+        {
+            Ok(_) => {
+                if !existed {
+                    let _ = std::fs::remove_file(path);
+                }
+                None
+            }
+            Err(error) => Some(format!("{} is not writable: {error}", path.display())),
+        }
+    }
+}
+
 impl Header {
     pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
         Self {
@@ -107,6 +205,13 @@ pub enum RequestBody {
     Json(String),
     FormData(HashMap<String, String>),
     MultipartFormData(Vec<MultipartField>),
+    /// GraphQL query plus its variables as raw JSON text
+    GraphQl {
+        query: String,
+        variables: String,
+    },
+    /// A file sent as the whole body, read when the request goes out
+    Binary(PathBuf),
 }
 
 #[allow(dead_code)]
@@ -117,10 +222,35 @@ impl RequestBody {
             RequestBody::Text(s) | RequestBody::Json(s) => s.is_empty(),
             RequestBody::FormData(m) => m.is_empty(),
             RequestBody::MultipartFormData(fields) => fields.is_empty(),
+            RequestBody::GraphQl { query, .. } => query.trim().is_empty(),
+            RequestBody::Binary(path) => path.as_os_str().is_empty(),
         }
     }
 }
 
+/// The Content-Type a binary body is sent with when no header sets one, from the file's
+/// extension.
+pub fn file_content_type(path: &Path) -> String {
+    mime_guess::from_path(path)
+        .first_or_octet_stream()
+        .to_string()
+}
+
+/// Build the `{"query": ..., "variables": ...}` JSON document for a GraphQL body.
+///
+/// Empty variables are sent as `{}`; anything else must be valid JSON.
+pub fn graphql_payload(query: &str, variables: &str) -> Result<String, serde_json::Error> {
+    let variables = if variables.trim().is_empty() {
+        serde_json::Value::Object(serde_json::Map::new())
+    } else {
+        serde_json::from_str(variables)?
+    };
+    serde_json::to_string(&serde_json::json!({
+        "query": query,
+        "variables": variables,
+    }))
+}
+
 /// Events emitted by RequestEntity
 #[derive(Debug, Clone)]
 pub enum RequestEvent {
@@ -128,7 +258,14 @@ pub enum RequestEvent {
     MethodChanged,
     HeadersChanged,
     BodyChanged,
+    AssertionsChanged,
+    PreRequestScriptChanged,
+    ExtractionsChanged,
+    SubprotocolsChanged,
+    AutoSaveChanged,
+    ParamsChanged,
     Sending,
+    UploadProgressed,
     Completed,
 }
 
@@ -141,6 +278,29 @@ pub struct RequestData {
     pub method: HttpMethod,
     pub headers: Vec<Header>,
     pub body: RequestBody,
+    /// Send headers exactly in editor order instead of letting the client merge them.
+    #[serde(default)]
+    pub preserve_header_order: bool,
+    /// Take auth from the owning collection's default instead of the request's own.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub inherit_auth: bool,
+    /// Checks run against each response, one per line; see `utils::run_assertions`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub assertions: String,
+    /// Sets headers and variables right before each send; see `utils::run_pre_request_script`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub pre_request_script: String,
+    /// Rules copying response values into the active environment; see `utils::run_extractions`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub extractions: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_params: Vec<DisabledParam>,
+    /// Subprotocols offered when the request opens a WebSocket, comma-separated.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub subprotocols: String,
+    /// Write each response body to a file as it arrives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_save: Option<ResponseAutoSave>,
     #[serde(skip)]
     pub is_sending: bool,
 }
@@ -154,14 +314,126 @@ impl Default for RequestData {
             method: HttpMethod::Get,
             headers: vec![Header::new("Content-Type", "application/json")],
             body: RequestBody::None,
+            preserve_header_order: false,
+            inherit_auth: false,
+            assertions: String::new(),
+            pre_request_script: String::new(),
+            extractions: String::new(),
+            disabled_params: Vec::new(),
+            subprotocols: String::new(),
+            auto_save: None,
             is_sending: false,
         }
     }
 }
 
+impl RequestData {
+    /// Whether both requests would send the same thing; ids, names and send state are ignored.
+    pub fn same_content(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.url == other.url
+            && self.headers == other.headers
+            && self.body == other.body
+            && self.preserve_header_order == other.preserve_header_order
+            && self.inherit_auth == other.inherit_auth
+            && self.assertions == other.assertions
+            && self.pre_request_script == other.pre_request_script
+            && self.extractions == other.extractions
+            && self.disabled_params == other.disabled_params
+            && self.subprotocols == other.subprotocols
+            && self.auto_save == other.auto_save
+    }
+
+    /// Render this request as a single-line `curl` command.
+    pub fn to_curl(&self) -> String {
+        let mut parts = vec!["curl".to_string()];
+        if self.method != HttpMethod::Get {
+            parts.push(format!("-X {}", self.method.as_str()));
+        }
+        parts.push(shell_quote(&self.url));
+
+        for header in &self.headers {
+            if header.enabled && !header.key.trim().is_empty() {
+                // curl drops `-H 'Key:'`; `Key;` is how it sends an empty value.
+                let line = if header.value.is_empty() {
+                    format!("{};", header.key.trim())
+                } else {
+                    format!("{}: {}", header.key.trim(), header.value)
+                };
+                parts.push(format!("-H {}", shell_quote(&line)));
+            }
+        }
+
+        match &self.body {
+            RequestBody::None => {}
+            RequestBody::Text(content) | RequestBody::Json(content) => {
+                if !content.is_empty() {
+                    parts.push(format!("--data-raw {}", shell_quote(content)));
+                }
+            }
+            RequestBody::FormData(map) => {
+                let mut pairs = map.iter().collect::<Vec<_>>();
+                pairs.sort();
+                for (key, value) in pairs {
+                    parts.push(format!(
+                        "--data-urlencode {}",
+                        shell_quote(&format!("{key}={value}"))
+                    ));
+                }
+            }
+            RequestBody::GraphQl { query, variables } => {
+                if let Ok(payload) = graphql_payload(query, variables) {
+                    parts.push(format!("--data-raw {}", shell_quote(&payload)));
+                }
+            }
+            RequestBody::MultipartFormData(fields) => {
+                for field in fields {
+                    match &field.file_path {
+                        Some(path) => parts.push(format!(
+                            "--form {}",
+                            shell_quote(&format!("{}=@{}", field.key, path))
+                        )),
+                        // --form-string keeps a leading `@` or `<` literal.
+                        None => parts.push(format!(
+                            "--form-string {}",
+                            shell_quote(&format!("{}={}", field.key, field.value))
+                        )),
+                    }
+                }
+            }
+            RequestBody::Binary(path) => {
+                if !path.as_os_str().is_empty() {
+                    parts.push(format!(
+                        "--data-binary {}",
+                        shell_quote(&format!("@{}", path.display()))
+                    ));
+                }
+            }
+        }
+
+        parts.join(" ")
+    }
+}
+
+/// Quote a value for POSIX shells, leaving plain tokens untouched.
+fn shell_quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '-' | '_' | '.' | '/' | ':' | '=' | '@' | ',' | '+' | '%')
+        });
+    if is_plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
 /// RequestEntity - GPUI Entity wrapper
 pub struct RequestEntity {
     pub data: RequestData,
+    /// File bytes uploaded and in total while a multipart send is under way
+    upload_progress: Option<(u64, u64)>,
 }
 
 #[allow(dead_code)]
@@ -169,6 +441,7 @@ impl RequestEntity {
     pub fn new() -> Self {
         Self {
             data: RequestData::default(),
+            upload_progress: None,
         }
     }
 
@@ -231,8 +504,109 @@ impl RequestEntity {
         self
     }
 
+    pub fn with_preserve_header_order(mut self, preserve: bool) -> Self {
+        self.data.preserve_header_order = preserve;
+        self
+    }
+
+    pub fn with_inherit_auth(mut self, inherit: bool) -> Self {
+        self.data.inherit_auth = inherit;
+        self
+    }
+
+    pub fn set_inherit_auth(&mut self, inherit: bool, cx: &mut Context<Self>) {
+        if self.data.inherit_auth != inherit {
+            self.data.inherit_auth = inherit;
+            cx.emit(RequestEvent::HeadersChanged);
+            cx.notify();
+        }
+    }
+
+    pub fn with_assertions(mut self, assertions: String) -> Self {
+        self.data.assertions = assertions;
+        self
+    }
+
+    pub fn set_assertions(&mut self, assertions: String, cx: &mut Context<Self>) {
+        if self.data.assertions != assertions {
+            self.data.assertions = assertions;
+            cx.emit(RequestEvent::AssertionsChanged);
+            cx.notify();
+        }
+    }
+
+    pub fn with_pre_request_script(mut self, script: String) -> Self {
+        self.data.pre_request_script = script;
+        self
+    }
+
+    pub fn set_pre_request_script(&mut self, script: String, cx: &mut Context<Self>) {
+        if self.data.pre_request_script != script {
+            self.data.pre_request_script = script;
+            cx.emit(RequestEvent::PreRequestScriptChanged);
+            cx.notify();
+        }
+    }
+
+    pub fn with_extractions(mut self, extractions: String) -> Self {
+        self.data.extractions = extractions;
+        self
+    }
+
+    pub fn set_extractions(&mut self, extractions: String, cx: &mut Context<Self>) {
+        if self.data.extractions != extractions {
+            self.data.extractions = extractions;
+            cx.emit(RequestEvent::ExtractionsChanged);
+            cx.notify();
+        }
+    }
+
+    pub fn with_subprotocols(mut self, subprotocols: String) -> Self {
+        self.data.subprotocols = subprotocols;
+        self
+    }
+
+    pub fn set_subprotocols(&mut self, subprotocols: String, cx: &mut Context<Self>) {
+        if self.data.subprotocols != subprotocols {
+            self.data.subprotocols = subprotocols;
+            cx.emit(RequestEvent::SubprotocolsChanged);
+            cx.notify();
+        }
+    }
+
+    pub fn with_disabled_params(mut self, params: Vec<DisabledParam>) -> Self {
+        self.data.disabled_params = params;
+        self
+    }
+
+    pub fn set_disabled_params(&mut self, params: Vec<DisabledParam>, cx: &mut Context<Self>) {
+        if self.data.disabled_params != params {
+            self.data.disabled_params = params;
+            cx.emit(RequestEvent::ParamsChanged);
+            cx.notify();
+        }
+    }
+
+    pub fn with_auto_save(mut self, auto_save: Option<ResponseAutoSave>) -> Self {
+        self.data.auto_save = auto_save;
+        self
+    }
+
+    pub fn set_auto_save(&mut self, auto_save: Option<ResponseAutoSave>, cx: &mut Context<Self>) {
+        self.data.auto_save = auto_save;
+        cx.emit(RequestEvent::AutoSaveChanged);
+        cx.notify();
+    }
+
+    pub fn set_preserve_header_order(&mut self, preserve: bool, cx: &mut Context<Self>) {
+        self.data.preserve_header_order = preserve;
+        cx.emit(RequestEvent::HeadersChanged);
+        cx.notify();
+    }
+
     pub fn set_sending(&mut self, sending: bool, cx: &mut Context<Self>) {
         self.data.is_sending = sending;
+        self.upload_progress = None;
         if sending {
             cx.emit(RequestEvent::Sending);
         } else {
@@ -257,9 +631,311 @@ impl RequestEntity {
         &self.data.body
     }
 
+    pub fn preserve_header_order(&self) -> bool {
+        self.data.preserve_header_order
+    }
+
+    pub fn inherit_auth(&self) -> bool {
+        self.data.inherit_auth
+    }
+
+    pub fn assertions(&self) -> &str {
+        &self.data.assertions
+    }
+
+    pub fn pre_request_script(&self) -> &str {
+        &self.data.pre_request_script
+    }
+
+    pub fn extractions(&self) -> &str {
+        &self.data.extractions
+    }
+
+    pub fn disabled_params(&self) -> &[DisabledParam] {
+        &self.data.disabled_params
+    }
+
+    pub fn subprotocols(&self) -> &str {
+        &self.data.subprotocols
+    }
+
+    pub fn auto_save(&self) -> Option<&ResponseAutoSave> {
+        self.data.auto_save.as_ref()
+    }
+
     pub fn is_sending(&self) -> bool {
         self.data.is_sending
     }
+
+    pub fn set_upload_progress(&mut self, sent: u64, total: u64, cx: &mut Context<Self>) {
+        self.upload_progress = Some((sent, total));
+        cx.emit(RequestEvent::UploadProgressed);
+        cx.notify();
+    }
+
+    /// Share of the files uploaded so far, from 0 to 1; `None` outside a multipart send.
+    pub fn upload_fraction(&self) -> Option<f32> {
+        let (sent, total) = self.upload_progress?;
+        (total > 0).then(|| (sent as f64 / total as f64).min(1.0) as f32)
+    }
 }
 
 impl EventEmitter<RequestEvent> for RequestEntity {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_curl;
+
+    #[test]
+    fn warns_about_bodies_on_get_and_head_only() {
+        assert_eq!(
+            HttpMethod::Get.body_warning(true),
+            Some("GET request with a body: many servers ignore or reject it".to_string())
+        );
+        assert!(HttpMethod::Head.body_warning(true).is_some());
+        assert_eq!(HttpMethod::Post.body_warning(true), None);
+        assert_eq!(HttpMethod::Get.body_warning(false), None);
+    }
+
+    fn request(method: HttpMethod, url: &str, body: RequestBody) -> RequestData {
+        RequestData {
+            method,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn to_curl_omits_method_for_get() {
+        let request = request(HttpMethod::Get, "https://api.test/users", RequestBody::None);
+        assert_eq!(request.to_curl(), "curl https://api.test/users");
+    }
+
+    #[test]
+    fn to_curl_quotes_values_and_skips_disabled_headers() {
+        let mut request = request(
+            HttpMethod::Post,
+            "https://api.test/users?q=a b",
+            RequestBody::Json(r#"{"name":"o'brien"}"#.to_string()),
+        );
+        let mut disabled = Header::new("X-Debug", "1");
+        disabled.enabled = false;
+        request.headers = vec![Header::new("Content-Type", "application/json"), disabled];
+
+        assert_eq!(
+            request.to_curl(),
+            r#"curl -X POST 'https://api.test/users?q=a b' -H 'Content-Type: application/json' --data-raw '{"name":"o'\''brien"}'"#
+        );
+    }
+
+    #[test]
+    fn to_curl_keeps_empty_value_headers() {
+        let mut request = request(HttpMethod::Get, "https://api.test", RequestBody::None);
+        request.headers = vec![Header::new("X-Debug", "")];
+
+        let command = request.to_curl();
+        assert_eq!(command, "curl https://api.test -H 'X-Debug;'");
+
+        let parsed = parse_curl(&command).unwrap();
+        assert_eq!(parsed.headers.len(), 1);
+        assert_eq!(parsed.headers[0].key, "X-Debug");
+        assert_eq!(parsed.headers[0].value, "");
+    }
+
+    #[test]
+    fn to_curl_emits_form_flags() {
+        let urlencoded = request(
+            HttpMethod::Post,
+            "https://api.test",
+            RequestBody::FormData(HashMap::from([
+                ("b".to_string(), "2".to_string()),
+                ("a".to_string(), "x y".to_string()),
+            ])),
+        );
+        assert_eq!(
+            urlencoded.to_curl(),
+            "curl -X POST https://api.test --data-urlencode 'a=x y' --data-urlencode b=2"
+        );
+
+        let multipart = request(
+            HttpMethod::Put,
+            "https://api.test",
+            RequestBody::MultipartFormData(vec![
+                MultipartField::text("note", "@literal"),
+                MultipartField::file("avatar", "/tmp/my avatar.png"),
+            ]),
+        );
+        assert_eq!(
+            multipart.to_curl(),
+            "curl -X PUT https://api.test --form-string note=@literal --form 'avatar=@/tmp/my avatar.png'"
+        );
+
+        let binary = request(
+            HttpMethod::Post,
+            "https://api.test",
+            RequestBody::Binary(PathBuf::from("/tmp/photo.png")),
+        );
+        assert_eq!(
+            binary.to_curl(),
+            "curl -X POST https://api.test --data-binary @/tmp/photo.png"
+        );
+    }
+
+    #[test]
+    fn graphql_payload_wraps_query_and_variables() {
+        let payload = graphql_payload("query { me { id } }", r#"{"limit": 10}"#).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(value["query"], "query { me { id } }");
+        assert_eq!(value["variables"]["limit"], 10);
+
+        let payload = graphql_payload("{ ping }", "  ").unwrap();
+        assert_eq!(payload, r#"{"query":"{ ping }","variables":{}}"#);
+
+        assert!(graphql_payload("{ ping }", "{not json").is_err());
+    }
+
+    #[test]
+    fn graphql_body_survives_a_serde_round_trip() {
+        let body = RequestBody::GraphQl {
+            query: "{ ping }".to_string(),
+            variables: r#"{"a":1}"#.to_string(),
+        };
+        let json = serde_json::to_string(&body).unwrap();
+        assert_eq!(serde_json::from_str::<RequestBody>(&json).unwrap(), body);
+    }
+
+    #[test]
+    fn assertions_and_scripts_are_stored_only_when_present() {
+        let mut request = request(HttpMethod::Get, "https://api.test", RequestBody::None);
+        assert!(
+            !serde_json::to_string(&request)
+                .unwrap()
+                .contains("assertions")
+        );
+
+        request.assertions = "status == 200".to_string();
+        let json = serde_json::to_string(&request).unwrap();
+        let restored: RequestData = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.assertions, "status == 200");
+        assert!(!json.contains("pre_request_script"));
+
+        request.pre_request_script = "header X-Id = uuid()".to_string();
+        let json = serde_json::to_string(&request).unwrap();
+        let restored: RequestData = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.pre_request_script, "header X-Id = uuid()");
+    }
+
+    #[test]
+    fn auto_save_timestamps_file_names_and_reports_unwritable_paths() {
+        use chrono::TimeZone;
+
+        let now = chrono::Local
+            .with_ymd_and_hms(2026, 10, 14, 9, 30, 5)
+            .unwrap();
+        let mut auto_save = ResponseAutoSave {
+            path: "/tmp/poll/status.json".to_string(),
+            timestamped: false,
+        };
+        assert_eq!(
+            auto_save.target_path(now),
+            PathBuf::from("/tmp/poll/status.json")
+        );
+        auto_save.timestamped = true;
+        assert_eq!(
+            auto_save.target_path(now),
+            PathBuf::from("/tmp/poll/status-20261014-093005-000.json")
+        );
+
+        let problem = |path: &str| {
+            ResponseAutoSave {
+                path: path.to_string(),
+                timestamped: false,
+            }
+            .problem()
+        };
+        assert!(problem("  ").is_some());
+        assert!(problem("responses/out.json").is_some());
+        assert!(problem("/nonexistent-setu-dir/out.json").is_some());
+        let folder = std::env::temp_dir();
+        assert!(problem(&folder.display().to_string()).is_some());
+        let probe = folder.join(format!("setu-auto-save-{}.json", Uuid::new_v4()));
+        assert_eq!(problem(&probe.display().to_string()), None);
+        assert!(
+            !probe.exists(),
+            "the writability check leaves no file behind"
+        );
+    }
+
+    #[test]
+    fn disabled_params_survive_a_round_trip_and_count_as_content() {
+        let mut request = request(
+            HttpMethod::Get,
+            "https://api.test?page=1",
+            RequestBody::None,
+        );
+        assert!(
+            !serde_json::to_string(&request)
+                .unwrap()
+                .contains("disabled_params")
+        );
+
+        let original = request.clone();
+        request.disabled_params = vec![DisabledParam {
+            key: "debug".to_string(),
+            value: "true".to_string(),
+            index: Some(1),
+        }];
+        let json = serde_json::to_string(&request).unwrap();
+        let restored: RequestData = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.disabled_params, request.disabled_params);
+        assert!(!restored.same_content(&original));
+    }
+
+    #[test]
+    fn same_content_ignores_identity_but_not_edits() {
+        let mut original = request(HttpMethod::Get, "https://api.test", RequestBody::None);
+        original.headers = vec![Header::new("Accept", "text/plain")];
+        let mut copy = original.clone();
+        copy.id = Uuid::new_v4();
+        copy.name = "Renamed".to_string();
+        assert!(original.same_content(&copy));
+
+        copy.headers[0].enabled = false;
+        assert!(!original.same_content(&copy));
+        copy.headers[0].enabled = true;
+        copy.body = RequestBody::Text("hi".to_string());
+        assert!(!original.same_content(&copy));
+        copy.body = original.body.clone();
+        copy.auto_save = Some(ResponseAutoSave {
+            path: "users.json".to_string(),
+            timestamped: false,
+        });
+        assert!(!original.same_content(&copy));
+        copy.auto_save = None;
+        copy.subprotocols = "graphql-transport-ws".to_string();
+        assert!(!original.same_content(&copy));
+
+        let restored: RequestData =
+            serde_json::from_str(&serde_json::to_string(&copy).unwrap()).unwrap();
+        assert_eq!(restored.subprotocols, "graphql-transport-ws");
+    }
+
+    #[test]
+    fn to_curl_round_trips_through_the_parser() {
+        let mut original = request(
+            HttpMethod::Patch,
+            "https://api.test/items/1",
+            RequestBody::Json(r#"{"done":true}"#.to_string()),
+        );
+        original.headers = vec![Header::new("Content-Type", "application/json")];
+
+        let parsed = parse_curl(&original.to_curl()).unwrap();
+        assert_eq!(parsed.method, HttpMethod::Patch);
+        assert_eq!(parsed.url, original.url);
+        assert_eq!(parsed.headers[0].value, "application/json");
+        assert_eq!(parsed.body, original.body);
+    }
+}
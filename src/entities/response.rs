@@ -7,6 +7,15 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+use crate::entities::Header;
+use crate::http::{SseEvent, encoding_label};
+use crate::utils::{format_size, pretty_html, pretty_json, pretty_xml};
+
+/// Oldest events are dropped once a stream's live list grows past this.
+pub const MAX_SSE_EVENTS: usize = 5_000;
+/// Raw bytes of a stream kept as its body; later bytes still produce events but are not kept.
+pub const MAX_STREAM_BODY_BYTES: usize = 8 * 1024 * 1024;
+
 fn serialize_bytes_as_base64<S>(bytes: &Bytes, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -38,11 +47,65 @@ pub enum ResponseState {
     #[default]
     Idle,
     Loading,
+    /// Headers arrived for an event stream that is still delivering events.
+    Streaming,
     Success,
     Cancelled,
     Error(String),
 }
 
+/// What the response panel's Details section shows about a failed request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorDetails {
+    /// Messages of the errors beneath the top-level one, outermost first.
+    pub causes: Vec<String>,
+    /// Label and value of each setting the request ran with.
+    pub context: Vec<(&'static str, String)>,
+}
+
+impl ErrorDetails {
+    /// Walk the source chain of `error` below its own message.
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        Self {
+            causes: error.chain().skip(1).map(ToString::to_string).collect(),
+            context: Vec::new(),
+        }
+    }
+
+    pub fn with_context(mut self, label: &'static str, value: impl Into<String>) -> Self {
+        self.context.push((label, value.into()));
+        self
+    }
+}
+
+/// A response the server marked `Content-Disposition: attachment`, meant to be saved
+/// rather than displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    /// File name suggested by the server, already stripped of any directory part.
+    pub filename: Option<String>,
+}
+
+/// HSTS lifetimes shorter than this (180 days) are flagged as weak.
+const HSTS_MIN_MAX_AGE_SECS: u64 = 180 * 24 * 60 * 60;
+
+/// How well a response sets one of the common security headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityHeaderStatus {
+    Good,
+    /// Set, but to a value that leaves a known gap.
+    Weak,
+    Missing,
+}
+
+/// One line of the security header checklist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityHeaderCheck {
+    pub header: &'static str,
+    pub status: SecurityHeaderStatus,
+    pub note: &'static str,
+}
+
 /// Content category for response body rendering
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ContentCategory {
@@ -51,6 +114,8 @@ pub enum ContentCategory {
     Xml,
     Image,
     Audio,
+    Video,
+    Pdf,
     #[default]
     Text,
     Binary,
@@ -65,6 +130,8 @@ impl ContentCategory {
             ContentCategory::Xml => "xml",
             ContentCategory::Image => "text",
             ContentCategory::Audio => "text",
+            ContentCategory::Video => "text",
+            ContentCategory::Pdf => "text",
             ContentCategory::Text => "text",
             ContentCategory::Binary => "text",
         }
@@ -78,6 +145,8 @@ impl ContentCategory {
             ContentCategory::Xml => "XML",
             ContentCategory::Image => "Image",
             ContentCategory::Audio => "Audio",
+            ContentCategory::Video => "Video",
+            ContentCategory::Pdf => "PDF",
             ContentCategory::Text => "Text",
             ContentCategory::Binary => "Binary",
         }
@@ -90,6 +159,8 @@ impl ContentCategory {
 pub enum ResponseEvent {
     Loading,
     Received,
+    /// New events arrived on an open stream.
+    StreamUpdated,
     Error(String),
     Cleared,
 }
@@ -127,8 +198,84 @@ pub struct ResponseData {
     #[serde(flatten)]
     pub payload: ResponsePayload,
     pub body_size_bytes: usize,
+    /// Bytes received on the wire when the body arrived compressed; `None` when it was not.
+    #[serde(default)]
+    pub wire_size_bytes: Option<usize>,
+    /// Why a compressed body was kept as it arrived instead of being decoded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decode_warning: Option<String>,
     pub duration_ms: u64,
     pub content_type: Option<String>,
+    /// Redirects followed before this response: each hop's status and the URL it pointed to.
+    #[serde(default)]
+    pub redirects: Vec<(u16, String)>,
+    /// Interim 1xx responses, such as 103 Early Hints, received before this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub informational: Vec<InformationalResponse>,
+    /// Whether the server answered an `Expect: 100-continue` request with a 100.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_continue: Option<ExpectContinue>,
+    /// Attempts the request took, retries included.
+    #[serde(default = "first_attempt")]
+    pub attempts: u32,
+    /// Built by a dry run: the body is the request as it would have been sent.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+/// A 1xx response the server sent ahead of the final one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InformationalResponse {
+    pub status_code: u16,
+    pub status_text: String,
+    /// In the order received; Early Hints often repeat `Link`.
+    pub headers: Vec<(String, String)>,
+}
+
+/// How a request sent with `Expect: 100-continue` fared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpectContinue {
+    /// The server sent 100 Continue before its final response.
+    Continued,
+    /// No 100 arrived. hyper does not wait for one, so the body went out regardless.
+    NotReceived,
+}
+
+impl ExpectContinue {
+    /// The outcome of a request sent with `request_headers`, or `None` when it did not ask
+    /// for a 100.
+    pub fn of(request_headers: &[Header], informational: &[InformationalResponse]) -> Option<Self> {
+        let expected = request_headers.iter().any(|header| {
+            header.enabled
+                && header.key.eq_ignore_ascii_case("expect")
+                && header.value.trim().eq_ignore_ascii_case("100-continue")
+        });
+        expected.then(|| {
+            if informational
+                .iter()
+                .any(|interim| interim.status_code == 100)
+            {
+                ExpectContinue::Continued
+            } else {
+                ExpectContinue::NotReceived
+            }
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ExpectContinue::Continued => {
+                "Expect: 100-continue honored, body sent after 100 Continue"
+            }
+            ExpectContinue::NotReceived => {
+                "Expect: 100-continue not answered, body sent without a 100 Continue"
+            }
+        }
+    }
+}
+
+fn first_attempt() -> u32 {
+    1
 }
 
 /// Cheaply cloned response text source for background display preparation.
@@ -141,7 +288,7 @@ pub struct ResponseTextSnapshot {
     body_bytes: Bytes,
     cached_raw_body: Option<Arc<str>>,
     cached_formatted_body: Option<Arc<str>>,
-    is_json: bool,
+    category: ContentCategory,
 }
 
 impl ResponseTextSnapshot {
@@ -169,17 +316,23 @@ impl ResponseTextSnapshot {
             return formatted.clone();
         }
 
-        let body = self.raw_body();
-        if !self.is_json {
-            return body;
-        }
+        format_body(self.category, self.raw_body())
+    }
+}
 
-        serde_json::from_str::<serde_json::Value>(&body)
-            .ok()
-            .and_then(|value| serde_json::to_string_pretty(&value).ok())
-            .map(Arc::<str>::from)
-            .unwrap_or(body)
+/// Pretty-print JSON, XML and HTML bodies; anything else, or markup that fails to parse, is
+/// returned as it arrived. A blank body is never parsed, whatever its type claims.
+fn format_body(category: ContentCategory, body: Arc<str>) -> Arc<str> {
+    if body.trim().is_empty() {
+        return body;
     }
+    let formatted = match category {
+        ContentCategory::Json => pretty_json(&body),
+        ContentCategory::Xml => pretty_xml(&body),
+        ContentCategory::Html => pretty_html(&body),
+        _ => None,
+    };
+    formatted.map(Arc::<str>::from).unwrap_or(body)
 }
 
 impl Default for ResponsePayload {
@@ -202,8 +355,15 @@ impl Default for ResponseData {
             headers: HashMap::new(),
             payload: ResponsePayload::default(),
             body_size_bytes: 0,
+            wire_size_bytes: None,
+            decode_warning: None,
             duration_ms: 0,
             content_type: None,
+            redirects: Vec::new(),
+            informational: Vec::new(),
+            expect_continue: None,
+            attempts: 1,
+            dry_run: false,
         }
     }
 }
@@ -264,6 +424,10 @@ impl ResponseData {
             || bytes.starts_with(b"BM")
     }
 
+    fn looks_like_pdf(bytes: &[u8]) -> bool {
+        bytes.starts_with(b"%PDF-")
+    }
+
     fn looks_like_text(bytes: &[u8]) -> bool {
         if bytes.is_empty() {
             return true;
@@ -305,13 +469,37 @@ impl ResponseData {
                 ..ResponsePayload::default()
             },
             body_size_bytes,
+            wire_size_bytes: None,
+            decode_warning: None,
             duration_ms,
             content_type,
+            redirects: Vec::new(),
+            informational: Vec::new(),
+            expect_continue: None,
+            attempts: 1,
+            dry_run: false,
         };
         response.compact_storage();
         response
     }
 
+    /// A stand-in response showing `preview`, the request a dry run did not send.
+    pub fn dry_run(preview: String) -> Self {
+        let size = preview.len();
+        let mut response = Self::new(
+            0,
+            "Dry run".to_string(),
+            HashMap::new(),
+            preview,
+            Vec::new(),
+            size,
+            0,
+            Some("text/plain".to_string()),
+        );
+        response.dry_run = true;
+        response
+    }
+
     pub fn from_bytes(
         status_code: u16,
         status_text: String,
@@ -342,8 +530,15 @@ impl ResponseData {
                 ..ResponsePayload::default()
             },
             body_size_bytes,
+            wire_size_bytes: None,
+            decode_warning: None,
             duration_ms,
             content_type,
+            redirects: Vec::new(),
+            informational: Vec::new(),
+            expect_continue: None,
+            attempts: 1,
+            dry_run: false,
         };
         response.compact_storage();
         response
@@ -369,6 +564,9 @@ impl ResponseData {
             ContentCategory::Json
         } else if ct.contains("text/html") {
             ContentCategory::Html
+        } else if ct == "image/svg+xml" {
+            // Rendered like any image; the body tab can switch to its markup.
+            ContentCategory::Image
         } else if ct.contains("application/xml") || ct.contains("text/xml") || ct.ends_with("+xml")
         {
             ContentCategory::Xml
@@ -376,10 +574,16 @@ impl ResponseData {
             ContentCategory::Image
         } else if ct.starts_with("audio/") {
             ContentCategory::Audio
+        } else if ct.starts_with("video/") {
+            ContentCategory::Video
+        } else if ct == "application/pdf" || ct == "application/x-pdf" {
+            ContentCategory::Pdf
         } else if Self::looks_like_audio(body_bytes) {
             ContentCategory::Audio
         } else if Self::looks_like_image(body_bytes) {
             ContentCategory::Image
+        } else if Self::looks_like_pdf(body_bytes) {
+            ContentCategory::Pdf
         } else if ct.starts_with("text/")
             || ct.contains("javascript")
             || ct.contains("css")
@@ -418,6 +622,21 @@ impl ResponseData {
         &self.payload.body_bytes
     }
 
+    /// The response carried nothing, as with a 204 or a HEAD, whatever its Content-Type says.
+    pub fn has_empty_body(&self) -> bool {
+        self.payload.body.is_empty() && self.payload.body_bytes.is_empty()
+    }
+
+    /// The body as written to a file: the original bytes, or the stored text for history
+    /// entries, which drop the bytes once the text is kept.
+    pub fn file_contents(&self) -> Bytes {
+        if self.payload.body_bytes.is_empty() {
+            Bytes::from(self.payload.body.to_string())
+        } else {
+            self.payload.body_bytes.clone()
+        }
+    }
+
     /// Capture the response body for CPU-heavy preparation on a worker.
     pub fn text_snapshot(&self) -> ResponseTextSnapshot {
         ResponseTextSnapshot {
@@ -425,7 +644,7 @@ impl ResponseData {
             body_bytes: self.payload.body_bytes.clone(),
             cached_raw_body: self.payload.cached_raw_body.clone(),
             cached_formatted_body: self.payload.cached_formatted_body.clone(),
-            is_json: self.is_json(),
+            category: self.content_category(),
         }
     }
 
@@ -481,6 +700,42 @@ impl ResponseData {
         had_body_bytes && self.payload.body_bytes.is_empty()
     }
 
+    /// Cut the stored body to at most `max_bytes`, ending it with a note that says so.
+    ///
+    /// Text is cut on a character boundary. Binary bodies are of no use in part, so they are
+    /// replaced by the note. Status, headers, sizes and timing are left as they are. Returns
+    /// whether the body was cut.
+    pub fn truncate_body(&mut self, max_bytes: usize) -> bool {
+        let is_text = self.payload.body_bytes.is_empty();
+        let stored_len = if is_text {
+            self.payload.body.len()
+        } else {
+            self.payload.body_bytes.len()
+        };
+        if stored_len <= max_bytes {
+            return false;
+        }
+
+        let full_size = format_size(self.body_size_bytes.max(stored_len) as u64);
+        let body = if is_text {
+            let mut cut = max_bytes;
+            while !self.payload.body.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            format!(
+                "{}\n\n[Body truncated in history: first {} of {full_size} kept]",
+                &self.payload.body[..cut],
+                format_size(max_bytes as u64)
+            )
+        } else {
+            format!("[Binary body of {full_size} not kept in history]")
+        };
+        self.payload.body = Arc::from(body);
+        self.payload.body_bytes = Bytes::new();
+        self.compact_storage();
+        true
+    }
+
     /// Detect content category from content-type header
     pub fn content_category(&self) -> ContentCategory {
         Self::classify_content(self.content_type.as_deref(), &self.payload.body_bytes)
@@ -509,6 +764,15 @@ impl ResponseData {
         self.content_category() == ContentCategory::Image
     }
 
+    /// Whether the body is an SVG image, which can be shown as markup as well as rendered.
+    pub fn is_svg(&self) -> bool {
+        self.is_image()
+            && self
+                .content_type
+                .as_deref()
+                .is_some_and(|ct| ct.to_ascii_lowercase().contains("svg"))
+    }
+
     /// Get image MIME type if this is an image
     pub fn image_mime_type(&self) -> Option<&str> {
         if self.is_image() {
@@ -518,24 +782,13 @@ impl ResponseData {
         }
     }
 
-    /// Get formatted body if JSON, otherwise raw
+    /// Get formatted body if JSON or XML, otherwise raw
     pub fn formatted_body(&mut self) -> Arc<str> {
         if let Some(ref cached) = self.payload.cached_formatted_body {
             return cached.clone();
         }
 
-        let body = self.raw_body_arc();
-        let formatted = if self.is_json() {
-            // Try to pretty-print JSON
-            match serde_json::from_str::<serde_json::Value>(&body) {
-                Ok(value) => serde_json::to_string_pretty(&value)
-                    .map(Arc::<str>::from)
-                    .unwrap_or_else(|_| body.clone()),
-                Err(_) => body.clone(),
-            }
-        } else {
-            body.clone()
-        };
+        let formatted = format_body(self.content_category(), self.raw_body_arc());
 
         self.payload.cached_formatted_body = Some(formatted.clone());
         formatted
@@ -552,30 +805,215 @@ impl ResponseData {
 
     /// Human-readable size
     pub fn formatted_size(&self) -> String {
-        let bytes = self.body_size_bytes;
-        if bytes < 1024 {
-            format!("{} B", bytes)
-        } else if bytes < 1024 * 1024 {
-            format!("{:.1} KB", bytes as f64 / 1024.0)
-        } else {
-            format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+        format_size(self.body_size_bytes as u64)
+    }
+
+    /// Human-readable size including the on-wire size of compressed bodies,
+    /// e.g. "12.3 KB (4.1 KB gzipped)".
+    pub fn formatted_size_with_wire(&self) -> String {
+        match self.wire_size_bytes {
+            Some(wire) => format!(
+                "{} ({} {})",
+                self.formatted_size(),
+                format_size(wire as u64),
+                encoding_label(self.headers.get("content-encoding").map(String::as_str))
+            ),
+            None => self.formatted_size(),
+        }
+    }
+
+    /// Number of pages in a PDF body, counted from its page objects.
+    ///
+    /// Returns `None` when no page objects are visible, as happens when a PDF keeps them
+    /// in compressed object streams.
+    pub fn pdf_page_count(&self) -> Option<usize> {
+        let bytes = self.payload.body_bytes.as_ref();
+        let mut pages = 0;
+        let mut rest = bytes;
+        while let Some(start) = find_bytes(rest, b"/Type") {
+            rest = &rest[start + b"/Type".len()..];
+            let value = rest.trim_ascii_start();
+            if let Some(after) = value.strip_prefix(b"/Page")
+                && !after.first().is_some_and(u8::is_ascii_alphanumeric)
+            {
+                pages += 1;
+            }
         }
+        (pages > 0).then_some(pages)
+    }
+
+    /// The attachment this response carries, if its `Content-Disposition` asks for a download.
+    pub fn attachment(&self) -> Option<Attachment> {
+        parse_content_disposition(self.headers.get("content-disposition")?)
+    }
+
+    /// Check the headers browsers use to harden a site, like a small security linter.
+    pub fn security_header_checks(&self) -> Vec<SecurityHeaderCheck> {
+        use SecurityHeaderStatus::{Good, Missing, Weak};
+
+        let header = |name: &str| {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim().to_ascii_lowercase())
+        };
+        let check = |header, status, note| SecurityHeaderCheck {
+            header,
+            status,
+            note,
+        };
+        let csp = header("content-security-policy");
+
+        let hsts = match header("strict-transport-security") {
+            None => check(
+                "Strict-Transport-Security",
+                Missing,
+                "Browsers may still reach the site over plain HTTP",
+            ),
+            Some(value) => {
+                let max_age = value.split(';').find_map(|directive| {
+                    directive
+                        .trim()
+                        .strip_prefix("max-age=")?
+                        .trim_matches('"')
+                        .parse::<u64>()
+                        .ok()
+                });
+                match max_age {
+                    Some(age) if age >= HSTS_MIN_MAX_AGE_SECS => {
+                        check("Strict-Transport-Security", Good, "HTTPS is enforced")
+                    }
+                    Some(_) => check(
+                        "Strict-Transport-Security",
+                        Weak,
+                        "max-age is under 180 days",
+                    ),
+                    None => check("Strict-Transport-Security", Weak, "No valid max-age"),
+                }
+            }
+        };
+
+        let csp_check = match (&csp, header("content-security-policy-report-only")) {
+            (Some(policy), _)
+                if policy.contains("'unsafe-inline'") || policy.contains("'unsafe-eval'") =>
+            {
+                check(
+                    "Content-Security-Policy",
+                    Weak,
+                    "Allows 'unsafe-inline' or 'unsafe-eval' scripts",
+                )
+            }
+            (Some(_), _) => check(
+                "Content-Security-Policy",
+                Good,
+                "Restricts where content loads from",
+            ),
+            (None, Some(_)) => check(
+                "Content-Security-Policy",
+                Weak,
+                "Only the report-only policy is set; nothing is blocked",
+            ),
+            (None, None) => check(
+                "Content-Security-Policy",
+                Missing,
+                "Nothing limits injected scripts",
+            ),
+        };
+
+        let nosniff = match header("x-content-type-options") {
+            Some(value) if value == "nosniff" => check(
+                "X-Content-Type-Options",
+                Good,
+                "Content types are not sniffed",
+            ),
+            Some(_) => check("X-Content-Type-Options", Weak, "Only nosniff has an effect"),
+            None => check(
+                "X-Content-Type-Options",
+                Missing,
+                "Browsers may guess the content type",
+            ),
+        };
+
+        let frame_ancestors = csp
+            .as_deref()
+            .is_some_and(|policy| policy.contains("frame-ancestors"));
+        let framing = match header("x-frame-options") {
+            Some(value) if value == "deny" || value == "sameorigin" => {
+                check("X-Frame-Options", Good, "Framing by other sites is blocked")
+            }
+            _ if frame_ancestors => check(
+                "X-Frame-Options",
+                Good,
+                "Framing is limited by the CSP frame-ancestors directive",
+            ),
+            Some(_) => check(
+                "X-Frame-Options",
+                Weak,
+                "Only DENY and SAMEORIGIN are supported",
+            ),
+            None => check(
+                "X-Frame-Options",
+                Missing,
+                "Other sites can embed the page (clickjacking)",
+            ),
+        };
+
+        let referrer = match header("referrer-policy") {
+            Some(value)
+                if value.split(',').next_back().is_some_and(|policy| {
+                    matches!(policy.trim(), "unsafe-url" | "no-referrer-when-downgrade")
+                }) =>
+            {
+                check(
+                    "Referrer-Policy",
+                    Weak,
+                    "Full URLs are sent to other origins",
+                )
+            }
+            Some(_) => check(
+                "Referrer-Policy",
+                Good,
+                "Limits the URL shared with other sites",
+            ),
+            None => check(
+                "Referrer-Policy",
+                Missing,
+                "The browser default decides what is shared",
+            ),
+        };
+
+        vec![hsts, csp_check, nosniff, framing, referrer]
     }
 
     /// Human-readable duration
     pub fn formatted_duration(&self) -> String {
-        if self.duration_ms < 1000 {
-            format!("{} ms", self.duration_ms)
-        } else {
-            format!("{:.2} s", self.duration_ms as f64 / 1000.0)
-        }
+        format_duration_ms(self.duration_ms)
     }
 }
 
+/// The retry a loading response is waiting on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryStatus {
+    pub attempt: u32,
+    pub max_attempts: u32,
+    /// Why the previous attempt failed.
+    pub reason: String,
+}
+
 /// ResponseEntity - GPUI Entity wrapper
 pub struct ResponseEntity {
     pub state: ResponseState,
     pub data: Option<ResponseData>,
+    /// Events of the latest event-stream response, kept after the stream ends.
+    sse_events: Arc<Vec<SseEvent>>,
+    /// Raw stream body so far, used as the final body when the user stops the stream.
+    stream_body: Vec<u8>,
+    /// Set once `stream_body` reached [`MAX_STREAM_BODY_BYTES`] and stopped growing.
+    stream_truncated: bool,
+    /// Cause chain and request settings behind the current error, if it kept any.
+    error_details: Option<ErrorDetails>,
+    /// Set while the request backs off before another attempt.
+    retry_status: Option<RetryStatus>,
 }
 
 #[allow(dead_code)]
@@ -584,16 +1022,36 @@ impl ResponseEntity {
         Self {
             state: ResponseState::Idle,
             data: None,
+            sse_events: Arc::new(Vec::new()),
+            stream_body: Vec::new(),
+            stream_truncated: false,
+            error_details: None,
+            retry_status: None,
         }
     }
 
     pub fn set_loading(&mut self, cx: &mut Context<Self>) {
         self.state = ResponseState::Loading;
         self.data = None;
+        self.clear_stream();
+        self.retry_status = None;
         cx.emit(ResponseEvent::Loading);
         cx.notify();
     }
 
+    /// Note that the request is about to be tried again; ignored once it has finished.
+    pub fn set_retrying(&mut self, status: RetryStatus, cx: &mut Context<Self>) {
+        if !self.is_loading() {
+            return;
+        }
+        self.retry_status = Some(status);
+        cx.notify();
+    }
+
+    pub fn retry_status(&self) -> Option<&RetryStatus> {
+        self.retry_status.as_ref().filter(|_| self.is_loading())
+    }
+
     pub fn set_response(&mut self, data: ResponseData, cx: &mut Context<Self>) {
         self.state = ResponseState::Success;
         self.data = Some(data);
@@ -609,10 +1067,76 @@ impl ResponseEntity {
     pub fn set_error(&mut self, error: String, cx: &mut Context<Self>) {
         self.state = ResponseState::Error(error.clone());
         self.data = None;
+        self.error_details = None;
         cx.emit(ResponseEvent::Error(error));
         cx.notify();
     }
 
+    /// Fail with a concise `error` and the details behind it, shown on request.
+    pub fn set_error_with_details(
+        &mut self,
+        error: String,
+        details: ErrorDetails,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_error(error, cx);
+        self.error_details = Some(details);
+    }
+
+    /// Show the status and headers of an event stream and start collecting its events.
+    pub fn set_streaming(&mut self, head: ResponseData, cx: &mut Context<Self>) {
+        self.state = ResponseState::Streaming;
+        self.data = Some(head);
+        self.clear_stream();
+        cx.emit(ResponseEvent::Received);
+        cx.notify();
+    }
+
+    pub fn push_stream_chunk(&mut self, raw: &[u8], events: Vec<SseEvent>, cx: &mut Context<Self>) {
+        if !self.is_streaming() {
+            return;
+        }
+        self.stream_truncated |= push_capped_stream_body(&mut self.stream_body, raw);
+        push_capped_events(Arc::make_mut(&mut self.sse_events), events);
+        cx.emit(ResponseEvent::StreamUpdated);
+        cx.notify();
+    }
+
+    /// Stop the response in progress. A stream keeps what it received; anything else is
+    /// marked cancelled.
+    pub fn stop(&mut self, cx: &mut Context<Self>) {
+        let Some(head) = self.data.take().filter(|_| self.is_streaming()) else {
+            self.set_cancelled(cx);
+            return;
+        };
+        let mut body = std::mem::take(&mut self.stream_body);
+        if std::mem::take(&mut self.stream_truncated) {
+            body.extend_from_slice(stream_truncated_marker().as_bytes());
+        }
+        let body = Bytes::from(body);
+        let mut data = ResponseData::from_bytes(
+            head.status_code,
+            head.status_text,
+            head.headers,
+            body,
+            head.duration_ms,
+            head.content_type,
+        );
+        data.redirects = head.redirects;
+        self.set_response(data, cx);
+    }
+
+    fn clear_stream(&mut self) {
+        Arc::make_mut(&mut self.sse_events).clear();
+        self.stream_body.clear();
+        self.stream_truncated = false;
+    }
+
+    /// Whether the live stream outgrew [`MAX_STREAM_BODY_BYTES`], so its body is incomplete.
+    pub fn stream_truncated(&self) -> bool {
+        self.stream_truncated
+    }
+
     pub fn set_cancelled(&mut self, cx: &mut Context<Self>) {
         self.state = ResponseState::Cancelled;
         self.data = None;
@@ -631,6 +1155,14 @@ impl ResponseEntity {
         matches!(self.state, ResponseState::Loading)
     }
 
+    pub fn is_streaming(&self) -> bool {
+        matches!(self.state, ResponseState::Streaming)
+    }
+
+    pub fn sse_events(&self) -> &Arc<Vec<SseEvent>> {
+        &self.sse_events
+    }
+
     pub fn is_success(&self) -> bool {
         matches!(self.state, ResponseState::Success)
     }
@@ -639,6 +1171,10 @@ impl ResponseEntity {
         matches!(self.state, ResponseState::Error(_))
     }
 
+    pub fn error_details(&self) -> Option<&ErrorDetails> {
+        self.error_details.as_ref()
+    }
+
     pub fn error_message(&self) -> Option<&str> {
         match &self.state {
             ResponseState::Error(msg) => Some(msg),
@@ -649,9 +1185,92 @@ impl ResponseEntity {
 
 impl EventEmitter<ResponseEvent> for ResponseEntity {}
 
+pub fn format_duration_ms(duration_ms: u64) -> String {
+    if duration_ms < 1000 {
+        format!("{} ms", duration_ms)
+    } else {
+        format!("{:.2} s", duration_ms as f64 / 1000.0)
+    }
+}
+
+/// Parse an `attachment` disposition, preferring the RFC 5987 `filename*` parameter over the
+/// plain `filename`.
+fn parse_content_disposition(value: &str) -> Option<Attachment> {
+    let mut parts = value.split(';');
+    if !parts.next()?.trim().eq_ignore_ascii_case("attachment") {
+        return None;
+    }
+
+    let mut filename = None;
+    let mut extended = None;
+    for part in parts {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename" => {
+                filename = Some(value.trim_matches('"').replace("\\\"", "\""));
+            }
+            "filename*" => {
+                // `charset'language'percent-encoded`; only UTF-8 and ASCII are in use.
+                extended = value
+                    .splitn(3, '\'')
+                    .nth(2)
+                    .and_then(|encoded| urlencoding::decode(encoded).ok())
+                    .map(|decoded| decoded.into_owned());
+            }
+            _ => {}
+        }
+    }
+
+    // Servers do not get to pick the directory the file lands in.
+    let filename = extended
+        .or(filename)
+        .and_then(|name| name.rsplit(['/', '\\']).next().map(str::to_string))
+        .filter(|name| !name.is_empty() && name != "." && name != "..");
+    Some(Attachment { filename })
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Append `raw` to a stream body, keeping at most [`MAX_STREAM_BODY_BYTES`]. Returns whether
+/// bytes had to be left out.
+pub fn push_capped_stream_body(body: &mut Vec<u8>, raw: &[u8]) -> bool {
+    let room = MAX_STREAM_BODY_BYTES.saturating_sub(body.len());
+    body.extend_from_slice(&raw[..raw.len().min(room)]);
+    raw.len() > room
+}
+
+/// Appended to a stream body that was cut at [`MAX_STREAM_BODY_BYTES`].
+pub fn stream_truncated_marker() -> String {
+    format!(
+        "\n\n[Stream body truncated: first {} kept]",
+        format_size(MAX_STREAM_BODY_BYTES as u64)
+    )
+}
+
+fn push_capped_events(list: &mut Vec<SseEvent>, events: Vec<SseEvent>) {
+    list.extend(events);
+    if list.len() > MAX_SSE_EVENTS {
+        let overflow = list.len() - MAX_SSE_EVENTS;
+        list.drain(..overflow);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ContentCategory, ResponseData};
+    use super::{
+        Attachment, ContentCategory, ErrorDetails, ExpectContinue, InformationalResponse,
+        MAX_SSE_EVENTS, MAX_STREAM_BODY_BYTES, ResponseData, SecurityHeaderStatus,
+        parse_content_disposition, push_capped_events, push_capped_stream_body,
+    };
+    use crate::entities::Header;
+    use crate::http::SseEvent;
     use base64::{Engine as _, engine::general_purpose::STANDARD};
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -670,6 +1289,42 @@ mod tests {
         )
     }
 
+    #[test]
+    fn error_details_list_the_causes_below_the_message() {
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "connection refused");
+        let error = anyhow::Error::new(io)
+            .context("error trying to connect")
+            .context("error sending request");
+        let details = ErrorDetails::from_error(&error).with_context("URL", "http://localhost:1");
+
+        assert_eq!(error.to_string(), "error sending request");
+        assert_eq!(
+            details.causes,
+            ["error trying to connect", "connection refused"]
+        );
+        assert_eq!(details.context, [("URL", "http://localhost:1".to_string())]);
+    }
+
+    #[test]
+    fn dry_run_responses_show_the_preview_as_text() {
+        let response = ResponseData::dry_run("GET https://api.test/ HTTP/1.1".to_string());
+        assert!(response.dry_run);
+        assert_eq!(response.content_category(), ContentCategory::Text);
+        assert_eq!(response.body_size_bytes, 30);
+        let plain = serde_json::to_string(&response_with(None, Vec::new())).unwrap();
+        assert!(!plain.contains("dry_run"));
+    }
+
+    #[test]
+    fn classifies_svg_as_an_image_not_xml() {
+        let data = response_with(Some("image/svg+xml; charset=utf-8"), b"<svg/>".to_vec());
+        assert_eq!(data.content_category(), ContentCategory::Image);
+        assert!(data.is_svg());
+        let feed = response_with(Some("application/atom+xml"), b"<feed/>".to_vec());
+        assert_eq!(feed.content_category(), ContentCategory::Xml);
+        assert!(!feed.is_svg());
+    }
+
     #[test]
     fn classifies_audio_from_content_type() {
         let data = response_with(Some("audio/mpeg"), b"not-important".to_vec());
@@ -685,12 +1340,59 @@ mod tests {
         assert_eq!(data.content_category(), ContentCategory::Audio);
     }
 
+    #[test]
+    fn classifies_video_from_content_type() {
+        let data = response_with(Some("video/mp4"), b"\0\0\0\x18ftypisom".to_vec());
+        assert_eq!(data.content_category(), ContentCategory::Video);
+    }
+
     #[test]
     fn classifies_unknown_non_text_without_header_as_binary() {
         let data = response_with(None, vec![0x00, 0x9F, 0x92, 0x00, 0xFF]);
         assert_eq!(data.content_category(), ContentCategory::Binary);
     }
 
+    #[test]
+    fn classifies_pdf_and_counts_its_pages() {
+        let pdf = b"%PDF-1.4\n1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj\n\
+            2 0 obj << /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >> endobj\n\
+            3 0 obj << /Type /Page /Parent 2 0 R >> endobj\n\
+            4 0 obj << /Type/Page /Parent 2 0 R >> endobj\n%%EOF"
+            .to_vec();
+        let data = response_with(Some("application/pdf"), pdf.clone());
+        assert_eq!(data.content_category(), ContentCategory::Pdf);
+        assert_eq!(data.pdf_page_count(), Some(2));
+
+        let sniffed = response_with(Some("application/octet-stream"), pdf);
+        assert_eq!(sniffed.content_category(), ContentCategory::Pdf);
+
+        let compressed = response_with(Some("application/pdf"), b"%PDF-1.7\n".to_vec());
+        assert_eq!(compressed.pdf_page_count(), None);
+    }
+
+    #[test]
+    fn truncates_large_bodies_with_a_note() {
+        let mut small = response_with(Some("text/plain"), b"short".to_vec());
+        assert!(!small.truncate_body(1024));
+        assert_eq!(small.body(), "short");
+
+        let mut text = response_with(Some("text/plain"), "é".repeat(1024).into_bytes());
+        let before = text.body_hash();
+        assert!(text.truncate_body(1023));
+        assert!(text.body().starts_with(&"é".repeat(511)));
+        assert!(
+            text.body()
+                .ends_with("[Body truncated in history: first 1023 B of 2.0 KB kept]")
+        );
+        assert_eq!(text.body_size_bytes, 2048);
+        assert_ne!(text.body_hash(), before);
+
+        let mut image = response_with(Some("image/png"), vec![0x89; 4096]);
+        assert!(image.truncate_body(1024));
+        assert!(image.body_bytes().is_empty());
+        assert_eq!(image.body(), "[Binary body of 4.0 KB not kept in history]");
+    }
+
     #[test]
     fn compacts_text_responses_by_dropping_duplicate_raw_bytes() {
         let data = response_with(Some("application/json"), br#"{"ok":true}"#.to_vec());
@@ -760,4 +1462,189 @@ mod tests {
         assert_eq!(&*formatted, "{\n  \"a\": 1\n}");
         assert_eq!(&*data.raw_body(), r#"{"a":1}"#);
     }
+
+    #[test]
+    fn empty_bodies_are_not_formatted_as_their_declared_type() {
+        let mut data = response_with(Some("application/json"), Vec::new());
+        assert!(data.has_empty_body());
+        assert_eq!(&*data.formatted_body(), "");
+        assert_eq!(&*data.text_snapshot().formatted_body(), "");
+
+        let mut blank = response_with(Some("application/xml"), b"\n".to_vec());
+        assert!(!blank.has_empty_body());
+        assert_eq!(&*blank.formatted_body(), "\n");
+    }
+
+    #[test]
+    fn formats_json_once_per_response() {
+        let mut data = response_with(Some("application/json"), br#"{"a":[1,2]}"#.to_vec());
+        let first = data.formatted_body();
+        let second = data.formatted_body();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn worker_prepared_body_is_reused_by_later_snapshots() {
+        let mut data = response_with(Some("application/json"), br#"{"a":1}"#.to_vec());
+        let prepared = data.text_snapshot().formatted_body();
+        assert!(data.cache_prepared_body(data.body_hash(), true, prepared.clone()));
+
+        assert!(Arc::ptr_eq(
+            &data.text_snapshot().formatted_body(),
+            &prepared
+        ));
+        assert!(Arc::ptr_eq(&data.formatted_body(), &prepared));
+    }
+
+    #[test]
+    fn rejects_prepared_body_for_a_different_response() {
+        let mut data = response_with(Some("application/json"), br#"{"a":1}"#.to_vec());
+        let stale_hash = data.body_hash().wrapping_add(1);
+        assert!(!data.cache_prepared_body(stale_hash, true, Arc::from("stale")));
+        assert_eq!(&*data.formatted_body(), "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn shows_wire_size_only_for_compressed_bodies() {
+        let mut data = response_with(Some("text/plain"), vec![b'a'; 12_595]);
+        data.body_size_bytes = 12_595;
+        assert_eq!(data.formatted_size_with_wire(), "12.3 KB");
+
+        data.headers
+            .insert("content-encoding".to_string(), "gzip".to_string());
+        data.wire_size_bytes = Some(4_198);
+        assert_eq!(data.formatted_size_with_wire(), "12.3 KB (4.1 KB gzipped)");
+    }
+
+    #[test]
+    fn pretty_prints_xml_bodies_and_keeps_raw_text() {
+        let xml = r#"<feed id="1"><entry><![CDATA[a < b]]></entry></feed>"#;
+        let mut data = response_with(Some("application/atom+xml"), xml.as_bytes().to_vec());
+        let formatted = data.formatted_body();
+        assert!(formatted.contains("\n  <entry>"));
+        assert!(formatted.contains("<![CDATA[a < b]]>"));
+        assert_eq!(&*data.raw_body(), xml);
+        assert_eq!(data.text_snapshot().formatted_body(), formatted);
+
+        let mut broken = response_with(Some("text/xml"), b"<a><b></a>".to_vec());
+        assert_eq!(&*broken.formatted_body(), "<a><b></a>");
+    }
+
+    #[test]
+    fn parses_attachment_dispositions() {
+        let filename = |value: &str| parse_content_disposition(value).map(|a| a.filename);
+
+        assert_eq!(
+            filename(r#"attachment; filename="report 2024.pdf""#),
+            Some(Some("report 2024.pdf".to_string()))
+        );
+        assert_eq!(
+            filename("Attachment; filename=plain.txt; filename*=UTF-8''na%C3%AFve.csv"),
+            Some(Some("naïve.csv".to_string()))
+        );
+        assert_eq!(
+            filename(r#"attachment; filename="../../etc/passwd""#),
+            Some(Some("passwd".to_string()))
+        );
+        assert_eq!(filename("attachment"), Some(None));
+        assert_eq!(filename(r#"inline; filename="shown.png""#), None);
+
+        let mut data = response_with(Some("application/pdf"), b"%PDF".to_vec());
+        assert_eq!(data.attachment(), None);
+        data.headers.insert(
+            "content-disposition".to_string(),
+            "attachment; filename=a.pdf".to_string(),
+        );
+        assert_eq!(
+            data.attachment(),
+            Some(Attachment {
+                filename: Some("a.pdf".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn stream_event_list_drops_oldest_past_the_cap() {
+        let event = |data: String| SseEvent {
+            event: "message".to_string(),
+            data,
+            id: None,
+            received_at: chrono::Local::now(),
+        };
+        let mut events: Vec<_> = (0..MAX_SSE_EVENTS).map(|i| event(i.to_string())).collect();
+        push_capped_events(&mut events, vec![event("a".into()), event("b".into())]);
+
+        assert_eq!(events.len(), MAX_SSE_EVENTS);
+        assert_eq!(events[0].data, "2");
+        assert_eq!(events.last().unwrap().data, "b");
+    }
+
+    #[test]
+    fn records_whether_an_expect_request_got_its_continue() {
+        let expect = [Header::new("Expect", "100-continue")];
+        let continued = InformationalResponse {
+            status_code: 100,
+            status_text: "Continue".to_string(),
+            headers: Vec::new(),
+        };
+        assert_eq!(
+            ExpectContinue::of(&expect, &[continued.clone()]),
+            Some(ExpectContinue::Continued)
+        );
+        assert_eq!(
+            ExpectContinue::of(&expect, &[]),
+            Some(ExpectContinue::NotReceived)
+        );
+        assert_eq!(ExpectContinue::of(&[], &[continued]), None);
+    }
+
+    #[test]
+    fn stream_body_stops_growing_at_the_cap() {
+        let mut body = vec![b'a'; MAX_STREAM_BODY_BYTES - 2];
+        assert!(!push_capped_stream_body(&mut body, b"bc"));
+        assert!(push_capped_stream_body(&mut body, b"d"));
+        assert_eq!(body.len(), MAX_STREAM_BODY_BYTES);
+        assert!(body.ends_with(b"bc"));
+    }
+
+    #[test]
+    fn grades_security_headers_by_presence_and_value() {
+        let mut data = response_with(Some("text/html"), b"<html></html>".to_vec());
+        let statuses = |data: &ResponseData| -> Vec<_> {
+            data.security_header_checks()
+                .into_iter()
+                .map(|check| (check.header, check.status))
+                .collect()
+        };
+        assert!(
+            statuses(&data)
+                .iter()
+                .all(|(_, status)| *status == SecurityHeaderStatus::Missing)
+        );
+
+        for (key, value) in [
+            (
+                "strict-transport-security",
+                "max-age=3600; includeSubDomains",
+            ),
+            (
+                "content-security-policy",
+                "default-src 'self'; frame-ancestors 'none'",
+            ),
+            ("x-content-type-options", "nosniff"),
+            ("referrer-policy", "no-referrer, unsafe-url"),
+        ] {
+            data.headers.insert(key.to_string(), value.to_string());
+        }
+        assert_eq!(
+            statuses(&data),
+            vec![
+                ("Strict-Transport-Security", SecurityHeaderStatus::Weak),
+                ("Content-Security-Policy", SecurityHeaderStatus::Good),
+                ("X-Content-Type-Options", SecurityHeaderStatus::Good),
+                ("X-Frame-Options", SecurityHeaderStatus::Good),
+                ("Referrer-Policy", SecurityHeaderStatus::Weak),
+            ]
+        );
+    }
 }
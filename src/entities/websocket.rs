@@ -0,0 +1,280 @@
+use bytes::Bytes;
+use chrono::{DateTime, Local};
+use gpui::{Context, EventEmitter, Task};
+
+use crate::http::{WsClient, WsClientEvent, WsConnection};
+
+use super::Header;
+
+/// Oldest frames are dropped once a session's log grows past this.
+pub const MAX_WS_MESSAGES: usize = 5_000;
+
+/// Which side of the socket a frame came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WsDirection {
+    Sent,
+    Received,
+}
+
+/// Payload of a WebSocket data frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsPayload {
+    Text(String),
+    Binary(Bytes),
+}
+
+impl WsPayload {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Text(text) => text.len(),
+            Self::Binary(data) => data.len(),
+        }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::Binary(_))
+    }
+
+    /// Text frames copy verbatim; binary frames copy as space-separated hex bytes.
+    pub fn copy_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Binary(data) => data
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// Single-line summary for the message log.
+    pub fn preview(&self, max_chars: usize) -> String {
+        let text = self.copy_text().replace(['\r', '\n'], " ");
+        if text.chars().count() > max_chars {
+            let truncated: String = text.chars().take(max_chars).collect();
+            format!("{}…", truncated)
+        } else {
+            text
+        }
+    }
+}
+
+/// A timestamped frame in a WebSocket session log.
+#[derive(Debug, Clone)]
+pub struct WsMessage {
+    pub direction: WsDirection,
+    pub payload: WsPayload,
+    pub timestamp: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum WsConnectionState {
+    #[default]
+    Idle,
+    Connecting,
+    Connected,
+    /// Closed by either side, with the peer's close code and reason when it sent one.
+    Closed(Option<String>),
+    Failed(String),
+}
+
+impl WsConnectionState {
+    /// Connecting or connected; the connect button acts as disconnect.
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Connecting | Self::Connected)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum WebSocketEvent {
+    StateChanged,
+    MessagesChanged,
+}
+
+/// Live WebSocket session for a tab: connection state plus the frame log.
+pub struct WebSocketEntity {
+    state: WsConnectionState,
+    /// Subprotocol the server agreed to on the current connection.
+    protocol: Option<String>,
+    messages: Vec<WsMessage>,
+    connection: Option<WsConnection>,
+    _events_task: Option<Task<()>>,
+}
+
+impl WebSocketEntity {
+    pub fn new() -> Self {
+        Self {
+            state: WsConnectionState::Idle,
+            protocol: None,
+            messages: Vec::new(),
+            connection: None,
+            _events_task: None,
+        }
+    }
+
+    pub fn connect(
+        &mut self,
+        client: &WsClient,
+        url: &str,
+        headers: &[Header],
+        subprotocols: &[String],
+        cx: &mut Context<Self>,
+    ) {
+        let (mut events, connection) = client.connect(url, headers, subprotocols);
+        self.connection = Some(connection);
+        self.protocol = None;
+        self.set_state(WsConnectionState::Connecting, cx);
+
+        // Replacing the task drops any previous session's event stream.
+        self._events_task = Some(cx.spawn(async move |this, cx| {
+            while let Some(event) = events.recv().await {
+                if this
+                    .update(cx, |socket, cx| socket.handle_event(event, cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Close the socket; the close frame is sent when the connection handle drops.
+    pub fn disconnect(&mut self, cx: &mut Context<Self>) {
+        if self.connection.take().is_some() {
+            self._events_task = None;
+            self.set_state(WsConnectionState::Closed(None), cx);
+        }
+    }
+
+    /// Record a failure that happened before a connection could be attempted.
+    pub fn fail(&mut self, error: String, cx: &mut Context<Self>) {
+        self.connection = None;
+        self._events_task = None;
+        self.set_state(WsConnectionState::Failed(error), cx);
+    }
+
+    /// Send a data frame and log it; returns `false` when no connection is open.
+    pub fn send(&mut self, payload: WsPayload, cx: &mut Context<Self>) -> bool {
+        if self.state != WsConnectionState::Connected {
+            return false;
+        }
+        let Some(connection) = &self.connection else {
+            return false;
+        };
+        if !connection.send(payload.clone()) {
+            return false;
+        }
+        self.push_message(WsDirection::Sent, payload, cx);
+        true
+    }
+
+    pub fn clear_messages(&mut self, cx: &mut Context<Self>) {
+        self.messages.clear();
+        cx.emit(WebSocketEvent::MessagesChanged);
+        cx.notify();
+    }
+
+    pub fn state(&self) -> &WsConnectionState {
+        &self.state
+    }
+
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    pub fn messages(&self) -> &[WsMessage] {
+        &self.messages
+    }
+
+    fn handle_event(&mut self, event: WsClientEvent, cx: &mut Context<Self>) {
+        match event {
+            WsClientEvent::Connected(protocol) => {
+                self.protocol = protocol;
+                self.set_state(WsConnectionState::Connected, cx);
+            }
+            WsClientEvent::Message(payload) => {
+                self.push_message(WsDirection::Received, payload, cx)
+            }
+            WsClientEvent::Closed(reason) => {
+                self.connection = None;
+                self.set_state(WsConnectionState::Closed(reason), cx);
+            }
+            WsClientEvent::Failed(error) => {
+                self.connection = None;
+                self.set_state(WsConnectionState::Failed(error), cx);
+            }
+        }
+    }
+
+    fn push_message(&mut self, direction: WsDirection, payload: WsPayload, cx: &mut Context<Self>) {
+        push_capped(
+            &mut self.messages,
+            WsMessage {
+                direction,
+                payload,
+                timestamp: Local::now(),
+            },
+        );
+        cx.emit(WebSocketEvent::MessagesChanged);
+        cx.notify();
+    }
+
+    fn set_state(&mut self, state: WsConnectionState, cx: &mut Context<Self>) {
+        self.state = state;
+        cx.emit(WebSocketEvent::StateChanged);
+        cx.notify();
+    }
+}
+
+impl EventEmitter<WebSocketEvent> for WebSocketEntity {}
+
+fn push_capped(messages: &mut Vec<WsMessage>, message: WsMessage) {
+    if messages.len() >= MAX_WS_MESSAGES {
+        let overflow = messages.len() + 1 - MAX_WS_MESSAGES;
+        messages.drain(..overflow);
+    }
+    messages.push(message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(text: &str) -> WsMessage {
+        WsMessage {
+            direction: WsDirection::Received,
+            payload: WsPayload::Text(text.to_string()),
+            timestamp: Local::now(),
+        }
+    }
+
+    #[test]
+    fn binary_payloads_copy_as_hex() {
+        let payload = WsPayload::Binary(Bytes::from_static(&[0x00, 0xab, 0x10]));
+        assert_eq!(payload.copy_text(), "00 ab 10");
+        assert_eq!(payload.len(), 3);
+        assert!(payload.is_binary());
+    }
+
+    #[test]
+    fn previews_are_single_line_and_truncated() {
+        let payload = WsPayload::Text("line one\nline two".to_string());
+        assert_eq!(payload.preview(100), "line one line two");
+        assert_eq!(payload.preview(4), "line…");
+    }
+
+    #[test]
+    fn log_drops_oldest_frames_past_the_cap() {
+        let mut messages: Vec<_> = (0..MAX_WS_MESSAGES)
+            .map(|index| message(&index.to_string()))
+            .collect();
+        push_capped(&mut messages, message("newest"));
+
+        assert_eq!(messages.len(), MAX_WS_MESSAGES);
+        assert_eq!(messages[0].payload, WsPayload::Text("1".to_string()));
+        assert_eq!(
+            messages.last().unwrap().payload,
+            WsPayload::Text("newest".to_string())
+        );
+    }
+}
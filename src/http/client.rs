@@ -1,18 +1,216 @@
-use crate::entities::{Header, HttpMethod, RequestBody, ResponseData};
-use crate::utils::shared_tokio_runtime;
+use super::cookies::{COOKIE_SCOPE, CookieJar};
+use super::decompress::{Decoded, MAX_DECODED_BODY_BYTES, decode_content};
+use super::informational::InformationalLog;
+use super::pool::PoolConfig;
+use super::proxy::ProxyConfig;
+use super::retry::{RetryPolicy, run_with_retries};
+use super::sigv4::{AwsSigV4, sign_request};
+use super::sse::{SseEvent, SseParser, is_event_stream};
+use super::tls::TlsConfig;
+use super::upload::{UploadTracker, tracked_stream};
+use crate::entities::{
+    ExpectContinue, Header, HttpMethod, RequestBody, ResponseData, file_content_type,
+    graphql_payload, push_capped_stream_body, stream_truncated_marker,
+};
+use crate::utils::{format_size, shared_tokio_runtime};
 use anyhow::{Result, anyhow};
-use reqwest::Client;
+use bytes::Bytes;
+use chrono::Utc;
+use reqwest::header::{ACCEPT, CONTENT_LENGTH, COOKIE, HOST, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::oneshot;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Timeout applied to requests that do not specify their own budget.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `User-Agent` sent when the request does not set its own.
+const USER_AGENT: &str = "Setu/0.1.0";
+
+/// Redirect hops followed when the user has not chosen otherwise.
+pub const DEFAULT_REDIRECT_LIMIT: usize = 10;
+
+/// How the client reacts to 3xx responses that carry a `Location`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedirectPolicy {
+    /// Return the redirect response itself.
+    None,
+    /// Follow up to this many hops, then fail the request.
+    Limited(usize),
+    /// Follow any number of hops; only a loop back to a visited URL fails.
+    All,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self::Limited(DEFAULT_REDIRECT_LIMIT)
+    }
+}
+
+impl RedirectPolicy {
+    pub fn follows(self) -> bool {
+        self != Self::None
+    }
+
+    /// A reqwest policy that also records each followed hop for the running request.
+    fn to_reqwest(self) -> reqwest::redirect::Policy {
+        if self == Self::None {
+            return reqwest::redirect::Policy::none();
+        }
+        reqwest::redirect::Policy::custom(move |attempt| {
+            let hops = attempt.previous().len();
+            match self {
+                Self::Limited(limit) if hops > limit => {
+                    return attempt.error(format!("Stopped after {} redirects", limit));
+                }
+                Self::All if attempt.previous().contains(attempt.url()) => {
+                    return attempt.error(format!("Redirect loop at {}", attempt.url()));
+                }
+                _ => {}
+            }
+            let hop = (attempt.status().as_u16(), attempt.url().to_string());
+            let _ = REDIRECT_CHAIN.try_with(|chain| chain.borrow_mut().push(hop));
+            attempt.follow()
+        })
+    }
+}
+
+/// Scheme put in front of URLs typed without one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlScheme {
+    #[default]
+    Https,
+    Http,
+}
+
+impl UrlScheme {
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Https => "https://",
+            Self::Http => "http://",
+        }
+    }
+
+    /// `url` with this scheme in front, unless it already starts with `http://` or `https://`.
+    pub fn apply(self, url: &str) -> String {
+        if has_http_scheme(url) {
+            url.to_string()
+        } else {
+            format!("{}{url}", self.prefix())
+        }
+    }
+}
+
+pub fn has_http_scheme(url: &str) -> bool {
+    let start = url.get(..8).unwrap_or(url).to_ascii_lowercase();
+    start.starts_with("http://") || start.starts_with("https://")
+}
+
+/// Byte offset where the scheme and host of `url` end, found by text alone so that
+/// templates like `{{baseUrl}}/users` split too.
+fn origin_end(url: &str) -> usize {
+    let path_start = |text: &str| text.find(['/', '?', '#']).unwrap_or(text.len());
+    let authority_start = url
+        .find("://")
+        .filter(|&scheme_end| scheme_end < path_start(url))
+        .map_or(0, |scheme_end| scheme_end + 3);
+    authority_start + path_start(&url[authority_start..])
+}
+
+/// The scheme and host `url` starts with, such as `https://api.test:8443`.
+pub fn url_origin(url: &str) -> &str {
+    let url = url.trim();
+    &url[..origin_end(url)]
+}
+
+/// `url` pointed at `origin`, keeping its path, query and fragment. An origin typed without
+/// a scheme keeps the one the URL had.
+pub fn replace_origin(url: &str, origin: &str) -> Result<String, String> {
+    let origin = origin.trim().trim_end_matches('/');
+    if origin.is_empty() || origin_end(origin) != origin.len() || origin.ends_with("://") {
+        return Err("Enter only a scheme and host, like http://localhost:3000".to_string());
+    }
+    let url = url.trim();
+    let current = url_origin(url);
+    let origin = match current.find("://") {
+        Some(scheme_end) if !origin.contains("://") => {
+            format!("{}{origin}", &current[..scheme_end + 3])
+        }
+        _ => origin.to_string(),
+    };
+    Ok(format!("{origin}{}", &url[current.len()..]))
+}
+
+tokio::task_local! {
+    /// Redirect hops followed by the request running on the current task.
+    static REDIRECT_CHAIN: RefCell<Vec<(u16, String)>>;
+}
+
+/// Per-request knobs layered on top of the client-wide configuration.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Overrides the client-wide timeout for this request only.
+    pub timeout: Option<Duration>,
+    /// Send headers as one map built in editor order, without body-implied duplicates.
+    ///
+    /// The HTTP stack still normalizes some things: names go out lowercased, repeated
+    /// names are grouped together, client defaults (`User-Agent`, `Accept`) are written
+    /// first, and `Host`/`Content-Length` are placed by hyper itself.
+    pub preserve_header_order: bool,
+    /// Environment whose cookie store this request reads and updates.
+    pub cookie_scope: Option<Uuid>,
+    /// Sign the request with AWS Signature Version 4 once it is fully built.
+    pub aws_sigv4: Option<AwsSigV4>,
+    /// Route the request through this proxy instead of the system settings.
+    pub proxy: Option<ProxyConfig>,
+    /// Client certificate and extra CAs presented for this request.
+    pub tls: Option<TlsConfig>,
+    /// Try again after connection errors and 5xx responses; POST and PATCH only when the
+    /// policy opts in.
+    pub retry: RetryPolicy,
+}
+
+/// Progress of a response delivered before its final result.
+#[derive(Debug)]
+pub enum ResponseUpdate {
+    /// Status and headers of a `text/event-stream` response whose body is still open.
+    StreamStarted(ResponseData),
+    /// One body chunk of the stream and the events it completed.
+    StreamChunk { raw: Bytes, events: Vec<SseEvent> },
+    /// File bytes of a multipart body handed to the connection, out of all of them.
+    UploadProgress { sent: u64, total: u64 },
+    /// The last attempt failed for `reason`; attempt `attempt` of `max_attempts` follows
+    /// once the backoff delay has passed.
+    Retrying {
+        attempt: u32,
+        max_attempts: u32,
+        reason: String,
+    },
+}
+
+type ClientKey = (Option<ProxyConfig>, Option<TlsConfig>);
 
 /// HTTP Client wrapper for making requests
 #[derive(Clone)]
 pub struct HttpClient {
     client: Client,
     runtime: Arc<tokio::runtime::Runtime>,
+    timeout: Duration,
+    redirect_policy: RedirectPolicy,
+    pool: PoolConfig,
+    cookie_jar: Arc<CookieJar>,
+    /// Clients built for each proxy and TLS setup in use, since reqwest fixes both at build
+    /// time.
+    configured_clients: Arc<Mutex<HashMap<ClientKey, Client>>>,
 }
 
 /// Handle for canceling an in-flight HTTP request.
@@ -33,56 +231,429 @@ impl InFlightRequest {
 
 impl HttpClient {
     pub fn new() -> Result<Self> {
-        let client = Client::builder().user_agent("Setu/0.1.0").build()?;
+        Self::with_timeout(DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Create a client whose requests fail once `timeout` elapses.
+    pub fn with_timeout(timeout: Duration) -> Result<Self> {
+        Self::with_config(timeout, RedirectPolicy::default(), PoolConfig::default())
+    }
+
+    pub fn with_config(
+        timeout: Duration,
+        redirect_policy: RedirectPolicy,
+        pool: PoolConfig,
+    ) -> Result<Self> {
+        Self::build(timeout, redirect_policy, pool, Arc::default())
+    }
+
+    fn build(
+        timeout: Duration,
+        redirect_policy: RedirectPolicy,
+        pool: PoolConfig,
+        cookie_jar: Arc<CookieJar>,
+    ) -> Result<Self> {
+        let client = Self::client_builder(redirect_policy, pool, &cookie_jar).build()?;
 
         Ok(Self {
             client,
             runtime: shared_tokio_runtime(),
+            timeout,
+            redirect_policy,
+            pool,
+            cookie_jar,
+            configured_clients: Arc::default(),
         })
     }
 
+    fn client_builder(
+        redirect_policy: RedirectPolicy,
+        pool: PoolConfig,
+        cookie_jar: &Arc<CookieJar>,
+    ) -> reqwest::ClientBuilder {
+        // The budget is enforced per request so event streams can outlive it.
+        let builder = Client::builder()
+            .user_agent(USER_AGENT)
+            .redirect(redirect_policy.to_reqwest())
+            .cookie_provider(cookie_jar.clone());
+        pool.apply(builder)
+    }
+
+    /// The client for requests through `proxy` presenting `tls`, built on first use and
+    /// kept for the next. Configuring a proxy turns off the system proxy settings for those
+    /// requests.
+    fn client_for(&self, proxy: Option<&ProxyConfig>, tls: Option<&TlsConfig>) -> Result<Client> {
+        let proxy = proxy.filter(|proxy| !proxy.is_empty());
+        let tls = tls.filter(|tls| !tls.is_empty());
+        if proxy.is_none() && tls.is_none() {
+            return Ok(self.client.clone());
+        }
+        let key = (proxy.cloned(), tls.cloned());
+        let mut clients = self
+            .configured_clients
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+        let mut builder = Self::client_builder(self.redirect_policy, self.pool, &self.cookie_jar);
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(proxy.to_reqwest()?);
+        }
+        if let Some(tls) = tls {
+            builder = tls.load()?.apply(builder);
+        }
+        let client = builder.build()?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// [`Self::client_for`] on a blocking thread, since building a client presenting `tls`
+    /// reads its certificate files.
+    async fn client_for_blocking(
+        &self,
+        proxy: Option<ProxyConfig>,
+        tls: Option<TlsConfig>,
+    ) -> Result<Client> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.client_for(proxy.as_ref(), tls.as_ref())).await?
+    }
+
+    /// Rebuild the client with a different redirect policy; in-flight requests keep the old one.
+    /// Stored cookies carry over.
+    pub fn set_redirect_policy(&mut self, redirect_policy: RedirectPolicy) -> Result<()> {
+        *self = Self::build(
+            self.timeout,
+            redirect_policy,
+            self.pool,
+            self.cookie_jar.clone(),
+        )?;
+        Ok(())
+    }
+
+    /// Rebuild the client with different pool settings, dropping the idle connections of the
+    /// old one. In-flight requests finish on their connections; stored cookies carry over.
+    pub fn set_pool_config(&mut self, pool: PoolConfig) -> Result<()> {
+        *self = Self::build(
+            self.timeout,
+            self.redirect_policy,
+            pool,
+            self.cookie_jar.clone(),
+        )?;
+        Ok(())
+    }
+
+    /// Cookies received from `Set-Cookie` headers, kept per environment.
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        &self.cookie_jar
+    }
+
+    /// Label and value of each connection setting, for showing next to a failed request
+    /// that went through `proxy` presenting `tls`.
+    pub fn settings_summary(
+        &self,
+        proxy: Option<&ProxyConfig>,
+        tls: Option<&TlsConfig>,
+    ) -> Vec<(&'static str, String)> {
+        let redirects = match self.redirect_policy {
+            RedirectPolicy::None => "Not followed".to_string(),
+            RedirectPolicy::Limited(limit) => format!("Followed up to {limit} hops"),
+            RedirectPolicy::All => "Followed without a limit".to_string(),
+        };
+        let mut summary = vec![
+            ("Timeout", format!("{}s", self.timeout.as_secs_f64())),
+            ("Redirects", redirects),
+            ("Connection pool", self.pool.describe()),
+            (
+                "Proxy",
+                proxy
+                    .filter(|proxy| !proxy.is_empty())
+                    .map_or_else(|| "System settings".to_string(), ProxyConfig::describe),
+            ),
+            (
+                "TLS",
+                if tls.is_some_and(|tls| tls.accept_invalid_certs) {
+                    "rustls, certificate verification off".to_string()
+                } else {
+                    "rustls, certificates verified".to_string()
+                },
+            ),
+        ];
+        if let Some(tls) = tls {
+            if !tls.client_cert.trim().is_empty() {
+                summary.push(("Client certificate", tls.client_cert.trim().to_string()));
+            }
+            if !tls.ca_bundle.trim().is_empty() {
+                summary.push(("CA bundle", tls.ca_bundle.trim().to_string()));
+            }
+        }
+        summary
+    }
+
+    pub fn redirect_policy(&self) -> RedirectPolicy {
+        self.redirect_policy
+    }
+
+    pub fn pool_config(&self) -> PoolConfig {
+        self.pool
+    }
+
+    /// The request [`Self::spawn_request`] would send, built the same way on the Tokio
+    /// runtime but never sent, written out as an HTTP/1.1 message. Files in the body are read
+    /// there too. Headers the connection adds, such as cookies and `Host`, are filled in as
+    /// they would be without a proxy.
+    pub fn preview_request(
+        &self,
+        method: HttpMethod,
+        url: String,
+        headers: Vec<Header>,
+        body: RequestBody,
+        options: RequestOptions,
+    ) -> oneshot::Receiver<Result<String>> {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+
+        self.runtime.spawn(async move {
+            let upload = UploadTracker::new(None);
+            let result = async {
+                let client = this
+                    .client_for_blocking(options.proxy.clone(), options.tls.clone())
+                    .await?;
+                let request =
+                    build_request(&client, method, &url, &headers, &body, &options, &upload)
+                        .await?
+                        .build()?;
+                let cookies = this
+                    .cookie_jar
+                    .header_for(options.cookie_scope, request.url());
+                Ok::<_, anyhow::Error>(http_message(&request, cookies))
+            }
+            .await;
+            let _ = tx.send(result);
+        });
+        rx
+    }
+
     /// Spawn an HTTP request on the Tokio runtime and return a receiver for the result.
     /// This allows GPUI's async executor to await the result without being in a Tokio context.
+    ///
+    /// Event-stream responses report their events on the update channel as they arrive; it
+    /// closes before the final result is sent.
+    ///
+    /// With a retry policy, failed attempts are announced on the update channel and the
+    /// result is that of the last attempt. Canceling also stops a pending backoff.
     pub fn spawn_request(
         &self,
         method: HttpMethod,
         url: String,
         headers: Vec<Header>,
         body: RequestBody,
-    ) -> (oneshot::Receiver<Result<ResponseData>>, InFlightRequest) {
+        options: RequestOptions,
+    ) -> (
+        oneshot::Receiver<Result<ResponseData>>,
+        mpsc::UnboundedReceiver<ResponseUpdate>,
+        InFlightRequest,
+    ) {
         let (tx, rx) = oneshot::channel();
-        let client = self.client.clone();
+        let (updates_tx, updates_rx) = mpsc::unbounded_channel();
+        let options = RequestOptions {
+            timeout: Some(options.timeout.unwrap_or(self.timeout)),
+            ..options
+        };
+        let this = self.clone();
 
         let task = self.runtime.spawn(async move {
-            let result = execute_request(client, method, url, headers, body).await;
-            let _ = tx.send(result);
+            let client = match this
+                .client_for_blocking(options.proxy.clone(), options.tls.clone())
+                .await
+            {
+                Ok(client) => client,
+                Err(error) => {
+                    drop(updates_tx);
+                    let _ = tx.send(Err(error));
+                    return;
+                }
+            };
+            let (result, attempts) = run_with_retries(
+                options.retry,
+                method,
+                || {
+                    execute_request(
+                        client.clone(),
+                        method,
+                        url.clone(),
+                        headers.clone(),
+                        body.clone(),
+                        options.clone(),
+                        Some(updates_tx.clone()),
+                    )
+                },
+                |attempt, max_attempts, reason| {
+                    let _ = updates_tx.send(ResponseUpdate::Retrying {
+                        attempt,
+                        max_attempts,
+                        reason,
+                    });
+                },
+            )
+            .await;
+            // Receivers wait for the update channel to close before reading the result.
+            drop(updates_tx);
+            let _ = tx.send(result.map(|mut data| {
+                data.attempts = attempts;
+                data
+            }));
         });
 
-        (rx, InFlightRequest { task: Some(task) })
+        (rx, updates_rx, InFlightRequest { task: Some(task) })
     }
 }
 
 /// Internal function to execute the HTTP request
+///
+/// With an update channel, `text/event-stream` bodies are read chunk by chunk without the
+/// timeout, which then only covers receiving the response headers.
 async fn execute_request(
     client: Client,
     method: HttpMethod,
     url: String,
     headers: Vec<Header>,
     body: RequestBody,
+    options: RequestOptions,
+    updates: Option<mpsc::UnboundedSender<ResponseUpdate>>,
 ) -> Result<ResponseData> {
+    let timeout = options.timeout;
+    let start = Instant::now();
+    // Multipart files stream from disk and report how much of them has gone out.
+    let upload = UploadTracker::new(updates.clone());
+    let request = build_request(&client, method, &url, &headers, &body, &options, &upload).await?;
+    let informational = InformationalLog::default();
+    let request = informational.attach(request)?;
+
+    // Execute request, collecting the hops the redirect policy follows along the way
+    let send = REDIRECT_CHAIN.scope(RefCell::new(Vec::new()), async {
+        let response = within_budget(timeout, start, request.send()).await?;
+        Ok::<_, anyhow::Error>((response, REDIRECT_CHAIN.with(RefCell::take)))
+    });
+    let sent = COOKIE_SCOPE.scope(options.cookie_scope, send).await;
+    upload.finish();
+    let (mut response, redirects) = sent?;
+    let duration = start.elapsed();
+    let interim = informational.take();
+
+    // Extract response data
+    let status_code = response.status().as_u16();
+    let status_text = response
+        .status()
+        .canonical_reason()
+        .unwrap_or("Unknown")
+        .to_string();
+
+    // Get content-type before consuming response
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    // Convert headers
+    let mut response_headers = HashMap::new();
+    for (key, value) in response.headers() {
+        if let Ok(v) = value.to_str() {
+            response_headers.insert(key.to_string(), v.to_string());
+        }
+    }
+
+    let body_bytes = match updates {
+        Some(updates) if is_event_stream(content_type.as_deref()) => {
+            let mut head = ResponseData::from_bytes(
+                status_code,
+                status_text.clone(),
+                response_headers.clone(),
+                Bytes::new(),
+                duration.as_millis() as u64,
+                content_type.clone(),
+            );
+            head.redirects = redirects.clone();
+            head.informational = interim.clone();
+            head.expect_continue = ExpectContinue::of(&headers, &interim);
+            let _ = updates.send(ResponseUpdate::StreamStarted(head));
+            let mut parser = SseParser::default();
+            let mut body = Vec::new();
+            let mut truncated = false;
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|error| transport_error(error, None))?
+            {
+                truncated |= push_capped_stream_body(&mut body, &chunk);
+                let events = parser.feed(&chunk);
+                let _ = updates.send(ResponseUpdate::StreamChunk { raw: chunk, events });
+            }
+            if truncated {
+                body.extend_from_slice(stream_truncated_marker().as_bytes());
+            }
+            Bytes::from(body)
+        }
+        // Get body as bytes first
+        _ => within_budget(timeout, start, response.bytes()).await?,
+    };
+
+    // reqwest is built without its decoders so the encoded size is still visible here.
+    let content_encoding = response_headers.get("content-encoding").map(String::as_str);
+    let mut decode_warning = None;
+    let (body_bytes, wire_size_bytes) =
+        match decode_content(content_encoding, &body_bytes, MAX_DECODED_BODY_BYTES) {
+            Decoded::Body(decoded) => (decoded, Some(body_bytes.len())),
+            Decoded::Unchanged => (body_bytes, None),
+            Decoded::TooLarge => {
+                let warning = format!(
+                    "Not decompressed: the body would be larger than {}",
+                    format_size(MAX_DECODED_BODY_BYTES as u64)
+                );
+                log::warn!(
+                    "{warning} ({} on the wire)",
+                    format_size(body_bytes.len() as u64)
+                );
+                decode_warning = Some(warning);
+                (body_bytes, None)
+            }
+        };
+
+    let mut data = ResponseData::from_bytes(
+        status_code,
+        status_text,
+        response_headers,
+        body_bytes,
+        duration.as_millis() as u64,
+        content_type,
+    );
+    data.redirects = redirects;
+    data.expect_continue = ExpectContinue::of(&headers, &interim);
+    data.informational = interim;
+    data.wire_size_bytes = wire_size_bytes;
+    data.decode_warning = decode_warning;
+    Ok(data)
+}
+
+/// The request `execute_request` sends: headers in the chosen order, the body encoded for
+/// its type and, with SigV4 credentials, the signature over all of it.
+async fn build_request(
+    client: &Client,
+    method: HttpMethod,
+    url: &str,
+    headers: &[Header],
+    body: &RequestBody,
+    options: &RequestOptions,
+    upload: &Arc<UploadTracker>,
+) -> Result<RequestBuilder> {
     // Validate URL
     if url.is_empty() {
         return Err(anyhow!("URL cannot be empty"));
     }
 
-    // Ensure URL has a scheme
-    let url = if !url.starts_with("http://") && !url.starts_with("https://") {
-        format!("https://{}", url)
-    } else {
-        url.to_string()
-    };
-
-    let start = Instant::now();
+    // Callers apply the user's default scheme; this covers any that pass a bare host.
+    let url = UrlScheme::default().apply(url);
 
     // Build request
     let mut request = match method {
@@ -99,15 +670,29 @@ async fn execute_request(
     let is_multipart = matches!(body, RequestBody::MultipartFormData(_));
 
     // Add headers (skip Content-Type for multipart - reqwest sets it with boundary)
-    for header in headers.iter().filter(|h| h.enabled) {
-        if is_multipart && header.key.to_lowercase() == "content-type" {
-            continue;
+    if options.preserve_header_order {
+        request = request.headers(ordered_header_map(headers, is_multipart)?);
+    } else {
+        for header in headers.iter().filter(|h| h.enabled) {
+            if is_multipart && header.key.to_lowercase() == "content-type" {
+                continue;
+            }
+            request = request.header(&header.key, &header.value);
         }
-        request = request.header(&header.key, &header.value);
     }
+    // `Expect: 100-continue` goes out as set. hyper sends the body without waiting for the
+    // interim response and skips any 1xx it reads, so the final status is the one recorded;
+    // the skipped ones are kept on the side by `InformationalLog`, and whether a 100 came
+    // back is noted as the response's `expect_continue`.
+
+    // In ordered mode a user-set Content-Type keeps its slot instead of gaining a twin.
+    let implied_content_type = !(options.preserve_header_order
+        && headers
+            .iter()
+            .any(|h| h.enabled && h.key.eq_ignore_ascii_case("content-type")));
 
     // Add body
-    request = match &body {
+    request = match body {
         RequestBody::None => request,
         RequestBody::Text(text) => request.body(text.clone()),
         RequestBody::Json(json) => {
@@ -118,9 +703,12 @@ async fn execute_request(
                     json.clone()
                 }
             };
-            request
-                .header("Content-Type", "application/json")
-                .body(normalized_json)
+            json_content_type(request, implied_content_type).body(normalized_json)
+        }
+        RequestBody::GraphQl { query, variables } => {
+            let payload = graphql_payload(query, variables)
+                .map_err(|e| anyhow!("Invalid GraphQL variables JSON: {}", e))?;
+            json_content_type(request, implied_content_type).body(payload)
         }
         // For form data, send as application/x-www-form-urlencoded
         RequestBody::FormData(data) => request.form(data),
@@ -131,29 +719,37 @@ async fn execute_request(
             for field in fields {
                 if let Some(ref file_path) = field.file_path {
                     let path = std::path::Path::new(file_path);
-                    match tokio::fs::read(path).await {
-                        Ok(file_bytes) => {
-                            let file_name = path
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("file")
-                                .to_string();
-
-                            let mime_type = mime_guess::from_path(path)
-                                .first_or_octet_stream()
-                                .to_string();
-
-                            let part = reqwest::multipart::Part::bytes(file_bytes)
-                                .file_name(file_name)
-                                .mime_str(&mime_type)
-                                .unwrap_or_else(|_| reqwest::multipart::Part::bytes(vec![]));
-
-                            form = form.part(field.key.clone(), part);
-                        }
-                        Err(e) => {
-                            log::error!("Failed to read file {}: {}", file_path, e);
-                        }
-                    }
+                    // A file moved or deleted since it was picked aborts the send.
+                    let file = tokio::fs::File::open(path)
+                        .await
+                        .map_err(|error| file_read_error(file_path, error))?;
+                    let file_len = file
+                        .metadata()
+                        .await
+                        .map_err(|error| file_read_error(file_path, error))?
+                        .len();
+                    upload.add_file(file_len);
+                    let file_name = path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("file")
+                        .to_string();
+
+                    let mime_type = mime_guess::from_path(path)
+                        .first_or_octet_stream()
+                        .to_string();
+
+                    // Reading stops at the announced length even if the file grows meanwhile.
+                    let file_body = reqwest::Body::wrap_stream(tracked_stream(
+                        file.take(file_len),
+                        upload.clone(),
+                    ));
+                    let part = reqwest::multipart::Part::stream_with_length(file_body, file_len)
+                        .file_name(file_name)
+                        .mime_str(&mime_type)
+                        .unwrap_or_else(|_| reqwest::multipart::Part::bytes(vec![]));
+
+                    form = form.part(field.key.clone(), part);
                 } else {
                     form = form.text(field.key.clone(), field.value.clone());
                 }
@@ -161,46 +757,158 @@ async fn execute_request(
 
             request.multipart(form)
         }
+        RequestBody::Binary(path) => {
+            let display = path.to_string_lossy();
+            if display.is_empty() {
+                return Err(anyhow!("Choose a file to send as the body"));
+            }
+            let bytes = tokio::fs::read(path)
+                .await
+                .map_err(|error| file_read_error(&display, error))?;
+            let has_content_type = headers
+                .iter()
+                .any(|h| h.enabled && h.key.eq_ignore_ascii_case("content-type"));
+            if has_content_type {
+                request.body(bytes)
+            } else {
+                request
+                    .header("Content-Type", file_content_type(path))
+                    .body(bytes)
+            }
+        }
     };
 
-    // Execute request
-    let response = request.send().await?;
-    let duration = start.elapsed();
+    // Signing covers the final URL, headers and body, so it has to see the built request.
+    if let Some(credentials) = &options.aws_sigv4 {
+        let (client, built) = request.build_split();
+        let mut built = built?;
+        let payload = match built.body() {
+            None => Some(Vec::new()),
+            Some(body) => body.as_bytes().map(<[u8]>::to_vec),
+        };
+        let (method, url) = (built.method().clone(), built.url().clone());
+        sign_request(
+            credentials,
+            &method,
+            &url,
+            built.headers_mut(),
+            payload.as_deref(),
+            Utc::now(),
+        )?;
+        request = RequestBuilder::from_parts(client, built);
+    }
 
-    // Extract response data
-    let status_code = response.status().as_u16();
-    let status_text = response
-        .status()
-        .canonical_reason()
-        .unwrap_or("Unknown")
-        .to_string();
+    Ok(request)
+}
 
-    // Get content-type before consuming response
-    let content_type = response
-        .headers()
-        .get("content-type")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
+/// Write `request` out as an HTTP/1.1 message, with the headers reqwest and hyper add while
+/// sending. Names are lowercase, as they go out on the wire. A streamed body, which
+/// multipart forms use, is described rather than read.
+fn http_message(request: &reqwest::Request, cookies: Option<String>) -> String {
+    let url = request.url();
+    let headers = request.headers();
+    let mut target = url.path().to_string();
+    if let Some(query) = url.query() {
+        target.push('?');
+        target.push_str(query);
+    }
 
-    // Convert headers
-    let mut response_headers = HashMap::new();
-    for (key, value) in response.headers() {
-        if let Ok(v) = value.to_str() {
-            response_headers.insert(key.to_string(), v.to_string());
+    let mut lines = vec![format!("{} {target} HTTP/1.1", request.method())];
+    if !headers.contains_key(HOST) {
+        let host = url.host_str().unwrap_or_default();
+        lines.push(match url.port() {
+            Some(port) => format!("host: {host}:{port}"),
+            None => format!("host: {host}"),
+        });
+    }
+    for (name, value) in headers {
+        lines.push(format!(
+            "{name}: {}",
+            String::from_utf8_lossy(value.as_bytes())
+        ));
+    }
+    if !headers.contains_key(reqwest::header::USER_AGENT) {
+        lines.push(format!("user-agent: {USER_AGENT}"));
+    }
+    if !headers.contains_key(ACCEPT) {
+        lines.push("accept: */*".to_string());
+    }
+    if let Some(cookies) = cookies.filter(|_| !headers.contains_key(COOKIE)) {
+        lines.push(format!("cookie: {cookies}"));
+    }
+
+    let body = match request.body().map(|body| body.as_bytes()) {
+        None => String::new(),
+        Some(Some(bytes)) => {
+            if !headers.contains_key(CONTENT_LENGTH) {
+                lines.push(format!("content-length: {}", bytes.len()));
+            }
+            match std::str::from_utf8(bytes) {
+                Ok(text) => text.to_string(),
+                Err(_) => format!("[{} bytes of binary data]", bytes.len()),
+            }
+        }
+        Some(None) => "[Multipart body, streamed from its fields and files when sent]".to_string(),
+    };
+    format!("{}\n\n{body}", lines.join("\n"))
+}
+
+/// Build a header map that iterates in editor order.
+fn ordered_header_map(headers: &[Header], skip_content_type: bool) -> Result<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for header in headers.iter().filter(|h| h.enabled) {
+        if skip_content_type && header.key.eq_ignore_ascii_case("content-type") {
+            continue;
         }
+        let name = HeaderName::from_bytes(header.key.trim().as_bytes())
+            .map_err(|_| anyhow!("Invalid header name: {}", header.key))?;
+        let value = HeaderValue::from_str(&header.value)
+            .map_err(|_| anyhow!("Invalid value for header {}", header.key))?;
+        map.append(name, value);
     }
+    Ok(map)
+}
 
-    // Get body as bytes first
-    let body_bytes = response.bytes().await?;
+fn json_content_type(request: RequestBuilder, add: bool) -> RequestBuilder {
+    if add {
+        request.header("Content-Type", "application/json")
+    } else {
+        request
+    }
+}
 
-    Ok(ResponseData::from_bytes(
-        status_code,
-        status_text,
-        response_headers,
-        body_bytes,
-        duration.as_millis() as u64,
-        content_type,
-    ))
+/// Await `future` within what is left of the request's time budget.
+async fn within_budget<T>(
+    budget: Option<Duration>,
+    start: Instant,
+    future: impl Future<Output = reqwest::Result<T>>,
+) -> Result<T> {
+    let Some(budget) = budget else {
+        return future.await.map_err(|error| transport_error(error, None));
+    };
+    match tokio::time::timeout(budget.saturating_sub(start.elapsed()), future).await {
+        Ok(result) => result.map_err(|error| transport_error(error, Some(budget))),
+        Err(_) => Err(timeout_error(budget)),
+    }
+}
+
+fn file_read_error(path: &str, error: std::io::Error) -> anyhow::Error {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => anyhow!("File not found: {}", path),
+        _ => anyhow!("Cannot read file {}: {}", path, error),
+    }
+}
+
+fn timeout_error(timeout: Duration) -> anyhow::Error {
+    anyhow!("Request timed out after {}s", timeout.as_secs_f64())
+}
+
+/// Give timeouts a readable message; other transport errors pass through unchanged.
+fn transport_error(error: reqwest::Error, timeout: Option<Duration>) -> anyhow::Error {
+    match timeout {
+        Some(timeout) if error.is_timeout() => timeout_error(timeout),
+        _ => error.into(),
+    }
 }
 
 impl Default for HttpClient {
@@ -211,8 +919,13 @@ impl Default for HttpClient {
 
 #[cfg(test)]
 mod tests {
-    use super::{HttpClient, execute_request};
-    use crate::entities::{ContentCategory, HttpMethod, MultipartField, RequestBody};
+    use super::{
+        AwsSigV4, HttpClient, PoolConfig, ProxyConfig, RedirectPolicy, RequestOptions,
+        ResponseUpdate, TlsConfig, UrlScheme, execute_request, replace_origin, url_origin,
+    };
+    use crate::entities::{
+        ContentCategory, ExpectContinue, Header, HttpMethod, MultipartField, RequestBody,
+    };
     use crate::utils::shared_tokio_runtime;
     use std::collections::HashMap;
     use std::io::{Read, Write};
@@ -220,6 +933,7 @@ mod tests {
     use std::sync::mpsc;
     use std::thread;
     use std::time::Duration;
+    use uuid::Uuid;
 
     fn read_request(stream: &mut TcpStream) -> Vec<u8> {
         stream
@@ -277,6 +991,58 @@ mod tests {
         (format!("http://{address}"), request_rx, handle)
     }
 
+    #[test]
+    fn routes_requests_through_the_configured_proxy() {
+        let (proxy_url, request_rx, server) =
+            spawn_server("200 OK", "text/plain", b"proxied".to_vec());
+        let client = HttpClient::new().unwrap();
+        let (result_rx, _updates, _request) = client.spawn_request(
+            HttpMethod::Get,
+            "http://upstream.invalid/path".to_string(),
+            Vec::new(),
+            RequestBody::None,
+            RequestOptions {
+                proxy: Some(ProxyConfig {
+                    url: proxy_url,
+                    username: "alice".to_string(),
+                    password: "secret".to_string(),
+                    ..ProxyConfig::default()
+                }),
+                ..RequestOptions::default()
+            },
+        );
+        let response = shared_tokio_runtime().block_on(result_rx).unwrap().unwrap();
+        let request = String::from_utf8_lossy(&request_rx.recv().unwrap()).to_ascii_lowercase();
+        server.join().unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert!(request.starts_with("get http://upstream.invalid/path http/1.1"));
+        assert!(request.contains("proxy-authorization: basic "));
+    }
+
+    #[test]
+    fn fails_the_request_when_the_client_certificate_cannot_load() {
+        let client = HttpClient::new().unwrap();
+        let (result_rx, _updates, _request) = client.spawn_request(
+            HttpMethod::Get,
+            "https://mtls.invalid/".to_string(),
+            Vec::new(),
+            RequestBody::None,
+            RequestOptions {
+                tls: Some(TlsConfig {
+                    client_cert: "/nonexistent/client.pem".to_string(),
+                    ..TlsConfig::default()
+                }),
+                ..RequestOptions::default()
+            },
+        );
+        let error = shared_tokio_runtime()
+            .block_on(result_rx)
+            .unwrap()
+            .unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/client.pem"));
+    }
+
     #[test]
     fn sends_url_encoded_forms_with_reqwest_form_api() {
         let (url, request_rx, server) = spawn_server("200 OK", "text/plain", b"ok".to_vec());
@@ -291,6 +1057,8 @@ mod tests {
                 url,
                 Vec::new(),
                 body,
+                RequestOptions::default(),
+                None,
             ))
             .unwrap();
         let request = String::from_utf8_lossy(&request_rx.recv().unwrap()).into_owned();
@@ -301,6 +1069,287 @@ mod tests {
         assert!(request.ends_with("display+name=Setu+User"));
     }
 
+    #[test]
+    fn adds_the_default_scheme_only_to_schemeless_urls() {
+        assert_eq!(
+            UrlScheme::Http.apply("localhost:8080/health"),
+            "http://localhost:8080/health"
+        );
+        assert_eq!(UrlScheme::Https.apply("api.test/v1"), "https://api.test/v1");
+        assert_eq!(
+            UrlScheme::Http.apply("HTTPS://api.test"),
+            "HTTPS://api.test"
+        );
+        assert_eq!(UrlScheme::Https.apply("http://api.test"), "http://api.test");
+    }
+
+    #[test]
+    fn swaps_only_the_origin_of_a_url() {
+        assert_eq!(
+            url_origin("https://api.test:8443/v1?q=1"),
+            "https://api.test:8443"
+        );
+        assert_eq!(url_origin("{{baseUrl}}/users"), "{{baseUrl}}");
+        assert_eq!(
+            replace_origin(
+                "https://api.test/v1/users?page=2#top",
+                "http://localhost:3000/"
+            )
+            .unwrap(),
+            "http://localhost:3000/v1/users?page=2#top"
+        );
+        assert_eq!(
+            replace_origin("https://api.test/v1", "staging.api.test").unwrap(),
+            "https://staging.api.test/v1"
+        );
+        assert_eq!(
+            replace_origin("{{baseUrl}}/users", "http://localhost:3000").unwrap(),
+            "http://localhost:3000/users"
+        );
+        assert_eq!(
+            replace_origin("localhost/health?redirect=http://x", "api.test").unwrap(),
+            "api.test/health?redirect=http://x"
+        );
+        assert!(replace_origin("https://api.test/v1", "http://localhost/v2").is_err());
+        assert!(replace_origin("https://api.test/v1", " ").is_err());
+    }
+
+    #[test]
+    fn skips_the_interim_continue_response_of_an_expect_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = read_request(&mut stream);
+            stream
+                .write_all(
+                    b"HTTP/1.1 100 Continue\r\n\r\n\
+                      HTTP/1.1 201 Created\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                )
+                .unwrap();
+            request
+        });
+
+        let response = shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Post,
+                format!("http://{address}"),
+                vec![Header::new("Expect", "100-continue")],
+                RequestBody::Text("upload".to_string()),
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap();
+        let request = String::from_utf8_lossy(&server.join().unwrap()).into_owned();
+
+        assert!(request.contains("expect: 100-continue"));
+        assert!(request.ends_with("upload"));
+        assert_eq!(response.status_code, 201);
+        assert_eq!(response.body(), "ok");
+        assert_eq!(response.expect_continue, Some(ExpectContinue::Continued));
+    }
+
+    #[test]
+    fn keeps_early_hints_sent_before_the_final_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_request(&mut stream);
+            stream
+                .write_all(
+                    b"HTTP/1.1 103 Early Hints\r\n\
+                      Link: </app.css>; rel=preload; as=style\r\n\
+                      Link: </app.js>; rel=preload; as=script\r\n\r\n\
+                      HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                )
+                .unwrap();
+        });
+
+        let response = shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Get,
+                format!("http://{address}"),
+                Vec::new(),
+                RequestBody::None,
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body(), "ok");
+        assert_eq!(response.informational.len(), 1);
+        let hints = &response.informational[0];
+        assert_eq!(hints.status_code, 103);
+        assert_eq!(
+            hints.headers,
+            [
+                (
+                    "link".to_string(),
+                    "</app.css>; rel=preload; as=style".to_string()
+                ),
+                (
+                    "link".to_string(),
+                    "</app.js>; rel=preload; as=script".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn sends_headers_with_empty_values() {
+        let (url, request_rx, server) = spawn_server("200 OK", "text/plain", b"ok".to_vec());
+        shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Get,
+                url,
+                vec![Header::new("X-Debug", "")],
+                RequestBody::None,
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap();
+        let request = String::from_utf8_lossy(&request_rx.recv().unwrap()).into_owned();
+        server.join().unwrap();
+
+        assert!(request.lines().any(|line| line.trim_end() == "x-debug:"));
+    }
+
+    #[test]
+    fn signs_the_final_request_with_aws_sigv4() {
+        let (url, request_rx, server) = spawn_server("200 OK", "text/plain", b"ok".to_vec());
+        shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Post,
+                format!("{url}/items?b=2&a=1"),
+                vec![Header::new("X-Amz-Target", "Items.Put")],
+                RequestBody::Json("{}".to_string()),
+                RequestOptions {
+                    aws_sigv4: Some(AwsSigV4 {
+                        access_key: "AKID".to_string(),
+                        secret_key: "secret".to_string(),
+                        region: "eu-west-1".to_string(),
+                        service: "dynamodb".to_string(),
+                        session_token: "token".to_string(),
+                    }),
+                    ..RequestOptions::default()
+                },
+                None,
+            ))
+            .unwrap();
+        let request = String::from_utf8_lossy(&request_rx.recv().unwrap()).into_owned();
+        server.join().unwrap();
+
+        let authorization = request
+            .lines()
+            .find_map(|line| line.strip_prefix("authorization: "))
+            .expect("request should be signed");
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"));
+        assert!(authorization.contains(
+            "SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target,"
+        ));
+        assert!(request.contains("x-amz-security-token: token"));
+        assert!(request.lines().any(|line| line.starts_with("x-amz-date: ")));
+    }
+
+    #[test]
+    fn preserves_editor_header_order_without_duplicating_content_type() {
+        let (url, request_rx, server) = spawn_server("200 OK", "text/plain", b"ok".to_vec());
+        shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Post,
+                url,
+                vec![
+                    Header::new("X-Zulu", "1"),
+                    Header::new("Content-Type", "application/json; charset=utf-8"),
+                    Header::new("X-Alpha", "2"),
+                ],
+                RequestBody::Json("{}".to_string()),
+                RequestOptions {
+                    preserve_header_order: true,
+                    ..RequestOptions::default()
+                },
+                None,
+            ))
+            .unwrap();
+        let request = String::from_utf8_lossy(&request_rx.recv().unwrap()).into_owned();
+        server.join().unwrap();
+
+        let position = |name: &str| {
+            request
+                .lines()
+                .position(|line| line.to_ascii_lowercase().starts_with(name))
+                .unwrap_or_else(|| panic!("missing {name} in {request}"))
+        };
+        assert!(position("x-zulu:") < position("content-type:"));
+        assert!(position("content-type:") < position("x-alpha:"));
+        assert_eq!(
+            request
+                .lines()
+                .filter(|line| line.to_ascii_lowercase().starts_with("content-type:"))
+                .count(),
+            1
+        );
+        assert!(request.contains("application/json; charset=utf-8"));
+    }
+
+    #[test]
+    fn sends_graphql_bodies_as_json_documents() {
+        let (url, request_rx, server) = spawn_server("200 OK", "application/json", b"{}".to_vec());
+        let body = RequestBody::GraphQl {
+            query: "query { me { id } }".to_string(),
+            variables: r#"{ "id": 7 }"#.to_string(),
+        };
+        shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Post,
+                url,
+                Vec::new(),
+                body,
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap();
+        let request = String::from_utf8_lossy(&request_rx.recv().unwrap()).into_owned();
+        server.join().unwrap();
+
+        assert!(request.contains("content-type: application/json"));
+        assert!(request.ends_with(r#"{"query":"query { me { id } }","variables":{"id":7}}"#));
+    }
+
+    #[test]
+    fn rejects_graphql_bodies_with_invalid_variables() {
+        let body = RequestBody::GraphQl {
+            query: "{ ping }".to_string(),
+            variables: "{oops".to_string(),
+        };
+        let error = shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Post,
+                "http://127.0.0.1:9".to_string(),
+                Vec::new(),
+                body,
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid GraphQL variables JSON")
+        );
+    }
+
     #[test]
     fn sends_multipart_files_without_losing_bytes() {
         let path = std::env::temp_dir().join(format!("setu-{}.bin", uuid::Uuid::new_v4()));
@@ -317,6 +1366,8 @@ mod tests {
                 url,
                 Vec::new(),
                 body,
+                RequestOptions::default(),
+                None,
             ))
             .unwrap();
         let request = request_rx.recv().unwrap();
@@ -331,6 +1382,60 @@ mod tests {
         assert!(String::from_utf8_lossy(&request).contains("name=\"upload\""));
     }
 
+    #[test]
+    fn sends_a_binary_file_as_the_body_typed_by_its_extension() {
+        let path = std::env::temp_dir().join(format!("setu-{}.png", uuid::Uuid::new_v4()));
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\nbinary-body").unwrap();
+        let (url, request_rx, server) = spawn_server("200 OK", "text/plain", b"ok".to_vec());
+        shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Post,
+                url,
+                Vec::new(),
+                RequestBody::Binary(path.clone()),
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap();
+        let request = request_rx.recv().unwrap();
+        server.join().unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(request.ends_with(b"\x89PNG\r\n\x1a\nbinary-body"));
+        assert!(
+            String::from_utf8_lossy(&request)
+                .to_ascii_lowercase()
+                .contains("content-type: image/png")
+        );
+    }
+
+    #[test]
+    fn fails_with_a_clear_error_when_a_multipart_file_is_missing() {
+        let path = std::env::temp_dir().join(format!("setu-missing-{}.bin", uuid::Uuid::new_v4()));
+        let body = RequestBody::MultipartFormData(vec![MultipartField::file(
+            "upload",
+            path.to_string_lossy(),
+        )]);
+        // Nothing listens here: the send must stop before any connection is attempted.
+        let error = shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Post,
+                "http://127.0.0.1:9".to_string(),
+                Vec::new(),
+                body,
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            format!("File not found: {}", path.to_string_lossy())
+        );
+    }
+
     #[test]
     fn preserves_binary_image_and_audio_payloads() {
         for (content_type, body, category) in [
@@ -353,6 +1458,8 @@ mod tests {
                     url,
                     Vec::new(),
                     RequestBody::None,
+                    RequestOptions::default(),
+                    None,
                 ))
                 .unwrap();
             server.join().unwrap();
@@ -361,6 +1468,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn streams_event_stream_bodies_as_parsed_events() {
+        let body = b"event: greet\ndata: hello\n\ndata: bye\n\n".to_vec();
+        let (url, _request_rx, server) = spawn_server("200 OK", "text/event-stream", body.clone());
+        let (updates_tx, mut updates_rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Get,
+                url,
+                Vec::new(),
+                RequestBody::None,
+                RequestOptions::default(),
+                Some(updates_tx),
+            ))
+            .unwrap();
+        server.join().unwrap();
+
+        let mut started = false;
+        let mut events = Vec::new();
+        while let Ok(update) = updates_rx.try_recv() {
+            match update {
+                ResponseUpdate::StreamStarted(head) => {
+                    assert_eq!(head.status_code, 200);
+                    assert!(head.body().is_empty());
+                    started = true;
+                }
+                ResponseUpdate::StreamChunk { events: chunk, .. } => events.extend(chunk),
+            }
+        }
+        assert!(started);
+        let events: Vec<_> = events
+            .into_iter()
+            .map(|event| (event.event, event.data))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                ("greet".to_string(), "hello".to_string()),
+                ("message".to_string(), "bye".to_string()),
+            ]
+        );
+        assert_eq!(response.body().as_bytes(), body.as_slice());
+    }
+
     #[test]
     fn rejects_cancelled_http_results() {
         let listener = TcpListener::bind("127.0.0.1:0").unwrap();
@@ -373,14 +1525,77 @@ mod tests {
             thread::sleep(Duration::from_millis(50));
         });
         let client = HttpClient::new().unwrap();
-        let (result, mut in_flight) =
-            client.spawn_request(HttpMethod::Get, url, Vec::new(), RequestBody::None);
+        let (result, _updates, mut in_flight) = client.spawn_request(
+            HttpMethod::Get,
+            url,
+            Vec::new(),
+            RequestBody::None,
+            RequestOptions::default(),
+        );
         accepted_rx.recv_timeout(Duration::from_secs(5)).unwrap();
         assert!(in_flight.cancel());
         assert!(shared_tokio_runtime().block_on(result).is_err());
         server.join().unwrap();
     }
 
+    fn spawn_stalled_server() -> (String, mpsc::Sender<()>, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (release_tx, release_rx) = mpsc::channel();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = read_request(&mut stream);
+            let _ = release_rx.recv_timeout(Duration::from_secs(5));
+        });
+        (url, release_tx, server)
+    }
+
+    #[test]
+    fn reports_client_timeout_when_server_stalls() {
+        let (url, release_tx, server) = spawn_stalled_server();
+        let client = HttpClient::with_timeout(Duration::from_millis(200)).unwrap();
+        let (result, _updates, _in_flight) = client.spawn_request(
+            HttpMethod::Get,
+            url,
+            Vec::new(),
+            RequestBody::None,
+            RequestOptions::default(),
+        );
+        let error = shared_tokio_runtime()
+            .block_on(result)
+            .unwrap()
+            .unwrap_err();
+        let _ = release_tx.send(());
+        server.join().unwrap();
+
+        assert_eq!(error.to_string(), "Request timed out after 0.2s");
+    }
+
+    #[test]
+    fn per_request_timeout_overrides_client_default() {
+        let (url, release_tx, server) = spawn_stalled_server();
+        let client = HttpClient::new().unwrap();
+        assert_eq!(client.timeout, super::DEFAULT_REQUEST_TIMEOUT);
+        let (result, _updates, _in_flight) = client.spawn_request(
+            HttpMethod::Get,
+            url,
+            Vec::new(),
+            RequestBody::None,
+            RequestOptions {
+                timeout: Some(Duration::from_secs(1)),
+                ..RequestOptions::default()
+            },
+        );
+        let error = shared_tokio_runtime()
+            .block_on(result)
+            .unwrap()
+            .unwrap_err();
+        let _ = release_tx.send(());
+        server.join().unwrap();
+
+        assert_eq!(error.to_string(), "Request timed out after 1s");
+    }
+
     #[test]
     fn handles_full_ten_megabyte_json_response() {
         let body = format!("{{\"payload\":\"{}\"}}", "x".repeat(10 * 1024 * 1024)).into_bytes();
@@ -393,6 +1608,8 @@ mod tests {
                 url,
                 Vec::new(),
                 RequestBody::None,
+                RequestOptions::default(),
+                None,
             ))
             .unwrap();
         server.join().unwrap();
@@ -434,11 +1651,172 @@ mod tests {
                 format!("http://{address}/start"),
                 Vec::new(),
                 RequestBody::None,
+                RequestOptions::default(),
+                None,
             ))
             .unwrap();
         server.join().unwrap();
 
         assert_eq!(response.status_code, 200);
         assert_eq!(response.body(), "redirect");
+        assert_eq!(
+            response.redirects,
+            vec![(302, format!("http://{address}/final"))]
+        );
+    }
+
+    #[test]
+    fn returns_the_redirect_itself_when_following_is_disabled() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = read_request(&mut stream);
+            stream
+                .write_all(
+                    format!(
+                        "HTTP/1.1 301 Moved Permanently\r\nLocation: http://{address}/elsewhere\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    )
+                    .as_bytes(),
+                )
+                .unwrap();
+        });
+        let client = HttpClient::with_config(
+            Duration::from_secs(5),
+            RedirectPolicy::None,
+            PoolConfig::default(),
+        )
+        .unwrap();
+        let (result, _updates, _in_flight) = client.spawn_request(
+            HttpMethod::Get,
+            format!("http://{address}/start"),
+            Vec::new(),
+            RequestBody::None,
+            RequestOptions::default(),
+        );
+        let response = shared_tokio_runtime().block_on(result).unwrap().unwrap();
+        server.join().unwrap();
+
+        assert_eq!(response.status_code, 301);
+        assert_eq!(
+            response.headers.get("location"),
+            Some(&format!("http://{address}/elsewhere"))
+        );
+        assert!(response.redirects.is_empty());
+        assert!(!client.redirect_policy().follows());
+    }
+
+    #[test]
+    fn decodes_gzip_bodies_and_records_the_wire_size() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+
+        let body = "{\"ok\":true}".repeat(100);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let compressed_len = compressed.len();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = read_request(&mut stream);
+            let headers = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Encoding: gzip\r\nContent-Length: {compressed_len}\r\nConnection: close\r\n\r\n"
+            );
+            stream.write_all(headers.as_bytes()).unwrap();
+            stream.write_all(&compressed).unwrap();
+        });
+        let mut response = shared_tokio_runtime()
+            .block_on(execute_request(
+                reqwest::Client::new(),
+                HttpMethod::Get,
+                format!("http://{address}"),
+                Vec::new(),
+                RequestBody::None,
+                RequestOptions::default(),
+                None,
+            ))
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(response.body_size_bytes, body.len());
+        assert_eq!(response.wire_size_bytes, Some(compressed_len));
+        assert_eq!(&*response.raw_body(), body);
+    }
+
+    #[test]
+    fn keeps_cookies_between_requests_in_the_same_environment() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (request_tx, request_rx) = mpsc::channel();
+        let server = thread::spawn(move || {
+            for _ in 0..3 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let _ = request_tx.send(read_request(&mut stream));
+                stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\nSet-Cookie: session=abc; Path=/\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    )
+                    .unwrap();
+            }
+        });
+        let client = HttpClient::new().unwrap();
+        let environment = Some(Uuid::new_v4());
+        let send = |cookie_scope| {
+            let (result, _updates, _in_flight) = client.spawn_request(
+                HttpMethod::Get,
+                format!("http://{address}/"),
+                Vec::new(),
+                RequestBody::None,
+                RequestOptions {
+                    cookie_scope,
+                    ..RequestOptions::default()
+                },
+            );
+            shared_tokio_runtime().block_on(result).unwrap().unwrap();
+            String::from_utf8_lossy(&request_rx.recv().unwrap()).into_owned()
+        };
+
+        assert!(!send(environment).contains("cookie:"));
+        assert!(send(environment).contains("cookie: session=abc"));
+        assert!(!send(None).contains("cookie:"));
+        server.join().unwrap();
+
+        let stored = client.cookie_jar().cookies(environment);
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].domain, "127.0.0.1");
+    }
+
+    #[test]
+    fn previews_the_request_as_it_would_be_sent() {
+        let client = HttpClient::new().unwrap();
+        let preview = client.preview_request(
+            HttpMethod::Post,
+            "https://api.test:8443/items?page=2".to_string(),
+            vec![
+                Header::new("Authorization", "Bearer token"),
+                Header {
+                    enabled: false,
+                    ..Header::new("X-Debug", "1")
+                },
+            ],
+            RequestBody::Json(r#"{ "id": 1 }"#.to_string()),
+            RequestOptions::default(),
+        );
+        let preview = shared_tokio_runtime().block_on(preview).unwrap().unwrap();
+        assert_eq!(
+            preview,
+            "POST /items?page=2 HTTP/1.1\n\
+             host: api.test:8443\n\
+             authorization: Bearer token\n\
+             content-type: application/json\n\
+             user-agent: Setu/0.1.0\n\
+             accept: */*\n\
+             content-length: 8\n\
+             \n\
+             {\"id\":1}"
+        );
     }
 }
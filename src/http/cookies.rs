@@ -0,0 +1,329 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::Url;
+use reqwest::header::HeaderValue;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+tokio::task_local! {
+    /// Environment whose cookie store the request on the current task reads and writes.
+    pub(super) static COOKIE_SCOPE: Option<Uuid>;
+}
+
+/// A cookie accepted from a `Set-Cookie` response header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    /// Lowercased host the cookie belongs to, without a leading dot.
+    pub domain: String,
+    /// Set when the server sent no `Domain` attribute: subdomains do not receive it.
+    pub host_only: bool,
+    pub path: String,
+    pub secure: bool,
+    pub http_only: bool,
+    /// `None` for session cookies, which live until the jar is cleared.
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn same_slot(&self, other: &StoredCookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str().map(str::to_ascii_lowercase) else {
+            return false;
+        };
+        let domain_matches = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(&host, &self.domain)
+        };
+        domain_matches
+            && path_match(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+    }
+}
+
+/// Cookie stores keyed by environment, shared by every request the client sends.
+///
+/// reqwest's own `Jar` cannot list or delete individual cookies, so this implements
+/// `CookieStore` directly. Requests pick their store through [`COOKIE_SCOPE`]; requests sent
+/// without an active environment share the `None` store.
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    stores: Mutex<HashMap<Option<Uuid>, Vec<StoredCookie>>>,
+}
+
+impl CookieJar {
+    /// A panic while the lock was held leaves at worst a half-updated cookie list, so a
+    /// poisoned lock is recovered rather than taking every later request down with it.
+    fn stores(&self) -> MutexGuard<'_, HashMap<Option<Uuid>, Vec<StoredCookie>>> {
+        self.stores
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Unexpired cookies of one environment, ordered by domain, path and name.
+    pub fn cookies(&self, scope: Option<Uuid>) -> Vec<StoredCookie> {
+        let now = Utc::now();
+        let stores = self.stores();
+        let mut cookies: Vec<_> = stores
+            .get(&scope)
+            .into_iter()
+            .flatten()
+            .filter(|cookie| !cookie.is_expired(now))
+            .cloned()
+            .collect();
+        cookies.sort_by(|a, b| (&a.domain, &a.path, &a.name).cmp(&(&b.domain, &b.path, &b.name)));
+        cookies
+    }
+
+    pub fn remove(&self, scope: Option<Uuid>, cookie: &StoredCookie) {
+        let mut stores = self.stores();
+        if let Some(store) = stores.get_mut(&scope) {
+            store.retain(|stored| !stored.same_slot(cookie));
+        }
+    }
+
+    pub fn clear(&self, scope: Option<Uuid>) {
+        self.stores().remove(&scope);
+    }
+
+    fn store(&self, scope: Option<Uuid>, cookie: StoredCookie) {
+        let now = Utc::now();
+        let mut stores = self.stores();
+        let store = stores.entry(scope).or_default();
+        store.retain(|stored| !stored.same_slot(&cookie) && !stored.is_expired(now));
+        // An expiry in the past is how servers delete a cookie.
+        if !cookie.is_expired(now) {
+            store.push(cookie);
+        }
+    }
+
+    /// The `Cookie` header a request to `url` carries.
+    pub(super) fn header_for(&self, scope: Option<Uuid>, url: &Url) -> Option<String> {
+        let now = Utc::now();
+        let stores = self.stores();
+        let mut matching: Vec<_> = stores
+            .get(&scope)?
+            .iter()
+            .filter(|cookie| !cookie.is_expired(now) && cookie.matches(url))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        // Longer paths first, as RFC 6265 asks user agents to send them.
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        Some(
+            matching
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+}
+
+fn current_scope() -> Option<Uuid> {
+    COOKIE_SCOPE.try_with(|scope| *scope).unwrap_or(None)
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let scope = current_scope();
+        for header in cookie_headers {
+            if let Some(cookie) = header
+                .to_str()
+                .ok()
+                .and_then(|header| parse_set_cookie(header, url, Utc::now()))
+            {
+                self.store(scope, cookie);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.header_for(current_scope(), url)
+            .and_then(|header| HeaderValue::from_str(&header).ok())
+    }
+}
+
+/// Parse one `Set-Cookie` value received from `url`, rejecting cookies for foreign domains.
+fn parse_set_cookie(header: &str, url: &Url, now: DateTime<Utc>) -> Option<StoredCookie> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let mut parts = header.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut cookie = StoredCookie {
+        name: name.to_string(),
+        value: value.trim().to_string(),
+        domain: host.clone(),
+        host_only: true,
+        path: default_path(url.path()),
+        secure: false,
+        http_only: false,
+        expires: None,
+    };
+    let mut max_age = None;
+
+    for attribute in parts {
+        let (key, value) = match attribute.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => (attribute.trim(), ""),
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "domain" if !value.is_empty() => {
+                let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                // A bare TLD like `com` would leak the cookie to every site under it.
+                if !domain_match(&host, &domain) || (!domain.contains('.') && domain != host) {
+                    return None;
+                }
+                cookie.domain = domain;
+                cookie.host_only = false;
+            }
+            "path" if value.starts_with('/') => cookie.path = value.to_string(),
+            "secure" => cookie.secure = true,
+            "httponly" => cookie.http_only = true,
+            "max-age" => max_age = value.parse::<i64>().ok(),
+            "expires" => {
+                if let Ok(expires) = DateTime::parse_from_rfc2822(value) {
+                    cookie.expires = Some(expires.with_timezone(&Utc));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Max-Age wins over Expires when both are present.
+    if let Some(seconds) = max_age {
+        cookie.expires = ChronoDuration::try_seconds(seconds.max(0))
+            .and_then(|age| now.checked_add_signed(age))
+            .or(Some(DateTime::<Utc>::MAX_UTC));
+    }
+    Some(cookie)
+}
+
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+fn path_match(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || request_path
+            .strip_prefix(cookie_path)
+            .is_some_and(|rest| cookie_path.ends_with('/') || rest.starts_with('/'))
+}
+
+/// Directory of the request path, the default scope for cookies without `Path`.
+fn default_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => request_path[..index].to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CookieJar, parse_set_cookie};
+    use chrono::Utc;
+    use reqwest::Url;
+    use uuid::Uuid;
+
+    fn url(text: &str) -> Url {
+        Url::parse(text).unwrap()
+    }
+
+    fn store(jar: &CookieJar, scope: Option<Uuid>, header: &str, from: &str) {
+        let cookie = parse_set_cookie(header, &url(from), Utc::now()).unwrap();
+        jar.store(scope, cookie);
+    }
+
+    #[test]
+    fn parses_attributes_and_rejects_foreign_domains() {
+        let now = Utc::now();
+        let cookie = parse_set_cookie(
+            "session=abc; Domain=.example.com; Path=/api; Secure; HttpOnly; Max-Age=60",
+            &url("https://app.example.com/login"),
+            now,
+        )
+        .unwrap();
+        assert_eq!(cookie.domain, "example.com");
+        assert!(!cookie.host_only);
+        assert_eq!(cookie.path, "/api");
+        assert!(cookie.secure && cookie.http_only);
+        assert_eq!(cookie.expires, Some(now + chrono::Duration::seconds(60)));
+
+        let host_only = parse_set_cookie("id=1", &url("http://example.com/a/b"), now).unwrap();
+        assert!(host_only.host_only);
+        assert_eq!(host_only.path, "/a");
+
+        assert!(
+            parse_set_cookie("x=1; Domain=other.com", &url("http://example.com/"), now).is_none()
+        );
+        assert!(parse_set_cookie("novalue", &url("http://example.com/"), now).is_none());
+    }
+
+    #[test]
+    fn sends_matching_cookies_and_honours_scope() {
+        let jar = CookieJar::default();
+        let env = Some(Uuid::new_v4());
+        store(&jar, None, "a=1; Path=/", "http://example.com/");
+        store(&jar, None, "b=2; Path=/api", "http://example.com/");
+        store(&jar, None, "s=3; Secure", "https://example.com/");
+        store(&jar, env, "e=4", "http://example.com/");
+
+        assert_eq!(
+            jar.header_for(None, &url("http://example.com/api/users"))
+                .as_deref(),
+            Some("b=2; a=1")
+        );
+        assert_eq!(
+            jar.header_for(None, &url("http://example.com/apix"))
+                .as_deref(),
+            Some("a=1")
+        );
+        assert_eq!(
+            jar.header_for(None, &url("http://sub.example.com/")),
+            None,
+            "host-only cookies stay on their host"
+        );
+        assert_eq!(
+            jar.header_for(env, &url("http://example.com/")).as_deref(),
+            Some("e=4")
+        );
+    }
+
+    #[test]
+    fn replaces_deletes_and_clears_cookies() {
+        let jar = CookieJar::default();
+        store(&jar, None, "a=1", "http://example.com/");
+        store(&jar, None, "a=2", "http://example.com/");
+        store(&jar, None, "b=1", "http://example.com/");
+        let cookies = jar.cookies(None);
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].value, "2");
+
+        store(&jar, None, "b=gone; Max-Age=0", "http://example.com/");
+        assert_eq!(jar.cookies(None).len(), 1);
+
+        jar.remove(None, &cookies[0]);
+        assert!(jar.cookies(None).is_empty());
+
+        store(&jar, None, "c=1", "http://example.com/");
+        jar.clear(None);
+        assert!(jar.cookies(None).is_empty());
+    }
+}
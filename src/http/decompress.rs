@@ -0,0 +1,156 @@
+use bytes::Bytes;
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use std::io::Read;
+
+/// Most bytes a compressed body may decode to; anything bigger is kept as it arrived, so a
+/// small compressed bomb cannot take all memory.
+pub const MAX_DECODED_BODY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Outcome of undoing a response body's `Content-Encoding`.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Decoded {
+    /// The body was not encoded, uses a coding this client cannot decode, or fails to
+    /// decode; the caller keeps the bytes as they arrived.
+    Unchanged,
+    Body(Bytes),
+    /// The decoded body would be larger than the limit; the bytes are kept as they arrived.
+    TooLarge,
+}
+
+/// Undo the `Content-Encoding` codings of a response body, stopping at `limit` bytes.
+pub(super) fn decode_content(content_encoding: Option<&str>, raw: &[u8], limit: usize) -> Decoded {
+    let Some(content_encoding) = content_encoding else {
+        return Decoded::Unchanged;
+    };
+    let codings: Vec<_> = content_encoding
+        .split(',')
+        .map(|coding| coding.trim().to_ascii_lowercase())
+        .filter(|coding| !coding.is_empty() && coding != "identity")
+        .collect();
+    if codings.is_empty() {
+        return Decoded::Unchanged;
+    }
+
+    // Codings are listed in the order they were applied, so they are undone back to front.
+    let mut body = raw.to_vec();
+    for coding in codings.iter().rev() {
+        body = match decode_one(coding, &body, limit) {
+            Some(decoded) if decoded.len() > limit => return Decoded::TooLarge,
+            Some(decoded) => decoded,
+            None => return Decoded::Unchanged,
+        };
+    }
+    Decoded::Body(Bytes::from(body))
+}
+
+/// Short label for the encoded size, as in "4.1 KB gzipped".
+pub fn encoding_label(content_encoding: Option<&str>) -> &'static str {
+    match content_encoding
+        .and_then(|encoding| encoding.split(',').next_back())
+        .map(|coding| coding.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("gzip" | "x-gzip") => "gzipped",
+        Some("deflate") => "deflated",
+        _ => "compressed",
+    }
+}
+
+/// Decode one coding, reading one byte past `limit` so the caller can tell it was passed.
+fn decode_one(coding: &str, body: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let limit = limit as u64 + 1;
+    let mut decoded = Vec::new();
+    match coding {
+        "gzip" | "x-gzip" => GzDecoder::new(body)
+            .take(limit)
+            .read_to_end(&mut decoded)
+            .ok()?,
+        // `deflate` is meant to be zlib-wrapped, but some servers send a bare deflate stream.
+        "deflate" => match ZlibDecoder::new(body).take(limit).read_to_end(&mut decoded) {
+            Ok(read) => read,
+            Err(_) => {
+                decoded.clear();
+                DeflateDecoder::new(body)
+                    .take(limit)
+                    .read_to_end(&mut decoded)
+                    .ok()?
+            }
+        },
+        _ => return None,
+    };
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decoded, MAX_DECODED_BODY_BYTES, decode_content, encoding_label};
+    use bytes::Bytes;
+    use flate2::Compression;
+    use flate2::write::{DeflateEncoder, GzEncoder, ZlibEncoder};
+    use std::io::Write;
+
+    fn encode<W: Write>(mut encoder: W, finish: impl FnOnce(W) -> Vec<u8>) -> Vec<u8> {
+        encoder.write_all(b"hello hello hello").unwrap();
+        finish(encoder)
+    }
+
+    #[test]
+    fn decodes_gzip_and_both_deflate_flavours() {
+        let gzip = encode(GzEncoder::new(Vec::new(), Compression::default()), |e| {
+            e.finish().unwrap()
+        });
+        let zlib = encode(ZlibEncoder::new(Vec::new(), Compression::default()), |e| {
+            e.finish().unwrap()
+        });
+        let raw_deflate = encode(
+            DeflateEncoder::new(Vec::new(), Compression::default()),
+            |e| e.finish().unwrap(),
+        );
+
+        for (encoding, body) in [("gzip", gzip), ("deflate", zlib), ("deflate", raw_deflate)] {
+            assert_eq!(
+                decode_content(Some(encoding), &body, MAX_DECODED_BODY_BYTES),
+                Decoded::Body(Bytes::from_static(b"hello hello hello"))
+            );
+        }
+    }
+
+    #[test]
+    fn stops_decoding_past_the_limit() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&vec![0; 64 * 1024]).unwrap();
+        let bomb = encoder.finish().unwrap();
+        assert!(bomb.len() < 1024);
+
+        assert_eq!(
+            decode_content(Some("gzip"), &bomb, 16 * 1024),
+            Decoded::TooLarge
+        );
+        assert!(matches!(
+            decode_content(Some("gzip"), &bomb, 64 * 1024),
+            Decoded::Body(body) if body.len() == 64 * 1024
+        ));
+    }
+
+    #[test]
+    fn leaves_plain_unknown_or_corrupt_bodies_alone() {
+        for (encoding, body) in [
+            (None, &b"plain"[..]),
+            (Some("identity"), b"plain"),
+            (Some("br"), b"plain"),
+            (Some("gzip"), b"not gzip"),
+        ] {
+            assert_eq!(
+                decode_content(encoding, body, MAX_DECODED_BODY_BYTES),
+                Decoded::Unchanged
+            );
+        }
+    }
+
+    #[test]
+    fn labels_the_outermost_coding() {
+        assert_eq!(encoding_label(Some("gzip")), "gzipped");
+        assert_eq!(encoding_label(Some("gzip, deflate")), "deflated");
+        assert_eq!(encoding_label(Some("br")), "compressed");
+    }
+}
@@ -0,0 +1,53 @@
+use crate::entities::InformationalResponse;
+use anyhow::Result;
+use reqwest::RequestBuilder;
+use std::sync::{Arc, Mutex};
+
+/// Interim 1xx responses read while a request waits for its final response.
+///
+/// hyper skips these once it has told the request's hook about them, and the hook runs on
+/// the connection task, so it records into shared storage rather than a task-local.
+#[derive(Clone, Default)]
+pub(super) struct InformationalLog {
+    responses: Arc<Mutex<Vec<InformationalResponse>>>,
+}
+
+impl InformationalLog {
+    /// Have hyper report the interim responses to `request` into this log.
+    pub(super) fn attach(&self, request: RequestBuilder) -> Result<RequestBuilder> {
+        let (client, request) = request.build_split();
+        let mut request: hyper::Request<reqwest::Body> = request?.try_into()?;
+        let responses = self.responses.clone();
+        hyper::ext::on_informational(&mut request, move |response| {
+            let status = response.status();
+            let interim = InformationalResponse {
+                status_code: status.as_u16(),
+                status_text: status.canonical_reason().unwrap_or("Unknown").to_string(),
+                headers: response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            name.to_string(),
+                            String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                        )
+                    })
+                    .collect(),
+            };
+            if let Ok(mut responses) = responses.lock() {
+                responses.push(interim);
+            }
+        });
+        Ok(RequestBuilder::from_parts(
+            client,
+            reqwest::Request::try_from(request)?,
+        ))
+    }
+
+    pub(super) fn take(&self) -> Vec<InformationalResponse> {
+        self.responses
+            .lock()
+            .map(|mut responses| std::mem::take(&mut *responses))
+            .unwrap_or_default()
+    }
+}
@@ -1,3 +1,24 @@
 pub mod client;
+pub mod cookies;
+pub mod decompress;
+mod informational;
+pub mod oauth;
+pub mod pool;
+pub mod proxy;
+pub mod retry;
+pub mod sigv4;
+pub mod sse;
+pub mod tls;
+mod upload;
+pub mod websocket;
 
 pub use client::*;
+pub use cookies::*;
+pub use decompress::*;
+pub use pool::*;
+pub use proxy::*;
+pub use retry::*;
+pub use sigv4::*;
+pub use sse::*;
+pub use tls::*;
+pub use websocket::*;
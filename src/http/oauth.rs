@@ -0,0 +1,141 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{HttpClient, RequestOptions};
+use crate::entities::{Header, HttpMethod, OAuthToken, RequestBody, ResponseData};
+
+/// Fields of an RFC 6749 token response, success or error.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    /// Seconds; a few providers send it as a string.
+    expires_in: Option<Value>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+impl HttpClient {
+    /// POST a resolved token request and read the access token from the reply. `options`
+    /// carries the environment's proxy and TLS settings, as for the request itself.
+    ///
+    /// The returned future only waits on a channel, so GPUI tasks can await it directly.
+    pub fn fetch_oauth_token(
+        &self,
+        url: String,
+        headers: Vec<Header>,
+        body: RequestBody,
+        options: RequestOptions,
+    ) -> impl Future<Output = Result<OAuthToken>> + use<> {
+        let (result_rx, _updates, _in_flight) =
+            self.spawn_request(HttpMethod::Post, url, headers, body, options);
+        async move {
+            let response = result_rx
+                .await
+                .map_err(|_| anyhow!("Token request stopped unexpectedly"))??;
+            parse_token_response(&response, Utc::now())
+        }
+    }
+}
+
+fn parse_token_response(response: &ResponseData, now: DateTime<Utc>) -> Result<OAuthToken> {
+    let body = response.text_snapshot().raw_body();
+    let parsed = serde_json::from_str::<TokenResponse>(&body).ok();
+
+    if let Some(error) = parsed.as_ref().and_then(|parsed| parsed.error.as_deref()) {
+        let description = parsed
+            .as_ref()
+            .and_then(|parsed| parsed.error_description.as_deref());
+        return Err(match description {
+            Some(description) => anyhow!("{error}: {description}"),
+            None => anyhow!("{error}"),
+        });
+    }
+    if !(200..300).contains(&response.status_code) {
+        return Err(anyhow!(
+            "Token endpoint returned {} {}",
+            response.status_code,
+            response.status_text
+        ));
+    }
+
+    let parsed = parsed.ok_or_else(|| anyhow!("Token endpoint did not return JSON"))?;
+    let access_token = parsed
+        .access_token
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| anyhow!("Token response has no access_token"))?;
+    let expires_in = parsed.expires_in.and_then(|value| match value {
+        Value::Number(number) => number.as_i64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    });
+
+    Ok(OAuthToken {
+        access_token,
+        expires_at: expires_in
+            .and_then(Duration::try_seconds)
+            .and_then(|lifetime| now.checked_add_signed(lifetime)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_token_response;
+    use crate::entities::ResponseData;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    fn response(status_code: u16, body: &str) -> ResponseData {
+        ResponseData::new(
+            status_code,
+            String::new(),
+            HashMap::new(),
+            body.to_string(),
+            Vec::new(),
+            body.len(),
+            0,
+            Some("application/json".to_string()),
+        )
+    }
+
+    #[test]
+    fn reads_the_token_and_its_lifetime() {
+        let now = Utc::now();
+        let token = parse_token_response(
+            &response(
+                200,
+                r#"{"access_token":"abc","token_type":"Bearer","expires_in":3600}"#,
+            ),
+            now,
+        )
+        .unwrap();
+        assert_eq!(token.access_token, "abc");
+        assert_eq!(token.expires_at, Some(now + Duration::seconds(3600)));
+
+        let lenient = parse_token_response(
+            &response(200, r#"{"access_token":"x","expires_in":"60"}"#),
+            now,
+        )
+        .unwrap();
+        assert_eq!(lenient.expires_at, Some(now + Duration::seconds(60)));
+    }
+
+    #[test]
+    fn surfaces_oauth_errors() {
+        let now = Utc::now();
+        let error = parse_token_response(
+            &response(
+                401,
+                r#"{"error":"invalid_client","error_description":"Bad secret"}"#,
+            ),
+            now,
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "invalid_client: Bad secret");
+
+        let error = parse_token_response(&response(500, "oops"), now).unwrap_err();
+        assert!(error.to_string().starts_with("Token endpoint returned 500"));
+        assert!(parse_token_response(&response(200, "{}"), now).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How the shared client keeps connections open between requests.
+///
+/// By default idle connections are kept for 90 seconds with no per-host cap, and TCP
+/// keep-alive probes go out every 60 seconds so idle connections through NATs and load
+/// balancers are noticed before a request is sent on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PoolConfig {
+    /// Seconds an idle connection is kept for reuse; 0 closes it once its response is read.
+    pub idle_timeout_secs: u64,
+    /// Idle connections kept per host; `None` keeps as many as were opened.
+    pub max_idle_per_host: Option<usize>,
+    /// Seconds between TCP keep-alive probes on open connections; 0 sends none.
+    pub tcp_keepalive_secs: u64,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout_secs: 90,
+            max_idle_per_host: None,
+            tcp_keepalive_secs: 60,
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        builder
+            .pool_idle_timeout(Duration::from_secs(self.idle_timeout_secs))
+            .pool_max_idle_per_host(self.max_idle_per_host.unwrap_or(usize::MAX))
+            .tcp_keepalive(
+                (self.tcp_keepalive_secs > 0).then(|| Duration::from_secs(self.tcp_keepalive_secs)),
+            )
+    }
+
+    pub fn describe(&self) -> String {
+        let idle = match self.idle_timeout_secs {
+            0 => "closed when idle".to_string(),
+            secs => format!("idle kept {secs}s"),
+        };
+        let per_host = match self.max_idle_per_host {
+            None => "no per-host limit".to_string(),
+            Some(limit) => format!("up to {limit} per host"),
+        };
+        let keepalive = match self.tcp_keepalive_secs {
+            0 => "no TCP keep-alive".to_string(),
+            secs => format!("TCP keep-alive every {secs}s"),
+        };
+        format!("{idle}, {per_host}, {keepalive}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PoolConfig;
+
+    #[test]
+    fn describes_defaults_and_disabled_settings() {
+        assert_eq!(
+            PoolConfig::default().describe(),
+            "idle kept 90s, no per-host limit, TCP keep-alive every 60s"
+        );
+        let tight = PoolConfig {
+            idle_timeout_secs: 0,
+            max_idle_per_host: Some(4),
+            tcp_keepalive_secs: 0,
+        };
+        assert_eq!(
+            tight.describe(),
+            "closed when idle, up to 4 per host, no TCP keep-alive"
+        );
+    }
+}
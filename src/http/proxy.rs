@@ -0,0 +1,82 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// An outbound proxy that requests are routed through.
+///
+/// An empty URL means no proxy of our own, leaving the system settings in charge.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    /// `http://`, `https://` or `socks5://` URL of the proxy.
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// Comma-separated hosts, `.domain` suffixes and IP ranges reached directly.
+    pub no_proxy: String,
+}
+
+impl ProxyConfig {
+    pub fn is_empty(&self) -> bool {
+        self.url.trim().is_empty()
+    }
+
+    pub fn to_reqwest(&self) -> Result<reqwest::Proxy> {
+        let url = self.url.trim();
+        let scheme = url.split_once("://").map(|(scheme, _)| scheme);
+        if !matches!(scheme, Some("http" | "https" | "socks5" | "socks5h")) {
+            return Err(anyhow!(
+                "Proxy URL must start with http://, https:// or socks5://: {url}"
+            ));
+        }
+        let mut proxy = reqwest::Proxy::all(url)
+            .map_err(|error| anyhow!("Invalid proxy URL {url}: {error}"))?;
+        if !self.username.is_empty() {
+            proxy = proxy.basic_auth(&self.username, &self.password);
+        }
+        Ok(proxy.no_proxy(reqwest::NoProxy::from_string(&self.no_proxy)))
+    }
+
+    /// The proxy as shown to the user: its URL and user name, never the password.
+    pub fn describe(&self) -> String {
+        let mut description = self.url.trim().to_string();
+        if !self.username.is_empty() {
+            description.push_str(&format!(" as {}", self.username));
+        }
+        if !self.no_proxy.trim().is_empty() {
+            description.push_str(&format!(", bypassed for {}", self.no_proxy.trim()));
+        }
+        description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyConfig;
+
+    #[test]
+    fn accepts_http_and_socks_proxies_only() {
+        let proxy = |url: &str| ProxyConfig {
+            url: url.to_string(),
+            ..ProxyConfig::default()
+        };
+        assert!(proxy("http://proxy.corp:3128").to_reqwest().is_ok());
+        assert!(proxy("socks5://127.0.0.1:1080").to_reqwest().is_ok());
+        assert!(proxy("proxy.corp:3128").to_reqwest().is_err());
+        assert!(proxy("ftp://proxy.corp").to_reqwest().is_err());
+        assert!(proxy("  ").is_empty());
+    }
+
+    #[test]
+    fn describes_the_proxy_without_its_password() {
+        let proxy = ProxyConfig {
+            url: "http://proxy.corp:3128".to_string(),
+            username: "alice".to_string(),
+            password: "hunter2".to_string(),
+            no_proxy: "localhost, .internal".to_string(),
+        };
+        assert_eq!(
+            proxy.describe(),
+            "http://proxy.corp:3128 as alice, bypassed for localhost, .internal"
+        );
+    }
+}
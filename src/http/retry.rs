@@ -0,0 +1,229 @@
+use crate::entities::{HttpMethod, ResponseData};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+/// Longest wait between two attempts, however many have failed.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// How often a request is tried again after a connection error or a 5xx response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts in total, the first one included; 1 turns retrying off.
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for every one after it.
+    pub base_delay_ms: u64,
+    /// Also retry POST and PATCH, which may repeat their side effect on the server.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay_ms: 500,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn retries(&self) -> bool {
+        self.max_attempts > 1
+    }
+
+    /// Attempts in total for a `method` request: only idempotent methods are sent again
+    /// unless the policy opts into the others.
+    pub(super) fn attempts_for(&self, method: HttpMethod) -> u32 {
+        let idempotent = matches!(
+            method,
+            HttpMethod::Get
+                | HttpMethod::Head
+                | HttpMethod::Put
+                | HttpMethod::Delete
+                | HttpMethod::Options
+        );
+        if idempotent || self.retry_non_idempotent {
+            self.max_attempts
+        } else {
+            1
+        }
+    }
+
+    /// Wait before attempt `attempt` (2 for the first retry), given `jitter` in `0.0..1.0`.
+    ///
+    /// The exponential delay is scaled into its upper half by the jitter, so clients that
+    /// failed together spread out without any of them retrying almost at once.
+    pub(super) fn delay_before(&self, attempt: u32, jitter: f64) -> Duration {
+        let doublings = attempt.saturating_sub(2).min(16);
+        let delay = Duration::from_millis(self.base_delay_ms)
+            .saturating_mul(1 << doublings)
+            .min(MAX_RETRY_DELAY);
+        delay.mul_f64(0.5 + 0.5 * jitter.clamp(0.0, 1.0))
+    }
+}
+
+/// Why `result` is worth another attempt: the server failed with a 5xx status or could not
+/// be reached at all. Timeouts and other errors are reported straight away.
+pub(super) fn retry_reason(result: &Result<ResponseData>) -> Option<String> {
+    match result {
+        Ok(response) if response.status_code >= 500 => {
+            Some(format!("{} {}", response.status_code, response.status_text))
+        }
+        Ok(_) => None,
+        Err(error) => error
+            .downcast_ref::<reqwest::Error>()
+            .filter(|error| error.is_connect())
+            .map(|_| "Connection failed".to_string()),
+    }
+}
+
+/// Runs `attempt` until it succeeds, fails for a reason not worth retrying, or the policy
+/// runs out of attempts for `method`. `on_retry` hears about each retry before its backoff.
+///
+/// Returns the last result and how many attempts were made.
+pub(super) async fn run_with_retries<F, Fut>(
+    policy: RetryPolicy,
+    method: HttpMethod,
+    mut attempt: F,
+    mut on_retry: impl FnMut(u32, u32, String),
+) -> (Result<ResponseData>, u32)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<ResponseData>>,
+{
+    let max_attempts = policy.attempts_for(method);
+    let mut attempts = 1;
+    loop {
+        let result = attempt().await;
+        let Some(reason) = retry_reason(&result).filter(|_| attempts < max_attempts) else {
+            return (result, attempts);
+        };
+        attempts += 1;
+        on_retry(attempts, max_attempts, reason);
+        tokio::time::sleep(policy.delay_before(attempts, jitter())).await;
+    }
+}
+
+/// A random fraction in `0.0..1.0` for spreading out retries.
+pub(super) fn jitter() -> f64 {
+    let (random, _) = uuid::Uuid::new_v4().as_u64_pair();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RetryPolicy, jitter, retry_reason, run_with_retries};
+    use crate::entities::{HttpMethod, ResponseData};
+    use anyhow::anyhow;
+    use std::cell::RefCell;
+    use std::time::Duration;
+
+    /// Runs the retry loop over canned status codes, returning the final status, the
+    /// attempts made and the attempt numbers announced as retries.
+    fn run(policy: RetryPolicy, method: HttpMethod, statuses: &[u16]) -> (u16, u32, Vec<u32>) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let remaining = RefCell::new(statuses.iter().copied());
+        let announced = RefCell::new(Vec::new());
+        let (result, attempts) = runtime.block_on(run_with_retries(
+            policy,
+            method,
+            || {
+                let status_code = remaining.borrow_mut().next().unwrap();
+                async move {
+                    Ok(ResponseData {
+                        status_code,
+                        ..ResponseData::default()
+                    })
+                }
+            },
+            |attempt, _, _| announced.borrow_mut().push(attempt),
+        ));
+        (
+            result.unwrap().status_code,
+            attempts,
+            announced.into_inner(),
+        )
+    }
+
+    fn policy(max_attempts: u32, retry_non_idempotent: bool) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay_ms: 0,
+            retry_non_idempotent,
+        }
+    }
+
+    #[test]
+    fn doubles_the_delay_up_to_the_cap_and_applies_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay_ms: 200,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.delay_before(2, 1.0), Duration::from_millis(200));
+        assert_eq!(policy.delay_before(3, 1.0), Duration::from_millis(400));
+        assert_eq!(policy.delay_before(4, 0.0), Duration::from_millis(400));
+        assert_eq!(policy.delay_before(40, 1.0), Duration::from_secs(30));
+        let sample = jitter();
+        assert!((0.0..1.0).contains(&sample));
+        assert!(!RetryPolicy::default().retries());
+    }
+
+    #[test]
+    fn retries_server_errors_but_not_client_errors() {
+        let response = |status_code| {
+            Ok(ResponseData {
+                status_code,
+                status_text: "Service Unavailable".to_string(),
+                ..ResponseData::default()
+            })
+        };
+        assert_eq!(
+            retry_reason(&response(503)),
+            Some("503 Service Unavailable".to_string())
+        );
+        assert_eq!(retry_reason(&response(404)), None);
+        assert_eq!(
+            retry_reason(&Err(anyhow!("Request timed out after 30s"))),
+            None
+        );
+    }
+
+    #[test]
+    fn retries_idempotent_methods_until_one_succeeds() {
+        assert_eq!(
+            run(policy(3, false), HttpMethod::Get, &[503, 502, 200]),
+            (200, 3, vec![2, 3])
+        );
+        assert_eq!(
+            run(policy(2, false), HttpMethod::Delete, &[503, 503]),
+            (503, 2, vec![2])
+        );
+        assert_eq!(
+            run(policy(3, false), HttpMethod::Put, &[404]),
+            (404, 1, vec![])
+        );
+    }
+
+    #[test]
+    fn sends_non_idempotent_methods_once_unless_opted_in() {
+        assert_eq!(
+            run(policy(3, false), HttpMethod::Post, &[503]),
+            (503, 1, vec![])
+        );
+        assert_eq!(
+            run(policy(3, false), HttpMethod::Patch, &[503]),
+            (503, 1, vec![])
+        );
+        assert_eq!(
+            run(policy(3, true), HttpMethod::Post, &[503, 200]),
+            (200, 2, vec![2])
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Url};
+use sha2::{Digest, Sha256};
+
+/// Credentials and scope for AWS Signature Version 4, already resolved from templates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AwsSigV4 {
+    pub access_key: String,
+    pub secret_key: String,
+    pub region: String,
+    pub service: String,
+    /// Temporary credentials come with a session token; empty for long-term keys.
+    pub session_token: String,
+}
+
+/// Payload hash for bodies that are streamed, like multipart uploads, and so cannot be hashed
+/// up front. S3 accepts it; most other services reject such requests.
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Add `X-Amz-Date`, the optional session token and `Authorization` to a fully built request.
+///
+/// `payload` is `None` when the body is a stream. Only `host`, `content-type` and `x-amz-*`
+/// headers are signed, since the HTTP stack may still add or reorder the others.
+pub(super) fn sign_request(
+    credentials: &AwsSigV4,
+    method: &Method,
+    url: &Url,
+    headers: &mut HeaderMap,
+    payload: Option<&[u8]>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    if credentials.access_key.is_empty() || credentials.secret_key.is_empty() {
+        return Err(anyhow!(
+            "AWS Signature needs an access key and a secret key"
+        ));
+    }
+    if credentials.region.is_empty() || credentials.service.is_empty() {
+        return Err(anyhow!("AWS Signature needs a region and a service"));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("Cannot sign a request without a host"))?;
+    let host = match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    };
+
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = payload.map_or_else(|| UNSIGNED_PAYLOAD.to_string(), sha256_hex);
+    let is_s3 = credentials.service.eq_ignore_ascii_case("s3");
+
+    insert(headers, "x-amz-date", &amz_date)?;
+    if !credentials.session_token.is_empty() {
+        insert(headers, "x-amz-security-token", &credentials.session_token)?;
+    }
+    // S3 requires the payload hash as a header; other services compute it themselves.
+    if is_s3 || payload.is_none() {
+        insert(headers, "x-amz-content-sha256", &payload_hash)?;
+    }
+
+    let mut signed: Vec<(String, String)> = vec![("host".to_string(), host)];
+    for (name, value) in headers.iter() {
+        let name = name.as_str();
+        if name == "content-type" || name.starts_with("x-amz-") {
+            let value = value
+                .to_str()
+                .map_err(|_| anyhow!("Header {name} is not valid text and cannot be signed"))?;
+            signed.push((name.to_string(), collapse_whitespace(value)));
+        }
+    }
+    signed.sort();
+    // Repeated headers are signed as one comma-separated value.
+    signed.dedup_by(|next, kept| {
+        if next.0 == kept.0 {
+            kept.1 = format!("{},{}", kept.1, next.1);
+            true
+        } else {
+            false
+        }
+    });
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{name}:{value}\n"))
+        .collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method.as_str(),
+        canonical_uri(url, is_s3),
+        canonical_query(url),
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+    let scope = format!(
+        "{date}/{}/{}/aws4_request",
+        credentials.region, credentials.service
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+        sha256_hex(canonical_request.as_bytes())
+    );
+
+    let mut key = hmac_sha256(
+        format!("AWS4{}", credentials.secret_key).as_bytes(),
+        date.as_bytes(),
+    );
+    for part in [
+        credentials.region.as_str(),
+        credentials.service.as_str(),
+        "aws4_request",
+    ] {
+        key = hmac_sha256(&key, part.as_bytes());
+    }
+    let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credentials.access_key
+    );
+    headers.insert(AUTHORIZATION, HeaderValue::from_str(&authorization)?);
+    Ok(())
+}
+
+fn insert(headers: &mut HeaderMap, name: &'static str, value: &str) -> Result<()> {
+    let value = HeaderValue::from_str(value)
+        .map_err(|_| anyhow!("Invalid characters in the {name} value"))?;
+    headers.insert(HeaderName::from_static(name), value);
+    Ok(())
+}
+
+/// The URL path is already percent-encoded once; every service but S3 signs it encoded twice.
+fn canonical_uri(url: &Url, is_s3: bool) -> String {
+    let path = match url.path() {
+        "" => "/",
+        path => path,
+    };
+    if is_s3 {
+        path.to_string()
+    } else {
+        path.split('/')
+            .map(aws_encode)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn canonical_query(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| (aws_encode(&key), aws_encode(&value)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// RFC 3986 encoding as SigV4 defines it: everything but unreserved characters is escaped.
+fn aws_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AwsSigV4, canonical_uri, sign_request};
+    use chrono::{TimeZone, Utc};
+    use reqwest::header::HeaderMap;
+    use reqwest::{Method, Url};
+
+    fn example_credentials() -> AwsSigV4 {
+        AwsSigV4 {
+            access_key: "AKIDEXAMPLE".to_string(),
+            secret_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            region: "us-east-1".to_string(),
+            service: "service".to_string(),
+            session_token: String::new(),
+        }
+    }
+
+    fn authorization(url: &str, credentials: &AwsSigV4) -> (String, HeaderMap) {
+        let mut headers = HeaderMap::new();
+        let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+        sign_request(
+            credentials,
+            &Method::GET,
+            &Url::parse(url).unwrap(),
+            &mut headers,
+            Some(&b""[..]),
+            now,
+        )
+        .unwrap();
+        let authorization = headers["authorization"].to_str().unwrap().to_string();
+        (authorization, headers)
+    }
+
+    #[test]
+    fn matches_the_aws_test_suite() {
+        // `get-vanilla` and `get-vanilla-query-order-key-case` from the SigV4 test suite.
+        let (vanilla, headers) =
+            authorization("https://example.amazonaws.com/", &example_credentials());
+        assert_eq!(
+            vanilla,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+        assert_eq!(headers["x-amz-date"], "20150830T123600Z");
+
+        let (ordered, _) = authorization(
+            "https://example.amazonaws.com/?Param2=value2&Param1=value1",
+            &example_credentials(),
+        );
+        assert!(ordered.ends_with(
+            "Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"
+        ));
+    }
+
+    #[test]
+    fn signs_session_tokens_and_rejects_missing_keys() {
+        let credentials = AwsSigV4 {
+            session_token: "session".to_string(),
+            ..example_credentials()
+        };
+        let (authorization, headers) =
+            authorization("https://example.amazonaws.com/", &credentials);
+        assert!(authorization.contains("SignedHeaders=host;x-amz-date;x-amz-security-token,"));
+        assert_eq!(headers["x-amz-security-token"], "session");
+
+        let mut headers = HeaderMap::new();
+        let missing = AwsSigV4 {
+            secret_key: String::new(),
+            ..example_credentials()
+        };
+        assert!(
+            sign_request(
+                &missing,
+                &Method::GET,
+                &Url::parse("https://example.amazonaws.com/").unwrap(),
+                &mut headers,
+                None,
+                Utc::now(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn double_encodes_paths_except_for_s3() {
+        let url = Url::parse("https://example.com/my%20file/a b").unwrap();
+        assert_eq!(canonical_uri(&url, false), "/my%2520file/a%2520b");
+        assert_eq!(canonical_uri(&url, true), "/my%20file/a%20b");
+    }
+}
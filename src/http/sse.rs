@@ -0,0 +1,152 @@
+use chrono::{DateTime, Local};
+
+/// One dispatched Server-Sent Event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event type from the `event:` field, `message` when the server sent none.
+    pub event: String,
+    /// `data:` lines joined with newlines.
+    pub data: String,
+    /// Last event ID seen on the stream, which persists across events like the spec requires.
+    pub id: Option<String>,
+    pub received_at: DateTime<Local>,
+}
+
+/// Whether a response `Content-Type` announces an event stream.
+pub fn is_event_stream(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|ct| ct.split(';').next())
+        .is_some_and(|ct| ct.trim().eq_ignore_ascii_case("text/event-stream"))
+}
+
+/// Incremental `text/event-stream` parser fed with raw body chunks.
+///
+/// Chunks may split lines and UTF-8 sequences anywhere; bytes are buffered until a line
+/// ends. An event the stream never terminates with a blank line is not dispatched.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    after_cr: bool,
+    event: Option<String>,
+    data: Vec<String>,
+    last_id: Option<String>,
+}
+
+impl SseParser {
+    /// Feed the next body chunk and return the events it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            // A CRLF pair is one line break; the CR already ended the line.
+            if std::mem::take(&mut self.after_cr) && byte == b'\n' {
+                continue;
+            }
+            match byte {
+                b'\r' => {
+                    self.after_cr = true;
+                    self.end_line(&mut events);
+                }
+                b'\n' => self.end_line(&mut events),
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    fn end_line(&mut self, events: &mut Vec<SseEvent>) {
+        let line = String::from_utf8_lossy(&std::mem::take(&mut self.line)).into_owned();
+        if line.is_empty() {
+            events.extend(self.dispatch());
+            return;
+        }
+        if line.starts_with(':') {
+            return;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_str(), ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            // `retry:` only matters to reconnecting clients; unknown fields are ignored.
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(SseEvent {
+            event: event
+                .filter(|event| !event.is_empty())
+                .unwrap_or_else(|| "message".to_string()),
+            data: std::mem::take(&mut self.data).join("\n"),
+            id: self.last_id.clone().filter(|id| !id.is_empty()),
+            received_at: Local::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SseParser, is_event_stream};
+
+    fn fields(parser: &mut SseParser, chunk: &str) -> Vec<(String, String, Option<String>)> {
+        parser
+            .feed(chunk.as_bytes())
+            .into_iter()
+            .map(|event| (event.event, event.data, event.id))
+            .collect()
+    }
+
+    #[test]
+    fn parses_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(fields(&mut parser, "event: tick\r\nda").is_empty());
+        assert_eq!(
+            fields(&mut parser, "ta: 1\r\n\r\ndata:plain\n\n"),
+            vec![
+                ("tick".to_string(), "1".to_string(), None),
+                ("message".to_string(), "plain".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn joins_multiline_data_and_keeps_the_last_id() {
+        let mut parser = SseParser::default();
+        let events = fields(
+            &mut parser,
+            ": keep-alive\nid: 7\ndata: first\ndata: second\n\nevent: empty\n\ndata: next\n\n",
+        );
+        assert_eq!(
+            events,
+            vec![
+                (
+                    "message".to_string(),
+                    "first\nsecond".to_string(),
+                    Some("7".to_string())
+                ),
+                (
+                    "message".to_string(),
+                    "next".to_string(),
+                    Some("7".to_string())
+                ),
+            ]
+        );
+        assert!(fields(&mut parser, "data: unterminated\n").is_empty());
+    }
+
+    #[test]
+    fn detects_event_stream_content_types() {
+        assert!(is_event_stream(Some("text/event-stream")));
+        assert!(is_event_stream(Some("Text/Event-Stream; charset=utf-8")));
+        assert!(!is_event_stream(Some("text/plain")));
+        assert!(!is_event_stream(None));
+    }
+}
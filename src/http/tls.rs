@@ -0,0 +1,252 @@
+use anyhow::{Result, anyhow};
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Client certificate and extra trusted CAs for servers that require mutual TLS.
+///
+/// Paths are read when a client is built, so replacing a file on disk takes effect on the
+/// next launch or configuration change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    /// PEM file with the certificate chain, or a `.p12`/`.pfx` bundle holding the key too.
+    pub client_cert: String,
+    /// PEM private key, when it is not in the certificate file.
+    pub client_key: String,
+    /// Password of a PKCS#12 bundle.
+    pub pkcs12_password: String,
+    /// PEM bundle of CA certificates trusted on top of the built-in roots.
+    pub ca_bundle: String,
+    /// Skip certificate verification, for local servers with self-signed certificates.
+    pub accept_invalid_certs: bool,
+}
+
+/// The parts of a certificate worth showing next to its path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateSummary {
+    pub common_name: Option<String>,
+    pub not_after: DateTime<Utc>,
+}
+
+impl CertificateSummary {
+    fn from_der(der: &[u8]) -> Result<Self> {
+        let (_, certificate) = x509_parser::parse_x509_certificate(der)
+            .map_err(|error| anyhow!("Not a valid X.509 certificate: {error}"))?;
+        let common_name = certificate
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|name| name.as_str().ok())
+            .map(str::to_string);
+        let not_after = DateTime::from_timestamp(certificate.validity().not_after.timestamp(), 0)
+            .ok_or_else(|| anyhow!("Certificate expiry is out of range"))?;
+        Ok(Self {
+            common_name,
+            not_after,
+        })
+    }
+
+    pub fn describe(&self, now: DateTime<Utc>) -> String {
+        let name = self.common_name.as_deref().unwrap_or("no common name");
+        let verb = if self.not_after < now {
+            "expired"
+        } else {
+            "expires"
+        };
+        format!("CN={name}, {verb} {}", self.not_after.format("%Y-%m-%d"))
+    }
+}
+
+/// A [`TlsConfig`] read from disk and ready to hand to a client builder.
+pub struct LoadedTls {
+    identity: Option<reqwest::Identity>,
+    roots: Vec<reqwest::Certificate>,
+    accept_invalid_certs: bool,
+    /// The client certificate's leaf, when one is configured.
+    pub certificate: Option<CertificateSummary>,
+}
+
+impl TlsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.client_cert.trim().is_empty()
+            && self.ca_bundle.trim().is_empty()
+            && !self.accept_invalid_certs
+    }
+
+    /// Read and parse every configured file, naming the file in the error when one is bad.
+    pub fn load(&self) -> Result<LoadedTls> {
+        let (identity, certificate) = match self.client_cert.trim() {
+            "" => (None, None),
+            path if is_pkcs12(path) => {
+                let (pem, leaf) = pkcs12_to_pem(&read(path)?, &self.pkcs12_password)
+                    .map_err(|error| anyhow!("Could not read PKCS#12 bundle {path}: {error}"))?;
+                (Some(identity_from_pem(&pem, path)?), Some(leaf))
+            }
+            path => {
+                let mut pem = read(path)?;
+                let leaf = first_certificate(&pem).map_err(|error| {
+                    anyhow!("Could not read client certificate {path}: {error}")
+                })?;
+                let key_path = self.client_key.trim();
+                if !key_path.is_empty() {
+                    pem.push(b'\n');
+                    pem.extend(read(key_path)?);
+                }
+                (Some(identity_from_pem(&pem, path)?), Some(leaf))
+            }
+        };
+        let roots = match self.ca_bundle.trim() {
+            "" => Vec::new(),
+            path => reqwest::Certificate::from_pem_bundle(&read(path)?)
+                .map_err(|error| anyhow!("Could not read CA bundle {path}: {error}"))?,
+        };
+        Ok(LoadedTls {
+            identity,
+            roots,
+            accept_invalid_certs: self.accept_invalid_certs,
+            certificate,
+        })
+    }
+}
+
+impl LoadedTls {
+    pub fn apply(self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(identity) = self.identity {
+            builder = builder.identity(identity);
+        }
+        for root in self.roots {
+            builder = builder.add_root_certificate(root);
+        }
+        builder.danger_accept_invalid_certs(self.accept_invalid_certs)
+    }
+}
+
+fn read(path: &str) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|error| anyhow!("Could not open {path}: {error}"))
+}
+
+fn is_pkcs12(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("p12") || extension.eq_ignore_ascii_case("pfx")
+        })
+}
+
+fn identity_from_pem(pem: &[u8], path: &str) -> Result<reqwest::Identity> {
+    reqwest::Identity::from_pem(pem).map_err(|error| {
+        anyhow!("Client certificate {path} needs a PEM certificate and private key: {error}")
+    })
+}
+
+fn first_certificate(pem: &[u8]) -> Result<CertificateSummary> {
+    for block in x509_parser::pem::Pem::iter_from_buffer(pem) {
+        let block = block.map_err(|error| anyhow!("Malformed PEM: {error}"))?;
+        if block.label == "CERTIFICATE" {
+            return CertificateSummary::from_der(&block.contents);
+        }
+    }
+    Err(anyhow!("No certificate found"))
+}
+
+/// rustls cannot read PKCS#12 itself, so the bundle is unpacked into the PEM it accepts.
+fn pkcs12_to_pem(der: &[u8], password: &str) -> Result<(Vec<u8>, CertificateSummary)> {
+    let keystore = p12_keystore::KeyStore::from_pkcs12(der, password)
+        .map_err(|error| anyhow!("Wrong password or unsupported bundle: {error}"))?;
+    let (_, chain) = keystore
+        .private_key_chain()
+        .ok_or_else(|| anyhow!("The bundle has no private key"))?;
+    let leaf = chain
+        .chain()
+        .first()
+        .ok_or_else(|| anyhow!("The bundle has no certificate"))?;
+    let summary = CertificateSummary::from_der(leaf.as_der())?;
+    let mut pem = String::new();
+    for certificate in chain.chain() {
+        pem.push_str(&pem_block("CERTIFICATE", certificate.as_der()));
+    }
+    pem.push_str(&pem_block("PRIVATE KEY", chain.key()));
+    Ok((pem.into_bytes(), summary))
+}
+
+fn pem_block(label: &str, der: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut block = format!("-----BEGIN {label}-----\n");
+    for line in encoded.as_bytes().chunks(64) {
+        block.push_str(std::str::from_utf8(line).unwrap_or_default());
+        block.push('\n');
+    }
+    block.push_str(&format!("-----END {label}-----\n"));
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CertificateSummary, TlsConfig, is_pkcs12, pem_block};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn reports_which_file_failed_to_load() {
+        let config = TlsConfig {
+            client_cert: "/nonexistent/client.pem".to_string(),
+            ..TlsConfig::default()
+        };
+        let error = config.load().err().expect("missing file fails").to_string();
+        assert!(error.contains("/nonexistent/client.pem"), "{error}");
+
+        let config = TlsConfig {
+            ca_bundle: "/nonexistent/ca.pem".to_string(),
+            ..TlsConfig::default()
+        };
+        let error = config.load().err().expect("missing file fails").to_string();
+        assert!(error.contains("/nonexistent/ca.pem"), "{error}");
+        assert!(TlsConfig::default().load().is_ok());
+    }
+
+    #[test]
+    fn skipping_verification_alone_still_counts_as_configured() {
+        let config = TlsConfig {
+            accept_invalid_certs: true,
+            ..TlsConfig::default()
+        };
+        assert!(!config.is_empty());
+        assert!(TlsConfig::default().is_empty());
+        assert!(
+            config
+                .load()
+                .is_ok_and(|loaded| loaded.certificate.is_none())
+        );
+    }
+
+    #[test]
+    fn wraps_pem_blocks_and_recognises_pkcs12_bundles() {
+        let block = pem_block("CERTIFICATE", &[0; 60]);
+        let lines: Vec<_> = block.lines().collect();
+        assert_eq!(lines.first(), Some(&"-----BEGIN CERTIFICATE-----"));
+        assert_eq!(lines[1].len(), 64);
+        assert_eq!(lines[2].len(), 16);
+        assert_eq!(lines.last(), Some(&"-----END CERTIFICATE-----"));
+
+        assert!(is_pkcs12("/certs/client.P12"));
+        assert!(is_pkcs12("client.pfx"));
+        assert!(!is_pkcs12("client.pem"));
+    }
+
+    #[test]
+    fn describes_expired_certificates() {
+        let summary = CertificateSummary {
+            common_name: Some("api-client".to_string()),
+            not_after: Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap(),
+        };
+        let before = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            summary.describe(before),
+            "CN=api-client, expires 2026-03-01"
+        );
+        assert_eq!(summary.describe(after), "CN=api-client, expired 2026-03-01");
+    }
+}
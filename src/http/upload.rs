@@ -0,0 +1,121 @@
+use super::client::ResponseUpdate;
+use bytes::Bytes;
+use futures_util::Stream;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::mpsc;
+
+/// Bytes read from disk per body chunk.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Counts the file bytes a request body has handed to the connection so far.
+///
+/// Progress goes out on the update channel whenever it moves by a whole percent, so a
+/// large upload does not flood the UI with updates.
+pub(super) struct UploadTracker {
+    sent: AtomicU64,
+    total: AtomicU64,
+    updates: Mutex<Option<mpsc::UnboundedSender<ResponseUpdate>>>,
+}
+
+impl UploadTracker {
+    pub(super) fn new(updates: Option<mpsc::UnboundedSender<ResponseUpdate>>) -> Arc<Self> {
+        Arc::new(Self {
+            sent: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+            updates: Mutex::new(updates),
+        })
+    }
+
+    /// Count a file of `len` bytes toward the total before the body starts streaming.
+    pub(super) fn add_file(&self, len: u64) {
+        self.total.fetch_add(len, Ordering::Relaxed);
+    }
+
+    fn record(&self, len: usize) {
+        let total = self.total.load(Ordering::Relaxed);
+        let before = self.sent.fetch_add(len as u64, Ordering::Relaxed);
+        let sent = before + len as u64;
+        if total == 0 || (sent < total && before * 100 / total == sent * 100 / total) {
+            return;
+        }
+        if let Ok(updates) = self.updates.lock()
+            && let Some(updates) = updates.as_ref()
+        {
+            let _ = updates.send(ResponseUpdate::UploadProgress { sent, total });
+        }
+    }
+
+    /// Stop reporting. The body may outlive the response, and holding the channel open
+    /// would keep the caller waiting for updates that no longer matter.
+    pub(super) fn finish(&self) {
+        if let Ok(mut updates) = self.updates.lock() {
+            updates.take();
+        }
+    }
+}
+
+/// Read `reader` chunk by chunk as a request body, counting every chunk on `tracker`.
+pub(super) fn tracked_stream<R>(
+    reader: R,
+    tracker: Arc<UploadTracker>,
+) -> impl Stream<Item = std::io::Result<Bytes>> + Send + 'static
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    futures_util::stream::unfold(Some(reader), move |reader| {
+        let tracker = tracker.clone();
+        async move {
+            let mut reader = reader?;
+            let mut chunk = vec![0; CHUNK_SIZE];
+            match reader.read(&mut chunk).await {
+                Ok(0) => None,
+                Ok(len) => {
+                    chunk.truncate(len);
+                    tracker.record(len);
+                    Some((Ok(Bytes::from(chunk)), Some(reader)))
+                }
+                // The error ends the body, so nothing is read after it.
+                Err(error) => Some((Err(error), None)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CHUNK_SIZE, UploadTracker, tracked_stream};
+    use crate::http::ResponseUpdate;
+    use crate::utils::shared_tokio_runtime;
+    use futures_util::StreamExt;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn streams_every_byte_and_reports_progress_up_to_the_total() {
+        let data: Vec<u8> = (0..CHUNK_SIZE * 3 + 10).map(|i| i as u8).collect();
+        let (updates_tx, mut updates_rx) = mpsc::unbounded_channel();
+        let tracker = UploadTracker::new(Some(updates_tx));
+        tracker.add_file(data.len() as u64);
+
+        let chunks: Vec<_> = shared_tokio_runtime().block_on(
+            tracked_stream(std::io::Cursor::new(data.clone()), tracker.clone()).collect(),
+        );
+        tracker.finish();
+
+        let streamed: Vec<u8> = chunks
+            .into_iter()
+            .flat_map(|chunk| chunk.unwrap().to_vec())
+            .collect();
+        assert_eq!(streamed, data);
+        let mut progress = Vec::new();
+        while let Ok(ResponseUpdate::UploadProgress { sent, total }) = updates_rx.try_recv() {
+            progress.push((sent, total));
+        }
+        assert_eq!(progress.len(), 4);
+        assert_eq!(
+            progress.last(),
+            Some(&(data.len() as u64, data.len() as u64))
+        );
+    }
+}
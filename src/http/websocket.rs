@@ -0,0 +1,401 @@
+use crate::entities::{Header, WsPayload};
+use crate::utils::shared_tokio_runtime;
+use futures_util::{SinkExt, StreamExt};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+
+/// Headers tungstenite derives itself while performing the upgrade handshake.
+const HANDSHAKE_MANAGED_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "upgrade",
+    "sec-websocket-key",
+    "sec-websocket-version",
+];
+
+/// Progress of a WebSocket connection, delivered in order on the event channel.
+#[derive(Debug)]
+pub enum WsClientEvent {
+    /// The handshake finished; carries the subprotocol the server picked, if any.
+    Connected(Option<String>),
+    Message(WsPayload),
+    /// The socket closed; carries the peer's close code and reason when it sent one.
+    Closed(Option<String>),
+    Failed(String),
+}
+
+enum WsCommand {
+    Send(WsPayload),
+}
+
+/// Handle for an open socket. Dropping it sends a close frame and ends the session.
+pub struct WsConnection {
+    commands: mpsc::UnboundedSender<WsCommand>,
+}
+
+impl WsConnection {
+    /// Queue a data frame; returns `false` once the socket task has exited.
+    pub fn send(&self, payload: WsPayload) -> bool {
+        self.commands.send(WsCommand::Send(payload)).is_ok()
+    }
+}
+
+/// WebSocket client sibling of `HttpClient`, sharing the same Tokio runtime.
+#[derive(Clone)]
+pub struct WsClient {
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl WsClient {
+    pub fn new() -> Self {
+        Self {
+            runtime: shared_tokio_runtime(),
+        }
+    }
+
+    /// Open `url` on the Tokio runtime and stream connection events back to the caller.
+    ///
+    /// `subprotocols` are offered in order on `Sec-WebSocket-Protocol`, replacing any such
+    /// header in `headers`.
+    pub fn connect(
+        &self,
+        url: &str,
+        headers: &[Header],
+        subprotocols: &[String],
+    ) -> (mpsc::UnboundedReceiver<WsClientEvent>, WsConnection) {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+
+        match handshake_request(url, headers, subprotocols) {
+            Ok(request) => {
+                self.runtime
+                    .spawn(run_socket(request, event_tx, command_rx));
+            }
+            Err(error) => {
+                let _ = event_tx.send(WsClientEvent::Failed(error));
+            }
+        }
+
+        (
+            event_rx,
+            WsConnection {
+                commands: command_tx,
+            },
+        )
+    }
+}
+
+impl Default for WsClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn run_socket(
+    request: Request,
+    events: mpsc::UnboundedSender<WsClientEvent>,
+    mut commands: mpsc::UnboundedReceiver<WsCommand>,
+) {
+    let (socket, protocol) = match tokio_tungstenite::connect_async(request).await {
+        Ok((socket, response)) => {
+            let protocol = response
+                .headers()
+                .get(SEC_WEBSOCKET_PROTOCOL)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            (socket, protocol)
+        }
+        Err(error) => {
+            let _ = events.send(WsClientEvent::Failed(error.to_string()));
+            return;
+        }
+    };
+    let _ = events.send(WsClientEvent::Connected(protocol));
+    let (mut sink, mut stream) = socket.split();
+
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(WsCommand::Send(payload)) => {
+                    if let Err(error) = sink.send(payload_message(payload)).await {
+                        let _ = events.send(WsClientEvent::Failed(error.to_string()));
+                        break;
+                    }
+                }
+                None => {
+                    let _ = sink.send(Message::Close(None)).await;
+                    let _ = events.send(WsClientEvent::Closed(None));
+                    break;
+                }
+            },
+            frame = stream.next() => match frame {
+                Some(Ok(Message::Text(text))) => {
+                    let payload = WsPayload::Text(text.as_str().to_string());
+                    let _ = events.send(WsClientEvent::Message(payload));
+                }
+                Some(Ok(Message::Binary(data))) => {
+                    let _ = events.send(WsClientEvent::Message(WsPayload::Binary(data)));
+                }
+                Some(Ok(Message::Close(frame))) => {
+                    let reason = frame.map(|frame| {
+                        let code = u16::from(frame.code);
+                        if frame.reason.is_empty() {
+                            code.to_string()
+                        } else {
+                            format!("{} {}", code, frame.reason.as_str())
+                        }
+                    });
+                    let _ = events.send(WsClientEvent::Closed(reason));
+                    break;
+                }
+                // Ping/pong replies are handled by tungstenite itself.
+                Some(Ok(_)) => {}
+                Some(Err(error)) => {
+                    let _ = events.send(WsClientEvent::Failed(error.to_string()));
+                    break;
+                }
+                None => {
+                    let _ = events.send(WsClientEvent::Closed(None));
+                    break;
+                }
+            },
+        }
+    }
+}
+
+fn payload_message(payload: WsPayload) -> Message {
+    match payload {
+        WsPayload::Text(text) => Message::text(text),
+        WsPayload::Binary(data) => Message::Binary(data),
+    }
+}
+
+/// Map the URL bar text onto a WebSocket URL, defaulting to `wss://` like HTTP defaults to TLS.
+pub fn normalize_ws_url(url: &str) -> String {
+    let url = url.trim();
+    if let Some(rest) = url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else if url.starts_with("ws://") || url.starts_with("wss://") {
+        url.to_string()
+    } else {
+        format!("wss://{}", url)
+    }
+}
+
+/// Split the comma- or space-separated subprotocol field into its names.
+pub fn parse_subprotocols(text: &str) -> Vec<String> {
+    text.split([',', ' '])
+        .map(str::trim)
+        .filter(|protocol| !protocol.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn handshake_request(
+    url: &str,
+    headers: &[Header],
+    subprotocols: &[String],
+) -> Result<Request, String> {
+    if url.trim().is_empty() {
+        return Err("URL cannot be empty".to_string());
+    }
+    let mut request = normalize_ws_url(url)
+        .into_client_request()
+        .map_err(|error| error.to_string())?;
+
+    for header in headers.iter().filter(|h| h.enabled) {
+        let key = header.key.trim();
+        if key.is_empty()
+            || HANDSHAKE_MANAGED_HEADERS
+                .iter()
+                .any(|managed| key.eq_ignore_ascii_case(managed))
+        {
+            continue;
+        }
+        let name = HeaderName::from_bytes(key.as_bytes())
+            .map_err(|_| format!("Invalid header name: {}", key))?;
+        let value = HeaderValue::from_str(&header.value)
+            .map_err(|_| format!("Invalid value for header {}", key))?;
+        request.headers_mut().append(name, value);
+    }
+
+    if !subprotocols.is_empty() {
+        let value = HeaderValue::from_str(&subprotocols.join(", "))
+            .map_err(|_| "Invalid subprotocol name".to_string())?;
+        request.headers_mut().insert(SEC_WEBSOCKET_PROTOCOL, value);
+    }
+
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WsClient, WsClientEvent, handshake_request, normalize_ws_url, parse_subprotocols};
+    use crate::entities::{Header, WsPayload};
+    use crate::utils::shared_tokio_runtime;
+    use bytes::Bytes;
+    use futures_util::{SinkExt, StreamExt};
+
+    #[test]
+    fn normalizes_http_schemes_to_websocket_schemes() {
+        assert_eq!(
+            normalize_ws_url("https://echo.test/ws"),
+            "wss://echo.test/ws"
+        );
+        assert_eq!(
+            normalize_ws_url("http://localhost:8080"),
+            "ws://localhost:8080"
+        );
+        assert_eq!(normalize_ws_url(" ws://localhost/ "), "ws://localhost/");
+        assert_eq!(normalize_ws_url("echo.test"), "wss://echo.test");
+    }
+
+    #[test]
+    fn handshake_keeps_custom_headers_and_skips_managed_ones() {
+        let mut disabled = Header::new("X-Disabled", "1");
+        disabled.enabled = false;
+        let request = handshake_request(
+            "ws://localhost/socket",
+            &[
+                Header::new("Authorization", "Bearer token"),
+                Header::new("Sec-WebSocket-Protocol", "graphql-ws"),
+                Header::new("Upgrade", "nope"),
+                disabled,
+            ],
+            &[],
+        )
+        .unwrap();
+
+        let headers = request.headers();
+        assert_eq!(headers["authorization"], "Bearer token");
+        assert_eq!(headers["sec-websocket-protocol"], "graphql-ws");
+        assert_eq!(headers["upgrade"], "websocket");
+        assert!(!headers.contains_key("x-disabled"));
+    }
+
+    #[test]
+    fn rejects_empty_urls() {
+        assert!(handshake_request("  ", &[], &[]).is_err());
+    }
+
+    #[test]
+    fn offered_subprotocols_replace_a_protocol_header() {
+        let subprotocols = parse_subprotocols("graphql-transport-ws, graphql-ws ,");
+        assert_eq!(subprotocols, ["graphql-transport-ws", "graphql-ws"]);
+        let request = handshake_request(
+            "ws://localhost/graphql",
+            &[Header::new("Sec-WebSocket-Protocol", "mqtt")],
+            &subprotocols,
+        )
+        .unwrap();
+        let offered: Vec<_> = request
+            .headers()
+            .get_all("sec-websocket-protocol")
+            .iter()
+            .collect();
+        assert_eq!(offered, ["graphql-transport-ws, graphql-ws"]);
+    }
+
+    #[test]
+    fn exchanges_frames_with_an_echo_server() {
+        let runtime = shared_tokio_runtime();
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = runtime.spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(message)) = socket.next().await {
+                if message.is_text() || message.is_binary() {
+                    socket.send(message).await.unwrap();
+                } else if message.is_close() {
+                    break;
+                }
+            }
+        });
+
+        let (mut events, connection) =
+            WsClient::new().connect(&format!("ws://{address}/echo"), &[], &[]);
+        runtime.block_on(async move {
+            assert!(matches!(
+                events.recv().await,
+                Some(WsClientEvent::Connected(None))
+            ));
+
+            assert!(connection.send(WsPayload::Text("hello".to_string())));
+            assert!(connection.send(WsPayload::Binary(Bytes::from_static(&[1, 2, 3]))));
+            match events.recv().await {
+                Some(WsClientEvent::Message(WsPayload::Text(text))) => assert_eq!(text, "hello"),
+                other => panic!("expected text echo, got {other:?}"),
+            }
+            match events.recv().await {
+                Some(WsClientEvent::Message(WsPayload::Binary(data))) => {
+                    assert_eq!(data.as_ref(), &[1, 2, 3])
+                }
+                other => panic!("expected binary echo, got {other:?}"),
+            }
+
+            drop(connection);
+            assert!(matches!(
+                events.recv().await,
+                Some(WsClientEvent::Closed(None))
+            ));
+        });
+        runtime.block_on(server).unwrap();
+    }
+
+    #[test]
+    fn reports_the_subprotocol_the_server_picked() {
+        use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+
+        let runtime = shared_tokio_runtime();
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = runtime.spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let pick_last =
+                |request: &Request, mut response: Response| -> Result<Response, ErrorResponse> {
+                    let offered = request.headers()["sec-websocket-protocol"]
+                        .to_str()
+                        .unwrap();
+                    let picked = offered.rsplit(", ").next().unwrap().parse().unwrap();
+                    response
+                        .headers_mut()
+                        .insert("sec-websocket-protocol", picked);
+                    Ok(response)
+                };
+            let mut socket = tokio_tungstenite::accept_hdr_async(stream, pick_last)
+                .await
+                .unwrap();
+            while let Some(Ok(message)) = socket.next().await {
+                if message.is_close() {
+                    break;
+                }
+            }
+        });
+
+        let subprotocols = parse_subprotocols("v1.chat v2.chat");
+        let (mut events, connection) =
+            WsClient::new().connect(&format!("ws://{address}/chat"), &[], &subprotocols);
+        runtime.block_on(async move {
+            match events.recv().await {
+                Some(WsClientEvent::Connected(protocol)) => {
+                    assert_eq!(protocol.as_deref(), Some("v2.chat"))
+                }
+                other => panic!("expected a connection, got {other:?}"),
+            }
+            drop(connection);
+        });
+        runtime.block_on(server).unwrap();
+    }
+}
@@ -53,6 +53,7 @@ pub enum IconName {
     Square,
     Volume2,
     VolumeX,
+    Repeat,
     Ellipsis,
     Variable,
     Lock,
@@ -61,6 +62,14 @@ pub enum IconName {
     Package,
     Eye,
     EyeOff,
+    Table,
+    ArrowUp,
+    ArrowDown,
+    Cookie,
+    BookOpen,
+    FileCode,
+    FileText,
+    Diff,
 }
 
 impl IconNamed for IconName {
@@ -112,6 +121,7 @@ impl IconNamed for IconName {
             Self::Square => "icons/square.svg",
             Self::Volume2 => "icons/volume-2.svg",
             Self::VolumeX => "icons/volume-x.svg",
+            Self::Repeat => "icons/repeat.svg",
             Self::Ellipsis => "icons/ellipsis.svg",
             Self::Variable => "icons/variable.svg",
             Self::Lock => "icons/lock.svg",
@@ -120,6 +130,14 @@ impl IconNamed for IconName {
             Self::Package => "icons/package.svg",
             Self::Eye => "icons/eye.svg",
             Self::EyeOff => "icons/eye-closed.svg",
+            Self::Table => "icons/table.svg",
+            Self::ArrowUp => "icons/arrow-up.svg",
+            Self::ArrowDown => "icons/arrow-down.svg",
+            Self::Cookie => "icons/cookie.svg",
+            Self::BookOpen => "icons/book-open.svg",
+            Self::FileCode => "icons/file-code.svg",
+            Self::FileText => "icons/file-text.svg",
+            Self::Diff => "icons/diff.svg",
         }
         .into()
     }
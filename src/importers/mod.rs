@@ -1,10 +1,12 @@
+mod openapi;
 mod postman;
 
 use anyhow::{Context, Result, anyhow};
 use std::path::Path;
 
-use crate::entities::RequestData;
+use crate::entities::{Collection, RequestData};
 
+pub use openapi::OpenApiImporter;
 pub use postman::{PostmanCollectionImporter, import_postman_environment};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -91,6 +93,8 @@ pub struct ImportedEnvironment {
 pub enum ImportedPayload {
     Collection(ImportedCollection),
     Environment(ImportedEnvironment),
+    /// A collection exported from setu, imported as it was written.
+    SetuCollection(Collection),
 }
 
 #[derive(Debug, Clone)]
@@ -120,7 +124,10 @@ pub struct ImportRegistry {
 impl Default for ImportRegistry {
     fn default() -> Self {
         Self {
-            importers: vec![Box::new(PostmanCollectionImporter::default())],
+            importers: vec![
+                Box::new(PostmanCollectionImporter::default()),
+                Box::new(OpenApiImporter::default()),
+            ],
         }
     }
 }
@@ -130,6 +137,16 @@ impl ImportRegistry {
         let contents = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
 
+        if let Some(collection) = Collection::from_export_json(&contents) {
+            return collection
+                .map(|collection| ImportedFileResult {
+                    provider: "Setu",
+                    payload: ImportedPayload::SetuCollection(collection),
+                    warnings: Vec::new(),
+                })
+                .map_err(|error| anyhow!(error));
+        }
+
         if let Some(environment) = import_postman_environment(path, &contents) {
             return environment.map(|environment| ImportedFileResult {
                 provider: "Postman",
@@ -161,7 +178,8 @@ impl ImportRegistry {
         }
 
         Err(anyhow!(
-            "Unsupported import file. Select a Postman collection or environment JSON export."
+            "Unsupported import file. Select a setu or Postman collection, a Postman \
+             environment JSON export, or an OpenAPI spec."
         ))
     }
 }
@@ -0,0 +1,796 @@
+use anyhow::{Result, anyhow};
+use serde_json::{Map, Value};
+use std::path::Path;
+use uuid::Uuid;
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::entities::{
+    DisabledParam, Header, HttpMethod, MultipartField, RequestBody, RequestData,
+};
+
+use super::{
+    CollectionImporter, ImportResult, ImportWarning, ImportedCollection, ImportedNode,
+    ImportedVariable,
+};
+
+/// How deep example bodies follow nested schemas, which also keeps recursive `$ref`s finite.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// Path item keys that hold operations, in the order the spec lists them.
+const OPERATION_METHODS: [(&str, HttpMethod); 7] = [
+    ("get", HttpMethod::Get),
+    ("put", HttpMethod::Put),
+    ("post", HttpMethod::Post),
+    ("delete", HttpMethod::Delete),
+    ("options", HttpMethod::Options),
+    ("head", HttpMethod::Head),
+    ("patch", HttpMethod::Patch),
+];
+
+/// Imports OpenAPI 3 and Swagger 2 specs, as JSON or YAML, with one request per operation.
+#[derive(Default)]
+pub struct OpenApiImporter;
+
+impl CollectionImporter for OpenApiImporter {
+    fn provider_name(&self) -> &'static str {
+        "OpenAPI"
+    }
+
+    fn matches(&self, path: &Path, contents: &str) -> bool {
+        parse_document(path, contents).is_some_and(|document| {
+            // YAML reads an unquoted `3.1` or `2.0` as a number.
+            let version = |key| document.get(key).map(plain_value);
+            let openapi_3 = version("openapi").is_some_and(|version| version.starts_with('3'));
+            let swagger_2 = version("swagger").is_some_and(|version| version == "2.0");
+            (openapi_3 || swagger_2) && document.get("paths").is_some_and(Value::is_object)
+        })
+    }
+
+    fn import(&self, path: &Path, contents: &str) -> Result<ImportResult> {
+        let document = parse_document(path, contents)
+            .ok_or_else(|| anyhow!("Failed to parse the OpenAPI spec as JSON or YAML"))?;
+
+        let collection_name = document
+            .pointer("/info/title")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(str::to_string)
+            })
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| "Imported API".to_string());
+
+        let mut warnings = Vec::new();
+        let mut variables = Vec::new();
+        let base_url = match server_url(&document) {
+            Some(url) if url.contains("://") => url,
+            url => {
+                warnings.push(ImportWarning::new(
+                    Some(collection_name.clone()),
+                    "The spec names no absolute server URL; set {{baseUrl}} in an environment.",
+                ));
+                variables.push(ImportedVariable {
+                    key: "baseUrl".to_string(),
+                    value: String::new(),
+                    enabled: true,
+                    secret: false,
+                });
+                format!("{{{{baseUrl}}}}{}", url.unwrap_or_default())
+            }
+        };
+        if document.get("security").is_some()
+            || document.pointer("/components/securitySchemes").is_some()
+            || document.get("securityDefinitions").is_some()
+        {
+            warnings.push(ImportWarning::new(
+                Some(collection_name.clone()),
+                "Security schemes are skipped; set auth on the collection instead.",
+            ));
+        }
+
+        // Operations are grouped into a folder per first tag, keeping the order tags appear in.
+        let mut root = Vec::new();
+        let mut folders: Vec<(String, Vec<ImportedNode>)> = Vec::new();
+        let paths = document.get("paths").and_then(Value::as_object);
+        for (route, path_item) in paths.into_iter().flatten() {
+            let path_item = resolve(&document, path_item);
+            for (key, method) in OPERATION_METHODS {
+                let Some(operation) = path_item.get(key) else {
+                    continue;
+                };
+                let label = format!("{collection_name} / {} {route}", method.as_str());
+                let request = import_operation(
+                    &document,
+                    &base_url,
+                    route,
+                    method,
+                    path_item,
+                    operation,
+                    &label,
+                    &mut warnings,
+                );
+                let node = ImportedNode::Request { request };
+                match operation
+                    .get("tags")
+                    .and_then(Value::as_array)
+                    .and_then(|tags| tags.first())
+                    .and_then(Value::as_str)
+                {
+                    Some(tag) => match folders.iter_mut().find(|(name, _)| name == tag) {
+                        Some((_, children)) => children.push(node),
+                        None => folders.push((tag.to_string(), vec![node])),
+                    },
+                    None => root.push(node),
+                }
+            }
+        }
+
+        let mut nodes: Vec<ImportedNode> = folders
+            .into_iter()
+            .map(|(name, children)| ImportedNode::Folder { name, children })
+            .collect();
+        nodes.extend(root);
+
+        Ok(ImportResult {
+            provider: self.provider_name(),
+            collection: ImportedCollection {
+                name: collection_name,
+                nodes,
+                variables,
+            },
+            warnings,
+        })
+    }
+}
+
+/// JSON is tried first, since it is the common case and keeps key order; YAML otherwise.
+fn parse_document(path: &Path, contents: &str) -> Option<Value> {
+    if let Ok(document) = serde_json::from_str::<Value>(contents) {
+        return Some(document);
+    }
+    let yaml = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("yaml") || extension.eq_ignore_ascii_case("yml")
+        });
+    if !yaml {
+        return None;
+    }
+    let document = YamlLoader::load_from_str(contents)
+        .ok()?
+        .into_iter()
+        .next()?;
+    yaml_to_json(document)
+}
+
+/// A YAML document as JSON. Scalar keys such as `200:` become strings; anchors are already
+/// resolved by the loader.
+fn yaml_to_json(yaml: Yaml) -> Option<Value> {
+    Some(match yaml {
+        Yaml::Null => Value::Null,
+        Yaml::Boolean(value) => Value::Bool(value),
+        Yaml::Integer(value) => Value::from(value),
+        real @ Yaml::Real(_) => Value::from(real.as_f64()?),
+        Yaml::String(text) => Value::String(text),
+        Yaml::Array(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect::<Option<_>>()?)
+        }
+        Yaml::Hash(entries) => {
+            let mut object = Map::new();
+            for (key, value) in entries {
+                let key = match key {
+                    Yaml::String(key) | Yaml::Real(key) => key,
+                    Yaml::Integer(key) => key.to_string(),
+                    Yaml::Boolean(key) => key.to_string(),
+                    Yaml::Null => "null".to_string(),
+                    _ => return None,
+                };
+                object.insert(key, yaml_to_json(value)?);
+            }
+            Value::Object(object)
+        }
+        Yaml::Alias(_) | Yaml::BadValue => return None,
+    })
+}
+
+/// The first server's URL with its variables set to their defaults, without a trailing `/`.
+fn server_url(document: &Value) -> Option<String> {
+    let url = if let Some(server) = document.pointer("/servers/0") {
+        let mut url = server.get("url")?.as_str()?.to_string();
+        for (name, variable) in server
+            .get("variables")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                url = url.replace(&format!("{{{name}}}"), default);
+            }
+        }
+        url
+    } else {
+        let host = document.get("host").and_then(Value::as_str)?;
+        let scheme = document
+            .pointer("/schemes/0")
+            .and_then(Value::as_str)
+            .unwrap_or("https");
+        let base_path = document
+            .get("basePath")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        format!("{scheme}://{host}{base_path}")
+    };
+    Some(url.trim_end_matches('/').to_string())
+}
+
+/// Follow local `$ref`s like `#/components/schemas/User`, leaving others as they are.
+fn resolve<'a>(document: &'a Value, mut value: &'a Value) -> &'a Value {
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        let Some(pointer) = value
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+        else {
+            break;
+        };
+        match document.pointer(pointer) {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+    value
+}
+
+fn import_operation(
+    document: &Value,
+    base_url: &str,
+    route: &str,
+    method: HttpMethod,
+    path_item: &Value,
+    operation: &Value,
+    label: &str,
+    warnings: &mut Vec<ImportWarning>,
+) -> RequestData {
+    let name = operation
+        .get("summary")
+        .or_else(|| operation.get("operationId"))
+        .and_then(Value::as_str)
+        .filter(|name| !name.trim().is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} {route}", method.as_str()));
+
+    // Operation parameters override path-level ones with the same name and location.
+    let mut parameters: Vec<&Value> = Vec::new();
+    for parameter in [path_item, operation]
+        .into_iter()
+        .filter_map(|owner| owner.get("parameters").and_then(Value::as_array))
+        .flatten()
+    {
+        let parameter = resolve(document, parameter);
+        let key =
+            |parameter: &Value| (parameter.get("name").cloned(), parameter.get("in").cloned());
+        parameters.retain(|existing| key(existing) != key(parameter));
+        parameters.push(parameter);
+    }
+
+    let mut query = Vec::new();
+    let mut disabled_params = Vec::new();
+    let mut headers = Vec::new();
+    let mut body = RequestBody::None;
+    // Swagger 2 form fields, and whether each one is a file.
+    let mut form_fields: Vec<(String, bool)> = Vec::new();
+    let mut skipped_cookies = false;
+    for parameter in parameters {
+        let Some(name) = parameter.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let placeholder = format!("{{{{{name}}}}}");
+        let required = parameter
+            .get("required")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        match parameter.get("in").and_then(Value::as_str) {
+            Some("query") if required => query.push(format!("{name}={placeholder}")),
+            Some("query") => disabled_params.push(DisabledParam {
+                key: name.to_string(),
+                value: placeholder,
+                index: None,
+            }),
+            Some("header") => headers.push(Header::new(name, placeholder)),
+            Some("cookie") => skipped_cookies = true,
+            // Swagger 2 describes the body as a parameter.
+            Some("body") => {
+                if let Some(schema) = parameter.get("schema") {
+                    body = json_body(example_from_schema(document, schema, 0));
+                }
+            }
+            // ...and each form field as one too.
+            Some("formData") => form_fields.push((
+                name.to_string(),
+                parameter.get("type").and_then(Value::as_str) == Some("file"),
+            )),
+            _ => {}
+        }
+    }
+    if !form_fields.is_empty() {
+        // `consumes` on the operation replaces the document's.
+        let multipart = operation
+            .get("consumes")
+            .or_else(|| document.get("consumes"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .any(|media_type| media_type.as_str() == Some("multipart/form-data"));
+        body = if multipart || form_fields.iter().any(|(_, is_file)| *is_file) {
+            RequestBody::MultipartFormData(
+                form_fields
+                    .into_iter()
+                    .map(|(name, is_file)| {
+                        if is_file {
+                            MultipartField::file(name, "")
+                        } else {
+                            let placeholder = format!("{{{{{name}}}}}");
+                            MultipartField::text(name, placeholder)
+                        }
+                    })
+                    .collect(),
+            )
+        } else {
+            RequestBody::FormData(
+                form_fields
+                    .into_iter()
+                    .map(|(name, _)| {
+                        let placeholder = format!("{{{{{name}}}}}");
+                        (name, placeholder)
+                    })
+                    .collect(),
+            )
+        };
+    }
+    if skipped_cookies {
+        warnings.push(ImportWarning::new(
+            Some(label.to_string()),
+            "Cookie parameters are skipped.",
+        ));
+    }
+
+    if let Some(request_body) = operation.get("requestBody") {
+        body = import_request_body(document, resolve(document, request_body), label, warnings);
+    }
+    let content_type = match &body {
+        RequestBody::Json(_) => Some("application/json"),
+        RequestBody::FormData(_) => Some("application/x-www-form-urlencoded"),
+        _ => None,
+    };
+    if let Some(content_type) = content_type
+        && !headers
+            .iter()
+            .any(|header| header.key.eq_ignore_ascii_case("Content-Type"))
+    {
+        headers.push(Header::new("Content-Type", content_type));
+    }
+
+    let mut url = format!("{base_url}{route}");
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(&query.join("&"));
+    }
+
+    RequestData {
+        id: Uuid::new_v4(),
+        name,
+        url,
+        method,
+        headers,
+        body,
+        preserve_header_order: false,
+        inherit_auth: false,
+        assertions: String::new(),
+        pre_request_script: String::new(),
+        extractions: String::new(),
+        disabled_params,
+        subprotocols: String::new(),
+        auto_save: None,
+        is_sending: false,
+    }
+}
+
+fn import_request_body(
+    document: &Value,
+    request_body: &Value,
+    label: &str,
+    warnings: &mut Vec<ImportWarning>,
+) -> RequestBody {
+    let Some(content) = request_body.get("content").and_then(Value::as_object) else {
+        return RequestBody::None;
+    };
+    let media = |wanted: &dyn Fn(&str) -> bool| {
+        content
+            .iter()
+            .find(|(media_type, _)| wanted(&media_type.to_ascii_lowercase()))
+            .map(|(_, media)| media)
+    };
+    let example = |media: &Value| {
+        media
+            .get("example")
+            .cloned()
+            .or_else(|| {
+                media
+                    .get("examples")
+                    .and_then(Value::as_object)
+                    .and_then(|examples| examples.values().next())
+                    .map(|example| resolve(document, example))
+                    .and_then(|example| example.get("value"))
+                    .cloned()
+            })
+            .or_else(|| {
+                media
+                    .get("schema")
+                    .map(|schema| example_from_schema(document, schema, 0))
+            })
+    };
+
+    if let Some(media) = media(&|media_type: &str| {
+        media_type.starts_with("application/json") || media_type.contains("+json")
+    }) {
+        return example(media).map(json_body).unwrap_or_default();
+    }
+    if let Some(media) =
+        media(&|media_type: &str| media_type == "application/x-www-form-urlencoded")
+    {
+        let fields = example(media).and_then(|example| example.as_object().cloned());
+        return RequestBody::FormData(
+            fields
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| (key, plain_value(&value)))
+                .collect(),
+        );
+    }
+    if let Some(media) = media(&|media_type: &str| media_type == "multipart/form-data") {
+        let schema = media.get("schema").map(|schema| resolve(document, schema));
+        let properties = schema
+            .and_then(|schema| schema.get("properties"))
+            .and_then(Value::as_object);
+        let example = example(media).unwrap_or(Value::Null);
+        return RequestBody::MultipartFormData(
+            properties
+                .into_iter()
+                .flatten()
+                .map(|(key, property)| {
+                    let property = resolve(document, property);
+                    if property.get("format").and_then(Value::as_str) == Some("binary") {
+                        MultipartField::file(key, "")
+                    } else {
+                        MultipartField::text(key, plain_value(&example[key.as_str()]))
+                    }
+                })
+                .collect(),
+        );
+    }
+    match content.values().next().and_then(example) {
+        Some(Value::String(text)) => RequestBody::Text(text),
+        _ => {
+            warnings.push(ImportWarning::new(
+                Some(label.to_string()),
+                "The request body's media type is not supported and was left empty.",
+            ));
+            RequestBody::None
+        }
+    }
+}
+
+fn json_body(example: Value) -> RequestBody {
+    RequestBody::Json(serde_json::to_string_pretty(&example).unwrap_or_default())
+}
+
+fn plain_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// A value that fits `schema`, from its own example when it has one.
+fn example_from_schema(document: &Value, schema: &Value, depth: usize) -> Value {
+    let schema = resolve(document, schema);
+    if depth > MAX_SCHEMA_DEPTH {
+        return Value::Null;
+    }
+    if let Some(example) = schema.get("example").or_else(|| schema.get("default")) {
+        return example.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in parts {
+            if let Value::Object(fields) = example_from_schema(document, part, depth + 1) {
+                merged.extend(fields);
+            }
+        }
+        return Value::Object(merged);
+    }
+    if let Some(first) = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|key| schema.get(*key).and_then(Value::as_array))
+        .and_then(|choices| choices.first())
+    {
+        return example_from_schema(document, first, depth + 1);
+    }
+
+    let kind = match schema.get("type") {
+        // OpenAPI 3.1 allows a list of types, like `[string, "null"]`.
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|kind| *kind != "null"),
+        Some(kind) => kind.as_str(),
+        None if schema.get("properties").is_some() => Some("object"),
+        None if schema.get("items").is_some() => Some("array"),
+        None => None,
+    };
+    match kind {
+        Some("object") => Value::Object(
+            schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(key, property)| {
+                    (
+                        key.clone(),
+                        example_from_schema(document, property, depth + 1),
+                    )
+                })
+                .collect(),
+        ),
+        Some("array") => Value::Array(
+            schema
+                .get("items")
+                .map(|items| vec![example_from_schema(document, items, depth + 1)])
+                .unwrap_or_default(),
+        ),
+        Some("integer") | Some("number") => Value::from(0),
+        Some("boolean") => Value::Bool(false),
+        Some("string") => Value::String(
+            match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => "2024-01-01T00:00:00Z",
+                Some("date") => "2024-01-01",
+                Some("email") => "user@example.com",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                Some("uri") | Some("url") => "https://example.com",
+                _ => "string",
+            }
+            .to_string(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"
+openapi: 3.0.3
+info:
+  title: Billing API
+servers:
+  - url: https://{region}.billing.example.com/v1/
+    variables:
+      region:
+        default: eu
+paths:
+  /invoices/{invoiceId}:
+    parameters:
+      - name: invoiceId
+        in: path
+        required: true
+    get:
+      tags: [Invoices]
+      summary: Get invoice
+      parameters:
+        - name: expand
+          in: query
+        - name: X-Tenant
+          in: header
+    put:
+      tags: [Invoices]
+      operationId: updateInvoice
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Invoice'
+  /health:
+    get:
+      parameters:
+        - name: verbose
+          in: query
+          required: true
+components:
+  schemas:
+    Invoice:
+      type: object
+      properties:
+        id:
+          type: string
+          format: uuid
+        total:
+          type: number
+        status:
+          type: string
+          enum: [draft, paid]
+        lines:
+          type: array
+          items:
+            type: object
+            properties:
+              sku:
+                type: string
+                example: ABC-1
+"##;
+
+    fn requests(node: &ImportedNode) -> Vec<&RequestData> {
+        match node {
+            ImportedNode::Folder { children, .. } => children.iter().flat_map(requests).collect(),
+            ImportedNode::Request { request } => vec![request],
+        }
+    }
+
+    #[test]
+    fn yaml_documents_read_as_json_with_string_keys() {
+        let yaml =
+            "base: &base\n  type: integer\nresponses:\n  200:\n    schema: *base\n  ratio: 1.5\n";
+        let document = parse_document(Path::new("spec.yml"), yaml).expect("yaml parses");
+        assert_eq!(
+            document.pointer("/responses/200/schema/type"),
+            Some(&Value::from("integer"))
+        );
+        assert_eq!(
+            document.pointer("/responses/ratio"),
+            Some(&Value::from(1.5))
+        );
+        assert!(parse_document(Path::new("spec.txt"), yaml).is_none());
+    }
+
+    #[test]
+    fn imports_yaml_operations_grouped_by_tag_with_example_bodies() {
+        let path = Path::new("billing.yaml");
+        let importer = OpenApiImporter;
+        assert!(importer.matches(path, SPEC));
+        let result = importer.import(path, SPEC).expect("spec imports");
+        let collection = result.collection;
+        assert_eq!(collection.name, "Billing API");
+        assert!(collection.variables.is_empty());
+        assert_eq!(collection.request_count(), 3);
+        assert!(matches!(
+            &collection.nodes[0],
+            ImportedNode::Folder { name, children } if name == "Invoices" && children.len() == 2
+        ));
+
+        let all: Vec<_> = collection.nodes.iter().flat_map(requests).collect();
+        let get = all[0];
+        assert_eq!(get.name, "Get invoice");
+        assert_eq!(
+            get.url,
+            "https://eu.billing.example.com/v1/invoices/{invoiceId}"
+        );
+        assert_eq!(get.disabled_params[0].key, "expand");
+        assert_eq!(get.headers[0].key, "X-Tenant");
+        assert_eq!(get.headers[0].value, "{{X-Tenant}}");
+
+        let put = all[1];
+        assert_eq!(put.name, "updateInvoice");
+        assert_eq!(put.method, HttpMethod::Put);
+        let RequestBody::Json(body) = &put.body else {
+            panic!("expected a JSON body, got {:?}", put.body);
+        };
+        let body: Value = serde_json::from_str(body).expect("example is JSON");
+        assert_eq!(body["status"], "draft");
+        assert_eq!(body["lines"][0]["sku"], "ABC-1");
+        assert_eq!(body["id"], "00000000-0000-0000-0000-000000000000");
+
+        let health = all[2];
+        assert_eq!(
+            health.url,
+            "https://eu.billing.example.com/v1/health?verbose={{verbose}}"
+        );
+    }
+
+    #[test]
+    fn swagger_specs_use_host_and_base_path_and_body_parameters() {
+        let spec = r#"{
+            "swagger": "2.0",
+            "info": { "title": "Pets" },
+            "host": "pets.example.com",
+            "basePath": "/api",
+            "schemes": ["http"],
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "parameters": [
+                            { "name": "pet", "in": "body", "schema": { "properties": { "name": { "type": "string" } } } }
+                        ]
+                    }
+                }
+            }
+        }"#;
+        let path = Path::new("pets.json");
+        assert!(OpenApiImporter.matches(path, spec));
+        let collection = OpenApiImporter.import(path, spec).unwrap().collection;
+        let request = requests(&collection.nodes[0])[0];
+        assert_eq!(request.url, "http://pets.example.com/api/pets");
+        assert_eq!(request.name, "POST /pets");
+        assert!(
+            matches!(&request.body, RequestBody::Json(body) if body.contains("\"name\": \"string\""))
+        );
+
+        assert!(!OpenApiImporter.matches(path, r#"{ "info": {}, "item": [] }"#));
+    }
+
+    #[test]
+    fn swagger_form_data_parameters_become_a_form_body() {
+        let spec = r#"{
+            "swagger": "2.0",
+            "info": { "title": "Pets" },
+            "host": "pets.example.com",
+            "paths": {
+                "/login": {
+                    "post": {
+                        "parameters": [
+                            { "name": "user", "in": "formData", "type": "string" },
+                            { "name": "password", "in": "formData", "type": "string" }
+                        ]
+                    }
+                },
+                "/pets/{id}/photo": {
+                    "post": {
+                        "parameters": [
+                            { "name": "caption", "in": "formData", "type": "string" },
+                            { "name": "photo", "in": "formData", "type": "file" }
+                        ]
+                    }
+                }
+            }
+        }"#;
+        let collection = OpenApiImporter
+            .import(Path::new("pets.json"), spec)
+            .unwrap()
+            .collection;
+        let all: Vec<_> = collection.nodes.iter().flat_map(requests).collect();
+        let login = all
+            .iter()
+            .find(|request| request.url.ends_with("/login"))
+            .expect("login is imported");
+        let RequestBody::FormData(fields) = &login.body else {
+            panic!("expected a form body, got {:?}", login.body);
+        };
+        assert_eq!(fields.get("user").map(String::as_str), Some("{{user}}"));
+        assert_eq!(fields.len(), 2);
+        assert!(login.headers.iter().any(|header| {
+            header.key == "Content-Type" && header.value == "application/x-www-form-urlencoded"
+        }));
+
+        let upload = all
+            .iter()
+            .find(|request| request.url.ends_with("/photo"))
+            .expect("upload is imported");
+        let RequestBody::MultipartFormData(fields) = &upload.body else {
+            panic!("expected a multipart body, got {:?}", upload.body);
+        };
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].value, "{{caption}}");
+        assert!(fields[1].file_path.is_some());
+    }
+}
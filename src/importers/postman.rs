@@ -508,6 +508,14 @@ fn import_request(
         method,
         headers,
         body,
+        preserve_header_order: false,
+        inherit_auth: false,
+        assertions: String::new(),
+        pre_request_script: String::new(),
+        extractions: String::new(),
+        disabled_params: Vec::new(),
+        subprotocols: String::new(),
+        auto_save: None,
         is_sending: false,
     })
 }
@@ -21,6 +21,8 @@
 mod actions;
 mod app;
 mod assets;
+mod cli;
+mod codegen;
 mod completion;
 mod components;
 mod entities;
@@ -0,0 +1,387 @@
+use serde_json::Value;
+
+use crate::entities::ResponseData;
+
+use super::JsonPath;
+
+/// Actual values longer than this many characters are cut in results.
+const ACTUAL_PREVIEW_CHARS: usize = 200;
+
+/// What part of the response an assertion looks at.
+#[derive(Debug, Clone, PartialEq)]
+enum Subject {
+    Status,
+    /// Response time in milliseconds.
+    Duration,
+    /// A header, matched case-insensitively.
+    Header(String),
+    Body,
+    JsonPath(JsonPath),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    NotContains,
+    Exists,
+}
+
+/// One post-response check, written as `subject operator expected`:
+///
+/// ```text
+/// status == 200
+/// duration < 500
+/// header Content-Type contains json
+/// body contains "ok"
+/// body.jsonpath($.user.active) == true
+/// header ETag exists
+/// ```
+///
+/// Expected values are read as JSON when they parse as JSON and as text otherwise, so `200`,
+/// `true` and `"quoted text"` all work.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assertion {
+    subject: Subject,
+    operator: Operator,
+    expected: Value,
+}
+
+/// The outcome of one line of a request's tests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssertionResult {
+    pub source: String,
+    pub passed: bool,
+    /// The value found in the response, or why the line could not be checked.
+    pub actual: String,
+}
+
+impl Assertion {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (subject, rest) = parse_subject(line)?;
+        let rest = rest.trim_start();
+        let (operator, expected) = parse_operator(rest)?;
+        let expected = expected.trim();
+        if operator == Operator::Exists {
+            if !expected.is_empty() {
+                return Err("exists takes no value".to_string());
+            }
+            return Ok(Self {
+                subject,
+                operator,
+                expected: Value::Null,
+            });
+        }
+        if expected.is_empty() {
+            return Err("Expected a value after the operator".to_string());
+        }
+        let expected =
+            serde_json::from_str(expected).unwrap_or_else(|_| Value::String(expected.into()));
+        if matches!(
+            operator,
+            Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge
+        ) && as_number(&expected).is_none()
+        {
+            return Err("Ordering comparisons need a number".to_string());
+        }
+        Ok(Self {
+            subject,
+            operator,
+            expected,
+        })
+    }
+
+    /// Whether `response` satisfies the assertion, and the value it was checked against.
+    pub fn evaluate(&self, response: &ResponseData) -> (bool, String) {
+        let actual = match &self.subject {
+            Subject::Status => Some(Value::from(response.status_code)),
+            Subject::Duration => Some(Value::from(response.duration_ms)),
+            Subject::Header(name) => response
+                .headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| Value::String(value.clone())),
+            Subject::Body => Some(Value::String(
+                response.text_snapshot().raw_body().to_string(),
+            )),
+            Subject::JsonPath(path) => {
+                match serde_json::from_str::<Value>(&response.text_snapshot().raw_body()) {
+                    Ok(root) => {
+                        let matches = path.query(&root);
+                        // A definite path names one value, which may not be there.
+                        if path.is_definite() {
+                            matches.first().map(|value| (*value).clone())
+                        } else {
+                            Some(Value::Array(matches.into_iter().cloned().collect()))
+                        }
+                    }
+                    Err(_) => return (false, "Body is not valid JSON".to_string()),
+                }
+            }
+        };
+        let passed = compare(actual.as_ref(), self.operator, &self.expected);
+        let actual = match actual {
+            Some(Value::String(text)) => text,
+            Some(value) => value.to_string(),
+            None => "(missing)".to_string(),
+        };
+        (passed, preview(actual))
+    }
+}
+
+/// Check every assertion in `script`, one per line. Blank lines and `#` comments are skipped;
+/// lines that do not parse fail with the reason as their actual value.
+pub fn run_assertions(script: &str, response: &ResponseData) -> Vec<AssertionResult> {
+    script
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (passed, actual) = match Assertion::parse(line) {
+                Ok(assertion) => assertion.evaluate(response),
+                Err(error) => (false, error),
+            };
+            AssertionResult {
+                source: line.to_string(),
+                passed,
+                actual,
+            }
+        })
+        .collect()
+}
+
+/// Cut long actual values, such as whole bodies, to a length that fits a result row.
+fn preview(mut text: String) -> String {
+    if let Some((cut, _)) = text.char_indices().nth(ACTUAL_PREVIEW_CHARS) {
+        text.truncate(cut);
+        text.push('…');
+    }
+    text
+}
+
+fn parse_subject(line: &str) -> Result<(Subject, &str), String> {
+    let word_end = line.find(char::is_whitespace).unwrap_or(line.len());
+    let (word, rest) = line.split_at(word_end);
+    match word.to_ascii_lowercase().as_str() {
+        "status" => return Ok((Subject::Status, rest)),
+        "duration" => return Ok((Subject::Duration, rest)),
+        "body" => return Ok((Subject::Body, rest)),
+        "header" => {
+            let rest = rest.trim_start();
+            let name_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            if name_end == 0 {
+                return Err("Expected a header name".to_string());
+            }
+            let (name, rest) = rest.split_at(name_end);
+            return Ok((Subject::Header(name.to_string()), rest));
+        }
+        _ => {}
+    }
+
+    let Some(expression) = line.strip_prefix("body.jsonpath(") else {
+        return Err(format!(
+            "Unknown subject \"{word}\"; use status, duration, header, body or body.jsonpath(...)"
+        ));
+    };
+    let close = closing_paren(expression).ok_or("Missing ) after the JSONPath")?;
+    let path = JsonPath::parse(&expression[..close])
+        .map_err(|error| format!("Invalid JSONPath: {error}"))?;
+    Ok((Subject::JsonPath(path), &expression[close + 1..]))
+}
+
+/// Offset of the `)` closing an expression that started just after a `(`, skipping quoted
+/// names like `['a)b']`.
+fn closing_paren(expression: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (index, c) in expression.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => return Some(index),
+            (None, ')') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_operator(rest: &str) -> Result<(Operator, &str), String> {
+    // Longer symbols first, so `<=` is not read as `<` followed by `=`.
+    const OPERATORS: &[(&str, Operator)] = &[
+        ("==", Operator::Eq),
+        ("!=", Operator::Ne),
+        ("<=", Operator::Le),
+        (">=", Operator::Ge),
+        ("<", Operator::Lt),
+        (">", Operator::Gt),
+        ("!contains", Operator::NotContains),
+        ("contains", Operator::Contains),
+        ("exists", Operator::Exists),
+    ];
+    OPERATORS
+        .iter()
+        .find_map(|(symbol, operator)| {
+            let after = rest.strip_prefix(symbol)?;
+            // Word operators must end at a space, so `containsx` is not `contains x`.
+            let is_word = symbol.ends_with(|c: char| c.is_ascii_alphabetic());
+            (!is_word || after.is_empty() || after.starts_with(char::is_whitespace))
+                .then_some((*operator, after))
+        })
+        .ok_or_else(|| {
+            "Expected an operator: ==, !=, <, <=, >, >=, contains, !contains or exists".to_string()
+        })
+}
+
+fn compare(actual: Option<&Value>, operator: Operator, expected: &Value) -> bool {
+    let Some(actual) = actual else {
+        // Only a negative check can pass against a value that is not there.
+        return matches!(operator, Operator::Ne | Operator::NotContains);
+    };
+    match operator {
+        Operator::Exists => true,
+        Operator::Eq => values_equal(actual, expected),
+        Operator::Ne => !values_equal(actual, expected),
+        Operator::Contains => contains(actual, expected),
+        Operator::NotContains => !contains(actual, expected),
+        Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge => {
+            let (Some(actual), Some(expected)) = (as_number(actual), as_number(expected)) else {
+                return false;
+            };
+            match operator {
+                Operator::Lt => actual < expected,
+                Operator::Le => actual <= expected,
+                Operator::Gt => actual > expected,
+                _ => actual >= expected,
+            }
+        }
+    }
+}
+
+/// JSON equality, except that numbers compare by value (`1` equals `1.0`) and text such as
+/// a header value equals the expected value written out.
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    if let (Some(actual), Some(expected)) = (actual.as_f64(), expected.as_f64()) {
+        return actual == expected;
+    }
+    match (actual, expected) {
+        (Value::String(actual), Value::String(expected)) => actual == expected,
+        (Value::String(actual), expected) => *actual == expected.to_string(),
+        (actual, expected) => actual == expected,
+    }
+}
+
+fn contains(actual: &Value, expected: &Value) -> bool {
+    let needle = match expected {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    match actual {
+        Value::String(text) => text.contains(&needle),
+        Value::Array(items) => items.iter().any(|item| values_equal(item, expected)),
+        Value::Object(map) => map.contains_key(&needle),
+        other => other.to_string().contains(&needle),
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(text) => text.trim().parse().ok(),
+        other => other.as_f64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Assertion, run_assertions};
+    use crate::entities::ResponseData;
+    use std::collections::HashMap;
+
+    fn response() -> ResponseData {
+        ResponseData::new(
+            201,
+            "Created".to_string(),
+            HashMap::from([(
+                "content-type".to_string(),
+                "application/json; charset=utf-8".to_string(),
+            )]),
+            r#"{"ok": true, "count": 3, "tags": ["a", "b"], "user": {"name": "setu"}}"#.to_string(),
+            Vec::new(),
+            70,
+            120,
+            Some("application/json".to_string()),
+        )
+    }
+
+    fn check(line: &str) -> (bool, String) {
+        Assertion::parse(line).unwrap().evaluate(&response())
+    }
+
+    #[test]
+    fn equality_operators() {
+        assert_eq!(check("status == 201"), (true, "201".to_string()));
+        assert!(!check("status == 200").0);
+        assert!(check("status != 200").0);
+        assert!(check("body.jsonpath($.ok) == true").0);
+        assert!(check("body.jsonpath($.user.name) == setu").0);
+        assert!(check(r#"body.jsonpath($.user.name) == "setu""#).0);
+        assert!(check("body.jsonpath($.count) == 3.0").0);
+    }
+
+    #[test]
+    fn ordering_operators() {
+        assert!(check("duration < 500").0);
+        assert!(check("duration <= 120").0);
+        assert!(!check("duration > 120").0);
+        assert!(check("duration >= 120").0);
+        assert!(check("body.jsonpath($.count) > 2").0);
+        assert!(Assertion::parse("status < abc").is_err());
+    }
+
+    #[test]
+    fn contains_operators() {
+        assert_eq!(
+            check("header Content-Type contains json"),
+            (true, "application/json; charset=utf-8".to_string())
+        );
+        assert!(check("header content-type !contains xml").0);
+        assert!(check(r#"body contains "count""#).0);
+        assert!(check("body.jsonpath($.tags) contains b").0);
+        assert!(!check("body.jsonpath($.tags) contains c").0);
+        assert!(check("body.jsonpath($.user) contains name").0);
+    }
+
+    #[test]
+    fn exists_operator_and_missing_values() {
+        assert!(check("header Content-Type exists").0);
+        assert_eq!(
+            check("header ETag exists"),
+            (false, "(missing)".to_string())
+        );
+        assert!(!check("body.jsonpath($.missing) exists").0);
+        assert!(check("body.jsonpath($.missing) != 1").0);
+        assert!(Assertion::parse("header ETag exists now").is_err());
+    }
+
+    #[test]
+    fn reports_lines_that_do_not_parse() {
+        let results = run_assertions(
+            "# status checks\n\nstatus == 201\nstatuz == 201\nstatus ~ 1\nbody.jsonpath($.a == 1",
+            &response(),
+        );
+        assert_eq!(results.len(), 4);
+        assert!(results[0].passed);
+        assert!(results[1].actual.starts_with("Unknown subject \"statuz\""));
+        assert!(results[2].actual.starts_with("Expected an operator"));
+        assert_eq!(results[3].actual, "Missing ) after the JSONPath");
+        assert!(results[1..].iter().all(|result| !result.passed));
+    }
+}
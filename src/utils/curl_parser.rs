@@ -1,5 +1,6 @@
 use base64::Engine as _;
 use std::collections::HashMap;
+use std::fmt;
 
 use crate::entities::{Header, HttpMethod, RequestBody};
 
@@ -10,8 +11,35 @@ pub struct ParsedCurl {
     pub url: String,
     pub headers: Vec<Header>,
     pub body: RequestBody,
+    /// `-u user:pass` credentials, so the auth editor can be populated.
+    pub basic_auth: Option<(String, String)>,
 }
 
+/// Why a curl command could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    NotCurl,
+    /// A flag that takes a value was the last argument.
+    MissingValue(&'static str),
+    MissingUrl,
+    UnterminatedQuote,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "Empty curl command"),
+            ParseError::NotCurl => write!(f, "Command does not start with curl"),
+            ParseError::MissingValue(flag) => write!(f, "Missing value for {flag}"),
+            ParseError::MissingUrl => write!(f, "Could not find URL in curl command"),
+            ParseError::UnterminatedQuote => write!(f, "Unterminated quote in curl command"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /// Quick check whether a string looks like a curl command.
 pub fn looks_like_curl(text: &str) -> bool {
     let trimmed = text.trim_start();
@@ -22,53 +50,71 @@ pub fn looks_like_curl(text: &str) -> bool {
 ///
 /// Supports the most common flags:
 /// `-X/--request`, `-H/--header`, `-d/--data/--data-raw/--data-binary/--data-urlencode`,
-/// `-F/--form`, `-u/--user`, `-A/--user-agent`, `-e/--referer`, `-b/--cookie`,
+/// `--json`, `-F/--form`, `-u/--user`, `-A/--user-agent`, `-e/--referer`, `-b/--cookie`,
 /// `--url`, `--location`, `--get`, `-G`, `--compressed`, `--insecure`, `-k`.
-pub fn parse_curl(input: &str) -> Result<ParsedCurl, String> {
+/// A lone `--data-binary @file` becomes a binary body that sends the file.
+pub fn parse_curl(input: &str) -> Result<ParsedCurl, ParseError> {
     let tokens = tokenize(input)?;
-    if tokens.is_empty() {
-        return Err("Empty curl command".into());
-    }
-
-    let mut iter = tokens.into_iter().peekable();
+    let mut iter = tokens.into_iter();
 
     // Skip leading "curl"
-    let first = iter.next().ok_or("Missing curl")?;
+    let first = iter.next().ok_or(ParseError::Empty)?;
     if !first.eq_ignore_ascii_case("curl") {
-        return Err("Command does not start with curl".into());
+        return Err(ParseError::NotCurl);
     }
 
+    parse_curl_args(iter)
+}
+
+/// Parse already split curl arguments, without the leading `curl`.
+pub fn parse_curl_args(args: impl IntoIterator<Item = String>) -> Result<ParsedCurl, ParseError> {
+    let mut iter = args.into_iter().peekable();
+
     let mut url: Option<String> = None;
     let mut method: Option<HttpMethod> = None;
     let mut headers: Vec<Header> = Vec::new();
     let mut data_parts: Vec<String> = Vec::new();
+    let mut json_parts: Vec<String> = Vec::new();
     // (key, value, is_file): is_file marks a `-F key=@path` upload field.
     let mut form_parts: Vec<(String, String, bool)> = Vec::new();
+    let mut binary_file: Option<String> = None;
     let mut basic_auth: Option<String> = None;
     let mut force_get = false;
 
     while let Some(arg) = iter.next() {
         match arg.as_str() {
             "-X" | "--request" => {
-                let v = iter.next().ok_or("Missing value for -X")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("-X"))?;
                 method = Some(parse_method(&v));
             }
             "-H" | "--header" => {
-                let v = iter.next().ok_or("Missing value for -H")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("-H"))?;
                 if let Some(h) = parse_header(&v) {
                     headers.push(h);
                 }
             }
             "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" => {
-                let v = iter.next().ok_or("Missing value for -d")?;
-                data_parts.push(v);
+                let v = iter.next().ok_or(ParseError::MissingValue("-d"))?;
+                match v.strip_prefix('@') {
+                    // `@-` reads stdin, which an imported request cannot do.
+                    Some(path) if arg == "--data-binary" && !path.is_empty() && path != "-" => {
+                        binary_file = Some(path.to_string());
+                    }
+                    _ => data_parts.push(v),
+                }
+            }
+            "--json" => {
+                let v = iter.next().ok_or(ParseError::MissingValue("--json"))?;
+                json_parts.push(v);
             }
             "--data-urlencode" => {
-                let v = iter.next().ok_or("Missing value for --data-urlencode")?;
+                let v = iter
+                    .next()
+                    .ok_or(ParseError::MissingValue("--data-urlencode"))?;
                 data_parts.push(v);
             }
             "-F" | "--form" => {
-                let v = iter.next().ok_or("Missing value for -F")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("-F"))?;
                 if let Some((k, val)) = split_once(&v, '=') {
                     if let Some(rest) = val.strip_prefix('@') {
                         // `-F key=@path[;type=...;filename=...]` — strip parameters,
@@ -82,29 +128,31 @@ pub fn parse_curl(input: &str) -> Result<ParsedCurl, String> {
             }
             "--form-string" => {
                 // --form-string never interprets `@`; value is always literal text.
-                let v = iter.next().ok_or("Missing value for --form-string")?;
+                let v = iter
+                    .next()
+                    .ok_or(ParseError::MissingValue("--form-string"))?;
                 if let Some((k, val)) = split_once(&v, '=') {
                     form_parts.push((k.to_string(), val.to_string(), false));
                 }
             }
             "-u" | "--user" => {
-                let v = iter.next().ok_or("Missing value for -u")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("-u"))?;
                 basic_auth = Some(v);
             }
             "-A" | "--user-agent" => {
-                let v = iter.next().ok_or("Missing value for -A")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("-A"))?;
                 headers.push(Header::new("User-Agent", v));
             }
             "-e" | "--referer" => {
-                let v = iter.next().ok_or("Missing value for -e")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("-e"))?;
                 headers.push(Header::new("Referer", v));
             }
             "-b" | "--cookie" => {
-                let v = iter.next().ok_or("Missing value for -b")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("-b"))?;
                 headers.push(Header::new("Cookie", v));
             }
             "--url" => {
-                let v = iter.next().ok_or("Missing value for --url")?;
+                let v = iter.next().ok_or(ParseError::MissingValue("--url"))?;
                 url = Some(v);
             }
             "-G" | "--get" => {
@@ -158,11 +206,37 @@ pub fn parse_curl(input: &str) -> Result<ParsedCurl, String> {
         }
     }
 
-    let mut url = url.ok_or("Could not find URL in curl command")?;
+    let mut url = url.ok_or(ParseError::MissingUrl)?;
 
-    if let Some(creds) = basic_auth {
+    // Like curl, an explicit `-H Authorization` wins over `-u`.
+    if header_value(&headers, "Authorization").is_some() {
+        basic_auth = None;
+    }
+    let basic_auth = basic_auth.map(|creds| {
         let encoded = base64::engine::general_purpose::STANDARD.encode(creds.as_bytes());
         headers.push(Header::new("Authorization", format!("Basic {}", encoded)));
+        match split_once(&creds, ':') {
+            Some((user, pass)) => (user.to_string(), pass.to_string()),
+            None => (creds, String::new()),
+        }
+    });
+
+    // --json is shorthand for --data plus JSON Content-Type/Accept headers;
+    // explicit -H values win.
+    if !json_parts.is_empty() {
+        for name in ["Content-Type", "Accept"] {
+            if header_value(&headers, name).is_none() {
+                headers.push(Header::new(name, "application/json"));
+            }
+        }
+        data_parts.push(json_parts.concat());
+    }
+
+    // A file is only sent on its own; with other data curl joins it in.
+    if (force_get || !data_parts.is_empty() || !form_parts.is_empty())
+        && let Some(path) = binary_file.take()
+    {
+        data_parts.push(format!("@{path}"));
     }
 
     // -G/--get: data is appended to the URL as a query string and the body is
@@ -175,7 +249,10 @@ pub fn parse_curl(input: &str) -> Result<ParsedCurl, String> {
         data_parts.clear();
     }
 
-    let body = build_body(&data_parts, &form_parts, &headers);
+    let body = match binary_file {
+        Some(path) => RequestBody::Binary(path.into()),
+        None => build_body(&data_parts, &form_parts, &headers),
+    };
 
     let method = if force_get {
         HttpMethod::Get
@@ -194,6 +271,7 @@ pub fn parse_curl(input: &str) -> Result<ParsedCurl, String> {
         url,
         headers,
         body,
+        basic_auth,
     })
 }
 
@@ -211,6 +289,13 @@ fn parse_method(value: &str) -> HttpMethod {
 }
 
 fn parse_header(raw: &str) -> Option<Header> {
+    // `-H 'Key;'` is curl's spelling of a header with an empty value.
+    if !raw.contains(':')
+        && let Some(key) = raw.trim().strip_suffix(';')
+    {
+        let key = key.trim();
+        return (!key.is_empty()).then(|| Header::new(key, ""));
+    }
     let (key, value) = split_once(raw, ':')?;
     let key = key.trim();
     let value = value.trim();
@@ -290,7 +375,7 @@ fn looks_like_json(text: &str) -> bool {
 
 /// Tokenize a shell-style command line, honoring single quotes, double quotes,
 /// backslash escapes, and line continuations (`\` at end of line).
-fn tokenize(input: &str) -> Result<Vec<String>, String> {
+fn tokenize(input: &str) -> Result<Vec<String>, ParseError> {
     let mut tokens = Vec::new();
     let mut current = String::new();
     let mut in_token = false;
@@ -369,7 +454,7 @@ fn tokenize(input: &str) -> Result<Vec<String>, String> {
     }
 
     if !matches!(quote, Quote::None) {
-        return Err("Unterminated quote in curl command".into());
+        return Err(ParseError::UnterminatedQuote);
     }
     if in_token {
         tokens.push(current);
@@ -470,6 +555,83 @@ mod tests {
         assert!(auth.value.starts_with("Basic "));
     }
 
+    #[test]
+    fn exposes_basic_auth_credentials() {
+        let parsed = parse_curl("curl -u alice:s3:cr3t https://api.example.com").unwrap();
+        assert_eq!(
+            parsed.basic_auth,
+            Some(("alice".to_string(), "s3:cr3t".to_string()))
+        );
+
+        let parsed = parse_curl("curl --user alice https://api.example.com").unwrap();
+        assert_eq!(
+            parsed.basic_auth,
+            Some(("alice".to_string(), String::new()))
+        );
+    }
+
+    #[test]
+    fn explicit_authorization_header_wins_over_basic_auth() {
+        let parsed = parse_curl(
+            "curl -u alice:secret -H 'Authorization: Bearer abc' https://api.example.com",
+        )
+        .unwrap();
+        assert_eq!(parsed.basic_auth, None);
+        assert_eq!(parsed.headers.len(), 1);
+        assert_eq!(parsed.headers[0].value, "Bearer abc");
+    }
+
+    #[test]
+    fn reports_typed_errors() {
+        assert_eq!(parse_curl("  ").unwrap_err(), ParseError::Empty);
+        assert_eq!(
+            parse_curl("wget https://x").unwrap_err(),
+            ParseError::NotCurl
+        );
+        assert_eq!(
+            parse_curl("curl https://x -H").unwrap_err(),
+            ParseError::MissingValue("-H")
+        );
+        assert_eq!(parse_curl("curl -s").unwrap_err(), ParseError::MissingUrl);
+        assert_eq!(
+            parse_curl("curl 'https://x").unwrap_err(),
+            ParseError::UnterminatedQuote
+        );
+    }
+
+    #[test]
+    fn json_flag_sets_body_method_and_headers() {
+        let parsed =
+            parse_curl(r#"curl https://api.example.com/users --json '{"name":"alice"}'"#).unwrap();
+        assert_eq!(parsed.method, HttpMethod::Post);
+        assert_eq!(
+            header_value(&parsed.headers, "Content-Type"),
+            Some("application/json")
+        );
+        assert_eq!(
+            header_value(&parsed.headers, "Accept"),
+            Some("application/json")
+        );
+        match &parsed.body {
+            RequestBody::Json(s) => assert_eq!(s, r#"{"name":"alice"}"#),
+            other => panic!("expected JSON body, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn json_flag_keeps_explicit_headers() {
+        let parsed = parse_curl(
+            r#"curl -X PUT https://api.example.com --json '{}' -H 'Accept: application/vnd.api+json'"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.method, HttpMethod::Put);
+        assert_eq!(parsed.headers.len(), 2);
+        assert_eq!(
+            header_value(&parsed.headers, "Accept"),
+            Some("application/vnd.api+json")
+        );
+    }
+
     #[test]
     fn handles_location_flag() {
         let parsed = parse_curl("curl --location 'https://ssp.veonadx.com/bid/prebid'").unwrap();
@@ -545,6 +707,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn data_binary_file_becomes_a_binary_body() {
+        let parsed = parse_curl("curl https://api.test --data-binary @/tmp/photo.png").unwrap();
+        assert_eq!(parsed.method, HttpMethod::Post);
+        assert_eq!(
+            parsed.body,
+            RequestBody::Binary(std::path::PathBuf::from("/tmp/photo.png"))
+        );
+
+        let parsed = parse_curl("curl https://api.test -d @/tmp/a.txt").unwrap();
+        assert!(!matches!(parsed.body, RequestBody::Binary(_)));
+    }
+
     #[test]
     fn form_string_keeps_at_prefix_as_literal() {
         let parsed =
@@ -1,4 +1,6 @@
-use gpui::{Entity, Keystroke, Window};
+use std::ops::Range;
+
+use gpui::{App, Entity, EntityInputHandler, Keystroke, Modifiers, Window};
 use gpui_component::input::InputState;
 
 /// Triggers the search panel in an editor by focusing it and dispatching Cmd+F.
@@ -14,3 +16,63 @@ pub fn trigger_editor_search(editor: Option<Entity<InputState>>, window: &Window
         }
     });
 }
+
+/// Where Alt+Up or Alt+Down moves row `index` of a `len`-row editor, or `None` for other
+/// keys and for a row already at that end.
+pub fn keyboard_row_move(keystroke: &Keystroke, index: usize, len: usize) -> Option<usize> {
+    if keystroke.modifiers != Modifiers::alt() {
+        return None;
+    }
+    match keystroke.key.as_str() {
+        "up" => index.checked_sub(1),
+        "down" => (index + 1 < len).then_some(index + 1),
+        _ => None,
+    }
+}
+
+/// The editor's non-empty selection and its UTF-16 range, as the input handler reports it.
+pub fn editor_selection(
+    editor: &Entity<InputState>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<(Range<usize>, String)> {
+    editor.update(cx, |state, cx| {
+        let selection = state.selected_text_range(true, window, cx)?;
+        if selection.range.is_empty() {
+            return None;
+        }
+        let text = state.text_for_range(selection.range.clone(), &mut None, window, cx)?;
+        Some((selection.range, text))
+    })
+}
+
+/// Replace a range previously returned by [`editor_selection`], keeping undo history.
+pub fn replace_editor_range(
+    editor: &Entity<InputState>,
+    range: Range<usize>,
+    text: &str,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    editor.update(cx, |state, cx| {
+        state.replace_text_in_range(Some(range), text, window, cx);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::keyboard_row_move;
+    use gpui::Keystroke;
+
+    #[test]
+    fn alt_arrows_move_rows_within_bounds() {
+        let up = Keystroke::parse("alt-up").unwrap();
+        let down = Keystroke::parse("alt-down").unwrap();
+        assert_eq!(keyboard_row_move(&up, 2, 3), Some(1));
+        assert_eq!(keyboard_row_move(&up, 0, 3), None);
+        assert_eq!(keyboard_row_move(&down, 1, 3), Some(2));
+        assert_eq!(keyboard_row_move(&down, 2, 3), None);
+        let plain = Keystroke::parse("up").unwrap();
+        assert_eq!(keyboard_row_move(&plain, 2, 3), None);
+    }
+}
@@ -0,0 +1,212 @@
+use serde_json::Value;
+
+use crate::entities::ResponseData;
+
+use super::JsonPath;
+
+/// Where in the response a value is copied from.
+#[derive(Debug, Clone, PartialEq)]
+enum Source {
+    Status,
+    Body,
+    /// A header, matched case-insensitively.
+    Header(String),
+    JsonPath(JsonPath),
+}
+
+/// The outcome of one line of a request's post-response rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extraction {
+    pub variable: String,
+    /// Written as `secret set ...`: the variable is stored masked, like a password.
+    pub secret: bool,
+    /// The value found, or why the line could not be applied.
+    pub value: Result<String, String>,
+}
+
+/// Copy values out of `response`, one rule per line, written as `set name = source`:
+///
+/// ```text
+/// set authToken = $.token
+/// set csrf = header:X-CSRF
+/// set lastStatus = status
+/// secret set refreshToken = $.refresh_token
+/// ```
+///
+/// A rule starting with `secret` stores its variable as a secret, masked wherever it is
+/// shown.
+///
+/// A JSONPath that matches several values stores them as a JSON array; strings are stored
+/// without their quotes. Blank lines and `#` comments are skipped.
+pub fn run_extractions(script: &str, response: &ResponseData) -> Vec<Extraction> {
+    let mut parsed_body = None;
+    script
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (secret, rule) = match line.strip_prefix("secret ") {
+                Some(rule) => (true, rule.trim_start()),
+                None => (false, line),
+            };
+            match parse_rule(rule) {
+                Ok((variable, source)) => Extraction {
+                    variable: variable.to_string(),
+                    secret,
+                    value: extract(&source, response, &mut parsed_body),
+                },
+                Err(error) => Extraction {
+                    variable: line.to_string(),
+                    secret,
+                    value: Err(error),
+                },
+            }
+        })
+        .collect()
+}
+
+fn parse_rule(line: &str) -> Result<(&str, Source), String> {
+    let rest = line
+        .strip_prefix("set ")
+        .ok_or("Rules are written as set name = $.path or set name = header:Name")?;
+    let (variable, source) = rest
+        .split_once('=')
+        .ok_or("Expected = between the variable and where its value comes from")?;
+    let variable = variable.trim();
+    if variable.is_empty() || variable.contains(char::is_whitespace) || variable.contains("{{") {
+        return Err("Expected a single variable name before =".to_string());
+    }
+
+    let source = source.trim();
+    let source = if source.starts_with('$') {
+        Source::JsonPath(
+            JsonPath::parse(source).map_err(|error| format!("Invalid JSONPath: {error}"))?,
+        )
+    } else if let Some(name) = source.strip_prefix("header:") {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Expected a header name after header:".to_string());
+        }
+        Source::Header(name.to_string())
+    } else {
+        match source {
+            "status" => Source::Status,
+            "body" => Source::Body,
+            _ => {
+                return Err(format!(
+                    "Unknown source \"{source}\"; use $.path, header:Name, status or body"
+                ));
+            }
+        }
+    };
+    Ok((variable, source))
+}
+
+/// The value `source` names in `response`. The body is parsed once, by the first JSONPath
+/// rule that needs it.
+fn extract(
+    source: &Source,
+    response: &ResponseData,
+    parsed_body: &mut Option<Result<Value, String>>,
+) -> Result<String, String> {
+    match source {
+        Source::Status => Ok(response.status_code.to_string()),
+        Source::Body => Ok(response.text_snapshot().raw_body().to_string()),
+        Source::Header(name) => response
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| format!("No {name} header")),
+        Source::JsonPath(path) => {
+            let root = parsed_body
+                .get_or_insert_with(|| {
+                    serde_json::from_str(&response.text_snapshot().raw_body())
+                        .map_err(|_| "Body is not valid JSON".to_string())
+                })
+                .as_ref()
+                .map_err(Clone::clone)?;
+            let matches = path.query(root);
+            let value = if path.is_definite() {
+                (*matches.first().ok_or("Nothing matched the path")?).clone()
+            } else {
+                Value::Array(matches.into_iter().cloned().collect())
+            };
+            Ok(match value {
+                Value::String(text) => text,
+                value => value.to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_extractions;
+    use crate::entities::ResponseData;
+    use std::collections::HashMap;
+
+    fn response() -> ResponseData {
+        ResponseData::new(
+            200,
+            "OK".to_string(),
+            HashMap::from([("X-CSRF".to_string(), "abc123".to_string())]),
+            r#"{"token":"t0k","user":{"id":7},"roles":["a","b"]}"#.to_string(),
+            Vec::new(),
+            48,
+            12,
+            Some("application/json".to_string()),
+        )
+    }
+
+    #[test]
+    fn copies_json_paths_headers_and_status() {
+        let values: Vec<_> = run_extractions(
+            "# login\nset authToken = $.token\nset userId = $.user.id\n\
+             set csrf = header:x-csrf\nset code = status\nset roles = $.roles[*]",
+            &response(),
+        )
+        .into_iter()
+        .map(|extraction| (extraction.variable, extraction.value.unwrap()))
+        .collect();
+
+        assert_eq!(
+            values,
+            [
+                ("authToken", "t0k"),
+                ("userId", "7"),
+                ("csrf", "abc123"),
+                ("code", "200"),
+                ("roles", r#"["a","b"]"#),
+            ]
+            .map(|(variable, value)| (variable.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn reports_rules_that_find_nothing_or_do_not_parse() {
+        let results = run_extractions(
+            "set missing = $.nope\nset etag = header:ETag\nauthToken = $.token",
+            &response(),
+        );
+        assert_eq!(
+            results[0].value,
+            Err("Nothing matched the path".to_string())
+        );
+        assert_eq!(results[1].value, Err("No ETag header".to_string()));
+        assert!(results[2].value.is_err());
+    }
+
+    #[test]
+    fn marks_rules_written_as_secret() {
+        let results = run_extractions(
+            "secret set authToken = $.token
+set code = status",
+            &response(),
+        );
+        assert_eq!(results[0].variable, "authToken");
+        assert_eq!(results[0].value, Ok("t0k".to_string()));
+        assert!(results[0].secret);
+        assert!(!results[1].secret);
+    }
+}
@@ -0,0 +1,148 @@
+use gpui::Global;
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
+use serde_json::Value;
+
+/// App-wide JSON formatting choices, mirrored from `UiPreferences` by the main view.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatSettings {
+    /// Write object keys in alphabetical order instead of the order they came in.
+    pub sort_keys: bool,
+    /// Largest response body pretty-printed when it arrives; 0 has no limit.
+    pub auto_format_max_bytes: usize,
+}
+
+impl Global for JsonFormatSettings {}
+
+/// Human-readable byte count, e.g. "512 B", "2.0 KB" or "1.25 GB".
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else if bytes < 1024 * 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+/// Pretty-print `text` as JSON, or `None` when it does not parse. Keys keep their order.
+pub fn pretty_json(text: &str) -> Option<String> {
+    let value = serde_json::from_str::<Value>(text).ok()?;
+    serde_json::to_string_pretty(&value).ok()
+}
+
+/// Pretty-print `text` as JSON with the keys of every object sorted.
+pub fn pretty_json_sorted(text: &str) -> Option<String> {
+    let mut value = serde_json::from_str::<Value>(text).ok()?;
+    sort_json_keys(&mut value);
+    serde_json::to_string_pretty(&value).ok()
+}
+
+/// Sort the keys of `value` and of every object nested in it.
+pub fn sort_json_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, nested) in &mut entries {
+                sort_json_keys(nested);
+            }
+            map.extend(entries);
+        }
+        Value::Array(items) => items.iter_mut().for_each(sort_json_keys),
+        _ => {}
+    }
+}
+
+/// Re-indent `text` as XML with two spaces per level.
+///
+/// Attribute order, CDATA sections and comments are written back untouched. Returns `None`
+/// for malformed markup or input without a single element (plain text is not XML).
+pub fn pretty_xml(text: &str) -> Option<String> {
+    let mut reader = Reader::from_str(text);
+    reader.config_mut().trim_text(true);
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    let mut saw_element = false;
+    let mut depth = 0usize;
+
+    loop {
+        match reader.read_event().ok()? {
+            Event::Eof => break,
+            event => {
+                match &event {
+                    Event::Start(_) => {
+                        saw_element = true;
+                        depth += 1;
+                    }
+                    Event::End(_) => depth = depth.checked_sub(1)?,
+                    Event::Empty(_) => saw_element = true,
+                    _ => {}
+                }
+                writer.write_event(event).ok()?;
+            }
+        }
+    }
+
+    if !saw_element || depth != 0 {
+        return None;
+    }
+    String::from_utf8(writer.into_inner()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_size, pretty_json, pretty_json_sorted, pretty_xml};
+
+    #[test]
+    fn formats_sizes_in_the_nearest_unit() {
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 / 2), "1.5 MB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024 / 4), "1.25 GB");
+    }
+
+    #[test]
+    fn formats_json_and_rejects_invalid_input() {
+        assert_eq!(
+            pretty_json(r#"{"a":[1,2]}"#).as_deref(),
+            Some("{\n  \"a\": [\n    1,\n    2\n  ]\n}")
+        );
+        assert!(pretty_json("{not json").is_none());
+    }
+
+    #[test]
+    fn keeps_key_order_unless_asked_to_sort() {
+        let json = r#"{"b":1,"a":[{"z":1,"y":2}]}"#;
+        assert_eq!(
+            pretty_json(json).unwrap().replace(char::is_whitespace, ""),
+            json
+        );
+        assert_eq!(
+            pretty_json_sorted(json)
+                .unwrap()
+                .replace(char::is_whitespace, ""),
+            r#"{"a":[{"y":2,"z":1}],"b":1}"#
+        );
+        assert!(pretty_json_sorted("[1,").is_none());
+    }
+
+    #[test]
+    fn indents_xml_preserving_attributes_and_cdata() {
+        let formatted = pretty_xml(r#"<a z="1" b="2"><b><![CDATA[x < y]]></b><c/></a>"#).unwrap();
+        let lines: Vec<_> = formatted.lines().collect();
+        assert_eq!(lines.first(), Some(&r#"<a z="1" b="2">"#));
+        assert!(lines.iter().any(|line| line.starts_with("  <b>")));
+        assert!(formatted.contains("<![CDATA[x < y]]>"));
+        assert!(lines.contains(&"  <c/>"));
+        assert_eq!(lines.last(), Some(&"</a>"));
+    }
+
+    #[test]
+    fn rejects_malformed_or_non_xml_text() {
+        assert!(pretty_xml("<a><b></a>").is_none());
+        assert!(pretty_xml("<a><b>").is_none());
+        assert!(pretty_xml("just some text").is_none());
+    }
+}
@@ -0,0 +1,350 @@
+/// Elements that never have content or a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements kept on the line of the text around them when re-indenting.
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "cite", "code", "data", "dfn", "em", "i", "img", "kbd",
+    "label", "mark", "q", "s", "samp", "small", "span", "strong", "sub", "sup", "time", "u", "var",
+    "wbr",
+];
+
+/// Elements whose content is copied verbatim: scripts and styles are not markup, and
+/// whitespace inside `pre` and `textarea` is significant.
+const VERBATIM_ELEMENTS: &[&str] = &["pre", "script", "style", "textarea"];
+
+/// Elements whose content is not page text.
+const HIDDEN_ELEMENTS: &[&str] = &["head", "noscript", "script", "style", "template"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Open {
+        raw: &'a str,
+        name: String,
+        self_closing: bool,
+    },
+    Close {
+        raw: &'a str,
+        name: String,
+    },
+    /// Comments, doctypes and processing instructions.
+    Other(&'a str),
+    Text(&'a str),
+}
+
+/// Split markup into tags and text. Content of verbatim elements comes back as one text token.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut text_start = 0;
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &html[start..];
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|end| start + end + 3)
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest.find('>').map(|end| start + end + 1)
+        } else if rest.starts_with("</") || rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            tag_end(rest).map(|end| start + end)
+        } else {
+            None
+        };
+        let Some(end) = end else {
+            pos = start + 1;
+            continue;
+        };
+
+        if text_start < start {
+            tokens.push(Token::Text(&html[text_start..start]));
+        }
+        let raw = &html[start..end];
+        let token = if raw.starts_with("<!") || raw.starts_with("<?") {
+            Token::Other(raw)
+        } else if let Some(close) = raw.strip_prefix("</") {
+            Token::Close {
+                raw,
+                name: tag_name(close),
+            }
+        } else {
+            let name = tag_name(&raw[1..]);
+            Token::Open {
+                raw,
+                self_closing: raw.ends_with("/>") || VOID_ELEMENTS.contains(&name.as_str()),
+                name,
+            }
+        };
+        pos = end;
+        text_start = end;
+
+        if let Token::Open {
+            name,
+            self_closing: false,
+            ..
+        } = &token
+            && VERBATIM_ELEMENTS.contains(&name.as_str())
+        {
+            let closing = format!("</{name}");
+            let content_end = html[end..]
+                .to_ascii_lowercase()
+                .find(&closing)
+                .map_or(html.len(), |offset| end + offset);
+            tokens.push(token);
+            if end < content_end {
+                tokens.push(Token::Text(&html[end..content_end]));
+            }
+            pos = content_end;
+            text_start = content_end;
+            continue;
+        }
+        tokens.push(token);
+    }
+    if text_start < html.len() {
+        tokens.push(Token::Text(&html[text_start..]));
+    }
+    tokens
+}
+
+/// Length of the tag at the start of `rest`, skipping `>` inside quoted attribute values.
+fn tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in rest.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn tag_name(after_bracket: &str) -> String {
+    after_bracket
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == ':')
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                collapsed.push(' ');
+            }
+            in_space = true;
+        } else {
+            collapsed.push(c);
+            in_space = false;
+        }
+    }
+    collapsed
+}
+
+/// Re-indent `html` with two spaces per level, keeping inline elements on their text line.
+///
+/// Attributes and entities are written back untouched, as is the content of `pre`,
+/// `textarea`, `script` and `style`. Returns `None` for text without any tags.
+pub fn pretty_html(html: &str) -> Option<String> {
+    let tokens = tokenize(html);
+    if !tokens
+        .iter()
+        .any(|token| matches!(token, Token::Open { .. } | Token::Close { .. }))
+    {
+        return None;
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    let mut depth = 0usize;
+    let mut verbatim = false;
+    let flush = |line: &mut String, lines: &mut Vec<String>, depth: usize| {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            lines.push(format!("{}{trimmed}", "  ".repeat(depth)));
+        }
+        line.clear();
+    };
+
+    for token in tokens {
+        match token {
+            Token::Text(text) if verbatim => line.push_str(text),
+            Token::Text(text) => line.push_str(&collapse_whitespace(text)),
+            Token::Open { raw, name, .. } | Token::Close { raw, name }
+                if INLINE_ELEMENTS.contains(&name.as_str()) =>
+            {
+                line.push_str(raw);
+            }
+            Token::Open {
+                raw,
+                name,
+                self_closing,
+            } => {
+                flush(&mut line, &mut lines, depth);
+                if VERBATIM_ELEMENTS.contains(&name.as_str()) && !self_closing {
+                    // The element stays on one line with its content, so nothing inside shifts.
+                    line.push_str(raw);
+                    verbatim = true;
+                    continue;
+                }
+                lines.push(format!("{}{raw}", "  ".repeat(depth)));
+                if !self_closing {
+                    depth += 1;
+                }
+            }
+            Token::Close { raw, .. } if verbatim => {
+                line.push_str(raw);
+                lines.push(format!("{}{line}", "  ".repeat(depth)));
+                line.clear();
+                verbatim = false;
+            }
+            Token::Close { raw, .. } => {
+                flush(&mut line, &mut lines, depth);
+                depth = depth.saturating_sub(1);
+                lines.push(format!("{}{raw}", "  ".repeat(depth)));
+            }
+            Token::Other(raw) => {
+                flush(&mut line, &mut lines, depth);
+                lines.push(format!("{}{raw}", "  ".repeat(depth)));
+            }
+        }
+    }
+    flush(&mut line, &mut lines, depth);
+    Some(lines.join("\n"))
+}
+
+/// The text a browser would show for `html`: tags dropped, entities decoded and block
+/// elements on their own lines. Scripts, styles and the document head are left out.
+pub fn html_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut hidden_depth = 0usize;
+    let mut verbatim = false;
+    for token in tokenize(html) {
+        match token {
+            Token::Open {
+                name, self_closing, ..
+            } => {
+                if HIDDEN_ELEMENTS.contains(&name.as_str()) && !self_closing {
+                    hidden_depth += 1;
+                } else if name == "br" || !INLINE_ELEMENTS.contains(&name.as_str()) {
+                    text.push('\n');
+                }
+                verbatim = name == "pre" || name == "textarea";
+            }
+            Token::Close { name, .. } => {
+                if HIDDEN_ELEMENTS.contains(&name.as_str()) {
+                    hidden_depth = hidden_depth.saturating_sub(1);
+                } else if !INLINE_ELEMENTS.contains(&name.as_str()) {
+                    text.push('\n');
+                }
+                verbatim = false;
+            }
+            Token::Text(_) | Token::Other(_) if hidden_depth > 0 => {}
+            Token::Text(content) if verbatim => text.push_str(&decode_entities(content)),
+            Token::Text(content) => text.push_str(&collapse_whitespace(&decode_entities(content))),
+            Token::Other(_) => {}
+        }
+    }
+
+    // Tidy the line breaks block elements leave behind: trim lines, drop runs of blank ones.
+    let mut tidy = String::with_capacity(text.len());
+    let mut blank_run = 0;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if !tidy.is_empty() {
+            tidy.push_str(if blank_run > 1 { "\n\n" } else { "\n" });
+        }
+        tidy.push_str(line);
+        blank_run = 0;
+    }
+    tidy
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| decode_entity(&rest[1..=end]).map(|c| (c, end + 2)));
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "copy" => '©',
+        "reg" => '®',
+        "hellip" => '…',
+        "mdash" => '—',
+        "ndash" => '–',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{html_text, pretty_html};
+
+    const PAGE: &str = r#"<!DOCTYPE html><html><head><title>Hi</title><style>p > a { color: red }</style></head><body><div class="card"><p>Hello <b>world</b>,<br>again &amp; again</p><pre>  keep
+   this</pre><img src="a.png"></div><script>if (a < b) {}</script></body></html>"#;
+
+    #[test]
+    fn indents_blocks_and_keeps_inline_and_verbatim_content() {
+        let formatted = pretty_html(PAGE).unwrap();
+        let lines: Vec<_> = formatted.lines().collect();
+        assert_eq!(lines[0], "<!DOCTYPE html>");
+        assert!(lines.contains(&"    <div class=\"card\">"));
+        assert!(lines.contains(&"        Hello <b>world</b>,<br>again &amp; again"));
+        assert!(formatted.contains("<pre>  keep\n   this</pre>"));
+        assert!(lines.contains(&"      <img src=\"a.png\">"));
+        assert!(lines.contains(&"    <script>if (a < b) {}</script>"));
+        assert!(formatted.contains("<style>p > a { color: red }</style>"));
+        assert_eq!(lines.last(), Some(&"</html>"));
+        assert!(pretty_html("no markup here").is_none());
+    }
+
+    #[test]
+    fn extracts_page_text_without_head_or_scripts() {
+        assert_eq!(html_text(PAGE), "Hello world,\nagain & again\nkeep\nthis");
+        assert_eq!(
+            html_text("<p>caf&eacute; &#233; &#xE9;</p>"),
+            "caf&eacute; é é"
+        );
+    }
+}
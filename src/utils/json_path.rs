@@ -0,0 +1,341 @@
+use serde_json::Value;
+use std::fmt;
+
+/// Why a JSONPath expression could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonPathError {
+    /// Byte offset in the expression where parsing stopped.
+    pub position: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position + 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+    Slice(Option<i64>, Option<i64>),
+    Union(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    /// `..`: apply the selector to the node and every node below it.
+    recursive: bool,
+    selector: Selector,
+}
+
+/// A parsed JSONPath expression.
+///
+/// Supports the common subset: `$`, `.name`, `['name']`, `[index]` (negative from the end),
+/// `[*]` and `.*`, `[start:end]`, unions like `[0,2]` or `['a','b']`, and `..` descent.
+/// Filter expressions (`[?(...)]`) are not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    steps: Vec<Step>,
+}
+
+impl JsonPath {
+    pub fn parse(expression: &str) -> Result<Self, JsonPathError> {
+        Parser {
+            text: expression.trim(),
+            pos: 0,
+        }
+        .parse()
+    }
+
+    /// Whether the path names a single location, so its match is shown bare rather than
+    /// wrapped in an array.
+    pub fn is_definite(&self) -> bool {
+        self.steps.iter().all(|step| {
+            !step.recursive && matches!(step.selector, Selector::Name(_) | Selector::Index(_))
+        })
+    }
+
+    /// Every value the path selects, in document order.
+    pub fn query<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        let mut nodes = vec![root];
+        for step in &self.steps {
+            let mut next = Vec::new();
+            for node in nodes {
+                if step.recursive {
+                    let mut stack = vec![node];
+                    while let Some(current) = stack.pop() {
+                        select(&step.selector, current, &mut next);
+                        match current {
+                            Value::Object(map) => stack.extend(map.values().rev()),
+                            Value::Array(items) => stack.extend(items.iter().rev()),
+                            _ => {}
+                        }
+                    }
+                } else {
+                    select(&step.selector, node, &mut next);
+                }
+            }
+            nodes = next;
+        }
+        nodes
+    }
+
+    /// The match of a definite path, or an array of all matches.
+    pub fn evaluate(&self, root: &Value) -> Value {
+        let matches = self.query(root);
+        if self.is_definite()
+            && let [single] = matches.as_slice()
+        {
+            return (*single).clone();
+        }
+        Value::Array(matches.into_iter().cloned().collect())
+    }
+}
+
+fn select<'a>(selector: &Selector, node: &'a Value, out: &mut Vec<&'a Value>) {
+    match (selector, node) {
+        (Selector::Name(name), Value::Object(map)) => out.extend(map.get(name)),
+        (Selector::Index(index), Value::Array(items)) => {
+            let index = if *index < 0 {
+                items.len() as i64 + index
+            } else {
+                *index
+            };
+            if let Ok(index) = usize::try_from(index) {
+                out.extend(items.get(index));
+            }
+        }
+        (Selector::Wildcard, Value::Object(map)) => out.extend(map.values()),
+        (Selector::Wildcard, Value::Array(items)) => out.extend(items.iter()),
+        (Selector::Slice(start, end), Value::Array(items)) => {
+            let len = items.len() as i64;
+            let clamp = |bound: i64| {
+                let bound = if bound < 0 { len + bound } else { bound };
+                bound.clamp(0, len) as usize
+            };
+            let start = start.map_or(0, clamp);
+            let end = end.map_or(items.len(), clamp);
+            if start < end {
+                out.extend(items[start..end].iter());
+            }
+        }
+        (Selector::Union(selectors), _) => {
+            for selector in selectors {
+                select(selector, node, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> JsonPathError {
+        JsonPathError {
+            position: self.pos,
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn parse(mut self) -> Result<JsonPath, JsonPathError> {
+        if !self.eat('$') {
+            return Err(self.error("Expression must start with $"));
+        }
+        let mut steps = Vec::new();
+        while self.pos < self.text.len() {
+            let recursive = if self.text[self.pos..].starts_with("..") {
+                self.pos += 2;
+                true
+            } else {
+                false
+            };
+            let selector = if self.eat('[') {
+                self.parse_bracket()?
+            } else if recursive || self.eat('.') {
+                self.parse_dot_member()?
+            } else {
+                return Err(self.error("Expected . or ["));
+            };
+            steps.push(Step {
+                recursive,
+                selector,
+            });
+        }
+        Ok(JsonPath { steps })
+    }
+
+    fn parse_dot_member(&mut self) -> Result<Selector, JsonPathError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.pos;
+        while let Some(c) = self
+            .peek()
+            .filter(|&c| c != '.' && c != '[' && !c.is_whitespace())
+        {
+            self.pos += c.len_utf8();
+        }
+        if start == self.pos {
+            return Err(self.error("Expected a member name"));
+        }
+        Ok(Selector::Name(self.text[start..self.pos].to_string()))
+    }
+
+    fn parse_bracket(&mut self) -> Result<Selector, JsonPathError> {
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_spaces();
+            selectors.push(self.parse_bracket_item()?);
+            self.skip_spaces();
+            if self.eat(']') {
+                break;
+            }
+            if !self.eat(',') {
+                return Err(self.error("Expected , or ]"));
+            }
+        }
+        Ok(if selectors.len() == 1 {
+            selectors.remove(0)
+        } else {
+            Selector::Union(selectors)
+        })
+    }
+
+    fn parse_bracket_item(&mut self) -> Result<Selector, JsonPathError> {
+        match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some(quote @ ('\'' | '"')) => {
+                self.pos += 1;
+                let mut name = String::new();
+                loop {
+                    match self.peek() {
+                        None => return Err(self.error("Unterminated string")),
+                        Some(c) if c == quote => {
+                            self.pos += 1;
+                            return Ok(Selector::Name(name));
+                        }
+                        Some('\\') => {
+                            self.pos += 1;
+                            let escaped = self
+                                .peek()
+                                .ok_or_else(|| self.error("Unterminated string"))?;
+                            name.push(escaped);
+                            self.pos += escaped.len_utf8();
+                        }
+                        Some(c) => {
+                            name.push(c);
+                            self.pos += c.len_utf8();
+                        }
+                    }
+                }
+            }
+            _ => {
+                let start = self.parse_integer()?;
+                self.skip_spaces();
+                if !self.eat(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("Expected an index, a name or *"));
+                }
+                self.skip_spaces();
+                let end = self.parse_integer()?;
+                Ok(Selector::Slice(start, end))
+            }
+        }
+    }
+
+    /// An optional signed integer; `None` when there are no digits.
+    fn parse_integer(&mut self) -> Result<Option<i64>, JsonPathError> {
+        let start = self.pos;
+        self.eat('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        match &self.text[start..self.pos] {
+            "" => Ok(None),
+            digits => digits.parse().map(Some).map_err(|_| JsonPathError {
+                position: start,
+                message: "Invalid index",
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonPath;
+    use serde_json::json;
+
+    fn eval(expression: &str) -> serde_json::Value {
+        let doc = json!({
+            "data": {
+                "items": [
+                    {"id": 1, "name": "a"},
+                    {"id": 2, "name": "b"},
+                    {"id": 3, "tags": {"id": "nested"}}
+                ],
+                "total": 3
+            },
+            "odd key": true
+        });
+        JsonPath::parse(expression).unwrap().evaluate(&doc)
+    }
+
+    #[test]
+    fn selects_members_indices_and_wildcards() {
+        assert_eq!(eval("$.data.total"), json!(3));
+        assert_eq!(eval("$['odd key']"), json!(true));
+        assert_eq!(eval("$.data.items[-1].id"), json!(3));
+        assert_eq!(eval("$.data.items[*].id"), json!([1, 2, 3]));
+        assert_eq!(eval("$.data.items[0:2].name"), json!(["a", "b"]));
+        assert_eq!(eval("$.data.items[0,2].id"), json!([1, 3]));
+        assert_eq!(eval("$.data.items[9]"), json!([]));
+    }
+
+    #[test]
+    fn descends_recursively_in_document_order() {
+        assert_eq!(eval("$..id"), json!([1, 2, 3, "nested"]));
+        assert_eq!(eval("$..items[1].name"), json!(["b"]));
+    }
+
+    #[test]
+    fn reports_where_an_expression_is_invalid() {
+        let error = JsonPath::parse("data.items").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Expression must start with $ at position 1"
+        );
+        assert_eq!(JsonPath::parse("$.items[").unwrap_err().position, 8);
+        assert!(JsonPath::parse("$['open").is_err());
+        assert!(JsonPath::parse("$.").is_err());
+    }
+}
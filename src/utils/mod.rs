@@ -1,9 +1,27 @@
+mod assertions;
 mod curl_parser;
 mod dialog_focus;
 mod editor;
+mod extract;
+mod format;
+mod html;
+mod json_path;
+mod mp4;
+mod pre_request;
 mod runtime;
 
-pub use curl_parser::{ParsedCurl, looks_like_curl, parse_curl};
+pub use assertions::{AssertionResult, run_assertions};
+pub use curl_parser::{ParsedCurl, looks_like_curl, parse_curl, parse_curl_args};
 pub use dialog_focus::{close_dialog, open_dialog, set_app_focus_handle};
-pub use editor::trigger_editor_search;
+pub use editor::{
+    editor_selection, keyboard_row_move, replace_editor_range, trigger_editor_search,
+};
+pub use extract::{Extraction, run_extractions};
+pub use format::{
+    JsonFormatSettings, format_size, pretty_json, pretty_json_sorted, pretty_xml, sort_json_keys,
+};
+pub use html::{html_text, pretty_html};
+pub use json_path::JsonPath;
+pub use mp4::probe_mp4;
+pub use pre_request::{PreRequestContext, PreRequestOutput, run_pre_request_script};
 pub use runtime::{DebouncedJsonWriter, shared_tokio_runtime};
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+/// What the `moov` box of an MP4 or QuickTime file says about it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Mp4Info {
+    pub duration: Option<Duration>,
+    /// Display size of the first track that has one.
+    pub dimensions: Option<(u32, u32)>,
+}
+
+/// Read the duration and frame size from the box headers, without decoding any media.
+pub fn probe_mp4(bytes: &[u8]) -> Mp4Info {
+    let mut info = Mp4Info::default();
+    for (kind, moov) in boxes(bytes) {
+        if &kind != b"moov" {
+            continue;
+        }
+        for (kind, content) in boxes(moov) {
+            match &kind {
+                b"mvhd" => info.duration = movie_duration(content),
+                b"trak" if info.dimensions.is_none() => {
+                    info.dimensions = boxes(content)
+                        .find(|(kind, _)| kind == b"tkhd")
+                        .and_then(|(_, tkhd)| track_dimensions(tkhd));
+                }
+                _ => {}
+            }
+        }
+    }
+    info
+}
+
+/// The boxes laid end to end in `bytes`, stopping at the first malformed one.
+fn boxes(bytes: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    let mut rest = bytes;
+    std::iter::from_fn(move || {
+        let kind: [u8; 4] = rest.get(4..8)?.try_into().ok()?;
+        let (header_len, size) = match be_u32(rest, 0)? {
+            0 => (8, rest.len() as u64),
+            1 => (16, be_u64(rest, 8)?),
+            size => (8, u64::from(size)),
+        };
+        let size = usize::try_from(size)
+            .ok()
+            .filter(|&size| size >= header_len && size <= rest.len())?;
+        let content = &rest[header_len..size];
+        rest = &rest[size..];
+        Some((kind, content))
+    })
+}
+
+fn movie_duration(mvhd: &[u8]) -> Option<Duration> {
+    let (timescale, duration) = match mvhd.first()? {
+        0 => (be_u32(mvhd, 12)?, be_u32(mvhd, 16).map(u64::from)?),
+        1 => (be_u32(mvhd, 20)?, be_u64(mvhd, 24)?),
+        _ => return None,
+    };
+    // All ones marks a duration the writer did not know.
+    if timescale == 0 || duration == u64::from(u32::MAX) || duration == u64::MAX {
+        return None;
+    }
+    let millis = u128::from(duration) * 1000 / u128::from(timescale);
+    Some(Duration::from_millis(u64::try_from(millis).ok()?))
+}
+
+fn track_dimensions(tkhd: &[u8]) -> Option<(u32, u32)> {
+    // Width and height follow the version-sized times, the layer fields and the matrix.
+    let offset = match tkhd.first()? {
+        0 => 76,
+        1 => 88,
+        _ => return None,
+    };
+    // Both are 16.16 fixed point.
+    let width = be_u32(tkhd, offset)? >> 16;
+    let height = be_u32(tkhd, offset + 4)? >> 16;
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(bytes: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(bytes.get(at..at + 8)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mp4Info, probe_mp4};
+    use std::time::Duration;
+
+    fn mp4_box(kind: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        let mut bytes = ((content.len() + 8) as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(kind);
+        bytes.extend_from_slice(content);
+        bytes
+    }
+
+    fn tkhd(width: u32, height: u32) -> Vec<u8> {
+        let mut content = vec![0; 76];
+        content.extend_from_slice(&(width << 16).to_be_bytes());
+        content.extend_from_slice(&(height << 16).to_be_bytes());
+        mp4_box(b"tkhd", &content)
+    }
+
+    #[test]
+    fn reads_duration_and_the_first_sized_track() {
+        let mut mvhd = vec![0; 12];
+        mvhd.extend_from_slice(&1000_u32.to_be_bytes());
+        mvhd.extend_from_slice(&12_500_u32.to_be_bytes());
+        mvhd.resize(100, 0);
+        let moov = [
+            mp4_box(b"mvhd", &mvhd),
+            mp4_box(b"trak", &tkhd(0, 0)),
+            mp4_box(b"trak", &tkhd(1280, 720)),
+        ]
+        .concat();
+        let file = [
+            mp4_box(b"ftyp", b"isom\0\0\x02\0"),
+            mp4_box(b"mdat", &[0xAB; 64]),
+            mp4_box(b"moov", &moov),
+        ]
+        .concat();
+
+        assert_eq!(
+            probe_mp4(&file),
+            Mp4Info {
+                duration: Some(Duration::from_millis(12_500)),
+                dimensions: Some((1280, 720)),
+            }
+        );
+    }
+
+    #[test]
+    fn returns_nothing_for_other_data() {
+        assert_eq!(probe_mp4(b"\x1A\x45\xDF\xA3webm"), Mp4Info::default());
+        assert_eq!(probe_mp4(&[0xFF; 3]), Mp4Info::default());
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use base64::Engine as _;
+use chrono::{DateTime, SecondsFormat, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// Values longer than this are refused, so a few self-concatenating lines cannot eat memory.
+const MAX_VALUE_BYTES: usize = 1024 * 1024;
+
+/// What a pre-request script can read besides its own variables.
+pub struct PreRequestContext<'a> {
+    /// The time `now()` and its siblings report; fixed for the whole script.
+    pub now: DateTime<Utc>,
+    /// Looks a name up in the active environments, for `env("KEY")`.
+    pub env: &'a dyn Fn(&str) -> Option<String>,
+}
+
+/// Headers and variables a pre-request script set, in the order they were first set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreRequestOutput {
+    pub headers: Vec<(String, String)>,
+    /// Used for `{{name}}` in the request, ahead of environment values.
+    pub variables: HashMap<String, String>,
+}
+
+/// Run a script that computes values right before a request is sent, one statement per
+/// line:
+///
+/// ```text
+/// set ts = now()
+/// header X-Timestamp = ts
+/// header X-Signature = hmac_sha256(env("API_SECRET"), ts + ":" + env("API_KEY"))
+/// header X-Request-Id = uuid()
+/// ```
+///
+/// Values are strings joined with `+`: quoted literals, numbers, names from earlier `set`
+/// lines and calls to `now()`, `now_ms()`, `now_iso()`, `uuid()`, `base64(text)`,
+/// `sha256(text)`, `hmac_sha256(key, text)` and `env(name)`. Nothing can touch files or the
+/// network. Blank lines and `#` comments are skipped; the first line that fails stops the
+/// script.
+pub fn run_pre_request_script(
+    script: &str,
+    context: &PreRequestContext,
+) -> Result<PreRequestOutput, String> {
+    let mut output = PreRequestOutput::default();
+    for (index, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        run_statement(line, context, &mut output)
+            .map_err(|error| format!("Line {}: {error}", index + 1))?;
+    }
+    Ok(output)
+}
+
+fn run_statement(
+    line: &str,
+    context: &PreRequestContext,
+    output: &mut PreRequestOutput,
+) -> Result<(), String> {
+    let keyword_end = line.find(char::is_whitespace).unwrap_or(line.len());
+    let (keyword, rest) = line.split_at(keyword_end);
+    let is_header = match keyword {
+        "header" => true,
+        "set" => false,
+        _ => {
+            return Err(format!(
+                "Unknown statement \"{keyword}\"; use header NAME = value or set name = value"
+            ));
+        }
+    };
+    let (name, expression) = rest
+        .split_once('=')
+        .ok_or("Expected = between the name and the value")?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(format!("Expected a single {keyword} name before ="));
+    }
+
+    let mut parser = Parser {
+        input: expression,
+        position: 0,
+        context,
+        variables: &output.variables,
+    };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    if parser.position < expression.len() {
+        return Err(format!(
+            "Unexpected \"{}\" after the value",
+            &expression[parser.position..]
+        ));
+    }
+
+    if is_header {
+        match output
+            .headers
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        {
+            Some(header) => header.1 = value,
+            None => output.headers.push((name.to_string(), value)),
+        }
+    } else {
+        output.variables.insert(name.to_string(), value);
+    }
+    Ok(())
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    context: &'a PreRequestContext<'a>,
+    variables: &'a HashMap<String, String>,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expression(&mut self) -> Result<String, String> {
+        let mut value = self.term()?;
+        while self.eat('+') {
+            value.push_str(&self.term()?);
+            if value.len() > MAX_VALUE_BYTES {
+                return Err("Value is longer than 1 MB".to_string());
+            }
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        match rest.chars().next() {
+            None => Err("Expected a value".to_string()),
+            Some('"') => self.string(),
+            Some(c) if c.is_ascii_digit() => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                    .unwrap_or(rest.len());
+                self.position += end;
+                Ok(rest[..end].to_string())
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let name = &rest[..end];
+                self.position += end;
+                if self.eat('(') {
+                    let arguments = self.arguments()?;
+                    self.call(name, arguments)
+                } else {
+                    self.variables.get(name).cloned().ok_or_else(|| {
+                        format!("\"{name}\" is not set; add a set line above this one")
+                    })
+                }
+            }
+            Some(c) => Err(format!("Unexpected \"{c}\"")),
+        }
+    }
+
+    /// A double-quoted literal with JSON escapes.
+    fn string(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let mut escaped = false;
+        for (offset, c) in rest.char_indices().skip(1) {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => {
+                    let literal = &rest[..=offset];
+                    self.position += offset + 1;
+                    return serde_json::from_str(literal)
+                        .map_err(|_| format!("Invalid escape in {literal}"));
+                }
+                _ => escaped = false,
+            }
+        }
+        Err("Missing closing quote".to_string())
+    }
+
+    /// Comma-separated values up to the `)` closing a call.
+    fn arguments(&mut self) -> Result<Vec<String>, String> {
+        let mut arguments = Vec::new();
+        if self.eat(')') {
+            return Ok(arguments);
+        }
+        loop {
+            arguments.push(self.expression()?);
+            if self.eat(')') {
+                return Ok(arguments);
+            }
+            if !self.eat(',') {
+                return Err("Expected , or ) in the argument list".to_string());
+            }
+        }
+    }
+
+    fn call(&self, name: &str, arguments: Vec<String>) -> Result<String, String> {
+        let expected = match name {
+            "now" | "now_ms" | "now_iso" | "uuid" => 0,
+            "base64" | "sha256" | "env" => 1,
+            "hmac_sha256" => 2,
+            _ => {
+                return Err(format!(
+                    "Unknown function \"{name}\"; use now, now_ms, now_iso, uuid, base64, \
+                     sha256, hmac_sha256 or env"
+                ));
+            }
+        };
+        if arguments.len() != expected {
+            return Err(format!(
+                "{name}() takes {expected} argument{}",
+                if expected == 1 { "" } else { "s" }
+            ));
+        }
+
+        let now = self.context.now;
+        Ok(match (name, arguments.as_slice()) {
+            ("now", []) => now.timestamp().to_string(),
+            ("now_ms", []) => now.timestamp_millis().to_string(),
+            ("now_iso", []) => now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            ("uuid", []) => uuid::Uuid::new_v4().to_string(),
+            ("base64", [text]) => base64::engine::general_purpose::STANDARD.encode(text),
+            ("sha256", [text]) => hex(&Sha256::digest(text.as_bytes())),
+            ("hmac_sha256", [key, text]) => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+                    .expect("HMAC accepts keys of any length");
+                mac.update(text.as_bytes());
+                hex(&mac.finalize().into_bytes())
+            }
+            ("env", [key]) => (self.context.env)(key)
+                .ok_or_else(|| format!("\"{key}\" is not set in the active environment"))?,
+            _ => unreachable!("argument counts are checked above"),
+        })
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PreRequestContext, run_pre_request_script};
+    use chrono::{TimeZone, Utc};
+
+    fn run(script: &str) -> Result<super::PreRequestOutput, String> {
+        let env = |key: &str| (key == "SECRET").then(|| "key".to_string());
+        let context = PreRequestContext {
+            now: Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap(),
+            env: &env,
+        };
+        run_pre_request_script(script, &context)
+    }
+
+    #[test]
+    fn sets_headers_and_variables_from_helpers() {
+        let output = run(r#"
+            # signed with the shared secret
+            set ts = now()
+            header X-Timestamp = ts
+            header X-Sig = hmac_sha256(env("SECRET"), "The quick brown fox jumps over the lazy dog")
+            header X-Auth = base64("user:" + "pass")
+            header x-timestamp = now_iso()
+            set id = uuid()
+        "#)
+        .unwrap();
+
+        assert_eq!(
+            output.headers,
+            vec![
+                (
+                    "X-Timestamp".to_string(),
+                    "2024-05-01T12:00:00Z".to_string()
+                ),
+                (
+                    "X-Sig".to_string(),
+                    "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8".to_string()
+                ),
+                ("X-Auth".to_string(), "dXNlcjpwYXNz".to_string()),
+            ]
+        );
+        assert_eq!(output.variables["ts"], "1714564800");
+        assert_eq!(output.variables["id"].len(), 36);
+    }
+
+    #[test]
+    fn reports_the_line_that_failed() {
+        assert_eq!(
+            run("set a = 1\n\nheader X = env(\"MISSING\")").unwrap_err(),
+            "Line 3: \"MISSING\" is not set in the active environment"
+        );
+        assert!(
+            run("header X = nope")
+                .unwrap_err()
+                .starts_with("Line 1: \"nope\" is not set")
+        );
+        assert!(
+            run("fetch X = 1")
+                .unwrap_err()
+                .contains("Unknown statement")
+        );
+        assert!(
+            run("header X = read_file(\"/etc/passwd\")")
+                .unwrap_err()
+                .contains("Unknown function")
+        );
+        assert!(
+            run("header X = \"open")
+                .unwrap_err()
+                .contains("closing quote")
+        );
+    }
+}
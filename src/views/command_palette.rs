@@ -12,15 +12,42 @@ use crate::icons::IconName;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommandId {
     SendRequest,
+    SendAndSave,
+    PreviewRequest,
+    ChangeHost,
     CancelRequest,
     NewRequest,
     DuplicateRequest,
+    EditAndResend,
     ToggleSidebar,
     ToggleRequestResponseLayout,
+    ToggleFormatOnPaste,
+    FormatSelectionAsJson,
+    ToggleFollowRedirects,
+    ToggleDryRun,
+    ToggleDefaultScheme,
+    ToggleFocusUrlOnNewTab,
+    ConfigureProxy,
+    ConfigureEnvironmentProxy,
+    ConfigureClientCertificate,
+    ToggleInsecureTls,
+    ConfigureRetries,
+    ConfigurePool,
+    ConfigureAutoSave,
+    ConfigureAutoFormat,
+    ManageHeaderPresets,
     FocusUrlBar,
     ClearHistory,
+    ToggleHistoryStar,
+    ExportHistoryJson,
+    ExportHistoryCsv,
+    ConfigureHistoryBodyLimit,
+    SaveRequest,
     SaveToCollection,
     ImportCollection,
+    ImportCurl,
+    CopyAsCurl,
+    GenerateCode,
     SetMethodGet,
     SetMethodPost,
     SetMethodPut,
@@ -31,6 +58,7 @@ pub enum CommandId {
     CloseTab,
     CloseAllTabs,
     CloseOtherTabs,
+    ReopenClosedTab,
     NextTab,
     PreviousTab,
     GoToTab1,
@@ -46,6 +74,7 @@ pub enum CommandId {
     SwitchToParamsTab,
     SwitchToHeadersTab,
     SwitchToAuthTab,
+    SwitchToTestsTab,
     SwitchToResponseBody,
     SwitchToResponseHeaders,
 }
@@ -76,6 +105,7 @@ impl Command {
             CommandId::CloseTab
             | CommandId::CloseAllTabs
             | CommandId::CloseOtherTabs
+            | CommandId::ReopenClosedTab
             | CommandId::NextTab
             | CommandId::PreviousTab
             | CommandId::GoToTab1
@@ -86,10 +116,18 @@ impl Command {
             | CommandId::GoToTab6
             | CommandId::GoToTab7
             | CommandId::GoToTab8
-            | CommandId::GoToLastTab => "tabs",
+            | CommandId::GoToLastTab
+            | CommandId::ToggleFocusUrlOnNewTab => "tabs",
             CommandId::ToggleSidebar | CommandId::ToggleRequestResponseLayout => "view",
-            CommandId::ClearHistory => "history",
-            CommandId::ImportCollection | CommandId::SaveToCollection => "collections",
+            CommandId::ToggleFormatOnPaste | CommandId::FormatSelectionAsJson => "editor",
+            CommandId::ClearHistory
+            | CommandId::ToggleHistoryStar
+            | CommandId::ExportHistoryJson
+            | CommandId::ExportHistoryCsv
+            | CommandId::ConfigureHistoryBodyLimit => "history",
+            CommandId::ImportCollection | CommandId::SaveRequest | CommandId::SaveToCollection => {
+                "collections"
+            }
             CommandId::SwitchToResponseBody | CommandId::SwitchToResponseHeaders => "response",
             _ => "request",
         }
@@ -104,6 +142,13 @@ impl Command {
 pub fn default_commands() -> Vec<Command> {
     vec![
         Command::new(CommandId::SendRequest, "Send Request", IconName::Send).with_shortcut("⌘↵"),
+        Command::new(CommandId::SendAndSave, "Send and Save", IconName::Send).with_shortcut("⌘⇧↵"),
+        Command::new(CommandId::PreviewRequest, "Preview Request", IconName::Eye),
+        Command::new(
+            CommandId::ChangeHost,
+            "Change Request Host…",
+            IconName::Link,
+        ),
         Command::new(CommandId::CancelRequest, "Cancel Request", IconName::Ban).with_shortcut("⌘."),
         Command::new(CommandId::NewRequest, "New Request", IconName::CopyPlus).with_shortcut("⌘N"),
         Command::new(
@@ -112,6 +157,11 @@ pub fn default_commands() -> Vec<Command> {
             IconName::CopyPlus,
         )
         .with_shortcut("⌘D"),
+        Command::new(
+            CommandId::EditAndResend,
+            "Edit and Resend Last Request",
+            IconName::FilePen,
+        ),
         Command::new(CommandId::CloseTab, "Close Tab", IconName::Close).with_shortcut("⌘W"),
         Command::new(CommandId::CloseAllTabs, "Close All Tabs", IconName::Close)
             .with_shortcut("⌘⇧W"),
@@ -121,6 +171,12 @@ pub fn default_commands() -> Vec<Command> {
             IconName::Close,
         )
         .with_shortcut("⌘⌥W"),
+        Command::new(
+            CommandId::ReopenClosedTab,
+            "Reopen Closed Tab",
+            IconName::History,
+        )
+        .with_shortcut("⌘⇧T"),
         Command::new(CommandId::NextTab, "Next Tab", IconName::ChevronDown).with_shortcut("⌃⇥"),
         Command::new(CommandId::PreviousTab, "Previous Tab", IconName::ChevronUp)
             .with_shortcut("⌃⇧⇥"),
@@ -149,7 +205,75 @@ pub fn default_commands() -> Vec<Command> {
             "Toggle Request/Response Layout",
             IconName::LayoutSplit,
         ),
+        Command::new(
+            CommandId::ToggleFormatOnPaste,
+            "Toggle Format Body on Paste",
+            IconName::Sparkles,
+        ),
+        Command::new(
+            CommandId::ToggleFollowRedirects,
+            "Toggle Follow Redirects",
+            IconName::Replace,
+        ),
+        Command::new(CommandId::ToggleDryRun, "Toggle Dry Run", IconName::Eye),
+        Command::new(
+            CommandId::ToggleDefaultScheme,
+            "Toggle Default Scheme (HTTPS/HTTP)",
+            IconName::Link,
+        ),
+        Command::new(
+            CommandId::ToggleFocusUrlOnNewTab,
+            "Toggle Focus URL Bar on New Tab",
+            IconName::CopyPlus,
+        ),
+        Command::new(
+            CommandId::ConfigureProxy,
+            "Configure Proxy…",
+            IconName::Link,
+        ),
+        Command::new(
+            CommandId::ConfigureEnvironmentProxy,
+            "Configure Proxy for Active Environment",
+            IconName::Variable,
+        ),
+        Command::new(
+            CommandId::ConfigureClientCertificate,
+            "Configure Client Certificate for Active Environment",
+            IconName::Lock,
+        ),
+        Command::new(
+            CommandId::ToggleInsecureTls,
+            "Toggle Certificate Verification for Active Environment",
+            IconName::Unlock,
+        ),
+        Command::new(
+            CommandId::ConfigureRetries,
+            "Configure Retries…",
+            IconName::Repeat,
+        ),
+        Command::new(
+            CommandId::ConfigurePool,
+            "Configure Connection Pool…",
+            IconName::Link,
+        ),
+        Command::new(
+            CommandId::ConfigureAutoSave,
+            "Auto-Save Responses to File…",
+            IconName::FileDown,
+        ),
+        Command::new(
+            CommandId::ConfigureAutoFormat,
+            "Auto-Format Size Limit…",
+            IconName::FileCode,
+        ),
+        Command::new(
+            CommandId::ManageHeaderPresets,
+            "Manage Header Presets…",
+            IconName::CopyPlus,
+        ),
         Command::new(CommandId::FocusUrlBar, "Focus URL Bar", IconName::Link).with_shortcut("⌘L"),
+        Command::new(CommandId::SaveRequest, "Save Request", IconName::FilePlus)
+            .with_shortcut("⌘S"),
         Command::new(
             CommandId::SaveToCollection,
             "Save to Collection",
@@ -157,9 +281,22 @@ pub fn default_commands() -> Vec<Command> {
         ),
         Command::new(
             CommandId::ImportCollection,
-            "Import Postman Data",
+            "Import Postman Data or OpenAPI Spec",
             IconName::FileUp,
         ),
+        Command::new(
+            CommandId::ImportCurl,
+            "Import curl from Clipboard",
+            IconName::FileUp,
+        ),
+        Command::new(CommandId::CopyAsCurl, "Copy as curl", IconName::Copy),
+        Command::new(CommandId::GenerateCode, "Generate Code", IconName::FileCode),
+        Command::new(
+            CommandId::FormatSelectionAsJson,
+            "Format Selection as JSON",
+            IconName::Sparkles,
+        )
+        .with_shortcut("⌘⇧J"),
         Command::new(
             CommandId::SwitchToBodyTab,
             "Switch to Body Tab",
@@ -184,6 +321,11 @@ pub fn default_commands() -> Vec<Command> {
             IconName::CircleDot,
         )
         .with_shortcut("⌘⇧A"),
+        Command::new(
+            CommandId::SwitchToTestsTab,
+            "Switch to Tests Tab",
+            IconName::CircleDot,
+        ),
         Command::new(
             CommandId::SwitchToResponseBody,
             "Switch to Response Body",
@@ -198,6 +340,27 @@ pub fn default_commands() -> Vec<Command> {
         .with_shortcut("⌘⌥H"),
         Command::new(CommandId::ClearHistory, "Clear History", IconName::Trash)
             .with_shortcut("⌘⇧⌫"),
+        Command::new(
+            CommandId::ToggleHistoryStar,
+            "Star Last Sent Request",
+            IconName::Star,
+        )
+        .with_shortcut("⌘⌥S"),
+        Command::new(
+            CommandId::ExportHistoryJson,
+            "Export History as JSON",
+            IconName::FileDown,
+        ),
+        Command::new(
+            CommandId::ExportHistoryCsv,
+            "Export History as CSV",
+            IconName::FileDown,
+        ),
+        Command::new(
+            CommandId::ConfigureHistoryBodyLimit,
+            "History Body Size Limit…",
+            IconName::History,
+        ),
         Command::new(
             CommandId::SetMethodGet,
             "Set Method: GET",
@@ -257,6 +420,8 @@ pub struct CommandPaletteView {
     selected_index: usize,
     focus_handle: FocusHandle,
     app_focus_handle: FocusHandle,
+    /// Whatever had focus when the palette opened, handed back when it closes
+    focus_before_open: Option<FocusHandle>,
     input_state: Option<Entity<InputState>>,
     query: String,
     scroll_handle: ScrollHandle,
@@ -284,6 +449,7 @@ impl CommandPaletteView {
             selected_index: 0,
             focus_handle: cx.focus_handle(),
             app_focus_handle,
+            focus_before_open: None,
             input_state: None,
             query: String::new(),
             scroll_handle: ScrollHandle::new(),
@@ -320,6 +486,7 @@ impl CommandPaletteView {
     pub fn toggle(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.is_open = !self.is_open;
         if self.is_open {
+            self.focus_before_open = window.focused(cx);
             self.query.clear();
             self.selected_index = 0;
             self.refresh_filtered_indices();
@@ -349,10 +516,21 @@ impl CommandPaletteView {
         cx.notify();
     }
 
+    /// Focus what was focused before the palette opened, or the app when nothing was.
+    ///
+    /// Runs before any command that opens a dialog gets its window, so the dialog still
+    /// takes focus after this.
+    pub fn restore_focus(&self, window: &mut Window, cx: &mut App) {
+        self.focus_before_open
+            .as_ref()
+            .unwrap_or(&self.app_focus_handle)
+            .focus(window, cx);
+    }
+
     fn close_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.close(cx);
         window.close_dialog(cx);
-        self.app_focus_handle.focus(window, cx);
+        self.restore_focus(window, cx);
     }
 
     fn refresh_filtered_indices(&mut self) {
@@ -628,6 +806,11 @@ mod tests {
                 .iter()
                 .any(|command| matches!(command.id, CommandId::SaveToCollection))
         );
+        assert!(
+            commands
+                .iter()
+                .any(|command| matches!(command.id, CommandId::SaveRequest))
+        );
         assert!(
             commands
                 .iter()
@@ -1,15 +1,16 @@
 use gpui::prelude::*;
 use gpui::{
-    App, Entity, FocusHandle, Focusable, IntoElement, PathPromptOptions, Render, ScrollHandle,
-    SharedString, Styled, Window, div, px,
+    AnyElement, AnyWindowHandle, App, Entity, FocusHandle, Focusable, IntoElement,
+    PathPromptOptions, Render, ScrollHandle, SharedString, Styled, Window, div, px,
 };
 use gpui_component::Root;
 use gpui_component::Selectable;
 use gpui_component::Sizable;
 use gpui_component::WindowExt;
 use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::checkbox::Checkbox;
 use gpui_component::dialog::DialogFooter;
-use gpui_component::input::{Input, InputState};
+use gpui_component::input::{Input, InputEvent, InputState};
 use gpui_component::menu::{DropdownMenu, PopupMenuItem};
 use gpui_component::notification::NotificationType;
 use gpui_component::resizable::{ResizableState, h_resizable, resizable_panel, v_resizable};
@@ -17,31 +18,49 @@ use gpui_component::scroll::ScrollableElement;
 use gpui_component::select::{Select, SelectItem, SelectState};
 use gpui_component::v_flex;
 use gpui_component::{ActiveTheme, Icon};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use uuid::Uuid;
 
 use crate::actions::*;
+use crate::cli::LaunchContext;
+use crate::codegen::{CodeTarget, generate_code};
 use crate::completion::{CompletionContext, CompletionEngine, configure_completion};
+use crate::components::audio_player::AudioSettings;
 use crate::components::{
-    AppSidebar, BodyType, EnvironmentPanel, HistoryFilter, HistoryGroupBy, MethodDropdownState,
-    ProtocolSelector, ProtocolType, SidebarTab, TabBar, TabIcon, TabInfo, UrlBar,
+    AppSidebar, BodyType, EnvironmentPanel, HeaderMergeMode, HeaderPresets, HistoryFilter,
+    HistoryGroupBy, MethodDropdownState, ProtocolSelector, ProtocolType, SidebarTab, TabBar,
+    TabIcon, TabInfo, UrlBar,
 };
 use crate::entities::{
-    CollectionDestination, CollectionDestinationEntry, CollectionsEntity, EnvironmentColor,
-    EnvironmentScope, EnvironmentVariable, EnvironmentsEntity, HistoryEntity, HistoryGrouping,
-    HistoryRow, HttpMethod, PreferredLayout, RequestBody, RequestData, RequestEntity, RequestEvent,
-    ResponseData, ResponseEntity, SidebarLoadState, UiPreferences, UiPreferencesStore,
-    WorkspacesEntity,
+    AuthType, CollectionDestination, CollectionDestinationEntry, CollectionsEntity,
+    DEFAULT_HISTORY_BODY_MAX_KB, DisabledParam, EnvironmentColor, EnvironmentScope,
+    EnvironmentVariable, EnvironmentsEntity, ErrorDetails, Header, HistoryEntity,
+    HistoryExportFormat, HistoryGrouping, HistoryRow, HttpMethod, MAX_AUTO_FORMAT_MAX_KB,
+    MAX_HISTORY_BODY_MAX_KB, MAX_REQUEST_VERSIONS, PreferredLayout, RequestBody, RequestData,
+    RequestEntity, RequestEvent, ResponseAutoSave, ResponseData, ResponseEntity, RetryStatus,
+    SidebarLoadState, UiPreferences, UiPreferencesStore, WebSocketEntity, WorkspacesEntity,
+    format_header_presets, parse_header_presets,
+};
+use crate::http::{
+    AwsSigV4, CertificateSummary, DEFAULT_REQUEST_TIMEOUT, HttpClient, InFlightRequest, PoolConfig,
+    ProxyConfig, RedirectPolicy, RequestOptions, ResponseUpdate, RetryPolicy, StoredCookie,
+    TlsConfig, UrlScheme, WsClient, replace_origin, url_origin,
 };
-use crate::http::{HttpClient, InFlightRequest};
 use crate::icons::IconName;
 use crate::importers::{ImportRegistry, ImportWarning, ImportedPayload};
-use crate::utils::{close_dialog, open_dialog};
+use crate::utils::{
+    JsonFormatSettings, PreRequestContext, PreRequestOutput, close_dialog, editor_selection,
+    format_size, open_dialog, pretty_json, replace_editor_range, run_extractions,
+    run_pre_request_script,
+};
 use crate::views::environment_view::EnvironmentView;
-use crate::views::request_view::RequestView;
-use crate::views::response_view::ResponseView;
+use crate::views::request_view::{BodyEditorSettings, RequestTab, RequestView, RequestViewEvent};
+use crate::views::response_view::{ResponseTab, ResponseView};
+use crate::views::websocket_view::{WebSocketLogView, WebSocketView};
 use crate::views::{CommandId, CommandPaletteEvent, CommandPaletteView};
 
 #[derive(Clone)]
@@ -75,7 +94,13 @@ pub enum TabContent {
         request_view: Entity<RequestView>,
         response_view: Entity<ResponseView>,
         in_flight_request: Option<InFlightRequest>,
+        /// The last send with its variables resolved. Kept in memory only, so secrets the
+        /// templates hide never reach history or collections.
+        sent_request: Option<RequestData>,
         request_generation: RequestGeneration,
+        protocol: ProtocolType,
+        /// Created the first time the tab switches to WebSocket and kept while it is open.
+        websocket: Option<WebSocketTab>,
     },
     Environment {
         environment_id: Uuid,
@@ -83,12 +108,23 @@ pub enum TabContent {
     },
 }
 
+/// A tab's WebSocket session and the panels that replace the request/response views.
+pub struct WebSocketTab {
+    pub socket: Entity<WebSocketEntity>,
+    pub view: Entity<WebSocketView>,
+    pub log_view: Entity<WebSocketLogView>,
+}
+
 pub struct TabState {
     pub id: TabId,
     pub name: String,
     pub is_custom_name: bool,
     pub content: TabContent,
     pub collection_id: Option<Uuid>,
+    /// Saved collection request this tab was opened from or last saved as.
+    pub collection_node_id: Option<Uuid>,
+    /// History entry of the tab's last send, or the one it was loaded from.
+    pub history_entry_id: Option<Uuid>,
 }
 
 impl TabState {
@@ -187,6 +223,23 @@ impl SelectItem for DestinationOption {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CodeTargetOption {
+    target: CodeTarget,
+}
+
+impl SelectItem for CodeTargetOption {
+    type Value = CodeTargetOption;
+
+    fn title(&self) -> SharedString {
+        self.target.label().into()
+    }
+
+    fn value(&self) -> &Self::Value {
+        self
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct EnvironmentScopeOption {
     scope: EnvironmentScope,
@@ -222,6 +275,112 @@ struct ImportSummary {
     warnings: Vec<ImportWarning>,
 }
 
+/// A save queued by "Send and Save", made once that tab's request succeeds.
+struct SaveAfterSend {
+    tab_id: TabId,
+    /// Where to add the request and its name; `None` updates the request the tab is linked to.
+    destination: Option<(CollectionDestination, String)>,
+    /// Also keep the response as an example on the saved request.
+    save_response: bool,
+    /// Window the outcome is reported in.
+    window: AnyWindowHandle,
+}
+
+/// A request ready to send: the editor's templates, kept for history, next to their
+/// resolved values.
+struct OutgoingRequest {
+    /// URL as typed, params included and `{{variables}}` still in place.
+    url: String,
+    method: HttpMethod,
+    template_headers: Vec<Header>,
+    template_body: RequestBody,
+    preserve_header_order: bool,
+    inherit_auth: bool,
+    assertions: String,
+    pre_request_script: String,
+    extractions: String,
+    disabled_params: Vec<DisabledParam>,
+    resolved_url: String,
+    resolved_headers: Vec<Header>,
+    resolved_body: RequestBody,
+    aws_sigv4: Option<AwsSigV4>,
+}
+
+/// Closed tabs kept for reopening; older ones are forgotten.
+const CLOSED_TAB_LIMIT: usize = 20;
+/// Hosts offered again by the change-host dialog; older ones are forgotten.
+const RECENT_ORIGIN_LIMIT: usize = 5;
+
+/// The request and response panel tabs a REST tab was showing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SubTabs {
+    request: RequestTab,
+    response: ResponseTab,
+}
+
+impl SubTabs {
+    fn of(content: &TabContent, cx: &App) -> Option<Self> {
+        match content {
+            TabContent::Request {
+                request_view,
+                response_view,
+                ..
+            } => Some(Self {
+                request: request_view.read(cx).active_tab(),
+                response: response_view.read(cx).active_tab(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn restore(self, content: &TabContent, cx: &mut App) {
+        if let TabContent::Request {
+            request_view,
+            response_view,
+            ..
+        } = content
+        {
+            request_view.update(cx, |view, cx| view.set_tab(self.request, cx));
+            response_view.update(cx, |view, cx| view.set_tab(self.response, cx));
+        }
+    }
+}
+
+/// What a closed REST tab needs to come back as it was.
+struct ClosedTab {
+    name: String,
+    is_custom_name: bool,
+    request: RequestData,
+    sub_tabs: SubTabs,
+    collection_id: Option<Uuid>,
+    collection_node_id: Option<Uuid>,
+    history_entry_id: Option<Uuid>,
+}
+
+/// Push `tab` onto `closed_tabs`, dropping the oldest beyond [`CLOSED_TAB_LIMIT`].
+fn push_closed_tab(closed_tabs: &mut Vec<ClosedTab>, tab: ClosedTab) {
+    closed_tabs.push(tab);
+    if closed_tabs.len() > CLOSED_TAB_LIMIT {
+        closed_tabs.remove(0);
+    }
+}
+
+/// Read and parse `tls`'s files on a blocking thread, yielding the client certificate's
+/// summary or the error naming the bad file.
+fn load_tls_in_background(
+    tls: TlsConfig,
+) -> tokio::sync::oneshot::Receiver<Result<Option<CertificateSummary>, String>> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+        let result = tls
+            .load()
+            .map(|loaded| loaded.certificate)
+            .map_err(|error| error.to_string());
+        let _ = tx.send(result);
+    });
+    rx
+}
+
 /// Main application view
 pub struct MainView {
     // Tabs
@@ -229,6 +388,7 @@ pub struct MainView {
     active_tab_index: usize,
     next_tab_id: u64,
     tab_scroll_handle: ScrollHandle,
+    closed_tabs: Vec<ClosedTab>,
 
     // Command palette (shared across tabs)
     command_palette: Entity<CommandPaletteView>,
@@ -241,6 +401,7 @@ pub struct MainView {
     environment_panel: Entity<EnvironmentPanel>,
     completion_engine: CompletionEngine,
     http_client: HttpClient,
+    ws_client: WsClient,
 
     // UI state
     sidebar_visible: bool,
@@ -256,6 +417,11 @@ pub struct MainView {
     request_response_layout: RequestResponseLayout,
     focus_handle: FocusHandle,
     pending_window_command: Option<CommandId>,
+    /// Inline editor of the active request's name, and the tab it renames
+    request_name_input: Option<(TabId, Entity<InputState>)>,
+    save_after_send: Option<SaveAfterSend>,
+    /// Hosts the active request was last pointed at, newest first
+    recent_origins: Vec<String>,
     ui_preferences: UiPreferences,
     ui_preferences_store: UiPreferencesStore,
     stacked_split_state: Entity<ResizableState>,
@@ -263,7 +429,8 @@ pub struct MainView {
 }
 
 impl MainView {
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    /// `launch` names the collection and environment to open once they have loaded.
+    pub fn new(launch: LaunchContext, cx: &mut Context<Self>) -> Self {
         // Create initial tab
         let request = cx.new(|_| RequestEntity::new());
         let response = cx.new(|_| ResponseEntity::new());
@@ -281,6 +448,7 @@ impl MainView {
             RequestView::new(request.clone(), BodyType::None, cx)
                 .with_completion_engine(completion_engine_for_request)
         });
+        Self::subscribe_request_view_changes(&request_view, cx);
         let response_view = cx.new(|cx| ResponseView::new(response.clone(), cx));
 
         let initial_tab = TabState {
@@ -295,9 +463,14 @@ impl MainView {
                 request_view,
                 response_view,
                 in_flight_request: None,
+                sent_request: None,
                 request_generation: RequestGeneration::default(),
+                protocol: ProtocolType::Rest,
+                websocket: None,
             },
             collection_id: None,
+            collection_node_id: None,
+            history_entry_id: None,
         };
         let environment_panel =
             cx.new(|cx| EnvironmentPanel::new(environments.clone(), collections.clone(), cx));
@@ -317,7 +490,8 @@ impl MainView {
 
         let environments_load = EnvironmentsEntity::spawn_storage_load();
         let environments_for_load = environments.clone();
-        cx.spawn(async move |_view, cx| {
+        let launch_environment = launch.environment;
+        cx.spawn(async move |view, cx| {
             let result = environments_load
                 .await
                 .unwrap_or_else(|_| Err("Environment loader stopped unexpectedly".to_string()));
@@ -325,13 +499,18 @@ impl MainView {
                 environments_for_load.update(app, |environments, cx| {
                     environments.apply_storage_load(result, cx);
                 });
+                if let Some(name) = launch_environment {
+                    view.update(app, |view, cx| view.open_launch_environment(&name, cx))
+                        .ok();
+                }
             })
         })
         .detach();
 
         let collections_load = CollectionsEntity::spawn_storage_load();
         let collections_for_load = collections.clone();
-        cx.spawn(async move |_view, cx| {
+        let launch_collection = launch.collection;
+        cx.spawn(async move |view, cx| {
             let result = collections_load
                 .await
                 .unwrap_or_else(|_| Err("Collections loader stopped unexpectedly".to_string()));
@@ -339,10 +518,50 @@ impl MainView {
                 collections_for_load.update(app, |collections, cx| {
                     collections.apply_storage_load(result, cx);
                 });
+                if let Some(name) = launch_collection {
+                    view.update(app, |view, cx| view.open_launch_collection(&name, cx))
+                        .ok();
+                }
             })
         })
         .detach();
         let (ui_preferences, ui_preferences_store) = UiPreferencesStore::load();
+        cx.set_global(BodyEditorSettings {
+            format_on_paste: ui_preferences.format_on_paste,
+        });
+        cx.set_global(JsonFormatSettings {
+            sort_keys: ui_preferences.sort_json_keys,
+            auto_format_max_bytes: (ui_preferences.auto_format_max_kb * 1024) as usize,
+        });
+        cx.observe_global::<JsonFormatSettings>(|this, cx| {
+            let settings = *cx.global::<JsonFormatSettings>();
+            this.ui_preferences.sort_json_keys = settings.sort_keys;
+            this.ui_preferences.auto_format_max_kb = settings.auto_format_max_bytes as u64 / 1024;
+            this.persist_ui_preferences();
+        })
+        .detach();
+        cx.set_global(HeaderPresets(ui_preferences.header_presets.clone()));
+        cx.observe_global::<HeaderPresets>(|this, cx| {
+            this.ui_preferences.header_presets = cx.global::<HeaderPresets>().0.clone();
+            this.persist_ui_preferences();
+        })
+        .detach();
+        cx.set_global(AudioSettings {
+            volume: ui_preferences.audio_volume,
+            muted: ui_preferences.audio_muted,
+            speed: ui_preferences.audio_speed,
+        });
+        cx.observe_global::<AudioSettings>(|this, cx| {
+            let settings = *cx.global::<AudioSettings>();
+            this.ui_preferences.audio_volume = settings.volume;
+            this.ui_preferences.audio_muted = settings.muted;
+            this.ui_preferences.audio_speed = settings.speed;
+            this.persist_ui_preferences();
+        })
+        .detach();
+        history.update(cx, |history, _| {
+            history.max_response_body_bytes = (ui_preferences.history_body_max_kb * 1024) as usize;
+        });
         let stacked_split_state = cx.new(|_| ResizableState::default());
         let side_by_side_split_state = cx.new(|_| ResizableState::default());
 
@@ -364,12 +583,18 @@ impl MainView {
         cx.subscribe(&workspaces, |_this, _, _event, cx| cx.notify())
             .detach();
 
-        let http_client = HttpClient::new().expect("Failed to create HTTP client");
+        let http_client = HttpClient::with_config(
+            DEFAULT_REQUEST_TIMEOUT,
+            ui_preferences.redirect_policy,
+            ui_preferences.pool,
+        )
+        .expect("Failed to create HTTP client");
 
         Self {
             tabs: vec![initial_tab],
             active_tab_index: 0,
             next_tab_id: 1,
+            closed_tabs: Vec::new(),
             tab_scroll_handle: ScrollHandle::new(),
             command_palette,
             history,
@@ -379,6 +604,7 @@ impl MainView {
             environment_panel,
             completion_engine,
             http_client,
+            ws_client: WsClient::new(),
             sidebar_visible: ui_preferences.sidebar_visible,
             sidebar_width: ui_preferences.sidebar_width,
             sidebar_tab: SidebarTab::History,
@@ -395,6 +621,9 @@ impl MainView {
             },
             focus_handle,
             pending_window_command: None,
+            request_name_input: None,
+            save_after_send: None,
+            recent_origins: Vec::new(),
             ui_preferences,
             ui_preferences_store,
             stacked_split_state,
@@ -410,7 +639,12 @@ impl MainView {
         cx.subscribe(request, |_this, _request, event: &RequestEvent, cx| {
             if matches!(
                 event,
-                RequestEvent::UrlChanged | RequestEvent::MethodChanged
+                RequestEvent::UrlChanged
+                    | RequestEvent::MethodChanged
+                    | RequestEvent::AutoSaveChanged
+                    | RequestEvent::UploadProgressed
+                    | RequestEvent::Sending
+                    | RequestEvent::Completed
             ) {
                 cx.notify();
             }
@@ -418,11 +652,26 @@ impl MainView {
         .detach();
     }
 
+    /// Redraw the URL bar warnings when the body type switches.
+    fn subscribe_request_view_changes(request_view: &Entity<RequestView>, cx: &mut Context<Self>) {
+        cx.subscribe(
+            request_view,
+            |_this, _view, _event: &RequestViewEvent, cx| {
+                cx.notify();
+            },
+        )
+        .detach();
+    }
+
     /// Ensure URL input is initialized for a tab
     fn ensure_url_input(&mut self, tab_index: usize, window: &mut Window, cx: &mut Context<Self>) {
         let completion_engine = self.completion_engine.clone();
         if let Some(tab) = self.tabs.get_mut(tab_index)
-            && let TabContent::Request { url_input, .. } = &mut tab.content
+            && let TabContent::Request {
+                url_input,
+                request_view,
+                ..
+            } = &mut tab.content
             && url_input.is_none()
         {
             let input = cx.new(|cx| {
@@ -434,6 +683,9 @@ impl MainView {
             });
             let tab_id = tab.id;
             Self::subscribe_url_input(&input, tab_id, window, cx);
+            request_view.update(cx, |view, cx| {
+                view.attach_url_input(input.clone(), window, cx);
+            });
             *url_input = Some(input);
         }
     }
@@ -630,6 +882,102 @@ impl MainView {
         cx.notify();
     }
 
+    fn set_tab_history_entry(&mut self, tab_id: TabId, entry_id: Uuid) {
+        if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == tab_id) {
+            tab.history_entry_id = Some(entry_id);
+        }
+    }
+
+    /// Star or unstar the history entry the active tab last sent or was loaded from.
+    pub fn toggle_active_history_star(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let entry_id = self
+            .active_tab()
+            .and_then(|tab| tab.history_entry_id)
+            .filter(|id| self.history.read(cx).get_entry(*id).is_some());
+        let Some(entry_id) = entry_id else {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    "Send the request first to star it in history",
+                ),
+                cx,
+            );
+            return;
+        };
+
+        let starred = self.history.update(cx, |history, cx| {
+            history.toggle_star(entry_id, cx);
+            history
+                .get_entry(entry_id)
+                .is_some_and(|entry| entry.starred)
+        });
+        let message = if starred {
+            "Starred in history"
+        } else {
+            "Removed star from history"
+        };
+        window.push_notification((NotificationType::Success, message), cx);
+    }
+
+    /// Pretty-print the JSON selected in the body editor in place, or copy the selected
+    /// response text formatted. The focused editor wins; otherwise the first with a selection.
+    pub fn format_selection_as_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.active_tab() else {
+            return;
+        };
+        // `true` marks editors whose selection is replaced rather than copied.
+        let mut editors: Vec<(Entity<InputState>, bool)> = Vec::new();
+        if let Some(request_view) = tab.request_view() {
+            editors.extend(
+                request_view
+                    .read(cx)
+                    .json_editors()
+                    .into_iter()
+                    .map(|editor| (editor, true)),
+            );
+        }
+        if let Some(editor) = tab
+            .response_view()
+            .and_then(|view| view.read(cx).text_editor())
+        {
+            editors.push((editor, false));
+        }
+        editors.sort_by_key(|(editor, _)| !editor.read(cx).focus_handle(cx).is_focused(window));
+
+        let selection = editors.into_iter().find_map(|(editor, editable)| {
+            editor_selection(&editor, window, cx)
+                .map(|(range, text)| (editor, editable, range, text))
+        });
+        let Some((editor, editable, range, text)) = selection else {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    "Select JSON in the body or the response first",
+                ),
+                cx,
+            );
+            return;
+        };
+        let Some(formatted) = pretty_json(text.trim()) else {
+            window.push_notification(
+                (NotificationType::Warning, "The selection is not valid JSON"),
+                cx,
+            );
+            return;
+        };
+
+        if editable {
+            replace_editor_range(&editor, range, &formatted, window, cx);
+            window.push_notification((NotificationType::Success, "Selection formatted"), cx);
+        } else {
+            cx.write_to_clipboard(gpui::ClipboardItem::new_string(formatted));
+            window.push_notification(
+                (NotificationType::Success, "Formatted selection copied"),
+                cx,
+            );
+        }
+    }
+
     /// Load a history entry into a new tab
     pub fn load_history_entry(
         &mut self,
@@ -653,91 +1001,17 @@ impl MainView {
             return;
         };
 
-        // Derive body type from the stored request body
-        let body_type = BodyType::from_request_body(&request_data.body);
-
-        // Extract body content for text-based body types
-        let body_content: Option<String> = match &request_data.body {
-            RequestBody::Json(content) | RequestBody::Text(content) => {
-                if content.is_empty() {
-                    None
-                } else {
-                    Some(content.clone())
-                }
-            }
-            RequestBody::None | RequestBody::FormData(_) | RequestBody::MultipartFormData(_) => {
-                None
-            }
-        };
-
-        // Extract form data for FormUrlEncoded
-        let form_data = match &request_data.body {
-            RequestBody::FormData(data) => Some(data.clone()),
-            _ => None,
-        };
-
-        // Extract multipart data for FormData
-        let multipart_data = match &request_data.body {
-            RequestBody::MultipartFormData(fields) => Some(fields.clone()),
-            _ => None,
-        };
-
-        // Now we can use cx freely
-        let request = cx.new(|cx| {
-            let mut req = RequestEntity::new().with_headers(request_data.headers.clone());
-            req.set_url(request_data.url.clone(), cx);
-            req.set_method(request_data.method, cx);
-            req.set_body(request_data.body.clone(), cx);
-            req
-        });
-
-        let response = cx.new(|cx| {
-            let mut resp = ResponseEntity::new();
-            if let Some(resp_data) = response_data {
-                resp.set_response(resp_data, cx);
-            }
-            resp
-        });
-
-        let method_dropdown = cx.new(|_| MethodDropdownState::new(request_data.method));
-        let completion_engine = self.completion_engine.clone();
-        let request_view = cx.new(|cx| {
-            RequestView::new(request.clone(), body_type, cx)
-                .with_completion_engine(completion_engine)
-                .with_initial_body_content(body_content)
-                .with_initial_form_data(form_data)
-                .with_initial_multipart_data(multipart_data)
-        });
-        let response_view = cx.new(|cx| ResponseView::new(response.clone(), cx));
-        let completion_engine = self.completion_engine.clone();
-        let url_input = cx.new(|cx| {
-            configure_completion(
-                InputState::new(window, cx)
-                    .placeholder("Enter request URL...")
-                    .default_value(&request_data.url),
-                Some(&completion_engine),
-                CompletionContext::Url,
-            )
-        });
         let tab_id = TabId(self.next_tab_id);
-        Self::subscribe_url_input(&url_input, tab_id, window, cx);
-        Self::subscribe_request_changes(&request, cx);
-
+        let content =
+            self.build_history_tab_content(tab_id, &request_data, response_data, window, cx);
         let tab = TabState {
             id: tab_id,
             name: tab_name,
             is_custom_name: true,
-            content: TabContent::Request {
-                request: request.clone(),
-                response: response.clone(),
-                url_input: Some(url_input),
-                method_dropdown,
-                request_view,
-                response_view,
-                in_flight_request: None,
-                request_generation: RequestGeneration::default(),
-            },
+            content,
             collection_id: None,
+            collection_node_id: None,
+            history_entry_id: Some(entry_id),
         };
 
         self.tabs.push(tab);
@@ -746,62 +1020,385 @@ impl MainView {
         cx.notify();
     }
 
-    /// Delete a history entry
-    pub fn delete_history_entry(&mut self, entry_id: Uuid, cx: &mut Context<Self>) {
-        self.history.update(cx, |history, cx| {
-            history.remove_entry(entry_id, cx);
-        });
-    }
-
-    /// Toggle star status of a history entry
-    pub fn toggle_history_star(&mut self, entry_id: Uuid, cx: &mut Context<Self>) {
-        self.history.update(cx, |history, cx| {
-            history.toggle_star(entry_id, cx);
-        });
-    }
-
-    /// Clear all history
-    pub fn clear_history(&mut self, cx: &mut Context<Self>) {
-        self.history.update(cx, |history, cx| {
-            history.clear_unstarred(cx);
-        });
-    }
-
-    #[allow(dead_code)]
-    /// Add current request to history
-    pub fn add_to_history(
-        &mut self,
-        request: RequestData,
-        response: Option<ResponseData>,
-        cx: &mut Context<Self>,
-    ) {
-        self.history.update(cx, |history, cx| {
-            history.add_entry(request, response, cx);
-        });
-    }
-
-    /// Load a collection item into a new tab
-    pub fn load_collection_item(
+    /// Load a history entry over the active tab, keeping its id, name and collection link.
+    ///
+    /// Asks before discarding edits, and opens a new tab instead when the active one is not
+    /// a REST request.
+    pub fn load_history_entry_into_active(
         &mut self,
-        collection_id: Uuid,
-        node_id: Uuid,
+        entry_id: Uuid,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        // First extract all data we need, then drop the borrow
-        let item_data = {
-            let collections = self.collections.read(cx);
-            collections
-                .get_request_node(collection_id, node_id)
-                .map(|node| (node.request.clone(), node.display_name()))
-        };
-
-        let Some((request_data, tab_name)) = item_data else {
+        let replaceable = matches!(
+            self.active_tab().map(|tab| &tab.content),
+            Some(TabContent::Request {
+                protocol: ProtocolType::Rest,
+                ..
+            })
+        );
+        if !replaceable {
+            self.load_history_entry(entry_id, window, cx);
             return;
-        };
-
-        // Derive body type from the stored request body
-        let body_type = BodyType::from_request_body(&request_data.body);
+        }
+        if self.history.read(cx).get_entry(entry_id).is_none() {
+            return;
+        }
+        if !self.active_tab_has_edits(cx) {
+            self.replace_active_tab_with_history_entry(entry_id, window, cx);
+            return;
+        }
+
+        let this = cx.entity().clone();
+        let tab_id = self.tabs[self.active_tab_index].id;
+        let tab_name = self.tabs[self.active_tab_index].name.clone();
+        open_dialog(window, cx, move |dialog, _, _| {
+            let this_for_replace = this.clone();
+            dialog
+                .title("Replace Tab Contents")
+                .child(format!(
+                    "“{tab_name}” has changes that are not saved. Replace them with the history entry?"
+                ))
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("replace-tab-confirm")
+                                .label("Replace")
+                                .danger()
+                                .on_click(move |_, window, cx| {
+                                    this_for_replace.update(cx, |view, cx| {
+                                        if view.is_active_tab(
+                                            tab_id,
+                                            "Replacing the tab contents",
+                                            window,
+                                            cx,
+                                        ) {
+                                            view.replace_active_tab_with_history_entry(
+                                                entry_id, window, cx,
+                                            );
+                                        }
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("replace-tab-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
+
+    fn replace_active_tab_with_history_entry(
+        &mut self,
+        entry_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((request_data, response_data)) = self
+            .history
+            .read(cx)
+            .get_entry(entry_id)
+            .map(|entry| (entry.request.clone(), entry.response.clone()))
+        else {
+            return;
+        };
+        let index = self.active_tab_index;
+        let Some(tab_id) = self.tabs.get(index).map(|tab| tab.id) else {
+            return;
+        };
+
+        self.cancel_in_flight_for_tab(index, cx);
+        // The views are rebuilt, so carry over the panel tabs the user had picked.
+        let sub_tabs = SubTabs::of(&self.tabs[index].content, cx);
+        let content =
+            self.build_history_tab_content(tab_id, &request_data, response_data, window, cx);
+        if let Some(sub_tabs) = sub_tabs {
+            sub_tabs.restore(&content, cx);
+        }
+        let tab = &mut self.tabs[index];
+        tab.content = content;
+        tab.history_entry_id = Some(entry_id);
+        cx.notify();
+    }
+
+    /// Offer to merge a history entry's headers into the active request, or replace its
+    /// headers with them.
+    pub fn show_copy_history_headers_dialog(
+        &mut self,
+        entry_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(headers) = self
+            .history
+            .read(cx)
+            .get_entry(entry_id)
+            .map(|entry| entry.request.headers.clone())
+        else {
+            return;
+        };
+        if headers.is_empty() {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    "That request was sent without headers",
+                ),
+                cx,
+            );
+            return;
+        }
+        let Some((tab_id, request_view)) = self.active_tab().and_then(|tab| {
+            tab.request_view()
+                .map(|request_view| (tab.id, request_view.clone()))
+        }) else {
+            window.push_notification(
+                (
+                    NotificationType::Warning,
+                    "Open a request tab to use these headers",
+                ),
+                cx,
+            );
+            return;
+        };
+
+        let this = cx.entity().clone();
+        let headers = Rc::new(headers);
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let muted_foreground = cx.theme().muted_foreground;
+            let apply = |mode: HeaderMergeMode| {
+                let this = this.clone();
+                let request_view = request_view.clone();
+                let headers = headers.clone();
+                move |_: &gpui::ClickEvent, window: &mut Window, cx: &mut App| {
+                    let still_active = this.update(cx, |view, cx| {
+                        view.is_active_tab(tab_id, "Applying the headers", window, cx)
+                    });
+                    if still_active {
+                        request_view.update(cx, |view, cx| {
+                            view.apply_headers(&headers, mode, cx);
+                        });
+                    }
+                    close_dialog(window, cx);
+                }
+            };
+            dialog
+                .title(format!("Use {} Headers", headers.len()))
+                .child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap(px(4.0))
+                        .max_h(px(240.0))
+                        .overflow_y_scrollbar()
+                        .children(headers.iter().map(|header| {
+                            div()
+                                .text_size(px(12.0))
+                                .overflow_hidden()
+                                .text_ellipsis()
+                                .when(!header.enabled, |el| el.text_color(muted_foreground))
+                                .child(format!("{}: {}", header.key, header.value))
+                        })),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("copy-headers-merge")
+                                .label("Merge")
+                                .primary()
+                                .on_click(apply(HeaderMergeMode::Merge)),
+                        )
+                        .child(
+                            Button::new("copy-headers-replace")
+                                .label("Replace")
+                                .on_click(apply(HeaderMergeMode::Replace)),
+                        )
+                        .child(
+                            Button::new("copy-headers-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
+
+    /// List the responses saved as examples of a collection request, to open or delete.
+    pub fn show_request_examples_dialog(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity().clone();
+        let collections = self.collections.clone();
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let muted_foreground = cx.theme().muted_foreground;
+            // Read on every render, so a deleted example drops out of the list at once.
+            let examples: Vec<_> = collections
+                .read(cx)
+                .get_request_node(collection_id, node_id)
+                .map(|node| {
+                    node.examples
+                        .iter()
+                        .map(|example| {
+                            format!(
+                                "{} {} · {} · {} ms",
+                                example.status_code,
+                                example.status_text,
+                                format_size(example.body_size_bytes as u64),
+                                example.duration_ms
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            dialog
+                .title("Saved Examples")
+                .child(
+                    v_flex()
+                        .gap(px(4.0))
+                        .max_h(px(320.0))
+                        .overflow_y_scrollbar()
+                        .when(examples.is_empty(), |el| {
+                            el.child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .text_color(muted_foreground)
+                                    .child("No examples left"),
+                            )
+                        })
+                        .children(examples.into_iter().enumerate().map(|(index, summary)| {
+                            let this_for_open = this.clone();
+                            let collections_for_delete = collections.clone();
+                            div()
+                                .flex()
+                                .flex_row()
+                                .items_center()
+                                .gap(px(8.0))
+                                .child(div().flex_1().text_size(px(12.0)).child(summary))
+                                .child(
+                                    Button::new(("example-open", index))
+                                        .label("Open")
+                                        .ghost()
+                                        .xsmall()
+                                        .on_click(move |_, window, cx| {
+                                            this_for_open.update(cx, |view, cx| {
+                                                view.open_request_example(
+                                                    collection_id,
+                                                    node_id,
+                                                    index,
+                                                    window,
+                                                    cx,
+                                                );
+                                            });
+                                            close_dialog(window, cx);
+                                        }),
+                                )
+                                .child(
+                                    Button::new(("example-delete", index))
+                                        .icon(Icon::new(IconName::Trash).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip("Delete this example")
+                                        .on_click(move |_, window, cx| {
+                                            collections_for_delete.update(cx, |collections, cx| {
+                                                collections.delete_request_example(
+                                                    collection_id,
+                                                    node_id,
+                                                    index,
+                                                    cx,
+                                                );
+                                            });
+                                            window.refresh();
+                                        }),
+                                )
+                        })),
+                )
+                .footer(
+                    DialogFooter::new().child(
+                        Button::new("examples-close")
+                            .label("Close")
+                            .on_click(|_, window, cx| close_dialog(window, cx)),
+                    ),
+                )
+        });
+    }
+
+    /// Show example `index` of a collection request in the response panel of the request's
+    /// tab, opening the request first when no tab has it.
+    fn open_request_example(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(example) = self
+            .collections
+            .read(cx)
+            .get_request_node(collection_id, node_id)
+            .and_then(|node| node.examples.get(index).cloned())
+        else {
+            return;
+        };
+        let open_tab = self.tabs.iter().position(|tab| {
+            tab.collection_id == Some(collection_id) && tab.collection_node_id == Some(node_id)
+        });
+        match open_tab {
+            Some(index) => self.switch_tab(index, cx),
+            None => self.load_collection_item(collection_id, node_id, window, cx),
+        }
+        if let Some(TabContent::Request { response, .. }) =
+            self.active_tab().map(|tab| &tab.content)
+        {
+            response
+                .clone()
+                .update(cx, |response, cx| response.set_success(example, cx));
+        }
+    }
+
+    /// Whether the active tab differs from what it was opened from: its saved request, its
+    /// history entry, or a blank request.
+    fn active_tab_has_edits(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(current) = self.build_request_snapshot_for_tab(self.active_tab_index, cx) else {
+            return false;
+        };
+        let tab = &self.tabs[self.active_tab_index];
+        let saved = match (tab.collection_id, tab.collection_node_id) {
+            (Some(collection_id), Some(node_id)) => self
+                .collections
+                .read(cx)
+                .get_request_node(collection_id, node_id)
+                .map(|node| node.request.clone()),
+            _ => None,
+        };
+        let origin = saved
+            .or_else(|| {
+                tab.history_entry_id.and_then(|entry_id| {
+                    self.history
+                        .read(cx)
+                        .get_entry(entry_id)
+                        .map(|entry| entry.request.clone())
+                })
+            })
+            .unwrap_or_default();
+        !current.same_content(&origin)
+    }
+
+    /// Entities for a REST tab showing `request_data` and, if recorded, its response.
+    fn build_history_tab_content(
+        &mut self,
+        tab_id: TabId,
+        request_data: &RequestData,
+        response_data: Option<ResponseData>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> TabContent {
+        // Derive body type from the stored request body
+        let body_type = BodyType::from_request_body(&request_data.body);
 
         // Extract body content for text-based body types
         let body_content: Option<String> = match &request_data.body {
@@ -812,9 +1409,11 @@ impl MainView {
                     Some(content.clone())
                 }
             }
-            RequestBody::None | RequestBody::FormData(_) | RequestBody::MultipartFormData(_) => {
-                None
-            }
+            RequestBody::None
+            | RequestBody::FormData(_)
+            | RequestBody::MultipartFormData(_)
+            | RequestBody::GraphQl { .. }
+            | RequestBody::Binary(_) => None,
         };
 
         // Extract form data for FormUrlEncoded
@@ -829,16 +1428,31 @@ impl MainView {
             _ => None,
         };
 
-        // Now we can use cx freely
         let request = cx.new(|cx| {
-            let mut req = RequestEntity::new().with_headers(request_data.headers.clone());
+            let mut req = RequestEntity::new()
+                .with_headers(request_data.headers.clone())
+                .with_preserve_header_order(request_data.preserve_header_order)
+                .with_inherit_auth(request_data.inherit_auth)
+                .with_assertions(request_data.assertions.clone())
+                .with_pre_request_script(request_data.pre_request_script.clone())
+                .with_extractions(request_data.extractions.clone())
+                .with_subprotocols(request_data.subprotocols.clone())
+                .with_disabled_params(request_data.disabled_params.clone())
+                .with_auto_save(request_data.auto_save.clone());
             req.set_url(request_data.url.clone(), cx);
             req.set_method(request_data.method, cx);
             req.set_body(request_data.body.clone(), cx);
             req
         });
 
-        let response = cx.new(|_| ResponseEntity::new());
+        let response = cx.new(|cx| {
+            let mut resp = ResponseEntity::new();
+            if let Some(resp_data) = response_data {
+                resp.set_response(resp_data, cx);
+            }
+            resp
+        });
+
         let method_dropdown = cx.new(|_| MethodDropdownState::new(request_data.method));
         let completion_engine = self.completion_engine.clone();
         let request_view = cx.new(|cx| {
@@ -859,348 +1473,506 @@ impl MainView {
                 CompletionContext::Url,
             )
         });
-        let tab_id = TabId(self.next_tab_id);
         Self::subscribe_url_input(&url_input, tab_id, window, cx);
         Self::subscribe_request_changes(&request, cx);
+        Self::subscribe_request_view_changes(&request_view, cx);
 
-        let tab = TabState {
-            id: tab_id,
-            name: tab_name,
-            is_custom_name: true,
-            content: TabContent::Request {
-                request: request.clone(),
-                response: response.clone(),
-                url_input: Some(url_input),
-                method_dropdown,
-                request_view,
-                response_view,
-                in_flight_request: None,
-                request_generation: RequestGeneration::default(),
-            },
-            collection_id: Some(collection_id),
-        };
+        TabContent::Request {
+            request,
+            response,
+            url_input: Some(url_input),
+            method_dropdown,
+            request_view,
+            response_view,
+            in_flight_request: None,
+            sent_request: None,
+            request_generation: RequestGeneration::default(),
+            protocol: ProtocolType::Rest,
+            websocket: None,
+        }
+    }
 
-        self.tabs.push(tab);
-        self.active_tab_index = self.tabs.len() - 1;
-        self.next_tab_id += 1;
-        cx.notify();
+    /// Parse a curl command from the clipboard and open it in a new tab
+    pub fn import_curl_from_clipboard(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            window.push_notification(
+                (NotificationType::Warning, "Clipboard does not contain text"),
+                cx,
+            );
+            return;
+        };
+        match crate::utils::parse_curl(&text) {
+            Ok(parsed) => self.load_parsed_curl(&parsed, window, cx),
+            Err(err) => {
+                window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::from(format!("Could not import curl: {err}")),
+                    ),
+                    cx,
+                );
+            }
+        }
     }
 
-    /// Create a new collection
-    pub fn create_new_collection(&mut self, cx: &mut Context<Self>) {
-        self.collections.update(cx, |collections, cx| {
-            collections.create_collection("New Collection", cx);
-        });
+    /// Copy the active request to the clipboard as a curl command
+    pub fn copy_active_request_as_curl(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(request_data) = self.build_active_request_snapshot(cx) else {
+            window.push_notification(
+                (NotificationType::Warning, "No request to copy as curl"),
+                cx,
+            );
+            return;
+        };
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(request_data.to_curl()));
+        window.push_notification((NotificationType::Success, "Copied as curl"), cx);
     }
 
-    fn switch_workspace(&mut self, workspace_id: Uuid, cx: &mut Context<Self>) {
-        if self.workspaces.read(cx).active_workspace_id() == workspace_id {
+    /// Show the active request as code, with a picker for the language it is written in.
+    pub fn show_generate_code_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(request_data) = self.build_active_request_snapshot(cx) else {
+            window.push_notification(
+                (NotificationType::Warning, "No request to generate code for"),
+                cx,
+            );
             return;
-        }
-        self.cancel_in_flight_for_all_tabs(cx);
-        self.collections.update(cx, |collections, cx| {
-            collections.set_active_workspace(workspace_id, cx);
-        });
-        self.history.update(cx, |history, cx| {
-            history.set_active_workspace(workspace_id, cx);
-        });
-        self.environments.update(cx, |environments, cx| {
-            environments.set_active_workspace(workspace_id, cx);
+        };
+        let options: Vec<CodeTargetOption> = CodeTarget::all()
+            .iter()
+            .map(|&target| CodeTargetOption { target })
+            .collect();
+        let target_select = cx.new(|cx| {
+            SelectState::new(options, Some(gpui_component::IndexPath::new(0)), window, cx)
         });
-        self.workspaces.update(cx, |workspaces, cx| {
-            workspaces.set_active_workspace(workspace_id, cx);
-        });
-
-        self.tabs.clear();
-        self.active_tab_index = 0;
-        self.new_tab(cx);
-        self.schedule_history_rows(cx);
-        cx.notify();
-    }
 
-    fn show_new_workspace_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let input = cx.new(|cx| {
-            InputState::new(window, cx)
-                .placeholder("My Workspace")
-                .default_value("New Workspace")
-        });
-        let input_for_create = input.clone();
-        let this = cx.entity().clone();
         open_dialog(window, cx, move |dialog, _, cx| {
-            let this_for_create = this.clone();
-            let input_for_create = input_for_create.clone();
+            let target = target_select
+                .read(cx)
+                .selected_value()
+                .map(|option| option.target)
+                .unwrap_or(CodeTarget::JavaScriptFetch);
+            let code = generate_code(&request_data, target);
+            let code_for_copy = code.clone();
             dialog
-                .title("New Workspace")
+                .title("Generate Code")
+                .w(px(640.0))
                 .child(
                     v_flex()
                         .gap_3()
+                        .child(Select::new(&target_select).menu_width(px(360.0)))
                         .child(
                             div()
-                                .text_sm()
-                                .text_color(cx.theme().muted_foreground)
-                                .child(
-                                    "Collections, request history, and environments stay isolated inside this workspace.",
-                                ),
-                        )
-                        .child(Input::new(&input)),
+                                .id("generated-code")
+                                .max_h(px(420.0))
+                                .overflow_y_scroll()
+                                .p(px(8.0))
+                                .rounded(px(4.0))
+                                .bg(cx.theme().muted)
+                                .font_family(cx.theme().mono_font_family.clone())
+                                .text_size(px(12.0))
+                                .children(code.lines().map(|line| {
+                                    div().min_h(px(16.0)).child(line.replace('\t', "    "))
+                                })),
+                        ),
                 )
                 .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("create-workspace-confirm")
-                                .label("Create workspace")
+                            Button::new("generate-code-copy")
+                                .label("Copy")
                                 .primary()
                                 .on_click(move |_, window, cx| {
-                                    let name =
-                                        input_for_create.read(cx).text().to_string();
-                                    this_for_create.update(cx, |view, cx| {
-                                        let workspace_id =
-                                            view.workspaces.update(cx, |workspaces, cx| {
-                                                workspaces.create_workspace(name, cx)
-                                            });
-                                        view.switch_workspace(workspace_id, cx);
-                                    });
-                                    close_dialog(window, cx);
+                                    cx.write_to_clipboard(gpui::ClipboardItem::new_string(
+                                        code_for_copy.clone(),
+                                    ));
+                                    window.push_notification(
+                                        (
+                                            NotificationType::Success,
+                                            SharedString::from(format!(
+                                                "{} code copied",
+                                                target.label()
+                                            )),
+                                        ),
+                                        cx,
+                                    );
                                 }),
                         )
                         .child(
-                            Button::new("create-workspace-cancel")
-                                .label("Cancel")
+                            Button::new("generate-code-close")
+                                .label("Close")
                                 .on_click(|_, window, cx| close_dialog(window, cx)),
                         ),
                 )
         });
     }
 
-    fn show_rename_workspace_dialog(
+    /// Load a parsed curl command into a new tab
+    pub fn load_parsed_curl(
         &mut self,
-        workspace_id: Uuid,
-        current_name: String,
+        parsed: &crate::utils::ParsedCurl,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let input = cx.new(|cx| {
-            InputState::new(window, cx)
-                .placeholder("Workspace name")
-                .default_value(current_name)
+        self.new_tab(cx);
+        self.fill_active_tab_from_curl(parsed, window, cx);
+    }
+
+    /// Expand the collection named on the command line and show it in the sidebar.
+    fn open_launch_collection(&mut self, name: &str, cx: &mut Context<Self>) {
+        let Some(collection_id) = self
+            .collections
+            .read(cx)
+            .find_by_name(name)
+            .map(|collection| collection.id)
+        else {
+            Self::report_launch_option(
+                NotificationType::Warning,
+                format!("No collection named \"{name}\""),
+                cx,
+            );
+            return;
+        };
+        self.collections.update(cx, |collections, cx| {
+            collections.expand_collection(collection_id, cx);
         });
-        let input_for_rename = input.clone();
-        let this = cx.entity().clone();
-        open_dialog(window, cx, move |dialog, _, _| {
-            let this_for_rename = this.clone();
-            let input_for_rename = input_for_rename.clone();
-            dialog
-                .title("Rename Workspace")
-                .child(Input::new(&input))
-                .footer(
-                    DialogFooter::new()
-                        .child(
-                            Button::new("rename-workspace-confirm")
-                                .label("Rename")
-                                .primary()
-                                .on_click(move |_, window, cx| {
-                                    let name = input_for_rename.read(cx).text().to_string();
-                                    this_for_rename.update(cx, |view, cx| {
-                                        view.workspaces.update(cx, |workspaces, cx| {
-                                            workspaces.rename_workspace(workspace_id, name, cx);
-                                        });
-                                    });
-                                    close_dialog(window, cx);
-                                }),
-                        )
-                        .child(
-                            Button::new("rename-workspace-cancel")
-                                .label("Cancel")
-                                .on_click(|_, window, cx| close_dialog(window, cx)),
-                        ),
-                )
+        self.sidebar_visible = true;
+        self.set_sidebar_tab(SidebarTab::Collections, cx);
+    }
+
+    /// Activate the environment named on the command line and say which one it was, since
+    /// a global environment and one of the workspace's may share the name.
+    fn open_launch_environment(&mut self, name: &str, cx: &mut Context<Self>) {
+        let activated = self.environments.update(cx, |environments, cx| {
+            environments.activate_by_name(name, cx)
         });
+        let Some(environment) =
+            activated.and_then(|id| self.environments.read(cx).get(id).cloned())
+        else {
+            Self::report_launch_option(
+                NotificationType::Warning,
+                format!("No environment named \"{name}\""),
+                cx,
+            );
+            return;
+        };
+        let scope = match environment.scope {
+            EnvironmentScope::Global => "global".to_string(),
+            EnvironmentScope::Workspace => "this workspace".to_string(),
+            EnvironmentScope::Project(project_id) => self
+                .collections
+                .read(cx)
+                .collections
+                .iter()
+                .find(|collection| collection.id == project_id)
+                .map(|collection| format!("collection {}", collection.name))
+                .unwrap_or_else(|| "a collection".to_string()),
+        };
+        Self::report_launch_option(
+            NotificationType::Info,
+            format!("Activated environment \"{}\" ({scope})", environment.name),
+            cx,
+        );
     }
 
-    fn show_delete_workspace_dialog(
+    /// Report on a command-line option in the log and in the window, as the app may have
+    /// been started without a terminal.
+    fn report_launch_option(
+        notification_type: NotificationType,
+        message: String,
+        cx: &mut Context<Self>,
+    ) {
+        if matches!(notification_type, NotificationType::Warning) {
+            log::warn!("{message}");
+        } else {
+            log::info!("{message}");
+        }
+        cx.spawn(async move |_view, cx| {
+            let _ = cx.update(|app| {
+                if let Some(window) = app.active_window() {
+                    let _ = window.update(app, |_, window, cx| {
+                        window.push_notification(
+                            (notification_type, SharedString::from(message)),
+                            cx,
+                        );
+                    });
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Fill the startup tab with the request given on the command line.
+    pub fn open_launch_request(
         &mut self,
-        workspace_id: Uuid,
+        parsed: &crate::utils::ParsedCurl,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let (name, fallback_id) = {
-            let workspaces = self.workspaces.read(cx);
-            let Some(workspace) = workspaces
-                .workspaces()
-                .iter()
-                .find(|workspace| workspace.id == workspace_id)
-            else {
-                return;
-            };
-            let Some(fallback) = workspaces
-                .workspaces()
-                .iter()
-                .find(|workspace| workspace.id != workspace_id)
-            else {
-                return;
-            };
-            (workspace.name.clone(), fallback.id)
-        };
-        let this = cx.entity().clone();
-        open_dialog(window, cx, move |dialog, _, _| {
-            let this_for_delete = this.clone();
-            dialog
-                .title("Delete Workspace")
-                .child(format!(
-                    "Delete “{name}” and its collections, history, and environments? This cannot be undone."
-                ))
-                .footer(
-                    DialogFooter::new()
-                        .child(
-                            Button::new("delete-workspace-confirm")
-                                .label("Delete workspace")
-                                .danger()
-                                .on_click(move |_, window, cx| {
-                                    this_for_delete.update(cx, |view, cx| {
-                                        view.switch_workspace(fallback_id, cx);
-                                        view.collections.update(cx, |collections, _| {
-                                            collections.remove_workspace(workspace_id);
-                                        });
-                                        view.history.update(cx, |history, _| {
-                                            history.remove_workspace(workspace_id);
-                                        });
-                                        view.environments.update(cx, |environments, _| {
-                                            environments.remove_workspace(workspace_id);
-                                        });
-                                        view.workspaces.update(cx, |workspaces, cx| {
-                                            workspaces.remove_workspace(workspace_id, cx);
-                                        });
-                                    });
-                                    close_dialog(window, cx);
-                                }),
-                        )
-                        .child(
-                            Button::new("delete-workspace-cancel")
-                                .label("Cancel")
-                                .on_click(|_, window, cx| close_dialog(window, cx)),
-                        ),
-                )
-        });
+        self.fill_active_tab_from_curl(parsed, window, cx);
     }
 
-    pub fn open_environment_tab(
+    fn fill_active_tab_from_curl(
         &mut self,
-        environment_id: Uuid,
+        parsed: &crate::utils::ParsedCurl,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(index) = self
-            .tabs
-            .iter()
-            .position(|tab| tab.is_environment(environment_id))
-        {
-            self.active_tab_index = index;
-            cx.notify();
+        let tab_index = self.active_tab_index;
+        self.ensure_url_input(tab_index, window, cx);
+        let Some(tab) = self.tabs.get(tab_index) else {
             return;
+        };
+        let tab_id = tab.id;
+        if let TabContent::Request {
+            url_input: Some(url_input),
+            ..
+        } = &tab.content
+        {
+            let url_input = url_input.clone();
+            url_input.update(cx, |state, cx| {
+                state.set_value(parsed.url.clone(), window, cx);
+            });
         }
+        self.apply_curl_to_tab(tab_id, parsed, window, cx);
+    }
 
-        let env_name = self
-            .environments
-            .read(cx)
-            .get(environment_id)
-            .map(|e| e.name.clone())
-            .unwrap_or_else(|| "Environment".to_string());
+    /// Delete a history entry
+    pub fn delete_history_entry(&mut self, entry_id: Uuid, cx: &mut Context<Self>) {
+        self.history.update(cx, |history, cx| {
+            history.remove_entry(entry_id, cx);
+        });
+    }
 
-        let collection_id =
-            self.environments
-                .read(cx)
-                .get(environment_id)
-                .and_then(|env| match env.scope {
-                    EnvironmentScope::Project(col_id) => Some(col_id),
-                    _ => None,
-                });
+    /// Toggle star status of a history entry
+    pub fn toggle_history_star(&mut self, entry_id: Uuid, cx: &mut Context<Self>) {
+        self.history.update(cx, |history, cx| {
+            history.toggle_star(entry_id, cx);
+        });
+    }
 
-        let environments = self.environments.clone();
-        let collections = self.collections.clone();
-        let editor_view = cx.new(|cx| {
-            let mut view =
-                EnvironmentView::new(environment_id, environments, collections, window, cx);
-            view.set_collection_context(collection_id);
-            view
+    /// Clear all history
+    pub fn clear_history(&mut self, cx: &mut Context<Self>) {
+        self.history.update(cx, |history, cx| {
+            history.clear_unstarred(cx);
         });
+    }
 
-        let tab_id = TabId(self.next_tab_id);
-        self.next_tab_id += 1;
+    /// Environment whose cookie store the active tab's requests use.
+    fn active_cookie_scope(&self, cx: &App) -> Option<Uuid> {
+        let collection_id = self.active_tab().and_then(|tab| tab.collection_id);
+        self.environments
+            .read(cx)
+            .active_environment_id(collection_id)
+    }
 
-        let new_tab = TabState {
-            id: tab_id,
-            name: env_name,
-            is_custom_name: true,
-            content: TabContent::Environment {
-                environment_id,
-                view: editor_view,
-            },
-            collection_id,
-        };
+    pub fn delete_cookie(&mut self, cookie: &StoredCookie, cx: &mut Context<Self>) {
+        let scope = self.active_cookie_scope(cx);
+        self.http_client.cookie_jar().remove(scope, cookie);
+        cx.notify();
+    }
 
-        self.tabs.push(new_tab);
-        self.active_tab_index = self.tabs.len() - 1;
-        self.tab_scroll_handle.scroll_to_item(self.active_tab_index);
+    pub fn clear_cookies(&mut self, cx: &mut Context<Self>) {
+        let scope = self.active_cookie_scope(cx);
+        self.http_client.cookie_jar().clear(scope);
         cx.notify();
     }
 
-    pub fn show_new_environment_dialog(
+    #[allow(dead_code)]
+    /// Add current request to history
+    pub fn add_to_history(
         &mut self,
-        project_id: Option<Uuid>,
+        request: RequestData,
+        response: Option<ResponseData>,
+        cx: &mut Context<Self>,
+    ) {
+        self.history.update(cx, |history, cx| {
+            history.add_entry(request, response, cx);
+        });
+    }
+
+    /// Load a collection item into a new tab
+    pub fn load_collection_item(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let mut scope_options = vec![
-            EnvironmentScopeOption {
-                scope: EnvironmentScope::Global,
-                label: "Global · Available in every workspace".to_string(),
-            },
-            EnvironmentScopeOption {
-                scope: EnvironmentScope::Workspace,
-                label: "Workspace · Available in this workspace".to_string(),
+        // First extract all data we need, then drop the borrow
+        let item_data = {
+            let collections = self.collections.read(cx);
+            collections
+                .get_request_node(collection_id, node_id)
+                .map(|node| (node.request.clone(), node.display_name(), node.run_on_open))
+        };
+
+        let Some((request_data, tab_name, run_on_open)) = item_data else {
+            return;
+        };
+
+        // Derive body type from the stored request body
+        let body_type = BodyType::from_request_body(&request_data.body);
+
+        // Extract body content for text-based body types
+        let body_content: Option<String> = match &request_data.body {
+            RequestBody::Json(content) | RequestBody::Text(content) => {
+                if content.is_empty() {
+                    None
+                } else {
+                    Some(content.clone())
+                }
+            }
+            RequestBody::None
+            | RequestBody::FormData(_)
+            | RequestBody::MultipartFormData(_)
+            | RequestBody::GraphQl { .. }
+            | RequestBody::Binary(_) => None,
+        };
+
+        // Extract form data for FormUrlEncoded
+        let form_data = match &request_data.body {
+            RequestBody::FormData(data) => Some(data.clone()),
+            _ => None,
+        };
+
+        // Extract multipart data for FormData
+        let multipart_data = match &request_data.body {
+            RequestBody::MultipartFormData(fields) => Some(fields.clone()),
+            _ => None,
+        };
+
+        // Now we can use cx freely
+        let request = cx.new(|cx| {
+            let mut req = RequestEntity::new()
+                .with_headers(request_data.headers.clone())
+                .with_preserve_header_order(request_data.preserve_header_order)
+                .with_inherit_auth(request_data.inherit_auth)
+                .with_assertions(request_data.assertions.clone())
+                .with_pre_request_script(request_data.pre_request_script.clone())
+                .with_extractions(request_data.extractions.clone())
+                .with_subprotocols(request_data.subprotocols.clone())
+                .with_disabled_params(request_data.disabled_params.clone())
+                .with_auto_save(request_data.auto_save.clone());
+            req.set_url(request_data.url.clone(), cx);
+            req.set_method(request_data.method, cx);
+            req.set_body(request_data.body.clone(), cx);
+            req
+        });
+
+        let response = cx.new(|_| ResponseEntity::new());
+        let method_dropdown = cx.new(|_| MethodDropdownState::new(request_data.method));
+        let completion_engine = self.completion_engine.clone();
+        let request_view = cx.new(|cx| {
+            RequestView::new(request.clone(), body_type, cx)
+                .with_completion_engine(completion_engine)
+                .with_initial_body_content(body_content)
+                .with_initial_form_data(form_data)
+                .with_initial_multipart_data(multipart_data)
+        });
+        let response_view = cx.new(|cx| ResponseView::new(response.clone(), cx));
+        let completion_engine = self.completion_engine.clone();
+        let url_input = cx.new(|cx| {
+            configure_completion(
+                InputState::new(window, cx)
+                    .placeholder("Enter request URL...")
+                    .default_value(&request_data.url),
+                Some(&completion_engine),
+                CompletionContext::Url,
+            )
+        });
+        let tab_id = TabId(self.next_tab_id);
+        Self::subscribe_url_input(&url_input, tab_id, window, cx);
+        Self::subscribe_request_changes(&request, cx);
+        Self::subscribe_request_view_changes(&request_view, cx);
+
+        let tab = TabState {
+            id: tab_id,
+            name: tab_name,
+            is_custom_name: true,
+            content: TabContent::Request {
+                request: request.clone(),
+                response: response.clone(),
+                url_input: Some(url_input),
+                method_dropdown,
+                request_view,
+                response_view,
+                in_flight_request: None,
+                sent_request: None,
+                request_generation: RequestGeneration::default(),
+                protocol: ProtocolType::Rest,
+                websocket: None,
             },
-        ];
-        scope_options.extend(
-            self.collections
-                .read(cx)
-                .collections
-                .iter()
-                .map(|collection| EnvironmentScopeOption {
-                    scope: EnvironmentScope::Project(collection.id),
-                    label: format!("Project · {}", collection.name),
-                }),
-        );
-        let selected_index = project_id
-            .and_then(|project_id| {
-                scope_options
-                    .iter()
-                    .position(|option| option.scope == EnvironmentScope::Project(project_id))
-            })
-            .unwrap_or(1);
-        let scope_select = cx.new(|cx| {
-            SelectState::new(
-                scope_options,
-                Some(gpui_component::IndexPath::new(selected_index)),
-                window,
+            collection_id: Some(collection_id),
+            collection_node_id: Some(node_id),
+            history_entry_id: None,
+        };
+
+        self.tabs.push(tab);
+        self.active_tab_index = self.tabs.len() - 1;
+        self.next_tab_id += 1;
+
+        if run_on_open.allows(request_data.method) {
+            self.send_request(cx);
+        } else if !run_on_open.is_off() {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    format!(
+                        "Not sent on open: {} requests need Run on Open allowed for them",
+                        request_data.method.as_str()
+                    ),
+                ),
                 cx,
-            )
+            );
+        }
+        cx.notify();
+    }
+
+    /// Create a new collection
+    pub fn create_new_collection(&mut self, cx: &mut Context<Self>) {
+        self.collections.update(cx, |collections, cx| {
+            collections.create_collection("New Collection", cx);
         });
-        let name_input = cx.new(|cx| {
+    }
+
+    fn switch_workspace(&mut self, workspace_id: Uuid, cx: &mut Context<Self>) {
+        if self.workspaces.read(cx).active_workspace_id() == workspace_id {
+            return;
+        }
+        self.cancel_in_flight_for_all_tabs(cx);
+        self.collections.update(cx, |collections, cx| {
+            collections.set_active_workspace(workspace_id, cx);
+        });
+        self.history.update(cx, |history, cx| {
+            history.set_active_workspace(workspace_id, cx);
+        });
+        self.environments.update(cx, |environments, cx| {
+            environments.set_active_workspace(workspace_id, cx);
+        });
+        self.workspaces.update(cx, |workspaces, cx| {
+            workspaces.set_active_workspace(workspace_id, cx);
+        });
+
+        self.tabs.clear();
+        self.active_tab_index = 0;
+        self.new_tab(cx);
+        self.schedule_history_rows(cx);
+        cx.notify();
+    }
+
+    fn show_new_workspace_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let input = cx.new(|cx| {
             InputState::new(window, cx)
-                .placeholder("Development, Staging, Production…")
-                .default_value("Development")
+                .placeholder("My Workspace")
+                .default_value("New Workspace")
         });
+        let input_for_create = input.clone();
         let this = cx.entity().clone();
-        let name_for_footer = name_input.clone();
-        let scope_for_footer = scope_select.clone();
-
         open_dialog(window, cx, move |dialog, _, cx| {
             let this_for_create = this.clone();
-            let name_for_create = name_for_footer.clone();
-            let scope_for_create = scope_for_footer.clone();
-
+            let input_for_create = input_for_create.clone();
             dialog
-                .title("New Environment")
+                .title("New Workspace")
                 .child(
                     v_flex()
                         .gap_3()
@@ -1209,55 +1981,32 @@ impl MainView {
                                 .text_sm()
                                 .text_color(cx.theme().muted_foreground)
                                 .child(
-                                    "Global environments are shared by every workspace. Workspace and project environments override them with more specific values.",
+                                    "Collections, request history, and environments stay isolated inside this workspace.",
                                 ),
                         )
-                        .child("Environment name")
-                        .child(Input::new(&name_input))
-                        .child("Scope")
-                        .child(Select::new(&scope_select).menu_width(px(360.0))),
+                        .child(Input::new(&input)),
                 )
                 .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("create-environment")
-                                .label("Create environment")
+                            Button::new("create-workspace-confirm")
+                                .label("Create workspace")
                                 .primary()
                                 .on_click(move |_, window, cx| {
-                                    let name = name_for_create
-                                        .read(cx)
-                                        .text()
-                                        .to_string()
-                                        .trim()
-                                        .to_string();
-                                    let name = if name.is_empty() {
-                                        "Development".to_string()
-                                    } else {
-                                        name
-                                    };
-                                    let Some(scope) = scope_for_create
-                                        .read(cx)
-                                        .selected_value()
-                                        .map(|option| option.scope)
-                                    else {
-                                        return;
-                                    };
+                                    let name =
+                                        input_for_create.read(cx).text().to_string();
                                     this_for_create.update(cx, |view, cx| {
-                                        let environment_id = view.environments.update(
-                                            cx,
-                                            |environments, cx| {
-                                                environments.create_environment(name, scope, cx)
-                                            },
-                                        );
-                                        view.environment_panel.update(cx, |panel, cx| {
-                                            panel.select_environment(environment_id, cx);
-                                        });
+                                        let workspace_id =
+                                            view.workspaces.update(cx, |workspaces, cx| {
+                                                workspaces.create_workspace(name, cx)
+                                            });
+                                        view.switch_workspace(workspace_id, cx);
                                     });
                                     close_dialog(window, cx);
                                 }),
                         )
                         .child(
-                            Button::new("create-environment-cancel")
+                            Button::new("create-workspace-cancel")
                                 .label("Cancel")
                                 .on_click(|_, window, cx| close_dialog(window, cx)),
                         ),
@@ -1265,45 +2014,44 @@ impl MainView {
         });
     }
 
-    pub fn show_delete_environment_dialog(
+    fn show_rename_workspace_dialog(
         &mut self,
-        environment_id: Uuid,
+        workspace_id: Uuid,
+        current_name: String,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(name) = self
-            .environments
-            .read(cx)
-            .get(environment_id)
-            .map(|environment| environment.name.clone())
-        else {
-            return;
-        };
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Workspace name")
+                .default_value(current_name)
+        });
+        let input_for_rename = input.clone();
         let this = cx.entity().clone();
         open_dialog(window, cx, move |dialog, _, _| {
-            let this_for_delete = this.clone();
+            let this_for_rename = this.clone();
+            let input_for_rename = input_for_rename.clone();
             dialog
-                .title("Delete Environment")
-                .child(format!(
-                    "Delete “{name}”? Requests using its variables will stop resolving."
-                ))
+                .title("Rename Workspace")
+                .child(Input::new(&input))
                 .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("delete-environment-confirm")
-                                .label("Delete")
-                                .danger()
+                            Button::new("rename-workspace-confirm")
+                                .label("Rename")
+                                .primary()
                                 .on_click(move |_, window, cx| {
-                                    this_for_delete.update(cx, |view, cx| {
-                                        view.environments.update(cx, |environments, cx| {
-                                            environments.remove_environment(environment_id, cx);
+                                    let name = input_for_rename.read(cx).text().to_string();
+                                    this_for_rename.update(cx, |view, cx| {
+                                        view.workspaces.update(cx, |workspaces, cx| {
+                                            workspaces.rename_workspace(workspace_id, name, cx);
                                         });
                                     });
                                     close_dialog(window, cx);
                                 }),
                         )
                         .child(
-                            Button::new("delete-environment-cancel")
+                            Button::new("rename-workspace-cancel")
                                 .label("Cancel")
                                 .on_click(|_, window, cx| close_dialog(window, cx)),
                         ),
@@ -1311,48 +2059,65 @@ impl MainView {
         });
     }
 
-    pub fn show_rename_environment_dialog(
+    fn show_delete_workspace_dialog(
         &mut self,
-        environment_id: Uuid,
-        current_name: String,
+        workspace_id: Uuid,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let input = cx.new(|cx| {
-            InputState::new(window, cx)
-                .placeholder("Environment name")
-                .default_value(current_name)
-        });
+        let (name, fallback_id) = {
+            let workspaces = self.workspaces.read(cx);
+            let Some(workspace) = workspaces
+                .workspaces()
+                .iter()
+                .find(|workspace| workspace.id == workspace_id)
+            else {
+                return;
+            };
+            let Some(fallback) = workspaces
+                .workspaces()
+                .iter()
+                .find(|workspace| workspace.id != workspace_id)
+            else {
+                return;
+            };
+            (workspace.name.clone(), fallback.id)
+        };
         let this = cx.entity().clone();
-        let input_for_footer = input.clone();
         open_dialog(window, cx, move |dialog, _, _| {
-            let this_for_rename = this.clone();
-            let input_for_rename = input_for_footer.clone();
+            let this_for_delete = this.clone();
             dialog
-                .title("Rename Environment")
-                .child(Input::new(&input))
+                .title("Delete Workspace")
+                .child(format!(
+                    "Delete “{name}” and its collections, history, and environments? This cannot be undone."
+                ))
                 .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("rename-environment-confirm")
-                                .label("Rename")
-                                .primary()
+                            Button::new("delete-workspace-confirm")
+                                .label("Delete workspace")
+                                .danger()
                                 .on_click(move |_, window, cx| {
-                                    let name = input_for_rename.read(cx).text().to_string();
-                                    this_for_rename.update(cx, |view, cx| {
-                                        view.environments.update(cx, |environments, cx| {
-                                            environments.rename_environment(
-                                                environment_id,
-                                                name,
-                                                cx,
-                                            );
+                                    this_for_delete.update(cx, |view, cx| {
+                                        view.switch_workspace(fallback_id, cx);
+                                        view.collections.update(cx, |collections, _| {
+                                            collections.remove_workspace(workspace_id);
+                                        });
+                                        view.history.update(cx, |history, _| {
+                                            history.remove_workspace(workspace_id);
+                                        });
+                                        view.environments.update(cx, |environments, _| {
+                                            environments.remove_workspace(workspace_id);
+                                        });
+                                        view.workspaces.update(cx, |workspaces, cx| {
+                                            workspaces.remove_workspace(workspace_id, cx);
                                         });
                                     });
                                     close_dialog(window, cx);
                                 }),
                         )
                         .child(
-                            Button::new("rename-environment-cancel")
+                            Button::new("delete-workspace-cancel")
                                 .label("Cancel")
                                 .on_click(|_, window, cx| close_dialog(window, cx)),
                         ),
@@ -1360,1113 +2125,3772 @@ impl MainView {
         });
     }
 
-    /// Delete a collection
-    pub fn delete_collection(&mut self, collection_id: Uuid, cx: &mut Context<Self>) {
-        self.collections.update(cx, |collections, cx| {
-            collections.remove_collection(collection_id, cx);
-        });
-        self.environments.update(cx, |environments, cx| {
-            environments.remove_project_environments(collection_id, cx);
-        });
-        for tab in &mut self.tabs {
-            if tab.collection_id == Some(collection_id) {
-                tab.collection_id = None;
-            }
-        }
-    }
-
-    /// Delete an item from a collection
-    pub fn delete_collection_node(
+    pub fn open_environment_tab(
         &mut self,
-        collection_id: Uuid,
-        node_id: Uuid,
+        environment_id: Uuid,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.collections.update(cx, |collections, cx| {
-            collections.remove_node(collection_id, node_id, cx);
-        });
-    }
+        if let Some(index) = self
+            .tabs
+            .iter()
+            .position(|tab| tab.is_environment(environment_id))
+        {
+            self.active_tab_index = index;
+            cx.notify();
+            return;
+        }
 
-    /// Toggle collection expanded state
-    pub fn toggle_collection_expand(&mut self, collection_id: Uuid, cx: &mut Context<Self>) {
-        self.collections.update(cx, |collections, cx| {
-            collections.toggle_collection_expanded(collection_id, cx);
-        });
-    }
+        let env_name = self
+            .environments
+            .read(cx)
+            .get(environment_id)
+            .map(|e| e.name.clone())
+            .unwrap_or_else(|| "Environment".to_string());
 
-    pub fn toggle_collection_node_expand(
-        &mut self,
-        collection_id: Uuid,
-        node_id: Uuid,
-        cx: &mut Context<Self>,
-    ) {
-        self.collections.update(cx, |collections, cx| {
-            collections.toggle_node_expanded(collection_id, node_id, cx);
-        });
-    }
+        let collection_id =
+            self.environments
+                .read(cx)
+                .get(environment_id)
+                .and_then(|env| match env.scope {
+                    EnvironmentScope::Project(col_id) => Some(col_id),
+                    _ => None,
+                });
 
-    pub fn create_collection_folder(
-        &mut self,
-        collection_id: Uuid,
-        parent_folder_id: Option<Uuid>,
-        name: &str,
-        cx: &mut Context<Self>,
-    ) {
-        self.collections.update(cx, |collections, cx| {
-            collections.create_folder(collection_id, parent_folder_id, name, cx);
+        let environments = self.environments.clone();
+        let collections = self.collections.clone();
+        let editor_view = cx.new(|cx| {
+            let mut view =
+                EnvironmentView::new(environment_id, environments, collections, window, cx);
+            view.set_collection_context(collection_id);
+            view
         });
+
+        let tab_id = TabId(self.next_tab_id);
+        self.next_tab_id += 1;
+
+        let new_tab = TabState {
+            id: tab_id,
+            name: env_name,
+            is_custom_name: true,
+            content: TabContent::Environment {
+                environment_id,
+                view: editor_view,
+            },
+            collection_id,
+            collection_node_id: None,
+            history_entry_id: None,
+        };
+
+        self.tabs.push(new_tab);
+        self.active_tab_index = self.tabs.len() - 1;
+        self.tab_scroll_handle.scroll_to_item(self.active_tab_index);
+        cx.notify();
     }
 
-    pub fn save_request_to_destination(
+    pub fn show_new_environment_dialog(
         &mut self,
-        destination: CollectionDestination,
-        request_name: String,
-        request_data: RequestData,
+        project_id: Option<Uuid>,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let mut request_data = request_data;
-        request_data.name = request_name;
-        self.collections.update(cx, |collections, cx| {
-            collections.add_request_node(
-                destination.collection_id,
-                destination.folder_id,
-                request_data,
+        let mut scope_options = vec![
+            EnvironmentScopeOption {
+                scope: EnvironmentScope::Global,
+                label: "Global · Available in every workspace".to_string(),
+            },
+            EnvironmentScopeOption {
+                scope: EnvironmentScope::Workspace,
+                label: "Workspace · Available in this workspace".to_string(),
+            },
+        ];
+        scope_options.extend(
+            self.collections
+                .read(cx)
+                .collections
+                .iter()
+                .map(|collection| EnvironmentScopeOption {
+                    scope: EnvironmentScope::Project(collection.id),
+                    label: format!("Project · {}", collection.name),
+                }),
+        );
+        let selected_index = project_id
+            .and_then(|project_id| {
+                scope_options
+                    .iter()
+                    .position(|option| option.scope == EnvironmentScope::Project(project_id))
+            })
+            .unwrap_or(1);
+        let scope_select = cx.new(|cx| {
+            SelectState::new(
+                scope_options,
+                Some(gpui_component::IndexPath::new(selected_index)),
+                window,
                 cx,
-            );
+            )
+        });
+        let name_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Development, Staging, Production…")
+                .default_value("Development")
+        });
+        let this = cx.entity().clone();
+        let name_for_footer = name_input.clone();
+        let scope_for_footer = scope_select.clone();
+
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_create = this.clone();
+            let name_for_create = name_for_footer.clone();
+            let scope_for_create = scope_for_footer.clone();
+
+            dialog
+                .title("New Environment")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(
+                                    "Global environments are shared by every workspace. Workspace and project environments override them with more specific values.",
+                                ),
+                        )
+                        .child("Environment name")
+                        .child(Input::new(&name_input))
+                        .child("Scope")
+                        .child(Select::new(&scope_select).menu_width(px(360.0))),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("create-environment")
+                                .label("Create environment")
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    let name = name_for_create
+                                        .read(cx)
+                                        .text()
+                                        .to_string()
+                                        .trim()
+                                        .to_string();
+                                    let name = if name.is_empty() {
+                                        "Development".to_string()
+                                    } else {
+                                        name
+                                    };
+                                    let Some(scope) = scope_for_create
+                                        .read(cx)
+                                        .selected_value()
+                                        .map(|option| option.scope)
+                                    else {
+                                        return;
+                                    };
+                                    this_for_create.update(cx, |view, cx| {
+                                        let environment_id = view.environments.update(
+                                            cx,
+                                            |environments, cx| {
+                                                environments.create_environment(name, scope, cx)
+                                            },
+                                        );
+                                        view.environment_panel.update(cx, |panel, cx| {
+                                            panel.select_environment(environment_id, cx);
+                                        });
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("create-environment-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
         });
-        if let Some(tab) = self.tabs.get_mut(self.active_tab_index) {
-            tab.collection_id = Some(destination.collection_id);
-        }
-        cx.notify();
-    }
-
-    fn build_active_request_snapshot(&mut self, cx: &mut Context<Self>) -> Option<RequestData> {
-        self.build_request_snapshot_for_tab(self.active_tab_index, cx)
     }
 
-    fn build_request_snapshot_for_tab(
+    pub fn show_delete_environment_dialog(
         &mut self,
-        tab_index: usize,
+        environment_id: Uuid,
+        window: &mut Window,
         cx: &mut Context<Self>,
-    ) -> Option<RequestData> {
-        let (tab_name, is_custom_name, request_entity, request_view, url_input) = {
-            let tab = self.tabs.get(tab_index)?;
-            if let TabContent::Request {
-                request,
-                request_view,
-                url_input,
-                ..
-            } = &tab.content
-            {
-                (
-                    tab.name.clone(),
-                    tab.is_custom_name,
-                    request.clone(),
-                    request_view.clone(),
-                    url_input.clone(),
-                )
-            } else {
-                return None;
-            }
+    ) {
+        let Some(name) = self
+            .environments
+            .read(cx)
+            .get(environment_id)
+            .map(|environment| environment.name.clone())
+        else {
+            return;
         };
+        let this = cx.entity().clone();
+        open_dialog(window, cx, move |dialog, _, _| {
+            let this_for_delete = this.clone();
+            dialog
+                .title("Delete Environment")
+                .child(format!(
+                    "Delete “{name}”? Requests using its variables will stop resolving."
+                ))
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("delete-environment-confirm")
+                                .label("Delete")
+                                .danger()
+                                .on_click(move |_, window, cx| {
+                                    this_for_delete.update(cx, |view, cx| {
+                                        view.environments.update(cx, |environments, cx| {
+                                            environments.remove_environment(environment_id, cx);
+                                        });
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("delete-environment-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
 
-        request_view.update(cx, |view, cx| {
-            view.sync_body_to_request(cx);
-            view.sync_headers_to_request(cx);
+    pub fn show_rename_environment_dialog(
+        &mut self,
+        environment_id: Uuid,
+        current_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Environment name")
+                .default_value(current_name)
+        });
+        let this = cx.entity().clone();
+        let input_for_footer = input.clone();
+        open_dialog(window, cx, move |dialog, _, _| {
+            let this_for_rename = this.clone();
+            let input_for_rename = input_for_footer.clone();
+            dialog
+                .title("Rename Environment")
+                .child(Input::new(&input))
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("rename-environment-confirm")
+                                .label("Rename")
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    let name = input_for_rename.read(cx).text().to_string();
+                                    this_for_rename.update(cx, |view, cx| {
+                                        view.environments.update(cx, |environments, cx| {
+                                            environments.rename_environment(
+                                                environment_id,
+                                                name,
+                                                cx,
+                                            );
+                                        });
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("rename-environment-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
         });
+    }
 
-        let query_string = request_view.read(cx).get_query_string(cx);
-        let base_url = if let Some(ref input) = url_input {
-            input.read(cx).text().to_string()
-        } else {
-            request_entity.read(cx).url().to_string()
+    /// Edit the global proxy, or the override of `environment_id` when given.
+    pub fn show_proxy_dialog(
+        &mut self,
+        environment_id: Option<Uuid>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (title, current) = match environment_id {
+            Some(id) => {
+                let Some(environment) = self.environments.read(cx).get(id) else {
+                    return;
+                };
+                (
+                    format!("Proxy for {}", environment.name),
+                    environment
+                        .proxy
+                        .clone()
+                        .unwrap_or_else(|| self.ui_preferences.proxy.clone()),
+                )
+            }
+            None => ("Proxy".to_string(), self.ui_preferences.proxy.clone()),
         };
-        let final_url = Self::compose_request_url(base_url, query_string);
-
-        request_entity.update(cx, |request, cx| {
-            request.set_url(final_url.clone(), cx);
+        let url_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("http://proxy.example.com:3128 or socks5://127.0.0.1:1080")
+                .default_value(current.url)
         });
-
-        let request = request_entity.read(cx);
-        let snapshot_name = if is_custom_name {
-            tab_name
+        let username_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Username (optional)")
+                .default_value(current.username)
+        });
+        let password_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Password (optional)")
+                .masked(true)
+                .default_value(current.password)
+        });
+        let no_proxy_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("localhost, .internal, 10.0.0.0/8")
+                .default_value(current.no_proxy)
+        });
+        let hint = if environment_id.is_some() {
+            "Requests sent while this environment is active use this proxy instead of the global one. Leave the URL empty to use the system settings."
         } else {
-            Self::derive_request_display_name(request.method(), &final_url)
+            "Requests go through this proxy unless the active environment sets its own. Leave the URL empty to use the system settings."
         };
-        Some(RequestData {
-            id: Uuid::new_v4(),
-            name: snapshot_name,
-            url: final_url,
-            method: request.method(),
-            headers: request.headers().to_vec(),
-            body: request.body().clone(),
-            is_sending: false,
-        })
-    }
+        let this = cx.entity().clone();
 
-    fn compose_request_url(base_url: String, query_string: String) -> String {
-        if query_string.is_empty() {
-            return base_url;
-        }
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_save = this.clone();
+            let this_for_reset = this.clone();
+            let inputs = (
+                url_input.clone(),
+                username_input.clone(),
+                password_input.clone(),
+                no_proxy_input.clone(),
+            );
 
-        if base_url.contains('?') {
-            format!("{}&{}", base_url, &query_string[1..])
-        } else {
-            format!("{base_url}{query_string}")
-        }
-    }
+            let mut footer = DialogFooter::new().child(
+                Button::new("proxy-save")
+                    .label("Save")
+                    .primary()
+                    .on_click(move |_, window, cx| {
+                        let (url, username, password, no_proxy) = &inputs;
+                        let proxy = ProxyConfig {
+                            url: url.read(cx).text().to_string().trim().to_string(),
+                            username: username.read(cx).text().to_string(),
+                            password: password.read(cx).text().to_string(),
+                            no_proxy: no_proxy.read(cx).text().to_string(),
+                        };
+                        if !proxy.is_empty()
+                            && let Err(error) = proxy.to_reqwest()
+                        {
+                            window.push_notification(
+                                (
+                                    NotificationType::Error,
+                                    SharedString::from(error.to_string()),
+                                ),
+                                cx,
+                            );
+                            return;
+                        }
+                        this_for_save.update(cx, |view, cx| match environment_id {
+                            Some(id) => view.environments.update(cx, |environments, cx| {
+                                environments.set_environment_proxy(id, Some(proxy), cx);
+                            }),
+                            None => {
+                                view.ui_preferences.proxy = proxy;
+                                view.persist_ui_preferences();
+                            }
+                        });
+                        close_dialog(window, cx);
+                    }),
+            );
+            if let Some(id) = environment_id {
+                footer = footer.child(
+                    Button::new("proxy-use-global")
+                        .label("Use Global Proxy")
+                        .on_click(move |_, window, cx| {
+                            this_for_reset.update(cx, |view, cx| {
+                                view.environments.update(cx, |environments, cx| {
+                                    environments.set_environment_proxy(id, None, cx);
+                                });
+                            });
+                            close_dialog(window, cx);
+                        }),
+                );
+            }
+            let footer = footer.child(
+                Button::new("proxy-cancel")
+                    .label("Cancel")
+                    .on_click(|_, window, cx| close_dialog(window, cx)),
+            );
 
-    fn extract_url_path(url: &str) -> Option<String> {
-        let url = url.trim();
-        if url.is_empty() {
-            return None;
-        }
-
-        let path = if let Some(after_scheme) = url
-            .strip_prefix("https://")
-            .or_else(|| url.strip_prefix("http://"))
-        {
-            after_scheme
-                .find('/')
-                .map(|i| &after_scheme[i..])
-                .unwrap_or("/")
-        } else if url.starts_with('/') {
-            url
-        } else {
-            url.find('/').map(|i| &url[i..]).unwrap_or(url)
-        };
-
-        let path = path.split('?').next().unwrap_or(path);
-
-        if path.len() > 32 {
-            Some(path[..32].to_string())
-        } else {
-            Some(path.to_string())
-        }
-    }
-
-    /// Tab display name — just the path, since the tab bar already shows a method badge
-    fn derive_tab_name(url: &str) -> String {
-        Self::extract_url_path(url).unwrap_or_else(|| "New Request".to_string())
-    }
-
-    /// Full display name for history entries and saved requests — includes method prefix
-    fn derive_request_display_name(method: HttpMethod, url: &str) -> String {
-        match Self::extract_url_path(url) {
-            Some(path) => format!("{} {}", method.as_str(), path),
-            None => "New Request".to_string(),
-        }
-    }
-
-    fn destination_options(entries: Vec<CollectionDestinationEntry>) -> Vec<DestinationOption> {
-        entries
-            .into_iter()
-            .map(|entry| DestinationOption {
-                destination: entry.destination,
-                label: entry.label,
-            })
-            .collect()
-    }
-
-    fn active_tab(&self) -> Option<&TabState> {
-        self.tabs.get(self.active_tab_index)
-    }
-
-    fn cancel_in_flight_for_tab(&mut self, index: usize, cx: &mut Context<Self>) {
-        if let Some(tab) = self.tabs.get_mut(index)
-            && let TabContent::Request {
-                request,
-                response,
-                in_flight_request,
-                request_generation,
-                ..
-            } = &mut tab.content
-            && let Some(mut in_flight) = in_flight_request.take()
-        {
-            let _ = in_flight.cancel();
-            request_generation.advance();
-            request.update(cx, |req, cx| req.set_sending(false, cx));
-            response.update(cx, |resp, cx| resp.set_cancelled(cx));
-        }
-    }
-
-    fn cancel_in_flight_for_all_tabs(&mut self, cx: &mut Context<Self>) {
-        for index in 0..self.tabs.len() {
-            self.cancel_in_flight_for_tab(index, cx);
-        }
-    }
-
-    /// Add a new tab
-    pub fn new_tab(&mut self, cx: &mut Context<Self>) {
-        let request = cx.new(|_| RequestEntity::new());
-        let response = cx.new(|_| ResponseEntity::new());
-        let method_dropdown = cx.new(|_| MethodDropdownState::new(HttpMethod::Get));
-        let completion_engine = self.completion_engine.clone();
-        let request_view = cx.new(|cx| {
-            RequestView::new(request.clone(), BodyType::None, cx)
-                .with_completion_engine(completion_engine)
+            dialog
+                .title(title.clone())
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(hint),
+                        )
+                        .child("Proxy URL")
+                        .child(Input::new(&url_input))
+                        .child("Username")
+                        .child(Input::new(&username_input))
+                        .child("Password")
+                        .child(Input::new(&password_input))
+                        .child("Bypass for hosts")
+                        .child(Input::new(&no_proxy_input)),
+                )
+                .footer(footer)
         });
-        let response_view = cx.new(|cx| ResponseView::new(response.clone(), cx));
-
-        let tab_id = TabId(self.next_tab_id);
-        self.next_tab_id += 1;
-        Self::subscribe_request_changes(&request, cx);
-        let tab = TabState {
-            id: tab_id,
-            name: "New Request".to_string(),
-            is_custom_name: false,
-            content: TabContent::Request {
-                request: request.clone(),
-                response: response.clone(),
-                url_input: None,
-                method_dropdown,
-                request_view,
-                response_view,
-                in_flight_request: None,
-                request_generation: RequestGeneration::default(),
-            },
-            collection_id: None,
-        };
-
-        self.tabs.push(tab);
-        self.active_tab_index = self.tabs.len() - 1;
-
-        // Scroll to the newly added tab
-        self.tab_scroll_handle.scroll_to_item(self.active_tab_index);
-
-        cx.notify();
-    }
-
-    /// Switch to a tab by index
-    pub fn switch_tab(&mut self, index: usize, cx: &mut Context<Self>) {
-        if index < self.tabs.len() && index != self.active_tab_index {
-            self.active_tab_index = index;
-
-            // Notify the views to re-render with their current data
-            if let Some(tab) = self.tabs.get(index)
-                && let TabContent::Request {
-                    request_view,
-                    response_view,
-                    ..
-                } = &tab.content
-            {
-                request_view.update(cx, |_, cx| cx.notify());
-                response_view.update(cx, |_, cx| cx.notify());
-            }
-
-            // Scroll to the selected tab to ensure it's visible
-            self.tab_scroll_handle.scroll_to_item(index);
-
-            cx.notify();
-        }
-    }
-
-    /// Close a tab
-    pub fn close_tab(&mut self, index: usize, cx: &mut Context<Self>) {
-        if self.tabs.len() > 1 && index < self.tabs.len() {
-            self.cancel_in_flight_for_tab(index, cx);
-            self.tabs.remove(index);
-
-            // Adjust active index
-            if self.active_tab_index >= self.tabs.len() {
-                self.active_tab_index = self.tabs.len() - 1;
-            } else if index < self.active_tab_index {
-                self.active_tab_index -= 1;
-            }
-
-            cx.notify();
-        }
-    }
-
-    /// Rename a tab (marks as custom since user explicitly renamed)
-    pub fn rename_tab(&mut self, index: usize, new_name: String, cx: &mut Context<Self>) {
-        if index < self.tabs.len() {
-            self.tabs[index].name = new_name;
-            self.tabs[index].is_custom_name = true;
-            cx.notify();
-        }
     }
 
-    /// Show rename dialog for a tab
-    pub fn show_rename_dialog(
-        &mut self,
-        index: usize,
-        current_name: String,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
+    /// Edit how often failed requests are tried again.
+    fn show_retry_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current = self.ui_preferences.retry;
+        let attempts_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("1 sends once")
+                .default_value(current.max_attempts.to_string())
+        });
+        let delay_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("500")
+                .default_value(current.base_delay_ms.to_string())
+        });
+        let retry_non_idempotent = Rc::new(Cell::new(current.retry_non_idempotent));
         let this = cx.entity().clone();
-        let input = cx.new(|cx| InputState::new(window, cx).default_value(&current_name));
-
-        // Subscribe to Enter key press on the input
-        cx.subscribe_in(&input, window, move |view, state, event, window, cx| {
-            use gpui_component::input::InputEvent;
-            if let InputEvent::PressEnter { .. } = event {
-                let new_name = state.read(cx).text().to_string();
-                view.rename_tab(index, new_name, cx);
-                close_dialog(window, cx);
-            }
-        })
-        .detach();
-
-        // Clone for footer button clicks
-        let input_for_footer = input.clone();
-        let this_for_footer = this.clone();
-
-        open_dialog(window, cx, move |dialog, _, _| {
-            // Clone again before the inner move closure
-            let input_for_buttons = input_for_footer.clone();
-            let this_for_buttons = this_for_footer.clone();
 
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_save = this.clone();
+            let attempts_for_save = attempts_input.clone();
+            let delay_for_save = delay_input.clone();
+            let non_idempotent_for_save = retry_non_idempotent.clone();
+            let non_idempotent_for_toggle = retry_non_idempotent.clone();
             dialog
-                .title("Rename Tab")
+                .title("Retries")
                 .child(
                     v_flex()
                         .gap_3()
-                        .child("Enter a new name for this tab:")
-                        .child(Input::new(&input)),
+                        .child(
+                            div().text_sm().text_color(cx.theme().muted_foreground).child(
+                                "GET, HEAD, PUT, DELETE and OPTIONS requests that cannot connect or get a 5xx response are sent again, waiting twice as long before each retry. Timeouts are not retried.",
+                            ),
+                        )
+                        .child("Attempts in total (1–10)")
+                        .child(Input::new(&attempts_input))
+                        .child("First retry delay in milliseconds")
+                        .child(Input::new(&delay_input))
+                        .child(
+                            Checkbox::new("retry-non-idempotent")
+                                .label("Also retry POST and PATCH, which may repeat their effect")
+                                .checked(retry_non_idempotent.get())
+                                .on_click(move |checked, window, _| {
+                                    non_idempotent_for_toggle.set(*checked);
+                                    window.refresh();
+                                }),
+                        ),
                 )
-                .footer({
-                    let input_click = input_for_buttons.clone();
-                    let this_click = this_for_buttons.clone();
-
+                .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("rename-submit")
+                            Button::new("retry-save")
+                                .label("Save")
                                 .primary()
-                                .label("Rename")
                                 .on_click(move |_, window, cx| {
-                                    let new_name = input_click.read(cx).text().to_string();
-                                    this_click.update(cx, |view, cx| {
-                                        view.rename_tab(index, new_name, cx);
+                                    let attempts =
+                                        attempts_for_save.read(cx).text().to_string().trim().parse();
+                                    let delay =
+                                        delay_for_save.read(cx).text().to_string().trim().parse();
+                                    let (Ok(max_attempts), Ok(base_delay_ms)) = (attempts, delay)
+                                    else {
+                                        window.push_notification(
+                                            (
+                                                NotificationType::Error,
+                                                "Attempts and delay must be whole numbers",
+                                            ),
+                                            cx,
+                                        );
+                                        return;
+                                    };
+                                    this_for_save.update(cx, |view, _| {
+                                        view.ui_preferences.retry = RetryPolicy {
+                                            max_attempts: u32::clamp(max_attempts, 1, 10),
+                                            base_delay_ms: u64::clamp(base_delay_ms, 50, 60_000),
+                                            retry_non_idempotent: non_idempotent_for_save.get(),
+                                        };
+                                        view.persist_ui_preferences();
                                     });
                                     close_dialog(window, cx);
                                 }),
                         )
-                        .child(Button::new("rename-cancel").label("Cancel").on_click(
-                            |_, window, cx| {
-                                close_dialog(window, cx);
-                            },
-                        ))
-                })
+                        .child(
+                            Button::new("retry-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
         });
     }
 
-    fn rename_collection_target(
-        &mut self,
-        target: RenameTarget,
-        new_name: String,
-        cx: &mut Context<Self>,
-    ) {
-        let new_name = new_name.trim();
-        if new_name.is_empty() {
-            return;
-        }
-
-        match target {
-            RenameTarget::Collection(collection_id) => {
-                self.collections.update(cx, |collections, cx| {
-                    collections.rename_collection(collection_id, new_name, cx);
-                });
-            }
-            RenameTarget::Node {
-                collection_id,
-                node_id,
-            } => {
-                self.collections.update(cx, |collections, cx| {
-                    collections.rename_node(collection_id, node_id, new_name, cx);
-                });
-            }
-        }
-    }
-
-    fn show_collection_rename_dialog_internal(
-        &mut self,
-        target: RenameTarget,
-        title: &'static str,
-        prompt: &'static str,
-        current_name: String,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        let this = cx.entity().clone();
-        let input = cx.new(|cx| InputState::new(window, cx).default_value(&current_name));
-
-        cx.subscribe_in(&input, window, move |view, state, event, window, cx| {
-            use gpui_component::input::InputEvent;
-            if let InputEvent::PressEnter { .. } = event {
-                view.rename_collection_target(target, state.read(cx).text().to_string(), cx);
-                close_dialog(window, cx);
-            }
-        })
-        .detach();
-
-        let input_for_footer = input.clone();
-        let this_for_footer = this.clone();
-
-        open_dialog(window, cx, move |dialog, _, _| {
-            let input_for_buttons = input_for_footer.clone();
-            let this_for_buttons = this_for_footer.clone();
+    /// Edit the response size above which bodies open on the Raw tab unformatted.
+    fn show_auto_format_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current = self.ui_preferences.auto_format_max_kb;
+        let limit_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("0 formats every response")
+                .default_value(current.to_string())
+        });
 
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let limit_for_save = limit_input.clone();
             dialog
-                .title(title)
-                .child(v_flex().gap_3().child(prompt).child(Input::new(&input)))
-                .footer({
-                    let input_click = input_for_buttons.clone();
-                    let this_click = this_for_buttons.clone();
-
+                .title("Auto-Format Size Limit")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(
+                            div().text_sm().text_color(cx.theme().muted_foreground).child(
+                                "JSON, XML and HTML responses larger than this open on the Raw tab, skipping the cost of pretty-printing them. They can still be formatted from there.",
+                            ),
+                        )
+                        .child("Largest response to format, in KB")
+                        .child(Input::new(&limit_input)),
+                )
+                .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("rename-collection-target-submit")
+                            Button::new("auto-format-save")
+                                .label("Save")
                                 .primary()
-                                .label("Rename")
                                 .on_click(move |_, window, cx| {
-                                    let new_name = input_click.read(cx).text().to_string();
-                                    this_click.update(cx, |view, cx| {
-                                        view.rename_collection_target(target, new_name, cx);
+                                    let Ok(max_kb) =
+                                        limit_for_save.read(cx).text().to_string().trim().parse::<u64>()
+                                    else {
+                                        window.push_notification(
+                                            (
+                                                NotificationType::Error,
+                                                "The size must be a whole number of KB",
+                                            ),
+                                            cx,
+                                        );
+                                        return;
+                                    };
+                                    // The main view mirrors the setting into its preferences.
+                                    cx.set_global(JsonFormatSettings {
+                                        auto_format_max_bytes: (max_kb.min(MAX_AUTO_FORMAT_MAX_KB)
+                                            * 1024)
+                                            as usize,
+                                        ..*cx.global::<JsonFormatSettings>()
                                     });
                                     close_dialog(window, cx);
                                 }),
                         )
                         .child(
-                            Button::new("rename-collection-target-cancel")
+                            Button::new("auto-format-cancel")
                                 .label("Cancel")
-                                .on_click(|_, window, cx| {
-                                    close_dialog(window, cx);
-                                }),
-                        )
-                })
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
         });
     }
 
-    pub fn show_rename_collection_dialog(
-        &mut self,
-        collection_id: Uuid,
-        current_name: String,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        self.show_collection_rename_dialog_internal(
-            RenameTarget::Collection(collection_id),
-            "Rename Collection",
-            "Enter a new name for this collection:",
-            current_name,
-            window,
-            cx,
-        );
-    }
-
-    pub fn show_rename_collection_node_dialog(
-        &mut self,
-        collection_id: Uuid,
-        node_id: Uuid,
-        current_name: String,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        self.show_collection_rename_dialog_internal(
-            RenameTarget::Node {
-                collection_id,
-                node_id,
-            },
-            "Rename Item",
-            "Enter a new name:",
-            current_name,
-            window,
-            cx,
-        );
-    }
-
-    pub fn show_new_folder_dialog(
-        &mut self,
-        collection_id: Uuid,
-        parent_folder_id: Option<Uuid>,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
+    /// Edit the response size above which history keeps only the start of a body.
+    fn show_history_body_limit_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current = self.ui_preferences.history_body_max_kb;
+        let limit_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(DEFAULT_HISTORY_BODY_MAX_KB.to_string())
+                .default_value(current.to_string())
+        });
         let this = cx.entity().clone();
-        let input = cx.new(|cx| InputState::new(window, cx).default_value("New Folder"));
-
-        cx.subscribe_in(&input, window, move |view, state, event, window, cx| {
-            use gpui_component::input::InputEvent;
-            if let InputEvent::PressEnter { .. } = event {
-                let name = state.read(cx).text().to_string().trim().to_string();
-                if !name.is_empty() {
-                    view.create_collection_folder(collection_id, parent_folder_id, &name, cx);
-                }
-                close_dialog(window, cx);
-            }
-        })
-        .detach();
-
-        let input_for_footer = input.clone();
-        let this_for_footer = this.clone();
-
-        open_dialog(window, cx, move |dialog, _, _| {
-            let input_for_buttons = input_for_footer.clone();
-            let this_for_buttons = this_for_footer.clone();
 
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_save = this.clone();
+            let limit_for_save = limit_input.clone();
             dialog
-                .title("New Folder")
+                .title("History Body Size Limit")
                 .child(
                     v_flex()
                         .gap_3()
-                        .child("Enter a name for the new folder:")
-                        .child(Input::new(&input)),
+                        .child(
+                            div().text_sm().text_color(cx.theme().muted_foreground).child(
+                                "Response bodies larger than this are saved to history cut short, with a note giving their full size. Binary bodies over the limit are not kept at all. Entries already in history are left as they are.",
+                            ),
+                        )
+                        .child(format!("Largest body to keep, in KB (1–{MAX_HISTORY_BODY_MAX_KB})"))
+                        .child(Input::new(&limit_input)),
                 )
-                .footer({
-                    let input_click = input_for_buttons.clone();
-                    let this_click = this_for_buttons.clone();
-
+                .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("new-folder-submit")
+                            Button::new("history-body-limit-save")
+                                .label("Save")
                                 .primary()
-                                .label("Create")
                                 .on_click(move |_, window, cx| {
-                                    let name =
-                                        input_click.read(cx).text().to_string().trim().to_string();
-                                    if !name.is_empty() {
-                                        this_click.update(cx, |view, cx| {
-                                            view.create_collection_folder(
-                                                collection_id,
-                                                parent_folder_id,
-                                                &name,
-                                                cx,
-                                            );
-                                        });
-                                    }
+                                    let Ok(max_kb) =
+                                        limit_for_save.read(cx).text().to_string().trim().parse::<u64>()
+                                    else {
+                                        window.push_notification(
+                                            (
+                                                NotificationType::Error,
+                                                "The size must be a whole number of KB",
+                                            ),
+                                            cx,
+                                        );
+                                        return;
+                                    };
+                                    this_for_save.update(cx, |view, cx| {
+                                        view.set_history_body_max_kb(max_kb, cx);
+                                    });
                                     close_dialog(window, cx);
                                 }),
                         )
-                        .child(Button::new("new-folder-cancel").label("Cancel").on_click(
-                            |_, window, cx| {
-                                close_dialog(window, cx);
-                            },
-                        ))
-                })
+                        .child(
+                            Button::new("history-body-limit-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
         });
     }
 
-    pub fn show_move_collection_node_dialog(
-        &mut self,
-        source_collection_id: Uuid,
-        node_id: Uuid,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        let options = {
-            let collections = self.collections.read(cx);
-            Self::destination_options(
-                collections.move_destinations_for_node(source_collection_id, node_id),
-            )
-        };
-
-        if options.is_empty() {
-            window.push_notification(
-                (
-                    NotificationType::Warning,
-                    "No valid destinations are available for this item.",
-                ),
-                cx,
-            );
-            return;
-        }
-
-        let select_state = cx.new(|cx| {
-            SelectState::new(
-                options.clone(),
-                Some(gpui_component::IndexPath::new(0)),
-                window,
-                cx,
-            )
+    fn set_history_body_max_kb(&mut self, max_kb: u64, cx: &mut Context<Self>) {
+        let max_kb = max_kb.clamp(1, MAX_HISTORY_BODY_MAX_KB);
+        self.ui_preferences.history_body_max_kb = max_kb;
+        self.history.update(cx, |history, _| {
+            history.max_response_body_bytes = (max_kb * 1024) as usize;
         });
-        let this = cx.entity().clone();
+        self.persist_ui_preferences();
+    }
 
-        open_dialog(window, cx, move |dialog, _, _| {
-            let select_for_footer = select_state.clone();
-            let this_for_footer = this.clone();
+    /// Edit the header presets offered in the header editor, all in one text field.
+    fn show_header_presets_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current = format_header_presets(&self.ui_preferences.header_presets);
+        let presets_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .code_editor("text")
+                .line_number(true)
+                .placeholder("[JSON client]\nAccept: application/json")
+                .default_value(current)
+        });
 
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let presets_for_save = presets_input.clone();
             dialog
-                .title("Move Item")
+                .title("Header Presets")
+                .w(px(560.0))
                 .child(
                     v_flex()
                         .gap_3()
-                        .child("Choose a new destination:")
-                        .child(Select::new(&select_state).menu_width(px(360.0))),
+                        .child(
+                            div().text_sm().text_color(cx.theme().muted_foreground).child(
+                                "Start each preset with its [name] on a line of its own, then one Header-Name: value per line. Applying a preset from the header editor skips headers the request already has.",
+                            ),
+                        )
+                        .child(div().h(px(260.0)).child(Input::new(&presets_input).h_full())),
                 )
-                .footer({
-                    let select_click = select_for_footer.clone();
-                    let this_click = this_for_footer.clone();
-
+                .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("move-node-submit")
+                            Button::new("header-presets-save")
+                                .label("Save")
                                 .primary()
-                                .label("Move")
                                 .on_click(move |_, window, cx| {
-                                    let Some(selection) =
-                                        select_click.read(cx).selected_value().cloned()
-                                    else {
-                                        return;
-                                    };
-
-                                    let result = this_click.update(cx, |view, cx| {
-                                        view.collections.update(cx, |collections, cx| {
-                                            collections.move_node(
-                                                source_collection_id,
-                                                node_id,
-                                                selection.destination.collection_id,
-                                                selection.destination.folder_id,
-                                                cx,
-                                            )
-                                        })
-                                    });
-
-                                    match result {
-                                        Ok(()) => close_dialog(window, cx),
+                                    let text = presets_for_save.read(cx).text().to_string();
+                                    match parse_header_presets(&text) {
+                                        Ok(presets) => {
+                                            // The main view mirrors the presets into its preferences.
+                                            cx.set_global(HeaderPresets(presets));
+                                            close_dialog(window, cx);
+                                        }
                                         Err(error) => window.push_notification(
-                                            (
-                                                NotificationType::Error,
-                                                SharedString::from(error.to_string()),
-                                            ),
+                                            (NotificationType::Error, SharedString::from(error)),
                                             cx,
                                         ),
                                     }
                                 }),
                         )
-                        .child(Button::new("move-node-cancel").label("Cancel").on_click(
-                            |_, window, cx| {
-                                close_dialog(window, cx);
-                            },
-                        ))
-                })
+                        .child(
+                            Button::new("header-presets-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
         });
     }
 
-    pub fn show_save_to_collection_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let Some(request_data) = self.build_active_request_snapshot(cx) else {
-            window.push_notification(
-                (NotificationType::Warning, "No active request to save."),
-                cx,
-            );
-            return;
-        };
-
-        let destination_options = {
-            let collections = self.collections.read(cx);
-            Self::destination_options(collections.destination_entries())
-        };
-
-        let this = cx.entity().clone();
-        let request_name_input =
-            cx.new(|cx| InputState::new(window, cx).default_value(&request_data.name));
-
-        if destination_options.is_empty() {
-            let collection_name_input =
-                cx.new(|cx| InputState::new(window, cx).default_value("New Collection"));
-            let request_name_for_footer = request_name_input.clone();
-            let collection_name_for_footer = collection_name_input.clone();
-            let this_for_footer = this.clone();
-            let request_for_footer = request_data.clone();
-
-            open_dialog(window, cx, move |dialog, _, _| {
-                let request_name_for_buttons = request_name_for_footer.clone();
-                let collection_name_for_buttons = collection_name_for_footer.clone();
-                let this_for_buttons = this_for_footer.clone();
-                let request_for_buttons = request_for_footer.clone();
-
-                dialog
-                    .title("Save to Collection")
-                    .child(
-                        v_flex()
-                            .gap_3()
-                            .child("No collections exist yet, so a new collection will be created.")
-                            .child("Request name")
-                            .child(Input::new(&request_name_input))
-                            .child("Collection name")
-                            .child(Input::new(&collection_name_input)),
-                    )
-                    .footer({
-                        let request_name_click = request_name_for_buttons.clone();
-                        let collection_name_click = collection_name_for_buttons.clone();
-                        let this_click = this_for_buttons.clone();
-                        let request_click = request_for_buttons.clone();
-
-                        DialogFooter::new()
-                            .child(
-                                Button::new("save-to-new-collection")
-                                    .primary()
-                                    .label("Save")
-                                    .on_click(move |_, window, cx| {
-                                        let request_name = request_name_click
-                                            .read(cx)
-                                            .text()
-                                            .to_string()
-                                            .trim()
-                                            .to_string();
-                                        let collection_name = collection_name_click
-                                            .read(cx)
-                                            .text()
-                                            .to_string()
-                                            .trim()
-                                            .to_string();
-                                        let request_name = if request_name.is_empty() {
-                                            "New Request".to_string()
-                                        } else {
-                                            request_name
-                                        };
-                                        let collection_name = if collection_name.is_empty() {
-                                            "New Collection".to_string()
-                                        } else {
-                                            collection_name
-                                        };
-
-                                        this_click.update(cx, |view, cx| {
-                                            let collection_id =
-                                                view.collections.update(cx, |collections, cx| {
-                                                    collections
-                                                        .create_collection(&collection_name, cx)
-                                                });
-                                            view.save_request_to_destination(
-                                                CollectionDestination {
-                                                    collection_id,
-                                                    folder_id: None,
-                                                },
-                                                request_name,
-                                                request_click.clone(),
-                                                cx,
-                                            );
-                                        });
-                                        close_dialog(window, cx);
-                                    }),
-                            )
-                            .child(
-                                Button::new("save-to-new-collection-cancel")
-                                    .label("Cancel")
-                                    .on_click(|_, window, cx| {
-                                        close_dialog(window, cx);
-                                    }),
-                            )
-                    })
-            });
-            return;
-        }
-
-        let destination_select = cx.new(|cx| {
-            SelectState::new(
-                destination_options.clone(),
-                Some(gpui_component::IndexPath::new(0)),
-                window,
-                cx,
-            )
+    /// Edit how the HTTP client keeps connections open, rebuilding it on save.
+    fn show_pool_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current = self.http_client.pool_config();
+        let idle_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("90")
+                .default_value(current.idle_timeout_secs.to_string())
         });
-        let request_name_for_footer = request_name_input.clone();
-        let destination_for_footer = destination_select.clone();
-        let this_for_footer = this.clone();
-        let request_for_footer = request_data.clone();
-
-        open_dialog(window, cx, move |dialog, _, _| {
-            let request_name_for_buttons = request_name_for_footer.clone();
-            let destination_for_buttons = destination_for_footer.clone();
-            let this_for_buttons = this_for_footer.clone();
-            let request_for_buttons = request_for_footer.clone();
+        let per_host_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("No limit")
+                .default_value(
+                    current
+                        .max_idle_per_host
+                        .map(|limit| limit.to_string())
+                        .unwrap_or_default(),
+                )
+        });
+        let keepalive_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("60")
+                .default_value(current.tcp_keepalive_secs.to_string())
+        });
+        let this = cx.entity().clone();
 
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_save = this.clone();
+            let idle_for_save = idle_input.clone();
+            let per_host_for_save = per_host_input.clone();
+            let keepalive_for_save = keepalive_input.clone();
+            let this_for_reset = this.clone();
             dialog
-                .title("Save to Collection")
+                .title("Connection Pool")
                 .child(
                     v_flex()
                         .gap_3()
-                        .child("Request name")
-                        .child(Input::new(&request_name_input))
-                        .child("Destination")
-                        .child(Select::new(&destination_select).menu_width(px(360.0))),
+                        .child(
+                            div().text_sm().text_color(cx.theme().muted_foreground).child(
+                                "Defaults keep idle connections for 90 seconds with no per-host limit and probe them every 60 seconds. Saving closes the idle connections of the current pool.",
+                            ),
+                        )
+                        .child("Idle timeout in seconds (0 closes idle connections)")
+                        .child(Input::new(&idle_input))
+                        .child("Idle connections per host (empty for no limit)")
+                        .child(Input::new(&per_host_input))
+                        .child("TCP keep-alive interval in seconds (0 turns it off)")
+                        .child(Input::new(&keepalive_input)),
                 )
-                .footer({
-                    let request_name_click = request_name_for_buttons.clone();
-                    let destination_click = destination_for_buttons.clone();
-                    let this_click = this_for_buttons.clone();
-                    let request_click = request_for_buttons.clone();
-
+                .footer(
                     DialogFooter::new()
                         .child(
-                            Button::new("save-to-collection-submit")
-                                .primary()
+                            Button::new("pool-save")
                                 .label("Save")
+                                .primary()
                                 .on_click(move |_, window, cx| {
-                                    let Some(selection) =
-                                        destination_click.read(cx).selected_value().cloned()
-                                    else {
-                                        return;
+                                    let text = |input: &Entity<InputState>, cx: &App| {
+                                        input.read(cx).text().to_string().trim().to_string()
                                     };
-
-                                    let request_name = request_name_click
-                                        .read(cx)
-                                        .text()
-                                        .to_string()
-                                        .trim()
-                                        .to_string();
-                                    let request_name = if request_name.is_empty() {
-                                        "New Request".to_string()
+                                    let per_host = text(&per_host_for_save, cx);
+                                    let per_host = if per_host.is_empty() {
+                                        Ok(None)
                                     } else {
-                                        request_name
+                                        per_host.parse().map(Some)
                                     };
-
-                                    this_click.update(cx, |view, cx| {
-                                        view.save_request_to_destination(
-                                            selection.destination,
-                                            request_name,
-                                            request_click.clone(),
+                                    let (Ok(idle), Ok(max_idle_per_host), Ok(keepalive)) = (
+                                        text(&idle_for_save, cx).parse::<u64>(),
+                                        per_host,
+                                        text(&keepalive_for_save, cx).parse::<u64>(),
+                                    ) else {
+                                        window.push_notification(
+                                            (
+                                                NotificationType::Error,
+                                                "Pool settings must be whole numbers",
+                                            ),
                                             cx,
                                         );
+                                        return;
+                                    };
+                                    let pool = PoolConfig {
+                                        idle_timeout_secs: idle.min(3_600),
+                                        max_idle_per_host,
+                                        tcp_keepalive_secs: keepalive.min(3_600),
+                                    };
+                                    this_for_save.update(cx, |view, cx| {
+                                        view.set_pool_config(pool, window, cx);
                                     });
                                     close_dialog(window, cx);
                                 }),
                         )
                         .child(
-                            Button::new("save-to-collection-cancel")
-                                .label("Cancel")
-                                .on_click(|_, window, cx| {
+                            Button::new("pool-reset")
+                                .label("Use Defaults")
+                                .on_click(move |_, window, cx| {
+                                    this_for_reset.update(cx, |view, cx| {
+                                        view.set_pool_config(PoolConfig::default(), window, cx);
+                                    });
                                     close_dialog(window, cx);
                                 }),
                         )
-                })
+                        .child(
+                            Button::new("pool-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
         });
     }
 
-    pub fn import_collection_from_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let this = cx.entity().clone();
-        let options = PathPromptOptions {
-            files: true,
-            directories: false,
-            multiple: false,
-            prompt: Some("Select a Postman collection or environment".into()),
-        };
-        let paths_receiver = cx.prompt_for_paths(options);
+    fn set_pool_config(&mut self, pool: PoolConfig, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = self.http_client.set_pool_config(pool) {
+            window.push_notification(
+                (
+                    NotificationType::Error,
+                    SharedString::from(format!("Failed to update connection pool: {error}")),
+                ),
+                cx,
+            );
+            return;
+        }
+        self.ui_preferences.pool = pool;
+        self.persist_ui_preferences();
+        window.push_notification(
+            (
+                NotificationType::Info,
+                SharedString::from(format!("Connection pool: {}", pool.describe())),
+            ),
+            cx,
+        );
+    }
 
-        cx.spawn_in(window, async move |_weak_this, cx| {
-            let Ok(platform_result) = paths_receiver.await else {
-                log::error!("Collection import file picker channel closed unexpectedly");
-                return;
-            };
+    /// Choose the file the active request writes each response body to.
+    fn show_auto_save_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(request) = self.active_tab().and_then(|tab| tab.request()).cloned() else {
+            window.push_notification(
+                (NotificationType::Info, "Auto-save works with HTTP requests"),
+                cx,
+            );
+            return;
+        };
+        let current = request.read(cx).auto_save().cloned();
+        let path_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("/path/to/response.json")
+                .default_value(
+                    current
+                        .as_ref()
+                        .map(|auto_save| auto_save.path.clone())
+                        .unwrap_or_default(),
+                )
+        });
+        let timestamped = Rc::new(Cell::new(
+            current
+                .as_ref()
+                .is_some_and(|auto_save| auto_save.timestamped),
+        ));
+        let is_on = current.is_some();
+        let this = cx.entity().clone();
 
-            let Ok(paths_opt) = platform_result else {
-                let _ = cx.update(|window, app| {
-                    window.push_notification(
-                        (NotificationType::Error, "Failed to open file picker"),
-                        app,
-                    );
-                });
-                return;
-            };
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_save = this.clone();
+            let request_for_save = request.clone();
+            let path_for_save = path_input.clone();
+            let timestamped_for_save = timestamped.clone();
+            let timestamped_for_toggle = timestamped.clone();
+            let this_for_off = this.clone();
+            let request_for_off = request.clone();
+            dialog
+                .title("Auto-Save Responses")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(
+                            div().text_sm().text_color(cx.theme().muted_foreground).child(
+                                "Every response this request receives is written to the file, bytes as they arrived.",
+                            ),
+                        )
+                        .child("File")
+                        .child(Input::new(&path_input))
+                        .child(
+                            Checkbox::new("auto-save-timestamped")
+                                .label("Add the time to the file name to keep every response")
+                                .checked(timestamped.get())
+                                .on_click(move |checked, window, _| {
+                                    timestamped_for_toggle.set(*checked);
+                                    window.refresh();
+                                }),
+                        ),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("auto-save-save")
+                                .label("Save")
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    let auto_save = ResponseAutoSave {
+                                        path: path_for_save.read(cx).text().trim().to_string(),
+                                        timestamped: timestamped_for_save.get(),
+                                    };
+                                    this_for_save.update(cx, |view, cx| {
+                                        view.set_auto_save(
+                                            &request_for_save,
+                                            Some(auto_save),
+                                            window,
+                                            cx,
+                                        );
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .when(is_on, |footer| {
+                            footer.child(
+                                Button::new("auto-save-off")
+                                    .label("Turn Off")
+                                    .on_click(move |_, window, cx| {
+                                        this_for_off.update(cx, |view, cx| {
+                                            view.set_auto_save(&request_for_off, None, window, cx);
+                                        });
+                                        close_dialog(window, cx);
+                                    }),
+                            )
+                        })
+                        .child(
+                            Button::new("auto-save-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
 
-            let Some(paths) = paths_opt else {
-                return;
-            };
-            let Some(path) = paths.first().cloned() else {
+    fn set_auto_save(
+        &mut self,
+        request: &Entity<RequestEntity>,
+        auto_save: Option<ResponseAutoSave>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(problem) = auto_save.as_ref().and_then(ResponseAutoSave::problem) {
+            window.push_notification(
+                (
+                    NotificationType::Warning,
+                    SharedString::from(format!("Responses will not be saved: {problem}")),
+                ),
+                cx,
+            );
+            return;
+        }
+        let message = match &auto_save {
+            Some(auto_save) => format!("Responses will be saved to {}", auto_save.path),
+            None => "Responses are no longer saved automatically".to_string(),
+        };
+        request.update(cx, |request, cx| request.set_auto_save(auto_save, cx));
+        window.push_notification((NotificationType::Success, SharedString::from(message)), cx);
+    }
+
+    /// Write the body of `data` where `auto_save` points, warning if the write fails.
+    fn auto_save_response(
+        &self,
+        auto_save: &ResponseAutoSave,
+        data: &ResponseData,
+        cx: &mut Context<Self>,
+    ) {
+        let path = auto_save.target_path(chrono::Local::now());
+        let contents = data.file_contents();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+            let result = std::fs::write(&path, contents)
+                .map_err(|error| format!("Could not auto-save to {}: {error}", path.display()));
+            let _ = tx.send(result);
+        });
+        cx.spawn(async move |_view, cx| {
+            let Ok(Err(message)) = rx.await else {
                 return;
             };
+            log::warn!("{message}");
+            let _ = cx.update(|app| {
+                if let Some(window) = app.active_window() {
+                    let _ = window.update(app, |_, window, cx| {
+                        window.push_notification(
+                            (NotificationType::Warning, SharedString::from(message)),
+                            cx,
+                        );
+                    });
+                }
+            });
+        })
+        .detach();
+    }
 
-            let result = ImportRegistry::default().import_any_file(&path);
-            let _ = cx.update(|window, app| match result {
-                Ok(result) => {
-                    this.update(app, |view, cx| {
-                        let summary = match result.payload {
-                            ImportedPayload::Collection(collection) => {
-                                let name = collection.name.clone();
-                                let folder_count = collection.folder_count();
-                                let request_count = collection.request_count();
-                                let variables = collection.variables.clone();
-                                let variable_count = variables.len();
+    /// Store the values `rules` pick out of `data` in the active environment and say what
+    /// changed in `window`, masking secret variables.
+    fn apply_extractions(
+        &mut self,
+        rules: &str,
+        collection_id: Option<Uuid>,
+        data: &ResponseData,
+        window: Option<AnyWindowHandle>,
+        cx: &mut Context<Self>,
+    ) {
+        let extractions = run_extractions(rules, data);
+        let environment_id = self
+            .environments
+            .read(cx)
+            .active_environment_id(collection_id);
+        let (notification_type, message) = match environment_id {
+            None => (
+                NotificationType::Warning,
+                "Activate an environment to store extracted values".to_string(),
+            ),
+            Some(environment_id) => {
+                let values: Vec<_> = extractions
+                    .iter()
+                    .filter_map(|extraction| {
+                        let value = extraction.value.as_ref().ok()?;
+                        Some((
+                            extraction.variable.clone(),
+                            value.clone(),
+                            extraction.secret,
+                        ))
+                    })
+                    .collect();
+                if !values.is_empty() {
+                    self.environments.update(cx, |environments, cx| {
+                        environments.set_variable_values(environment_id, &values, cx)
+                    });
+                }
 
-                                let workspace_id = view.workspaces.update(cx, |workspaces, cx| {
-                                    workspaces.create_workspace(name.clone(), cx)
-                                });
-                                view.switch_workspace(workspace_id, cx);
+                let environment = self.environments.read(cx).get(environment_id);
+                let is_secret = |key: &str| {
+                    environment.is_some_and(|environment| {
+                        environment
+                            .variables
+                            .iter()
+                            .any(|variable| variable.secret && variable.key.trim() == key)
+                    })
+                };
+                let mut failed = false;
+                let lines: Vec<_> = extractions
+                    .iter()
+                    .map(|extraction| match &extraction.value {
+                        Ok(_) if is_secret(&extraction.variable) => {
+                            format!("{} = ••••••", extraction.variable)
+                        }
+                        Ok(value) => {
+                            let preview: String = value.chars().take(40).collect();
+                            let ellipsis = if preview.len() < value.len() {
+                                "…"
+                            } else {
+                                ""
+                            };
+                            format!("{} = {preview}{ellipsis}", extraction.variable)
+                        }
+                        Err(error) => {
+                            failed = true;
+                            format!("{}: {error}", extraction.variable)
+                        }
+                    })
+                    .collect();
+                let notification_type = if failed {
+                    NotificationType::Warning
+                } else {
+                    NotificationType::Success
+                };
+                (notification_type, lines.join("\n"))
+            }
+        };
 
-                                let collection_id =
-                                    view.collections.update(cx, |collections, cx| {
-                                        collections.import_collection(collection, cx)
-                                    });
-                                if !variables.is_empty() {
-                                    view.environments.update(cx, |environments, cx| {
-                                        environments.import_environment(
-                                            format!("{name} Variables"),
-                                            EnvironmentScope::Project(collection_id),
-                                            variables
-                                                .into_iter()
-                                                .map(|variable| EnvironmentVariable {
-                                                    key: variable.key,
-                                                    value: variable.value,
-                                                    enabled: variable.enabled,
-                                                    secret: variable.secret,
-                                                    ..EnvironmentVariable::default()
-                                                })
-                                                .collect(),
+        let Some(window) = window else {
+            return;
+        };
+        cx.spawn(async move |_view, cx| {
+            let _ = window.update(cx, |_, window, cx| {
+                window.push_notification((notification_type, SharedString::from(message)), cx);
+            });
+        })
+        .detach();
+    }
+
+    /// Edit the client certificate and CA bundle of `environment_id`, showing the loaded
+    /// certificate's name and expiry.
+    pub fn show_client_certificate_dialog(
+        &mut self,
+        environment_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(environment) = self.environments.read(cx).get(environment_id) else {
+            return;
+        };
+        let title = format!("Client Certificate for {}", environment.name);
+        let current = environment.tls.clone().unwrap_or_default();
+        // Verification is toggled on its own, so the dialog leaves it as it is.
+        let accept_invalid_certs = current.accept_invalid_certs;
+        let has_files =
+            !current.client_cert.trim().is_empty() || !current.ca_bundle.trim().is_empty();
+        // Filled in once the files are read; `None` while they still are.
+        let status: Rc<RefCell<Option<Result<String, String>>>> = Rc::default();
+        if has_files {
+            let loaded = load_tls_in_background(current.clone());
+            let status = status.clone();
+            cx.spawn_in(window, async move |_, cx| {
+                let result = loaded
+                    .await
+                    .unwrap_or_else(|_| Err("Certificate loader stopped unexpectedly".to_string()))
+                    .map(|certificate| {
+                        certificate.map_or_else(
+                            || "Only a CA bundle is configured".to_string(),
+                            |certificate| certificate.describe(chrono::Utc::now()),
+                        )
+                    });
+                status.replace(Some(result));
+                let _ = cx.update(|window, _| window.refresh());
+            })
+            .detach();
+        }
+        let cert_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("/path/to/client.pem or client.p12")
+                .default_value(current.client_cert)
+        });
+        let key_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("/path/to/client.key (when not in the certificate file)")
+                .default_value(current.client_key)
+        });
+        let password_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("PKCS#12 password (optional)")
+                .masked(true)
+                .default_value(current.pkcs12_password)
+        });
+        let ca_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("/path/to/ca-bundle.pem (optional)")
+                .default_value(current.ca_bundle)
+        });
+        let this = cx.entity().clone();
+
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_save = this.clone();
+            let this_for_remove = this.clone();
+            let inputs = (
+                cert_input.clone(),
+                key_input.clone(),
+                password_input.clone(),
+                ca_input.clone(),
+            );
+            let theme = cx.theme();
+            let status = has_files.then(|| match &*status.borrow() {
+                None => div()
+                    .text_sm()
+                    .text_color(theme.muted_foreground)
+                    .child("Loading certificate…"),
+                Some(Ok(summary)) => div()
+                    .text_sm()
+                    .text_color(theme.success)
+                    .child(format!("Loaded: {summary}")),
+                Some(Err(error)) => div()
+                    .text_sm()
+                    .text_color(theme.danger)
+                    .child(error.clone()),
+            });
+
+            dialog
+                .title(title.clone())
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(
+                            div().text_sm().text_color(theme.muted_foreground).child(
+                                "Presented to servers that require mutual TLS while this environment is active. PEM files and PKCS#12 (.p12, .pfx) bundles are supported.",
+                            ),
+                        )
+                        .children(status)
+                        .child("Certificate")
+                        .child(Input::new(&cert_input))
+                        .child("Private key")
+                        .child(Input::new(&key_input))
+                        .child("Bundle password")
+                        .child(Input::new(&password_input))
+                        .child("CA bundle")
+                        .child(Input::new(&ca_input)),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("client-certificate-save")
+                                .label("Save")
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    let (cert, key, password, ca) = &inputs;
+                                    let tls = TlsConfig {
+                                        client_cert: cert.read(cx).text().to_string(),
+                                        client_key: key.read(cx).text().to_string(),
+                                        pkcs12_password: password.read(cx).text().to_string(),
+                                        ca_bundle: ca.read(cx).text().to_string(),
+                                        accept_invalid_certs,
+                                    };
+                                    this_for_save.update(cx, |view, cx| {
+                                        view.save_client_certificate(
+                                            environment_id,
+                                            tls,
+                                            window,
                                             cx,
                                         );
                                     });
-                                }
-                                ImportSummary {
-                                    provider: result.provider,
-                                    item_kind: "Workspace",
-                                    item_name: name,
-                                    folder_count: Some(folder_count),
-                                    request_count: Some(request_count),
-                                    variable_count,
-                                    warnings: result.warnings,
-                                }
-                            }
-                            ImportedPayload::Environment(environment) => {
-                                let name = environment.name.clone();
-                                let variable_count = environment.variables.len();
-                                let environment_id =
-                                    view.environments.update(cx, |environments, cx| {
-                                        environments.import_environment(
-                                            name.clone(),
-                                            EnvironmentScope::Workspace,
-                                            environment
-                                                .variables
-                                                .into_iter()
-                                                .map(|variable| EnvironmentVariable {
-                                                    key: variable.key,
-                                                    value: variable.value,
-                                                    enabled: variable.enabled,
-                                                    secret: variable.secret,
-                                                    ..EnvironmentVariable::default()
-                                                })
-                                                .collect(),
-                                            cx,
-                                        )
+                                }),
+                        )
+                        .child(
+                            Button::new("client-certificate-remove")
+                                .label("Remove")
+                                .on_click(move |_, window, cx| {
+                                    let tls = TlsConfig {
+                                        accept_invalid_certs,
+                                        ..TlsConfig::default()
+                                    };
+                                    let tls = (!tls.is_empty()).then_some(tls);
+                                    this_for_remove.update(cx, |view, cx| {
+                                        view.environments.update(cx, |environments, cx| {
+                                            environments.set_environment_tls(
+                                                environment_id,
+                                                tls,
+                                                cx,
+                                            );
+                                        });
                                     });
-                                view.environment_panel.update(cx, |panel, cx| {
-                                    panel.select_environment(environment_id, cx);
-                                });
-                                view.sidebar_visible = true;
-                                view.sidebar_tab = SidebarTab::Environments;
-                                ImportSummary {
-                                    provider: result.provider,
-                                    item_kind: "Environment",
-                                    item_name: name,
-                                    folder_count: None,
-                                    request_count: None,
-                                    variable_count,
-                                    warnings: result.warnings,
-                                }
-                            }
-                        };
-                        view.show_import_summary_dialog(summary, window, cx);
-                    });
-                }
-                Err(error) => {
-                    window.push_notification(
-                        (
-                            NotificationType::Error,
-                            SharedString::from(format!("Import failed: {error}")),
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("client-certificate-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
                         ),
-                        app,
-                    );
-                }
+                )
+        });
+    }
+
+    /// Check that `tls`'s files load, then store it on `environment_id` and close the
+    /// certificate dialog; a bad file is reported and leaves the dialog open.
+    fn save_client_certificate(
+        &mut self,
+        environment_id: Uuid,
+        tls: TlsConfig,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let has_files = !tls.client_cert.trim().is_empty() || !tls.ca_bundle.trim().is_empty();
+        let loaded = has_files.then(|| load_tls_in_background(tls.clone()));
+        cx.spawn_in(window, async move |this, cx| {
+            let message = match loaded {
+                None => "Client certificate removed".to_string(),
+                Some(loaded) => match loaded
+                    .await
+                    .unwrap_or_else(|_| Err("Certificate loader stopped unexpectedly".to_string()))
+                {
+                    Ok(certificate) => certificate.map_or_else(
+                        || "CA bundle loaded".to_string(),
+                        |certificate| {
+                            format!(
+                                "Client certificate loaded: {}",
+                                certificate.describe(chrono::Utc::now())
+                            )
+                        },
+                    ),
+                    Err(error) => {
+                        let _ = cx.update(|window, app| {
+                            window.push_notification(
+                                (NotificationType::Error, SharedString::from(error)),
+                                app,
+                            );
+                        });
+                        return;
+                    }
+                },
+            };
+            let tls = (!tls.is_empty()).then_some(tls);
+            let _ = cx.update(|window, app| {
+                let _ = this.update(app, |view, cx| {
+                    view.environments.update(cx, |environments, cx| {
+                        environments.set_environment_tls(environment_id, tls, cx);
+                    });
+                });
+                window
+                    .push_notification((NotificationType::Info, SharedString::from(message)), app);
+                close_dialog(window, app);
+            });
+        })
+        .detach();
+    }
+
+    /// The environment the active tab resolves against, telling the user to activate one
+    /// when there is none.
+    fn active_environment_or_notify(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Uuid> {
+        let collection_id = self
+            .tabs
+            .get(self.active_tab_index)
+            .and_then(|tab| tab.collection_id);
+        let environment_id = self
+            .environments
+            .read(cx)
+            .active_environment_id(collection_id);
+        if environment_id.is_none() {
+            window.push_notification(
+                (NotificationType::Info, "Activate an environment first"),
+                cx,
+            );
+        }
+        environment_id
+    }
+
+    /// Whether requests of the active tab skip certificate verification.
+    fn active_tab_accepts_invalid_certs(&self, cx: &App) -> bool {
+        let collection_id = self
+            .tabs
+            .get(self.active_tab_index)
+            .and_then(|tab| tab.collection_id);
+        self.environments
+            .read(cx)
+            .active_environment(collection_id)
+            .and_then(|environment| environment.tls.as_ref())
+            .is_some_and(|tls| tls.accept_invalid_certs)
+    }
+
+    fn toggle_insecure_tls(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(environment_id) = self.active_environment_or_notify(window, cx) else {
+            return;
+        };
+        let Some((name, accept)) =
+            self.environments
+                .read(cx)
+                .get(environment_id)
+                .map(|environment| {
+                    let accepts = environment
+                        .tls
+                        .as_ref()
+                        .is_some_and(|tls| tls.accept_invalid_certs);
+                    (environment.name.clone(), !accepts)
+                })
+        else {
+            return;
+        };
+        self.environments.update(cx, |environments, cx| {
+            environments.set_environment_accepts_invalid_certs(environment_id, accept, cx);
+        });
+        let notification = if accept {
+            (
+                NotificationType::Warning,
+                format!("Certificate verification is off for {name}"),
+            )
+        } else {
+            (
+                NotificationType::Info,
+                format!("Certificate verification is back on for {name}"),
+            )
+        };
+        window.push_notification((notification.0, SharedString::from(notification.1)), cx);
+    }
+
+    /// Delete a collection
+    pub fn delete_collection(&mut self, collection_id: Uuid, cx: &mut Context<Self>) {
+        self.collections.update(cx, |collections, cx| {
+            collections.remove_collection(collection_id, cx);
+        });
+        self.environments.update(cx, |environments, cx| {
+            environments.remove_project_environments(collection_id, cx);
+        });
+        for tab in &mut self.tabs {
+            if tab.collection_id == Some(collection_id) {
+                tab.collection_id = None;
+                tab.collection_node_id = None;
+            }
+        }
+    }
+
+    /// Delete an item from a collection
+    pub fn delete_collection_node(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        cx: &mut Context<Self>,
+    ) {
+        self.collections.update(cx, |collections, cx| {
+            collections.remove_node(collection_id, node_id, cx);
+        });
+        for tab in &mut self.tabs {
+            if tab.collection_node_id == Some(node_id) {
+                tab.collection_node_id = None;
+            }
+        }
+    }
+
+    /// Toggle collection expanded state
+    pub fn toggle_collection_expand(&mut self, collection_id: Uuid, cx: &mut Context<Self>) {
+        self.collections.update(cx, |collections, cx| {
+            collections.toggle_collection_expanded(collection_id, cx);
+        });
+    }
+
+    pub fn toggle_collection_node_expand(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        cx: &mut Context<Self>,
+    ) {
+        self.collections.update(cx, |collections, cx| {
+            collections.toggle_node_expanded(collection_id, node_id, cx);
+        });
+    }
+
+    pub fn create_collection_folder(
+        &mut self,
+        collection_id: Uuid,
+        parent_folder_id: Option<Uuid>,
+        name: &str,
+        cx: &mut Context<Self>,
+    ) {
+        self.collections.update(cx, |collections, cx| {
+            collections.create_folder(collection_id, parent_folder_id, name, cx);
+        });
+    }
+
+    pub fn save_request_to_destination(
+        &mut self,
+        destination: CollectionDestination,
+        request_name: String,
+        request_data: RequestData,
+        cx: &mut Context<Self>,
+    ) {
+        let tab_id = self.active_tab().map(|tab| tab.id);
+        self.save_request_for_tab(tab_id, destination, request_name, request_data, cx);
+    }
+
+    /// Add `request_data` to a collection and link `tab_id`'s tab to the new node.
+    fn save_request_for_tab(
+        &mut self,
+        tab_id: Option<TabId>,
+        destination: CollectionDestination,
+        request_name: String,
+        request_data: RequestData,
+        cx: &mut Context<Self>,
+    ) {
+        let mut request_data = request_data;
+        request_data.name = request_name;
+        let node_id = self.collections.update(cx, |collections, cx| {
+            collections.add_request_node(
+                destination.collection_id,
+                destination.folder_id,
+                request_data,
+                cx,
+            )
+        });
+        if let Some(tab) = tab_id.and_then(|id| self.tabs.iter_mut().find(|tab| tab.id == id)) {
+            tab.collection_id = Some(destination.collection_id);
+            tab.collection_node_id = node_id;
+        }
+        cx.notify();
+    }
+
+    /// Overwrite the saved request behind the active tab, keeping the old version.
+    pub fn update_saved_request(
+        &mut self,
+        note: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(tab_id) = self
+            .active_tab()
+            .filter(|tab| tab.collection_node_id.is_some())
+            .map(|tab| tab.id)
+        else {
+            return;
+        };
+        let Some(request_data) = self.build_active_request_snapshot(cx) else {
+            return;
+        };
+
+        if self.update_saved_request_for_tab(tab_id, request_data, note, cx) {
+            window.push_notification((NotificationType::Success, "Saved request updated"), cx);
+        } else {
+            window.push_notification(
+                (
+                    NotificationType::Warning,
+                    "The saved request no longer exists.",
+                ),
+                cx,
+            );
+        }
+    }
+
+    /// Overwrite the saved request `tab_id` is linked to, keeping its id and name. Returns
+    /// `false`, and unlinks the tab, when that request no longer exists.
+    fn update_saved_request_for_tab(
+        &mut self,
+        tab_id: TabId,
+        mut request_data: RequestData,
+        note: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(tab_index) = self.tabs.iter().position(|tab| tab.id == tab_id) else {
+            return false;
+        };
+        let Some((collection_id, node_id)) = self.tabs[tab_index]
+            .collection_id
+            .zip(self.tabs[tab_index].collection_node_id)
+        else {
+            return false;
+        };
+        if let Some(saved) = self
+            .collections
+            .read(cx)
+            .get_request_node(collection_id, node_id)
+        {
+            request_data.id = saved.request.id;
+            request_data.name = saved.request.name.clone();
+        }
+
+        let updated = self.collections.update(cx, |collections, cx| {
+            collections.update_request_node(collection_id, node_id, request_data, note, cx)
+        });
+        if !updated {
+            self.tabs[tab_index].collection_node_id = None;
+            cx.notify();
+        }
+        updated
+    }
+
+    /// Save the active tab: over the request it was opened from when there is one, otherwise
+    /// into a collection picked in the save dialog.
+    pub fn save_active_request(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let is_saved = self
+            .active_tab()
+            .is_some_and(|tab| tab.collection_node_id.is_some());
+        if is_saved {
+            self.show_update_saved_request_dialog(window, cx);
+        } else {
+            self.show_save_to_collection_dialog(window, cx);
+        }
+    }
+
+    pub fn show_update_saved_request_dialog(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let note_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("What changed? (optional)"));
+        let this = cx.entity().clone();
+        let note_for_footer = note_input.clone();
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_update = this.clone();
+            let note_for_update = note_for_footer.clone();
+            dialog
+                .title("Update Saved Request")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(format!(
+                                    "The current version is kept so you can revert. Up to {MAX_REQUEST_VERSIONS} previous versions are stored."
+                                )),
+                        )
+                        .child("Change note")
+                        .child(Input::new(&note_input)),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("update-saved-request-confirm")
+                                .label("Update")
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    let note = note_for_update.read(cx).text().to_string();
+                                    this_for_update.update(cx, |view, cx| {
+                                        view.update_saved_request(Some(note), window, cx);
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("update-saved-request-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
+
+    pub fn revert_collection_node(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let reverted = self.collections.update(cx, |collections, cx| {
+            collections.revert_request_node(collection_id, node_id, cx)
+        });
+        if reverted {
+            window.push_notification(
+                (
+                    NotificationType::Success,
+                    "Reverted to the previous version",
+                ),
+                cx,
+            );
+        } else {
+            window.push_notification(
+                (NotificationType::Info, "No previous version to revert to"),
+                cx,
+            );
+        }
+    }
+
+    pub fn redo_revert_collection_node(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let restored = self.collections.update(cx, |collections, cx| {
+            collections.redo_revert_request_node(collection_id, node_id, cx)
+        });
+        if restored {
+            window.push_notification(
+                (NotificationType::Success, "Restored the reverted version"),
+                cx,
+            );
+        } else {
+            window.push_notification((NotificationType::Info, "No revert to redo"), cx);
+        }
+    }
+
+    fn build_active_request_snapshot(&mut self, cx: &mut Context<Self>) -> Option<RequestData> {
+        self.build_request_snapshot_for_tab(self.active_tab_index, cx)
+    }
+
+    fn build_request_snapshot_for_tab(
+        &mut self,
+        tab_index: usize,
+        cx: &mut Context<Self>,
+    ) -> Option<RequestData> {
+        let (tab_name, is_custom_name, request_entity, request_view, url_input) = {
+            let tab = self.tabs.get(tab_index)?;
+            if let TabContent::Request {
+                request,
+                request_view,
+                url_input,
+                ..
+            } = &tab.content
+            {
+                (
+                    tab.name.clone(),
+                    tab.is_custom_name,
+                    request.clone(),
+                    request_view.clone(),
+                    url_input.clone(),
+                )
+            } else {
+                return None;
+            }
+        };
+
+        request_view.update(cx, |view, cx| {
+            view.sync_body_to_request(cx);
+            view.sync_headers_to_request(cx);
+        });
+
+        let final_url = if let Some(ref input) = url_input {
+            input.read(cx).text().to_string()
+        } else {
+            request_entity.read(cx).url().to_string()
+        };
+
+        request_entity.update(cx, |request, cx| {
+            request.set_url(final_url.clone(), cx);
+        });
+
+        let request = request_entity.read(cx);
+        let snapshot_name = if is_custom_name {
+            tab_name
+        } else {
+            Self::derive_request_display_name(request.method(), &final_url)
+        };
+        Some(RequestData {
+            id: Uuid::new_v4(),
+            name: snapshot_name,
+            url: final_url,
+            method: request.method(),
+            headers: request.headers().to_vec(),
+            body: request.body().clone(),
+            preserve_header_order: request.preserve_header_order(),
+            inherit_auth: request.inherit_auth(),
+            assertions: request.assertions().to_string(),
+            pre_request_script: request.pre_request_script().to_string(),
+            extractions: request.extractions().to_string(),
+            disabled_params: request.disabled_params().to_vec(),
+            subprotocols: request.subprotocols().to_string(),
+            auto_save: request.auto_save().cloned(),
+            is_sending: false,
+        })
+    }
+
+    fn extract_url_path(url: &str) -> Option<String> {
+        let url = url.trim();
+        if url.is_empty() {
+            return None;
+        }
+
+        let path = if let Some(after_scheme) = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+        {
+            after_scheme
+                .find('/')
+                .map(|i| &after_scheme[i..])
+                .unwrap_or("/")
+        } else if url.starts_with('/') {
+            url
+        } else {
+            url.find('/').map(|i| &url[i..]).unwrap_or(url)
+        };
+
+        let path = path.split('?').next().unwrap_or(path);
+
+        if path.len() > 32 {
+            Some(path[..32].to_string())
+        } else {
+            Some(path.to_string())
+        }
+    }
+
+    /// Tab display name — just the path, since the tab bar already shows a method badge
+    fn derive_tab_name(url: &str) -> String {
+        Self::extract_url_path(url).unwrap_or_else(|| "New Request".to_string())
+    }
+
+    /// Full display name for history entries and saved requests — includes method prefix
+    fn derive_request_display_name(method: HttpMethod, url: &str) -> String {
+        match Self::extract_url_path(url) {
+            Some(path) => format!("{} {}", method.as_str(), path),
+            None => "New Request".to_string(),
+        }
+    }
+
+    fn destination_options(entries: Vec<CollectionDestinationEntry>) -> Vec<DestinationOption> {
+        entries
+            .into_iter()
+            .map(|entry| DestinationOption {
+                destination: entry.destination,
+                label: entry.label,
+            })
+            .collect()
+    }
+
+    fn active_tab(&self) -> Option<&TabState> {
+        self.tabs.get(self.active_tab_index)
+    }
+
+    /// Whether `tab_id` is still the active tab, for an `action` a dialog started on it.
+    /// The user may switch tabs while the dialog is open; if so, they are told the action
+    /// was skipped.
+    fn is_active_tab(
+        &self,
+        tab_id: TabId,
+        action: &str,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> bool {
+        let active = self.active_tab().is_some_and(|tab| tab.id == tab_id);
+        if !active {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    SharedString::from(format!(
+                        "{action} skipped: its tab is no longer the active one"
+                    )),
+                ),
+                cx,
+            );
+        }
+        active
+    }
+
+    fn cancel_in_flight_for_tab(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(tab) = self.tabs.get_mut(index)
+            && let TabContent::Request {
+                request,
+                response,
+                in_flight_request,
+                request_generation,
+                ..
+            } = &mut tab.content
+            && let Some(mut in_flight) = in_flight_request.take()
+        {
+            let _ = in_flight.cancel();
+            request_generation.advance();
+            request.update(cx, |req, cx| req.set_sending(false, cx));
+            response.update(cx, |resp, cx| resp.stop(cx));
+        }
+    }
+
+    fn cancel_in_flight_for_all_tabs(&mut self, cx: &mut Context<Self>) {
+        for index in 0..self.tabs.len() {
+            self.cancel_in_flight_for_tab(index, cx);
+        }
+    }
+
+    /// Add a new tab
+    pub fn new_tab(&mut self, cx: &mut Context<Self>) {
+        let request = cx.new(|_| RequestEntity::new());
+        let response = cx.new(|_| ResponseEntity::new());
+        let method_dropdown = cx.new(|_| MethodDropdownState::new(HttpMethod::Get));
+        let completion_engine = self.completion_engine.clone();
+        let request_view = cx.new(|cx| {
+            RequestView::new(request.clone(), BodyType::None, cx)
+                .with_completion_engine(completion_engine)
+        });
+        let response_view = cx.new(|cx| ResponseView::new(response.clone(), cx));
+
+        let tab_id = TabId(self.next_tab_id);
+        self.next_tab_id += 1;
+        Self::subscribe_request_changes(&request, cx);
+        Self::subscribe_request_view_changes(&request_view, cx);
+        let tab = TabState {
+            id: tab_id,
+            name: "New Request".to_string(),
+            is_custom_name: false,
+            content: TabContent::Request {
+                request: request.clone(),
+                response: response.clone(),
+                url_input: None,
+                method_dropdown,
+                request_view,
+                response_view,
+                in_flight_request: None,
+                sent_request: None,
+                request_generation: RequestGeneration::default(),
+                protocol: ProtocolType::Rest,
+                websocket: None,
+            },
+            collection_id: None,
+            collection_node_id: None,
+            history_entry_id: None,
+        };
+
+        self.tabs.push(tab);
+        self.active_tab_index = self.tabs.len() - 1;
+
+        // Scroll to the newly added tab
+        self.tab_scroll_handle.scroll_to_item(self.active_tab_index);
+
+        cx.notify();
+    }
+
+    /// Add a new tab for the user, moving focus to its URL bar unless they turned that off.
+    pub fn open_new_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.new_tab(cx);
+        if self.ui_preferences.focus_url_on_new_tab {
+            self.ensure_url_input(self.active_tab_index, window, cx);
+            self.focus_url_bar(window, cx);
+        }
+    }
+
+    /// Switch to a tab by index
+    pub fn switch_tab(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.tabs.len() && index != self.active_tab_index {
+            self.active_tab_index = index;
+
+            // Notify the views to re-render with their current data
+            if let Some(tab) = self.tabs.get(index)
+                && let TabContent::Request {
+                    request_view,
+                    response_view,
+                    ..
+                } = &tab.content
+            {
+                request_view.update(cx, |_, cx| cx.notify());
+                response_view.update(cx, |_, cx| cx.notify());
+            }
+
+            // Scroll to the selected tab to ensure it's visible
+            self.tab_scroll_handle.scroll_to_item(index);
+
+            cx.notify();
+        }
+    }
+
+    /// Close a tab
+    pub fn close_tab(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.tabs.len() > 1 && index < self.tabs.len() {
+            self.cancel_in_flight_for_tab(index, cx);
+            self.remember_closed_tab(index, cx);
+            self.tabs.remove(index);
+
+            // Adjust active index
+            if self.active_tab_index >= self.tabs.len() {
+                self.active_tab_index = self.tabs.len() - 1;
+            } else if index < self.active_tab_index {
+                self.active_tab_index -= 1;
+            }
+
+            cx.notify();
+        }
+    }
+
+    /// Keep the request of the tab at `index` so it can be reopened once closed. Only REST
+    /// tabs are kept; environment and WebSocket tabs are cheap to open again.
+    fn remember_closed_tab(&mut self, index: usize, cx: &mut Context<Self>) {
+        let is_rest = matches!(
+            self.tabs.get(index).map(|tab| &tab.content),
+            Some(TabContent::Request {
+                protocol: ProtocolType::Rest,
+                ..
+            })
+        );
+        if !is_rest {
+            return;
+        }
+        let Some(request) = self.build_request_snapshot_for_tab(index, cx) else {
+            return;
+        };
+        let tab = &self.tabs[index];
+        let closed = ClosedTab {
+            name: tab.name.clone(),
+            is_custom_name: tab.is_custom_name,
+            request,
+            sub_tabs: SubTabs::of(&tab.content, cx).unwrap_or_default(),
+            collection_id: tab.collection_id,
+            collection_node_id: tab.collection_node_id,
+            history_entry_id: tab.history_entry_id,
+        };
+        push_closed_tab(&mut self.closed_tabs, closed);
+    }
+
+    /// Reopen the most recently closed tab with the request it had when it was closed.
+    pub fn reopen_closed_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(closed) = self.closed_tabs.pop() else {
+            return;
+        };
+        let tab_id = TabId(self.next_tab_id);
+        self.next_tab_id += 1;
+        let content = self.build_history_tab_content(tab_id, &closed.request, None, window, cx);
+        closed.sub_tabs.restore(&content, cx);
+        self.tabs.push(TabState {
+            id: tab_id,
+            name: closed.name,
+            is_custom_name: closed.is_custom_name,
+            content,
+            collection_id: closed.collection_id,
+            collection_node_id: closed.collection_node_id,
+            history_entry_id: closed.history_entry_id,
+        });
+        self.active_tab_index = self.tabs.len() - 1;
+        self.tab_scroll_handle.scroll_to_item(self.active_tab_index);
+        cx.notify();
+    }
+
+    /// Name a request tab shows: its own once renamed, otherwise the path of its URL.
+    fn request_tab_name(
+        tab: &TabState,
+        url_input: Option<&Entity<InputState>>,
+        cx: &App,
+    ) -> String {
+        if tab.is_custom_name {
+            return tab.name.clone();
+        }
+        let url = url_input
+            .map(|input| input.read(cx).text().to_string())
+            .unwrap_or_default();
+        Self::derive_tab_name(&url)
+    }
+
+    /// Edit the active request's name in place, above its URL bar.
+    fn start_request_name_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.tabs.get(self.active_tab_index) else {
+            return;
+        };
+        let TabContent::Request { url_input, .. } = &tab.content else {
+            return;
+        };
+        let tab_id = tab.id;
+        let name = Self::request_tab_name(tab, url_input.as_ref(), cx);
+        let input = cx.new(|cx| InputState::new(window, cx).default_value(&name));
+        cx.subscribe_in(&input, window, move |view, _, event, _, cx| {
+            if matches!(event, InputEvent::PressEnter { .. } | InputEvent::Blur) {
+                view.commit_request_name_edit(tab_id, cx);
+            }
+        })
+        .detach();
+        input.update(cx, |state, cx| state.focus(window, cx));
+        self.request_name_input = Some((tab_id, input));
+        cx.notify();
+    }
+
+    /// Rename the tab being edited, which names the request it saves; a blank or unchanged
+    /// name leaves the tab as it was.
+    fn commit_request_name_edit(&mut self, tab_id: TabId, cx: &mut Context<Self>) {
+        let Some((_, input)) = self
+            .request_name_input
+            .take_if(|(editing, _)| *editing == tab_id)
+        else {
+            return;
+        };
+        let name = input.read(cx).text().to_string().trim().to_string();
+        if let Some(index) = self.tabs.iter().position(|tab| tab.id == tab_id)
+            && let TabContent::Request { url_input, .. } = &self.tabs[index].content
+            && !name.is_empty()
+            && name != Self::request_tab_name(&self.tabs[index], url_input.as_ref(), cx)
+        {
+            self.rename_tab(index, name, cx);
+        }
+        cx.notify();
+    }
+
+    /// Rename a tab (marks as custom since user explicitly renamed)
+    pub fn rename_tab(&mut self, index: usize, new_name: String, cx: &mut Context<Self>) {
+        if index < self.tabs.len() {
+            self.tabs[index].name = new_name;
+            self.tabs[index].is_custom_name = true;
+            cx.notify();
+        }
+    }
+
+    /// Show rename dialog for a tab
+    pub fn show_rename_dialog(
+        &mut self,
+        index: usize,
+        current_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity().clone();
+        let input = cx.new(|cx| InputState::new(window, cx).default_value(&current_name));
+
+        // Subscribe to Enter key press on the input
+        cx.subscribe_in(&input, window, move |view, state, event, window, cx| {
+            use gpui_component::input::InputEvent;
+            if let InputEvent::PressEnter { .. } = event {
+                let new_name = state.read(cx).text().to_string();
+                view.rename_tab(index, new_name, cx);
+                close_dialog(window, cx);
+            }
+        })
+        .detach();
+
+        // Clone for footer button clicks
+        let input_for_footer = input.clone();
+        let this_for_footer = this.clone();
+
+        open_dialog(window, cx, move |dialog, _, _| {
+            // Clone again before the inner move closure
+            let input_for_buttons = input_for_footer.clone();
+            let this_for_buttons = this_for_footer.clone();
+
+            dialog
+                .title("Rename Tab")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child("Enter a new name for this tab:")
+                        .child(Input::new(&input)),
+                )
+                .footer({
+                    let input_click = input_for_buttons.clone();
+                    let this_click = this_for_buttons.clone();
+
+                    DialogFooter::new()
+                        .child(
+                            Button::new("rename-submit")
+                                .primary()
+                                .label("Rename")
+                                .on_click(move |_, window, cx| {
+                                    let new_name = input_click.read(cx).text().to_string();
+                                    this_click.update(cx, |view, cx| {
+                                        view.rename_tab(index, new_name, cx);
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(Button::new("rename-cancel").label("Cancel").on_click(
+                            |_, window, cx| {
+                                close_dialog(window, cx);
+                            },
+                        ))
+                })
+        });
+    }
+
+    fn rename_collection_target(
+        &mut self,
+        target: RenameTarget,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return;
+        }
+
+        match target {
+            RenameTarget::Collection(collection_id) => {
+                self.collections.update(cx, |collections, cx| {
+                    collections.rename_collection(collection_id, new_name, cx);
+                });
+            }
+            RenameTarget::Node {
+                collection_id,
+                node_id,
+            } => {
+                self.collections.update(cx, |collections, cx| {
+                    collections.rename_node(collection_id, node_id, new_name, cx);
+                });
+            }
+        }
+    }
+
+    fn show_collection_rename_dialog_internal(
+        &mut self,
+        target: RenameTarget,
+        title: &'static str,
+        prompt: &'static str,
+        current_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity().clone();
+        let input = cx.new(|cx| InputState::new(window, cx).default_value(&current_name));
+
+        cx.subscribe_in(&input, window, move |view, state, event, window, cx| {
+            use gpui_component::input::InputEvent;
+            if let InputEvent::PressEnter { .. } = event {
+                view.rename_collection_target(target, state.read(cx).text().to_string(), cx);
+                close_dialog(window, cx);
+            }
+        })
+        .detach();
+
+        let input_for_footer = input.clone();
+        let this_for_footer = this.clone();
+
+        open_dialog(window, cx, move |dialog, _, _| {
+            let input_for_buttons = input_for_footer.clone();
+            let this_for_buttons = this_for_footer.clone();
+
+            dialog
+                .title(title)
+                .child(v_flex().gap_3().child(prompt).child(Input::new(&input)))
+                .footer({
+                    let input_click = input_for_buttons.clone();
+                    let this_click = this_for_buttons.clone();
+
+                    DialogFooter::new()
+                        .child(
+                            Button::new("rename-collection-target-submit")
+                                .primary()
+                                .label("Rename")
+                                .on_click(move |_, window, cx| {
+                                    let new_name = input_click.read(cx).text().to_string();
+                                    this_click.update(cx, |view, cx| {
+                                        view.rename_collection_target(target, new_name, cx);
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("rename-collection-target-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| {
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                })
+        });
+    }
+
+    pub fn show_rename_collection_dialog(
+        &mut self,
+        collection_id: Uuid,
+        current_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_collection_rename_dialog_internal(
+            RenameTarget::Collection(collection_id),
+            "Rename Collection",
+            "Enter a new name for this collection:",
+            current_name,
+            window,
+            cx,
+        );
+    }
+
+    pub fn show_rename_collection_node_dialog(
+        &mut self,
+        collection_id: Uuid,
+        node_id: Uuid,
+        current_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_collection_rename_dialog_internal(
+            RenameTarget::Node {
+                collection_id,
+                node_id,
+            },
+            "Rename Item",
+            "Enter a new name:",
+            current_name,
+            window,
+            cx,
+        );
+    }
+
+    pub fn show_new_folder_dialog(
+        &mut self,
+        collection_id: Uuid,
+        parent_folder_id: Option<Uuid>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity().clone();
+        let input = cx.new(|cx| InputState::new(window, cx).default_value("New Folder"));
+
+        cx.subscribe_in(&input, window, move |view, state, event, window, cx| {
+            use gpui_component::input::InputEvent;
+            if let InputEvent::PressEnter { .. } = event {
+                let name = state.read(cx).text().to_string().trim().to_string();
+                if !name.is_empty() {
+                    view.create_collection_folder(collection_id, parent_folder_id, &name, cx);
+                }
+                close_dialog(window, cx);
+            }
+        })
+        .detach();
+
+        let input_for_footer = input.clone();
+        let this_for_footer = this.clone();
+
+        open_dialog(window, cx, move |dialog, _, _| {
+            let input_for_buttons = input_for_footer.clone();
+            let this_for_buttons = this_for_footer.clone();
+
+            dialog
+                .title("New Folder")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child("Enter a name for the new folder:")
+                        .child(Input::new(&input)),
+                )
+                .footer({
+                    let input_click = input_for_buttons.clone();
+                    let this_click = this_for_buttons.clone();
+
+                    DialogFooter::new()
+                        .child(
+                            Button::new("new-folder-submit")
+                                .primary()
+                                .label("Create")
+                                .on_click(move |_, window, cx| {
+                                    let name =
+                                        input_click.read(cx).text().to_string().trim().to_string();
+                                    if !name.is_empty() {
+                                        this_click.update(cx, |view, cx| {
+                                            view.create_collection_folder(
+                                                collection_id,
+                                                parent_folder_id,
+                                                &name,
+                                                cx,
+                                            );
+                                        });
+                                    }
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(Button::new("new-folder-cancel").label("Cancel").on_click(
+                            |_, window, cx| {
+                                close_dialog(window, cx);
+                            },
+                        ))
+                })
+        });
+    }
+
+    pub fn show_move_collection_node_dialog(
+        &mut self,
+        source_collection_id: Uuid,
+        node_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let options = {
+            let collections = self.collections.read(cx);
+            Self::destination_options(
+                collections.move_destinations_for_node(source_collection_id, node_id),
+            )
+        };
+
+        if options.is_empty() {
+            window.push_notification(
+                (
+                    NotificationType::Warning,
+                    "No valid destinations are available for this item.",
+                ),
+                cx,
+            );
+            return;
+        }
+
+        let select_state = cx.new(|cx| {
+            SelectState::new(
+                options.clone(),
+                Some(gpui_component::IndexPath::new(0)),
+                window,
+                cx,
+            )
+        });
+        let this = cx.entity().clone();
+
+        open_dialog(window, cx, move |dialog, _, _| {
+            let select_for_footer = select_state.clone();
+            let this_for_footer = this.clone();
+
+            dialog
+                .title("Move Item")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child("Choose a new destination:")
+                        .child(Select::new(&select_state).menu_width(px(360.0))),
+                )
+                .footer({
+                    let select_click = select_for_footer.clone();
+                    let this_click = this_for_footer.clone();
+
+                    DialogFooter::new()
+                        .child(
+                            Button::new("move-node-submit")
+                                .primary()
+                                .label("Move")
+                                .on_click(move |_, window, cx| {
+                                    let Some(selection) =
+                                        select_click.read(cx).selected_value().cloned()
+                                    else {
+                                        return;
+                                    };
+
+                                    let result = this_click.update(cx, |view, cx| {
+                                        view.collections.update(cx, |collections, cx| {
+                                            collections.move_node(
+                                                source_collection_id,
+                                                node_id,
+                                                selection.destination.collection_id,
+                                                selection.destination.folder_id,
+                                                cx,
+                                            )
+                                        })
+                                    });
+
+                                    match result {
+                                        Ok(()) => close_dialog(window, cx),
+                                        Err(error) => window.push_notification(
+                                            (
+                                                NotificationType::Error,
+                                                SharedString::from(error.to_string()),
+                                            ),
+                                            cx,
+                                        ),
+                                    }
+                                }),
+                        )
+                        .child(Button::new("move-node-cancel").label("Cancel").on_click(
+                            |_, window, cx| {
+                                close_dialog(window, cx);
+                            },
+                        ))
+                })
+        });
+    }
+
+    pub fn show_save_to_collection_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.open_save_to_collection_dialog(false, window, cx);
+    }
+
+    /// The "Send and Save" choice to keep the response too, read when the dialog is submitted.
+    fn save_response_checkbox(save_response: Rc<Cell<bool>>) -> Checkbox {
+        Checkbox::new("save-response-as-example")
+            .label("Also save the response as an example")
+            .checked(save_response.get())
+            .on_click(move |checked, window, _| {
+                save_response.set(*checked);
+                window.refresh();
+            })
+    }
+
+    /// With `send_first`, the chosen destination is only saved to once the request succeeds.
+    fn open_save_to_collection_dialog(
+        &mut self,
+        send_first: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (title, save_label) = if send_first {
+            ("Send and Save", "Send and save")
+        } else {
+            ("Save to Collection", "Save")
+        };
+        let Some(request_data) = self.build_active_request_snapshot(cx) else {
+            window.push_notification(
+                (NotificationType::Warning, "No active request to save."),
+                cx,
+            );
+            return;
+        };
+
+        let destination_options = {
+            let collections = self.collections.read(cx);
+            Self::destination_options(collections.destination_entries())
+        };
+
+        let this = cx.entity().clone();
+        let request_name_input =
+            cx.new(|cx| InputState::new(window, cx).default_value(&request_data.name));
+        let save_response = Rc::new(Cell::new(false));
+
+        if destination_options.is_empty() {
+            let collection_name_input =
+                cx.new(|cx| InputState::new(window, cx).default_value("New Collection"));
+            let request_name_for_footer = request_name_input.clone();
+            let collection_name_for_footer = collection_name_input.clone();
+            let this_for_footer = this.clone();
+            let request_for_footer = request_data.clone();
+
+            open_dialog(window, cx, move |dialog, _, _| {
+                let request_name_for_buttons = request_name_for_footer.clone();
+                let collection_name_for_buttons = collection_name_for_footer.clone();
+                let this_for_buttons = this_for_footer.clone();
+                let request_for_buttons = request_for_footer.clone();
+                let save_response_toggle = save_response.clone();
+
+                dialog
+                    .title(title)
+                    .child(
+                        v_flex()
+                            .gap_3()
+                            .child("No collections exist yet, so a new collection will be created.")
+                            .child("Request name")
+                            .child(Input::new(&request_name_input))
+                            .child("Collection name")
+                            .child(Input::new(&collection_name_input))
+                            .when(send_first, |body| {
+                                body.child(Self::save_response_checkbox(save_response_toggle))
+                            }),
+                    )
+                    .footer({
+                        let request_name_click = request_name_for_buttons.clone();
+                        let collection_name_click = collection_name_for_buttons.clone();
+                        let this_click = this_for_buttons.clone();
+                        let request_click = request_for_buttons.clone();
+                        let save_response_click = save_response.clone();
+
+                        DialogFooter::new()
+                            .child(
+                                Button::new("save-to-new-collection")
+                                    .primary()
+                                    .label(save_label)
+                                    .on_click(move |_, window, cx| {
+                                        let request_name = request_name_click
+                                            .read(cx)
+                                            .text()
+                                            .to_string()
+                                            .trim()
+                                            .to_string();
+                                        let collection_name = collection_name_click
+                                            .read(cx)
+                                            .text()
+                                            .to_string()
+                                            .trim()
+                                            .to_string();
+                                        let request_name = if request_name.is_empty() {
+                                            "New Request".to_string()
+                                        } else {
+                                            request_name
+                                        };
+                                        let collection_name = if collection_name.is_empty() {
+                                            "New Collection".to_string()
+                                        } else {
+                                            collection_name
+                                        };
+
+                                        this_click.update(cx, |view, cx| {
+                                            let collection_id =
+                                                view.collections.update(cx, |collections, cx| {
+                                                    collections
+                                                        .create_collection(&collection_name, cx)
+                                                });
+                                            let destination = CollectionDestination {
+                                                collection_id,
+                                                folder_id: None,
+                                            };
+                                            if send_first {
+                                                view.queue_send_and_save(
+                                                    Some((destination, request_name)),
+                                                    save_response_click.get(),
+                                                    window,
+                                                    cx,
+                                                );
+                                            } else {
+                                                view.save_request_to_destination(
+                                                    destination,
+                                                    request_name,
+                                                    request_click.clone(),
+                                                    cx,
+                                                );
+                                            }
+                                        });
+                                        close_dialog(window, cx);
+                                    }),
+                            )
+                            .child(
+                                Button::new("save-to-new-collection-cancel")
+                                    .label("Cancel")
+                                    .on_click(|_, window, cx| {
+                                        close_dialog(window, cx);
+                                    }),
+                            )
+                    })
+            });
+            return;
+        }
+
+        let destination_select = cx.new(|cx| {
+            SelectState::new(
+                destination_options.clone(),
+                Some(gpui_component::IndexPath::new(0)),
+                window,
+                cx,
+            )
+        });
+        let new_collection_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Leave empty to use the destination above")
+        });
+        let request_name_for_footer = request_name_input.clone();
+        let destination_for_footer = destination_select.clone();
+        let new_collection_for_footer = new_collection_input.clone();
+        let this_for_footer = this.clone();
+        let request_for_footer = request_data.clone();
+
+        open_dialog(window, cx, move |dialog, _, _| {
+            let request_name_for_buttons = request_name_for_footer.clone();
+            let destination_for_buttons = destination_for_footer.clone();
+            let new_collection_for_buttons = new_collection_for_footer.clone();
+            let this_for_buttons = this_for_footer.clone();
+            let request_for_buttons = request_for_footer.clone();
+            let save_response_toggle = save_response.clone();
+
+            dialog
+                .title(title)
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child("Request name")
+                        .child(Input::new(&request_name_input))
+                        .child("Destination")
+                        .child(Select::new(&destination_select).menu_width(px(360.0)))
+                        .child("Or a new collection named")
+                        .child(Input::new(&new_collection_input))
+                        .when(send_first, |body| {
+                            body.child(Self::save_response_checkbox(save_response_toggle))
+                        }),
+                )
+                .footer({
+                    let request_name_click = request_name_for_buttons.clone();
+                    let destination_click = destination_for_buttons.clone();
+                    let new_collection_click = new_collection_for_buttons.clone();
+                    let this_click = this_for_buttons.clone();
+                    let request_click = request_for_buttons.clone();
+                    let save_response_click = save_response.clone();
+
+                    DialogFooter::new()
+                        .child(
+                            Button::new("save-to-collection-submit")
+                                .primary()
+                                .label(save_label)
+                                .on_click(move |_, window, cx| {
+                                    let Some(selection) =
+                                        destination_click.read(cx).selected_value().cloned()
+                                    else {
+                                        return;
+                                    };
+
+                                    let request_name = request_name_click
+                                        .read(cx)
+                                        .text()
+                                        .to_string()
+                                        .trim()
+                                        .to_string();
+                                    let request_name = if request_name.is_empty() {
+                                        "New Request".to_string()
+                                    } else {
+                                        request_name
+                                    };
+                                    let new_collection = new_collection_click
+                                        .read(cx)
+                                        .text()
+                                        .to_string()
+                                        .trim()
+                                        .to_string();
+
+                                    this_click.update(cx, |view, cx| {
+                                        let destination = if new_collection.is_empty() {
+                                            selection.destination
+                                        } else {
+                                            CollectionDestination {
+                                                collection_id: view.collections.update(
+                                                    cx,
+                                                    |collections, cx| {
+                                                        collections
+                                                            .create_collection(&new_collection, cx)
+                                                    },
+                                                ),
+                                                folder_id: None,
+                                            }
+                                        };
+                                        if send_first {
+                                            view.queue_send_and_save(
+                                                Some((destination, request_name)),
+                                                save_response_click.get(),
+                                                window,
+                                                cx,
+                                            );
+                                        } else {
+                                            view.save_request_to_destination(
+                                                destination,
+                                                request_name,
+                                                request_click.clone(),
+                                                cx,
+                                            );
+                                        }
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("save-to-collection-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| {
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                })
+        });
+    }
+
+    /// Export the history entries the sidebar's search and filter currently match.
+    pub fn export_history(
+        &mut self,
+        format: HistoryExportFormat,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let query = self
+            .history_search
+            .as_ref()
+            .map(|input| input.read(cx).text().to_string())
+            .unwrap_or_default();
+        let starred_only = self.history_filter == HistoryFilter::Starred;
+        let snapshot = self.history.read(cx).rows_snapshot();
+
+        let options = PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+            prompt: Some("Select folder to export history".into()),
+        };
+        let paths_receiver = cx.prompt_for_paths(options);
+
+        cx.spawn_in(window, async move |_weak_this, cx| {
+            let Ok(platform_result) = paths_receiver.await else {
+                log::error!("History export folder picker channel closed unexpectedly");
+                return;
+            };
+            let Ok(Some(paths)) = platform_result else {
+                return;
+            };
+            let Some(dir_path) = paths.first() else {
+                return;
+            };
+
+            let file_path = dir_path.join(format!("setu-history.{}", format.extension()));
+            let file_path_for_worker = file_path.clone();
+            let (write_tx, write_rx) = tokio::sync::oneshot::channel();
+            crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+                let entries = snapshot.matching_entries(&query, starred_only);
+                let result = std::fs::File::create(&file_path_for_worker).and_then(|file| {
+                    crate::entities::write_history_export(
+                        &entries,
+                        format,
+                        std::io::BufWriter::new(file),
+                    )
+                });
+                let _ = write_tx.send(result.map(|()| entries.len()));
+            });
+            let write_result = write_rx.await.unwrap_or_else(|_| {
+                Err(std::io::Error::other(
+                    "history export writer stopped unexpectedly",
+                ))
+            });
+
+            let _ = cx.update(|window, app| match write_result {
+                Ok(count) => {
+                    log::info!("History exported to: {}", file_path.display());
+                    let noun = if count == 1 { "entry" } else { "entries" };
+                    window.push_notification(
+                        (
+                            NotificationType::Success,
+                            SharedString::from(format!("Exported {count} history {noun}")),
+                        ),
+                        app,
+                    );
+                }
+                Err(error) => {
+                    log::error!("Failed to export history: {error}");
+                    window.push_notification(
+                        (NotificationType::Error, "Failed to export history"),
+                        app,
+                    );
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Save a collection as a setu export file through the platform save dialog.
+    pub fn export_collection(
+        &mut self,
+        collection_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((name, export)) = self
+            .collections
+            .read(cx)
+            .get_collection(collection_id)
+            .map(|collection| (collection.name.clone(), collection.to_export_json()))
+        else {
+            return;
+        };
+        let contents = match export {
+            Ok(contents) => contents,
+            Err(error) => {
+                log::error!("Failed to serialize collection: {}", error);
+                window.push_notification(
+                    (NotificationType::Error, "Failed to export collection"),
+                    cx,
+                );
+                return;
+            }
+        };
+        let default_name = format!(
+            "{}.json",
+            name.chars()
+                .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                })
+                .collect::<String>()
+        );
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(std::env::temp_dir);
+        let path_receiver = cx.prompt_for_new_path(&directory, Some(&default_name));
+
+        cx.spawn_in(window, async move |_weak_this, cx| {
+            let Ok(Ok(Some(path))) = path_receiver.await else {
+                return;
+            };
+
+            let path_for_worker = path.clone();
+            let (write_tx, write_rx) = tokio::sync::oneshot::channel();
+            crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+                let _ = write_tx.send(std::fs::write(&path_for_worker, contents));
+            });
+            let write_result = write_rx.await.unwrap_or_else(|_| {
+                Err(std::io::Error::other(
+                    "collection file writer stopped unexpectedly",
+                ))
+            });
+
+            let _ = cx.update(|window, app| {
+                let notification = match write_result {
+                    Ok(()) => {
+                        let name = path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_default();
+                        (
+                            NotificationType::Success,
+                            SharedString::from(format!("Exported to {name}")),
+                        )
+                    }
+                    Err(error) => {
+                        log::error!("Failed to export collection: {}", error);
+                        (
+                            NotificationType::Error,
+                            SharedString::from("Failed to export collection"),
+                        )
+                    }
+                };
+                window.push_notification(notification, app);
+            });
+        })
+        .detach();
+    }
+
+    pub fn import_collection_from_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let this = cx.entity().clone();
+        let options = PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some(
+                "Select a setu or Postman collection, a Postman environment, or an OpenAPI spec"
+                    .into(),
+            ),
+        };
+        let paths_receiver = cx.prompt_for_paths(options);
+
+        cx.spawn_in(window, async move |_weak_this, cx| {
+            let Ok(platform_result) = paths_receiver.await else {
+                log::error!("Collection import file picker channel closed unexpectedly");
+                return;
+            };
+
+            let Ok(paths_opt) = platform_result else {
+                let _ = cx.update(|window, app| {
+                    window.push_notification(
+                        (NotificationType::Error, "Failed to open file picker"),
+                        app,
+                    );
+                });
+                return;
+            };
+
+            let Some(paths) = paths_opt else {
+                return;
+            };
+            let Some(path) = paths.first().cloned() else {
+                return;
+            };
+
+            let result = ImportRegistry::default().import_any_file(&path);
+            let _ = cx.update(|window, app| match result {
+                Ok(result) => {
+                    this.update(app, |view, cx| {
+                        let summary = match result.payload {
+                            ImportedPayload::Collection(collection) => {
+                                let name = collection.name.clone();
+                                let folder_count = collection.folder_count();
+                                let request_count = collection.request_count();
+                                let variables = collection.variables.clone();
+                                let variable_count = variables.len();
+
+                                let workspace_id = view.workspaces.update(cx, |workspaces, cx| {
+                                    workspaces.create_workspace(name.clone(), cx)
+                                });
+                                view.switch_workspace(workspace_id, cx);
+
+                                let collection_id =
+                                    view.collections.update(cx, |collections, cx| {
+                                        collections.import_collection(collection, cx)
+                                    });
+                                if !variables.is_empty() {
+                                    view.environments.update(cx, |environments, cx| {
+                                        environments.import_environment(
+                                            format!("{name} Variables"),
+                                            EnvironmentScope::Project(collection_id),
+                                            variables
+                                                .into_iter()
+                                                .map(|variable| EnvironmentVariable {
+                                                    key: variable.key,
+                                                    value: variable.value,
+                                                    enabled: variable.enabled,
+                                                    secret: variable.secret,
+                                                    ..EnvironmentVariable::default()
+                                                })
+                                                .collect(),
+                                            cx,
+                                        );
+                                    });
+                                }
+                                ImportSummary {
+                                    provider: result.provider,
+                                    item_kind: "Workspace",
+                                    item_name: name,
+                                    folder_count: Some(folder_count),
+                                    request_count: Some(request_count),
+                                    variable_count,
+                                    warnings: result.warnings,
+                                }
+                            }
+                            ImportedPayload::Environment(environment) => {
+                                let name = environment.name.clone();
+                                let variable_count = environment.variables.len();
+                                let environment_id =
+                                    view.environments.update(cx, |environments, cx| {
+                                        environments.import_environment(
+                                            name.clone(),
+                                            EnvironmentScope::Workspace,
+                                            environment
+                                                .variables
+                                                .into_iter()
+                                                .map(|variable| EnvironmentVariable {
+                                                    key: variable.key,
+                                                    value: variable.value,
+                                                    enabled: variable.enabled,
+                                                    secret: variable.secret,
+                                                    ..EnvironmentVariable::default()
+                                                })
+                                                .collect(),
+                                            cx,
+                                        )
+                                    });
+                                view.environment_panel.update(cx, |panel, cx| {
+                                    panel.select_environment(environment_id, cx);
+                                });
+                                view.sidebar_visible = true;
+                                view.sidebar_tab = SidebarTab::Environments;
+                                ImportSummary {
+                                    provider: result.provider,
+                                    item_kind: "Environment",
+                                    item_name: name,
+                                    folder_count: None,
+                                    request_count: None,
+                                    variable_count,
+                                    warnings: result.warnings,
+                                }
+                            }
+                            ImportedPayload::SetuCollection(collection) => {
+                                let name = collection.name.clone();
+                                let folder_count = collection.folder_count();
+                                let request_count = collection.request_count();
+                                view.collections.update(cx, |collections, cx| {
+                                    collections.import_exported_collection(collection, cx);
+                                });
+                                view.sidebar_visible = true;
+                                view.sidebar_tab = SidebarTab::Collections;
+                                ImportSummary {
+                                    provider: result.provider,
+                                    item_kind: "Collection",
+                                    item_name: name,
+                                    folder_count: Some(folder_count),
+                                    request_count: Some(request_count),
+                                    variable_count: 0,
+                                    warnings: result.warnings,
+                                }
+                            }
+                        };
+                        view.show_import_summary_dialog(summary, window, cx);
+                    });
+                }
+                Err(error) => {
+                    window.push_notification(
+                        (
+                            NotificationType::Error,
+                            SharedString::from(format!("Import failed: {error}")),
+                        ),
+                        app,
+                    );
+                }
+            });
+        })
+        .detach();
+    }
+
+    fn show_import_summary_dialog(
+        &mut self,
+        summary: ImportSummary,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let muted_foreground = cx.theme().muted_foreground;
+        open_dialog(window, cx, move |dialog, _, _| {
+            let warning_count = summary.warnings.len();
+            let warnings = summary.warnings.clone();
+
+            dialog
+                .title("Import Summary")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(format!("Provider: {}", summary.provider))
+                        .child(format!("{}: {}", summary.item_kind, summary.item_name))
+                        .when_some(summary.folder_count, |element, count| {
+                            element.child(format!("Folders imported: {count}"))
+                        })
+                        .when_some(summary.request_count, |element, count| {
+                            element.child(format!("Requests imported: {count}"))
+                        })
+                        .child(format!("Variables imported: {}", summary.variable_count))
+                        .child(format!("Warnings: {}", warning_count))
+                        .child(if warnings.is_empty() {
+                            div()
+                                .text_size(px(12.0))
+                                .child("No warnings.")
+                                .into_any_element()
+                        } else {
+                            div()
+                                .flex()
+                                .flex_col()
+                                .gap(px(6.0))
+                                .max_h(px(220.0))
+                                .overflow_y_scrollbar()
+                                .children(warnings.into_iter().map(|warning| {
+                                    let location =
+                                        warning.path.unwrap_or_else(|| "Import".to_string());
+                                    div()
+                                        .flex()
+                                        .flex_col()
+                                        .gap(px(2.0))
+                                        .child(
+                                            div()
+                                                .text_size(px(11.0))
+                                                .font_weight(gpui::FontWeight::SEMIBOLD)
+                                                .child(location),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_size(px(12.0))
+                                                .text_color(muted_foreground)
+                                                .child(warning.message),
+                                        )
+                                }))
+                                .into_any_element()
+                        }),
+                )
+                .footer(
+                    DialogFooter::new().child(
+                        Button::new("import-summary-close")
+                            .primary()
+                            .label("Close")
+                            .on_click(|_, window, cx| {
+                                close_dialog(window, cx);
+                            }),
+                    ),
+                )
+        });
+    }
+
+    /// Close all tabs except the one at the given index
+    pub fn close_other_tabs(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.tabs.len() {
+            for i in 0..self.tabs.len() {
+                if i != index {
+                    self.cancel_in_flight_for_tab(i, cx);
+                    self.remember_closed_tab(i, cx);
+                }
+            }
+
+            // Keep only the tab at the given index
+            let tab_to_keep = self.tabs.remove(index);
+            self.tabs.clear();
+            self.tabs.push(tab_to_keep);
+            self.active_tab_index = 0;
+
+            cx.notify();
+        }
+    }
+
+    /// Request a client-credentials token for the active tab's OAuth config, then send the
+    /// request when `then_send` is set.
+    pub fn fetch_oauth_token(&mut self, then_send: bool, cx: &mut Context<Self>) {
+        let Some(tab) = self.active_tab() else {
+            return;
+        };
+        let TabContent::Request {
+            response: response_entity,
+            request_view,
+            ..
+        } = &tab.content
+        else {
+            return;
+        };
+        let tab_id = tab.id;
+        let collection_id = tab.collection_id;
+        let response_entity = response_entity.clone();
+        let request_view = request_view.clone();
+
+        let collections = self.collections.clone();
+        let auth = request_view.update(cx, |view, cx| {
+            view.set_owning_collection(&collections, collection_id, cx);
+            view.resolved_auth(cx)
+        });
+        if auth.auth_type != AuthType::OAuth2ClientCredentials {
+            return;
+        }
+
+        let (url, headers, body) = auth.client_credentials_request();
+        let resolved =
+            self.environments
+                .read(cx)
+                .resolve_request(collection_id, &url, &headers, &body);
+        let resolved = match resolved {
+            Ok(resolved) if !resolved.url.is_empty() => resolved,
+            Ok(_) => {
+                let error = "Enter a token URL to get an OAuth token".to_string();
+                self.report_oauth_error(&request_view, &response_entity, error, then_send, cx);
+                return;
+            }
+            Err(error) => {
+                let error = error.user_message();
+                self.report_oauth_error(&request_view, &response_entity, error, then_send, cx);
+                return;
+            }
+        };
+
+        request_view.update(cx, |view, cx| view.set_oauth_fetching(cx));
+        if then_send {
+            response_entity.update(cx, |response, cx| response.set_loading(cx));
+        }
+        let cookie_scope = self
+            .environments
+            .read(cx)
+            .active_environment_id(collection_id);
+        let (proxy, tls) = self.connection_settings(collection_id, cx);
+        let token = self.http_client.fetch_oauth_token(
+            resolved.url,
+            resolved.headers,
+            resolved.body,
+            RequestOptions {
+                cookie_scope,
+                proxy,
+                tls,
+                ..RequestOptions::default()
+            },
+        );
+
+        cx.spawn(async move |view, cx| {
+            let result = token.await.map_err(|error| error.to_string());
+            cx.update(|app| {
+                let _ = view.update(app, |main, cx| {
+                    let failed = result.as_ref().err().cloned();
+                    request_view.update(cx, |view, cx| view.store_oauth_token(result, cx));
+                    if !then_send {
+                        return;
+                    }
+                    match failed {
+                        Some(error) => response_entity.update(cx, |response, cx| {
+                            response.set_error(format!("Failed to get OAuth token: {error}"), cx);
+                        }),
+                        // Only resume if the user is still on the tab that asked.
+                        None if main.active_tab().is_some_and(|tab| tab.id == tab_id) => {
+                            main.send_request_with(false, cx)
+                        }
+                        None => response_entity.update(cx, |response, cx| response.clear(cx)),
+                    }
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn report_oauth_error(
+        &self,
+        request_view: &Entity<RequestView>,
+        response_entity: &Entity<ResponseEntity>,
+        error: String,
+        then_send: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if then_send {
+            response_entity.update(cx, |response, cx| {
+                response.set_error(format!("Failed to get OAuth token: {error}"), cx);
+            });
+        } else {
+            request_view.update(cx, |view, cx| view.store_oauth_token(Err(error), cx));
+        }
+    }
+
+    /// Point the active request at another scheme and host, keeping its path and query, and
+    /// optionally send it straight away.
+    pub fn show_change_host_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let tab_index = self.active_tab_index;
+        self.ensure_url_input(tab_index, window, cx);
+        let Some((tab_id, url_input)) =
+            self.tabs.get(tab_index).and_then(|tab| match &tab.content {
+                TabContent::Request {
+                    url_input: Some(url_input),
+                    protocol: ProtocolType::Rest,
+                    ..
+                } => Some((tab.id, url_input.clone())),
+                _ => None,
+            })
+        else {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    "Changing the host works with HTTP requests",
+                ),
+                cx,
+            );
+            return;
+        };
+        let url = url_input.read(cx).text().to_string();
+        if url.trim().is_empty() {
+            window.push_notification((NotificationType::Warning, "Please enter a URL"), cx);
+            return;
+        }
+        let current_origin = url_origin(&url).to_string();
+        let recent: Vec<String> = self
+            .recent_origins
+            .iter()
+            .filter(|origin| **origin != current_origin)
+            .cloned()
+            .collect();
+        let origin_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("http://localhost:3000")
+                .default_value(current_origin.clone())
+        });
+        let this = cx.entity().clone();
+
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let apply = |send: bool| {
+                let this = this.clone();
+                let url_input = url_input.clone();
+                let origin_input = origin_input.clone();
+                move |_: &gpui::ClickEvent, window: &mut Window, cx: &mut App| {
+                    let origin = origin_input.read(cx).text().to_string();
+                    let result = this.update(cx, |view, cx| {
+                        view.change_request_origin(tab_id, &url_input, &origin, send, window, cx)
+                    });
+                    match result {
+                        Ok(()) => close_dialog(window, cx),
+                        Err(message) => window.push_notification(
+                            (NotificationType::Error, SharedString::from(message)),
+                            cx,
+                        ),
+                    }
+                }
+            };
+            dialog
+                .title("Change Host")
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(
+                            div().text_sm().text_color(cx.theme().muted_foreground).child(
+                                "The path and query stay as they are. A host typed without a scheme keeps the current one.",
+                            ),
+                        )
+                        .child(Input::new(&origin_input))
+                        .when(!recent.is_empty(), |el| {
+                            el.child(
+                                div()
+                                    .flex()
+                                    .flex_row()
+                                    .flex_wrap()
+                                    .gap(px(4.0))
+                                    .children(recent.iter().enumerate().map(|(index, origin)| {
+                                        let origin_input = origin_input.clone();
+                                        let origin = origin.clone();
+                                        Button::new(("recent-origin", index))
+                                            .label(origin.clone())
+                                            .ghost()
+                                            .xsmall()
+                                            .on_click(move |_, window, cx| {
+                                                origin_input.update(cx, |state, cx| {
+                                                    state.set_value(origin.clone(), window, cx);
+                                                });
+                                            })
+                                    })),
+                            )
+                        }),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("change-host-send")
+                                .label("Apply and Send")
+                                .primary()
+                                .on_click(apply(true)),
+                        )
+                        .child(
+                            Button::new("change-host-apply")
+                                .label("Apply")
+                                .on_click(apply(false)),
+                        )
+                        .child(
+                            Button::new("change-host-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
+
+    fn change_request_origin(
+        &mut self,
+        tab_id: TabId,
+        url_input: &Entity<InputState>,
+        origin: &str,
+        send: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<(), String> {
+        let Some(TabContent::Request { request, .. }) = self
+            .tabs
+            .iter()
+            .find(|tab| tab.id == tab_id)
+            .map(|tab| &tab.content)
+        else {
+            return Err("The tab was closed".to_string());
+        };
+        let request = request.clone();
+        let url = replace_origin(&url_input.read(cx).text().to_string(), origin)?;
+        let origin = url_origin(&url).to_string();
+        url_input.update(cx, |state, cx| {
+            state.set_value(url.clone(), window, cx);
+        });
+        request.update(cx, |request, cx| request.set_url(url, cx));
+
+        self.recent_origins.retain(|recent| *recent != origin);
+        self.recent_origins.insert(0, origin);
+        self.recent_origins.truncate(RECENT_ORIGIN_LIMIT);
+        if send && self.is_active_tab(tab_id, "Sending", window, cx) {
+            self.send_request(cx);
+        }
+        cx.notify();
+        Ok(())
+    }
+
+    /// Send the current request
+    pub fn send_request(&mut self, cx: &mut Context<Self>) {
+        self.save_after_send = None;
+        self.send_request_with(true, cx);
+    }
+
+    /// Send the active request and save it once it succeeds: over the saved request the tab
+    /// is linked to, or to a collection picked first.
+    pub fn send_and_save(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.active_tab() else {
+            return;
+        };
+        let TabContent::Request {
+            request, protocol, ..
+        } = &tab.content
+        else {
+            return;
+        };
+        if *protocol != ProtocolType::Rest {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    "Send and save works with HTTP requests",
+                ),
+                cx,
+            );
+            return;
+        }
+        if request.read(cx).is_sending() {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    "Wait for the current request to finish",
+                ),
+                cx,
+            );
+            return;
+        }
+
+        let is_saved = tab.collection_id.zip(tab.collection_node_id).is_some_and(
+            |(collection_id, node_id)| {
+                self.collections
+                    .read(cx)
+                    .get_request_node(collection_id, node_id)
+                    .is_some()
+            },
+        );
+        if is_saved {
+            self.queue_send_and_save(None, false, window, cx);
+        } else {
+            self.open_save_to_collection_dialog(true, window, cx);
+        }
+    }
+
+    fn queue_send_and_save(
+        &mut self,
+        destination: Option<(CollectionDestination, String)>,
+        save_response: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(tab_id) = self.active_tab().map(|tab| tab.id) else {
+            return;
+        };
+        self.save_after_send = Some(SaveAfterSend {
+            tab_id,
+            destination,
+            save_response,
+            window: window.window_handle(),
+        });
+        self.send_request_with(true, cx);
+    }
+
+    /// Make a queued save now that its request has finished; only 2xx responses are saved.
+    fn finish_send_and_save(
+        &mut self,
+        save: SaveAfterSend,
+        request_data: RequestData,
+        response: Option<&ResponseData>,
+        cx: &mut Context<Self>,
+    ) {
+        let (kind, message) = match response.map(|response| response.status_code) {
+            None => (
+                NotificationType::Warning,
+                "The request failed, so it was not saved".to_string(),
+            ),
+            Some(status) if !(200..300).contains(&status) => (
+                NotificationType::Warning,
+                format!("Not saved: the server responded with {status}"),
+            ),
+            Some(_) => match save.destination {
+                Some((destination, name)) => {
+                    let message = format!("Saved “{name}” to the collection");
+                    self.save_request_for_tab(
+                        Some(save.tab_id),
+                        destination,
+                        name,
+                        request_data,
+                        cx,
+                    );
+                    (NotificationType::Success, message)
+                }
+                None if self.update_saved_request_for_tab(save.tab_id, request_data, None, cx) => (
+                    NotificationType::Success,
+                    "Saved request updated".to_string(),
+                ),
+                None => (
+                    NotificationType::Warning,
+                    "The saved request no longer exists.".to_string(),
+                ),
+            },
+        };
+        if save.save_response
+            && matches!(kind, NotificationType::Success)
+            && let Some(response) = response
+        {
+            self.save_response_example(save.tab_id, response.clone(), cx);
+        }
+        let _ = save.window.update(cx, |_, window, cx| {
+            window.push_notification((kind, SharedString::from(message)), cx);
+        });
+    }
+
+    /// Keep `response` on the saved request `tab_id` is linked to, with its body cut to the
+    /// same size history keeps.
+    fn save_response_example(
+        &mut self,
+        tab_id: TabId,
+        mut response: ResponseData,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((collection_id, node_id)) = self
+            .tabs
+            .iter()
+            .find(|tab| tab.id == tab_id)
+            .and_then(|tab| tab.collection_id.zip(tab.collection_node_id))
+        else {
+            return;
+        };
+        response.truncate_body(self.history.read(cx).max_response_body_bytes);
+        self.collections.update(cx, |collections, cx| {
+            collections.add_request_example(collection_id, node_id, response, cx)
+        });
+    }
+
+    /// The request in `request_view` as it goes out to `url`, which already carries the
+    /// params: templates resolved and the default scheme applied. Errors are ready to show.
+    fn resolve_outgoing_request(
+        &self,
+        request_entity: &Entity<RequestEntity>,
+        request_view: &Entity<RequestView>,
+        url: String,
+        collection_id: Option<Uuid>,
+        cx: &App,
+    ) -> Result<OutgoingRequest, String> {
+        // Get request params, then resolve templates only for the outgoing request.
+        // Stored requests and history retain {{variables}} so secrets are not copied there.
+        let (
+            method,
+            template_headers,
+            template_body,
+            preserve_header_order,
+            inherit_auth,
+            assertions,
+            pre_request_script,
+            extractions,
+            disabled_params,
+        ) = {
+            let request = request_entity.read(cx);
+            (
+                request.method(),
+                request.headers().to_vec(),
+                request.body().clone(),
+                request.preserve_header_order(),
+                request.inherit_auth(),
+                request.assertions().to_string(),
+                request.pre_request_script().to_string(),
+                request.extractions().to_string(),
+                request.disabled_params().to_vec(),
+            )
+        };
+        // The script runs first so the variables it sets can fill `{{name}}` below.
+        let script_output = if pre_request_script.trim().is_empty() {
+            PreRequestOutput::default()
+        } else {
+            let environments = self.environments.read(cx);
+            let env = |key: &str| {
+                let template = format!("{{{{{key}}}}}");
+                environments
+                    .resolve_values(collection_id, [template.as_str()])
+                    .ok()
+                    .map(|[value]| value)
+            };
+            run_pre_request_script(
+                &pre_request_script,
+                &PreRequestContext {
+                    now: chrono::Utc::now(),
+                    env: &env,
+                },
+            )
+            .map_err(|error| format!("Pre-request script failed. {error}"))?
+        };
+        let resolved = self
+            .environments
+            .read(cx)
+            .resolve_request_with(
+                collection_id,
+                &script_output.variables,
+                &url,
+                &template_headers,
+                &template_body,
+            )
+            .map_err(|error| error.user_message())?;
+        let resolved_url = self.ui_preferences.default_scheme.apply(&resolved.url);
+        let auth = request_view.read(cx).resolved_auth(cx);
+        let mut resolved_headers = resolved.headers;
+        // The OAuth token is only ever added here, so it stays out of history and collections.
+        let oauth_header = auth.oauth_header(chrono::Utc::now());
+        for (key, value) in oauth_header.into_iter().chain(script_output.headers) {
+            resolved_headers.retain(|header| !header.key.eq_ignore_ascii_case(&key));
+            resolved_headers.push(Header::new(key, value));
+        }
+        let resolved_body = resolved.body;
+
+        // SigV4 signs the final request, so the client computes it after building the URL above.
+        let aws_sigv4 = if auth.auth_type == AuthType::AwsSigV4 {
+            let credentials = self.environments.read(cx).resolve_values(
+                collection_id,
+                [
+                    auth.aws_access_key.as_str(),
+                    auth.aws_secret_key.as_str(),
+                    auth.aws_region.as_str(),
+                    auth.aws_service.as_str(),
+                    auth.aws_session_token.as_str(),
+                ],
+            );
+            match credentials {
+                Ok(values) => {
+                    let [access_key, secret_key, region, service, session_token] =
+                        values.map(|value| value.trim().to_string());
+                    Some(AwsSigV4 {
+                        access_key,
+                        secret_key,
+                        region,
+                        service,
+                        session_token,
+                    })
+                }
+                Err(error) => return Err(error.user_message()),
+            }
+        } else {
+            None
+        };
+
+        Ok(OutgoingRequest {
+            url,
+            method,
+            template_headers,
+            template_body,
+            preserve_header_order,
+            inherit_auth,
+            assertions,
+            pre_request_script,
+            extractions,
+            disabled_params,
+            resolved_url,
+            resolved_headers,
+            resolved_body,
+            aws_sigv4,
+        })
+    }
+
+    /// The proxy and client certificate requests in `collection_id` go out with: the active
+    /// environment's own, else the app-wide proxy.
+    fn connection_settings(
+        &self,
+        collection_id: Option<Uuid>,
+        cx: &App,
+    ) -> (Option<ProxyConfig>, Option<TlsConfig>) {
+        let environments = self.environments.read(cx);
+        let environment = environments.active_environment(collection_id);
+        let proxy = environment
+            .and_then(|environment| environment.proxy.clone())
+            .unwrap_or_else(|| self.ui_preferences.proxy.clone());
+        let tls = environment
+            .and_then(|environment| environment.tls.clone())
+            .filter(|tls| !tls.is_empty());
+        ((!proxy.is_empty()).then_some(proxy), tls)
+    }
+
+    /// Show the active request exactly as it would be sent, without sending it.
+    pub fn preview_active_request(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.active_tab() else {
+            return;
+        };
+        let TabContent::Request {
+            request: request_entity,
+            url_input,
+            request_view,
+            protocol: ProtocolType::Rest,
+            ..
+        } = &tab.content
+        else {
+            window.push_notification(
+                (NotificationType::Info, "Preview works with HTTP requests"),
+                cx,
+            );
+            return;
+        };
+        let request_entity = request_entity.clone();
+        let request_view = request_view.clone();
+        let collection_id = tab.collection_id;
+        let base_url = url_input
+            .as_ref()
+            .map(|input| input.read(cx).text().to_string())
+            .unwrap_or_default();
+        if base_url.is_empty() {
+            window.push_notification((NotificationType::Warning, "Please enter a URL"), cx);
+            return;
+        }
+
+        let collections = self.collections.clone();
+        request_view.update(cx, |view, cx| {
+            view.set_owning_collection(&collections, collection_id, cx);
+            view.sync_body_to_request(cx);
+            view.sync_headers_to_request(cx);
+        });
+        let outgoing = match self.resolve_outgoing_request(
+            &request_entity,
+            &request_view,
+            base_url,
+            collection_id,
+            cx,
+        ) {
+            Ok(outgoing) => outgoing,
+            Err(message) => {
+                window
+                    .push_notification((NotificationType::Error, SharedString::from(message)), cx);
+                return;
+            }
+        };
+        let cookie_scope = self
+            .environments
+            .read(cx)
+            .active_environment_id(collection_id);
+        let (proxy, tls) = self.connection_settings(collection_id, cx);
+        let preview_rx = self.http_client.preview_request(
+            outgoing.method,
+            outgoing.resolved_url,
+            outgoing.resolved_headers,
+            outgoing.resolved_body,
+            RequestOptions {
+                preserve_header_order: outgoing.preserve_header_order,
+                cookie_scope,
+                aws_sigv4: outgoing.aws_sigv4,
+                proxy,
+                tls,
+                ..RequestOptions::default()
+            },
+        );
+
+        cx.spawn_in(window, async move |_, cx| {
+            let preview = preview_rx
+                .await
+                .unwrap_or_else(|_| Err(anyhow::anyhow!("Request preview was cancelled")));
+            let _ = cx.update(|window, cx| match preview {
+                Ok(preview) => Self::show_request_preview_dialog(preview, window, cx),
+                Err(error) => window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::from(error.to_string()),
+                    ),
+                    cx,
+                ),
             });
         })
         .detach();
     }
 
-    fn show_import_summary_dialog(
-        &mut self,
-        summary: ImportSummary,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        let muted_foreground = cx.theme().muted_foreground;
-        open_dialog(window, cx, move |dialog, _, _| {
-            let warning_count = summary.warnings.len();
-            let warnings = summary.warnings.clone();
-
+    fn show_request_preview_dialog(preview: String, window: &mut Window, cx: &mut App) {
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let preview_for_copy = preview.clone();
             dialog
-                .title("Import Summary")
+                .title("Request Preview")
+                .w(px(640.0))
                 .child(
-                    v_flex()
-                        .gap_3()
-                        .child(format!("Provider: {}", summary.provider))
-                        .child(format!("{}: {}", summary.item_kind, summary.item_name))
-                        .when_some(summary.folder_count, |element, count| {
-                            element.child(format!("Folders imported: {count}"))
-                        })
-                        .when_some(summary.request_count, |element, count| {
-                            element.child(format!("Requests imported: {count}"))
-                        })
-                        .child(format!("Variables imported: {}", summary.variable_count))
-                        .child(format!("Warnings: {}", warning_count))
-                        .child(if warnings.is_empty() {
-                            div()
-                                .text_size(px(12.0))
-                                .child("No warnings.")
-                                .into_any_element()
-                        } else {
-                            div()
-                                .flex()
-                                .flex_col()
-                                .gap(px(6.0))
-                                .max_h(px(220.0))
-                                .overflow_y_scrollbar()
-                                .children(warnings.into_iter().map(|warning| {
-                                    let location =
-                                        warning.path.unwrap_or_else(|| "Import".to_string());
-                                    div()
-                                        .flex()
-                                        .flex_col()
-                                        .gap(px(2.0))
-                                        .child(
-                                            div()
-                                                .text_size(px(11.0))
-                                                .font_weight(gpui::FontWeight::SEMIBOLD)
-                                                .child(location),
-                                        )
-                                        .child(
-                                            div()
-                                                .text_size(px(12.0))
-                                                .text_color(muted_foreground)
-                                                .child(warning.message),
-                                        )
-                                }))
-                                .into_any_element()
-                        }),
+                    div()
+                        .id("request-preview")
+                        .max_h(px(420.0))
+                        .overflow_y_scroll()
+                        .p(px(8.0))
+                        .rounded(px(4.0))
+                        .bg(cx.theme().muted)
+                        .font_family(cx.theme().mono_font_family.clone())
+                        .text_size(px(12.0))
+                        .children(
+                            preview
+                                .lines()
+                                .map(|line| div().min_h(px(16.0)).child(line.to_string())),
+                        ),
                 )
                 .footer(
-                    DialogFooter::new().child(
-                        Button::new("import-summary-close")
-                            .primary()
-                            .label("Close")
-                            .on_click(|_, window, cx| {
-                                close_dialog(window, cx);
-                            }),
-                    ),
+                    DialogFooter::new()
+                        .child(
+                            Button::new("request-preview-copy")
+                                .label("Copy")
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    cx.write_to_clipboard(gpui::ClipboardItem::new_string(
+                                        preview_for_copy.clone(),
+                                    ));
+                                    window.push_notification(
+                                        (NotificationType::Success, "Request copied"),
+                                        cx,
+                                    );
+                                }),
+                        )
+                        .child(
+                            Button::new("request-preview-close")
+                                .label("Close")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
                 )
         });
     }
 
-    /// Close all tabs except the one at the given index
-    pub fn close_other_tabs(&mut self, index: usize, cx: &mut Context<Self>) {
-        if index < self.tabs.len() {
-            for i in 0..self.tabs.len() {
-                if i != index {
-                    self.cancel_in_flight_for_tab(i, cx);
-                }
-            }
-
-            // Keep only the tab at the given index
-            let tab_to_keep = self.tabs.remove(index);
-            self.tabs.clear();
-            self.tabs.push(tab_to_keep);
-            self.active_tab_index = 0;
-
-            cx.notify();
-        }
-    }
-
-    /// Send the current request
-    pub fn send_request(&mut self, cx: &mut Context<Self>) {
+    /// `refresh_oauth_token` is cleared when resuming after a token fetch, so a token the
+    /// server issues already near expiry cannot loop.
+    fn send_request_with(&mut self, refresh_oauth_token: bool, cx: &mut Context<Self>) {
         let tab_index = self.active_tab_index;
         let Some(tab) = self.tabs.get(tab_index) else {
             return;
@@ -2477,16 +5901,31 @@ impl MainView {
             response: response_entity,
             url_input,
             request_view,
+            response_view,
+            protocol,
+            websocket,
             ..
         } = &tab.content
         else {
             return;
         };
 
+        // The send shortcut connects WebSocket tabs; disconnecting stays on the button.
+        if *protocol == ProtocolType::WebSocket {
+            let is_idle = websocket
+                .as_ref()
+                .is_some_and(|websocket| !websocket.socket.read(cx).state().is_active());
+            if is_idle {
+                self.toggle_websocket_connection(cx);
+            }
+            return;
+        }
+
         let tab_id = tab.id;
         let request_entity = request_entity.clone();
         let response_entity = response_entity.clone();
         let request_view = request_view.clone();
+        let response_view = response_view.clone();
         let tab_name = tab.name.clone();
         let collection_id = tab.collection_id;
 
@@ -2511,54 +5950,103 @@ impl MainView {
         }
 
         // Sync body and headers from RequestView to RequestEntity
+        let collections = self.collections.clone();
         request_view.update(cx, |view, cx| {
+            view.set_owning_collection(&collections, collection_id, cx);
             view.sync_body_to_request(cx);
             view.sync_headers_to_request(cx);
         });
 
-        // Get query string from params editor
-        let query_string = request_view.read(cx).get_query_string(cx);
-
-        // Build final URL with query params
-        let url = if !query_string.is_empty() {
-            // Check if URL already has query params
-            if base_url.contains('?') {
-                format!("{}&{}", base_url, &query_string[1..]) // Skip the leading '?'
-            } else {
-                format!("{}{}", base_url, query_string)
-            }
-        } else {
-            base_url
-        };
+        // An OAuth config without a live token fetches one first; the send resumes after. A dry
+        // run never fetches one and shows a placeholder instead.
+        let needs_oauth_token = request_view
+            .read(cx)
+            .resolved_auth(cx)
+            .needs_oauth_token(chrono::Utc::now());
+        if refresh_oauth_token && needs_oauth_token && !self.ui_preferences.dry_run {
+            self.fetch_oauth_token(true, cx);
+            return;
+        }
 
-        // Get request params, then resolve templates only for the outgoing request.
-        // Stored requests and history retain {{variables}} so secrets are not copied there.
-        let (method, template_headers, template_body) = {
-            let request = request_entity.read(cx);
-            (
-                request.method(),
-                request.headers().to_vec(),
-                request.body().clone(),
-            )
-        };
-        let resolved = self.environments.read(cx).resolve_request(
+        let outgoing = match self.resolve_outgoing_request(
+            &request_entity,
+            &request_view,
+            base_url,
             collection_id,
-            &url,
-            &template_headers,
-            &template_body,
-        );
-        let resolved = match resolved {
-            Ok(resolved) => resolved,
-            Err(error) => {
+            cx,
+        ) {
+            Ok(outgoing) => outgoing,
+            Err(message) => {
                 response_entity.update(cx, |response, cx| {
-                    response.set_error(error.user_message(), cx);
+                    response.set_error(message, cx);
                 });
                 return;
             }
         };
-        let resolved_url = resolved.url;
-        let resolved_headers = resolved.headers;
-        let resolved_body = resolved.body;
+        let OutgoingRequest {
+            url,
+            method,
+            template_headers,
+            template_body,
+            preserve_header_order,
+            inherit_auth,
+            assertions,
+            pre_request_script,
+            extractions,
+            disabled_params,
+            resolved_url,
+            resolved_headers,
+            resolved_body,
+            aws_sigv4,
+        } = outgoing;
+        let cookie_scope = self
+            .environments
+            .read(cx)
+            .active_environment_id(collection_id);
+
+        let (proxy, tls) = self.connection_settings(collection_id, cx);
+        response_view.update(cx, |view, _| view.begin_request(method, &url));
+
+        // A dry run shows the request in place of a response and never touches the network.
+        if self.ui_preferences.dry_run {
+            let mut resolved_headers = resolved_headers;
+            if needs_oauth_token
+                && !resolved_headers
+                    .iter()
+                    .any(|header| header.key.eq_ignore_ascii_case("Authorization"))
+            {
+                resolved_headers.push(Header::new(
+                    "Authorization",
+                    "Bearer <OAuth token, fetched on send>",
+                ));
+            }
+            let preview_rx = self.http_client.preview_request(
+                method,
+                resolved_url,
+                resolved_headers,
+                resolved_body,
+                RequestOptions {
+                    preserve_header_order,
+                    cookie_scope,
+                    aws_sigv4,
+                    proxy,
+                    tls,
+                    ..RequestOptions::default()
+                },
+            );
+            cx.spawn(async move |_, cx| {
+                let preview = preview_rx
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Request preview was cancelled")));
+                let _ = response_entity.update(cx, |response, cx| match preview {
+                    Ok(preview) => response.set_response(ResponseData::dry_run(preview), cx),
+                    Err(error) => response.set_error(error.to_string(), cx),
+                });
+            })
+            .detach();
+            return;
+        }
+
         request_entity.update(cx, |request, cx| {
             request.set_sending(true, cx);
         });
@@ -2568,6 +6056,27 @@ impl MainView {
 
         let started_at = std::time::Instant::now();
         log::info!("Sending {} request", method.as_str());
+        let auto_save = request_entity.read(cx).auto_save().cloned();
+        let subprotocols = request_entity.read(cx).subprotocols().to_string();
+
+        let sent_url = resolved_url.clone();
+        let sent_request = RequestData {
+            id: Uuid::new_v4(),
+            name: tab_name.clone(),
+            url: resolved_url.clone(),
+            method,
+            headers: resolved_headers.clone(),
+            body: resolved_body.clone(),
+            preserve_header_order,
+            inherit_auth,
+            assertions: assertions.clone(),
+            pre_request_script: pre_request_script.clone(),
+            extractions: extractions.clone(),
+            disabled_params: disabled_params.clone(),
+            subprotocols: subprotocols.clone(),
+            auto_save: auto_save.clone(),
+            is_sending: false,
+        };
 
         // Create request data for history before sending
         let history_request_data = RequestData {
@@ -2577,24 +6086,46 @@ impl MainView {
             method,
             headers: template_headers,
             body: template_body,
+            preserve_header_order,
+            inherit_auth,
+            assertions,
+            pre_request_script,
+            extractions: extractions.clone(),
+            disabled_params,
+            subprotocols,
+            auto_save: auto_save.clone(),
             is_sending: false,
         };
 
         let history_entity = self.history.clone();
 
         // Spawn HTTP request on Tokio runtime and keep a cancel handle on the tab.
-        let (result_rx, in_flight_request) =
-            self.http_client
-                .spawn_request(method, resolved_url, resolved_headers, resolved_body);
+        let (result_rx, mut updates_rx, in_flight_request) = self.http_client.spawn_request(
+            method,
+            resolved_url,
+            resolved_headers,
+            resolved_body,
+            RequestOptions {
+                preserve_header_order,
+                cookie_scope,
+                aws_sigv4,
+                proxy: proxy.clone(),
+                tls: tls.clone(),
+                retry: self.ui_preferences.retry,
+                ..RequestOptions::default()
+            },
+        );
         let generation = if let Some(tab) = self.tabs.get_mut(tab_index) {
             if let TabContent::Request {
                 in_flight_request: tab_in_flight,
+                sent_request: tab_sent_request,
                 request_generation,
                 ..
             } = &mut tab.content
             {
                 let generation_count = request_generation.advance();
                 *tab_in_flight = Some(in_flight_request);
+                *tab_sent_request = Some(sent_request);
                 generation_count
             } else {
                 return;
@@ -2602,9 +6133,64 @@ impl MainView {
         } else {
             return;
         };
+        // Taken only once the request is really sent, so a send that waits for an OAuth
+        // token still saves when it resumes.
+        let save_after_send = self.save_after_send.take_if(|save| save.tab_id == tab_id);
+        // Where the send started, for notices about its response.
+        let origin_window = save_after_send
+            .as_ref()
+            .map(|save| save.window)
+            .or_else(|| cx.active_window());
 
         // Spawn foreground task to await result and update UI
         cx.spawn(async move |view, cx| {
+            // Uploads and event streams report progress first; the channel closes before the
+            // result lands.
+            while let Some(update) = updates_rx.recv().await {
+                let is_current = view
+                    .update(cx, |main, cx| {
+                        let current = main.tabs.iter().any(|tab| {
+                            tab.id == tab_id
+                                && matches!(
+                                    &tab.content,
+                                    TabContent::Request { request_generation, .. }
+                                        if *request_generation == generation
+                                )
+                        });
+                        if current {
+                            match update {
+                                ResponseUpdate::StreamStarted(head) => response_entity
+                                    .update(cx, |resp, cx| resp.set_streaming(head, cx)),
+                                ResponseUpdate::StreamChunk { raw, events } => response_entity
+                                    .update(cx, |resp, cx| {
+                                        resp.push_stream_chunk(&raw, events, cx)
+                                    }),
+                                ResponseUpdate::UploadProgress { sent, total } => request_entity
+                                    .update(cx, |req, cx| req.set_upload_progress(sent, total, cx)),
+                                ResponseUpdate::Retrying {
+                                    attempt,
+                                    max_attempts,
+                                    reason,
+                                } => response_entity.update(cx, |resp, cx| {
+                                    resp.set_retrying(
+                                        RetryStatus {
+                                            attempt,
+                                            max_attempts,
+                                            reason,
+                                        },
+                                        cx,
+                                    )
+                                }),
+                            }
+                        }
+                        current
+                    })
+                    .unwrap_or(false);
+                if !is_current {
+                    break;
+                }
+            }
+
             // Await the result from Tokio runtime
             let result = result_rx.await;
 
@@ -2637,13 +6223,35 @@ impl MainView {
                                 data.duration_ms,
                                 data.body_size_bytes
                             );
-                            history_entity.update(cx, |history, cx| {
+                            let entry_id = history_entity.update(cx, |history, cx| {
                                 history.add_entry(
                                     history_request_data.clone(),
                                     Some(data.clone()),
                                     cx,
-                                );
+                                )
                             });
+                            main.set_tab_history_entry(tab_id, entry_id);
+                            if let Some(save) = save_after_send {
+                                main.finish_send_and_save(
+                                    save,
+                                    history_request_data.clone(),
+                                    Some(&data),
+                                    cx,
+                                );
+                            }
+                            if let Some(auto_save) = auto_save.as_ref() {
+                                main.auto_save_response(auto_save, &data, cx);
+                            }
+                            request_view.update(cx, |view, cx| view.run_tests(&data, cx));
+                            if !extractions.trim().is_empty() {
+                                main.apply_extractions(
+                                    &extractions,
+                                    collection_id,
+                                    &data,
+                                    origin_window,
+                                    cx,
+                                );
+                            }
                             response_entity.update(cx, |resp, cx| resp.set_success(data, cx));
                         }
                         Ok(Err(error)) => {
@@ -2653,11 +6261,27 @@ impl MainView {
                                 started_at.elapsed().as_millis(),
                                 error
                             );
-                            history_entity.update(cx, |history, cx| {
-                                history.add_entry(history_request_data.clone(), None, cx);
+                            let entry_id = history_entity.update(cx, |history, cx| {
+                                history.add_entry(history_request_data.clone(), None, cx)
+                            });
+                            main.set_tab_history_entry(tab_id, entry_id);
+                            if let Some(save) = save_after_send {
+                                main.finish_send_and_save(
+                                    save,
+                                    history_request_data.clone(),
+                                    None,
+                                    cx,
+                                );
+                            }
+                            let mut details = ErrorDetails::from_error(&error)
+                                .with_context("URL", sent_url.clone());
+                            details.context.extend(
+                                main.http_client
+                                    .settings_summary(proxy.as_ref(), tls.as_ref()),
+                            );
+                            response_entity.update(cx, |resp, cx| {
+                                resp.set_error_with_details(error.to_string(), details, cx)
                             });
-                            response_entity
-                                .update(cx, |resp, cx| resp.set_error(error.to_string(), cx));
                         }
                         Err(_) => {
                             // Cancellation advances the generation immediately, so this is only
@@ -2671,6 +6295,111 @@ impl MainView {
         .detach();
     }
 
+    fn active_protocol(&self) -> ProtocolType {
+        match self.active_tab().map(|tab| &tab.content) {
+            Some(TabContent::Request { protocol, .. }) => *protocol,
+            _ => ProtocolType::Rest,
+        }
+    }
+
+    /// Switch the active request tab between REST and WebSocket panels.
+    fn set_active_protocol(
+        &mut self,
+        new_protocol: ProtocolType,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity().downgrade();
+        let Some(tab) = self.tabs.get_mut(self.active_tab_index) else {
+            return;
+        };
+        let TabContent::Request {
+            request,
+            url_input: Some(url_input),
+            request_view,
+            protocol,
+            websocket,
+            ..
+        } = &mut tab.content
+        else {
+            return;
+        };
+
+        if new_protocol == ProtocolType::WebSocket && websocket.is_none() {
+            let socket = cx.new(|_| WebSocketEntity::new());
+            let url_input = url_input.clone();
+            let request = request.clone();
+            let header_editor = request_view.update(cx, |view, cx| view.header_editor(cx));
+            let view = cx.new(|cx| {
+                WebSocketView::new(
+                    socket.clone(),
+                    url_input,
+                    request,
+                    header_editor,
+                    window,
+                    cx,
+                )
+                .on_toggle_connection(move |_, cx| {
+                    let _ = this.update(cx, |view, cx| view.toggle_websocket_connection(cx));
+                })
+            });
+            let log_view = cx.new(|cx| WebSocketLogView::new(socket.clone(), cx));
+            *websocket = Some(WebSocketTab {
+                socket,
+                view,
+                log_view,
+            });
+        }
+        *protocol = new_protocol;
+        cx.notify();
+    }
+
+    /// Connect the active WebSocket tab, or close it when already connecting/connected.
+    fn toggle_websocket_connection(&mut self, cx: &mut Context<Self>) {
+        let Some(tab) = self.active_tab() else {
+            return;
+        };
+        let TabContent::Request {
+            request,
+            url_input: Some(url_input),
+            request_view,
+            websocket: Some(websocket),
+            ..
+        } = &tab.content
+        else {
+            return;
+        };
+        let socket = websocket.socket.clone();
+        if socket.read(cx).state().is_active() {
+            socket.update(cx, |socket, cx| socket.disconnect(cx));
+            return;
+        }
+
+        // Handshake headers come from the Headers tab's editor and are resolved like an HTTP
+        // send.
+        let url = url_input.read(cx).text().to_string();
+        let subprotocols = websocket.view.read(cx).subprotocols(cx);
+        request_view.update(cx, |view, cx| view.sync_headers_to_request(cx));
+        let headers = request.read(cx).headers().to_vec();
+        let resolved = self.environments.read(cx).resolve_request(
+            tab.collection_id,
+            &url,
+            &headers,
+            &RequestBody::None,
+        );
+        let ws_client = self.ws_client.clone();
+        socket.update(cx, |socket, cx| match resolved {
+            Ok(resolved) => socket.connect(
+                &ws_client,
+                &resolved.url,
+                &resolved.headers,
+                &subprotocols,
+                cx,
+            ),
+            Err(error) => socket.fail(error.user_message(), cx),
+        });
+    }
+
     pub fn cancel_request(&mut self, cx: &mut Context<Self>) {
         let tab_index = self.active_tab_index;
         let Some(tab) = self.tabs.get_mut(tab_index) else {
@@ -2695,7 +6424,7 @@ impl MainView {
             }
             request_generation.advance();
             request.update(cx, |r, cx| r.set_sending(false, cx));
-            response.update(cx, |r, cx| r.set_cancelled(cx));
+            response.update(cx, |r, cx| r.stop(cx));
             cx.notify();
         }
     }
@@ -2707,6 +6436,97 @@ impl MainView {
         cx.notify();
     }
 
+    fn toggle_format_on_paste(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let enabled = !self.ui_preferences.format_on_paste;
+        self.ui_preferences.format_on_paste = enabled;
+        cx.set_global(BodyEditorSettings {
+            format_on_paste: enabled,
+        });
+        self.persist_ui_preferences();
+        let message = if enabled {
+            "Pasted JSON/XML bodies will be formatted"
+        } else {
+            "Format on paste turned off"
+        };
+        window.push_notification((NotificationType::Info, message), cx);
+    }
+
+    /// Switch between sending requests and only showing what would be sent.
+    fn toggle_dry_run(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let enabled = !self.ui_preferences.dry_run;
+        self.ui_preferences.dry_run = enabled;
+        self.persist_ui_preferences();
+        let notification = if enabled {
+            (
+                NotificationType::Warning,
+                "Dry run on: sends show the request without reaching the network",
+            )
+        } else {
+            (NotificationType::Info, "Dry run off: requests will be sent")
+        };
+        window.push_notification(notification, cx);
+        cx.notify();
+    }
+
+    /// Switch between returning 3xx responses as-is and following up to the default limit.
+    fn toggle_follow_redirects(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let policy = if self.http_client.redirect_policy().follows() {
+            RedirectPolicy::None
+        } else {
+            RedirectPolicy::default()
+        };
+        if let Err(error) = self.http_client.set_redirect_policy(policy) {
+            window.push_notification(
+                (
+                    NotificationType::Error,
+                    SharedString::from(format!("Failed to update redirect policy: {error}")),
+                ),
+                cx,
+            );
+            return;
+        }
+        self.ui_preferences.redirect_policy = policy;
+        self.persist_ui_preferences();
+        let message = if policy.follows() {
+            "Redirects will be followed"
+        } else {
+            "Redirects will be returned without following"
+        };
+        window.push_notification((NotificationType::Info, message), cx);
+    }
+
+    /// Switch the scheme added to URLs typed without one between https and http.
+    fn toggle_default_scheme(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let scheme = match self.ui_preferences.default_scheme {
+            UrlScheme::Https => UrlScheme::Http,
+            UrlScheme::Http => UrlScheme::Https,
+        };
+        self.ui_preferences.default_scheme = scheme;
+        self.persist_ui_preferences();
+        window.push_notification(
+            (
+                NotificationType::Info,
+                SharedString::from(format!(
+                    "URLs without a scheme will use {}",
+                    scheme.prefix()
+                )),
+            ),
+            cx,
+        );
+    }
+
+    fn toggle_focus_url_on_new_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let enabled = !self.ui_preferences.focus_url_on_new_tab;
+        self.ui_preferences.focus_url_on_new_tab = enabled;
+        self.persist_ui_preferences();
+        let message = if enabled {
+            "New tabs will focus the URL bar"
+        } else {
+            "New tabs will leave focus where it was"
+        };
+        window.push_notification((NotificationType::Info, message), cx);
+    }
+
     fn toggle_request_response_layout(&mut self, cx: &mut Context<Self>) {
         let next_layout = match self.request_response_layout {
             RequestResponseLayout::Stacked => RequestResponseLayout::SideBySide,
@@ -2737,6 +6557,9 @@ impl MainView {
                 palette.close(cx);
             });
             close_dialog(window, cx);
+            self.command_palette.update(cx, |palette, cx| {
+                palette.restore_focus(window, cx);
+            });
             return;
         }
 
@@ -2746,10 +6569,8 @@ impl MainView {
         let palette = self.command_palette.clone();
         let palette_for_close = palette.clone();
         let palette_for_focus = palette.clone();
-        let app_focus_for_close = self.focus_handle.clone();
         window.open_dialog(cx, move |dialog, _, _| {
             let palette_for_close = palette_for_close.clone();
-            let app_focus_for_close = app_focus_for_close.clone();
             dialog
                 .w(px(560.0))
                 .margin_top(px(48.0))
@@ -2757,8 +6578,10 @@ impl MainView {
                 .overflow_hidden()
                 .close_button(false)
                 .on_close(move |_, window, cx| {
-                    palette_for_close.update(cx, |palette, cx| palette.close(cx));
-                    app_focus_for_close.focus(window, cx);
+                    palette_for_close.update(cx, |palette, cx| {
+                        palette.close(cx);
+                        palette.restore_focus(window, cx);
+                    });
                 })
                 .child(palette.clone())
         });
@@ -2771,8 +6594,11 @@ impl MainView {
         match cmd_id {
             CommandId::SendRequest => self.send_request(cx),
             CommandId::CancelRequest => self.cancel_request(cx),
-            CommandId::NewRequest => self.new_tab(cx),
             CommandId::CloseTab => self.close_current_tab(cx),
+            CommandId::ReopenClosedTab => {
+                self.pending_window_command = Some(cmd_id);
+                cx.notify();
+            }
             CommandId::CloseAllTabs => self.close_all_tabs(cx),
             CommandId::CloseOtherTabs => self.close_current_other_tabs(cx),
             CommandId::NextTab => self.next_tab(cx),
@@ -2788,7 +6614,10 @@ impl MainView {
             CommandId::GoToLastTab => self.go_to_last_tab(cx),
             CommandId::ToggleSidebar => self.toggle_sidebar(cx),
             CommandId::ToggleRequestResponseLayout => self.toggle_request_response_layout(cx),
-            CommandId::SaveToCollection | CommandId::ImportCollection => {
+            CommandId::SendAndSave
+            | CommandId::SaveRequest
+            | CommandId::SaveToCollection
+            | CommandId::ImportCollection => {
                 self.pending_window_command = Some(cmd_id);
                 cx.notify();
             }
@@ -2803,24 +6632,54 @@ impl MainView {
                 self.history.update(cx, |h, cx| h.clear(cx));
             }
             CommandId::SwitchToBodyTab => {
-                self.switch_to_request_tab(crate::views::request_view::RequestTab::Body, cx);
+                self.switch_to_request_tab(RequestTab::Body, cx);
             }
             CommandId::SwitchToParamsTab => {
-                self.switch_to_request_tab(crate::views::request_view::RequestTab::Params, cx);
+                self.switch_to_request_tab(RequestTab::Params, cx);
             }
             CommandId::SwitchToHeadersTab => {
-                self.switch_to_request_tab(crate::views::request_view::RequestTab::Headers, cx);
+                self.switch_to_request_tab(RequestTab::Headers, cx);
             }
             CommandId::SwitchToAuthTab => {
-                self.switch_to_request_tab(crate::views::request_view::RequestTab::Auth, cx);
+                self.switch_to_request_tab(RequestTab::Auth, cx);
+            }
+            CommandId::SwitchToTestsTab => {
+                self.switch_to_request_tab(RequestTab::Tests, cx);
             }
             CommandId::SwitchToResponseBody => {
-                self.switch_to_response_tab(crate::views::response_view::ResponseTab::Body, cx);
+                self.switch_to_response_tab(ResponseTab::Body, cx);
             }
             CommandId::SwitchToResponseHeaders => {
-                self.switch_to_response_tab(crate::views::response_view::ResponseTab::Headers, cx);
+                self.switch_to_response_tab(ResponseTab::Headers, cx);
             }
-            CommandId::DuplicateRequest | CommandId::FocusUrlBar => {
+            CommandId::NewRequest
+            | CommandId::DuplicateRequest
+            | CommandId::EditAndResend
+            | CommandId::FocusUrlBar
+            | CommandId::ExportHistoryJson
+            | CommandId::ExportHistoryCsv
+            | CommandId::ToggleHistoryStar
+            | CommandId::ImportCurl
+            | CommandId::CopyAsCurl
+            | CommandId::ToggleFormatOnPaste
+            | CommandId::FormatSelectionAsJson
+            | CommandId::ToggleFollowRedirects
+            | CommandId::ToggleDryRun
+            | CommandId::ToggleDefaultScheme
+            | CommandId::ToggleFocusUrlOnNewTab
+            | CommandId::ConfigureProxy
+            | CommandId::ConfigureEnvironmentProxy
+            | CommandId::ConfigureClientCertificate
+            | CommandId::ToggleInsecureTls
+            | CommandId::ConfigureRetries
+            | CommandId::PreviewRequest
+            | CommandId::GenerateCode
+            | CommandId::ChangeHost
+            | CommandId::ConfigurePool
+            | CommandId::ConfigureAutoSave
+            | CommandId::ConfigureAutoFormat
+            | CommandId::ConfigureHistoryBodyLimit
+            | CommandId::ManageHeaderPresets => {
                 self.pending_window_command = Some(cmd_id);
                 cx.notify();
             }
@@ -2859,6 +6718,7 @@ impl MainView {
     pub fn close_all_tabs(&mut self, cx: &mut Context<Self>) {
         self.cancel_in_flight_for_all_tabs(cx);
         while self.tabs.len() > 1 {
+            self.remember_closed_tab(self.tabs.len() - 1, cx);
             self.tabs.pop();
         }
         self.active_tab_index = 0;
@@ -2889,6 +6749,14 @@ impl MainView {
             let old_body_type = old_request_view.read(cx).get_body_type();
             let old_method = old_request.read(cx).method();
             let old_headers: Vec<_> = old_request.read(cx).headers().to_vec();
+            let old_preserve_header_order = old_request.read(cx).preserve_header_order();
+            let old_inherit_auth = old_request.read(cx).inherit_auth();
+            let old_assertions = old_request.read(cx).assertions().to_string();
+            let old_pre_request_script = old_request.read(cx).pre_request_script().to_string();
+            let old_extractions = old_request.read(cx).extractions().to_string();
+            let old_subprotocols = old_request.read(cx).subprotocols().to_string();
+            let old_disabled_params = old_request.read(cx).disabled_params().to_vec();
+            let old_auto_save = old_request.read(cx).auto_save().cloned();
             let old_body = old_request.read(cx).body().clone();
             let new_response = cx.new(|_| ResponseEntity::new());
 
@@ -2909,7 +6777,9 @@ impl MainView {
                 }
                 RequestBody::None
                 | RequestBody::FormData(_)
-                | RequestBody::MultipartFormData(_) => None,
+                | RequestBody::MultipartFormData(_)
+                | RequestBody::GraphQl { .. }
+                | RequestBody::Binary(_) => None,
             };
 
             let form_data = match &old_body {
@@ -2925,7 +6795,15 @@ impl MainView {
             let new_request = cx.new(|_| {
                 let mut req = RequestEntity::new()
                     .with_method(old_method)
-                    .with_headers(old_headers);
+                    .with_headers(old_headers)
+                    .with_preserve_header_order(old_preserve_header_order)
+                    .with_inherit_auth(old_inherit_auth)
+                    .with_assertions(old_assertions)
+                    .with_pre_request_script(old_pre_request_script)
+                    .with_extractions(old_extractions)
+                    .with_subprotocols(old_subprotocols)
+                    .with_disabled_params(old_disabled_params)
+                    .with_auto_save(old_auto_save);
                 req.data.url = url_text;
                 req.data.body = old_body;
                 req
@@ -2963,6 +6841,7 @@ impl MainView {
             let tab_id = TabId(self.next_tab_id);
             self.next_tab_id += 1;
             Self::subscribe_request_changes(&new_request, cx);
+            Self::subscribe_request_view_changes(&new_request_view, cx);
             let new_tab = TabState {
                 id: tab_id,
                 name: format!("{} (copy)", old_name),
@@ -2975,9 +6854,14 @@ impl MainView {
                     request_view: new_request_view,
                     response_view: new_response_view,
                     in_flight_request: None,
+                    sent_request: None,
                     request_generation: RequestGeneration::default(),
+                    protocol: ProtocolType::Rest,
+                    websocket: None,
                 },
                 collection_id: old_collection_id,
+                collection_node_id: None,
+                history_entry_id: None,
             };
 
             self.tabs.push(new_tab);
@@ -2987,6 +6871,45 @@ impl MainView {
         }
     }
 
+    /// Open the active tab's last send, variables already resolved, in a new tab to tweak
+    /// and send again.
+    pub fn edit_and_resend(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((sent_request, name, collection_id)) = self.active_tab().and_then(|tab| {
+            let TabContent::Request { sent_request, .. } = &tab.content else {
+                return None;
+            };
+            Some((sent_request.clone(), tab.name.clone(), tab.collection_id))
+        }) else {
+            return;
+        };
+        let Some(sent_request) = sent_request else {
+            window.push_notification(
+                (
+                    NotificationType::Info,
+                    "Send the request first to edit what went out",
+                ),
+                cx,
+            );
+            return;
+        };
+
+        let tab_id = TabId(self.next_tab_id);
+        let content = self.build_history_tab_content(tab_id, &sent_request, None, window, cx);
+        self.tabs.push(TabState {
+            id: tab_id,
+            name: format!("{name} (edited)"),
+            is_custom_name: true,
+            content,
+            collection_id,
+            collection_node_id: None,
+            history_entry_id: None,
+        });
+        self.next_tab_id += 1;
+        self.active_tab_index = self.tabs.len() - 1;
+        self.tab_scroll_handle.scroll_to_item(self.active_tab_index);
+        cx.notify();
+    }
+
     pub fn set_method(&mut self, method: HttpMethod, cx: &mut Context<Self>) {
         if let Some(tab) = self.tabs.get(self.active_tab_index)
             && let TabContent::Request {
@@ -3004,11 +6927,7 @@ impl MainView {
         }
     }
 
-    pub fn switch_to_request_tab(
-        &mut self,
-        tab: crate::views::request_view::RequestTab,
-        cx: &mut Context<Self>,
-    ) {
+    pub fn switch_to_request_tab(&mut self, tab: RequestTab, cx: &mut Context<Self>) {
         if let Some(active_tab) = self.tabs.get(self.active_tab_index)
             && let TabContent::Request { request_view, .. } = &active_tab.content
         {
@@ -3018,11 +6937,7 @@ impl MainView {
         }
     }
 
-    pub fn switch_to_response_tab(
-        &mut self,
-        tab: crate::views::response_view::ResponseTab,
-        cx: &mut Context<Self>,
-    ) {
+    pub fn switch_to_response_tab(&mut self, tab: ResponseTab, cx: &mut Context<Self>) {
         if let Some(active_tab) = self.tabs.get(self.active_tab_index)
             && let TabContent::Request { response_view, .. } = &active_tab.content
         {
@@ -3056,12 +6971,64 @@ impl Render for MainView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.completion_engine
             .set_collection_id(self.active_tab().and_then(|tab| tab.collection_id));
+        if let Some(tab) = self.active_tab()
+            && let Some(request_view) = tab.request_view()
+        {
+            let collection_id = tab.collection_id;
+            let collections = self.collections.clone();
+            request_view.update(cx, |view, cx| {
+                view.set_owning_collection(&collections, collection_id, cx);
+            });
+        }
         if let Some(cmd_id) = self.pending_window_command.take() {
             match cmd_id {
+                CommandId::NewRequest => self.open_new_tab(window, cx),
+                CommandId::ReopenClosedTab => self.reopen_closed_tab(window, cx),
                 CommandId::DuplicateRequest => self.duplicate_request(window, cx),
+                CommandId::EditAndResend => self.edit_and_resend(window, cx),
                 CommandId::FocusUrlBar => self.focus_url_bar(window, cx),
+                CommandId::SendAndSave => self.send_and_save(window, cx),
+                CommandId::SaveRequest => self.save_active_request(window, cx),
                 CommandId::SaveToCollection => self.show_save_to_collection_dialog(window, cx),
                 CommandId::ImportCollection => self.import_collection_from_file(window, cx),
+                CommandId::ImportCurl => self.import_curl_from_clipboard(window, cx),
+                CommandId::ToggleHistoryStar => self.toggle_active_history_star(window, cx),
+                CommandId::ExportHistoryJson => {
+                    self.export_history(HistoryExportFormat::Json, window, cx)
+                }
+                CommandId::ExportHistoryCsv => {
+                    self.export_history(HistoryExportFormat::Csv, window, cx)
+                }
+                CommandId::CopyAsCurl => self.copy_active_request_as_curl(window, cx),
+                CommandId::ToggleFormatOnPaste => self.toggle_format_on_paste(window, cx),
+                CommandId::FormatSelectionAsJson => self.format_selection_as_json(window, cx),
+                CommandId::ToggleFollowRedirects => self.toggle_follow_redirects(window, cx),
+                CommandId::ToggleDryRun => self.toggle_dry_run(window, cx),
+                CommandId::ToggleDefaultScheme => self.toggle_default_scheme(window, cx),
+                CommandId::ToggleFocusUrlOnNewTab => self.toggle_focus_url_on_new_tab(window, cx),
+                CommandId::ConfigureProxy => self.show_proxy_dialog(None, window, cx),
+                CommandId::ConfigureEnvironmentProxy => {
+                    if let Some(id) = self.active_environment_or_notify(window, cx) {
+                        self.show_proxy_dialog(Some(id), window, cx);
+                    }
+                }
+                CommandId::ToggleInsecureTls => self.toggle_insecure_tls(window, cx),
+                CommandId::ConfigureRetries => self.show_retry_dialog(window, cx),
+                CommandId::PreviewRequest => self.preview_active_request(window, cx),
+                CommandId::GenerateCode => self.show_generate_code_dialog(window, cx),
+                CommandId::ChangeHost => self.show_change_host_dialog(window, cx),
+                CommandId::ConfigurePool => self.show_pool_dialog(window, cx),
+                CommandId::ConfigureAutoSave => self.show_auto_save_dialog(window, cx),
+                CommandId::ConfigureAutoFormat => self.show_auto_format_dialog(window, cx),
+                CommandId::ConfigureHistoryBodyLimit => {
+                    self.show_history_body_limit_dialog(window, cx)
+                }
+                CommandId::ManageHeaderPresets => self.show_header_presets_dialog(window, cx),
+                CommandId::ConfigureClientCertificate => {
+                    if let Some(id) = self.active_environment_or_notify(window, cx) {
+                        self.show_client_certificate_dialog(id, window, cx);
+                    }
+                }
                 _ => {}
             }
         }
@@ -3087,21 +7054,17 @@ impl Render for MainView {
             .iter()
             .enumerate()
             .map(|(i, tab)| {
-                let (display_name, icon) = match &tab.content {
+                let (display_name, icon, is_loading) = match &tab.content {
                     TabContent::Request {
                         request, url_input, ..
                     } => {
-                        let method = request.read(cx).method();
-                        let name = if tab.is_custom_name {
-                            tab.name.clone()
-                        } else {
-                            let url = url_input
-                                .as_ref()
-                                .map(|input| input.read(cx).text().to_string())
-                                .unwrap_or_default();
-                            Self::derive_tab_name(&url)
-                        };
-                        (name, TabIcon::Method(method))
+                        let request = request.read(cx);
+                        let name = Self::request_tab_name(tab, url_input.as_ref(), cx);
+                        (
+                            name,
+                            TabIcon::Method(request.method()),
+                            request.is_sending(),
+                        )
                     }
                     TabContent::Environment { environment_id, .. } => {
                         let name = self
@@ -3110,10 +7073,11 @@ impl Render for MainView {
                             .get(*environment_id)
                             .map(|e| e.name.clone())
                             .unwrap_or_else(|| tab.name.clone());
-                        (name, TabIcon::Icon(IconName::Package))
+                        (name, TabIcon::Icon(IconName::Package), false)
                     }
                 };
-                let mut info = TabInfo::new(tab.id.0 as usize, i, display_name, icon);
+                let mut info =
+                    TabInfo::new(tab.id.0 as usize, i, display_name, icon).loading(is_loading);
                 if i == self.active_tab_index {
                     info = info.active();
                 }
@@ -3140,6 +7104,7 @@ impl Render for MainView {
                     req.is_sending(),
                     request_view.clone(),
                     response_view.clone(),
+                    Self::request_tab_name(tab, url_input.as_ref(), cx),
                 ))
             } else {
                 None
@@ -3199,15 +7164,36 @@ impl Render for MainView {
             .on_action(cx.listener(|this, _: &SendRequest, _window, cx| {
                 this.send_request(cx);
             }))
+            .on_action(cx.listener(|this, _: &SendAndSave, window, cx| {
+                this.send_and_save(window, cx);
+            }))
             .on_action(cx.listener(|this, _: &CancelRequest, _window, cx| {
                 this.cancel_request(cx);
             }))
-            .on_action(cx.listener(|this, _: &NewRequest, _window, cx| {
-                this.new_tab(cx);
+            .on_action(cx.listener(|this, _: &FormatSelectionAsJson, window, cx| {
+                this.format_selection_as_json(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ToggleHistoryStar, window, cx| {
+                this.toggle_active_history_star(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &FetchOAuthToken, _window, cx| {
+                this.fetch_oauth_token(false, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ManageHeaderPresets, window, cx| {
+                this.show_header_presets_dialog(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &NewRequest, window, cx| {
+                this.open_new_tab(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &EditAndResend, window, cx| {
+                this.edit_and_resend(window, cx);
             }))
             .on_action(cx.listener(|this, _: &DuplicateRequest, window, cx| {
                 this.duplicate_request(window, cx);
             }))
+            .on_action(cx.listener(|this, _: &SaveRequest, window, cx| {
+                this.save_active_request(window, cx);
+            }))
             .on_action(cx.listener(|this, _: &SaveToCollection, window, cx| {
                 this.show_save_to_collection_dialog(window, cx);
             }))
@@ -3230,6 +7216,9 @@ impl Render for MainView {
             .on_action(cx.listener(|this, _: &CloseOtherTabs, _window, cx| {
                 this.close_current_other_tabs(cx);
             }))
+            .on_action(cx.listener(|this, _: &ReopenClosedTab, window, cx| {
+                this.reopen_closed_tab(window, cx);
+            }))
             // Go to specific tabs
             .on_action(cx.listener(|this, _: &GoToTab1, _window, cx| {
                 this.go_to_tab(0, cx);
@@ -3272,27 +7261,27 @@ impl Render for MainView {
             }))
             // Request panel tabs
             .on_action(cx.listener(|this, _: &SwitchToBodyTab, _window, cx| {
-                this.switch_to_request_tab(crate::views::request_view::RequestTab::Body, cx);
+                this.switch_to_request_tab(RequestTab::Body, cx);
             }))
             .on_action(cx.listener(|this, _: &SwitchToParamsTab, _window, cx| {
-                this.switch_to_request_tab(crate::views::request_view::RequestTab::Params, cx);
+                this.switch_to_request_tab(RequestTab::Params, cx);
             }))
             .on_action(cx.listener(|this, _: &SwitchToHeadersTab, _window, cx| {
-                this.switch_to_request_tab(crate::views::request_view::RequestTab::Headers, cx);
+                this.switch_to_request_tab(RequestTab::Headers, cx);
             }))
             .on_action(cx.listener(|this, _: &SwitchToAuthTab, _window, cx| {
-                this.switch_to_request_tab(crate::views::request_view::RequestTab::Auth, cx);
+                this.switch_to_request_tab(RequestTab::Auth, cx);
+            }))
+            .on_action(cx.listener(|this, _: &SwitchToTestsTab, _window, cx| {
+                this.switch_to_request_tab(RequestTab::Tests, cx);
             }))
             // Response panel tabs
             .on_action(cx.listener(|this, _: &SwitchToResponseBody, _window, cx| {
-                this.switch_to_response_tab(crate::views::response_view::ResponseTab::Body, cx);
+                this.switch_to_response_tab(ResponseTab::Body, cx);
             }))
             .on_action(
                 cx.listener(|this, _: &SwitchToResponseHeaders, _window, cx| {
-                    this.switch_to_response_tab(
-                        crate::views::response_view::ResponseTab::Headers,
-                        cx,
-                    );
+                    this.switch_to_response_tab(ResponseTab::Headers, cx);
                 }),
             )
             // Focus URL bar
@@ -3325,9 +7314,11 @@ impl Render for MainView {
                 let history_active = self.sidebar_tab == SidebarTab::History;
                 let collections_active = self.sidebar_tab == SidebarTab::Collections;
                 let environments_active = self.sidebar_tab == SidebarTab::Environments;
+                let cookies_active = self.sidebar_tab == SidebarTab::Cookies;
                 let this_for_history = this.clone();
                 let this_for_collections = this.clone();
                 let this_for_environments = this.clone();
+                let this_for_cookies = this.clone();
                 el.child(
                     div()
                         .w(px(44.0))
@@ -3378,6 +7369,19 @@ impl Render for MainView {
                                         view.set_sidebar_tab(SidebarTab::Environments, cx);
                                     });
                                 }),
+                        )
+                        .child(
+                            Button::new("rail-cookies")
+                                .icon(Icon::new(IconName::Cookie).size(px(15.0)))
+                                .ghost()
+                                .xsmall()
+                                .selected(cookies_active)
+                                .tooltip("Cookies")
+                                .on_click(move |_, _, cx| {
+                                    this_for_cookies.update(cx, |view, cx| {
+                                        view.set_sidebar_tab(SidebarTab::Cookies, cx);
+                                    });
+                                }),
                         ),
                 )
             })
@@ -3402,9 +7406,21 @@ impl Render for MainView {
                 };
                 let history_filter = self.history_filter;
                 let history_group_by = self.history_group_by;
+                // The jar is only read while its tab is visible.
+                let (cookie_scope_label, cookies) = if sidebar_tab == SidebarTab::Cookies {
+                    let scope = self.active_cookie_scope(cx);
+                    let label = scope
+                        .and_then(|id| self.environments.read(cx).get(id))
+                        .map(|environment| SharedString::from(environment.name.clone()));
+                    (label, self.http_client.cookie_jar().cookies(scope))
+                } else {
+                    (None, Vec::new())
+                };
 
                 let this_for_tab = this.clone();
                 let this_for_load_history = this.clone();
+                let this_for_load_history_in_place = this.clone();
+                let this_for_copy_history_headers = this.clone();
                 let this_for_delete_history = this.clone();
                 let this_for_toggle_star = this.clone();
                 let this_for_clear_history = this.clone();
@@ -3415,12 +7431,19 @@ impl Render for MainView {
                 let this_for_rename_node = this.clone();
                 let this_for_new_collection = this.clone();
                 let this_for_import_collection = this.clone();
+                let this_for_export_collection = this.clone();
                 let this_for_new_folder = this.clone();
                 let this_for_move_node = this.clone();
+                let this_for_revert_node = this.clone();
+                let this_for_redo_revert_node = this.clone();
+                let this_for_examples = this.clone();
+                let this_for_run_on_open = this.clone();
                 let this_for_toggle_expand = this.clone();
                 let this_for_toggle_node_expand = this.clone();
                 let this_for_filter_change = this.clone();
                 let this_for_group_by_change = this.clone();
+                let this_for_delete_cookie = this.clone();
+                let this_for_clear_cookies = this.clone();
 
                 el.child(
                     div()
@@ -3440,6 +7463,17 @@ impl Render for MainView {
                             .active_tab(sidebar_tab)
                             .history_filter(history_filter)
                             .history_group_by(history_group_by)
+                            .cookies(cookie_scope_label, cookies)
+                            .on_delete_cookie(move |cookie, _window, cx| {
+                                this_for_delete_cookie.update(cx, |view, cx| {
+                                    view.delete_cookie(cookie, cx);
+                                });
+                            })
+                            .on_clear_cookies(move |_window, cx| {
+                                this_for_clear_cookies.update(cx, |view, cx| {
+                                    view.clear_cookies(cx);
+                                });
+                            })
                             .on_tab_change(move |tab, _window, cx| {
                                 this_for_tab.update(cx, |view, cx| {
                                     view.set_sidebar_tab(tab, cx);
@@ -3460,6 +7494,16 @@ impl Render for MainView {
                                     view.load_history_entry(entry_id, window, cx);
                                 });
                             })
+                            .on_load_history_request_in_place(move |entry_id, window, cx| {
+                                this_for_load_history_in_place.update(cx, |view, cx| {
+                                    view.load_history_entry_into_active(entry_id, window, cx);
+                                });
+                            })
+                            .on_copy_history_headers(move |entry_id, window, cx| {
+                                this_for_copy_history_headers.update(cx, |view, cx| {
+                                    view.show_copy_history_headers_dialog(entry_id, window, cx);
+                                });
+                            })
                             .on_delete_history_entry(move |entry_id, _window, cx| {
                                 this_for_delete_history.update(cx, |view, cx| {
                                     view.delete_history_entry(entry_id, cx);
@@ -3532,6 +7576,11 @@ impl Render for MainView {
                                     view.import_collection_from_file(window, cx);
                                 });
                             })
+                            .on_export_collection(move |collection_id, window, cx| {
+                                this_for_export_collection.update(cx, |view, cx| {
+                                    view.export_collection(collection_id, window, cx);
+                                });
+                            })
                             .on_new_folder(move |collection_id, folder_id, window, cx| {
                                 this_for_new_folder.update(cx, |view, cx| {
                                     view.show_new_folder_dialog(
@@ -3552,6 +7601,47 @@ impl Render for MainView {
                                     );
                                 });
                             })
+                            .on_revert_collection_node(move |collection_id, node_id, window, cx| {
+                                this_for_revert_node.update(cx, |view, cx| {
+                                    view.revert_collection_node(collection_id, node_id, window, cx);
+                                });
+                            })
+                            .on_redo_revert_collection_node(
+                                move |collection_id, node_id, window, cx| {
+                                    this_for_redo_revert_node.update(cx, |view, cx| {
+                                        view.redo_revert_collection_node(
+                                            collection_id,
+                                            node_id,
+                                            window,
+                                            cx,
+                                        );
+                                    });
+                                },
+                            )
+                            .on_show_request_examples(move |collection_id, node_id, window, cx| {
+                                this_for_examples.update(cx, |view, cx| {
+                                    view.show_request_examples_dialog(
+                                        collection_id,
+                                        node_id,
+                                        window,
+                                        cx,
+                                    );
+                                });
+                            })
+                            .on_set_collection_node_run_on_open(
+                                move |collection_id, node_id, run_on_open, _window, cx| {
+                                    this_for_run_on_open.update(cx, |view, cx| {
+                                        view.collections.update(cx, |collections, cx| {
+                                            collections.set_run_on_open(
+                                                collection_id,
+                                                node_id,
+                                                run_on_open,
+                                                cx,
+                                            );
+                                        });
+                                    });
+                                },
+                            )
                             .on_toggle_collection_expand(move |collection_id, _window, cx| {
                                 this_for_toggle_expand.update(cx, |view, cx| {
                                     view.toggle_collection_expand(collection_id, cx);
@@ -3635,6 +7725,16 @@ impl Render for MainView {
                                 TabContent::Environment { view, .. } => {
                                     view.clone().into_any_element()
                                 }
+                                TabContent::Request {
+                                    protocol: ProtocolType::WebSocket,
+                                    websocket: Some(websocket),
+                                    ..
+                                } => self.render_split_panels(
+                                    websocket.view.clone().into_any_element(),
+                                    websocket.log_view.clone().into_any_element(),
+                                    this_for_send,
+                                    effective_layout,
+                                ),
                                 TabContent::Request { .. } => {
                                     if let Some((
                                         url_input,
@@ -3643,19 +7743,23 @@ impl Render for MainView {
                                         is_loading,
                                         request_view,
                                         response_view,
+                                        request_name,
                                     )) = request_tab_state
                                     {
+                                        let body_warning = request_view.read(cx).body_warning(cx);
                                         self.render_request_response_split(
+                                            request_name,
                                             url_input,
                                             method_dropdown,
                                             request_entity,
                                             is_loading,
+                                            self.active_tab_accepts_invalid_certs(cx),
+                                            body_warning,
                                             this_for_send,
                                             request_view,
                                             response_view,
                                             effective_layout,
                                         )
-                                        .into_any_element()
                                     } else {
                                         div().into_any_element()
                                     }
@@ -3676,40 +7780,15 @@ impl Render for MainView {
 
 #[cfg(test)]
 mod tests {
-    use super::{MainView, RequestGeneration, TabId};
-
-    #[test]
-    fn compose_request_url_appends_query_to_plain_url() {
-        assert_eq!(
-            MainView::compose_request_url(
-                "https://api.example.com/users".to_string(),
-                "?page=1".to_string()
-            ),
-            "https://api.example.com/users?page=1"
-        );
-    }
-
-    #[test]
-    fn compose_request_url_merges_with_existing_query() {
-        assert_eq!(
-            MainView::compose_request_url(
-                "https://api.example.com/users?sort=name".to_string(),
-                "?page=1".to_string()
-            ),
-            "https://api.example.com/users?sort=name&page=1"
-        );
-    }
-
-    #[test]
-    fn compose_request_url_preserves_base_when_query_is_empty() {
-        assert_eq!(
-            MainView::compose_request_url(
-                "https://api.example.com/users?sort=name".to_string(),
-                String::new()
-            ),
-            "https://api.example.com/users?sort=name"
-        );
-    }
+    use super::{
+        CLOSED_TAB_LIMIT, ClosedTab, MainView, RequestGeneration, SubTabs, TabContent, TabId,
+        push_closed_tab,
+    };
+    use crate::components::{BodyType, MethodDropdownState, ProtocolType};
+    use crate::entities::{RequestData, RequestEntity, ResponseEntity};
+    use crate::views::request_view::{RequestTab, RequestView};
+    use crate::views::response_view::{ResponseTab, ResponseView};
+    use gpui::{App, AppContext as _, TestAppContext};
 
     #[test]
     fn tab_ids_are_stable_values() {
@@ -3726,6 +7805,95 @@ mod tests {
         assert_eq!(current, second);
     }
 
+    #[test]
+    fn closed_tabs_reopen_newest_first_and_forget_the_oldest() {
+        let mut closed_tabs = Vec::new();
+        for index in 0..CLOSED_TAB_LIMIT + 2 {
+            push_closed_tab(
+                &mut closed_tabs,
+                ClosedTab {
+                    name: format!("Tab {index}"),
+                    is_custom_name: false,
+                    request: RequestData::default(),
+                    sub_tabs: SubTabs::default(),
+                    collection_id: None,
+                    collection_node_id: None,
+                    history_entry_id: None,
+                },
+            );
+        }
+        assert_eq!(closed_tabs.len(), CLOSED_TAB_LIMIT);
+        assert_eq!(closed_tabs[0].name, "Tab 2");
+        assert_eq!(
+            closed_tabs.pop().map(|tab| tab.name),
+            Some(format!("Tab {}", CLOSED_TAB_LIMIT + 1))
+        );
+    }
+
+    /// A REST tab's content with fresh views, as a new or rebuilt tab gets.
+    fn rest_tab_content(cx: &mut App) -> TabContent {
+        let request = cx.new(|_| RequestEntity::new());
+        let response = cx.new(|_| ResponseEntity::new());
+        TabContent::Request {
+            request_view: cx.new(|cx| RequestView::new(request.clone(), BodyType::None, cx)),
+            response_view: cx.new(|cx| ResponseView::new(response.clone(), cx)),
+            request,
+            response,
+            url_input: None,
+            method_dropdown: cx.new(|_| MethodDropdownState::new(HttpMethod::Get)),
+            in_flight_request: None,
+            sent_request: None,
+            request_generation: RequestGeneration::default(),
+            protocol: ProtocolType::Rest,
+            websocket: None,
+        }
+    }
+
+    fn pick_sub_tabs(content: &TabContent, sub_tabs: SubTabs, cx: &mut App) {
+        let TabContent::Request {
+            request_view,
+            response_view,
+            ..
+        } = content
+        else {
+            unreachable!("REST tabs have request content");
+        };
+        request_view.update(cx, |view, cx| view.set_tab(sub_tabs.request, cx));
+        response_view.update(cx, |view, cx| view.set_tab(sub_tabs.response, cx));
+    }
+
+    #[gpui::test]
+    fn closed_tabs_come_back_on_the_panel_tabs_they_were_showing(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let sub_tabs = SubTabs {
+                request: RequestTab::Auth,
+                response: ResponseTab::Raw,
+            };
+            let closing = rest_tab_content(cx);
+            pick_sub_tabs(&closing, sub_tabs, cx);
+
+            let mut closed_tabs = Vec::new();
+            push_closed_tab(
+                &mut closed_tabs,
+                ClosedTab {
+                    name: "Users".to_string(),
+                    is_custom_name: false,
+                    request: RequestData::default(),
+                    sub_tabs: SubTabs::of(&closing, cx).unwrap_or_default(),
+                    collection_id: None,
+                    collection_node_id: None,
+                    history_entry_id: None,
+                },
+            );
+
+            let reopened = rest_tab_content(cx);
+            assert_eq!(SubTabs::of(&reopened, cx), Some(SubTabs::default()));
+            let closed = closed_tabs.pop().expect("closed tab kept");
+            closed.sub_tabs.restore(&reopened, cx);
+            assert_eq!(SubTabs::of(&reopened, cx), Some(sub_tabs));
+        });
+    }
+
     use crate::entities::HttpMethod;
 
     #[test]
@@ -3803,13 +7971,26 @@ mod tests {
 impl MainView {
     fn render_request_panel(
         &self,
+        request_name: String,
         url_input: Option<Entity<InputState>>,
         method_dropdown: Entity<MethodDropdownState>,
         request: Entity<RequestEntity>,
         is_loading: bool,
+        accepts_invalid_certs: bool,
+        body_warning: Option<String>,
         this: Entity<MainView>,
         request_view: Entity<RequestView>,
     ) -> impl IntoElement {
+        let has_saved_request = self
+            .active_tab()
+            .is_some_and(|tab| tab.collection_node_id.is_some());
+        let is_custom_name = self.active_tab().is_some_and(|tab| tab.is_custom_name);
+        let name_input = self
+            .request_name_input
+            .as_ref()
+            .filter(|(tab_id, _)| self.active_tab().is_some_and(|tab| tab.id == *tab_id))
+            .map(|(_, input)| input.clone());
+
         div()
             .flex()
             .flex_col()
@@ -3818,14 +7999,52 @@ impl MainView {
             // URL Bar
             .child(
                 div()
+                    .flex()
+                    .flex_col()
+                    .gap(px(6.0))
                     .px(px(16.0))
                     .py(px(12.0))
+                    // Request name, edited in place
+                    .child(match name_input {
+                        Some(input) => div()
+                            .w(px(320.0))
+                            .child(Input::new(&input).xsmall())
+                            .into_any_element(),
+                        None => {
+                            let this = this.clone();
+                            div()
+                                .flex()
+                                .max_w(px(480.0))
+                                .child(
+                                    Button::new("request-name")
+                                        .label(request_name)
+                                        .icon(Icon::new(IconName::FilePen).size(px(12.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip(if is_custom_name {
+                                            "Rename request"
+                                        } else {
+                                            "Name this request"
+                                        })
+                                        .on_click(move |_, window, cx| {
+                                            this.update(cx, |view, cx| {
+                                                view.start_request_name_edit(window, cx);
+                                            });
+                                        }),
+                                )
+                                .into_any_element()
+                        }
+                    })
                     .when_some(url_input, |el, input| {
                         let this_for_send = this.clone();
                         let this_for_cancel = this.clone();
                         el.child(
                             UrlBar::new(input)
                                 .method_dropdown(method_dropdown, request)
+                                .default_scheme(self.ui_preferences.default_scheme)
+                                .insecure(accepts_invalid_certs)
+                                .body_warning(body_warning)
+                                .dry_run(self.ui_preferences.dry_run)
                                 .loading(is_loading)
                                 .on_send(move |_, _, cx| {
                                     this_for_send.update(cx, |view, cx| {
@@ -3837,6 +8056,14 @@ impl MainView {
                                         view.cancel_request(cx);
                                     });
                                 })
+                                .on_preview({
+                                    let this = this.clone();
+                                    move |_, window, cx| {
+                                        this.update(cx, |view, cx| {
+                                            view.preview_active_request(window, cx);
+                                        });
+                                    }
+                                })
                                 .on_save_to_collection({
                                     let this = this.clone();
                                     move |_, window, cx| {
@@ -3844,6 +8071,14 @@ impl MainView {
                                             view.show_save_to_collection_dialog(window, cx);
                                         });
                                     }
+                                })
+                                .when(has_saved_request, |bar| {
+                                    let this = this.clone();
+                                    bar.on_update_saved_request(move |_, window, cx| {
+                                        this.update(cx, |view, cx| {
+                                            view.show_update_saved_request_dialog(window, cx);
+                                        });
+                                    })
                                 }),
                         )
                     }),
@@ -3854,15 +8089,46 @@ impl MainView {
 
     fn render_request_response_split(
         &self,
+        request_name: String,
         url_input: Option<Entity<InputState>>,
         method_dropdown: Entity<MethodDropdownState>,
         request: Entity<RequestEntity>,
         is_loading: bool,
+        accepts_invalid_certs: bool,
+        body_warning: Option<String>,
         this: Entity<MainView>,
         request_view: Entity<RequestView>,
         response_view: Entity<ResponseView>,
         layout: RequestResponseLayout,
-    ) -> impl IntoElement {
+    ) -> AnyElement {
+        let request_content = self
+            .render_request_panel(
+                request_name,
+                url_input,
+                method_dropdown,
+                request,
+                is_loading,
+                accepts_invalid_certs,
+                body_warning,
+                this.clone(),
+                request_view,
+            )
+            .into_any_element();
+        self.render_split_panels(
+            request_content,
+            response_view.into_any_element(),
+            this,
+            layout,
+        )
+    }
+
+    fn render_split_panels(
+        &self,
+        request_content: AnyElement,
+        response_content: AnyElement,
+        this: Entity<MainView>,
+        layout: RequestResponseLayout,
+    ) -> AnyElement {
         let initial_sizes = match layout {
             RequestResponseLayout::Stacked => self.ui_preferences.stacked_split,
             RequestResponseLayout::SideBySide => self.ui_preferences.side_by_side_split,
@@ -3876,16 +8142,14 @@ impl MainView {
                     RequestResponseLayout::SideBySide => px(900.0),
                 },
             )
-            .child(div().flex().flex_col().size_full().overflow_hidden().child(
-                self.render_request_panel(
-                    url_input,
-                    method_dropdown,
-                    request,
-                    is_loading,
-                    this,
-                    request_view,
-                ),
-            ));
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .size_full()
+                    .overflow_hidden()
+                    .child(request_content),
+            );
 
         let response_panel = resizable_panel()
             .size(px(initial_sizes[1]))
@@ -3896,7 +8160,7 @@ impl MainView {
                     .flex_col()
                     .size_full()
                     .overflow_hidden()
-                    .child(response_view),
+                    .child(response_content),
             );
 
         let on_resize =
@@ -3947,6 +8211,7 @@ impl MainView {
         let this_for_commands = this.clone();
         let this_for_layout = this.clone();
         let this_for_manage_environments = this.clone();
+        let this_for_protocol = this.clone();
         let this_for_workspace_menu = this;
         let (active_workspace_id, active_workspace_name, workspace_options) = {
             let workspaces = self.workspaces.read(cx);
@@ -4100,7 +8365,14 @@ impl MainView {
                                 menu
                             }),
                     )
-                    .child(ProtocolSelector::new(ProtocolType::Rest)),
+                    .child(ProtocolSelector::new(self.active_protocol()).on_select({
+                        let this = this_for_protocol;
+                        move |protocol, window, cx| {
+                            this.update(cx, |view, cx| {
+                                view.set_active_protocol(protocol, window, cx);
+                            });
+                        }
+                    })),
             )
             .child(
                 div()
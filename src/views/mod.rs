@@ -3,6 +3,7 @@ mod environment_view;
 mod main_view;
 mod request_view;
 mod response_view;
+mod websocket_view;
 
 pub use command_palette::*;
 pub use main_view::*;
@@ -1,22 +1,57 @@
 use gpui::prelude::*;
 use gpui::{
-    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
-    PathPromptOptions, Render, Styled, Window, div, px,
+    AnyElement, App, Context, Div, Entity, EventEmitter, ExternalPaths, FocusHandle, Focusable,
+    Global, IntoElement, PathPromptOptions, Render, SharedString, Stateful, Styled, Window, div,
+    px,
 };
 use gpui_component::WindowExt;
-use gpui_component::input::{Input, InputState};
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::dialog::DialogFooter;
+use gpui_component::input::{Input, InputEvent, InputState};
 use gpui_component::notification::NotificationType;
+use gpui_component::tooltip::Tooltip;
 
 use crate::components::{
-    AuthEditor, BodyType, BodyTypeSelector, BodyTypeSelectorEvent, FormDataEditor, HeaderEditor,
-    MultipartFormDataEditor, ParamsEditor,
+    AuthConfig, AuthEditor, AuthType, BodyType, BodyTypeSelector, BodyTypeSelectorEvent,
+    FormDataEditor, HeaderEditor, HeaderMergeMode, MultipartFormDataEditor, ParamsEditor,
+    ParamsEditorEvent, file_size, merge_headers, parse_query, replace_query,
+};
+use crate::entities::{
+    CollectionsEntity, Header, HttpMethod, MultipartField, OAuthToken, RequestBody, RequestEntity,
+    RequestEvent, ResponseData,
 };
-use crate::entities::{Header, MultipartField, RequestBody, RequestEntity, RequestEvent};
 use crate::icons::IconName;
 use gpui_component::{ActiveTheme, Icon};
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use uuid::Uuid;
 
 use crate::completion::{CompletionContext, CompletionEngine, CompletionInput};
+use crate::utils::{
+    AssertionResult, JsonFormatSettings, close_dialog, format_size, open_dialog, pretty_json,
+    pretty_xml, run_assertions, sort_json_keys,
+};
+
+/// A single edit growing the body by at least this much is treated as a paste, not typing.
+const PASTE_MIN_INSERT_BYTES: usize = 16;
+
+/// Largest dropped text file loaded into the body editor; bigger ones are sent from disk.
+const MAX_DROPPED_TEXT_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Bytes read from a dropped file to tell text from binary before reading the rest.
+const DROP_SNIFF_BYTES: u64 = 8 * 1024;
+
+/// App-wide body editor behaviour, mirrored from `UiPreferences` by the main view.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BodyEditorSettings {
+    pub format_on_paste: bool,
+}
+
+impl Global for BodyEditorSettings {}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub enum RequestViewEvent {
@@ -31,21 +66,41 @@ pub enum RequestTab {
     Headers,
     Params,
     Auth,
+    PreRequest,
+    PostResponse,
+    Tests,
 }
 
 pub struct RequestView {
     pub request: Entity<RequestEntity>,
     active_tab: RequestTab,
     body_editor: Option<Entity<InputState>>,
+    /// Body length after the last edit, used to spot pastes.
+    body_text_len: usize,
     body_type: BodyType,
     /// Last body type applied to the editor (for syntax highlighting)
     last_applied_body_type: BodyType,
     body_type_selector: Option<Entity<BodyTypeSelector>>,
     form_data_editor: Option<Entity<FormDataEditor>>,
     multipart_form_data_editor: Option<Entity<MultipartFormDataEditor>>,
+    graphql_query_editor: Option<Entity<InputState>>,
+    graphql_variables_editor: Option<Entity<InputState>>,
+    /// The binary body's file and its size, or `None` for the size when the file is gone.
+    binary_file: Option<(PathBuf, Option<u64>)>,
     header_editor: Option<Entity<HeaderEditor>>,
     params_editor: Option<Entity<ParamsEditor>>,
+    /// The tab's URL bar, whose query the params editor mirrors.
+    url_input: Option<Entity<InputState>>,
     auth_editor: Option<Entity<AuthEditor>>,
+    pre_request_editor: Option<Entity<InputState>>,
+    post_response_editor: Option<Entity<InputState>>,
+    tests_editor: Option<Entity<InputState>>,
+    /// Outcome of the assertions for the last response; `None` until one arrives.
+    test_results: Option<Vec<AssertionResult>>,
+    /// Bumped on every send so a slow check of an older response is dropped.
+    tests_generation: u64,
+    /// Collection the tab belongs to, whose default auth inheriting requests use.
+    owning_collection: Option<(Entity<CollectionsEntity>, Uuid)>,
     focus_handle: FocusHandle,
     wrap_lines: bool,
     initial_body_content: Option<String>,
@@ -60,7 +115,11 @@ impl RequestView {
         initial_body_type: BodyType,
         cx: &mut Context<Self>,
     ) -> Self {
-        cx.subscribe(&request, |_this, _request, _event: &RequestEvent, cx| {
+        cx.subscribe(&request, |this, _request, event: &RequestEvent, cx| {
+            if matches!(event, RequestEvent::Sending) {
+                this.tests_generation += 1;
+                this.test_results = None;
+            }
             cx.notify();
         })
         .detach();
@@ -69,14 +128,25 @@ impl RequestView {
             request,
             active_tab: RequestTab::Body,
             body_editor: None,
+            body_text_len: 0,
             body_type: initial_body_type,
             last_applied_body_type: initial_body_type,
             body_type_selector: None,
             form_data_editor: None,
             multipart_form_data_editor: None,
+            graphql_query_editor: None,
+            graphql_variables_editor: None,
+            binary_file: None,
             header_editor: None,
             params_editor: None,
+            url_input: None,
             auth_editor: None,
+            pre_request_editor: None,
+            post_response_editor: None,
+            tests_editor: None,
+            test_results: None,
+            tests_generation: 0,
+            owning_collection: None,
             focus_handle: cx.focus_handle(),
             wrap_lines: true,
             initial_body_content: None,
@@ -145,6 +215,18 @@ impl RequestView {
                 }
             });
 
+            cx.subscribe_in(
+                &body_editor,
+                window,
+                |this, editor, event: &InputEvent, window, cx| {
+                    if matches!(event, InputEvent::Change) {
+                        this.format_pasted_body(editor, window, cx);
+                    }
+                },
+            )
+            .detach();
+
+            self.body_text_len = initial_content.len();
             self.body_editor = Some(body_editor);
             self.last_applied_body_type = self.body_type;
         } else if self.body_type != self.last_applied_body_type {
@@ -162,6 +244,71 @@ impl RequestView {
         }
     }
 
+    /// Initialize the GraphQL query and variables editors, seeded from the stored body
+    fn ensure_graphql_editors(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.graphql_query_editor.is_some() && self.graphql_variables_editor.is_some() {
+            return;
+        }
+
+        let (query, variables) = match self.request.read(cx).body() {
+            RequestBody::GraphQl { query, variables } => (query.clone(), variables.clone()),
+            _ => (String::new(), "{}".to_string()),
+        };
+        let wrap_lines = self.wrap_lines;
+        let completion_engine = self.completion_engine.clone();
+        self.graphql_query_editor = Some(cx.new(|cx| {
+            let input = InputState::new(window, cx)
+                .code_editor("text")
+                .line_number(true)
+                .searchable(true)
+                .soft_wrap(wrap_lines)
+                .placeholder("query { ... }")
+                .default_value(&query);
+            if let Some(engine) = completion_engine.as_ref() {
+                engine.configure_input(input, CompletionContext::Body)
+            } else {
+                input
+            }
+        }));
+        let completion_engine = self.completion_engine.clone();
+        self.graphql_variables_editor = Some(cx.new(|cx| {
+            let input = InputState::new(window, cx)
+                .code_editor("json")
+                .folding(true)
+                .line_number(true)
+                .soft_wrap(wrap_lines)
+                .default_value(&variables);
+            if let Some(engine) = completion_engine.as_ref() {
+                engine.configure_input(input, CompletionContext::Body)
+            } else {
+                input
+            }
+        }));
+    }
+
+    fn format_pasted_body(
+        &mut self,
+        editor: &Entity<InputState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let text = editor.read(cx).text().to_string();
+        let previous_len = std::mem::replace(&mut self.body_text_len, text.len());
+        let enabled = cx
+            .try_global::<BodyEditorSettings>()
+            .is_some_and(|settings| settings.format_on_paste);
+        if !enabled {
+            return;
+        }
+        let Some(formatted) = pasted_body_format(self.body_type, previous_len, &text) else {
+            return;
+        };
+        self.body_text_len = formatted.len();
+        editor.update(cx, |state, cx| {
+            state.set_value(formatted, window, cx);
+        });
+    }
+
     fn ensure_body_type_selector(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.body_type_selector.is_none() {
             let initial_body_type = self.body_type;
@@ -194,8 +341,15 @@ impl RequestView {
                     }
                     BodyTypeSelectorEvent::WrapToggled(wrap) => {
                         this.wrap_lines = *wrap;
-                        if let Some(ref body_editor) = this.body_editor {
-                            body_editor.update(cx, |state, cx| {
+                        for editor in [
+                            &this.body_editor,
+                            &this.graphql_query_editor,
+                            &this.graphql_variables_editor,
+                        ]
+                        .into_iter()
+                        .flatten()
+                        {
+                            editor.update(cx, |state, cx| {
                                 state.set_soft_wrap(*wrap, window, cx);
                             });
                         }
@@ -212,12 +366,95 @@ impl RequestView {
         }
     }
 
-    fn ensure_params_editor(&mut self, cx: &mut Context<Self>) {
+    fn ensure_params_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.params_editor.is_none() {
             let completion_engine = self.completion_engine.clone();
-            self.params_editor =
-                Some(cx.new(|cx| ParamsEditor::new(completion_engine.clone(), cx)));
+            let editor = cx.new(|cx| ParamsEditor::new(completion_engine.clone(), cx));
+            cx.subscribe_in(
+                &editor,
+                window,
+                |this, _editor, event: &ParamsEditorEvent, window, cx| match event {
+                    ParamsEditorEvent::Changed => {
+                        this.sync_url_from_params(window, cx);
+                        this.sync_disabled_params_to_request(cx);
+                    }
+                },
+            )
+            .detach();
+            self.params_editor = Some(editor.clone());
+            if let Some(url_input) = self.url_input.as_ref() {
+                let url = url_input.read(cx).text().to_string();
+                self.sync_params_from_url(&url, window, cx);
+            }
+            let disabled_params = self.request.read(cx).disabled_params().to_vec();
+            editor.update(cx, |editor, cx| {
+                editor.add_disabled_params(&disabled_params, window, cx);
+            });
+        }
+    }
+
+    /// Mirror the query of `url_input` in the params editor, and params edits back into it.
+    ///
+    /// Each direction only writes when the other side's params actually differ, so an
+    /// update never bounces back and the text being typed is left as it is.
+    pub fn attach_url_input(
+        &mut self,
+        url_input: Entity<InputState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.subscribe_in(
+            &url_input,
+            window,
+            |this, input, event: &InputEvent, window, cx| {
+                if matches!(event, InputEvent::Change) {
+                    let url = input.read(cx).text().to_string();
+                    this.sync_params_from_url(&url, window, cx);
+                }
+            },
+        )
+        .detach();
+        self.url_input = Some(url_input);
+    }
+
+    fn sync_params_from_url(&mut self, url: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editor) = self.params_editor.clone() else {
+            return;
+        };
+        let pairs = parse_query(url);
+        if editor.read(cx).active_pairs(cx) != pairs {
+            editor.update(cx, |editor, cx| {
+                editor.set_params_from_query(&pairs, window, cx);
+            });
+        }
+    }
+
+    fn sync_disabled_params_to_request(&mut self, cx: &mut Context<Self>) {
+        if let Some(editor) = self.params_editor.as_ref() {
+            let disabled_params = editor.read(cx).disabled_params(cx);
+            self.request.update(cx, |request, cx| {
+                request.set_disabled_params(disabled_params, cx);
+            });
+        }
+    }
+
+    fn sync_url_from_params(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(editor), Some(url_input)) = (self.params_editor.clone(), self.url_input.clone())
+        else {
+            return;
+        };
+        let editor = editor.read(cx);
+        let url = url_input.read(cx).text().to_string();
+        if parse_query(&url) == editor.active_pairs(cx) {
+            return;
         }
+        let updated = replace_query(&url, &editor.build_query_string(cx));
+        url_input.update(cx, |state, cx| {
+            state.set_value(updated.clone(), window, cx);
+        });
+        self.request.update(cx, |request, cx| {
+            request.set_url(updated, cx);
+        });
     }
 
     fn ensure_form_data_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -248,6 +485,14 @@ impl RequestView {
         }
     }
 
+    /// The headers editor, shared with the WebSocket handshake panel.
+    pub fn header_editor(&mut self, cx: &mut Context<Self>) -> Entity<HeaderEditor> {
+        self.ensure_header_editor(cx);
+        self.header_editor
+            .clone()
+            .expect("header editor was just created")
+    }
+
     fn ensure_header_editor(&mut self, cx: &mut Context<Self>) {
         if self.header_editor.is_none() {
             let request = self.request.clone();
@@ -260,9 +505,194 @@ impl RequestView {
     fn ensure_auth_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.auth_editor.is_none() {
             let completion_engine = self.completion_engine.clone();
-            self.auth_editor =
-                Some(cx.new(|cx| AuthEditor::new(window, completion_engine.clone(), cx)));
+            let owning_collection = self.owning_collection.clone();
+            let inherit = self.request.read(cx).inherit_auth();
+            self.auth_editor = Some(cx.new(|cx| {
+                let mut editor = AuthEditor::new(window, completion_engine.clone(), cx);
+                editor.set_owning_collection(owning_collection, cx);
+                if inherit {
+                    editor.set_auth_type(AuthType::Inherit, window, cx);
+                }
+                editor
+            }));
+        }
+    }
+
+    fn ensure_pre_request_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.pre_request_editor.is_none() {
+            let script = self.request.read(cx).pre_request_script().to_string();
+            self.pre_request_editor = Some(self.new_script_editor(
+                "header X-Request-Id = uuid()",
+                &script,
+                RequestEntity::set_pre_request_script,
+                window,
+                cx,
+            ));
+        }
+    }
+
+    fn ensure_post_response_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.post_response_editor.is_none() {
+            let rules = self.request.read(cx).extractions().to_string();
+            self.post_response_editor = Some(self.new_script_editor(
+                "set authToken = $.token",
+                &rules,
+                RequestEntity::set_extractions,
+                window,
+                cx,
+            ));
+        }
+    }
+
+    fn ensure_tests_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.tests_editor.is_none() {
+            let assertions = self.request.read(cx).assertions().to_string();
+            self.tests_editor = Some(self.new_script_editor(
+                "status == 200",
+                &assertions,
+                RequestEntity::set_assertions,
+                window,
+                cx,
+            ));
+        }
+    }
+
+    /// Line-numbered editor for one of the request's scripts, starting from `text` and
+    /// handing every edit to `save`.
+    fn new_script_editor(
+        &self,
+        placeholder: &'static str,
+        text: &str,
+        save: fn(&mut RequestEntity, String, &mut Context<RequestEntity>),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<InputState> {
+        let editor = cx.new(|cx| {
+            InputState::new(window, cx)
+                .code_editor("text")
+                .line_number(true)
+                .soft_wrap(true)
+                .placeholder(placeholder)
+                .default_value(text)
+        });
+        cx.subscribe(&editor, move |this, editor, event: &InputEvent, cx| {
+            if matches!(event, InputEvent::Change) {
+                let text = editor.read(cx).text().to_string();
+                this.request
+                    .update(cx, |request, cx| save(request, text, cx));
+            }
+        })
+        .detach();
+        editor
+    }
+
+    /// Check `response` against the request's assertions on a worker thread.
+    pub fn run_tests(&mut self, response: &ResponseData, cx: &mut Context<Self>) {
+        self.tests_generation += 1;
+        let assertions = self.request.read(cx).assertions().to_string();
+        if assertions.trim().is_empty() {
+            self.test_results = None;
+            cx.notify();
+            return;
+        }
+
+        let generation = self.tests_generation;
+        let response = response.clone();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+            let _ = tx.send(run_assertions(&assertions, &response));
+        });
+        cx.spawn(async move |this, cx| {
+            let Ok(results) = rx.await else {
+                return;
+            };
+            let _ = this.update(cx, |view, cx| {
+                if view.tests_generation == generation {
+                    view.test_results = Some(results);
+                    cx.notify();
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Point inheriting auth at the collection the tab now belongs to.
+    pub fn set_owning_collection(
+        &mut self,
+        collections: &Entity<CollectionsEntity>,
+        collection_id: Option<Uuid>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.owning_collection.as_ref().map(|(_, id)| *id) == collection_id {
+            return;
+        }
+        self.owning_collection = collection_id.map(|id| (collections.clone(), id));
+        if let Some(editor) = self.auth_editor.clone() {
+            let owning_collection = self.owning_collection.clone();
+            editor.update(cx, |editor, cx| {
+                editor.set_owning_collection(owning_collection, cx);
+            });
+        }
+    }
+
+    /// Auth the request sends, with `Inherit` resolved against the owning collection.
+    pub fn resolved_auth(&self, cx: &App) -> AuthConfig {
+        let config = match &self.auth_editor {
+            Some(editor) => editor.read(cx).get_config(cx),
+            // The editor is created lazily; a loaded request may inherit before it exists.
+            None if self.request.read(cx).inherit_auth() => AuthConfig {
+                auth_type: AuthType::Inherit,
+                ..AuthConfig::default()
+            },
+            None => return AuthConfig::default(),
+        };
+        let collection_auth = self
+            .owning_collection
+            .as_ref()
+            .and_then(|(collections, id)| collections.read(cx).get_collection(*id))
+            .and_then(|collection| collection.auth.as_ref());
+        config.resolve(collection_auth)
+    }
+
+    /// Whether the request's own auth editor is the one holding an OAuth token.
+    fn owns_oauth_config(&self, cx: &App) -> bool {
+        self.auth_editor
+            .as_ref()
+            .is_some_and(|editor| editor.read(cx).auth_type() == AuthType::OAuth2ClientCredentials)
+    }
+
+    pub fn set_oauth_fetching(&self, cx: &mut Context<Self>) {
+        if self.owns_oauth_config(cx)
+            && let Some(editor) = self.auth_editor.clone()
+        {
+            editor.update(cx, |editor, cx| editor.set_oauth_fetching(cx));
+        }
+    }
+
+    /// Cache a fetched token where the resolved auth came from: the request's own editor, or
+    /// the default of the collection it inherits from.
+    pub fn store_oauth_token(&self, result: Result<OAuthToken, String>, cx: &mut Context<Self>) {
+        if self.owns_oauth_config(cx) {
+            if let Some(editor) = self.auth_editor.clone() {
+                editor.update(cx, |editor, cx| editor.set_oauth_token(result, cx));
+            }
+            return;
         }
+        let (Ok(token), Some((collections, collection_id))) =
+            (result, self.owning_collection.clone())
+        else {
+            return;
+        };
+        collections.update(cx, |collections, cx| {
+            let Some(mut auth) = collections
+                .get_collection(collection_id)
+                .and_then(|collection| collection.auth.clone())
+            else {
+                return;
+            };
+            auth.oauth_token = Some(token);
+            collections.set_collection_auth(collection_id, Some(auth), cx);
+        });
     }
 
     fn upsert_request_header(&self, key: &str, value: &str, cx: &mut Context<Self>) {
@@ -286,6 +716,10 @@ impl RequestView {
         if self.body_type == BodyType::FormData {
             return;
         }
+        if self.body_type == BodyType::Binary {
+            self.remove_default_content_type(cx);
+            return;
+        }
 
         if let Some(content_type) = self.body_type.content_type() {
             if let Some(ref header_editor) = self.header_editor {
@@ -298,6 +732,84 @@ impl RequestView {
         }
     }
 
+    /// Drop a Content-Type another body type set, so the binary file's own type is sent.
+    fn remove_default_content_type(&mut self, cx: &mut Context<Self>) {
+        let is_default = |header: &Header| {
+            header.key.eq_ignore_ascii_case("Content-Type")
+                && !header.value.trim().is_empty()
+                && BodyType::is_default_content_type(&header.value)
+        };
+        if let Some(header_editor) = self.header_editor.clone() {
+            let index = header_editor
+                .read(cx)
+                .get_headers(cx)
+                .iter()
+                .position(is_default);
+            if let Some(index) = index {
+                header_editor.update(cx, |editor, cx| editor.remove_header(index, cx));
+            }
+        } else {
+            self.request.update(cx, |request, cx| {
+                let mut headers = request.headers().to_vec();
+                let count = headers.len();
+                headers.retain(|header| !is_default(header));
+                if headers.len() != count {
+                    request.set_headers(headers, cx);
+                }
+            });
+        }
+    }
+
+    /// Look up the size of the binary body's file when the file changed since last time.
+    fn ensure_binary_file(&mut self, cx: &mut Context<Self>) {
+        let path = match self.request.read(cx).body() {
+            RequestBody::Binary(path) if !path.as_os_str().is_empty() => path.clone(),
+            _ => {
+                self.binary_file = None;
+                return;
+            }
+        };
+        if self
+            .binary_file
+            .as_ref()
+            .is_none_or(|(current, _)| *current != path)
+        {
+            let size = file_size(&path);
+            self.binary_file = Some((path, size));
+        }
+    }
+
+    /// Pick the file sent as the binary body.
+    pub fn pick_binary_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let this = cx.entity().clone();
+        let paths_receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Select file to send".into()),
+        });
+
+        cx.spawn_in(window, async move |_weak_this, cx| {
+            let Ok(Ok(Some(paths))) = paths_receiver.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let _ = cx.update(|_window, app| {
+                this.update(app, |view, cx| {
+                    // Re-read even when the same file is picked again, as it may have changed.
+                    view.binary_file = None;
+                    view.request.update(cx, |request, cx| {
+                        request.set_body(RequestBody::Binary(path), cx);
+                    });
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
     fn ensure_body_tab_state(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let body_type_changed = self.body_type != self.last_applied_body_type;
 
@@ -314,6 +826,14 @@ impl RequestView {
             BodyType::Json | BodyType::Text | BodyType::Xml | BodyType::Html => {
                 self.ensure_body_editor(window, cx);
             }
+            BodyType::GraphQl => {
+                self.last_applied_body_type = self.body_type;
+                self.ensure_graphql_editors(window, cx);
+            }
+            BodyType::Binary => {
+                self.last_applied_body_type = self.body_type;
+                self.ensure_binary_file(cx);
+            }
         }
 
         match self.body_type {
@@ -327,11 +847,18 @@ impl RequestView {
         match self.active_tab {
             RequestTab::Body => self.ensure_body_tab_state(window, cx),
             RequestTab::Headers => self.ensure_header_editor(cx),
-            RequestTab::Params => self.ensure_params_editor(cx),
+            RequestTab::PreRequest => self.ensure_pre_request_editor(window, cx),
+            RequestTab::PostResponse => self.ensure_post_response_editor(window, cx),
+            RequestTab::Tests => self.ensure_tests_editor(window, cx),
+            RequestTab::Params => self.ensure_params_editor(window, cx),
             RequestTab::Auth => self.ensure_auth_editor(window, cx),
         }
     }
 
+    pub fn active_tab(&self) -> RequestTab {
+        self.active_tab
+    }
+
     pub fn set_tab(&mut self, tab: RequestTab, cx: &mut Context<Self>) {
         self.active_tab = tab;
         cx.notify();
@@ -344,6 +871,26 @@ impl RequestView {
             .map(|editor| editor.read(cx).text().to_string())
     }
 
+    /// Warning shown before sending when the method is not expected to carry the body.
+    ///
+    /// Called on every render, so a text body is checked in its editor rather than copied
+    /// out of it.
+    pub fn body_warning(&self, cx: &App) -> Option<String> {
+        let method = self.request.read(cx).method();
+        // Skip looking at the body for the methods that never warn about it.
+        if self.body_type == BodyType::None || !matches!(method, HttpMethod::Get | HttpMethod::Head)
+        {
+            return None;
+        }
+        let has_body = match (self.body_type, &self.body_editor) {
+            (BodyType::Json | BodyType::Text | BodyType::Html | BodyType::Xml, Some(editor)) => {
+                !editor.read(cx).text().is_empty()
+            }
+            _ => !self.get_request_body(cx).is_empty(),
+        };
+        method.body_warning(has_body)
+    }
+
     /// Get the request body with proper type
     pub fn get_request_body(&self, cx: &App) -> RequestBody {
         let stored_body = self.request.read(cx).body().clone();
@@ -378,6 +925,28 @@ impl RequestView {
                     RequestBody::MultipartFormData(Vec::new())
                 }
             }
+            BodyType::GraphQl => match (
+                self.graphql_query_editor.as_ref(),
+                self.graphql_variables_editor.as_ref(),
+            ) {
+                (Some(query), Some(variables)) => RequestBody::GraphQl {
+                    query: query.read(cx).text().to_string(),
+                    variables: variables.read(cx).text().to_string(),
+                },
+                _ => match stored_body {
+                    RequestBody::GraphQl { query, variables } => {
+                        RequestBody::GraphQl { query, variables }
+                    }
+                    _ => RequestBody::GraphQl {
+                        query: String::new(),
+                        variables: String::new(),
+                    },
+                },
+            },
+            BodyType::Binary => match stored_body {
+                RequestBody::Binary(path) => RequestBody::Binary(path),
+                _ => RequestBody::Binary(PathBuf::new()),
+            },
         }
     }
 
@@ -410,18 +979,15 @@ impl RequestView {
         }
 
         // Add auth header if applicable
-        if let Some(ref auth_editor) = self.auth_editor {
-            let config = auth_editor.read(cx).get_config(cx);
-            if let Some((key, value)) = config.to_header() {
-                if let Some(header) = headers
-                    .iter_mut()
-                    .find(|header| header.key.eq_ignore_ascii_case(&key))
-                {
-                    header.value = value;
-                    header.enabled = true;
-                } else {
-                    headers.push(Header::new(key, value));
-                }
+        if let Some((key, value)) = self.resolved_auth(cx).to_header() {
+            if let Some(header) = headers
+                .iter_mut()
+                .find(|header| header.key.eq_ignore_ascii_case(&key))
+            {
+                header.value = value;
+                header.enabled = true;
+            } else {
+                headers.push(Header::new(key, value));
             }
         }
 
@@ -439,20 +1005,18 @@ impl RequestView {
     /// Sync headers to request entity
     pub fn sync_headers_to_request(&self, cx: &mut Context<Self>) {
         let headers = self.get_all_headers(cx);
+        let inherit_auth = self
+            .auth_editor
+            .as_ref()
+            .map(|editor| editor.read(cx).auth_type() == AuthType::Inherit);
         self.request.update(cx, |req, cx| {
             req.set_headers(headers, cx);
+            if let Some(inherit_auth) = inherit_auth {
+                req.set_inherit_auth(inherit_auth, cx);
+            }
         });
     }
 
-    /// Get query string from params editor
-    pub fn get_query_string(&self, cx: &App) -> String {
-        if let Some(ref params_editor) = self.params_editor {
-            params_editor.read(cx).build_query_string(cx)
-        } else {
-            String::new()
-        }
-    }
-
     /// Import body content from a file
     pub fn import_body_from_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let body_editor = self.body_editor.clone();
@@ -543,51 +1107,357 @@ impl RequestView {
         .detach();
     }
 
-    /// Beautify/format JSON content in the body editor
-    pub fn beautify_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let Some(ref body_editor) = self.body_editor else {
-            log::warn!("Body editor not found");
-            window.push_notification(
-                (NotificationType::Warning, "Body editor not initialized"),
-                cx,
-            );
+    /// Load a file dragged onto the body editor. Text goes into the editor; binary content is
+    /// sent from the file, as the whole body or as a form-data file field.
+    fn import_dropped_body(
+        &mut self,
+        paths: &ExternalPaths,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(path) = paths.paths().first().cloned() else {
             return;
         };
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let read_path = path.clone();
+        crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+            let _ = tx.send(read_dropped_file(&read_path));
+        });
+        cx.spawn_in(window, async move |view, cx| {
+            let Ok(contents) = rx.await else {
+                return;
+            };
+            let _ = cx.update(|window, app| {
+                view.update(app, |view, cx| {
+                    view.apply_dropped_body(path, contents, window, cx);
+                })
+            });
+        })
+        .detach();
+    }
 
-        let current_text = body_editor.read(cx).text().to_string();
-
-        if current_text.trim().is_empty() {
-            window.push_notification((NotificationType::Warning, "No content to beautify"), cx);
-            return;
-        }
-
-        match serde_json::from_str::<serde_json::Value>(&current_text) {
-            Ok(value) => match serde_json::to_string_pretty(&value) {
-                Ok(formatted) => {
-                    body_editor.update(cx, |state, cx| {
-                        state.set_value(formatted, window, cx);
-                    });
-                    window.push_notification(
-                        (NotificationType::Success, "JSON formatted successfully"),
-                        cx,
-                    );
-                    cx.notify();
-                }
-                Err(e) => {
-                    log::warn!("Failed to format JSON: {}", e);
-                    window
-                        .push_notification((NotificationType::Error, "Failed to format JSON"), cx);
-                }
-            },
-            Err(e) => {
-                log::warn!("Invalid JSON, cannot beautify: {}", e);
-                window.push_notification((NotificationType::Error, "Invalid JSON syntax"), cx);
-            }
+    fn apply_dropped_body(
+        &mut self,
+        path: PathBuf,
+        contents: io::Result<DroppedFile>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let contents = match contents {
+            Ok(contents) => contents,
+            Err(error) => {
+                log::error!("Failed to read dropped file {}: {}", path.display(), error);
+                window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::from(format!("Could not read {name}: {error}")),
+                    ),
+                    cx,
+                );
+                return;
+            }
+        };
+        let text = match contents {
+            DroppedFile::Text(text) => text,
+            _ if self.body_type == BodyType::Binary => {
+                self.use_binary_file(path, window, cx);
+                return;
+            }
+            DroppedFile::Binary => {
+                let reason = format!("{name} is not text, so it cannot be edited as the body.");
+                self.offer_binary_body(reason, path, window, cx);
+                return;
+            }
+            DroppedFile::TooLarge(size) => {
+                let reason = format!(
+                    "{name} is {}, too large to edit as the body.",
+                    format_size(size)
+                );
+                self.offer_binary_body(reason, path, window, cx);
+                return;
+            }
+        };
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        match dropped_text_body(self.body_type, extension) {
+            DroppedTextBody::Load(body_type) => self.load_body_text(body_type, text, window, cx),
+            DroppedTextBody::OfferSwitch(body_type) => {
+                self.offer_body_type_switch(name, body_type, text, window, cx)
+            }
+        }
+    }
+
+    /// Replace the body editor's content, first switching the body to `body_type`.
+    fn load_body_text(
+        &mut self,
+        body_type: BodyType,
+        content: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if body_type != self.body_type {
+            self.body_type = body_type;
+            if let Some(selector) = self.body_type_selector.clone() {
+                selector.update(cx, |s, cx| s.set_type(body_type, window, cx));
+            }
+            self.sync_body_type_header(window, cx);
+        }
+        self.initial_body_content = Some(content.clone());
+        self.ensure_body_editor(window, cx);
+        if let Some(editor) = self.body_editor.clone() {
+            editor.update(cx, |state, cx| {
+                state.set_value(content, window, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    fn offer_body_type_switch(
+        &mut self,
+        name: String,
+        detected: BodyType,
+        content: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let current = self.body_type;
+        let content: Rc<str> = content.into();
+        let this = cx.entity().clone();
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_switch = this.clone();
+            let content_for_switch = content.clone();
+            let this_for_keep = this.clone();
+            let content_for_keep = content.clone();
+            dialog
+                .title(format!("Switch body to {}?", detected.as_str()))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!(
+                            "{name} looks like {}, but the body is set to {}.",
+                            detected.as_str(),
+                            current.as_str()
+                        )),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("body-drop-switch")
+                                .label(format!("Switch to {}", detected.as_str()))
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    this_for_switch.update(cx, |view, cx| {
+                                        view.load_body_text(
+                                            detected,
+                                            content_for_switch.to_string(),
+                                            window,
+                                            cx,
+                                        );
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("body-drop-keep")
+                                .label(format!("Keep {}", current.as_str()))
+                                .on_click(move |_, window, cx| {
+                                    this_for_keep.update(cx, |view, cx| {
+                                        view.load_body_text(
+                                            current,
+                                            content_for_keep.to_string(),
+                                            window,
+                                            cx,
+                                        );
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("body-drop-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
+
+    /// Offer to send the file at `path` without loading it into the editor, for `reason`.
+    fn offer_binary_body(
+        &mut self,
+        reason: String,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity().clone();
+        open_dialog(window, cx, move |dialog, _, cx| {
+            let this_for_binary = this.clone();
+            let path_for_binary = path.clone();
+            let this_for_attach = this.clone();
+            let path_for_attach = path.clone();
+            dialog
+                .title("Send file as the body?")
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!(
+                            "{reason} It can be sent as the whole body, read from the file, \
+                             or as a file field of a form-data body."
+                        )),
+                )
+                .footer(
+                    DialogFooter::new()
+                        .child(
+                            Button::new("body-drop-binary")
+                                .label("Use Binary")
+                                .primary()
+                                .on_click(move |_, window, cx| {
+                                    this_for_binary.update(cx, |view, cx| {
+                                        view.use_binary_file(path_for_binary.clone(), window, cx);
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("body-drop-form-data")
+                                .label("Use form-data")
+                                .on_click(move |_, window, cx| {
+                                    this_for_attach.update(cx, |view, cx| {
+                                        view.attach_form_data_file(
+                                            path_for_attach.clone(),
+                                            window,
+                                            cx,
+                                        );
+                                    });
+                                    close_dialog(window, cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("body-drop-form-data-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| close_dialog(window, cx)),
+                        ),
+                )
+        });
+    }
+
+    /// Switch the body to binary and send the file at `path` as the whole body.
+    fn use_binary_file(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
+        if self.body_type != BodyType::Binary {
+            self.body_type = BodyType::Binary;
+            if let Some(selector) = self.body_type_selector.clone() {
+                selector.update(cx, |s, cx| s.set_type(BodyType::Binary, window, cx));
+            }
+            self.sync_body_type_header(window, cx);
+        }
+        self.binary_file = None;
+        self.request.update(cx, |request, cx| {
+            request.set_body(RequestBody::Binary(path), cx);
+        });
+        cx.notify();
+    }
+
+    /// Switch the body to form-data and add `path` as a `file` field.
+    fn attach_form_data_file(
+        &mut self,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.body_type != BodyType::FormData {
+            self.body_type = BodyType::FormData;
+            if let Some(selector) = self.body_type_selector.clone() {
+                selector.update(cx, |s, cx| s.set_type(BodyType::FormData, window, cx));
+            }
+        }
+        self.ensure_multipart_form_data_editor(window, cx);
+        if let Some(editor) = self.multipart_form_data_editor.clone() {
+            editor.update(cx, |editor, cx| {
+                editor.add_file_row("file", path, window, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    /// Beautify/format JSON content in the body editor
+    pub fn beautify_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // GraphQL bodies only carry JSON in their variables pane
+        let target = if self.body_type == BodyType::GraphQl {
+            self.graphql_variables_editor.as_ref()
+        } else {
+            self.body_editor.as_ref()
+        };
+        let Some(body_editor) = target else {
+            log::warn!("Body editor not found");
+            window.push_notification(
+                (NotificationType::Warning, "Body editor not initialized"),
+                cx,
+            );
+            return;
+        };
+
+        let current_text = body_editor.read(cx).text().to_string();
+
+        if current_text.trim().is_empty() {
+            window.push_notification((NotificationType::Warning, "No content to beautify"), cx);
+            return;
+        }
+
+        match serde_json::from_str::<serde_json::Value>(&current_text) {
+            Ok(mut value) => {
+                if cx
+                    .try_global::<JsonFormatSettings>()
+                    .is_some_and(|settings| settings.sort_keys)
+                {
+                    sort_json_keys(&mut value);
+                }
+                match serde_json::to_string_pretty(&value) {
+                    Ok(formatted) => {
+                        body_editor.update(cx, |state, cx| {
+                            state.set_value(formatted, window, cx);
+                        });
+                        window.push_notification(
+                            (NotificationType::Success, "JSON formatted successfully"),
+                            cx,
+                        );
+                        cx.notify();
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to format JSON: {}", e);
+                        window.push_notification(
+                            (NotificationType::Error, "Failed to format JSON"),
+                            cx,
+                        );
+                    }
+                }
+            }
+            Err(e) => {
+                log::warn!("Invalid JSON, cannot beautify: {}", e);
+                window.push_notification((NotificationType::Error, "Invalid JSON syntax"), cx);
+            }
         }
     }
 
     /// Clear the body editor content
     pub fn clear_body(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.body_type == BodyType::GraphQl {
+            for editor in [&self.graphql_query_editor, &self.graphql_variables_editor]
+                .into_iter()
+                .flatten()
+            {
+                editor.update(cx, |state, cx| {
+                    state.set_value(String::new(), window, cx);
+                });
+            }
+            cx.notify();
+            return;
+        }
+
         let Some(ref body_editor) = self.body_editor else {
             log::warn!("Body editor not found");
             return;
@@ -602,7 +1472,22 @@ impl RequestView {
 
     /// Trigger search in the body editor
     pub fn trigger_search(&mut self, window: &mut Window, _cx: &mut Context<Self>) {
-        crate::utils::trigger_editor_search(self.body_editor.clone(), window);
+        let editor = if self.body_type == BodyType::GraphQl {
+            self.graphql_query_editor.clone()
+        } else {
+            self.body_editor.clone()
+        };
+        crate::utils::trigger_editor_search(editor, window);
+    }
+
+    /// Editors whose text is JSON-like: the body, or a GraphQL body's variables.
+    pub fn json_editors(&self) -> Vec<Entity<InputState>> {
+        let editor = if self.body_type == BodyType::GraphQl {
+            self.graphql_variables_editor.clone()
+        } else {
+            self.body_editor.clone()
+        };
+        editor.into_iter().collect()
     }
 
     /// Apply a parsed curl command to the request entity and editors.
@@ -614,13 +1499,36 @@ impl RequestView {
     ) {
         let body_type = BodyType::from_request_body(&parsed.body);
 
+        // `-u` credentials go to the auth editor, which adds the header on send. The parser
+        // only keeps them when no explicit `-H Authorization` was given, so the header
+        // dropped here is always the generated one.
+        let headers = if parsed.basic_auth.is_some() {
+            parsed
+                .headers
+                .iter()
+                .filter(|h| !h.key.eq_ignore_ascii_case("Authorization"))
+                .cloned()
+                .collect()
+        } else {
+            parsed.headers.clone()
+        };
+
         self.request.update(cx, |req, cx| {
             req.set_method(parsed.method, cx);
             req.set_url(parsed.url.clone(), cx);
-            req.set_headers(parsed.headers.clone(), cx);
+            req.set_headers(headers, cx);
             req.set_body(parsed.body.clone(), cx);
         });
 
+        if let Some((username, password)) = &parsed.basic_auth {
+            self.ensure_auth_editor(window, cx);
+            if let Some(editor) = self.auth_editor.clone() {
+                editor.update(cx, |editor, cx| {
+                    editor.set_basic_credentials(username, password, window, cx);
+                });
+            }
+        }
+
         self.body_type = body_type;
 
         if let Some(selector) = self.body_type_selector.clone() {
@@ -655,6 +1563,13 @@ impl RequestView {
                 self.initial_multipart_data = Some(fields.clone());
                 self.ensure_multipart_form_data_editor(window, cx);
             }
+            RequestBody::GraphQl { .. } => {
+                // Rebuilt from the request entity on next render.
+                self.graphql_query_editor = None;
+                self.graphql_variables_editor = None;
+            }
+            // Looked up again on next render.
+            RequestBody::Binary(_) => self.binary_file = None,
             RequestBody::None => {}
         }
 
@@ -667,6 +1582,27 @@ impl RequestView {
 
         cx.notify();
     }
+
+    /// Bring `headers` from another request into this one and show the headers tab.
+    pub fn apply_headers(
+        &mut self,
+        headers: &[Header],
+        mode: HeaderMergeMode,
+        cx: &mut Context<Self>,
+    ) {
+        let current = match &self.header_editor {
+            Some(editor) => editor.read(cx).get_headers(cx),
+            None => self.request.read(cx).headers().to_vec(),
+        };
+        let merged = merge_headers(current, headers, mode);
+        self.request
+            .update(cx, |request, cx| request.set_headers(merged, cx));
+
+        // Rebuilt from the request entity on next render.
+        self.header_editor = None;
+        self.active_tab = RequestTab::Headers;
+        cx.notify();
+    }
 }
 
 impl Focusable for RequestView {
@@ -753,6 +1689,36 @@ impl RequestView {
                         }
                     }),
             )
+            .child(
+                PanelTab::new("Pre-request")
+                    .active(self.active_tab == RequestTab::PreRequest)
+                    .on_click({
+                        let this = this.clone();
+                        move |_event, _window, cx| {
+                            this.update(cx, |view, cx| view.set_tab(RequestTab::PreRequest, cx));
+                        }
+                    }),
+            )
+            .child(
+                PanelTab::new("Post-response")
+                    .active(self.active_tab == RequestTab::PostResponse)
+                    .on_click({
+                        let this = this.clone();
+                        move |_event, _window, cx| {
+                            this.update(cx, |view, cx| view.set_tab(RequestTab::PostResponse, cx));
+                        }
+                    }),
+            )
+            .child(
+                PanelTab::new("Tests")
+                    .active(self.active_tab == RequestTab::Tests)
+                    .on_click({
+                        let this = this.clone();
+                        move |_event, _window, cx| {
+                            this.update(cx, |view, cx| view.set_tab(RequestTab::Tests, cx));
+                        }
+                    }),
+            )
     }
 
     fn render_tab_content(
@@ -763,14 +1729,23 @@ impl RequestView {
         let _request = self.request.read(cx);
 
         match self.active_tab {
-            RequestTab::Body => self.render_body_tab(theme).into_any_element(),
+            RequestTab::Body => self
+                .render_body_tab(theme, cx.entity().clone())
+                .into_any_element(),
             RequestTab::Params => self.render_params_tab().into_any_element(),
             RequestTab::Headers => self.render_headers_tab().into_any_element(),
             RequestTab::Auth => self.render_auth_tab().into_any_element(),
+            RequestTab::PreRequest => self.render_pre_request_tab(theme).into_any_element(),
+            RequestTab::PostResponse => self.render_post_response_tab(theme).into_any_element(),
+            RequestTab::Tests => self.render_tests_tab(theme).into_any_element(),
         }
     }
 
-    fn render_body_tab(&self, theme: &gpui_component::theme::ThemeColor) -> impl IntoElement {
+    fn render_body_tab(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        this: Entity<Self>,
+    ) -> impl IntoElement {
         div()
             .id("request-body-editor")
             .flex()
@@ -795,32 +1770,48 @@ impl RequestView {
                     el.child(editor.clone())
                 })
             })
-            // Body editor (only show when body type is not None, FormUrlEncoded, or FormData)
+            // GraphQL query and variables side by side
+            .when(self.body_type == BodyType::GraphQl, |el| {
+                el.child(self.render_graphql_editors(theme))
+            })
+            .when(self.body_type == BodyType::Binary, |el| {
+                el.child(self.render_binary_body(theme, this.clone()))
+            })
+            // Body editor (only show for plain text-based body types)
             .when(
-                self.body_type != BodyType::None
-                    && self.body_type != BodyType::FormUrlEncoded
-                    && self.body_type != BodyType::FormData,
+                !matches!(
+                    self.body_type,
+                    BodyType::None
+                        | BodyType::FormUrlEncoded
+                        | BodyType::FormData
+                        | BodyType::GraphQl
+                        | BodyType::Binary
+                ),
                 |el| {
                     el.child(
-                        div()
-                            .id("request-body-editor-scroll")
-                            .flex_1()
-                            .overflow_y_scroll()
-                            .bg(theme.muted)
-                            .when_some(self.body_editor.as_ref(), |el, editor| {
+                        accept_body_file_drops(
+                            div().id("request-body-editor-scroll"),
+                            this.clone(),
+                        )
+                        .flex_1()
+                        .overflow_y_scroll()
+                        .bg(theme.muted)
+                        .when_some(
+                            self.body_editor.as_ref(),
+                            |el, editor| {
                                 el.child(CompletionInput::new(
                                     editor,
                                     Input::new(editor).appearance(false).size_full().p_0(),
                                 ))
-                            }),
+                            },
+                        ),
                     )
                 },
             )
             // Placeholder when body type is None
             .when(self.body_type == BodyType::None, |el| {
                 el.child(
-                    div()
-                        .id("request-body-none-placeholder")
+                    accept_body_file_drops(div().id("request-body-none-placeholder"), this)
                         .flex_1()
                         .flex()
                         .flex_col()
@@ -838,11 +1829,162 @@ impl RequestView {
                                 .text_color(theme.muted_foreground)
                                 .text_size(px(13.0))
                                 .child("This request does not have a body"),
+                        )
+                        .child(
+                            div()
+                                .text_color(theme.muted_foreground.opacity(0.7))
+                                .text_size(px(12.0))
+                                .child("Drop a file here to use it as the body"),
                         ),
                 )
             })
     }
 
+    /// The chosen file's name and size, or a note when it moved or was deleted.
+    fn render_binary_body(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        this: Entity<Self>,
+    ) -> impl IntoElement {
+        let (title, detail, missing) = match &self.binary_file {
+            None => (
+                "No file selected".to_string(),
+                "The whole file is sent as the body, typed by its extension unless a \
+                 Content-Type header is set"
+                    .to_string(),
+                false,
+            ),
+            Some((path, size)) => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                match size {
+                    Some(size) => (
+                        name,
+                        format!("{} · {}", format_size(*size), path.display()),
+                        false,
+                    ),
+                    None => (
+                        name,
+                        format!("File not found at {}; choose it again", path.display()),
+                        true,
+                    ),
+                }
+            }
+        };
+
+        div()
+            .id("request-body-binary")
+            .flex_1()
+            .flex()
+            .flex_col()
+            .gap(px(8.0))
+            .items_center()
+            .justify_center()
+            .px(px(16.0))
+            .bg(theme.muted)
+            .child(
+                Icon::new(if missing {
+                    IconName::TriangleAlert
+                } else {
+                    IconName::File
+                })
+                .size(px(32.0))
+                .text_color(if missing {
+                    theme.warning
+                } else {
+                    theme.muted_foreground
+                }),
+            )
+            .child(
+                div()
+                    .text_color(theme.foreground)
+                    .text_size(px(13.0))
+                    .child(title),
+            )
+            .child(
+                div()
+                    .text_color(if missing {
+                        theme.warning
+                    } else {
+                        theme.muted_foreground.opacity(0.7)
+                    })
+                    .text_size(px(12.0))
+                    .text_center()
+                    .child(detail),
+            )
+            .child(
+                Button::new("body-binary-choose")
+                    .label(if self.binary_file.is_some() {
+                        "Choose Another File…"
+                    } else {
+                        "Choose File…"
+                    })
+                    .on_click(move |_, window, cx| {
+                        this.update(cx, |view, cx| view.pick_binary_file(window, cx));
+                    }),
+            )
+    }
+
+    fn render_graphql_editors(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> impl IntoElement {
+        let pane = |id: &'static str, label: &'static str, editor: Option<&Entity<InputState>>| {
+            div()
+                .id(id)
+                .flex()
+                .flex_col()
+                .flex_1()
+                .min_w(px(0.0))
+                .h_full()
+                .overflow_hidden()
+                .child(
+                    div()
+                        .px(px(12.0))
+                        .py(px(4.0))
+                        .border_b_1()
+                        .border_color(theme.border)
+                        .text_color(theme.muted_foreground)
+                        .text_size(px(11.0))
+                        .font_weight(gpui::FontWeight::SEMIBOLD)
+                        .child(label),
+                )
+                .child(
+                    div()
+                        .flex_1()
+                        .overflow_y_scroll()
+                        .when_some(editor, |el, editor| {
+                            el.child(CompletionInput::new(
+                                editor,
+                                Input::new(editor).appearance(false).size_full().p_0(),
+                            ))
+                        }),
+                )
+        };
+
+        div()
+            .id("request-graphql-editors")
+            .flex()
+            .flex_row()
+            .flex_1()
+            .w_full()
+            .overflow_hidden()
+            .bg(theme.muted)
+            .child(pane(
+                "request-graphql-query",
+                "Query",
+                self.graphql_query_editor.as_ref(),
+            ))
+            .child(div().w(px(1.0)).h_full().bg(theme.border))
+            .child(pane(
+                "request-graphql-variables",
+                "Variables",
+                self.graphql_variables_editor.as_ref(),
+            ))
+    }
+
     fn render_params_tab(&self) -> impl IntoElement {
         div()
             .flex()
@@ -878,6 +2020,358 @@ impl RequestView {
                 el.child(editor.clone())
             })
     }
+
+    fn render_pre_request_tab(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> impl IntoElement {
+        Self::render_script_tab(
+            "request-pre-request",
+            self.pre_request_editor.as_ref(),
+            "Runs before each send, one statement per line: header NAME = value sets \
+             a header and set name = value fills {{name}}. Values join with + and \
+             can call now(), now_ms(), now_iso(), uuid(), base64(..), sha256(..), \
+             hmac_sha256(key, text) and env(\"KEY\").",
+            theme,
+        )
+    }
+
+    fn render_post_response_tab(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> impl IntoElement {
+        Self::render_script_tab(
+            "request-post-response",
+            self.post_response_editor.as_ref(),
+            "Runs after each response, one rule per line: set name = $.path, \
+             header:Name, status or body copies that value into the active \
+             environment, so later requests can use {{name}}. Start a rule with \
+             secret to store a token masked.",
+            theme,
+        )
+    }
+
+    /// A script editor over a one-paragraph description of what the script may contain.
+    fn render_script_tab(
+        id: &'static str,
+        editor: Option<&Entity<InputState>>,
+        hint: &'static str,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> impl IntoElement {
+        div()
+            .id(id)
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .overflow_hidden()
+            .bg(theme.muted)
+            .child(Self::render_script_editor(id, editor))
+            .child(
+                div()
+                    .px(px(12.0))
+                    .py(px(8.0))
+                    .border_t_1()
+                    .border_color(theme.border)
+                    .text_size(px(12.0))
+                    .text_color(theme.muted_foreground.opacity(0.7))
+                    .child(hint),
+            )
+    }
+
+    fn render_script_editor(
+        id: &'static str,
+        editor: Option<&Entity<InputState>>,
+    ) -> impl IntoElement {
+        div()
+            .id(SharedString::from(format!("{id}-editor")))
+            .flex_1()
+            .min_h(px(80.0))
+            .overflow_y_scroll()
+            .when_some(editor, |el, editor| {
+                el.child(Input::new(editor).appearance(false).size_full().p_0())
+            })
+    }
+
+    fn render_tests_tab(&self, theme: &gpui_component::theme::ThemeColor) -> impl IntoElement {
+        let summary = match &self.test_results {
+            Some(results) => format!(
+                "{} of {} passed",
+                results.iter().filter(|result| result.passed).count(),
+                results.len()
+            ),
+            None => "Checks run after each response".to_string(),
+        };
+
+        div()
+            .id("request-tests")
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .overflow_hidden()
+            .bg(theme.muted)
+            .child(Self::render_script_editor(
+                "request-tests",
+                self.tests_editor.as_ref(),
+            ))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .justify_between()
+                    .px(px(12.0))
+                    .py(px(4.0))
+                    .border_t_1()
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .text_size(px(11.0))
+                    .child(
+                        div()
+                            .text_color(theme.muted_foreground)
+                            .font_weight(gpui::FontWeight::SEMIBOLD)
+                            .child("Results"),
+                    )
+                    .child(div().text_color(theme.muted_foreground).child(summary)),
+            )
+            .child(
+                div()
+                    .id("request-tests-results")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .map(|el| match &self.test_results {
+                        Some(results) if !results.is_empty() => {
+                            el.children(results.iter().enumerate().map(|(index, result)| {
+                                Self::render_test_result(index, result, theme)
+                            }))
+                        }
+                        _ => el.child(
+                            div()
+                                .px(px(12.0))
+                                .py(px(8.0))
+                                .text_size(px(12.0))
+                                .text_color(theme.muted_foreground.opacity(0.7))
+                                .child(
+                                    "One check per line, e.g. status == 200, header Content-Type \
+                                     contains json or body.jsonpath($.ok) == true. Lines \
+                                     starting with # are ignored.",
+                                ),
+                        ),
+                    }),
+            )
+    }
+
+    fn render_test_result(
+        index: usize,
+        result: &AssertionResult,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> AnyElement {
+        let (icon, color) = if result.passed {
+            (IconName::CircleCheck, theme.success)
+        } else {
+            (IconName::CircleX, theme.danger)
+        };
+        let actual = result.actual.clone();
+
+        div()
+            .id(SharedString::from(format!("request-test-result-{index}")))
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap(px(8.0))
+            .min_h(px(28.0))
+            .px(px(12.0))
+            .border_b_1()
+            .border_color(theme.border.opacity(0.3))
+            .text_size(px(12.0))
+            .child(Icon::new(icon).size(px(14.0)).text_color(color))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w(px(0.0))
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .text_color(theme.foreground)
+                    .child(result.source.clone()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w(px(0.0))
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .text_color(theme.muted_foreground)
+                    .child(result.actual.clone()),
+            )
+            .tooltip(move |window, cx| Tooltip::new(actual.clone()).build(window, cx))
+            .into_any_element()
+    }
 }
 
 impl EventEmitter<RequestViewEvent> for RequestView {}
+
+/// Highlight `element` while files are dragged over it and load the first one dropped.
+fn accept_body_file_drops(element: Stateful<Div>, this: Entity<RequestView>) -> Stateful<Div> {
+    element
+        .on_drop(move |paths: &ExternalPaths, window, cx| {
+            this.update(cx, |view, cx| view.import_dropped_body(paths, window, cx));
+        })
+        .drag_over::<ExternalPaths>(|style, _, _, cx| style.bg(cx.theme().primary.opacity(0.1)))
+}
+
+/// A dropped file, read just far enough to know how it can be used as the body.
+#[derive(Debug, PartialEq, Eq)]
+enum DroppedFile {
+    Text(String),
+    /// Not UTF-8 text, judging by its first bytes or all of them.
+    Binary,
+    /// Text, but `MAX_DROPPED_TEXT_BYTES` or more; holds the file size.
+    TooLarge(u64),
+}
+
+/// Read `path` for the body editor off the UI thread. Binary files are recognised from
+/// a prefix and large ones from their metadata, so neither is read in full.
+fn read_dropped_file(path: &Path) -> io::Result<DroppedFile> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let mut bytes = Vec::new();
+    (&mut file).take(DROP_SNIFF_BYTES).read_to_end(&mut bytes)?;
+    // A prefix may end part-way through a character; only bytes that can never be valid
+    // UTF-8 mark it as binary.
+    let binary = bytes.contains(&0)
+        || std::str::from_utf8(&bytes).is_err_and(|error| error.error_len().is_some());
+    if binary {
+        return Ok(DroppedFile::Binary);
+    }
+    if size > MAX_DROPPED_TEXT_BYTES {
+        return Ok(DroppedFile::TooLarge(size));
+    }
+    file.read_to_end(&mut bytes)?;
+    Ok(String::from_utf8(bytes).map_or(DroppedFile::Binary, DroppedFile::Text))
+}
+
+/// What a dropped text file does to the body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DroppedTextBody {
+    /// Load the text as this body type, switching to it if the body is not raw text.
+    Load(BodyType),
+    /// The file looks like a different raw type than the current one; ask before switching.
+    OfferSwitch(BodyType),
+}
+
+fn dropped_text_body(current: BodyType, extension: Option<&str>) -> DroppedTextBody {
+    let detected = extension.and_then(BodyType::from_file_extension);
+    if !current.is_raw_text() {
+        return DroppedTextBody::Load(detected.unwrap_or(BodyType::Text));
+    }
+    match detected {
+        Some(detected) if detected != current => DroppedTextBody::OfferSwitch(detected),
+        _ => DroppedTextBody::Load(current),
+    }
+}
+
+/// Formatted body for an edit that looks like a paste of valid JSON or XML.
+fn pasted_body_format(body_type: BodyType, previous_len: usize, text: &str) -> Option<String> {
+    if text.len() < previous_len + PASTE_MIN_INSERT_BYTES {
+        return None;
+    }
+    let formatted = match body_type {
+        BodyType::Json => pretty_json(text)?,
+        BodyType::Xml => pretty_xml(text)?,
+        _ => return None,
+    };
+    (formatted != text).then_some(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DROP_SNIFF_BYTES, DroppedFile, DroppedTextBody, MAX_DROPPED_TEXT_BYTES, dropped_text_body,
+        pasted_body_format, read_dropped_file,
+    };
+    use crate::components::BodyType;
+
+    #[test]
+    fn formats_large_json_and_xml_inserts() {
+        let json = r#"{"name":"setu","tags":["a","b"]}"#;
+        let formatted = pasted_body_format(BodyType::Json, 0, json).unwrap();
+        assert!(formatted.starts_with("{\n  \"name\": \"setu\""));
+
+        let xml = "<note><to>Tove</to><from>Jani</from></note>";
+        let formatted = pasted_body_format(BodyType::Xml, 0, xml).unwrap();
+        assert!(formatted.contains("\n  <to>Tove</to>"));
+    }
+
+    #[test]
+    fn ignores_typing_invalid_content_and_other_body_types() {
+        let json = r#"{"name":"setu","tags":["a","b"]}"#;
+        assert!(pasted_body_format(BodyType::Json, json.len() - 1, json).is_none());
+        assert!(pasted_body_format(BodyType::Json, 0, r#"{"name": "unterminated"#).is_none());
+        assert!(pasted_body_format(BodyType::Text, 0, json).is_none());
+    }
+
+    #[test]
+    fn leaves_already_formatted_content_alone() {
+        let json = "{\n  \"name\": \"setu\",\n  \"ok\": true\n}";
+        assert!(pasted_body_format(BodyType::Json, 0, json).is_none());
+    }
+
+    #[test]
+    fn dropped_files_are_sniffed_before_being_read_in_full() {
+        let dir = std::env::temp_dir().join(format!("setu-drop-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let text = dir.join("body.json");
+        std::fs::write(&text, "{\"name\": \"setu\"}").unwrap();
+        let binary = dir.join("image.png");
+        std::fs::write(&binary, [0x89, b'P', b'N', b'G', 0, 0, 0, 13]).unwrap();
+        // Text for as far as the sniff reads, then sparse to past the limit.
+        let large = dir.join("large.txt");
+        std::fs::write(&large, vec![b'a'; DROP_SNIFF_BYTES as usize]).unwrap();
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&large)
+            .and_then(|file| file.set_len(MAX_DROPPED_TEXT_BYTES + 1))
+            .unwrap();
+
+        assert_eq!(
+            read_dropped_file(&text).unwrap(),
+            DroppedFile::Text("{\"name\": \"setu\"}".to_string())
+        );
+        assert_eq!(read_dropped_file(&binary).unwrap(), DroppedFile::Binary);
+        assert_eq!(
+            read_dropped_file(&large).unwrap(),
+            DroppedFile::TooLarge(MAX_DROPPED_TEXT_BYTES + 1)
+        );
+        assert!(read_dropped_file(&dir.join("missing")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dropped_files_offer_a_switch_only_between_raw_types() {
+        assert_eq!(
+            dropped_text_body(BodyType::Json, Some("JSON")),
+            DroppedTextBody::Load(BodyType::Json)
+        );
+        assert_eq!(
+            dropped_text_body(BodyType::Json, Some("xml")),
+            DroppedTextBody::OfferSwitch(BodyType::Xml)
+        );
+        assert_eq!(
+            dropped_text_body(BodyType::Xml, Some("csv")),
+            DroppedTextBody::Load(BodyType::Xml)
+        );
+        assert_eq!(
+            dropped_text_body(BodyType::None, Some("htm")),
+            DroppedTextBody::Load(BodyType::Html)
+        );
+        assert_eq!(
+            dropped_text_body(BodyType::None, None),
+            DroppedTextBody::Load(BodyType::Text)
+        );
+    }
+}
@@ -1,4 +1,8 @@
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -7,28 +11,40 @@ use std::time::Duration;
 use bytes::Bytes;
 use gpui::prelude::*;
 use gpui::{
-    AnyElement, App, Context, ElementId, Entity, FocusHandle, Focusable, Image, ImageFormat,
-    IntoElement, ListHorizontalSizingBehavior, PathPromptOptions, Pixels, Render, SharedString,
-    Size, Styled, UniformListScrollHandle, Window, div, img, px, size, uniform_list,
+    Animation, AnimationExt, AnyElement, App, Context, CursorStyle, ElementId, Entity, FocusHandle,
+    Focusable, Image, ImageFormat, IntoElement, ListHorizontalSizingBehavior, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, Render, ScrollHandle,
+    SharedString, Size, Styled, UniformListScrollHandle, Window, div, img, pulsating_between, px,
+    size, uniform_list,
 };
 use gpui_component::Selectable;
 use gpui_component::Sizable;
 use gpui_component::VirtualListScrollHandle;
 use gpui_component::WindowExt;
 use gpui_component::button::{Button, ButtonVariants};
-use gpui_component::input::{Input, InputState};
+use gpui_component::highlighter::{Diagnostic, DiagnosticSeverity};
+use gpui_component::input::{Input, InputEvent, InputState, Position};
+use gpui_component::menu::{DropdownMenu, PopupMenuItem};
 use gpui_component::notification::NotificationType;
 use gpui_component::scroll::Scrollbar;
 use gpui_component::spinner::Spinner;
 use gpui_component::v_virtual_list;
 
+use crate::actions::{CancelRequest, EditAndResend};
+use crate::codegen::{FixtureFormat, render_fixture};
 use crate::components::StatusBadge;
 use crate::components::audio_player::AudioPlayer;
+use crate::components::json_tree::JsonTreeView;
 use crate::entities::{
-    ContentCategory, ResponseData, ResponseEntity, ResponseEvent, ResponseState,
-    ResponseTextSnapshot,
+    Attachment, ContentCategory, ErrorDetails, ExpectContinue, HttpMethod, MAX_STREAM_BODY_BYTES,
+    ResponseData, ResponseEntity, ResponseEvent, ResponseState, ResponseTextSnapshot,
+    SecurityHeaderStatus,
 };
+use crate::http::SseEvent;
 use crate::icons::IconName;
+use crate::utils::{
+    JsonFormatSettings, JsonPath, format_size, html_text, pretty_json_sorted, probe_mp4,
+};
 use gpui_component::ActiveTheme;
 use gpui_component::Icon;
 
@@ -38,6 +54,15 @@ const VIRTUAL_TEXT_ROW_MAX_BYTES: usize = 384;
 const VIRTUAL_TEXT_ROW_HEIGHT: Pixels = px(22.0);
 const VIRTUAL_TEXT_ROW_WIDTH: Pixels = px(8192.0);
 const LARGE_RESPONSE_DEBOUNCE: Duration = Duration::from_millis(16);
+/// Largest line table compared line by line when marking changes from the previous
+/// response; a bigger changed region is marked as a whole.
+const CHANGED_LINES_MAX_CELLS: usize = 1_000_000;
+const MAX_SEARCH_MATCHES: usize = 10_000;
+const SSE_ROW_HEIGHT: Pixels = px(30.0);
+const SSE_PREVIEW_CHARS: usize = 400;
+/// Scales the image zoom buttons step through.
+const IMAGE_ZOOM_STEPS: &[f32] = &[0.1, 0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 4.0, 8.0];
+const IMAGE_PADDING: Pixels = px(16.0);
 
 /// Active tab in the response panel
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -49,12 +74,88 @@ pub enum ResponseTab {
     Raw,
     /// Response headers
     Headers,
+    /// Redirect hops followed before the final response
+    Redirects,
+}
+
+/// How a JSON body is shown on the Body tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonBodyMode {
+    /// Formatted text in the editor
+    #[default]
+    Pretty,
+    /// Collapsible tree of the parsed value
+    Tree,
+}
+
+/// How large an image body is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum ImageZoom {
+    /// Shrunk to the pane's width, never enlarged
+    #[default]
+    Fit,
+    /// Drawn at this multiple of its pixel size
+    Scale(f32),
+}
+
+impl ImageZoom {
+    fn scale(self, fit: f32) -> f32 {
+        match self {
+            ImageZoom::Fit => fit,
+            ImageZoom::Scale(scale) => scale,
+        }
+    }
+
+    /// The next zoom step above or below the current scale, staying put at either end.
+    fn stepped(self, fit: f32, zoom_in: bool) -> Self {
+        let current = self.scale(fit);
+        let next = if zoom_in {
+            IMAGE_ZOOM_STEPS
+                .iter()
+                .find(|&&step| step > current + 0.001)
+        } else {
+            IMAGE_ZOOM_STEPS
+                .iter()
+                .rev()
+                .find(|&&step| step < current - 0.001)
+        };
+        next.map_or(self, |&step| ImageZoom::Scale(step))
+    }
+}
+
+struct DecodedImage {
+    body_hash: u64,
+    image: Arc<Image>,
+    /// Format detected from the bytes, which may differ from what the content type claims
+    format: Option<&'static str>,
+    /// Pixel size read from the image header; `None` for SVG and unrecognised data.
+    dimensions: Option<(u32, u32)>,
+}
+
+/// Whether formatted JSON bodies are shown with their keys sorted.
+fn sorts_json_keys(cx: &App) -> bool {
+    cx.try_global::<JsonFormatSettings>()
+        .is_some_and(|settings| settings.sort_keys)
+}
+
+/// Whether a body of `len` bytes is too large to pretty-print on arrival. Only bodies the
+/// Body tab would reformat count; a `max_bytes` of 0 formats everything.
+fn skips_auto_format(category: ContentCategory, len: usize, max_bytes: usize) -> bool {
+    matches!(
+        category,
+        ContentCategory::Json | ContentCategory::Xml | ContentCategory::Html
+    ) && max_bytes > 0
+        && len > max_bytes
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct ResponseTextKey {
     body_hash: u64,
     category: ContentCategory,
+    /// Object keys sorted alphabetically; only ever set for formatted JSON bodies
+    sorted_keys: bool,
+    /// Lines changed from the previous response are marked; only set for formatted bodies
+    changes_marked: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -79,6 +180,29 @@ struct PreparedTextDisplay {
     key: ResponseTextKey,
     content: Arc<str>,
     display: TextDisplay,
+    /// Line numbers that differ from the previous response of this tab.
+    changed_lines: Arc<HashSet<usize>>,
+}
+
+/// JSONPath filter over a JSON body and the matches of its last valid expression.
+struct JsonFilter {
+    input: Entity<InputState>,
+    /// Body hash and expression of the newest evaluation, finished or still running.
+    requested: Option<(u64, String)>,
+    /// Parsed body, reused while only the expression changes.
+    parsed: Option<(u64, Arc<serde_json::Value>)>,
+    /// Matches of the last valid expression, keyed by the hash of their text.
+    result: Option<PreparedTextDisplay>,
+    error: Option<String>,
+}
+
+/// Matches for the response search bar, tied to the text they were computed from.
+struct ResponseSearchMatches {
+    tab: ResponseTab,
+    key: ResponseTextKey,
+    query: String,
+    ranges: Vec<Range<usize>>,
+    current: usize,
 }
 
 fn virtual_text_rows(content: &str) -> Vec<VirtualTextRow> {
@@ -150,6 +274,80 @@ fn virtual_text_rows(content: &str) -> Vec<VirtualTextRow> {
     rows
 }
 
+/// Underline the 1-based `lines` of the editor as changed, with a note on hover; the code
+/// editor has no gutter of ours to put markers in.
+fn mark_changed_lines(state: &mut InputState, lines: &HashSet<usize>) {
+    let Some(diagnostics) = state.diagnostics_mut() else {
+        return;
+    };
+    diagnostics.clear();
+    for &line in lines {
+        let row = line.saturating_sub(1) as u32;
+        diagnostics.push(
+            Diagnostic::new(
+                Position::new(row, 0)..Position::new(row + 1, 0),
+                "Changed since the previous response",
+            )
+            .with_severity(DiagnosticSeverity::Info),
+        );
+    }
+}
+
+/// Line numbers (1-based) of `current` that are not in `previous`, found by a line diff.
+///
+/// Lines shared at the start and end are skipped first, so an edit in the middle of a
+/// large body only compares the region around it.
+fn changed_lines(previous: &str, current: &str) -> HashSet<usize> {
+    let old: Vec<&str> = previous.lines().collect();
+    let new: Vec<&str> = current.lines().collect();
+    let prefix = old
+        .iter()
+        .zip(&new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old = &old[prefix..old.len() - suffix];
+    let new = &new[prefix..new.len() - suffix];
+    let line_number = |index: usize| prefix + index + 1;
+
+    if old.is_empty() || (old.len() + 1) * (new.len() + 1) > CHANGED_LINES_MAX_CELLS {
+        return (0..new.len()).map(line_number).collect();
+    }
+
+    // Longest common subsequence of the remaining lines, filled from the end.
+    let width = new.len() + 1;
+    let mut common = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i * width + j] = if old[i] == new[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut changed = HashSet::new();
+    let (mut i, mut j) = (0, 0);
+    while j < new.len() {
+        if i < old.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && common[(i + 1) * width + j] >= common[i * width + j + 1] {
+            i += 1;
+        } else {
+            changed.insert(line_number(j));
+            j += 1;
+        }
+    }
+    changed
+}
+
 fn should_virtualize_response_text(content: &str) -> bool {
     content.len() >= LARGE_RESPONSE_THRESHOLD_BYTES
         || content
@@ -161,6 +359,164 @@ fn should_virtualize_response_text(content: &str) -> bool {
             >= LARGE_RESPONSE_MAX_EDITOR_LINES
 }
 
+/// Byte ranges of case-insensitive, non-overlapping matches of `query`.
+fn find_search_matches(content: &str, query: &str) -> Vec<Range<usize>> {
+    let needle = query.as_bytes();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let haystack = content.as_bytes();
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + needle.len() <= haystack.len() && matches.len() < MAX_SEARCH_MATCHES {
+        if haystack[start..start + needle.len()].eq_ignore_ascii_case(needle) {
+            matches.push(start..start + needle.len());
+            start += needle.len();
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+fn search_match_label(current: usize, total: usize) -> String {
+    if total == 0 {
+        "No matches".to_string()
+    } else if total >= MAX_SEARCH_MATCHES {
+        format!("{} of {}+", current + 1, MAX_SEARCH_MATCHES)
+    } else {
+        format!("{} of {}", current + 1, total)
+    }
+}
+
+/// Single-line, length-capped view of an event's data for the live event list.
+fn sse_data_preview(data: &str) -> String {
+    let text = data.replace(['\r', '\n'], " ");
+    if text.chars().count() > SSE_PREVIEW_CHARS {
+        let truncated: String = text.chars().take(SSE_PREVIEW_CHARS).collect();
+        format!("{}…", truncated)
+    } else {
+        text
+    }
+}
+
+/// Redirect hops as `[{"status": 302, "location": "..."}]` for copying and saving.
+fn redirects_json(redirects: &[(u16, String)]) -> serde_json::Value {
+    redirects
+        .iter()
+        .map(|(status, location)| serde_json::json!({ "status": status, "location": location }))
+        .collect()
+}
+
+/// Index of the virtual row that contains byte `offset`.
+fn virtual_row_for_offset(rows: &[VirtualTextRow], offset: usize) -> usize {
+    rows.partition_point(|row| row.byte_range.end <= offset)
+        .min(rows.len().saturating_sub(1))
+}
+
+/// Zero-based line and character column of byte `offset`.
+fn line_and_column(content: &str, offset: usize) -> (u32, u32) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (line as u32, before[line_start..].chars().count() as u32)
+}
+
+fn escape_markdown_cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// Render key/value pairs as a two-column GitHub-flavoured Markdown table.
+fn markdown_table(columns: (&str, &str), rows: &[(String, String)]) -> String {
+    let mut table = format!("| {} | {} |\n| --- | --- |\n", columns.0, columns.1);
+    for (key, value) in rows {
+        table.push_str(&format!(
+            "| {} | {} |\n",
+            escape_markdown_cell(key),
+            escape_markdown_cell(value)
+        ));
+    }
+    table
+}
+
+fn header_markdown_rows(
+    headers: &std::collections::HashMap<String, String>,
+) -> Vec<(String, String)> {
+    let mut rows: Vec<(String, String)> = headers
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    rows.sort();
+    rows
+}
+
+/// Headers as they appear on the wire, one `Key: Value` line each, sorted by name.
+fn raw_header_block(headers: &std::collections::HashMap<String, String>) -> String {
+    header_markdown_rows(headers)
+        .into_iter()
+        .map(|(key, value)| format!("{key}: {value}\n"))
+        .collect()
+}
+
+/// Headers as `.env` lines, `NAME=value` with names upper-cased and anything outside
+/// `A-Z`, `0-9` and `_` turned into `_`. Values that a shell would split are quoted.
+fn dotenv_header_block(headers: &std::collections::HashMap<String, String>) -> String {
+    header_markdown_rows(headers)
+        .into_iter()
+        .map(|(key, value)| {
+            let mut name: String = key
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() {
+                        c.to_ascii_uppercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            if name.starts_with(|c: char| c.is_ascii_digit()) {
+                name.insert(0, '_');
+            }
+            let needs_quotes = value.is_empty()
+                || value
+                    .chars()
+                    .any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '$' | '\\'));
+            if needs_quotes {
+                let escaped = value
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('$', "\\$");
+                format!("{name}=\"{escaped}\"\n")
+            } else {
+                format!("{name}={value}\n")
+            }
+        })
+        .collect()
+}
+
+/// Top-level fields of a JSON object body. Nested values are kept as compact JSON.
+fn json_markdown_rows(body: &str) -> Option<Vec<(String, String)>> {
+    let serde_json::Value::Object(fields) = serde_json::from_str(body).ok()? else {
+        return None;
+    };
+    Some(
+        fields
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(text) => text,
+                    other => other.to_string(),
+                };
+                (key, value)
+            })
+            .collect(),
+    )
+}
+
 /// Response view
 pub struct ResponseView {
     pub response: Entity<ResponseEntity>,
@@ -176,21 +532,90 @@ pub struct ResponseView {
     headers_scroll_handle: VirtualListScrollHandle,
     /// Whether to wrap lines in the editor
     wrap_lines: bool,
+    /// Whether body lines that changed since the previous response are marked; off by
+    /// default, since marked bodies are shown as plain rows rather than in the editor
+    mark_changes: bool,
+    /// Formatted body of the response shown before the current one
+    previous_body: Option<Arc<str>>,
+    /// Method and URL of the request last sent from this tab
+    sent_request: Option<(HttpMethod, String)>,
     audio_player: Option<Entity<AudioPlayer>>,
-    decoded_image: Option<(u64, Arc<Image>)>,
+    decoded_image: Option<DecodedImage>,
+    /// Details line of a PDF or video body, worked out once per response
+    body_summary: Option<(u64, String)>,
+    /// Whether the Details section of a failed request is open; closed for every new response
+    error_details_expanded: bool,
+    /// Zoom of the image body; back to fit for every new response
+    image_zoom: ImageZoom,
+    image_scroll_handle: ScrollHandle,
+    /// Pointer position and scroll offset where the current image pan started
+    image_pan: Option<(Point<Pixels>, Point<Pixels>)>,
+    json_body_mode: JsonBodyMode,
+    /// Tree for the JSON body with this hash, built the first time Tree mode is shown
+    json_tree: Option<(u64, Entity<JsonTreeView>)>,
+    /// Whether the tree shows string values decoded rather than as escaped JSON literals
+    unescape_json_strings: bool,
+    json_filter: Option<JsonFilter>,
+    /// Filter above the headers list, created the first time the Headers tab is shown
+    header_filter: Option<Entity<InputState>>,
+    /// Whether the security header checklist is shown above the headers list
+    security_checks_open: bool,
+    /// Whether text and HTML bodies are shown as wrapped prose instead of in the code editor
+    reading_view: bool,
+    /// Whether an SVG body is shown as highlighted XML markup instead of rendered
+    svg_source: bool,
+    /// Text content of the HTML body with this hash, shown by the reading view
+    html_preview: Option<(u64, Arc<str>)>,
+    /// Where the last response was saved, so its folder can be opened from the tools bar
+    last_saved_path: Option<PathBuf>,
+    search_input: Option<Entity<InputState>>,
+    search_open: bool,
+    search_matches: Option<ResponseSearchMatches>,
+    /// Scroll handle for the live event list, kept pinned to the newest event
+    stream_scroll_handle: UniformListScrollHandle,
+    /// Body hash of the response moved to the Raw tab for being over the auto-format size
+    raw_for_size: Option<u64>,
 }
 
 impl ResponseView {
     pub fn new(response: Entity<ResponseEntity>, cx: &mut Context<Self>) -> Self {
-        cx.subscribe(&response, |this, _response, _event: &ResponseEvent, cx| {
+        cx.observe_global::<JsonFormatSettings>(|_, cx| cx.notify())
+            .detach();
+        cx.subscribe(&response, |this, response, event: &ResponseEvent, cx| {
+            if let ResponseEvent::StreamUpdated = event {
+                let count = response.read(cx).sse_events().len();
+                if count > 0 {
+                    this.stream_scroll_handle
+                        .scroll_to_item(count - 1, gpui::ScrollStrategy::Bottom);
+                }
+                cx.notify();
+                return;
+            }
+            if let ResponseEvent::Received = event {
+                this.choose_tab_for_size(cx);
+            }
             this.body_generation.fetch_add(1, Ordering::AcqRel);
             this.raw_generation.fetch_add(1, Ordering::AcqRel);
             this.requested_body = None;
             this.requested_raw = None;
-            this.body_display = None;
+            // Kept to mark what the next response changes.
+            if let Some(display) = this.body_display.take() {
+                this.previous_body = Some(display.content);
+            }
             this.raw_display = None;
             this.audio_player = None;
             this.decoded_image = None;
+            this.image_zoom = ImageZoom::Fit;
+            this.image_pan = None;
+            this.json_tree = None;
+            this.html_preview = None;
+            this.error_details_expanded = false;
+            if let Some(filter) = this.json_filter.as_mut() {
+                filter.requested = None;
+                filter.parsed = None;
+                filter.result = None;
+                filter.error = None;
+            }
             cx.notify();
         })
         .detach();
@@ -207,18 +632,81 @@ impl ResponseView {
             focus_handle: cx.focus_handle(),
             headers_scroll_handle: VirtualListScrollHandle::new(),
             wrap_lines: true,
+            mark_changes: false,
+            previous_body: None,
+            sent_request: None,
             audio_player: None,
             decoded_image: None,
+            body_summary: None,
+            error_details_expanded: false,
+            image_zoom: ImageZoom::Fit,
+            image_scroll_handle: ScrollHandle::new(),
+            image_pan: None,
+            json_body_mode: JsonBodyMode::default(),
+            json_tree: None,
+            unescape_json_strings: false,
+            json_filter: None,
+            header_filter: None,
+            security_checks_open: false,
+            reading_view: false,
+            svg_source: false,
+            html_preview: None,
+            last_saved_path: None,
+            search_input: None,
+            search_open: false,
+            search_matches: None,
+            stream_scroll_handle: UniformListScrollHandle::new(),
+            raw_for_size: None,
+        }
+    }
+
+    /// Open a new response on the Raw tab when it is over the auto-format size, and go back
+    /// to the Body tab for the next one that is not.
+    fn choose_tab_for_size(&mut self, cx: &mut Context<Self>) {
+        let max_bytes = cx
+            .try_global::<JsonFormatSettings>()
+            .map_or(0, |settings| settings.auto_format_max_bytes);
+        let oversized = self.response.read(cx).data.as_ref().and_then(|data| {
+            skips_auto_format(
+                data.content_category(),
+                data.text_snapshot().source_len(),
+                max_bytes,
+            )
+            .then(|| data.body_hash())
+        });
+        match oversized {
+            Some(body_hash) if self.active_tab == ResponseTab::Body => {
+                self.active_tab = ResponseTab::Raw;
+                self.raw_for_size = Some(body_hash);
+            }
+            Some(body_hash) if self.raw_for_size.is_some() => {
+                self.raw_for_size = Some(body_hash);
+            }
+            Some(_) => {}
+            None => {
+                if self.raw_for_size.take().is_some() && self.active_tab == ResponseTab::Raw {
+                    self.active_tab = ResponseTab::Body;
+                }
+            }
         }
     }
 
+    /// Leave the Raw tab picked for size and pretty-print the body after all.
+    fn format_anyway(&mut self, cx: &mut Context<Self>) {
+        self.raw_for_size = None;
+        self.set_tab(ResponseTab::Body, cx);
+    }
+
     fn ensure_body_display(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let sort_keys = sorts_json_keys(cx);
         let Some((key, snapshot)) = self.response.read(cx).data.as_ref().map(|data| {
-            let category = data.content_category();
+            let category = self.body_category(data);
             (
                 ResponseTextKey {
                     body_hash: data.body_hash(),
                     category,
+                    sorted_keys: sort_keys && category == ContentCategory::Json,
+                    changes_marked: self.marks_changes(category),
                 },
                 data.text_snapshot(),
             )
@@ -272,12 +760,15 @@ impl ResponseView {
                         Some(ct) if ct.contains("svg") => ImageFormat::Svg,
                         _ => ImageFormat::Png,
                     };
-                    Some(Arc::new(Image::from_bytes(
-                        format,
-                        data.body_bytes().to_vec(),
-                    )))
+                    let (detected, dimensions) = probe_image(data.body_bytes());
+                    Some(DecodedImage {
+                        body_hash: key.body_hash,
+                        format: detected.or((format == ImageFormat::Svg).then_some("SVG")),
+                        image: Arc::new(Image::from_bytes(format, data.body_bytes().to_vec())),
+                        dimensions,
+                    })
                 });
-                self.decoded_image = image_data.map(|image| (key.body_hash, image));
+                self.decoded_image = image_data;
                 self.requested_body = Some(key);
             }
             return;
@@ -290,6 +781,48 @@ impl ResponseView {
         if self.decoded_image.is_some() {
             self.decoded_image = None;
         }
+        if matches!(key.category, ContentCategory::Pdf | ContentCategory::Video) {
+            if self.requested_body != Some(key) {
+                self.body_summary = self
+                    .response
+                    .read(cx)
+                    .data
+                    .as_ref()
+                    .map(|data| (key.body_hash, body_summary(data)));
+                self.requested_body = Some(key);
+            }
+            return;
+        }
+
+        if key.category == ContentCategory::Json
+            && self.json_body_mode == JsonBodyMode::Tree
+            && self
+                .json_tree
+                .as_ref()
+                .is_none_or(|(hash, _)| *hash != key.body_hash)
+        {
+            let snapshot = snapshot.clone();
+            let unescape = self.unescape_json_strings;
+            let tree =
+                cx.new(|cx| JsonTreeView::new(snapshot, cx).with_unescaped_strings(unescape));
+            self.json_tree = Some((key.body_hash, tree));
+        }
+
+        if key.category == ContentCategory::Html
+            && self.reading_view
+            && snapshot.source_len() < LARGE_RESPONSE_THRESHOLD_BYTES
+            && self
+                .html_preview
+                .as_ref()
+                .is_none_or(|(hash, _)| *hash != key.body_hash)
+        {
+            let text = html_text(&snapshot.raw_body());
+            self.html_preview = Some((key.body_hash, Arc::from(text)));
+        }
+
+        if key.category == ContentCategory::Json && self.json_body_mode == JsonBodyMode::Pretty {
+            self.ensure_json_filter(key.body_hash, &snapshot, window, cx);
+        }
 
         if needs_update && self.requested_body != Some(key) {
             self.body_display = None;
@@ -304,6 +837,8 @@ impl ResponseView {
                 ResponseTextKey {
                     body_hash: data.body_hash(),
                     category: data.content_category(),
+                    sorted_keys: false,
+                    changes_marked: false,
                 },
                 data.text_snapshot(),
             )
@@ -339,6 +874,10 @@ impl ResponseView {
             .fetch_add(1, Ordering::AcqRel)
             .wrapping_add(1);
         let worker_generation_clock = generation_clock.clone();
+        let previous_body = self
+            .previous_body
+            .clone()
+            .filter(|_| formatted && key.changes_marked);
         let should_debounce = snapshot.source_len() >= LARGE_RESPONSE_THRESHOLD_BYTES;
         let (tx, rx) = tokio::sync::oneshot::channel();
         crate::utils::shared_tokio_runtime().spawn(async move {
@@ -354,14 +893,21 @@ impl ResponseView {
                 if blocking_generation_clock.load(Ordering::Acquire) != generation {
                     return None;
                 }
-                let content = if formatted {
+                let content = if key.sorted_keys {
+                    pretty_json_sorted(&snapshot.raw_body())
+                        .map(Arc::<str>::from)
+                        .unwrap_or_else(|| snapshot.formatted_body())
+                } else if formatted {
                     snapshot.formatted_body()
                 } else {
                     snapshot.raw_body()
                 };
+                let changed = previous_body
+                    .map(|previous| changed_lines(&previous, &content))
+                    .filter(|changed| !changed.is_empty());
                 let rows = should_virtualize_response_text(&content)
                     .then(|| Arc::new(virtual_text_rows(&content)));
-                Some((content, rows))
+                Some((content, rows, Arc::new(changed.unwrap_or_default())))
             })
             .await;
 
@@ -379,7 +925,7 @@ impl ResponseView {
                 if generation_clock.load(Ordering::Acquire) != generation {
                     return;
                 }
-                let Ok((content, rows)) = result else {
+                let Ok((content, rows, changed_lines)) = result else {
                     let _ = view.update(app, |this, cx| {
                         if formatted && this.requested_body == Some(key) {
                             this.requested_body = None;
@@ -391,8 +937,10 @@ impl ResponseView {
                     return;
                 };
 
+                // An SVG's markup is prepared as XML, so it matches either category.
                 let is_current = response.read(app).data.as_ref().is_some_and(|data| {
-                    data.body_hash() == key.body_hash && data.content_category() == key.category
+                    data.body_hash() == key.body_hash
+                        && (data.content_category() == key.category || data.is_svg())
                 });
                 if !is_current {
                     let _ = view.update(app, |this, cx| {
@@ -406,11 +954,14 @@ impl ResponseView {
                     return;
                 }
 
-                response.update(app, |response, _cx| {
-                    if let Some(data) = response.data.as_mut() {
-                        data.cache_prepared_body(key.body_hash, formatted, content.clone());
-                    }
-                });
+                // The response keeps its body in arrival order; a sorted copy is only shown.
+                if !key.sorted_keys {
+                    response.update(app, |response, _cx| {
+                        if let Some(data) = response.data.as_mut() {
+                            data.cache_prepared_body(key.body_hash, formatted, content.clone());
+                        }
+                    });
+                }
 
                 let _ = view.update(app, |this, cx| {
                     let requested = if formatted {
@@ -445,6 +996,12 @@ impl ResponseView {
                                 .soft_wrap(wrap_lines)
                                 .default_value(editor_content)
                         });
+                        if !changed_lines.is_empty() {
+                            editor.update(cx, |state, cx| {
+                                mark_changed_lines(state, &changed_lines);
+                                cx.notify();
+                            });
+                        }
                         TextDisplay::Editor(editor)
                     };
 
@@ -452,6 +1009,7 @@ impl ResponseView {
                         key,
                         content,
                         display,
+                        changed_lines,
                     };
                     if formatted {
                         this.body_display = Some(prepared);
@@ -465,11 +1023,238 @@ impl ResponseView {
         .detach();
     }
 
+    /// Create the headers filter input on first use; the list is filtered as it renders.
+    fn ensure_header_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.header_filter.is_some() {
+            return;
+        }
+        let input = cx.new(|cx| InputState::new(window, cx).placeholder("Filter headers"));
+        cx.subscribe(&input, |_, _, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                cx.notify();
+            }
+        })
+        .detach();
+        self.header_filter = Some(input);
+    }
+
+    /// Create the filter input on first use and evaluate its expression once per body and
+    /// expression; an empty expression shows the whole body again.
+    fn ensure_json_filter(
+        &mut self,
+        body_hash: u64,
+        snapshot: &ResponseTextSnapshot,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let filter = self.json_filter.get_or_insert_with(|| {
+            let input = cx.new(|cx| {
+                InputState::new(window, cx).placeholder("Filter with JSONPath, e.g. $.data[*].id")
+            });
+            cx.subscribe(&input, |_, _, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            })
+            .detach();
+            JsonFilter {
+                input,
+                requested: None,
+                parsed: None,
+                result: None,
+                error: None,
+            }
+        });
+
+        let expression = filter.input.read(cx).text().trim().to_string();
+        if expression.is_empty() {
+            filter.requested = None;
+            filter.result = None;
+            filter.error = None;
+            return;
+        }
+        let request = (body_hash, expression.clone());
+        if filter.requested.as_ref() == Some(&request) {
+            return;
+        }
+        filter.requested = Some(request.clone());
+
+        let parsed = filter
+            .parsed
+            .as_ref()
+            .filter(|(hash, _)| *hash == body_hash)
+            .map(|(_, value)| value.clone());
+        let snapshot = snapshot.clone();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+            let value = match parsed {
+                Some(value) => Ok(value),
+                None => serde_json::from_str::<serde_json::Value>(&snapshot.raw_body())
+                    .map(Arc::new)
+                    .map_err(|error| format!("Body is not valid JSON: {error}")),
+            };
+            let outcome = value.map(|value| {
+                let matches = JsonPath::parse(&expression)
+                    .map(|path| {
+                        let text = serde_json::to_string_pretty(&path.evaluate(&value))
+                            .unwrap_or_default();
+                        let rows = should_virtualize_response_text(&text)
+                            .then(|| Arc::new(virtual_text_rows(&text)));
+                        (Arc::<str>::from(text), rows)
+                    })
+                    .map_err(|error| error.to_string());
+                (value, matches)
+            });
+            let _ = tx.send(outcome);
+        });
+
+        cx.spawn_in(window, async move |view, cx| {
+            let Ok(outcome) = rx.await else {
+                return;
+            };
+            let _ = cx.update(|window, app| {
+                let _ = view.update(app, |this, cx| {
+                    let wrap_lines = this.wrap_lines;
+                    let Some(filter) = this
+                        .json_filter
+                        .as_mut()
+                        .filter(|filter| filter.requested.as_ref() == Some(&request))
+                    else {
+                        return;
+                    };
+                    let matches = match outcome {
+                        Ok((value, matches)) => {
+                            filter.parsed = Some((body_hash, value));
+                            matches
+                        }
+                        Err(error) => Err(error),
+                    };
+                    match matches {
+                        Ok((content, rows)) => {
+                            let display = match rows {
+                                Some(rows) => TextDisplay::Virtual(VirtualTextDisplay {
+                                    content: content.clone(),
+                                    rows,
+                                    scroll_handle: UniformListScrollHandle::new(),
+                                }),
+                                None => {
+                                    let editor_content = content.clone();
+                                    TextDisplay::Editor(cx.new(move |cx| {
+                                        InputState::new(window, cx)
+                                            .code_editor("json")
+                                            .folding(true)
+                                            .line_number(true)
+                                            .searchable(true)
+                                            .soft_wrap(wrap_lines)
+                                            .default_value(editor_content)
+                                    }))
+                                }
+                            };
+                            let mut hasher = DefaultHasher::new();
+                            content.hash(&mut hasher);
+                            filter.result = Some(PreparedTextDisplay {
+                                key: ResponseTextKey {
+                                    body_hash: hasher.finish(),
+                                    category: ContentCategory::Json,
+                                    sorted_keys: false,
+                                    changes_marked: false,
+                                },
+                                content,
+                                display,
+                                changed_lines: Arc::default(),
+                            });
+                            filter.error = None;
+                        }
+                        // The last good result stays on screen under the error.
+                        Err(error) => filter.error = Some(error),
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    /// Filter matches shown in place of the body, when a JSONPath expression is active.
+    fn json_filter_result(&self) -> Option<&PreparedTextDisplay> {
+        if self.json_body_mode != JsonBodyMode::Pretty {
+            return None;
+        }
+        self.json_filter
+            .as_ref()
+            .filter(|filter| filter.requested.is_some())
+            .and_then(|filter| filter.result.as_ref())
+    }
+
+    pub fn active_tab(&self) -> ResponseTab {
+        self.active_tab
+    }
+
+    /// Whether a body of `category` is diffed against the previous response; the reading
+    /// view of text and HTML shows the body as prose, where markers do not fit.
+    fn marks_changes(&self, category: ContentCategory) -> bool {
+        self.mark_changes
+            && self.previous_body.is_some()
+            && !(self.reading_view
+                && matches!(category, ContentCategory::Text | ContentCategory::Html))
+    }
+
+    /// Note the request about to be sent. Changes are only marked between responses to the
+    /// same method and URL, so any other request starts without a previous body.
+    pub fn begin_request(&mut self, method: HttpMethod, url: &str) {
+        let same_request = self
+            .sent_request
+            .as_ref()
+            .is_some_and(|(sent_method, sent_url)| *sent_method == method && sent_url == url);
+        if !same_request {
+            self.sent_request = Some((method, url.to_string()));
+            self.previous_body = None;
+            self.body_display = None;
+        }
+    }
+
+    pub fn toggle_mark_changes(&mut self, cx: &mut Context<Self>) {
+        self.mark_changes = !self.mark_changes;
+        cx.notify();
+    }
+
+    pub fn toggle_security_checks(&mut self, cx: &mut Context<Self>) {
+        self.security_checks_open = !self.security_checks_open;
+        cx.notify();
+    }
+
     pub fn set_tab(&mut self, tab: ResponseTab, cx: &mut Context<Self>) {
         self.active_tab = tab;
         cx.notify();
     }
 
+    fn zoom_image(&mut self, zoom_in: bool, cx: &mut Context<Self>) {
+        let Some((width, _)) = self
+            .decoded_image
+            .as_ref()
+            .and_then(|image| image.dimensions)
+        else {
+            return;
+        };
+        let fit = image_fit_scale(width, self.image_viewport_width());
+        self.image_zoom = self.image_zoom.stepped(fit, zoom_in);
+        cx.notify();
+    }
+
+    fn toggle_image_fit(&mut self, cx: &mut Context<Self>) {
+        self.image_zoom = match self.image_zoom {
+            ImageZoom::Fit => ImageZoom::Scale(1.0),
+            ImageZoom::Scale(_) => ImageZoom::Fit,
+        };
+        self.image_scroll_handle.set_offset(Point::default());
+        cx.notify();
+    }
+
+    /// Width the image can take inside the body pane.
+    fn image_viewport_width(&self) -> Pixels {
+        self.image_scroll_handle.bounds().size.width - IMAGE_PADDING * 2.0
+    }
+
     pub fn toggle_wrap_lines(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.wrap_lines = !self.wrap_lines;
         if let Some(PreparedTextDisplay {
@@ -490,55 +1275,254 @@ impl ResponseView {
                 state.set_soft_wrap(self.wrap_lines, window, cx);
             });
         }
+        if let Some(PreparedTextDisplay {
+            display: TextDisplay::Editor(editor),
+            ..
+        }) = self
+            .json_filter
+            .as_ref()
+            .and_then(|filter| filter.result.as_ref())
+        {
+            editor.update(cx, |state, cx| {
+                state.set_soft_wrap(self.wrap_lines, window, cx);
+            });
+        }
         cx.notify();
     }
 
-    pub fn trigger_search(&mut self, window: &mut Window, _cx: &mut Context<Self>) {
-        let editor = match self.active_tab {
-            ResponseTab::Body => self.body_display.as_ref(),
-            ResponseTab::Raw => self.raw_display.as_ref(),
-            ResponseTab::Headers => None,
+    /// How the body tab shows `data`: an SVG counts as XML while its source is shown.
+    fn body_category(&self, data: &ResponseData) -> ContentCategory {
+        if self.svg_source && data.is_svg() {
+            ContentCategory::Xml
+        } else {
+            data.content_category()
         }
-        .and_then(|display| match &display.display {
-            TextDisplay::Editor(editor) => Some(editor.clone()),
-            TextDisplay::Virtual(_) => None,
-        });
-        crate::utils::trigger_editor_search(editor, window);
     }
 
-    fn active_text_is_virtual(&self) -> bool {
-        let display = match self.active_tab {
-            ResponseTab::Body => self.body_display.as_ref(),
-            ResponseTab::Raw => self.raw_display.as_ref(),
-            ResponseTab::Headers => None,
-        };
-        display.is_some_and(|display| matches!(display.display, TextDisplay::Virtual(_)))
+    fn set_svg_source(&mut self, svg_source: bool, cx: &mut Context<Self>) {
+        self.svg_source = svg_source;
+        if !svg_source {
+            self.close_search(cx);
+        }
+        cx.notify();
     }
 
-    fn prepared_text_for_tab(&self, tab: ResponseTab) -> Option<Arc<str>> {
-        match tab {
-            ResponseTab::Body => self.body_display.as_ref(),
-            ResponseTab::Raw => self.raw_display.as_ref(),
-            ResponseTab::Headers => None,
+    fn toggle_reading_view(&mut self, cx: &mut Context<Self>) {
+        self.reading_view = !self.reading_view;
+        if self.reading_view {
+            self.close_search(cx);
         }
-        .map(|display| display.content.clone())
+        cx.notify();
     }
 
-    fn copy_response(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let active_tab = self.active_tab;
-        if matches!(active_tab, ResponseTab::Body | ResponseTab::Raw) {
-            if let Some(content) = self.prepared_text_for_tab(active_tab)
-                && content.len() < LARGE_RESPONSE_THRESHOLD_BYTES
-            {
-                cx.write_to_clipboard(gpui::ClipboardItem::new_string(content.to_string()));
-                window.push_notification(
-                    (NotificationType::Success, "Response copied to clipboard"),
-                    cx,
-                );
-                return;
-            }
-
-            let prepared = self.prepared_text_for_tab(active_tab);
+    fn set_json_body_mode(&mut self, mode: JsonBodyMode, cx: &mut Context<Self>) {
+        self.json_body_mode = mode;
+        if mode == JsonBodyMode::Tree {
+            self.close_search(cx);
+        }
+        cx.notify();
+    }
+
+    fn toggle_unescape_json_strings(&mut self, cx: &mut Context<Self>) {
+        self.unescape_json_strings = !self.unescape_json_strings;
+        let unescape = self.unescape_json_strings;
+        if let Some((_, tree)) = &self.json_tree {
+            tree.update(cx, |tree, cx| tree.set_unescape_strings(unescape, cx));
+        }
+        cx.notify();
+    }
+
+    /// The JSON tree, when it is what the Body tab currently shows.
+    fn json_tree_shown(&self) -> Option<&Entity<JsonTreeView>> {
+        (self.active_tab == ResponseTab::Body && self.json_body_mode == JsonBodyMode::Tree)
+            .then_some(self.json_tree.as_ref())
+            .flatten()
+            .map(|(_, tree)| tree)
+    }
+
+    pub fn trigger_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if matches!(
+            self.active_tab,
+            ResponseTab::Headers | ResponseTab::Redirects
+        ) || self.json_tree_shown().is_some()
+        {
+            return;
+        }
+        let input = match self.search_input.clone() {
+            Some(input) => input,
+            None => {
+                let input =
+                    cx.new(|cx| InputState::new(window, cx).placeholder("Find in response"));
+                cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                    InputEvent::Change => {
+                        this.refresh_search_matches(cx);
+                        this.scroll_to_current_match(window, cx);
+                        cx.notify();
+                    }
+                    InputEvent::PressEnter { secondary } => {
+                        this.step_search_match(!secondary, window, cx);
+                    }
+                    _ => {}
+                })
+                .detach();
+                self.search_input = Some(input.clone());
+                input
+            }
+        };
+        self.search_open = true;
+        input.update(cx, |state, cx| state.focus(window, cx));
+        self.refresh_search_matches(cx);
+        cx.notify();
+    }
+
+    fn close_search(&mut self, cx: &mut Context<Self>) {
+        self.search_open = false;
+        self.search_matches = None;
+        cx.notify();
+    }
+
+    fn active_prepared_display(&self) -> Option<&PreparedTextDisplay> {
+        match self.active_tab {
+            ResponseTab::Body => self.json_filter_result().or(self.body_display.as_ref()),
+            ResponseTab::Raw => self.raw_display.as_ref(),
+            ResponseTab::Headers | ResponseTab::Redirects => None,
+        }
+    }
+
+    /// Recompute matches when the query, tab, or prepared text changed.
+    fn refresh_search_matches(&mut self, cx: &App) {
+        let Some(input) = self.search_input.as_ref().filter(|_| self.search_open) else {
+            self.search_matches = None;
+            return;
+        };
+        let query = input.read(cx).text().to_string();
+        let Some(prepared) = self.active_prepared_display() else {
+            self.search_matches = None;
+            return;
+        };
+        let is_current = self.search_matches.as_ref().is_some_and(|matches| {
+            matches.tab == self.active_tab && matches.key == prepared.key && matches.query == query
+        });
+        if is_current {
+            return;
+        }
+        self.search_matches = Some(ResponseSearchMatches {
+            tab: self.active_tab,
+            key: prepared.key,
+            ranges: find_search_matches(&prepared.content, &query),
+            query,
+            current: 0,
+        });
+    }
+
+    fn step_search_match(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.refresh_search_matches(cx);
+        let Some(matches) = self.search_matches.as_mut() else {
+            return;
+        };
+        let total = matches.ranges.len();
+        if total == 0 {
+            return;
+        }
+        matches.current = if forward {
+            (matches.current + 1) % total
+        } else {
+            (matches.current + total - 1) % total
+        };
+        self.scroll_to_current_match(window, cx);
+        cx.notify();
+    }
+
+    /// Bring the current match into view in the editor or the virtual list.
+    fn scroll_to_current_match(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(range) = self
+            .search_matches
+            .as_ref()
+            .and_then(|matches| matches.ranges.get(matches.current).cloned())
+        else {
+            return;
+        };
+        let Some(prepared) = self.active_prepared_display() else {
+            return;
+        };
+        match &prepared.display {
+            TextDisplay::Editor(editor) => {
+                let (line, character) = line_and_column(&prepared.content, range.start);
+                editor.update(cx, |state, cx| {
+                    state.set_cursor_position(Position::new(line, character), window, cx);
+                });
+            }
+            TextDisplay::Virtual(display) => {
+                let row = virtual_row_for_offset(&display.rows, range.start);
+                display
+                    .scroll_handle
+                    .scroll_to_item(row, gpui::ScrollStrategy::Center);
+            }
+        }
+    }
+
+    /// Editor showing the active Body or Raw text; large responses render without one.
+    pub fn text_editor(&self) -> Option<Entity<InputState>> {
+        if self.json_tree_shown().is_some() {
+            return None;
+        }
+        match &self.active_prepared_display()?.display {
+            TextDisplay::Editor(editor) => Some(editor.clone()),
+            TextDisplay::Virtual(_) => None,
+        }
+    }
+
+    fn active_text_is_virtual(&self) -> bool {
+        self.active_prepared_display()
+            .is_some_and(|display| matches!(display.display, TextDisplay::Virtual(_)))
+    }
+
+    /// How many lines of the active text differ from the previous response.
+    fn active_changed_line_count(&self) -> usize {
+        self.active_prepared_display()
+            .map_or(0, |display| display.changed_lines.len())
+    }
+
+    fn prepared_text_for_tab(&self, tab: ResponseTab) -> Option<Arc<str>> {
+        match tab {
+            ResponseTab::Body => self.json_filter_result().or(self.body_display.as_ref()),
+            ResponseTab::Raw => self.raw_display.as_ref(),
+            ResponseTab::Headers | ResponseTab::Redirects => None,
+        }
+        .map(|display| display.content.clone())
+    }
+
+    fn copy_response(&mut self, format: CopyFormat, window: &mut Window, cx: &mut Context<Self>) {
+        if format == CopyFormat::DotEnv {
+            let query = self
+                .header_filter
+                .as_ref()
+                .map(|input| input.read(cx).text().to_string())
+                .unwrap_or_default();
+            let Some(block) = self.response.read(cx).data.as_ref().map(|data| {
+                dotenv_header_block(&filter_headers(&data.headers, &query).into_iter().collect())
+            }) else {
+                return;
+            };
+            cx.write_to_clipboard(gpui::ClipboardItem::new_string(block));
+            window.push_notification((NotificationType::Success, "Headers copied as .env"), cx);
+            return;
+        }
+
+        let active_tab = self.active_tab;
+        if matches!(active_tab, ResponseTab::Body | ResponseTab::Raw) {
+            if let Some(content) = self.prepared_text_for_tab(active_tab)
+                && content.len() < LARGE_RESPONSE_THRESHOLD_BYTES
+            {
+                cx.write_to_clipboard(gpui::ClipboardItem::new_string(content.to_string()));
+                window.push_notification(
+                    (NotificationType::Success, "Response copied to clipboard"),
+                    cx,
+                );
+                return;
+            }
+
+            let prepared = self.prepared_text_for_tab(active_tab);
             let snapshot = prepared.is_none().then(|| {
                 self.response
                     .read(cx)
@@ -578,6 +1562,9 @@ impl ResponseView {
         }
 
         let content = self.response.read(cx).data.as_ref().map(|data| {
+            if active_tab == ResponseTab::Redirects {
+                return redirects_json(&data.redirects).to_string();
+            }
             let headers_json: Vec<serde_json::Value> = data
                 .headers
                 .iter()
@@ -593,28 +1580,137 @@ impl ResponseView {
 
         if let Some(content) = content {
             cx.write_to_clipboard(gpui::ClipboardItem::new_string(content));
-            window.push_notification(
-                (NotificationType::Success, "Response copied to clipboard"),
-                cx,
-            );
+            let message = match active_tab {
+                ResponseTab::Headers => "Headers copied as JSON",
+                _ => "Response copied to clipboard",
+            };
+            window.push_notification((NotificationType::Success, message), cx);
         }
     }
 
-    fn save_to_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        enum SaveContent {
-            Text(String),
-            SharedText(Arc<str>),
-            Bytes(Bytes),
+    fn copy_headers_raw(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(block) = self
+            .response
+            .read(cx)
+            .data
+            .as_ref()
+            .map(|data| raw_header_block(&data.headers))
+        else {
+            return;
+        };
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(block));
+        window.push_notification(
+            (NotificationType::Success, "Headers copied as raw HTTP"),
+            cx,
+        );
+    }
+
+    /// Copy the response as a test fixture in `format`, built off the UI thread since the
+    /// body may be large.
+    fn copy_as_fixture(
+        &mut self,
+        format: FixtureFormat,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(data) = self.response.read(cx).data.clone() else {
+            return;
+        };
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+            let _ = tx.send(render_fixture(&data, format));
+        });
+        cx.spawn_in(window, async move |_view, cx| {
+            if let Ok(fixture) = rx.await {
+                let _ = cx.update(|window, app| {
+                    app.write_to_clipboard(gpui::ClipboardItem::new_string(fixture));
+                    window.push_notification(
+                        (
+                            NotificationType::Success,
+                            SharedString::from(format!("{} fixture copied", format.label())),
+                        ),
+                        app,
+                    );
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn can_copy_as_markdown_table(&self, data: &ResponseData) -> bool {
+        match self.active_tab {
+            ResponseTab::Headers => true,
+            ResponseTab::Redirects => !data.redirects.is_empty(),
+            ResponseTab::Body => data.is_json(),
+            ResponseTab::Raw => false,
+        }
+    }
+
+    fn copy_as_markdown_table(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let table = self.response.read(cx).data.as_ref().and_then(|data| {
+            if self.active_tab == ResponseTab::Headers {
+                Some(markdown_table(
+                    ("Key", "Value"),
+                    &header_markdown_rows(&data.headers),
+                ))
+            } else if self.active_tab == ResponseTab::Redirects {
+                let rows: Vec<_> = data
+                    .redirects
+                    .iter()
+                    .map(|(status, location)| (status.to_string(), location.clone()))
+                    .collect();
+                Some(markdown_table(("Status", "Location"), &rows))
+            } else {
+                json_markdown_rows(&data.text_snapshot().raw_body())
+                    .map(|rows| markdown_table(("Field", "Value"), &rows))
+            }
+        });
+
+        match table {
+            Some(table) => {
+                cx.write_to_clipboard(gpui::ClipboardItem::new_string(table));
+                window.push_notification(
+                    (
+                        NotificationType::Success,
+                        "Markdown table copied to clipboard",
+                    ),
+                    cx,
+                );
+            }
+            None => window.push_notification(
+                (
+                    NotificationType::Warning,
+                    "Only JSON object responses can be copied as a table",
+                ),
+                cx,
+            ),
         }
+    }
 
+    fn save_to_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let active_tab = self.active_tab;
+        if active_tab == ResponseTab::Body
+            && self
+                .response
+                .read(cx)
+                .data
+                .as_ref()
+                .is_some_and(|data| data.attachment().is_some())
+        {
+            self.save_attachment(window, cx);
+            return;
+        }
         let prepared_text = self.prepared_text_for_tab(active_tab);
         let requires_prepared_text = matches!(active_tab, ResponseTab::Body | ResponseTab::Raw)
             && self.response.read(cx).data.as_ref().is_some_and(|data| {
                 let category = data.content_category();
                 let can_save_original_bytes = matches!(
                     category,
-                    ContentCategory::Image | ContentCategory::Binary | ContentCategory::Audio
+                    ContentCategory::Image
+                        | ContentCategory::Binary
+                        | ContentCategory::Audio
+                        | ContentCategory::Video
+                        | ContentCategory::Pdf
                 ) && !data.body_bytes().is_empty();
                 !can_save_original_bytes
             });
@@ -628,7 +1724,11 @@ impl ResponseView {
             let content_category = data.content_category();
             let is_binary = matches!(
                 content_category,
-                ContentCategory::Image | ContentCategory::Binary | ContentCategory::Audio
+                ContentCategory::Image
+                    | ContentCategory::Binary
+                    | ContentCategory::Audio
+                    | ContentCategory::Video
+                    | ContentCategory::Pdf
             );
 
             let save_content = match active_tab {
@@ -648,6 +1748,10 @@ impl ResponseView {
                             .unwrap_or_else(|_| "[]".to_string()),
                     )
                 }
+                ResponseTab::Redirects => SaveContent::Text(
+                    serde_json::to_string_pretty(&redirects_json(&data.redirects))
+                        .unwrap_or_else(|_| "[]".to_string()),
+                ),
                 _ if is_binary && !data.body_bytes().is_empty() => {
                     SaveContent::Bytes(data.body_bytes().clone())
                 }
@@ -659,48 +1763,8 @@ impl ResponseView {
             };
 
             let default_extension = match active_tab {
-                ResponseTab::Headers => "json",
-                _ => match content_category {
-                    ContentCategory::Json => "json",
-                    ContentCategory::Xml => "xml",
-                    ContentCategory::Html => "html",
-                    ContentCategory::Image => {
-                        let ct = data.content_type.as_deref().unwrap_or("");
-                        if ct.contains("jpeg") || ct.contains("jpg") {
-                            "jpg"
-                        } else if ct.contains("gif") {
-                            "gif"
-                        } else if ct.contains("webp") {
-                            "webp"
-                        } else if ct.contains("bmp") {
-                            "bmp"
-                        } else if ct.contains("svg") {
-                            "svg"
-                        } else {
-                            "png"
-                        }
-                    }
-                    ContentCategory::Audio => {
-                        let ct = data.content_type.as_deref().unwrap_or("");
-                        if ct.contains("mp3") || ct.contains("mpeg") {
-                            "mp3"
-                        } else if ct.contains("wav") {
-                            "wav"
-                        } else if ct.contains("ogg") {
-                            "ogg"
-                        } else if ct.contains("flac") {
-                            "flac"
-                        } else if ct.contains("aac") {
-                            "aac"
-                        } else if ct.contains("webm") {
-                            "webm"
-                        } else {
-                            "mp3"
-                        }
-                    }
-                    ContentCategory::Binary => "bin",
-                    _ => "txt",
-                },
+                ResponseTab::Headers | ResponseTab::Redirects => "json",
+                _ => default_body_extension(data),
             };
 
             Some((save_content, default_extension))
@@ -708,40 +1772,127 @@ impl ResponseView {
             return;
         };
 
-        let default_name = format!("response.{}", default_extension);
-        let this = cx.entity().clone();
+        self.save_with_dialog(
+            format!("response.{}", default_extension),
+            save_content,
+            window,
+            cx,
+        );
+    }
 
-        let options = PathPromptOptions {
-            files: false,
-            directories: true,
-            multiple: false,
-            prompt: Some("Select folder to save response".into()),
+    /// Save an attachment response under the file name its `Content-Disposition` suggested.
+    fn save_attachment(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((default_name, save_content)) = self.response.update(cx, |resp, _cx| {
+            let data = resp.data.as_mut()?;
+            let default_name = data
+                .attachment()
+                .and_then(|attachment| attachment.filename)
+                .unwrap_or_else(|| format!("response.{}", default_body_extension(data)));
+            Some((default_name, SaveContent::Bytes(data.file_contents())))
+        }) else {
+            return;
         };
 
-        let paths_receiver = cx.prompt_for_paths(options);
+        self.save_with_dialog(default_name, save_content, window, cx);
+    }
 
-        cx.spawn_in(window, async move |_weak_this, cx| {
-            let channel_result = paths_receiver.await;
+    /// Write the body to a temporary file and open it with the system's default app.
+    fn open_body_externally(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((bytes, path)) = self.response.read(cx).data.as_ref().and_then(|data| {
+            let bytes = data.body_bytes().clone();
+            // Named after the body, so opening the same response again reuses its file
+            // instead of leaving another copy behind.
+            let path = std::env::temp_dir().join("setu-responses").join(format!(
+                "{:016x}.{}",
+                data.body_hash(),
+                default_body_extension(data)
+            ));
+            (!bytes.is_empty()).then_some((bytes, path))
+        }) else {
+            return;
+        };
 
-            let Ok(platform_result) = channel_result else {
-                log::error!("File picker channel closed unexpectedly");
+        let (open_tx, open_rx) = tokio::sync::oneshot::channel();
+        crate::utils::shared_tokio_runtime().spawn_blocking(move || {
+            let written =
+                std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() == bytes.len() as u64);
+            let result = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| {
+                    if written {
+                        Ok(())
+                    } else {
+                        std::fs::write(&path, &bytes)
+                    }
+                })
+                .and_then(|_| open::that_detached(&path))
+                .map_err(|error| format!("{}: {error}", path.display()));
+            let _ = open_tx.send(result);
+        });
+
+        cx.spawn_in(window, async move |_, cx| {
+            let Ok(Err(error)) = open_rx.await else {
                 return;
             };
+            log::error!("Failed to open response {}", error);
+            let _ = cx.update(|window, app| {
+                window.push_notification(
+                    (NotificationType::Error, "Could not open the response"),
+                    app,
+                );
+            });
+        })
+        .detach();
+    }
+
+    /// Show the folder of the last saved response in the system file manager.
+    fn open_saved_folder(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(folder) = self
+            .last_saved_path
+            .as_deref()
+            .and_then(|path| path.parent())
+            .map(|folder| folder.to_path_buf())
+        else {
+            return;
+        };
+        if let Err(error) = open::that_detached(&folder) {
+            log::error!("Failed to open {}: {}", folder.display(), error);
+            window.push_notification((NotificationType::Error, "Could not open the folder"), cx);
+        }
+    }
+
+    /// Ask where to save through the platform save dialog, suggesting `default_name`.
+    fn save_with_dialog(
+        &mut self,
+        default_name: String,
+        save_content: SaveContent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity().clone();
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(std::env::temp_dir);
+        let path_receiver = cx.prompt_for_new_path(&directory, Some(&default_name));
+
+        cx.spawn_in(window, async move |_weak_this, cx| {
+            let channel_result = path_receiver.await;
 
-            let Ok(paths_opt) = platform_result else {
-                log::error!("File picker failed");
+            let Ok(platform_result) = channel_result else {
+                log::error!("Save dialog channel closed unexpectedly");
                 return;
             };
 
-            let Some(paths) = paths_opt else {
+            let Ok(path_opt) = platform_result else {
+                log::error!("Save dialog failed");
                 return;
             };
 
-            let Some(dir_path) = paths.first() else {
+            let Some(file_path) = path_opt else {
                 return;
             };
 
-            let file_path = dir_path.join(&default_name);
             let file_path_for_worker = file_path.clone();
             let (write_tx, write_rx) = tokio::sync::oneshot::channel();
             crate::utils::shared_tokio_runtime().spawn_blocking(move || {
@@ -773,11 +1924,19 @@ impl ResponseView {
 
             log::info!("Response saved to: {}", file_path.display());
             let _ = cx.update(|window, app| {
+                let name = file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
                 window.push_notification(
-                    (NotificationType::Success, "Response saved successfully"),
+                    (
+                        NotificationType::Success,
+                        SharedString::from(format!("Saved {name}")),
+                    ),
                     app,
                 );
-                this.update(app, |_view, cx| {
+                this.update(app, |view, cx| {
+                    view.last_saved_path = Some(file_path);
                     cx.notify();
                 });
             });
@@ -786,12 +1945,226 @@ impl ResponseView {
     }
 }
 
+enum SaveContent {
+    Text(String),
+    SharedText(Arc<str>),
+    Bytes(Bytes),
+}
+
+/// What [`ResponseView::copy_response`] puts on the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CopyFormat {
+    /// The active tab's content: the body text, or the headers or redirects as JSON
+    Shown,
+    /// The headers the filter shows, or all of them, as `KEY=value` lines for an env file
+    DotEnv,
+}
+
+/// Format and size of an image from its signature and header, without decoding the pixels.
+fn probe_image(bytes: &[u8]) -> (Option<&'static str>, Option<(u32, u32)>) {
+    let Ok(reader) = image::ImageReader::new(std::io::Cursor::new(bytes)).with_guessed_format()
+    else {
+        return (None, None);
+    };
+    let format = reader.format().map(|format| match format {
+        image::ImageFormat::Png => "PNG",
+        image::ImageFormat::Jpeg => "JPEG",
+        image::ImageFormat::Gif => "GIF",
+        image::ImageFormat::WebP => "WebP",
+        image::ImageFormat::Bmp => "BMP",
+        image::ImageFormat::Ico => "ICO",
+        image::ImageFormat::Tiff => "TIFF",
+        image::ImageFormat::Avif => "AVIF",
+        _ => "image",
+    });
+    (format, reader.into_dimensions().ok())
+}
+
+/// `image/png • PNG • 640×480 • 1.2 KB`, leaving out whatever could not be detected.
+fn image_info_line(
+    content_type: Option<&str>,
+    format: Option<&str>,
+    dimensions: Option<(u32, u32)>,
+    size: &str,
+) -> String {
+    let mut parts = vec![content_type.unwrap_or("image").to_string()];
+    parts.extend(format.map(str::to_string));
+    parts.extend(dimensions.map(|(width, height)| format!("{width}×{height}")));
+    parts.push(size.to_string());
+    parts.join(" • ")
+}
+
+/// Scale at which an image `width` pixels wide fits `available`; fitting never enlarges.
+fn image_fit_scale(width: u32, available: Pixels) -> f32 {
+    if width == 0 || available <= px(0.0) {
+        return 1.0;
+    }
+    (available / px(width as f32)).min(1.0)
+}
+
+/// Headers whose name or value contains `query`, ignoring case; all of them when it is blank.
+fn filter_headers(
+    headers: &std::collections::HashMap<String, String>,
+    query: &str,
+) -> Vec<(String, String)> {
+    let query = query.trim().to_lowercase();
+    headers
+        .iter()
+        .filter(|(key, value)| {
+            query.is_empty()
+                || key.to_lowercase().contains(&query)
+                || value.to_lowercase().contains(&query)
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// `3 pages • 1.2 MB` for a PDF, `video/mp4 • 00:12 • 1280×720 • 1.2 MB` for a video,
+/// leaving out whatever could not be read from the file.
+fn body_summary(data: &ResponseData) -> String {
+    let size = data.formatted_size();
+    match data.content_category() {
+        ContentCategory::Pdf => match data.pdf_page_count() {
+            Some(1) => format!("1 page • {size}"),
+            Some(pages) => format!("{pages} pages • {size}"),
+            None => size,
+        },
+        ContentCategory::Video => {
+            let info = probe_mp4(data.body_bytes());
+            let mut parts = vec![data.content_type.as_deref().unwrap_or("video").to_string()];
+            parts.extend(info.duration.map(|duration| {
+                let secs = duration.as_secs();
+                format!("{:02}:{:02}", secs / 60, secs % 60)
+            }));
+            parts.extend(
+                info.dimensions
+                    .map(|(width, height)| format!("{width}×{height}")),
+            );
+            parts.push(size);
+            parts.join(" • ")
+        }
+        _ => size,
+    }
+}
+
+/// File extension for a saved response body, picked from its content type.
+fn default_body_extension(data: &ResponseData) -> &'static str {
+    match data.content_category() {
+        ContentCategory::Json => "json",
+        ContentCategory::Xml => "xml",
+        ContentCategory::Html => "html",
+        ContentCategory::Image => {
+            let ct = data.content_type.as_deref().unwrap_or("");
+            if ct.contains("jpeg") || ct.contains("jpg") {
+                "jpg"
+            } else if ct.contains("gif") {
+                "gif"
+            } else if ct.contains("webp") {
+                "webp"
+            } else if ct.contains("bmp") {
+                "bmp"
+            } else if ct.contains("svg") {
+                "svg"
+            } else {
+                "png"
+            }
+        }
+        ContentCategory::Audio => {
+            let ct = data.content_type.as_deref().unwrap_or("");
+            if ct.contains("mp3") || ct.contains("mpeg") {
+                "mp3"
+            } else if ct.contains("wav") {
+                "wav"
+            } else if ct.contains("ogg") {
+                "ogg"
+            } else if ct.contains("flac") {
+                "flac"
+            } else if ct.contains("aac") {
+                "aac"
+            } else if ct.contains("webm") {
+                "webm"
+            } else {
+                "mp3"
+            }
+        }
+        ContentCategory::Video => {
+            let ct = data.content_type.as_deref().unwrap_or("");
+            if ct.contains("webm") {
+                "webm"
+            } else if ct.contains("quicktime") {
+                "mov"
+            } else if ct.contains("ogg") {
+                "ogv"
+            } else {
+                "mp4"
+            }
+        }
+        ContentCategory::Pdf => "pdf",
+        ContentCategory::Binary => "bin",
+        _ => "txt",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        LARGE_RESPONSE_MAX_EDITOR_LINES, LARGE_RESPONSE_THRESHOLD_BYTES,
-        VIRTUAL_TEXT_ROW_MAX_BYTES, should_virtualize_response_text, virtual_text_rows,
+        CHANGED_LINES_MAX_CELLS, ImageZoom, LARGE_RESPONSE_MAX_EDITOR_LINES,
+        LARGE_RESPONSE_THRESHOLD_BYTES, MAX_SEARCH_MATCHES, SSE_PREVIEW_CHARS,
+        VIRTUAL_TEXT_ROW_MAX_BYTES, changed_lines, filter_headers, find_search_matches,
+        header_markdown_rows, image_fit_scale, image_info_line, json_markdown_rows,
+        line_and_column, markdown_table, probe_image, raw_header_block, search_match_label,
+        should_virtualize_response_text, skips_auto_format, sse_data_preview,
+        virtual_row_for_offset, virtual_text_rows,
     };
+    use crate::entities::ContentCategory;
+    use gpui::px;
+    use std::collections::HashMap;
+
+    #[test]
+    fn marks_lines_added_or_edited_since_the_previous_body() {
+        let previous = "{\n  \"id\": 1,\n  \"name\": \"Ada\",\n  \"admin\": false\n}";
+        let current = "{\n  \"id\": 1,\n  \"name\": \"Ada L.\",\n  \"role\": \"user\",\n  \"admin\": false\n}";
+        let mut changed: Vec<_> = changed_lines(previous, current).into_iter().collect();
+        changed.sort();
+        assert_eq!(changed, vec![3, 4]);
+        assert!(changed_lines(current, current).is_empty());
+        // Only removed lines: nothing left in the new body to mark.
+        assert!(changed_lines(current, "{").is_empty());
+        // Lines kept between edits stay unmarked.
+        let mut changed: Vec<_> = changed_lines("a\nb\nc\nd", "a\nx\nc\ny\nd")
+            .into_iter()
+            .collect();
+        changed.sort();
+        assert_eq!(changed, vec![2, 4]);
+
+        // A changed region too large to compare line by line is marked as a whole.
+        let lines = (CHANGED_LINES_MAX_CELLS as f64).sqrt() as usize + 1;
+        let old: String = (0..lines).map(|line| format!("a{line}\n")).collect();
+        let new: String = (0..lines).map(|line| format!("b{line}\n")).collect();
+        assert_eq!(changed_lines(&old, &new).len(), lines);
+    }
+
+    #[test]
+    fn filters_headers_by_name_or_value_ignoring_case() {
+        let headers = HashMap::from([
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("X-Request-Id".to_string(), "abc123".to_string()),
+            ("Cache-Control".to_string(), "no-cache".to_string()),
+        ]);
+        let keys = |query: &str| {
+            let mut keys: Vec<String> = filter_headers(&headers, query)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys("content-TYPE"), ["Content-Type"]);
+        assert_eq!(keys("ABC"), ["X-Request-Id"]);
+        assert_eq!(keys("cache"), ["Cache-Control"]);
+        assert_eq!(keys("  ").len(), 3);
+        assert!(keys("etag").is_empty());
+    }
 
     fn rendered_rows(content: &str) -> Vec<&str> {
         virtual_text_rows(content)
@@ -800,6 +2173,15 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn sse_previews_are_single_line_and_capped() {
+        assert_eq!(sse_data_preview("{\n  \"a\": 1\n}"), "{   \"a\": 1 }");
+        let long = "x".repeat(SSE_PREVIEW_CHARS + 5);
+        let preview = sse_data_preview(&long);
+        assert_eq!(preview.chars().count(), SSE_PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+    }
+
     #[test]
     fn virtual_rows_preserve_lines_and_mark_continuations() {
         let long_line = "x".repeat(VIRTUAL_TEXT_ROW_MAX_BYTES + 12);
@@ -861,13 +2243,161 @@ mod tests {
         ));
         assert!(!should_virtualize_response_text("small\nresponse"));
     }
-}
 
-impl Focusable for ResponseView {
-    fn focus_handle(&self, _cx: &App) -> FocusHandle {
-        self.focus_handle.clone()
+    #[test]
+    fn formats_headers_as_sorted_markdown_table() {
+        let headers = HashMap::from([
+            ("x-request-id".to_string(), "abc".to_string()),
+            ("content-type".to_string(), "text/plain".to_string()),
+        ]);
+
+        assert_eq!(
+            markdown_table(("Key", "Value"), &header_markdown_rows(&headers)),
+            "| Key | Value |\n| --- | --- |\n| content-type | text/plain |\n| x-request-id | abc |\n"
+        );
     }
-}
+
+    #[test]
+    fn formats_headers_as_a_raw_block() {
+        let headers = HashMap::from([
+            ("x-request-id".to_string(), "abc".to_string()),
+            ("content-type".to_string(), "text/plain".to_string()),
+        ]);
+
+        assert_eq!(
+            raw_header_block(&headers),
+            "content-type: text/plain\nx-request-id: abc\n"
+        );
+    }
+
+    #[test]
+    fn formats_headers_as_dotenv_lines() {
+        let headers = HashMap::from([
+            ("x-request-id".to_string(), "abc".to_string()),
+            (
+                "content-type".to_string(),
+                "text/plain; charset=utf-8".to_string(),
+            ),
+            ("1st-party".to_string(), "a\"b$c".to_string()),
+        ]);
+
+        assert_eq!(
+            dotenv_header_block(&headers),
+            "_1ST_PARTY=\"a\\\"b\\$c\"\nCONTENT_TYPE=\"text/plain; charset=utf-8\"\nX_REQUEST_ID=abc\n"
+        );
+    }
+
+    #[test]
+    fn escapes_pipes_and_newlines_in_markdown_cells() {
+        let rows = vec![("a|b".to_string(), "line\nnext".to_string())];
+
+        assert_eq!(
+            markdown_table(("Field", "Value"), &rows),
+            "| Field | Value |\n| --- | --- |\n| a\\|b | line<br>next |\n"
+        );
+    }
+
+    #[test]
+    fn flattens_only_top_level_json_objects() {
+        let rows = json_markdown_rows(r#"{"name":"setu","count":2,"tags":["a"]}"#).unwrap();
+
+        assert!(rows.contains(&("name".to_string(), "setu".to_string())));
+        assert!(rows.contains(&("count".to_string(), "2".to_string())));
+        assert!(rows.contains(&("tags".to_string(), r#"["a"]"#.to_string())));
+        assert!(json_markdown_rows("[1, 2]").is_none());
+        assert!(json_markdown_rows("not json").is_none());
+    }
+
+    #[test]
+    fn finds_case_insensitive_non_overlapping_matches() {
+        assert_eq!(
+            find_search_matches("Foo foo FOO", "foo"),
+            vec![0..3, 4..7, 8..11]
+        );
+        assert_eq!(find_search_matches("aaaa", "aa"), vec![0..2, 2..4]);
+        assert!(find_search_matches("abc", "").is_empty());
+        assert!(find_search_matches("ab", "abc").is_empty());
+    }
+
+    #[test]
+    fn caps_search_matches_and_labels_the_overflow() {
+        let content = "x".repeat(MAX_SEARCH_MATCHES + 10);
+
+        assert_eq!(find_search_matches(&content, "x").len(), MAX_SEARCH_MATCHES);
+        assert_eq!(search_match_label(2, 27), "3 of 27");
+        assert_eq!(search_match_label(0, 0), "No matches");
+        assert_eq!(
+            search_match_label(0, MAX_SEARCH_MATCHES),
+            format!("1 of {MAX_SEARCH_MATCHES}+")
+        );
+    }
+
+    #[test]
+    fn locates_match_offsets_in_lines_and_virtual_rows() {
+        let content = "alpha\nbéta gamma\nomega";
+        let offset = content.find("gamma").unwrap();
+
+        assert_eq!(line_and_column(content, offset), (1, 5));
+        assert_eq!(line_and_column(content, 0), (0, 0));
+
+        let rows = virtual_text_rows(content);
+        assert_eq!(virtual_row_for_offset(&rows, offset), 1);
+        assert_eq!(virtual_row_for_offset(&rows, content.len()), rows.len() - 1);
+    }
+
+    #[test]
+    fn probes_image_format_and_size_from_the_header() {
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(3, 2)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        let (format, dimensions) = probe_image(png.get_ref());
+        assert_eq!((format, dimensions), (Some("PNG"), Some((3, 2))));
+        assert_eq!(
+            image_info_line(Some("image/jpeg"), format, dimensions, "77 B"),
+            "image/jpeg • PNG • 3×2 • 77 B"
+        );
+
+        assert_eq!(probe_image(b"<svg></svg>"), (None, None));
+        assert_eq!(image_info_line(None, None, None, "11 B"), "image • 11 B");
+    }
+
+    #[test]
+    fn image_zoom_steps_from_the_fit_scale() {
+        assert_eq!(image_fit_scale(2000, px(500.0)), 0.25);
+        assert_eq!(image_fit_scale(200, px(500.0)), 1.0);
+
+        let fit = image_fit_scale(3000, px(1000.0));
+        assert_eq!(ImageZoom::Fit.stepped(fit, true), ImageZoom::Scale(0.5));
+        assert_eq!(ImageZoom::Fit.stepped(fit, false), ImageZoom::Scale(0.25));
+        assert_eq!(
+            ImageZoom::Scale(1.0).stepped(fit, true),
+            ImageZoom::Scale(1.5)
+        );
+        assert_eq!(
+            ImageZoom::Scale(8.0).stepped(fit, true),
+            ImageZoom::Scale(8.0)
+        );
+        assert_eq!(
+            ImageZoom::Scale(0.1).stepped(fit, false),
+            ImageZoom::Scale(0.1)
+        );
+    }
+
+    #[test]
+    fn skips_auto_format_only_for_large_formattable_bodies() {
+        assert!(skips_auto_format(ContentCategory::Json, 2_048, 1_024));
+        assert!(!skips_auto_format(ContentCategory::Json, 1_024, 1_024));
+        assert!(!skips_auto_format(ContentCategory::Text, 2_048, 1_024));
+        assert!(!skips_auto_format(ContentCategory::Xml, usize::MAX, 0));
+    }
+}
+
+impl Focusable for ResponseView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
 
 impl Render for ResponseView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
@@ -877,9 +2407,11 @@ impl Render for ResponseView {
             match self.active_tab {
                 ResponseTab::Body => self.ensure_body_display(window, cx),
                 ResponseTab::Raw => self.ensure_raw_display(window, cx),
-                ResponseTab::Headers => {}
+                ResponseTab::Headers => self.ensure_header_filter(window, cx),
+                ResponseTab::Redirects => {}
             }
         }
+        self.refresh_search_matches(cx);
 
         let theme = cx.theme();
         let response = self.response.read(cx);
@@ -890,7 +2422,7 @@ impl Render for ResponseView {
             .flex_col()
             .w_full()
             .h_full()
-            .child(self.render_content(&theme, &state, response.data.as_ref(), cx))
+            .child(self.render_content(&theme, &state, response, cx))
     }
 }
 
@@ -899,14 +2431,28 @@ impl ResponseView {
         &self,
         theme: &gpui_component::theme::ThemeColor,
         state: &ResponseState,
-        data: Option<&ResponseData>,
+        response: &ResponseEntity,
         cx: &Context<Self>,
     ) -> AnyElement {
+        let data = response.data.as_ref();
         match state {
             ResponseState::Idle => self.render_empty(theme).into_any_element(),
-            ResponseState::Loading => self.render_loading(theme).into_any_element(),
+            ResponseState::Loading => self.render_loading(theme, response).into_any_element(),
+            ResponseState::Streaming => match data {
+                Some(data) => self
+                    .render_streaming(
+                        theme,
+                        data,
+                        response.sse_events().clone(),
+                        response.stream_truncated(),
+                    )
+                    .into_any_element(),
+                None => self.render_loading(theme, response).into_any_element(),
+            },
             ResponseState::Cancelled => self.render_cancelled(theme).into_any_element(),
-            ResponseState::Error(msg) => self.render_error(theme, msg).into_any_element(),
+            ResponseState::Error(msg) => self
+                .render_error(theme, msg, response.error_details(), cx)
+                .into_any_element(),
             ResponseState::Success => {
                 if let Some(data) = data {
                     self.render_success(theme, data, cx).into_any_element()
@@ -952,7 +2498,12 @@ impl ResponseView {
             )
     }
 
-    fn render_loading(&self, theme: &gpui_component::theme::ThemeColor) -> impl IntoElement {
+    fn render_loading(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        response: &ResponseEntity,
+    ) -> impl IntoElement {
+        let retry = response.retry_status();
         div()
             .flex_1()
             .flex()
@@ -965,15 +2516,253 @@ impl ResponseView {
                 div()
                     .text_color(theme.muted_foreground)
                     .text_size(px(12.0))
-                    .child("Sending request..."),
+                    .child(match retry {
+                        Some(retry) => {
+                            format!("Attempt {}/{}...", retry.attempt, retry.max_attempts)
+                        }
+                        None => "Sending request...".to_string(),
+                    }),
+            )
+            .when_some(retry, |this, retry| {
+                this.child(
+                    div()
+                        .text_color(theme.muted_foreground)
+                        .text_size(px(11.0))
+                        .child(format!("Previous attempt: {}", retry.reason)),
+                )
+            })
+    }
+
+    fn render_streaming(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        data: &ResponseData,
+        events: Arc<Vec<SseEvent>>,
+        truncated: bool,
+    ) -> impl IntoElement {
+        let count = events.len();
+
+        let header = div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .justify_between()
+            .px(px(16.0))
+            .h(px(40.0))
+            .border_b_1()
+            .border_color(theme.border)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(12.0))
+                    .child(StatusBadge::new(data.status_code))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(px(6.0))
+                            .child(
+                                div()
+                                    .size(px(8.0))
+                                    .rounded_full()
+                                    .bg(theme.success)
+                                    .with_animation(
+                                        "sse-streaming-pulse",
+                                        Animation::new(Duration::from_millis(1200))
+                                            .repeat()
+                                            .with_easing(pulsating_between(0.3, 1.0)),
+                                        |dot, delta| dot.opacity(delta),
+                                    ),
+                            )
+                            .child(
+                                div()
+                                    .text_color(theme.foreground)
+                                    .text_size(px(11.0))
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .child("Streaming"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(11.0))
+                            .child(if count == 1 {
+                                "1 event".to_string()
+                            } else {
+                                format!("{} events", count)
+                            }),
+                    )
+                    .when(truncated, |row| {
+                        row.child(div().text_color(theme.warning).text_size(px(11.0)).child(
+                            format!(
+                                "Body truncated at {}",
+                                format_size(MAX_STREAM_BODY_BYTES as u64)
+                            ),
+                        ))
+                    }),
             )
+            .child(
+                Button::new("sse-stop-stream")
+                    .icon(IconName::Square)
+                    .label("Stop")
+                    .ghost()
+                    .xsmall()
+                    .tooltip("Close the stream and keep received events")
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(CancelRequest), cx);
+                    }),
+            );
+
+        let body = if count == 0 {
+            div()
+                .flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .text_color(theme.muted_foreground)
+                .text_size(px(12.0))
+                .child("Waiting for events...")
+                .into_any_element()
+        } else {
+            let mono_font = theme.mono_font_family.clone();
+            let border_color = theme.border.opacity(0.3);
+            let muted_color = theme.muted_foreground;
+            let text_color = theme.foreground;
+            let badge_bg = theme.secondary;
+            let badge_color = theme.primary;
+
+            div()
+                .relative()
+                .flex()
+                .flex_col()
+                .flex_1()
+                .overflow_hidden()
+                .child(
+                    uniform_list(
+                        "sse-event-list",
+                        count,
+                        move |visible_range, _window, _cx| {
+                            visible_range
+                                .map(|index| {
+                                    let event = &events[index];
+                                    let copy_text = event.data.clone();
+                                    let preview = sse_data_preview(&event.data);
+
+                                    div()
+                                        .id(ElementId::from(SharedString::from(format!(
+                                            "sse-event-{index}"
+                                        ))))
+                                        .group("sse-event-row")
+                                        .flex()
+                                        .flex_row()
+                                        .items_center()
+                                        .gap(px(8.0))
+                                        .w_full()
+                                        .h(SSE_ROW_HEIGHT)
+                                        .px(px(16.0))
+                                        .border_b_1()
+                                        .border_color(border_color)
+                                        .child(
+                                            div()
+                                                .min_w(px(84.0))
+                                                .font_family(mono_font.clone())
+                                                .text_size(px(11.0))
+                                                .text_color(muted_color)
+                                                .child(
+                                                    event
+                                                        .received_at
+                                                        .format("%H:%M:%S%.3f")
+                                                        .to_string(),
+                                                ),
+                                        )
+                                        .child(
+                                            div()
+                                                .px(px(4.0))
+                                                .rounded(px(3.0))
+                                                .bg(badge_bg)
+                                                .text_size(px(10.0))
+                                                .font_weight(gpui::FontWeight::SEMIBOLD)
+                                                .text_color(badge_color)
+                                                .child(event.event.clone()),
+                                        )
+                                        .children(event.id.as_ref().map(|id| {
+                                            div()
+                                                .text_size(px(11.0))
+                                                .text_color(muted_color)
+                                                .child(format!("#{}", id))
+                                        }))
+                                        .child(
+                                            div()
+                                                .flex_1()
+                                                .overflow_hidden()
+                                                .whitespace_nowrap()
+                                                .text_ellipsis()
+                                                .font_family(mono_font.clone())
+                                                .text_size(px(12.0))
+                                                .text_color(text_color)
+                                                .child(preview),
+                                        )
+                                        .child(
+                                            div()
+                                                .id(SharedString::from(format!(
+                                                    "sse-copy-wrapper-{index}"
+                                                )))
+                                                .invisible()
+                                                .group_hover("sse-event-row", |s| s.visible())
+                                                .child(
+                                                    Button::new(SharedString::from(format!(
+                                                        "sse-copy-{index}"
+                                                    )))
+                                                    .icon(Icon::new(IconName::Copy).size(px(14.0)))
+                                                    .ghost()
+                                                    .xsmall()
+                                                    .tooltip("Copy data")
+                                                    .on_click(move |_event, window, cx| {
+                                                        cx.write_to_clipboard(
+                                                            gpui::ClipboardItem::new_string(
+                                                                copy_text.clone(),
+                                                            ),
+                                                        );
+                                                        window.push_notification(
+                                                            (
+                                                                NotificationType::Success,
+                                                                "Copied to clipboard",
+                                                            ),
+                                                            cx,
+                                                        );
+                                                    }),
+                                                ),
+                                        )
+                                })
+                                .collect()
+                        },
+                    )
+                    .track_scroll(&self.stream_scroll_handle)
+                    .flex_1(),
+                )
+                .into_any_element()
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .child(header)
+            .child(body)
     }
 
     fn render_error(
         &self,
         theme: &gpui_component::theme::ThemeColor,
         message: &str,
+        details: Option<&ErrorDetails>,
+        cx: &Context<Self>,
     ) -> impl IntoElement {
+        let expanded = self.error_details_expanded;
+        let this = cx.entity().clone();
+
         div()
             .flex_1()
             .flex()
@@ -995,6 +2784,73 @@ impl ResponseView {
                     .text_size(px(11.0))
                     .child(message.to_string()),
             )
+            .when_some(details, |el, details| {
+                el.child(
+                    Button::new("toggle-error-details")
+                        .icon(
+                            Icon::new(if expanded {
+                                IconName::ChevronDown
+                            } else {
+                                IconName::ChevronRight
+                            })
+                            .size(px(14.0)),
+                        )
+                        .label("Details")
+                        .ghost()
+                        .small()
+                        .on_click(move |_, _, cx| {
+                            this.update(cx, |view, cx| {
+                                view.error_details_expanded = !view.error_details_expanded;
+                                cx.notify();
+                            });
+                        }),
+                )
+                .when(expanded, |el| {
+                    el.child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap(px(4.0))
+                            .max_w(px(560.0))
+                            .p(px(12.0))
+                            .rounded(px(6.0))
+                            .border_1()
+                            .border_color(theme.border)
+                            .bg(theme.muted)
+                            .text_size(px(11.0))
+                            .children(details.causes.iter().map(|cause| {
+                                div()
+                                    .flex()
+                                    .gap(px(6.0))
+                                    .child(
+                                        div()
+                                            .flex_shrink_0()
+                                            .text_color(theme.muted_foreground)
+                                            .child("Caused by"),
+                                    )
+                                    .child(div().text_color(theme.foreground).child(cause.clone()))
+                            }))
+                            .children(details.context.iter().map(|(label, value)| {
+                                div()
+                                    .flex()
+                                    .gap(px(6.0))
+                                    .child(
+                                        div()
+                                            .flex_shrink_0()
+                                            .w(px(72.0))
+                                            .text_color(theme.muted_foreground)
+                                            .child(*label),
+                                    )
+                                    .child(
+                                        div()
+                                            .font_family(theme.mono_font_family.clone())
+                                            .text_color(theme.foreground)
+                                            .child(value.clone()),
+                                    )
+                            })),
+                    )
+                })
+            })
     }
 
     fn render_success(
@@ -1027,21 +2883,74 @@ impl ResponseView {
                             .flex()
                             .items_center()
                             .gap(px(12.0))
-                            .child(StatusBadge::new(data.status_code))
-                            .child(
-                                div()
+                            .when(data.dry_run, |this| {
+                                this.child(
+                                    div()
+                                        .text_color(theme.warning)
+                                        .font_weight(gpui::FontWeight::BOLD)
+                                        .text_size(px(12.0))
+                                        .child("DRY RUN"),
+                                )
+                                .child(
+                                    div()
+                                        .text_color(theme.muted_foreground)
+                                        .text_size(px(11.0))
+                                        .child("Nothing was sent"),
+                                )
+                            })
+                            .when(!data.dry_run, |this| {
+                                this.child(
+                                    Self::copyable_meta(
+                                        "copy-status-line",
+                                        format!("{} {}", data.status_code, data.status_text),
+                                        "Status line copied",
+                                        theme,
+                                    )
+                                    .child(StatusBadge::new(data.status_code)),
+                                )
+                            })
+                            .when(!data.dry_run, |this| {
+                                this.child(
+                                    Self::copyable_meta(
+                                        "copy-response-time",
+                                        data.formatted_duration(),
+                                        "Response time copied",
+                                        theme,
+                                    )
                                     .text_color(theme.muted_foreground)
                                     .text_size(px(11.0))
                                     .child(data.formatted_duration()),
-                            )
+                                )
+                            })
+                            .when_some(data.decode_warning.clone(), |this, warning| {
+                                this.child(
+                                    div()
+                                        .text_color(theme.warning)
+                                        .text_size(px(11.0))
+                                        .child(warning),
+                                )
+                            })
+                            .when(data.attempts > 1, |this| {
+                                this.child(
+                                    div()
+                                        .text_color(theme.muted_foreground)
+                                        .text_size(px(11.0))
+                                        .child(format!("{} attempts", data.attempts)),
+                                )
+                            })
                             .child(
-                                div()
-                                    .text_color(theme.muted_foreground)
-                                    .text_size(px(11.0))
-                                    .child(data.formatted_size()),
+                                Self::copyable_meta(
+                                    "copy-response-size",
+                                    data.formatted_size_with_wire(),
+                                    "Response size copied",
+                                    theme,
+                                )
+                                .text_color(theme.muted_foreground)
+                                .text_size(px(11.0))
+                                .child(data.formatted_size_with_wire()),
                             ),
                     )
-                    .child(self.render_tabs(theme, this)),
+                    .child(self.render_tabs(theme, this, data.redirects.len())),
             )
             // Content - fills remaining space
             .child(
@@ -1055,10 +2964,32 @@ impl ResponseView {
             )
     }
 
+    /// Meta-row item that copies `value` to the clipboard when clicked.
+    fn copyable_meta(
+        id: &'static str,
+        value: String,
+        toast: &'static str,
+        theme: &gpui_component::theme::ThemeColor,
+    ) -> gpui::Stateful<gpui::Div> {
+        let hover_bg = theme.muted;
+        div()
+            .id(id)
+            .px(px(4.0))
+            .py(px(2.0))
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .hover(move |style| style.bg(hover_bg))
+            .on_click(move |_, window, cx| {
+                cx.write_to_clipboard(gpui::ClipboardItem::new_string(value.clone()));
+                window.push_notification((NotificationType::Success, toast), cx);
+            })
+    }
+
     fn render_tabs(
         &self,
         _theme: &gpui_component::theme::ThemeColor,
         this: Entity<ResponseView>,
+        redirect_count: usize,
     ) -> impl IntoElement {
         use crate::components::{PanelTab, PanelTabBar};
 
@@ -1095,6 +3026,20 @@ impl ResponseView {
                         }
                     }),
             )
+            .when(
+                redirect_count > 0 || self.active_tab == ResponseTab::Redirects,
+                |bar| {
+                    bar.child(
+                        PanelTab::new("Redirects")
+                            .active(self.active_tab == ResponseTab::Redirects)
+                            .on_click(move |_event, _window, cx| {
+                                this.update(cx, |view, cx| {
+                                    view.set_tab(ResponseTab::Redirects, cx)
+                                });
+                            }),
+                    )
+                },
+            )
     }
 
     fn render_tab_content(
@@ -1106,17 +3051,73 @@ impl ResponseView {
         let this = cx.entity().clone();
         let this_save = cx.entity().clone();
         let this_wrap = cx.entity().clone();
+        let this_changes = cx.entity().clone();
         let this_search = cx.entity().clone();
+        let this_markdown = cx.entity().clone();
+        let this_fixture = cx.entity().clone();
+        let this_raw_headers = cx.entity().clone();
+        let this_dotenv = cx.entity().clone();
+        let this_json_mode = cx.entity().clone();
+        let this_unescape = cx.entity().clone();
+        let this_reading = cx.entity().clone();
+        let this_svg_mode = cx.entity().clone();
+        let this_open_folder = cx.entity().clone();
+        let saved_folder_tooltip = self
+            .last_saved_path
+            .as_deref()
+            .and_then(|path| path.parent())
+            .map(|folder| SharedString::from(format!("Open {}", folder.display())));
 
         let tab_label = match self.active_tab {
             ResponseTab::Body => "Response Body",
             ResponseTab::Raw => "Raw Response",
             ResponseTab::Headers => "Headers List",
+            ResponseTab::Redirects => "Redirect Chain",
         };
 
         let wrap_lines = self.wrap_lines;
-        let large_response_mode = self.active_text_is_virtual();
-        let show_editor_tools = self.active_tab != ResponseTab::Headers && !large_response_mode;
+        let show_json_modes = self.active_tab == ResponseTab::Body
+            && data.content_category() == ContentCategory::Json
+            && data.attachment().is_none();
+        let json_body_mode = self.json_body_mode;
+        let show_svg_modes =
+            self.active_tab == ResponseTab::Body && data.is_svg() && data.attachment().is_none();
+        let svg_source = self.svg_source;
+        let keys_sorted = sorts_json_keys(cx);
+        let strings_unescaped = self.unescape_json_strings;
+        let tree_mode = show_json_modes && json_body_mode == JsonBodyMode::Tree;
+        let large_response_mode = self.active_text_is_virtual() && !tree_mode;
+        let changed_line_count = self.active_changed_line_count();
+        let show_reading_toggle = self.active_tab == ResponseTab::Body
+            && matches!(
+                data.content_category(),
+                ContentCategory::Text | ContentCategory::Html
+            )
+            && data.attachment().is_none()
+            && !large_response_mode;
+        let reading_view = show_reading_toggle && self.reading_view;
+        let is_html = data.content_category() == ContentCategory::Html;
+        let text_tab = matches!(self.active_tab, ResponseTab::Body | ResponseTab::Raw);
+        let show_editor_tools = text_tab && !large_response_mode && !tree_mode && !reading_view;
+        let show_changes_toggle = self.active_tab == ResponseTab::Body
+            && self.previous_body.is_some()
+            && data.attachment().is_none()
+            && !tree_mode
+            && !reading_view;
+        let mark_changes = self.mark_changes;
+        let show_search_button = text_tab && !tree_mode && !reading_view;
+        let search_bar = self
+            .search_input
+            .clone()
+            .filter(|_| self.search_open && show_search_button);
+        let search_label = self.search_matches.as_ref().and_then(|matches| {
+            (!matches.query.is_empty())
+                .then(|| search_match_label(matches.current, matches.ranges.len()))
+        });
+        let this_search_prev = cx.entity().clone();
+        let this_search_next = cx.entity().clone();
+        let this_search_close = cx.entity().clone();
+        let show_markdown_copy = self.can_copy_as_markdown_table(data);
 
         div()
             .id("response-tab-content")
@@ -1153,6 +3154,19 @@ impl ResponseView {
                                         .text_size(px(10.0))
                                         .child("Large response mode"),
                                 )
+                            })
+                            .when(changed_line_count > 0, |el| {
+                                el.child(
+                                    div()
+                                        .font_weight(gpui::FontWeight::NORMAL)
+                                        .text_size(px(10.0))
+                                        .text_color(theme.info)
+                                        .child(if changed_line_count == 1 {
+                                            "1 line changed".to_string()
+                                        } else {
+                                            format!("{changed_line_count} lines changed")
+                                        }),
+                                )
                             }),
                     )
                     .child(
@@ -1161,51 +3175,325 @@ impl ResponseView {
                             .flex_row()
                             .items_center()
                             .gap(px(4.0))
-                            .when(show_editor_tools, |el| {
+                            .when_some(search_bar, |el, input| {
+                                el.child(div().w(px(180.0)).child(Input::new(&input).xsmall()))
+                                    .when_some(search_label, |el, label| {
+                                        el.child(
+                                            div()
+                                                .px(px(4.0))
+                                                .text_color(theme.muted_foreground)
+                                                .text_size(px(11.0))
+                                                .whitespace_nowrap()
+                                                .child(label),
+                                        )
+                                    })
+                                    .child(
+                                        Button::new("response-search-prev")
+                                            .icon(Icon::new(IconName::ChevronUp).size(px(14.0)))
+                                            .ghost()
+                                            .xsmall()
+                                            .tooltip("Previous match (Shift+Enter)")
+                                            .on_click(move |_, window, cx| {
+                                                this_search_prev.update(cx, |view, cx| {
+                                                    view.step_search_match(false, window, cx);
+                                                });
+                                            }),
+                                    )
+                                    .child(
+                                        Button::new("response-search-next")
+                                            .icon(Icon::new(IconName::ChevronDown).size(px(14.0)))
+                                            .ghost()
+                                            .xsmall()
+                                            .tooltip("Next match (Enter)")
+                                            .on_click(move |_, window, cx| {
+                                                this_search_next.update(cx, |view, cx| {
+                                                    view.step_search_match(true, window, cx);
+                                                });
+                                            }),
+                                    )
+                                    .child(
+                                        Button::new("response-search-close")
+                                            .icon(Icon::new(IconName::Close).size(px(14.0)))
+                                            .ghost()
+                                            .xsmall()
+                                            .tooltip("Close search")
+                                            .on_click(move |_, _, cx| {
+                                                this_search_close.update(cx, |view, cx| {
+                                                    view.close_search(cx);
+                                                });
+                                            }),
+                                    )
+                            })
+                            .when(show_json_modes, |el| {
                                 el.child(
-                                    Button::new("toggle-wrap-lines")
-                                        .icon(Icon::new(IconName::TextWrap).size(px(14.0)))
+                                    Button::new("json-mode-pretty")
+                                        .label("Pretty")
                                         .ghost()
                                         .xsmall()
-                                        .tooltip(if wrap_lines {
-                                            "Disable word wrap"
-                                        } else {
-                                            "Enable word wrap"
-                                        })
-                                        .when(wrap_lines, |btn| btn.selected(true))
-                                        .on_click(move |_, window, cx| {
-                                            this_wrap.update(cx, |view, cx| {
-                                                view.toggle_wrap_lines(window, cx);
+                                        .selected(json_body_mode == JsonBodyMode::Pretty)
+                                        .tooltip("Show formatted JSON text")
+                                        .on_click({
+                                            let this = this_json_mode.clone();
+                                            move |_, _, cx| {
+                                                this.update(cx, |view, cx| {
+                                                    view.set_json_body_mode(
+                                                        JsonBodyMode::Pretty,
+                                                        cx,
+                                                    );
+                                                });
+                                            }
+                                        }),
+                                )
+                                .child(
+                                    Button::new("json-mode-tree")
+                                        .label("Tree")
+                                        .ghost()
+                                        .xsmall()
+                                        .selected(json_body_mode == JsonBodyMode::Tree)
+                                        .tooltip("Show JSON as a collapsible tree")
+                                        .on_click(move |_, _, cx| {
+                                            this_json_mode.update(cx, |view, cx| {
+                                                view.set_json_body_mode(JsonBodyMode::Tree, cx);
                                             });
                                         }),
                                 )
+                                .when(json_body_mode == JsonBodyMode::Pretty, |el| {
+                                    el.child(
+                                        Button::new("json-sort-keys")
+                                            .label("A–Z")
+                                            .ghost()
+                                            .xsmall()
+                                            .selected(keys_sorted)
+                                            .tooltip(if keys_sorted {
+                                                "Show keys in the order they arrived"
+                                            } else {
+                                                "Sort object keys alphabetically"
+                                            })
+                                            .on_click(move |_, _, cx| {
+                                                cx.set_global(JsonFormatSettings {
+                                                    sort_keys: !keys_sorted,
+                                                    ..*cx.global::<JsonFormatSettings>()
+                                                });
+                                            }),
+                                    )
+                                })
+                                .when(
+                                    json_body_mode == JsonBodyMode::Tree,
+                                    |el| {
+                                        el.child(
+                                            Button::new("json-unescape-strings")
+                                                .label("Unescape")
+                                                .ghost()
+                                                .xsmall()
+                                                .selected(strings_unescaped)
+                                                .tooltip(if strings_unescaped {
+                                                    "Show strings as escaped JSON literals"
+                                                } else {
+                                                    "Show strings with escapes like \\n decoded"
+                                                })
+                                                .on_click(move |_, _, cx| {
+                                                    this_unescape.update(cx, |view, cx| {
+                                                        view.toggle_unescape_json_strings(cx);
+                                                    });
+                                                }),
+                                        )
+                                    },
+                                )
                             })
-                            .when(show_editor_tools, |el| {
+                            .when(show_svg_modes, |el| {
                                 el.child(
-                                    Button::new("find-in-response")
-                                        .icon(Icon::new(IconName::Search).size(px(14.0)))
+                                    Button::new("svg-mode-image")
+                                        .label("Image")
                                         .ghost()
                                         .xsmall()
-                                        .tooltip("Find in response (Cmd+F)")
-                                        .on_click(move |_, window, cx| {
-                                            this_search.update(cx, |view, cx| {
-                                                view.trigger_search(window, cx);
+                                        .selected(!svg_source)
+                                        .tooltip("Show the rendered SVG")
+                                        .on_click({
+                                            let this = this_svg_mode.clone();
+                                            move |_, _, cx| {
+                                                this.update(cx, |view, cx| {
+                                                    view.set_svg_source(false, cx);
+                                                });
+                                            }
+                                        }),
+                                )
+                                .child(
+                                    Button::new("svg-mode-source")
+                                        .label("Source")
+                                        .ghost()
+                                        .xsmall()
+                                        .selected(svg_source)
+                                        .tooltip("Show the SVG markup")
+                                        .on_click(move |_, _, cx| {
+                                            this_svg_mode.update(cx, |view, cx| {
+                                                view.set_svg_source(true, cx);
                                             });
                                         }),
                                 )
                             })
-                            .child(
-                                Button::new("copy-response")
-                                    .icon(Icon::new(IconName::Copy).size(px(14.0)))
-                                    .ghost()
-                                    .xsmall()
-                                    .tooltip("Copy response")
-                                    .on_click(move |_, window, cx| {
-                                        this.update(cx, |view, cx| {
-                                            view.copy_response(window, cx);
-                                        });
-                                    }),
-                            )
+                            .when(show_reading_toggle, |el| {
+                                el.child(
+                                    Button::new("toggle-reading-view")
+                                        .icon(Icon::new(IconName::BookOpen).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .when(is_html, |btn| btn.label("Preview"))
+                                        .tooltip(match (is_html, reading_view) {
+                                            (true, true) => "Show HTML source",
+                                            (true, false) => "Preview the page text",
+                                            (false, true) => "Show in code editor",
+                                            (false, false) => "Show as readable text",
+                                        })
+                                        .when(reading_view, |btn| btn.selected(true))
+                                        .on_click(move |_, _, cx| {
+                                            this_reading.update(cx, |view, cx| {
+                                                view.toggle_reading_view(cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .when(show_changes_toggle, |el| {
+                                el.child(
+                                    Button::new("toggle-mark-changes")
+                                        .icon(Icon::new(IconName::Diff).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip(if mark_changes {
+                                            "Stop marking changes from the previous response"
+                                        } else {
+                                            "Mark lines changed since the previous response"
+                                        })
+                                        .when(mark_changes, |btn| btn.selected(true))
+                                        .on_click(move |_, _, cx| {
+                                            this_changes.update(cx, |view, cx| {
+                                                view.toggle_mark_changes(cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .when(show_editor_tools, |el| {
+                                el.child(
+                                    Button::new("toggle-wrap-lines")
+                                        .icon(Icon::new(IconName::TextWrap).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip(if wrap_lines {
+                                            "Disable word wrap"
+                                        } else {
+                                            "Enable word wrap"
+                                        })
+                                        .when(wrap_lines, |btn| btn.selected(true))
+                                        .on_click(move |_, window, cx| {
+                                            this_wrap.update(cx, |view, cx| {
+                                                view.toggle_wrap_lines(window, cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .when(show_search_button, |el| {
+                                el.child(
+                                    Button::new("find-in-response")
+                                        .icon(Icon::new(IconName::Search).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip("Find in response (Cmd+F)")
+                                        .on_click(move |_, window, cx| {
+                                            this_search.update(cx, |view, cx| {
+                                                view.trigger_search(window, cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .when(show_markdown_copy, |el| {
+                                el.child(
+                                    Button::new("copy-response-markdown")
+                                        .icon(Icon::new(IconName::Table).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip("Copy as Markdown table")
+                                        .on_click(move |_, window, cx| {
+                                            this_markdown.update(cx, |view, cx| {
+                                                view.copy_as_markdown_table(window, cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .child(
+                                Button::new("edit-and-resend")
+                                    .icon(Icon::new(IconName::FilePen).size(px(14.0)))
+                                    .ghost()
+                                    .xsmall()
+                                    .tooltip("Edit and resend the request as it was sent")
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(Box::new(EditAndResend), cx);
+                                    }),
+                            )
+                            .child(
+                                Button::new("copy-response-fixture")
+                                    .icon(Icon::new(IconName::FileCode).size(px(14.0)))
+                                    .ghost()
+                                    .xsmall()
+                                    .tooltip("Copy as test fixture")
+                                    .dropdown_menu(move |mut menu, _window, _cx| {
+                                        for format in FixtureFormat::all() {
+                                            let this = this_fixture.clone();
+                                            menu = menu.item(
+                                                PopupMenuItem::new(format.label()).on_click(
+                                                    move |_, window, cx| {
+                                                        this.update(cx, |view, cx| {
+                                                            view.copy_as_fixture(
+                                                                *format, window, cx,
+                                                            );
+                                                        });
+                                                    },
+                                                ),
+                                            );
+                                        }
+                                        menu
+                                    }),
+                            )
+                            .when(self.active_tab == ResponseTab::Headers, |el| {
+                                el.child(
+                                    Button::new("copy-headers-raw")
+                                        .icon(Icon::new(IconName::FileText).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip("Copy headers as Key: Value lines")
+                                        .on_click(move |_, window, cx| {
+                                            this_raw_headers.update(cx, |view, cx| {
+                                                view.copy_headers_raw(window, cx);
+                                            });
+                                        }),
+                                )
+                                .child(
+                                    Button::new("copy-headers-dotenv")
+                                        .icon(Icon::new(IconName::Variable).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip("Copy the shown headers as .env lines")
+                                        .on_click(move |_, window, cx| {
+                                            this_dotenv.update(cx, |view, cx| {
+                                                view.copy_response(CopyFormat::DotEnv, window, cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .child(
+                                Button::new("copy-response")
+                                    .icon(Icon::new(IconName::Copy).size(px(14.0)))
+                                    .ghost()
+                                    .xsmall()
+                                    .tooltip(if self.active_tab == ResponseTab::Headers {
+                                        "Copy headers as JSON"
+                                    } else {
+                                        "Copy response"
+                                    })
+                                    .on_click(move |_, window, cx| {
+                                        this.update(cx, |view, cx| {
+                                            view.copy_response(CopyFormat::Shown, window, cx);
+                                        });
+                                    }),
+                            )
                             .child(
                                 Button::new("save-response")
                                     .icon(Icon::new(IconName::FileDown).size(px(14.0)))
@@ -1217,13 +3505,28 @@ impl ResponseView {
                                             view.save_to_file(window, cx);
                                         });
                                     }),
-                            ),
+                            )
+                            .when_some(saved_folder_tooltip, |el, tooltip| {
+                                el.child(
+                                    Button::new("open-saved-folder")
+                                        .icon(Icon::new(IconName::FolderOpen).size(px(14.0)))
+                                        .ghost()
+                                        .xsmall()
+                                        .tooltip(tooltip)
+                                        .on_click(move |_, window, cx| {
+                                            this_open_folder.update(cx, |view, cx| {
+                                                view.open_saved_folder(window, cx);
+                                            });
+                                        }),
+                                )
+                            }),
                     ),
             )
             .child(match self.active_tab {
                 ResponseTab::Body => self.render_body_tab(theme, data, cx).into_any_element(),
-                ResponseTab::Raw => self.render_raw_tab(theme, data, cx).into_any_element(),
+                ResponseTab::Raw => self.render_raw_tab(theme, data, cx),
                 ResponseTab::Headers => self.render_headers_tab(theme, data, cx).into_any_element(),
+                ResponseTab::Redirects => self.render_redirects_tab(theme, data).into_any_element(),
             })
             .into_any_element()
     }
@@ -1234,126 +3537,605 @@ impl ResponseView {
         data: &ResponseData,
         cx: &Context<Self>,
     ) -> impl IntoElement {
-        let content_type = data.content_category();
+        if let Some(attachment) = data.attachment() {
+            return self
+                .render_attachment_card(theme, data, attachment, cx)
+                .into_any_element();
+        }
+
+        // A 204 or HEAD reply may still declare a type; say so rather than format nothing.
+        if data.has_empty_body() {
+            return div()
+                .id("body-empty")
+                .flex()
+                .flex_col()
+                .flex_1()
+                .w_full()
+                .h_full()
+                .gap_1()
+                .items_center()
+                .justify_center()
+                .bg(theme.muted)
+                .child(
+                    div()
+                        .text_color(theme.muted_foreground)
+                        .text_size(px(13.0))
+                        .child("Empty body"),
+                )
+                .when_some(data.content_type.as_deref(), |el, content_type| {
+                    el.child(
+                        div()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(12.0))
+                            .child(format!(
+                                "Declared as {content_type}, but the response has no content"
+                            )),
+                    )
+                })
+                .into_any_element();
+        }
+
+        let content_type = self.body_category(data);
 
         if content_type == ContentCategory::Image {
             // Only render image if we have bytes
             if !data.body_bytes().is_empty() {
-                let Some(image) = self
+                let Some(decoded) = self
                     .decoded_image
                     .as_ref()
-                    .filter(|(hash, _)| *hash == data.body_hash())
-                    .map(|(_, image)| image.clone())
+                    .filter(|image| image.body_hash == data.body_hash())
                 else {
                     return div().into_any_element();
                 };
+                return self
+                    .render_image_body(theme, data, decoded, cx)
+                    .into_any_element();
+            } else {
+                // No bytes available, show placeholder
+                return div()
+                    .id("body-image-placeholder")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .w_full()
+                    .h_full()
+                    .items_center()
+                    .justify_center()
+                    .bg(theme.muted)
+                    .child(
+                        div()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(12.0))
+                            .child(format!(
+                                "Image ({}) - {} bytes",
+                                data.content_type.as_deref().unwrap_or("unknown"),
+                                data.body_size_bytes
+                            )),
+                    )
+                    .into_any_element();
+            }
+        }
+
+        if content_type == ContentCategory::Pdf {
+            return self
+                .render_external_body_card(theme, data, IconName::File, "PDF document", cx)
+                .into_any_element();
+        }
 
+        // Video is not decoded in-app, so it gets the same card with its metadata.
+        if content_type == ContentCategory::Video {
+            return self
+                .render_external_body_card(theme, data, IconName::Play, "Video", cx)
+                .into_any_element();
+        }
+
+        if content_type == ContentCategory::Audio {
+            if !data.body_bytes().is_empty()
+                && let Some(ref player) = self.audio_player
+            {
                 return div()
+                    .id("body-audio-container")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(player.clone())
+                    .into_any_element();
+            }
+
+            return div()
+                .id("body-audio-placeholder")
+                .flex()
+                .flex_col()
+                .flex_1()
+                .w_full()
+                .h_full()
+                .items_center()
+                .justify_center()
+                .bg(theme.muted)
+                .child(
+                    div()
+                        .text_color(theme.muted_foreground)
+                        .text_size(px(12.0))
+                        .child(format!(
+                            "Audio ({}) - {} bytes",
+                            data.content_type.as_deref().unwrap_or("unknown"),
+                            data.body_size_bytes
+                        )),
+                )
+                .into_any_element();
+        }
+
+        if content_type == ContentCategory::Json
+            && let Some(tree) = self.json_tree_shown()
+        {
+            let theme = cx.theme();
+            return div()
+                .id("body-json-tree")
+                .flex()
+                .flex_col()
+                .flex_1()
+                .w_full()
+                .h_full()
+                .overflow_hidden()
+                .when(self.unescape_json_strings, |el| {
+                    el.child(
+                        div()
+                            .px(px(8.0))
+                            .py(px(4.0))
+                            .border_b_1()
+                            .border_color(theme.border)
+                            .bg(theme.secondary)
+                            .text_size(px(11.0))
+                            .text_color(theme.muted_foreground)
+                            .child(
+                                "Strings are shown unescaped for reading. Pretty and Raw show the JSON as it arrived.",
+                            ),
+                    )
+                })
+                .child(tree.clone())
+                .into_any_element();
+        }
+
+        let key = ResponseTextKey {
+            body_hash: data.body_hash(),
+            category: content_type,
+            sorted_keys: sorts_json_keys(cx) && content_type == ContentCategory::Json,
+            changes_marked: self.marks_changes(content_type),
+        };
+        if self.reading_view
+            && matches!(content_type, ContentCategory::Text | ContentCategory::Html)
+            && let Some(prepared) = self
+                .body_display
+                .as_ref()
+                .filter(|display| display.key == key)
+                .filter(|display| matches!(display.display, TextDisplay::Editor(_)))
+        {
+            return div()
+                .id("body-reading-view")
+                .flex_1()
+                .w_full()
+                .h_full()
+                .overflow_y_scroll()
+                .bg(theme.muted)
+                .child(
+                    div()
+                        .max_w(px(760.0))
+                        .px(px(24.0))
+                        .py(px(16.0))
+                        .font_family(cx.theme().font_family.clone())
+                        .text_size(px(14.0))
+                        .line_height(px(22.0))
+                        .text_color(theme.foreground)
+                        .child(match content_type {
+                            ContentCategory::Html => self
+                                .html_preview
+                                .as_ref()
+                                .filter(|(hash, _)| *hash == key.body_hash)
+                                .map(|(_, text)| text.to_string())
+                                .unwrap_or_default(),
+                            _ => prepared.content.to_string(),
+                        }),
+                )
+                .into_any_element();
+        }
+
+        let preparing_label = if content_type == ContentCategory::Json {
+            "Formatting response..."
+        } else {
+            "Preparing response..."
+        };
+        let body = self.render_prepared_text(
+            "body",
+            self.json_filter_result().or(self
+                .body_display
+                .as_ref()
+                .filter(|display| display.key == key)),
+            preparing_label,
+            theme,
+            cx,
+        );
+        let Some(filter) = self
+            .json_filter
+            .as_ref()
+            .filter(|_| content_type == ContentCategory::Json)
+        else {
+            return body;
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .h_full()
+            .overflow_hidden()
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(8.0))
+                    .px(px(12.0))
+                    .py(px(4.0))
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .child(div().flex_1().child(Input::new(&filter.input).xsmall()))
+                    .when_some(filter.error.clone(), |el, error| {
+                        el.child(
+                            div()
+                                .text_color(theme.danger)
+                                .text_size(px(11.0))
+                                .whitespace_nowrap()
+                                .child(error),
+                        )
+                    }),
+            )
+            .child(body)
+            .into_any_element()
+    }
+
+    /// An image body with its zoom controls; the image is zoomed and panned in place.
+    fn render_image_body(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        data: &ResponseData,
+        decoded: &DecodedImage,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let zoom = self.image_zoom;
+        let dimensions = decoded.dimensions;
+        let scale = dimensions
+            .map(|(width, _)| zoom.scale(image_fit_scale(width, self.image_viewport_width())));
+        let image = match (zoom, dimensions) {
+            (ImageZoom::Scale(scale), Some((width, height))) => img(decoded.image.clone())
+                .flex_shrink_0()
+                .w(px(width as f32 * scale))
+                .h(px(height as f32 * scale)),
+            _ => img(decoded.image.clone())
+                .max_w_full()
+                .object_fit(gpui::ObjectFit::Contain),
+        };
+        let zoomed = matches!(zoom, ImageZoom::Scale(_)) && dimensions.is_some();
+        let info = image_info_line(
+            data.content_type.as_deref(),
+            decoded.format,
+            dimensions,
+            &data.formatted_size(),
+        );
+        let this_zoom_out = cx.entity().clone();
+        let this_zoom_in = cx.entity().clone();
+        let this_fit = cx.entity().clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .min_h_0()
+            .bg(theme.muted)
+            .child(
+                div()
                     .id("body-image-container")
                     .flex()
                     .flex_col()
-                    .flex_1()
-                    .w_full()
-                    .overflow_y_scroll()
-                    .overflow_x_hidden()
-                    .bg(theme.muted)
+                    .flex_1()
+                    .w_full()
+                    .min_h_0()
+                    .overflow_y_scroll()
+                    .when(zoomed, |el| {
+                        // Unstretched, the image row grows past the pane so both edges scroll.
+                        el.items_start()
+                            .overflow_x_scroll()
+                            .cursor(if self.image_pan.is_some() {
+                                CursorStyle::ClosedHand
+                            } else {
+                                CursorStyle::OpenHand
+                            })
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, event: &MouseDownEvent, _, cx| {
+                                    this.image_pan =
+                                        Some((event.position, this.image_scroll_handle.offset()));
+                                    cx.notify();
+                                }),
+                            )
+                            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _, cx| {
+                                let Some((origin, start)) = this.image_pan else {
+                                    return;
+                                };
+                                if event.pressed_button != Some(MouseButton::Left) {
+                                    this.image_pan = None;
+                                } else {
+                                    // Offsets grow negative as content scrolls; gpui clamps them.
+                                    this.image_scroll_handle
+                                        .set_offset(start + (event.position - origin));
+                                }
+                                cx.notify();
+                            }))
+                            .on_mouse_up(
+                                MouseButton::Left,
+                                cx.listener(|this, _: &MouseUpEvent, _, cx| {
+                                    this.image_pan = None;
+                                    cx.notify();
+                                }),
+                            )
+                    })
+                    .when(!zoomed, |el| el.overflow_x_hidden())
+                    .track_scroll(&self.image_scroll_handle)
+                    .child(
+                        div()
+                            .min_w_full()
+                            .flex()
+                            .flex_col()
+                            .items_center()
+                            .p(IMAGE_PADDING)
+                            .when(!zoomed, |el| el.w_full())
+                            .child(image),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(4.0))
+                    .px(px(12.0))
+                    .py(px(4.0))
+                    .border_t_1()
+                    .border_color(theme.border)
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(11.0))
+                            .child(info),
+                    )
+                    .when_some(scale, |el, scale| {
+                        el.child(
+                            Button::new("image-zoom-out")
+                                .label("−")
+                                .ghost()
+                                .xsmall()
+                                .tooltip("Zoom out")
+                                .disabled(scale <= IMAGE_ZOOM_STEPS[0])
+                                .on_click(move |_, _, cx| {
+                                    this_zoom_out.update(cx, |view, cx| view.zoom_image(false, cx));
+                                }),
+                        )
+                        .child(
+                            div()
+                                .min_w(px(40.0))
+                                .text_center()
+                                .text_color(theme.muted_foreground)
+                                .text_size(px(11.0))
+                                .child(format!("{:.0}%", scale * 100.0)),
+                        )
+                        .child(
+                            Button::new("image-zoom-in")
+                                .label("+")
+                                .ghost()
+                                .xsmall()
+                                .tooltip("Zoom in")
+                                .disabled(scale >= IMAGE_ZOOM_STEPS[IMAGE_ZOOM_STEPS.len() - 1])
+                                .on_click(move |_, _, cx| {
+                                    this_zoom_in.update(cx, |view, cx| view.zoom_image(true, cx));
+                                }),
+                        )
+                        .child(
+                            Button::new("image-fit-toggle")
+                                .label(match zoom {
+                                    ImageZoom::Fit => "1:1",
+                                    ImageZoom::Scale(_) => "Fit",
+                                })
+                                .ghost()
+                                .xsmall()
+                                .tooltip(match zoom {
+                                    ImageZoom::Fit => "Show at actual size",
+                                    ImageZoom::Scale(_) => "Fit to the pane",
+                                })
+                                .on_click(move |_, _, cx| {
+                                    this_fit.update(cx, |view, cx| view.toggle_image_fit(cx));
+                                }),
+                        )
+                    }),
+            )
+    }
+
+    /// Downloads are offered for saving, as a browser would, instead of being rendered.
+    fn render_attachment_card(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        data: &ResponseData,
+        attachment: Attachment,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let this = cx.entity().clone();
+        let filename = attachment
+            .filename
+            .unwrap_or_else(|| format!("response.{}", default_body_extension(data)));
+
+        div()
+            .id("body-attachment")
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .h_full()
+            .items_center()
+            .justify_center()
+            .bg(theme.muted)
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .gap(px(12.0))
+                    .max_w(px(360.0))
+                    .px(px(24.0))
+                    .py(px(20.0))
+                    .rounded(px(8.0))
+                    .border_1()
+                    .border_color(theme.border)
+                    .bg(theme.background)
+                    .child(
+                        Icon::new(IconName::FileDown)
+                            .size(px(32.0))
+                            .text_color(theme.muted_foreground),
+                    )
+                    .child(
+                        div()
+                            .text_color(theme.foreground)
+                            .text_size(px(13.0))
+                            .font_weight(gpui::FontWeight::MEDIUM)
+                            .child("This response is an attachment"),
+                    )
+                    .child(
+                        div()
+                            .max_w_full()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(11.0))
+                            .child(format!("{filename} • {}", data.formatted_size())),
+                    )
+                    .child(
+                        Button::new("save-attachment")
+                            .icon(Icon::new(IconName::FileDown).size(px(14.0)))
+                            .label("Save")
+                            .primary()
+                            .small()
+                            .on_click(move |_, window, cx| {
+                                this.update(cx, |view, cx| {
+                                    view.save_attachment(window, cx);
+                                });
+                            }),
+                    ),
+            )
+    }
+
+    /// A card for bodies setu shows no preview of, with buttons to open or save them.
+    fn render_external_body_card(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        data: &ResponseData,
+        icon: IconName,
+        title: &'static str,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let has_bytes = !data.body_bytes().is_empty();
+        let this_open = cx.entity().clone();
+        let this_save = cx.entity().clone();
+        let details = self
+            .body_summary
+            .as_ref()
+            .filter(|(hash, _)| *hash == data.body_hash())
+            .map_or_else(|| data.formatted_size(), |(_, summary)| summary.clone());
+
+        div()
+            .id("body-external")
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .h_full()
+            .items_center()
+            .justify_center()
+            .bg(theme.muted)
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .gap(px(12.0))
+                    .max_w(px(360.0))
+                    .px(px(24.0))
+                    .py(px(20.0))
+                    .rounded(px(8.0))
+                    .border_1()
+                    .border_color(theme.border)
+                    .bg(theme.background)
+                    .child(
+                        Icon::new(icon)
+                            .size(px(32.0))
+                            .text_color(theme.muted_foreground),
+                    )
                     .child(
                         div()
-                            .w_full()
-                            .flex()
-                            .flex_col()
-                            .items_center()
-                            .p(px(16.0))
-                            .child(img(image).max_w_full().object_fit(gpui::ObjectFit::Contain))
-                            .child(
-                                div()
-                                    .pt(px(8.0))
-                                    .text_color(theme.muted_foreground)
-                                    .text_size(px(11.0))
-                                    .child(format!(
-                                        "{} • {} bytes",
-                                        data.content_type.as_deref().unwrap_or("image"),
-                                        data.body_size_bytes
-                                    )),
-                            ),
+                            .text_color(theme.foreground)
+                            .text_size(px(13.0))
+                            .font_weight(gpui::FontWeight::MEDIUM)
+                            .child(title),
                     )
-                    .into_any_element();
-            } else {
-                // No bytes available, show placeholder
-                return div()
-                    .id("body-image-placeholder")
-                    .flex()
-                    .flex_col()
-                    .flex_1()
-                    .w_full()
-                    .h_full()
-                    .items_center()
-                    .justify_center()
-                    .bg(theme.muted)
                     .child(
                         div()
                             .text_color(theme.muted_foreground)
-                            .text_size(px(12.0))
-                            .child(format!(
-                                "Image ({}) - {} bytes",
-                                data.content_type.as_deref().unwrap_or("unknown"),
-                                data.body_size_bytes
-                            )),
+                            .text_size(px(11.0))
+                            .child(details),
                     )
-                    .into_any_element();
-            }
-        }
-
-        if content_type == ContentCategory::Audio {
-            if !data.body_bytes().is_empty()
-                && let Some(ref player) = self.audio_player
-            {
-                return div()
-                    .id("body-audio-container")
-                    .flex()
-                    .flex_col()
-                    .flex_1()
-                    .w_full()
-                    .overflow_hidden()
-                    .child(player.clone())
-                    .into_any_element();
-            }
-
-            return div()
-                .id("body-audio-placeholder")
-                .flex()
-                .flex_col()
-                .flex_1()
-                .w_full()
-                .h_full()
-                .items_center()
-                .justify_center()
-                .bg(theme.muted)
-                .child(
-                    div()
-                        .text_color(theme.muted_foreground)
-                        .text_size(px(12.0))
-                        .child(format!(
-                            "Audio ({}) - {} bytes",
-                            data.content_type.as_deref().unwrap_or("unknown"),
-                            data.body_size_bytes
-                        )),
-                )
-                .into_any_element();
-        }
-
-        let key = ResponseTextKey {
-            body_hash: data.body_hash(),
-            category: content_type,
-        };
-        self.render_prepared_text(
-            "body",
-            self.body_display
-                .as_ref()
-                .filter(|display| display.key == key),
-            theme,
-            cx,
-        )
+                    .when(!has_bytes, |this| {
+                        this.child(
+                            div()
+                                .text_color(theme.muted_foreground)
+                                .text_size(px(11.0))
+                                .child("The body was not kept in history"),
+                        )
+                    })
+                    .when(has_bytes, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .gap(px(8.0))
+                                .child(
+                                    Button::new("open-body-externally")
+                                        .icon(Icon::new(IconName::FolderOpen).size(px(14.0)))
+                                        .label("Open externally")
+                                        .primary()
+                                        .small()
+                                        .on_click(move |_, window, cx| {
+                                            this_open.update(cx, |view, cx| {
+                                                view.open_body_externally(window, cx);
+                                            });
+                                        }),
+                                )
+                                .child(
+                                    Button::new("save-body")
+                                        .icon(Icon::new(IconName::FileDown).size(px(14.0)))
+                                        .label("Save")
+                                        .ghost()
+                                        .small()
+                                        .on_click(move |_, window, cx| {
+                                            this_save.update(cx, |view, cx| {
+                                                view.save_to_file(window, cx);
+                                            });
+                                        }),
+                                ),
+                        )
+                    }),
+            )
     }
 
     fn render_raw_tab(
@@ -1361,25 +4143,70 @@ impl ResponseView {
         theme: &gpui_component::theme::ThemeColor,
         data: &ResponseData,
         cx: &Context<Self>,
-    ) -> impl IntoElement {
+    ) -> AnyElement {
         let key = ResponseTextKey {
             body_hash: data.body_hash(),
             category: data.content_category(),
+            sorted_keys: false,
+            changes_marked: false,
         };
-        self.render_prepared_text(
+        let raw = self.render_prepared_text(
             "raw",
             self.raw_display
                 .as_ref()
                 .filter(|display| display.key == key),
+            "Preparing response...",
             theme,
             cx,
-        )
+        );
+        if self.raw_for_size != Some(key.body_hash) {
+            return raw;
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .h_full()
+            .overflow_hidden()
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(8.0))
+                    .px(px(12.0))
+                    .py(px(4.0))
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_size(px(11.0))
+                            .text_color(theme.muted_foreground)
+                            .child(format!(
+                                "Shown raw: this {} response is over the auto-format size.",
+                                data.formatted_size()
+                            )),
+                    )
+                    .child(
+                        Button::new("format-anyway")
+                            .label("Format anyway")
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, _, cx| this.format_anyway(cx))),
+                    ),
+            )
+            .child(raw)
+            .into_any_element()
     }
 
     fn render_prepared_text(
         &self,
         id_prefix: &'static str,
         prepared: Option<&PreparedTextDisplay>,
+        preparing_label: &'static str,
         theme: &gpui_component::theme::ThemeColor,
         cx: &Context<Self>,
     ) -> AnyElement {
@@ -1400,7 +4227,7 @@ impl ResponseView {
                 .text_color(theme.muted_foreground)
                 .text_size(px(11.0))
                 .child(Spinner::new().small())
-                .child("Preparing response...")
+                .child(preparing_label)
                 .into_any_element();
         };
 
@@ -1421,6 +4248,8 @@ impl ResponseView {
                 let content = display.content.clone();
                 let rows = display.rows.clone();
                 let scroll_handle = display.scroll_handle.clone();
+                let changed_lines = prepared.changed_lines.clone();
+                let changed_color = theme.info;
                 let line_number_color = theme.muted_foreground;
                 let text_color = theme.foreground;
                 let border_color = theme.border.opacity(0.25);
@@ -1450,6 +4279,7 @@ impl ResponseView {
                                         } else {
                                             row.line_number.to_string()
                                         };
+                                        let changed = changed_lines.contains(&row.line_number);
 
                                         div()
                                             .id(ElementId::from(SharedString::from(format!(
@@ -1465,10 +4295,22 @@ impl ResponseView {
                                             .font_family(mono_font.clone())
                                             .text_size(px(12.0))
                                             .whitespace_nowrap()
+                                            .when(changed, |row| {
+                                                row.bg(changed_color.opacity(0.06))
+                                            })
+                                            .child(
+                                                div()
+                                                    .h_full()
+                                                    .w(px(2.0))
+                                                    .min_w(px(2.0))
+                                                    .when(changed, |marker| {
+                                                        marker.bg(changed_color.opacity(0.8))
+                                                    }),
+                                            )
                                             .child(
                                                 div()
-                                                    .w(px(56.0))
-                                                    .min_w(px(56.0))
+                                                    .w(px(54.0))
+                                                    .min_w(px(54.0))
                                                     .pr(px(10.0))
                                                     .text_align(gpui::TextAlign::Right)
                                                     .text_color(line_number_color)
@@ -1510,21 +4352,82 @@ impl ResponseView {
         }
     }
 
+    fn render_redirects_tab(
+        &self,
+        theme: &gpui_component::theme::ThemeColor,
+        data: &ResponseData,
+    ) -> impl IntoElement {
+        if data.redirects.is_empty() {
+            return div()
+                .flex()
+                .flex_col()
+                .items_center()
+                .justify_center()
+                .flex_1()
+                .w_full()
+                .text_color(theme.muted_foreground)
+                .text_size(px(12.0))
+                .child("No redirects were followed")
+                .into_any_element();
+        }
+
+        div()
+            .id("response-redirects")
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .overflow_y_scroll()
+            .bg(theme.muted)
+            .children(
+                data.redirects
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (status, location))| {
+                        div()
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(12.0))
+                            .w_full()
+                            .h(px(40.0))
+                            .px(px(16.0))
+                            .border_b_1()
+                            .border_color(theme.border.opacity(0.3))
+                            .child(
+                                div()
+                                    .w(px(20.0))
+                                    .text_color(theme.muted_foreground)
+                                    .text_size(px(11.0))
+                                    .child(format!("{}", index + 1)),
+                            )
+                            .child(StatusBadge::new(*status))
+                            .child(
+                                Icon::new(IconName::ChevronRight)
+                                    .size(px(14.0))
+                                    .text_color(theme.muted_foreground),
+                            )
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .overflow_hidden()
+                                    .text_ellipsis()
+                                    .text_color(theme.foreground)
+                                    .text_size(px(12.0))
+                                    .child(location.clone()),
+                            )
+                    }),
+            )
+            .into_any_element()
+    }
+
     fn render_headers_tab(
         &self,
         theme: &gpui_component::theme::ThemeColor,
         data: &ResponseData,
         cx: &Context<Self>,
     ) -> impl IntoElement {
-        // Convert HashMap to Vec for indexing
-        let headers: Vec<(String, String)> = data
-            .headers
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
-        let header_count = headers.len();
-
-        if header_count == 0 {
+        if data.headers.is_empty() {
             return div()
                 .flex()
                 .flex_col()
@@ -1538,6 +4441,196 @@ impl ResponseView {
                 .into_any_element();
         }
 
+        let query = self
+            .header_filter
+            .as_ref()
+            .map(|input| input.read(cx).text().to_string())
+            .unwrap_or_default();
+        let headers = filter_headers(&data.headers, &query);
+        let header_count = headers.len();
+        let checks = data.security_header_checks();
+        let passed = checks
+            .iter()
+            .filter(|check| check.status == SecurityHeaderStatus::Good)
+            .count();
+        let security_panel = self.security_checks_open.then(|| {
+            div()
+                .flex()
+                .flex_col()
+                .gap(px(4.0))
+                .px(px(16.0))
+                .py(px(8.0))
+                .border_b_1()
+                .border_color(theme.border)
+                .bg(theme.secondary)
+                .children(checks.iter().map(|check| {
+                    let (icon, color, label) = match check.status {
+                        SecurityHeaderStatus::Good => {
+                            (IconName::CircleCheck, theme.success, "Present")
+                        }
+                        SecurityHeaderStatus::Weak => {
+                            (IconName::TriangleAlert, theme.warning, "Weak")
+                        }
+                        SecurityHeaderStatus::Missing => {
+                            (IconName::CircleX, theme.danger, "Missing")
+                        }
+                    };
+                    div()
+                        .flex()
+                        .flex_row()
+                        .items_center()
+                        .gap(px(8.0))
+                        .text_size(px(12.0))
+                        .child(Icon::new(icon).size(px(14.0)).text_color(color))
+                        .child(
+                            div()
+                                .w(px(180.0))
+                                .min_w(px(180.0))
+                                .text_color(theme.secondary_foreground)
+                                .font_weight(gpui::FontWeight::MEDIUM)
+                                .child(check.header),
+                        )
+                        .child(div().w(px(56.0)).text_color(color).child(label))
+                        .child(
+                            div()
+                                .flex_1()
+                                .text_color(theme.muted_foreground)
+                                .overflow_hidden()
+                                .text_ellipsis()
+                                .child(check.note),
+                        )
+                }))
+        });
+        // Interim responses are shown whatever the filter, as a section of their own, along
+        // with whether an `Expect: 100-continue` request got its 100.
+        let has_interim = !data.informational.is_empty() || data.expect_continue.is_some();
+        let informational_panel = has_interim.then(|| {
+            div()
+                .id("informational-responses")
+                .flex()
+                .flex_col()
+                .gap(px(6.0))
+                .px(px(16.0))
+                .py(px(8.0))
+                .max_h(px(180.0))
+                .overflow_y_scroll()
+                .border_b_1()
+                .border_color(theme.border)
+                .bg(theme.secondary)
+                .children(data.expect_continue.map(|outcome| {
+                    let color = match outcome {
+                        ExpectContinue::Continued => theme.success,
+                        ExpectContinue::NotReceived => theme.warning,
+                    };
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(color)
+                        .child(outcome.description())
+                }))
+                .children(data.informational.iter().map(|interim| {
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap(px(2.0))
+                        .text_size(px(12.0))
+                        .child(
+                            div()
+                                .text_color(theme.info)
+                                .font_weight(gpui::FontWeight::SEMIBOLD)
+                                .child(format!("{} {}", interim.status_code, interim.status_text)),
+                        )
+                        .when(interim.headers.is_empty(), |el| {
+                            el.child(div().text_color(theme.muted_foreground).child("No headers"))
+                        })
+                        .children(interim.headers.iter().map(|(key, value)| {
+                            div()
+                                .flex()
+                                .flex_row()
+                                .child(
+                                    div()
+                                        .w(px(180.0))
+                                        .min_w(px(180.0))
+                                        .pr(px(12.0))
+                                        .text_color(theme.secondary_foreground)
+                                        .font_weight(gpui::FontWeight::MEDIUM)
+                                        .overflow_hidden()
+                                        .text_ellipsis()
+                                        .child(key.clone()),
+                                )
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .text_color(theme.foreground)
+                                        .overflow_hidden()
+                                        .text_ellipsis()
+                                        .child(value.clone()),
+                                )
+                        }))
+                }))
+        });
+        let this = cx.entity().clone();
+        let filter_bar = div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap(px(8.0))
+            .px(px(12.0))
+            .py(px(4.0))
+            .border_b_1()
+            .border_color(theme.border)
+            .bg(theme.muted)
+            .when_some(self.header_filter.as_ref(), |el, input| {
+                el.child(div().flex_1().child(Input::new(input).xsmall()))
+            })
+            .when(!query.trim().is_empty(), |el| {
+                el.child(
+                    div()
+                        .text_color(theme.muted_foreground)
+                        .text_size(px(11.0))
+                        .whitespace_nowrap()
+                        .child(format!("{header_count} of {}", data.headers.len())),
+                )
+            })
+            .child(
+                Button::new("toggle-security-checks")
+                    .icon(Icon::new(IconName::Lock).size(px(14.0)))
+                    .label(format!("{passed}/{}", checks.len()))
+                    .ghost()
+                    .xsmall()
+                    .tooltip(if self.security_checks_open {
+                        "Hide the security header checklist"
+                    } else {
+                        "Check for common security headers"
+                    })
+                    .when(self.security_checks_open, |btn| btn.selected(true))
+                    .on_click(move |_, _, cx| {
+                        this.update(cx, |view, cx| view.toggle_security_checks(cx));
+                    }),
+            );
+
+        if header_count == 0 {
+            return div()
+                .flex()
+                .flex_col()
+                .flex_1()
+                .w_full()
+                .child(filter_bar)
+                .children(security_panel)
+                .children(informational_panel)
+                .child(
+                    div()
+                        .flex()
+                        .flex_1()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.muted)
+                        .text_color(theme.muted_foreground)
+                        .text_size(px(12.0))
+                        .child("No matching headers"),
+                )
+                .into_any_element();
+        }
+
         // Fixed row height for consistent virtual list
         let row_height = px(40.0);
         let item_sizes: Rc<Vec<Size<Pixels>>> = Rc::new(
@@ -1552,7 +4645,7 @@ impl ResponseView {
         let key_color = theme.secondary_foreground;
         let value_color = theme.foreground;
 
-        div()
+        let list = div()
             .id("headers-virtual-container")
             .relative()
             .flex()
@@ -1678,7 +4771,18 @@ impl ResponseView {
                     .bottom_0()
                     .w(px(8.0))
                     .child(Scrollbar::vertical(&self.headers_scroll_handle)),
-            )
+            );
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .w_full()
+            .overflow_hidden()
+            .child(filter_bar)
+            .children(security_panel)
+            .children(informational_panel)
+            .child(list)
             .into_any_element()
     }
 }
@@ -0,0 +1,632 @@
+use std::rc::Rc;
+
+use bytes::Bytes;
+use gpui::prelude::*;
+use gpui::{
+    App, Context, ElementId, Entity, FocusHandle, Focusable, IntoElement, Render, SharedString,
+    Styled, UniformListScrollHandle, Window, div, px, uniform_list,
+};
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputEvent, InputState};
+use gpui_component::notification::NotificationType;
+use gpui_component::scroll::Scrollbar;
+use gpui_component::{ActiveTheme, Icon, Selectable, Sizable, WindowExt};
+
+use crate::completion::CompletionInput;
+use crate::components::HeaderEditor;
+use crate::entities::{
+    RequestEntity, WebSocketEntity, WebSocketEvent, WsConnectionState, WsDirection, WsMessage,
+    WsPayload,
+};
+use crate::http::parse_subprotocols;
+use crate::icons::IconName;
+use crate::utils::format_size;
+
+const LOG_ROW_HEIGHT: f32 = 32.0;
+const LOG_PREVIEW_CHARS: usize = 240;
+
+type ToggleConnectionCallback = Rc<dyn Fn(&mut Window, &mut App) + 'static>;
+
+/// Request panel for WebSocket tabs: URL, connect control and message composer.
+pub struct WebSocketView {
+    socket: Entity<WebSocketEntity>,
+    url_input: Entity<InputState>,
+    message_input: Entity<InputState>,
+    /// Headers sent on the upgrade request: the request view's own editor, so both show
+    /// the same rows.
+    header_editor: Entity<HeaderEditor>,
+    subprotocols_input: Entity<InputState>,
+    /// Whether the headers and subprotocols of the handshake are shown
+    handshake_open: bool,
+    send_binary: bool,
+    on_toggle_connection: Option<ToggleConnectionCallback>,
+    focus_handle: FocusHandle,
+}
+
+impl WebSocketView {
+    pub fn new(
+        socket: Entity<WebSocketEntity>,
+        url_input: Entity<InputState>,
+        request: Entity<RequestEntity>,
+        header_editor: Entity<HeaderEditor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.observe(&socket, |_, _, cx| cx.notify()).detach();
+        cx.observe(&header_editor, |_, _, cx| cx.notify()).detach();
+        let subprotocols = request.read(cx).subprotocols().to_string();
+        let subprotocols_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Subprotocols, e.g. graphql-transport-ws")
+                .default_value(subprotocols)
+        });
+        cx.subscribe(&subprotocols_input, move |_, input, event, cx| {
+            if matches!(event, InputEvent::Change) {
+                let subprotocols = input.read(cx).text().to_string();
+                request.update(cx, |request, cx| request.set_subprotocols(subprotocols, cx));
+            }
+        })
+        .detach();
+        let message_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .code_editor("text")
+                .soft_wrap(true)
+                .placeholder("Message to send...")
+        });
+
+        Self {
+            socket,
+            url_input,
+            message_input,
+            header_editor,
+            subprotocols_input,
+            handshake_open: false,
+            send_binary: false,
+            on_toggle_connection: None,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// Called when the connect/disconnect button is pressed.
+    pub fn on_toggle_connection(
+        mut self,
+        callback: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_toggle_connection = Some(Rc::new(callback));
+        self
+    }
+
+    /// Subprotocols to offer on the next connect.
+    pub fn subprotocols(&self, cx: &App) -> Vec<String> {
+        parse_subprotocols(&self.subprotocols_input.read(cx).text().to_string())
+    }
+
+    fn send_message(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.message_input.read(cx).text().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let payload = if self.send_binary {
+            WsPayload::Binary(Bytes::from(text.into_bytes()))
+        } else {
+            WsPayload::Text(text)
+        };
+        let sent = self
+            .socket
+            .update(cx, |socket, cx| socket.send(payload, cx));
+        if !sent {
+            window.push_notification(
+                (NotificationType::Warning, "Connect before sending messages"),
+                cx,
+            );
+        }
+    }
+
+    fn status_label(state: &WsConnectionState, protocol: Option<&str>) -> String {
+        match state {
+            WsConnectionState::Idle => "Not connected".to_string(),
+            WsConnectionState::Connecting => "Connecting…".to_string(),
+            WsConnectionState::Connected => match protocol {
+                Some(protocol) => format!("Connected · {}", protocol),
+                None => "Connected".to_string(),
+            },
+            WsConnectionState::Closed(None) => "Disconnected".to_string(),
+            WsConnectionState::Closed(Some(reason)) => format!("Closed · {}", reason),
+            WsConnectionState::Failed(error) => format!("Failed · {}", error),
+        }
+    }
+}
+
+impl Focusable for WebSocketView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for WebSocketView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let state = self.socket.read(cx).state().clone();
+        let protocol = self.socket.read(cx).protocol().map(str::to_string);
+        let handshake_open = self.handshake_open;
+        let header_count = self
+            .header_editor
+            .read(cx)
+            .get_headers(cx)
+            .iter()
+            .filter(|header| header.enabled)
+            .count();
+        let is_active = state.is_active();
+        let is_connected = state == WsConnectionState::Connected;
+        let status_color = match state {
+            WsConnectionState::Connected => theme.success,
+            WsConnectionState::Connecting => theme.warning,
+            WsConnectionState::Failed(_) => theme.danger,
+            WsConnectionState::Idle | WsConnectionState::Closed(_) => theme.muted_foreground,
+        };
+        let on_toggle_connection = self.on_toggle_connection.clone();
+        let this = cx.entity().clone();
+        let send_binary = self.send_binary;
+
+        let connect_button = Button::new("ws-connect")
+            .label(match state {
+                WsConnectionState::Connecting => "Cancel",
+                WsConnectionState::Connected => "Disconnect",
+                _ => "Connect",
+            })
+            .compact()
+            .on_click(move |_, window, cx| {
+                if let Some(callback) = on_toggle_connection.as_ref() {
+                    callback(window, cx);
+                }
+            });
+        let connect_button = if is_active {
+            connect_button.danger()
+        } else {
+            connect_button.primary()
+        };
+
+        div()
+            .id("websocket-view")
+            .track_focus(&self.focus_handle)
+            .flex()
+            .flex_col()
+            .size_full()
+            .child(
+                div()
+                    .px(px(16.0))
+                    .py(px(12.0))
+                    .flex()
+                    .flex_col()
+                    .gap(px(6.0))
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(2.0))
+                            .w_full()
+                            .h(px(40.0))
+                            .bg(theme.muted)
+                            .rounded(px(6.0))
+                            .child(
+                                div()
+                                    .ml(px(12.0))
+                                    .text_size(px(11.0))
+                                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                                    .text_color(theme.muted_foreground)
+                                    .child("WS"),
+                            )
+                            .child(div().w(px(1.0)).h(px(20.0)).ml(px(8.0)).bg(theme.border))
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .flex()
+                                    .items_center()
+                                    .h_full()
+                                    .px(px(8.0))
+                                    .child(CompletionInput::new(
+                                        &self.url_input,
+                                        Input::new(&self.url_input).appearance(false).size_full(),
+                                    )),
+                            )
+                            .child(div().mr(px(4.0)).child(connect_button)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(6.0))
+                            .text_size(px(11.0))
+                            .text_color(theme.muted_foreground)
+                            .child(div().size(px(6.0)).rounded_full().bg(status_color))
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .overflow_hidden()
+                                    .text_ellipsis()
+                                    .child(Self::status_label(&state, protocol.as_deref())),
+                            )
+                            .child(
+                                Button::new("ws-handshake-toggle")
+                                    .label(if header_count > 0 {
+                                        format!("Handshake ({})", header_count)
+                                    } else {
+                                        "Handshake".to_string()
+                                    })
+                                    .icon(if handshake_open {
+                                        IconName::ChevronUp
+                                    } else {
+                                        IconName::ChevronDown
+                                    })
+                                    .ghost()
+                                    .xsmall()
+                                    .tooltip("Headers and subprotocols sent when connecting")
+                                    .on_click({
+                                        let this = this.clone();
+                                        move |_, _, cx| {
+                                            this.update(cx, |view, cx| {
+                                                view.handshake_open = !view.handshake_open;
+                                                cx.notify();
+                                            });
+                                        }
+                                    }),
+                            ),
+                    ),
+            )
+            .when(handshake_open, |el| {
+                el.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap(px(8.0))
+                        .px(px(16.0))
+                        .pb(px(12.0))
+                        .child(
+                            div()
+                                .flex()
+                                .flex_row()
+                                .items_center()
+                                .gap(px(8.0))
+                                .child(
+                                    div()
+                                        .w(px(88.0))
+                                        .text_size(px(11.0))
+                                        .font_weight(gpui::FontWeight::SEMIBOLD)
+                                        .text_color(theme.muted_foreground)
+                                        .child("Subprotocols"),
+                                )
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .child(Input::new(&self.subprotocols_input).small()),
+                                ),
+                        )
+                        .child(
+                            div()
+                                .id("ws-handshake-headers")
+                                .max_h(px(220.0))
+                                .overflow_y_scroll()
+                                .child(self.header_editor.clone()),
+                        ),
+                )
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .justify_between()
+                    .h(px(36.0))
+                    .px(px(16.0))
+                    .bg(theme.secondary)
+                    .border_t_1()
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .child(
+                        div()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(11.0))
+                            .font_weight(gpui::FontWeight::SEMIBOLD)
+                            .child("Message"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(4.0))
+                            .child(
+                                Button::new("ws-frame-text")
+                                    .label("Text")
+                                    .ghost()
+                                    .xsmall()
+                                    .selected(!send_binary)
+                                    .on_click({
+                                        let this = this.clone();
+                                        move |_, _, cx| {
+                                            this.update(cx, |view, cx| {
+                                                view.send_binary = false;
+                                                cx.notify();
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                Button::new("ws-frame-binary")
+                                    .label("Binary")
+                                    .ghost()
+                                    .xsmall()
+                                    .selected(send_binary)
+                                    .tooltip("Send the message bytes as a binary frame")
+                                    .on_click({
+                                        let this = this.clone();
+                                        move |_, _, cx| {
+                                            this.update(cx, |view, cx| {
+                                                view.send_binary = true;
+                                                cx.notify();
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                Button::new("ws-send")
+                                    .icon(IconName::Send)
+                                    .label("Send")
+                                    .primary()
+                                    .xsmall()
+                                    .disabled(!is_connected)
+                                    .on_click(move |_, window, cx| {
+                                        this.update(cx, |view, cx| {
+                                            view.send_message(window, cx);
+                                        });
+                                    }),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .bg(theme.muted)
+                    .child(Input::new(&self.message_input).appearance(false).h_full()),
+            )
+    }
+}
+
+/// Response panel for WebSocket tabs: the scrolling, copyable frame log.
+pub struct WebSocketLogView {
+    socket: Entity<WebSocketEntity>,
+    scroll_handle: UniformListScrollHandle,
+}
+
+impl WebSocketLogView {
+    pub fn new(socket: Entity<WebSocketEntity>, cx: &mut Context<Self>) -> Self {
+        cx.subscribe(&socket, |this, socket, event, cx| {
+            if let WebSocketEvent::MessagesChanged = event {
+                let count = socket.read(cx).messages().len();
+                if count > 0 {
+                    this.scroll_handle
+                        .scroll_to_item(count - 1, gpui::ScrollStrategy::Bottom);
+                }
+            }
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            socket,
+            scroll_handle: UniformListScrollHandle::new(),
+        }
+    }
+}
+
+impl Render for WebSocketLogView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let messages: Rc<Vec<WsMessage>> = Rc::new(self.socket.read(cx).messages().to_vec());
+        let count = messages.len();
+        let socket = self.socket.clone();
+
+        let header = div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .justify_between()
+            .h(px(36.0))
+            .px(px(16.0))
+            .bg(theme.secondary)
+            .border_b_1()
+            .border_color(theme.border)
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(8.0))
+                    .child(
+                        div()
+                            .text_color(theme.muted_foreground)
+                            .text_size(px(11.0))
+                            .font_weight(gpui::FontWeight::SEMIBOLD)
+                            .child("Messages"),
+                    )
+                    .child(
+                        div()
+                            .text_color(theme.muted_foreground.opacity(0.7))
+                            .text_size(px(11.0))
+                            .child(count.to_string()),
+                    ),
+            )
+            .child(
+                Button::new("ws-clear-log")
+                    .icon(IconName::Trash)
+                    .ghost()
+                    .xsmall()
+                    .tooltip("Clear messages")
+                    .disabled(count == 0)
+                    .on_click(move |_, _, cx| {
+                        socket.update(cx, |socket, cx| socket.clear_messages(cx));
+                    }),
+            );
+
+        let body = if count == 0 {
+            div()
+                .flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .text_color(theme.muted_foreground)
+                .text_size(px(12.0))
+                .child("Sent and received messages appear here")
+                .into_any_element()
+        } else {
+            let mono_font = theme.mono_font_family.clone();
+            let border_color = theme.border.opacity(0.3);
+            let sent_color = theme.primary;
+            let received_color = theme.success;
+            let muted_color = theme.muted_foreground;
+            let text_color = theme.foreground;
+            let badge_bg = theme.secondary;
+
+            div()
+                .relative()
+                .flex()
+                .flex_col()
+                .flex_1()
+                .overflow_hidden()
+                .child(
+                    uniform_list(
+                        "ws-message-log",
+                        count,
+                        move |visible_range, _window, _cx| {
+                            visible_range
+                                .map(|index| {
+                                    let message = &messages[index];
+                                    let (icon, color) = match message.direction {
+                                        WsDirection::Sent => (IconName::ArrowUp, sent_color),
+                                        WsDirection::Received => {
+                                            (IconName::ArrowDown, received_color)
+                                        }
+                                    };
+                                    let copy_text = message.payload.copy_text();
+
+                                    div()
+                                        .id(ElementId::from(SharedString::from(format!(
+                                            "ws-message-{index}"
+                                        ))))
+                                        .group("ws-message-row")
+                                        .flex()
+                                        .flex_row()
+                                        .items_center()
+                                        .gap(px(8.0))
+                                        .w_full()
+                                        .h(px(LOG_ROW_HEIGHT))
+                                        .px(px(16.0))
+                                        .border_b_1()
+                                        .border_color(border_color)
+                                        .child(Icon::new(icon).size(px(13.0)).text_color(color))
+                                        .child(
+                                            div()
+                                                .min_w(px(84.0))
+                                                .font_family(mono_font.clone())
+                                                .text_size(px(11.0))
+                                                .text_color(muted_color)
+                                                .child(
+                                                    message
+                                                        .timestamp
+                                                        .format("%H:%M:%S%.3f")
+                                                        .to_string(),
+                                                ),
+                                        )
+                                        .child(
+                                            div()
+                                                .px(px(4.0))
+                                                .rounded(px(3.0))
+                                                .bg(badge_bg)
+                                                .text_size(px(9.0))
+                                                .font_weight(gpui::FontWeight::BOLD)
+                                                .text_color(muted_color)
+                                                .child(if message.payload.is_binary() {
+                                                    "BIN"
+                                                } else {
+                                                    "TEXT"
+                                                }),
+                                        )
+                                        .child(
+                                            div()
+                                                .flex_1()
+                                                .overflow_hidden()
+                                                .whitespace_nowrap()
+                                                .text_ellipsis()
+                                                .font_family(mono_font.clone())
+                                                .text_size(px(12.0))
+                                                .text_color(text_color)
+                                                .child(message.payload.preview(LOG_PREVIEW_CHARS)),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_size(px(11.0))
+                                                .text_color(muted_color)
+                                                .child(format_size(message.payload.len() as u64)),
+                                        )
+                                        .child(
+                                            div()
+                                                .id(SharedString::from(format!(
+                                                    "ws-copy-wrapper-{index}"
+                                                )))
+                                                .invisible()
+                                                .group_hover("ws-message-row", |s| s.visible())
+                                                .child(
+                                                    Button::new(SharedString::from(format!(
+                                                        "ws-copy-{index}"
+                                                    )))
+                                                    .icon(Icon::new(IconName::Copy).size(px(14.0)))
+                                                    .ghost()
+                                                    .xsmall()
+                                                    .tooltip("Copy message")
+                                                    .on_click(move |_event, window, cx| {
+                                                        cx.write_to_clipboard(
+                                                            gpui::ClipboardItem::new_string(
+                                                                copy_text.clone(),
+                                                            ),
+                                                        );
+                                                        window.push_notification(
+                                                            (
+                                                                NotificationType::Success,
+                                                                "Copied to clipboard",
+                                                            ),
+                                                            cx,
+                                                        );
+                                                    }),
+                                                ),
+                                        )
+                                })
+                                .collect()
+                        },
+                    )
+                    .flex_1()
+                    .track_scroll(&self.scroll_handle),
+                )
+                .child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .right_0()
+                        .bottom_0()
+                        .w(px(8.0))
+                        .child(Scrollbar::vertical(&self.scroll_handle)),
+                )
+                .into_any_element()
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.muted)
+            .child(header)
+            .child(body)
+    }
+}